@@ -0,0 +1,53 @@
+// Timing harness for schedule generation. criterion is not a dependency, so each case is
+// timed with `Instant` over a fixed number of runs and the best run is reported.
+// Run with `cargo bench --bench schedule`
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use amortize_rs::{Amortization, CalculatorConfig, Prepayment, PrepaymentTreatment, RatePeriod};
+use chrono::NaiveDate;
+
+const RUNS: u32 = 5;
+
+fn time(name: &str, iterations: u32, mut run: impl FnMut()) {
+    let mut best = Duration::MAX;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        for _ in 0..iterations {
+            run();
+        }
+        best = best.min(start.elapsed());
+    }
+    println!("{:<40} {:>10.2} us/schedule", name, best.as_secs_f64() * 1e6 / iterations as f64);
+}
+
+fn start_date() -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(2025, 1, 15)
+}
+
+fn main() {
+    time("30-year monthly, undated", 10_000, || {
+        black_box(Amortization::new(black_box(350_000.0), 6.5, 360, None).unwrap());
+    });
+    time("30-year monthly, dated", 10_000, || {
+        black_box(Amortization::new(black_box(350_000.0), 6.5, 360, start_date()).unwrap());
+    });
+
+    let adjustable = CalculatorConfig {
+        rate_schedule: (0..30).map(|year| RatePeriod::new(1 + 12 * year, 5.0 + (year % 5) as f64 * 0.25)).collect(),
+        prepayments: (1..=20).map(|year| Prepayment::new(12 * year, 1_000.0, PrepaymentTreatment::ReduceTerm)).collect(),
+        ..CalculatorConfig::new(350_000.0, 5.0, 360, start_date())
+    };
+    time("annual resets and prepayments", 10_000, || {
+        black_box(Amortization::from_config(black_box(adjustable.clone())).unwrap());
+    });
+
+    let configs: Vec<CalculatorConfig> = (0..100_000)
+        .map(|n| CalculatorConfig::new(100_000.0 + n as f64, 4.0 + (n % 400) as f64 / 100.0, 360, None))
+        .collect();
+    time("100k-loan pool (per loan)", 100_000, {
+        let mut loans = configs.iter().cycle();
+        move || {
+            black_box(Amortization::from_config(loans.next().unwrap().clone()).unwrap());
+        }
+    });
+}
@@ -0,0 +1,47 @@
+/* C interface to amortize-rs, built with `cargo rustc --release --features ffi --crate-type cdylib` */
+#ifndef AMORTIZE_H
+#define AMORTIZE_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct AmortizeLoan AmortizeLoan;
+
+/* One installment; the date fields are all zero on an undated schedule */
+typedef struct AmortizePayment {
+    uint32_t installment_number;
+    int32_t year;
+    uint32_t month;
+    uint32_t day;
+    double beginning_balance;
+    double installment_amount;
+    double interest;
+    double principal;
+    double remaining_balance;
+} AmortizePayment;
+
+/* Return NULL when the loan terms or the start date are invalid */
+AmortizeLoan *amortize_new(double principal, double apr, uint32_t term_months);
+AmortizeLoan *amortize_new_dated(double principal, double apr, uint32_t term_months,
+                                 int32_t year, uint32_t month, uint32_t day);
+
+size_t amortize_schedule_len(const AmortizeLoan *loan);
+double amortize_periodic_payment(const AmortizeLoan *loan);
+double amortize_total_interest(const AmortizeLoan *loan);
+
+/* Writes installment `index` (0-based) to `out`; false for NULL arguments or an index past the end */
+bool amortize_payment_at(const AmortizeLoan *loan, size_t index, AmortizePayment *out);
+
+/* Releases a loan; NULL is ignored */
+void amortize_free(AmortizeLoan *loan);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
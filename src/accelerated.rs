@@ -0,0 +1,58 @@
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::error::AmortizationError;
+use crate::fees::Fee;
+use crate::frequency::PaymentFrequency;
+use crate::rate::RatePeriod;
+
+#[derive(Debug, Clone)]
+pub struct BiweeklyAcceleration {
+    pub half_payment: f64,
+    pub accelerated: Amortization,
+    pub baseline_months: f64,
+    pub accelerated_months: f64,
+    pub months_saved: f64,
+    pub interest_saved: f64,
+}
+
+impl Amortization {
+    // Pays half of the monthly installment every two weeks, which adds up to one extra
+    // monthly payment a year
+    pub fn biweekly_accelerated(&self) -> Result<BiweeklyAcceleration, AmortizationError> {
+        if self.config.frequency != PaymentFrequency::Monthly {
+            return Err(AmortizationError::UnsupportedFrequency(self.config.frequency));
+        }
+
+        let biweekly = PaymentFrequency::BiWeekly;
+        let to_biweekly = |period: u32| ((period as u64 - 1) * 26 / 12) as u32 + 1;
+        let half_payment = self.config.currency.round((self.periodic_payment + self.config.extra_payment) / 2.0);
+
+        let mut config = CalculatorConfig {
+            frequency: biweekly,
+            rate_schedule: self.rate_schedule.iter()
+                .map(|r| RatePeriod::new(to_biweekly(r.start_period), r.apr))
+                .collect(),
+            // The loan's rates are already capped
+            rate_caps: None,
+            balloon_period: self.config.balloon_period.map(to_biweekly),
+            fees: self.config.fees.iter()
+                .map(|fee| Fee { period: fee.period.map(to_biweekly), ..fee.clone() })
+                .collect(),
+            ..self.config.clone()
+        };
+        let scheduled = Amortization::from_config(config.clone())?;
+        config.extra_payment = (half_payment - scheduled.periodic_payment).max(0.0);
+        let accelerated = Amortization::from_config(config)?;
+
+        let baseline_months = self.schedule.len() as f64;
+        let accelerated_months = accelerated.schedule.len() as f64 * 12.0 / 26.0;
+
+        Ok(BiweeklyAcceleration {
+            half_payment,
+            baseline_months,
+            accelerated_months,
+            months_saved: baseline_months - accelerated_months,
+            interest_saved: self.total_interest - accelerated.total_interest,
+            accelerated,
+        })
+    }
+}
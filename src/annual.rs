@@ -0,0 +1,47 @@
+use chrono::Datelike;
+use crate::calculator::Amortization;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnualSummary {
+    // Calendar year, or the 1-based loan year when the schedule has no dates
+    pub year: i32,
+    pub payments: u32,
+    pub principal: f64,
+    pub interest: f64,
+    pub total_paid: f64,
+    pub ending_balance: f64,
+}
+
+impl Amortization {
+    // Totals per calendar year; undated schedules are grouped into loan years of
+    // `periods_per_year` installments
+    pub fn annual_summary(&self) -> Vec<AnnualSummary> {
+        let periods_per_year = self.config.frequency.periods_per_year();
+        let mut summaries: Vec<AnnualSummary> = Vec::new();
+
+        for payment in &self.schedule {
+            let year = match payment.date {
+                Some(date) => date.year(),
+                None => ((payment.installment_number - 1) / periods_per_year + 1) as i32,
+            };
+            match summaries.last_mut() {
+                Some(summary) if summary.year == year => {
+                    summary.payments += 1;
+                    summary.principal += payment.principal;
+                    summary.interest += payment.interest;
+                    summary.total_paid += payment.installment_amount;
+                    summary.ending_balance = payment.remaining_balance;
+                }
+                _ => summaries.push(AnnualSummary {
+                    year,
+                    payments: 1,
+                    principal: payment.principal,
+                    interest: payment.interest,
+                    total_paid: payment.installment_amount,
+                    ending_balance: payment.remaining_balance,
+                }),
+            }
+        }
+        summaries
+    }
+}
@@ -0,0 +1,58 @@
+// Annual percentage rate of charge under the EU Consumer Credit Directive (Annex I): the
+// annual rate X that equates the drawdowns with the repayments and charges,
+// sum(C_k (1 + X)^-t_k) = sum(D_l (1 + X)^-s_l), with times measured in years from the
+// first drawdown. Unlike a US APR it is an effective annual rate.
+use chrono::{Months, NaiveDate};
+use crate::calculator::Amortization;
+use crate::cashflow;
+use crate::effective_apr::UpfrontCosts;
+use crate::error::AmortizationError;
+
+// Whole months counted back from `date`, then the days left over as a fraction of a 365 day
+// (or 366 in a leap year) year
+fn years_between(from: NaiveDate, date: NaiveDate) -> f64 {
+    let mut months = 0;
+    while date.checked_sub_months(Months::new(months + 1)).is_some_and(|earlier| earlier >= from) {
+        months += 1;
+    }
+    let month_boundary = date.checked_sub_months(Months::new(months)).unwrap_or(date);
+    let days = (month_boundary - from).num_days() as f64;
+    let days_in_year = if month_boundary.leap_year() { 366.0 } else { 365.0 };
+    months as f64 / 12.0 + days / days_in_year
+}
+
+// Drawdowns are amounts made available to the borrower, payments everything the borrower
+// pays: installments and every mandatory charge. Returned in percent
+pub fn aprc(drawdowns: &[(NaiveDate, f64)], payments: &[(NaiveDate, f64)]) -> Result<f64, AmortizationError> {
+    let start = drawdowns.iter().map(|&(date, _)| date).min()
+        .ok_or(AmortizationError::MissingParameter("drawdowns"))?;
+    let flows: Vec<(f64, f64)> = drawdowns.iter()
+        .map(|&(date, amount)| (years_between(start, date), amount))
+        .chain(payments.iter().map(|&(date, amount)| (years_between(start, date), -amount)))
+        .collect();
+    cashflow::check_sign_change(flows.iter().map(|&(_, flow)| flow))?;
+    let rate = cashflow::solve_rate(|rate| flows.iter().map(|&(years, flow)| flow / (1.0 + rate).powf(years)).sum())?;
+    Ok(rate * 100.0)
+}
+
+impl Amortization {
+    // APRC with the upfront costs and any interim interest collected at closing deducted
+    // from the amount drawn down at funding. Mortgage
+    // insurance is a condition of the credit, so its premiums count as charges; property
+    // taxes and hazard insurance are owed anyway and are left out.
+    pub fn aprc(&self, costs: &UpfrontCosts) -> Result<f64, AmortizationError> {
+        let drawdown_date = match self.config.funding_date {
+            Some(funding_date) => funding_date,
+            None => self.due_date(0).ok_or(AmortizationError::MissingParameter("start_date"))?,
+        };
+        let drawdown = self.balance - costs.total(self.balance) - self.closing_interest();
+        let payments = self.schedule.iter()
+            .map(|p| {
+                let date = p.date.ok_or(AmortizationError::MissingParameter("start_date"))?;
+                let mortgage_insurance = p.escrow.map_or(0.0, |escrow| escrow.mortgage_insurance);
+                Ok((date, p.installment_amount + mortgage_insurance))
+            })
+            .collect::<Result<Vec<_>, AmortizationError>>()?;
+        aprc(&[(drawdown_date, drawdown)], &payments)
+    }
+}
@@ -0,0 +1,217 @@
+// Vehicle financing: the amount financed built up from the deal sheet, and the car's value
+// against the loan balance to show how long the buyer owes more than the car is worth
+use chrono::NaiveDate;
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::error::AmortizationError;
+use crate::money::Currency;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoPurchase {
+    pub price: f64,
+    // Percent of the taxable price
+    pub sales_tax_rate: f64,
+    // Dealer, title and registration fees, financed but not taxed
+    pub fees: f64,
+    pub trade_in_value: f64,
+    // Still owed on the trade-in; anything above its value is rolled into the new loan
+    pub trade_in_payoff: f64,
+    // Most states tax only the price less the trade-in
+    pub trade_in_tax_credit: bool,
+    pub cash_down: f64,
+    pub apr: f64,
+    pub term_months: u32,
+    pub start_date: Option<NaiveDate>,
+    // Value lost in the first year and in each year after, in percent
+    pub first_year_depreciation: f64,
+    pub annual_depreciation: f64,
+    pub currency: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquityRow {
+    pub installment_number: u32,
+    pub date: Option<NaiveDate>,
+    pub vehicle_value: f64,
+    pub balance: f64,
+    // Negative while the buyer is underwater
+    pub equity: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct AutoLoan {
+    pub taxable_price: f64,
+    pub sales_tax: f64,
+    pub negative_equity: f64,
+    pub amount_financed: f64,
+    pub loan: Amortization,
+    // Equity at signing, then after each installment
+    pub equity: Vec<EquityRow>,
+}
+
+impl AutoPurchase {
+    pub fn new(price: f64, apr: f64, term_months: u32) -> Self {
+        AutoPurchase {
+            price,
+            sales_tax_rate: 0.0,
+            fees: 0.0,
+            trade_in_value: 0.0,
+            trade_in_payoff: 0.0,
+            trade_in_tax_credit: true,
+            cash_down: 0.0,
+            apr,
+            term_months,
+            start_date: None,
+            // Typical for a new car
+            first_year_depreciation: 20.0,
+            annual_depreciation: 15.0,
+            currency: Currency::default(),
+        }
+    }
+
+    pub fn with_sales_tax(mut self, sales_tax_rate: f64) -> Self {
+        self.sales_tax_rate = sales_tax_rate;
+        self
+    }
+
+    pub fn with_fees(mut self, fees: f64) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    pub fn with_trade_in(mut self, value: f64, payoff: f64) -> Self {
+        self.trade_in_value = value;
+        self.trade_in_payoff = payoff;
+        self
+    }
+
+    pub fn without_trade_in_tax_credit(mut self) -> Self {
+        self.trade_in_tax_credit = false;
+        self
+    }
+
+    pub fn with_cash_down(mut self, cash_down: f64) -> Self {
+        self.cash_down = cash_down;
+        self
+    }
+
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn with_depreciation(mut self, first_year: f64, annual: f64) -> Self {
+        self.first_year_depreciation = first_year;
+        self.annual_depreciation = annual;
+        self
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.price <= 0.0 || !self.price.is_finite() {
+            return Err(AmortizationError::InvalidAsset(format!("price must be greater than 0, got {}", self.price)));
+        }
+        let amounts = [
+            ("sales tax rate", self.sales_tax_rate),
+            ("fees", self.fees),
+            ("trade-in value", self.trade_in_value),
+            ("trade-in payoff", self.trade_in_payoff),
+            ("cash down", self.cash_down),
+        ];
+        if let Some((name, value)) = amounts.iter().find(|(_, value)| *value < 0.0 || !value.is_finite()) {
+            return Err(AmortizationError::InvalidAsset(format!("{} must not be negative, got {}", name, value)));
+        }
+        for depreciation in [self.first_year_depreciation, self.annual_depreciation] {
+            if !(0.0..100.0).contains(&depreciation) {
+                return Err(AmortizationError::InvalidAsset(format!("depreciation must be at least 0 and below 100%, got {}", depreciation)));
+            }
+        }
+        Ok(())
+    }
+
+    // Trade-in owed beyond what it is worth
+    pub fn negative_equity(&self) -> f64 {
+        (self.trade_in_payoff - self.trade_in_value).max(0.0)
+    }
+
+    pub fn taxable_price(&self) -> f64 {
+        let credit = if self.trade_in_tax_credit { self.trade_in_value } else { 0.0 };
+        (self.price - credit).max(0.0)
+    }
+
+    pub fn sales_tax(&self) -> f64 {
+        self.currency.round(self.taxable_price() * self.sales_tax_rate / 100.0)
+    }
+
+    // Price, tax and fees, less the trade-in's net value and the cash down
+    pub fn amount_financed(&self) -> f64 {
+        let trade_in_equity = self.trade_in_value - self.trade_in_payoff;
+        self.currency.round(self.price + self.sales_tax() + self.fees - trade_in_equity - self.cash_down)
+    }
+
+    // Estimated value `months` after the purchase, depreciating monthly at each year's rate
+    pub fn vehicle_value_at(&self, months: u32) -> f64 {
+        let first_year = months.min(12) as f64 / 12.0;
+        let later_years = months.saturating_sub(12) as f64 / 12.0;
+        self.price
+            * (1.0 - self.first_year_depreciation / 100.0).powf(first_year)
+            * (1.0 - self.annual_depreciation / 100.0).powf(later_years)
+    }
+
+    pub fn finance(&self) -> Result<AutoLoan, AmortizationError> {
+        self.validate()?;
+        let amount_financed = self.amount_financed();
+        let mut config = CalculatorConfig::new(amount_financed, self.apr, self.term_months, self.start_date);
+        config.currency = self.currency;
+        let loan = Amortization::from_config(config)?;
+
+        let signing = EquityRow {
+            installment_number: 0,
+            date: None,
+            vehicle_value: self.currency.round(self.price),
+            balance: amount_financed,
+            equity: self.currency.round(self.price - amount_financed),
+        };
+        let equity = std::iter::once(signing)
+            .chain(loan.schedule.iter().map(|payment| {
+                let vehicle_value = self.currency.round(self.vehicle_value_at(payment.installment_number));
+                EquityRow {
+                    installment_number: payment.installment_number,
+                    date: payment.date,
+                    vehicle_value,
+                    balance: payment.remaining_balance,
+                    equity: self.currency.round(vehicle_value - payment.remaining_balance),
+                }
+            }))
+            .collect();
+
+        Ok(AutoLoan {
+            taxable_price: self.taxable_price(),
+            sales_tax: self.sales_tax(),
+            negative_equity: self.negative_equity(),
+            amount_financed,
+            loan,
+            equity,
+        })
+    }
+}
+
+impl AutoLoan {
+    // First installment after which the car is worth at least the balance; Some(0) when the
+    // buyer is never underwater
+    pub fn break_even_installment(&self) -> Option<u32> {
+        self.equity.iter().find(|row| row.equity >= 0.0).map(|row| row.installment_number)
+    }
+
+    pub fn underwater_months(&self) -> u32 {
+        self.equity.iter().skip(1).filter(|row| row.equity < 0.0).count() as u32
+    }
+
+    // Largest amount owed beyond the car's value, zero if never underwater
+    pub fn max_negative_equity(&self) -> f64 {
+        self.equity.iter().map(|row| -row.equity).fold(0.0, f64::max)
+    }
+}
@@ -0,0 +1,158 @@
+// Level monthly payments and totals for large numbers of plain loans at once, for pricing
+// grids and sensitivity tables. Only the closed-form amounts are computed, never a schedule.
+// The `simd` feature runs the compounding for four loans in lockstep so it maps onto vector
+// registers; both paths give bit-for-bit the same results
+use crate::calculator::{covers_interest, timed_periodic_payment};
+use crate::error::AmortizationError;
+use crate::money::Currency;
+#[cfg(not(feature = "simd"))]
+use crate::num::AmortNum;
+use crate::tvm::PaymentTiming;
+
+#[cfg(feature = "simd")]
+const LANES: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatchLoan {
+    pub principal: f64,
+    // Annual rate in percent, compounded monthly
+    pub apr: f64,
+    pub term_months: u32,
+    pub currency: Currency,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatchQuote {
+    // The rounded level installment, as `Amortization` computes it for the same loan
+    pub payment: f64,
+    // The last installment clears the balance left by the rounded level payments
+    pub final_payment: f64,
+    pub total_payment: f64,
+    pub total_interest: f64,
+}
+
+impl BatchLoan {
+    pub fn new(principal: f64, apr: f64, term_months: u32) -> Self {
+        BatchLoan { principal, apr, term_months, currency: Currency::default() }
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.term_months == 0 {
+            return Err(AmortizationError::InvalidPeriods(self.term_months));
+        }
+        if self.apr < 0.0 || !self.apr.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(self.apr));
+        }
+        if self.principal <= 0.0 || !self.principal.is_finite() {
+            return Err(AmortizationError::InvalidLoanAmount(self.principal));
+        }
+        Ok(())
+    }
+
+    // Divided the way `Rate::periodic_rate` divides, so the payment matches to the bit
+    fn monthly_rate(&self) -> f64 {
+        self.apr / 100.0 / 12.0
+    }
+}
+
+impl From<(f64, f64, u32)> for BatchLoan {
+    fn from((principal, apr, term_months): (f64, f64, u32)) -> Self {
+        BatchLoan::new(principal, apr, term_months)
+    }
+}
+
+// (1 + r)^n for every loan, infinite where it overflows
+#[cfg(not(feature = "simd"))]
+fn growth(loans: &[BatchLoan]) -> Vec<f64> {
+    loans.iter()
+        .map(|loan| (1.0 + loan.monthly_rate()).checked_powi(loan.term_months).unwrap_or(f64::INFINITY))
+        .collect()
+}
+
+// The same repeated squaring as `AmortNum::checked_powi`, a chunk of loans at a time; a lane
+// whose exponent has run out keeps its result while the others finish
+#[cfg(feature = "simd")]
+fn growth(loans: &[BatchLoan]) -> Vec<f64> {
+    let mut factors = Vec::with_capacity(loans.len());
+    for chunk in loans.chunks(LANES) {
+        let mut base = [1.0; LANES];
+        let mut exponent = [0; LANES];
+        for (lane, loan) in chunk.iter().enumerate() {
+            base[lane] = 1.0 + loan.monthly_rate();
+            exponent[lane] = loan.term_months;
+        }
+        let mut result = [1.0; LANES];
+        while exponent.iter().any(|&n| n > 0) {
+            for ((result, base), exponent) in result.iter_mut().zip(base.iter_mut()).zip(exponent.iter_mut()) {
+                if *exponent & 1 == 1 {
+                    *result *= *base;
+                }
+                *exponent >>= 1;
+                *base *= *base;
+            }
+        }
+        factors.extend(result[..chunk.len()].iter().map(|&factor| if factor.is_finite() { factor } else { f64::INFINITY }));
+    }
+    factors
+}
+
+fn quote(loan: &BatchLoan, growth: f64) -> Result<BatchQuote, AmortizationError> {
+    if !growth.is_finite() {
+        return Err(AmortizationError::Overflow("compound growth"));
+    }
+    let rate = loan.monthly_rate();
+    let periods = loan.term_months as f64;
+    // Balance left after `paid` installments at a level payment, where (1 + r)^paid is `factor`
+    let balance_after = |payment: f64, paid: f64, factor: f64| if rate == 0.0 {
+        loan.principal - payment * paid
+    } else {
+        loan.principal * factor - payment * (factor - 1.0) / rate
+    };
+    let before_last = |payment: f64| balance_after(payment, periods - 1.0, growth / (1.0 + rate));
+
+    // Settled as `Amortization::from_config` settles it: a cent lower while the level payment
+    // would retire the balance early, as long as the last installment stays within one more
+    let currency = loan.currency;
+    let mut payment = timed_periodic_payment(rate, loan.term_months, loan.principal, PaymentTiming::Arrears, currency)?;
+    while loan.term_months > 1 && before_last(payment) <= 0.0
+        && covers_interest(payment - currency.minor_unit(), rate, loan.principal, PaymentTiming::Arrears) {
+        let lower = currency.round(payment - currency.minor_unit());
+        if before_last(lower) > lower + currency.minor_unit() / 2.0 {
+            break;
+        }
+        payment = lower;
+    }
+    // A payment kept a cent high pays off in fewer installments, the first after which
+    // nothing would be left
+    let installments = if before_last(payment) > 0.0 {
+        periods
+    } else if rate == 0.0 {
+        (loan.principal / payment).ceil()
+    } else {
+        ((payment / (payment - loan.principal * rate)).ln() / (1.0 + rate).ln()).ceil()
+    }.clamp(1.0, periods);
+    let left = balance_after(payment, installments, (1.0 + rate).powi(installments as i32));
+    let final_payment = currency.round(payment + left);
+    let total_payment = currency.round(payment * (installments - 1.0) + final_payment);
+    Ok(BatchQuote {
+        payment,
+        final_payment,
+        total_payment,
+        total_interest: currency.round(total_payment - loan.principal),
+    })
+}
+
+// Payments and totals for every loan, in order; the first invalid loan fails the batch
+pub fn quotes(loans: &[BatchLoan]) -> Result<Vec<BatchQuote>, AmortizationError> {
+    loans.iter().try_for_each(BatchLoan::validate)?;
+    loans.iter().zip(growth(loans)).map(|(loan, growth)| quote(loan, growth)).collect()
+}
+
+pub fn payments(loans: &[BatchLoan]) -> Result<Vec<f64>, AmortizationError> {
+    Ok(quotes(loans)?.into_iter().map(|quote| quote.payment).collect())
+}
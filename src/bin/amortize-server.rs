@@ -0,0 +1,14 @@
+use std::process::ExitCode;
+
+// Usage: amortize-server [address], listening on 127.0.0.1:8080 by default
+fn main() -> ExitCode {
+    let addr = std::env::args().nth(1).unwrap_or_else(|| "127.0.0.1:8080".to_string());
+    eprintln!("Listening on http://{}", addr);
+    match amortize_rs::server::serve(addr.as_str()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
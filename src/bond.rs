@@ -0,0 +1,118 @@
+use crate::error::AmortizationError;
+use crate::frequency::PaymentFrequency;
+use crate::money::Currency;
+use crate::tvm::{self, PaymentTiming};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bond {
+    pub face_value: f64,
+    // Annual coupon and market yield in percent
+    pub coupon_rate: f64,
+    pub market_yield: f64,
+    pub periods: u32,
+    pub frequency: PaymentFrequency,
+    pub currency: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BondAmortizationRow {
+    pub period: u32,
+    pub interest_expense: f64,
+    pub coupon_payment: f64,
+    // Positive when a premium is amortized, negative when a discount is accreted
+    pub amortization: f64,
+    pub carrying_value: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BondSchedule {
+    pub issue_price: f64,
+    // Issue price less face value: positive for a premium, negative for a discount
+    pub premium: f64,
+    pub rows: Vec<BondAmortizationRow>,
+}
+
+impl Bond {
+    pub fn new(face_value: f64, coupon_rate: f64, market_yield: f64, periods: u32) -> Self {
+        Bond {
+            face_value,
+            coupon_rate,
+            market_yield,
+            periods,
+            frequency: PaymentFrequency::SemiAnnually,
+            currency: Currency::default(),
+        }
+    }
+
+    pub fn with_frequency(mut self, frequency: PaymentFrequency) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.face_value <= 0.0 || !self.face_value.is_finite() {
+            return Err(AmortizationError::InvalidLoanAmount(self.face_value));
+        }
+        if self.coupon_rate < 0.0 || !self.coupon_rate.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(self.coupon_rate));
+        }
+        if self.market_yield <= 0.0 || !self.market_yield.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(self.market_yield));
+        }
+        if self.periods == 0 {
+            return Err(AmortizationError::InvalidPeriods(self.periods));
+        }
+        Ok(())
+    }
+
+    fn periodic(&self, annual_percent: f64) -> f64 {
+        annual_percent / 100.0 / self.frequency.periods_per_year() as f64
+    }
+
+    pub fn coupon_payment(&self) -> f64 {
+        self.currency.round(self.face_value * self.periodic(self.coupon_rate))
+    }
+
+    // Present value of the coupons and the face value at the market yield
+    pub fn issue_price(&self) -> Result<f64, AmortizationError> {
+        self.validate()?;
+        let price = tvm::pv(self.periodic(self.market_yield), self.periods, self.coupon_payment(), self.face_value, PaymentTiming::Arrears)?;
+        Ok(self.currency.round(-price))
+    }
+
+    // Effective interest method: expense is the carrying value times the periodic yield, and
+    // the difference from the coupon moves the carrying value towards face
+    pub fn schedule(&self) -> Result<BondSchedule, AmortizationError> {
+        let issue_price = self.issue_price()?;
+        let yield_rate = self.periodic(self.market_yield);
+        let coupon_payment = self.coupon_payment();
+
+        let mut carrying_value = issue_price;
+        let rows = (1..=self.periods).map(|period| {
+            let amortization = if period == self.periods {
+                carrying_value - self.face_value
+            } else {
+                coupon_payment - self.currency.round(carrying_value * yield_rate)
+            };
+            carrying_value = self.currency.round(carrying_value - amortization);
+            BondAmortizationRow {
+                period,
+                interest_expense: self.currency.round(coupon_payment - amortization),
+                coupon_payment,
+                amortization: self.currency.round(amortization),
+                carrying_value,
+            }
+        }).collect();
+
+        Ok(BondSchedule {
+            issue_price,
+            premium: self.currency.round(issue_price - self.face_value),
+            rows,
+        })
+    }
+}
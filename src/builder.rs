@@ -0,0 +1,398 @@
+use chrono::NaiveDate;
+use crate::calculator::{Amortization, CalculatorConfig, DEFAULT_MAX_PERIODS};
+use std::sync::Arc;
+use crate::calendar::{BusinessDayConvention, DateRollConvention, HolidayCalendar};
+use crate::day_count::DayCount;
+use crate::escrow::Escrow;
+use crate::fees::Fee;
+use crate::error::AmortizationError;
+use crate::floating::FloatingRate;
+use crate::frequency::PaymentFrequency;
+use crate::interest::{InterestCalculator, InterestMethod};
+use crate::loan_type::{CapitalizationFrequency, Deferment, FixedPayment, GraduatedPayment, LoanType};
+use crate::money::{Currency, RoundingPolicy};
+use crate::num::AmortNum;
+use crate::odd_days::FirstPeriodPolicy;
+use crate::penalty::PrepaymentPenalty;
+use crate::prepayment::Prepayment;
+use crate::property::PropertyValue;
+use crate::rate::{Jurisdiction, PaymentCap, RateCaps, RateConvention, RatePeriod};
+use crate::skip::SkipPayments;
+use crate::teaser::TeaserRate;
+use crate::tvm::PaymentTiming;
+use crate::units::{Principal, Rate, Term};
+
+#[derive(Debug, Clone, Default)]
+pub struct AmortizationBuilder {
+    principal: Option<f64>,
+    apr: Option<f64>,
+    term_months: Option<u32>,
+    start_date: Option<NaiveDate>,
+    // A start date given through `start_on` that isn't a calendar date, reported by `build()`
+    invalid_start: Option<(i32, u32, u32)>,
+    frequency: PaymentFrequency,
+    extra_payment: f64,
+    rate_schedule: Vec<RatePeriod>,
+    loan_type: LoanType,
+    balloon_period: Option<u32>,
+    day_count: Option<DayCount>,
+    payment_cap: Option<PaymentCap>,
+    deferment: Option<Deferment>,
+    capitalization: Option<CapitalizationFrequency>,
+    skip_payments: Option<SkipPayments>,
+    payment_timing: PaymentTiming,
+    graduated_payment: Option<GraduatedPayment>,
+    escrow: Option<Escrow>,
+    property: Option<PropertyValue>,
+    currency: Currency,
+    rounding: RoundingPolicy,
+    rate_convention: RateConvention,
+    interest_method: InterestMethod,
+    interest_calculator: Option<Arc<dyn InterestCalculator>>,
+    prepayments: Vec<Prepayment>,
+    date_roll: DateRollConvention,
+    business_day_convention: BusinessDayConvention,
+    holiday_calendar: Option<Arc<dyn HolidayCalendar>>,
+    funding_date: Option<NaiveDate>,
+    odd_days_treatment: FirstPeriodPolicy,
+    inflation_rate: Option<f64>,
+    prepayment_penalty: Option<PrepaymentPenalty>,
+    teaser: Option<TeaserRate>,
+    floating_rate: Option<FloatingRate>,
+    rate_caps: Option<RateCaps>,
+    fixed_payment: Option<FixedPayment>,
+    fees: Vec<Fee>,
+    max_periods: Option<u32>,
+}
+
+impl AmortizationBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn principal(mut self, principal: impl Into<Principal>) -> Self {
+        self.principal = Some(principal.into().amount());
+        self
+    }
+
+    #[deprecated(note = "use `rate(Rate::percent(apr))` or `rate(Rate::fraction(..))`")]
+    pub fn apr(mut self, apr: f64) -> Self {
+        self.apr = Some(apr);
+        self
+    }
+
+    pub fn rate(mut self, rate: Rate) -> Self {
+        self.apr = Some(rate.as_percent());
+        self
+    }
+
+    // The rate if it looks like a fraction given as a percent (0.05 meant as 5%), for callers
+    // that want to warn before building
+    pub fn suspicious_rate(&self) -> Option<f64> {
+        self.apr.filter(|&apr| Rate::percent(apr).is_likely_fraction())
+    }
+
+    pub fn term_months(mut self, term_months: u32) -> Self {
+        self.term_months = Some(term_months);
+        self
+    }
+
+    pub fn term(mut self, term: impl Into<Term>) -> Self {
+        self.term_months = Some(term.into().in_months());
+        self
+    }
+
+    pub fn start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self.invalid_start = None;
+        self
+    }
+
+    // The year, month and day of a `start_on` date chrono can't hold
+    pub(crate) fn invalid_start_date(mut self, year: i32, month: u32, day: u32) -> Self {
+        self.start_date = None;
+        self.invalid_start = Some((year, month, day));
+        self
+    }
+
+    pub fn frequency(mut self, frequency: PaymentFrequency) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    pub fn extra_payment(mut self, extra_payment: f64) -> Self {
+        self.extra_payment = extra_payment;
+        self
+    }
+
+    pub fn rate_schedule(mut self, rate_schedule: Vec<RatePeriod>) -> Self {
+        self.rate_schedule = rate_schedule;
+        self
+    }
+
+    // Applies in front of the APR or rate schedule, whichever is set
+    pub fn teaser_rate(mut self, teaser: TeaserRate) -> Self {
+        self.teaser = Some(teaser);
+        self
+    }
+
+    // Held at each reset of the rate schedule; a floating rate carries its own
+    pub fn rate_caps(mut self, rate_caps: RateCaps) -> Self {
+        self.rate_caps = Some(rate_caps);
+        self
+    }
+
+    // Rates projected off an index and margin over the loan's term instead of a single APR
+    pub fn floating_rate(mut self, floating_rate: FloatingRate) -> Self {
+        self.floating_rate = Some(floating_rate);
+        self
+    }
+
+    // Without a term the loan runs for as many periods as the payment takes to repay it
+    pub fn fixed_payment(mut self, fixed_payment: FixedPayment) -> Self {
+        self.fixed_payment = Some(fixed_payment);
+        self
+    }
+
+    pub fn loan_type(mut self, loan_type: LoanType) -> Self {
+        self.loan_type = loan_type;
+        self
+    }
+
+    pub fn balloon_at(mut self, period: u32) -> Self {
+        self.balloon_period = Some(period);
+        self
+    }
+
+    pub fn day_count(mut self, day_count: DayCount) -> Self {
+        self.day_count = Some(day_count);
+        self
+    }
+
+    pub fn payment_cap(mut self, payment_cap: PaymentCap) -> Self {
+        self.payment_cap = Some(payment_cap);
+        self
+    }
+
+    pub fn deferment(mut self, deferment: Deferment) -> Self {
+        self.deferment = Some(deferment);
+        self
+    }
+
+    pub fn capitalization(mut self, capitalization: CapitalizationFrequency) -> Self {
+        self.capitalization = Some(capitalization);
+        self
+    }
+
+    pub fn skip_payments(mut self, skip_payments: SkipPayments) -> Self {
+        self.skip_payments = Some(skip_payments);
+        self
+    }
+
+    pub fn payment_timing(mut self, payment_timing: PaymentTiming) -> Self {
+        self.payment_timing = payment_timing;
+        self
+    }
+
+    pub fn graduated_payment(mut self, growth_rate: f64, graduation_years: u32) -> Self {
+        self.graduated_payment = Some(GraduatedPayment::new(growth_rate, graduation_years));
+        self
+    }
+
+    pub fn escrow(mut self, escrow: Escrow) -> Self {
+        self.escrow = Some(escrow);
+        self
+    }
+
+    pub fn property_value(mut self, value: f64, annual_appreciation: f64) -> Self {
+        self.property = Some(PropertyValue::new(value).appreciating(annual_appreciation));
+        self
+    }
+
+    pub fn currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    // Takes a `RoundingPolicy`, or just a `RoundingMode` to keep full precision
+    pub fn rounding(mut self, rounding: impl Into<RoundingPolicy>) -> Self {
+        self.rounding = rounding.into();
+        self
+    }
+
+    pub fn rate_convention(mut self, rate_convention: RateConvention) -> Self {
+        self.rate_convention = rate_convention;
+        self
+    }
+
+    // Shorthand for the rate convention mortgages follow in `jurisdiction`
+    pub fn jurisdiction(mut self, jurisdiction: Jurisdiction) -> Self {
+        self.rate_convention = jurisdiction.rate_convention();
+        self
+    }
+
+    pub fn interest_method(mut self, interest_method: InterestMethod) -> Self {
+        self.interest_method = interest_method;
+        self
+    }
+
+    pub fn interest_calculator(mut self, interest_calculator: impl InterestCalculator + 'static) -> Self {
+        self.interest_calculator = Some(Arc::new(interest_calculator));
+        self
+    }
+
+    pub fn prepayment(mut self, prepayment: Prepayment) -> Self {
+        self.prepayments.push(prepayment);
+        self
+    }
+
+    pub fn fee(mut self, fee: Fee) -> Self {
+        self.fees.push(fee);
+        self
+    }
+
+    pub fn max_periods(mut self, max_periods: u32) -> Self {
+        self.max_periods = Some(max_periods);
+        self
+    }
+
+    pub fn date_roll(mut self, date_roll: DateRollConvention) -> Self {
+        self.date_roll = date_roll;
+        self
+    }
+
+    pub fn business_day_convention(mut self, business_day_convention: BusinessDayConvention) -> Self {
+        self.business_day_convention = business_day_convention;
+        self
+    }
+
+    pub fn holiday_calendar(mut self, holiday_calendar: impl HolidayCalendar + 'static) -> Self {
+        self.holiday_calendar = Some(Arc::new(holiday_calendar));
+        self
+    }
+
+    pub fn funding_date(mut self, funding_date: NaiveDate) -> Self {
+        self.funding_date = Some(funding_date);
+        self
+    }
+
+    pub fn odd_days_treatment(mut self, odd_days_treatment: FirstPeriodPolicy) -> Self {
+        self.odd_days_treatment = odd_days_treatment;
+        self
+    }
+
+    pub fn first_period_policy(self, policy: FirstPeriodPolicy) -> Self {
+        self.odd_days_treatment(policy)
+    }
+
+    pub fn inflation_rate(mut self, inflation_rate: f64) -> Self {
+        self.inflation_rate = Some(inflation_rate);
+        self
+    }
+
+    pub fn prepayment_penalty(mut self, prepayment_penalty: PrepaymentPenalty) -> Self {
+        self.prepayment_penalty = Some(prepayment_penalty);
+        self
+    }
+
+    pub fn config(&self) -> Result<CalculatorConfig, AmortizationError> {
+        let balance = self.principal.ok_or(AmortizationError::MissingParameter("principal"))?;
+        if let Some((year, month, day)) = self.invalid_start {
+            return Err(AmortizationError::InvalidStartDate(year, month, day));
+        }
+        // A floating rate contributes the index plus margin at each reset, held within its caps
+        let (floating_schedule, rate_caps) = match &self.floating_rate {
+            Some(_) if !self.rate_schedule.is_empty() => return Err(AmortizationError::ConflictingOptions("floating_rate", "rate_schedule")),
+            Some(_) if self.rate_caps.is_some() => return Err(AmortizationError::ConflictingOptions("floating_rate", "rate_caps")),
+            Some(floating) => {
+                let term_months = self.term_months.ok_or(AmortizationError::MissingParameter("term_months"))?;
+                (floating.uncapped_schedule(self.frequency.periods_for_months(term_months))?, Some(floating.caps))
+            }
+            None => (Vec::new(), self.rate_caps),
+        };
+        let rate_schedule = if floating_schedule.is_empty() { &self.rate_schedule } else { &floating_schedule };
+        let apr = match (self.apr, rate_schedule.first()) {
+            // A floating rate sets its own starting rate
+            (_, Some(first)) if self.floating_rate.is_some() => first.apr,
+            (Some(apr), _) => apr,
+            (None, Some(first)) => first.apr,
+            (None, None) => return Err(AmortizationError::MissingParameter("apr")),
+        };
+        let (apr, rate_schedule) = match self.teaser {
+            Some(teaser) if teaser.periods == 0 => return Err(AmortizationError::InvalidRateSchedule(
+                "Teaser rate must last at least one period".to_string()
+            )),
+            Some(teaser) => {
+                let standard = if rate_schedule.is_empty() { vec![RatePeriod::new(1, apr)] } else { rate_schedule.clone() };
+                (teaser.apr, teaser.apply(&standard))
+            }
+            None => (apr, rate_schedule.clone()),
+        };
+        let loan_term = match (self.term_months, self.fixed_payment) {
+            (Some(term_months), _) => term_months,
+            (None, Some(fixed)) => {
+                let uncapped = if rate_schedule.is_empty() { vec![RatePeriod::new(1, apr)] } else { rate_schedule.clone() };
+                let resolved = match rate_caps {
+                    Some(caps) => caps.capped_schedule(&uncapped),
+                    None => uncapped,
+                };
+                let rates: Vec<(u32, f64)> = resolved.iter()
+                    .map(|r| (r.start_period, r.periodic_rate_with(self.frequency, self.rate_convention)))
+                    .collect();
+                let periods = fixed.periods_to_repay(balance, &rates, self.payment_timing)?;
+                let periods_per_year = self.frequency.periods_per_year();
+                (periods * 12).div_ceil(periods_per_year)
+            }
+            (None, None) => return Err(AmortizationError::MissingParameter("term_months")),
+        };
+
+        Ok(CalculatorConfig {
+            frequency: self.frequency,
+            extra_payment: self.extra_payment,
+            rate_schedule,
+            rate_caps,
+            loan_type: self.loan_type,
+            balloon_period: self.balloon_period,
+            day_count: self.day_count,
+            payment_cap: self.payment_cap,
+            deferment: self.deferment,
+            capitalization: self.capitalization,
+            skip_payments: self.skip_payments.clone(),
+            payment_timing: self.payment_timing,
+            graduated_payment: self.graduated_payment,
+            escrow: self.escrow,
+            property: self.property,
+            currency: self.currency,
+            rounding: self.rounding,
+            rate_convention: self.rate_convention,
+            interest_method: self.interest_method,
+            interest_calculator: self.interest_calculator.clone(),
+            prepayments: self.prepayments.clone(),
+            date_roll: self.date_roll,
+            business_day_convention: self.business_day_convention,
+            holiday_calendar: self.holiday_calendar.clone(),
+            funding_date: self.funding_date,
+            odd_days_treatment: self.odd_days_treatment,
+            inflation_rate: self.inflation_rate,
+            prepayment_penalty: self.prepayment_penalty.clone(),
+            fixed_payment: self.fixed_payment,
+            fees: self.fees.clone(),
+            max_periods: self.max_periods.unwrap_or(DEFAULT_MAX_PERIODS),
+            ..CalculatorConfig::new(balance, apr, loan_term, self.start_date)
+        })
+    }
+
+    pub fn build(&self) -> Result<Amortization, AmortizationError> {
+        Amortization::from_config(self.config()?)
+    }
+
+    pub fn build_with<N: AmortNum>(&self) -> Result<Amortization, AmortizationError> {
+        Amortization::from_config_with_backend::<N>(self.config()?)
+    }
+}
+
+impl Amortization {
+    pub fn builder() -> AmortizationBuilder {
+        AmortizationBuilder::new()
+    }
+}
@@ -1,7 +1,30 @@
 use std::fmt;
 use chrono::NaiveDate;
-use crate::payment::Payment;
+use crate::money::{Currency, Money, RoundingPolicy};
+use crate::payment::{Payment, PaymentAmounts};
+use crate::penalty::PrepaymentPenalty;
+use crate::prepayment::Prepayment;
+use crate::property::PropertyValue;
+use std::sync::Arc;
+use crate::calendar::{BusinessDayConvention, DateRollConvention, HolidayCalendar, WeekendsOnly};
+use crate::day_count::DayCount;
 use crate::error::AmortizationError;
+use crate::events::ScheduleEvent;
+use crate::escrow::Escrow;
+use crate::fees::Fee;
+use crate::frequency::PaymentFrequency;
+use crate::interest::{InterestCalculator, InterestMethod};
+use crate::loan_type::{CapitalizationFrequency, Deferment, FixedPayment, GraduatedPayment, LoanType};
+use crate::num::AmortNum;
+use crate::odd_days::{is_valid_funding_date, FirstPeriodPolicy};
+use crate::rate::{PaymentCap, RateAdjustment, RateCaps, RateConvention, RatePeriod};
+use crate::schedule::Payments;
+use crate::skip::SkipPayments;
+use crate::tvm::{self, PaymentTiming};
+use crate::validate::ValidationIssue;
+
+// Fifty years of weekly installments, with room for extensions
+pub const DEFAULT_MAX_PERIODS: u32 = 6000;
 
 #[derive(Debug, Clone)]
 pub struct Amortization {
@@ -14,6 +37,11 @@ pub struct Amortization {
     pub total_interest: f64,      
     pub start_date: Option<NaiveDate>,
     pub end_date: Option<NaiveDate>,  
+    pub final_payment_amount: f64,
+    pub rate_schedule: Vec<RatePeriod>,
+    pub config: CalculatorConfig,
+    // Transforms applied to reach this schedule, oldest first
+    pub events: Vec<ScheduleEvent>,
 }
 
 impl fmt::Display for Amortization {
@@ -23,6 +51,7 @@ impl fmt::Display for Amortization {
         writeln!(f, "Periodic Interest Rate: {:.4}", self.periodic_interest)?;
         writeln!(f, "Total Periods: {}", self.periods)?;
         writeln!(f, "Periodic Payment: {:.2}", self.periodic_payment)?;
+        writeln!(f, "Final Payment: {:.2}", self.final_payment_amount)?;
         writeln!(f, "Total Payment: {:.2}", self.total_payment)?;
         writeln!(f, "Total Interest: {:.2}", self.total_interest)?;
         writeln!(f, "Amortization Schedule:")?;
@@ -40,141 +69,802 @@ pub struct CalculatorConfig {
     pub loan_term: u32,        
     pub apr: f64,              
     pub start_date: Option<NaiveDate>,
+    pub frequency: PaymentFrequency,
+    pub extra_payment: f64,
+    pub rate_schedule: Vec<RatePeriod>,
+    // Applied to the rate schedule at each reset; the schedule then holds the uncapped rates
+    pub rate_caps: Option<RateCaps>,
+    pub loan_type: LoanType,
+    pub balloon_period: Option<u32>,
+    pub day_count: Option<DayCount>,
+    pub payment_cap: Option<PaymentCap>,
+    pub deferment: Option<Deferment>,
+    // None keeps each event's usual timing: deferred interest at the end of the deferment,
+    // other unpaid interest every period
+    pub capitalization: Option<CapitalizationFrequency>,
+    pub skip_payments: Option<SkipPayments>,
+    pub payment_timing: PaymentTiming,
+    pub graduated_payment: Option<GraduatedPayment>,
+    pub escrow: Option<Escrow>,
+    pub property: Option<PropertyValue>,
+    pub currency: Currency,
+    pub rounding: RoundingPolicy,
+    pub rate_convention: RateConvention,
+    pub interest_method: InterestMethod,
+    // Replaces the interest method when set
+    pub interest_calculator: Option<Arc<dyn InterestCalculator>>,
+    pub prepayments: Vec<Prepayment>,
+    pub date_roll: DateRollConvention,
+    pub business_day_convention: BusinessDayConvention,
+    // Weekends only when not set
+    pub holiday_calendar: Option<Arc<dyn HolidayCalendar>>,
+    // Date the loan is disbursed, when it isn't exactly one period before the first due date
+    pub funding_date: Option<NaiveDate>,
+    pub odd_days_treatment: FirstPeriodPolicy,
+    // Annual inflation in percent used to restate the schedule in today's money
+    pub inflation_rate: Option<f64>,
+    pub prepayment_penalty: Option<PrepaymentPenalty>,
+    // Installment set by the borrower rather than derived from the term
+    pub fixed_payment: Option<FixedPayment>,
+    // Charged on top of the installment, outside principal and interest
+    pub fees: Vec<Fee>,
+    // Installments a schedule may run to before it is given up as never repaying
+    pub max_periods: u32,
+}
+
+impl CalculatorConfig {
+    pub fn new(balance: f64, apr: f64, loan_term: u32, start_date: Option<NaiveDate>) -> Self {
+        CalculatorConfig {
+            balance,
+            loan_term,
+            apr,
+            start_date,
+            frequency: PaymentFrequency::Monthly,
+            extra_payment: 0.0,
+            rate_schedule: Vec::new(),
+            rate_caps: None,
+            loan_type: LoanType::Amortizing,
+            balloon_period: None,
+            day_count: None,
+            payment_cap: None,
+            deferment: None,
+            capitalization: None,
+            skip_payments: None,
+            payment_timing: PaymentTiming::Arrears,
+            graduated_payment: None,
+            escrow: None,
+            property: None,
+            currency: Currency::USD,
+            rounding: RoundingPolicy::default(),
+            rate_convention: RateConvention::Nominal,
+            interest_method: InterestMethod::Actuarial,
+            interest_calculator: None,
+            prepayments: Vec::new(),
+            date_roll: DateRollConvention::SameDay,
+            business_day_convention: BusinessDayConvention::Unadjusted,
+            holiday_calendar: None,
+            funding_date: None,
+            odd_days_treatment: FirstPeriodPolicy::CollectAtClosing,
+            inflation_rate: None,
+            prepayment_penalty: None,
+            fixed_payment: None,
+            fees: Vec::new(),
+            max_periods: DEFAULT_MAX_PERIODS,
+        }
+    }
+
+    pub fn total_periods(&self) -> u32 {
+        self.frequency.periods_for_months(self.loan_term)
+    }
+
+    // The rates charged: the rate schedule, or the APR throughout, held within the rate caps
+    pub fn resolved_rate_schedule(&self) -> Vec<RatePeriod> {
+        let uncapped = self.uncapped_rate_schedule();
+        match &self.rate_caps {
+            Some(caps) => caps.capped_schedule(&uncapped),
+            None => uncapped,
+        }
+    }
+
+    // The rates each reset calls for, before any caps
+    pub fn uncapped_rate_schedule(&self) -> Vec<RatePeriod> {
+        if self.rate_schedule.is_empty() {
+            vec![RatePeriod::new(1, self.apr)]
+        } else {
+            self.rate_schedule.clone()
+        }
+    }
+
+    // Every problem with the configuration rather than just the first, e.g. to flag all the
+    // invalid fields of a form at once. Checks against the term are skipped when the term
+    // itself is invalid
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let mut issue = |field, error| issues.push(ValidationIssue { field, error });
+        let periods = self.total_periods();
+        if periods == 0 {
+            issue("loan_term", AmortizationError::InvalidPeriods(periods));
+        }
+        if let Err(error) = validate_rate_schedule(&self.uncapped_rate_schedule(), if periods == 0 { u32::MAX } else { periods }) {
+            issue(if self.rate_schedule.is_empty() { "apr" } else { "rate_schedule" }, error);
+        }
+        if let Some(invalid) = self.rate_caps.as_ref().and_then(RateCaps::invalid_value) {
+            issue("rate_caps", AmortizationError::InvalidRateSchedule(format!("Rate caps and floor must not be negative, got {}", invalid)));
+        }
+        if self.balance <= 0.0 || !self.balance.is_finite() {
+            issue("balance", AmortizationError::InvalidLoanAmount(self.balance));
+        } else if self.balance > self.currency.max_amount() {
+            issue("balance", AmortizationError::Overflow("loan amount"));
+        }
+        if self.extra_payment < 0.0 || !self.extra_payment.is_finite() {
+            issue("extra_payment", AmortizationError::InvalidExtraPayment(self.extra_payment));
+        }
+        if self.extra_payment > self.currency.max_amount() {
+            issue("extra_payment", AmortizationError::Overflow("extra payment"));
+        }
+        let deferred_periods = self.deferment.map_or(0, |d| d.periods());
+        let io_periods = self.loan_type.interest_only_periods();
+        if periods > 0 {
+            if deferred_periods >= periods {
+                issue("deferment", AmortizationError::InvalidDefermentPeriods(deferred_periods));
+            } else if deferred_periods + io_periods >= periods {
+                issue("loan_type", AmortizationError::InvalidInterestOnlyPeriods(io_periods));
+            }
+            if let Some(balloon_period) = self.balloon_period {
+                if balloon_period <= deferred_periods || balloon_period >= periods {
+                    issue("balloon_period", AmortizationError::InvalidBalloonPeriod(balloon_period));
+                }
+            }
+            if let Some(skip) = &self.skip_payments {
+                if let Some(&invalid) = skip.installments.iter().find(|&&n| n == 0 || n >= periods) {
+                    issue("skip_payments", AmortizationError::InvalidSkippedPayment(invalid));
+                }
+            }
+        }
+        if self.max_periods == 0 {
+            issue("max_periods", AmortizationError::InvalidPeriods(self.max_periods));
+        }
+        for prepayment in &self.prepayments {
+            if periods > 0 && (prepayment.period == 0 || prepayment.period >= periods) {
+                issue("prepayments", AmortizationError::PeriodOutOfRange(prepayment.period));
+            }
+            if prepayment.amount <= 0.0 || !prepayment.amount.is_finite() {
+                issue("prepayments", AmortizationError::InvalidExtraPayment(prepayment.amount));
+            }
+        }
+        for fee in &self.fees {
+            if periods > 0 && fee.period.is_some_and(|period| period == 0 || period > periods) {
+                issue("fees", AmortizationError::PeriodOutOfRange(fee.period.unwrap_or(0)));
+            }
+            if fee.amount < 0.0 || !fee.amount.is_finite() {
+                issue("fees", AmortizationError::InvalidFee(fee.amount));
+            }
+        }
+        if let Some(graduated) = self.graduated_payment {
+            let graduation_periods = graduated.graduation_years.saturating_mul(self.frequency.periods_per_year());
+            let amortizing_periods = periods.saturating_sub(deferred_periods + io_periods);
+            if self.loan_type == LoanType::EqualPrincipal
+                || graduated.growth_rate < 0.0 || !graduated.growth_rate.is_finite()
+                || (periods > 0 && graduation_periods >= amortizing_periods) {
+                issue("graduated_payment", AmortizationError::InvalidGraduatedPayment(graduated.growth_rate));
+            }
+        }
+        if let Some(escrow) = self.escrow {
+            let mi_rate = escrow.mortgage_insurance.map_or(0.0, |mi| mi.annual_rate);
+            let invalid = [escrow.annual_property_tax, escrow.annual_insurance, mi_rate].into_iter()
+                .find(|amount| *amount < 0.0 || !amount.is_finite());
+            if let Some(amount) = invalid {
+                issue("escrow", AmortizationError::InvalidEscrow(amount));
+            } else if escrow.property_value <= 0.0 || !escrow.property_value.is_finite() {
+                issue("escrow", AmortizationError::InvalidEscrow(escrow.property_value));
+            }
+        }
+        if let Some(property) = self.property {
+            if property.value <= 0.0 || !property.value.is_finite() {
+                issue("property", AmortizationError::InvalidPropertyValue(property.value));
+            } else if property.annual_appreciation <= -100.0 || !property.annual_appreciation.is_finite() {
+                issue("property", AmortizationError::InvalidPropertyValue(property.annual_appreciation));
+            }
+        }
+        if let Some(cap) = self.payment_cap {
+            let recast_invalid = cap.recast_balance_percent.is_some_and(|p| p <= 100.0 || !p.is_finite());
+            if cap.max_change_percent < 0.0 || !cap.max_change_percent.is_finite() || recast_invalid {
+                issue("payment_cap", AmortizationError::InvalidPaymentCap(cap.max_change_percent));
+            }
+        }
+        if let DateRollConvention::DayOfMonth(day) | DateRollConvention::DayOfMonthOrLast(day) = self.date_roll {
+            if !(1..=31).contains(&day) {
+                issue("date_roll", AmortizationError::InvalidDueDay(day));
+            }
+        }
+        if let Some(inflation_rate) = self.inflation_rate {
+            if inflation_rate <= -100.0 || !inflation_rate.is_finite() {
+                issue("inflation_rate", AmortizationError::InvalidInterestRate(inflation_rate));
+            }
+        }
+        if let Some(fixed) = self.fixed_payment {
+            if fixed.amount <= 0.0 || !fixed.amount.is_finite() {
+                issue("fixed_payment", AmortizationError::InvalidPaymentAmount(fixed.amount));
+            }
+            if self.loan_type != LoanType::Amortizing {
+                issue("fixed_payment", AmortizationError::ConflictingOptions("fixed_payment", "loan_type"));
+            }
+            if self.graduated_payment.is_some() {
+                issue("fixed_payment", AmortizationError::ConflictingOptions("fixed_payment", "graduated_payment"));
+            }
+        }
+        if let Some(invalid) = self.prepayment_penalty.as_ref().and_then(PrepaymentPenalty::invalid_value) {
+            issue("prepayment_penalty", AmortizationError::InvalidPrepaymentPenalty(invalid));
+        }
+        if let Some(funding_date) = self.funding_date {
+            let first_due_date = self.start_date.and_then(|start| self.frequency.due_date(start, 0, self.date_roll));
+            if self.payment_timing == PaymentTiming::Advance {
+                issue("funding_date", AmortizationError::ConflictingOptions("funding_date", "payment_timing"));
+            } else if !is_valid_funding_date(funding_date, first_due_date) {
+                issue("funding_date", AmortizationError::InvalidFundingDate(funding_date));
+            }
+        }
+        if self.interest_calculator.is_some() && self.interest_method != InterestMethod::Actuarial {
+            issue("interest_calculator", AmortizationError::ConflictingOptions("interest_calculator", "interest_method"));
+        }
+        if self.interest_method != InterestMethod::Actuarial {
+            let standard = self.rate_schedule.len() <= 1
+                && self.extra_payment == 0.0
+                && self.prepayments.is_empty()
+                && self.loan_type == LoanType::Amortizing
+                && self.balloon_period.is_none()
+                && self.deferment.is_none()
+                && self.skip_payments.is_none()
+                && self.graduated_payment.is_none()
+                && self.fixed_payment.is_none()
+                && self.payment_timing == PaymentTiming::Arrears
+                && (self.funding_date.is_none() || !self.odd_days_treatment.charges_installments());
+            if !standard {
+                issue("interest_method", AmortizationError::UnsupportedInterestMethod(self.interest_method));
+            }
+        }
+        issues
+    }
 }
 
 impl Amortization {
     pub fn new(balance: f64, apr: f64, periods: u32, start_date: Option<NaiveDate>) -> Result<Self, AmortizationError>  {
-        if periods == 0 {
-            return Err(AmortizationError::InvalidPeriods(periods));
+        Self::from_config(CalculatorConfig::new(balance, apr, periods, start_date))
+    }
+
+    pub fn new_adjustable(balance: f64, rate_schedule: Vec<RatePeriod>, periods: u32, start_date: Option<NaiveDate>) -> Result<Self, AmortizationError> {
+        Self::from_config(adjustable_config(balance, rate_schedule, periods, start_date)?)
+    }
+
+    pub fn new_with_backend<N: AmortNum>(balance: f64, apr: f64, periods: u32, start_date: Option<NaiveDate>) -> Result<Self, AmortizationError> {
+        Self::from_config_with_backend::<N>(CalculatorConfig::new(balance, apr, periods, start_date))
+    }
+
+    pub fn adjustable_with_backend<N: AmortNum>(balance: f64, rate_schedule: Vec<RatePeriod>, periods: u32, start_date: Option<NaiveDate>) -> Result<Self, AmortizationError> {
+        Self::from_config_with_backend::<N>(adjustable_config(balance, rate_schedule, periods, start_date)?)
+    }
+
+    pub fn from_config(config: CalculatorConfig) -> Result<Self, AmortizationError> {
+        let mut amortization = Self::unscheduled(config)?;
+
+        amortization.periodic_payment = amortization.calculate_periodic_payment_amount()?;
+        amortization.schedule = amortization.calculate_schedule()?;
+        // A level installment rounded up to the cent can retire a small balance before the last
+        // period. The installment then drops a cent (the currency's minor unit) at a time so the
+        // term is kept and the final payment absorbs the difference, as long as a cent less still
+        // covers the interest and leaves a final payment within about one installment
+        while amortization.settles_early(amortization.schedule.len()) {
+            let higher = (amortization.periodic_payment, std::mem::take(&mut amortization.schedule), amortization.end_date);
+            let currency = amortization.config.currency;
+            amortization.periodic_payment = AmortNum::round_dp(amortization.periodic_payment - currency.minor_unit(), currency.minor_units());
+            amortization.schedule = amortization.calculate_schedule()?;
+            if !amortization.ends_within_level(amortization.schedule.last()) {
+                (amortization.periodic_payment, amortization.schedule, amortization.end_date) = higher;
+                break;
+            }
         }
-        if apr <= 0.0 {
-            return Err(AmortizationError::InvalidInterestRate(apr));
+        amortization.periodic_payment = amortization.repayment_payment_amount();
+        amortization.final_payment_amount = amortization.calculate_final_payment_amount();
+        amortization.refresh_totals()?;
+
+        Ok(amortization)
+    }
+
+    pub fn from_config_with_backend<N: AmortNum>(config: CalculatorConfig) -> Result<Self, AmortizationError> {
+        let mut amortization = Self::unscheduled(config)?;
+        // A rate the backend can't hold is refused once here rather than at each conversion
+        let config = &amortization.config;
+        if amortization.rate_schedule.iter().any(|r| N::checked_from_f64(config.rate_convention.nominal_apr(r.apr, config.frequency)).is_none()) {
+            return Err(AmortizationError::Overflow("interest rate"));
+        }
+
+        let rate = backend_periodic_rate::<N>(&amortization.rate_schedule[0], &amortization.config);
+        let payment = amortization.initial_payment(rate, N::from_f64(amortization.balance), amortization.config.payment_timing)?;
+        amortization.periodic_payment = payment.to_f64();
+
+        let (mut schedule, mut total_payment) = amortization.calculate_schedule_with::<N>()?;
+        amortization.schedule = schedule;
+        // Settled a cent at a time as in `from_config`
+        while amortization.settles_early(amortization.schedule.len()) {
+            let higher = (amortization.periodic_payment, std::mem::take(&mut amortization.schedule), total_payment, amortization.end_date);
+            let currency = amortization.config.currency;
+            amortization.periodic_payment = (N::from_f64(amortization.periodic_payment) - N::from_f64(currency.minor_unit())).round_dp(currency.minor_units()).to_f64();
+            (schedule, total_payment) = amortization.calculate_schedule_with::<N>()?;
+            amortization.schedule = schedule;
+            if !amortization.ends_within_level(amortization.schedule.last()) {
+                (amortization.periodic_payment, amortization.schedule, total_payment, amortization.end_date) = higher;
+                break;
+            }
         }
-        if balance <= 0.0 {
-            return Err(AmortizationError::InvalidLoanAmount(balance));
+        amortization.periodic_payment = amortization.repayment_payment_amount();
+        amortization.final_payment_amount = amortization.calculate_final_payment_amount();
+        amortization.total_payment = total_payment.to_f64();
+        amortization.total_interest = (total_payment - N::from_f64(amortization.balance) - N::from_f64(amortization.total_fees())).to_f64();
+
+        Ok(amortization)
+    }
+
+    pub(crate) fn unscheduled(config: CalculatorConfig) -> Result<Self, AmortizationError> {
+        if let Some(issue) = config.validate().into_iter().next() {
+            return Err(issue.error);
         }
-        let periodic_interest = apr / 100.0 / 12.0; 
+        let periods = config.total_periods();
+        let rate_schedule = config.resolved_rate_schedule();
+        let periodic_interest = rate_schedule[0].periodic_rate_with(config.frequency, config.rate_convention);
 
-        let mut amortization = Amortization {
-            balance,
+        Ok(Amortization {
+            balance: config.balance,
             periods,
             periodic_interest,
             periodic_payment: 0.0, 
             schedule: Vec::new(),  
             total_payment: 0.0,    
             total_interest: 0.0,   
-            start_date,
-            end_date: start_date,  
-        };
+            start_date: config.start_date,
+            end_date: config.start_date,  
+            final_payment_amount: 0.0,
+            rate_schedule,
+            config,
+            events: Vec::new(),
+        })
+    }
 
-        amortization.periodic_payment = amortization.calculate_periodic_payment_amount()?;
-        amortization.schedule = amortization.calculate_schedule()?;
-        amortization.total_payment = amortization.calculate_total_payment();
-        amortization.total_interest = amortization.calculate_total_interest();
+    // Due date of installment `installment_number`; installment 0 is the date the first
+    // period starts accruing
+    pub fn due_date(&self, installment_number: u32) -> Option<NaiveDate> {
+        let start_date = self.start_date?;
+        self.config.frequency.due_date(start_date, installment_number as i32 - 1, self.config.date_roll)
+    }
 
-        Ok(amortization)
+    // Due date moved onto a business day by the loan's business day convention
+    pub fn payment_date(&self, installment_number: u32) -> Option<NaiveDate> {
+        self.adjust_to_business_day(self.due_date(installment_number)?)
     }
 
-    pub fn calculate_periodic_payment_amount(&self) -> Result<f64, AmortizationError> {
-        let rate = self.periodic_interest;
-        let nper = self.periods as f64;
-        let pv = self.balance;
-
-        let base = 1.0 + rate;
-        let exp = base.powf(nper);
-        
-        if exp.is_infinite() || exp.is_nan() {
-            return Err(AmortizationError::CalculationError(
-                "Overflow in payment calculation".to_string()
-            ));
+    pub(crate) fn adjust_to_business_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let calendar: &dyn HolidayCalendar = self.config.holiday_calendar.as_deref().unwrap_or(&WeekendsOnly);
+        self.config.business_day_convention.adjust(date, calendar)
+    }
+
+    pub fn is_adjustable(&self) -> bool {
+        self.rate_schedule.len() > 1
+    }
+
+    // One entry per reset in the rate schedule, with the rate it called for and the rate charged
+    pub fn rate_adjustments(&self) -> Vec<RateAdjustment> {
+        let uncapped = self.config.uncapped_rate_schedule();
+        match &self.config.rate_caps {
+            Some(caps) => caps.apply(&uncapped),
+            None => uncapped.iter()
+                .map(|r| RateAdjustment { period: r.start_period, uncapped_apr: r.apr, apr: r.apr, limit: None })
+                .collect(),
         }
+    }
 
-        // Using the PMT formula: PMT = PV * (r * (1 + r)^n) / ((1 + r)^n - 1)
-        let payment = pv * (rate * exp) / (exp - 1.0);
+    // The adjustment in effect for each installment, numbered by installment
+    pub fn period_rates(&self) -> Vec<RateAdjustment> {
+        let adjustments = self.rate_adjustments();
+        self.schedule.iter()
+            .filter_map(|payment| {
+                let current = adjustments.iter().rev().find(|a| a.period <= payment.installment_number)?;
+                Some(RateAdjustment { period: payment.installment_number, ..*current })
+            })
+            .collect()
+    }
 
-        if payment.is_infinite() || payment.is_nan() {
-            return Err(AmortizationError::CalculationError(
-                "Invalid payment calculation result".to_string()
-            ));
+    // Resets at which a cap or the floor held the rate back
+    pub fn binding_caps(&self) -> Vec<RateAdjustment> {
+        self.rate_adjustments().into_iter().filter(RateAdjustment::is_capped).collect()
+    }
+
+    pub fn deferred_periods(&self) -> u32 {
+        self.config.deferment.map_or(0, |d| d.periods())
+    }
+
+    pub fn is_deferred(&self, installment_number: u32) -> bool {
+        installment_number <= self.deferred_periods()
+    }
+
+    pub fn is_interest_only(&self, installment_number: u32) -> bool {
+        !self.is_deferred(installment_number)
+            && installment_number <= self.deferred_periods() + self.config.loan_type.interest_only_periods()
+    }
+
+    pub fn amortizing_periods(&self) -> u32 {
+        self.periods - self.deferred_periods() - self.config.loan_type.interest_only_periods()
+    }
+
+    pub fn is_skipped(&self, installment_number: u32, date: Option<NaiveDate>) -> bool {
+        self.config.skip_payments.as_ref().is_some_and(|skip| skip.is_skipped(installment_number, date))
+    }
+
+    pub fn skipped_installments(&self) -> Vec<u32> {
+        self.schedule.iter().filter(|p| p.skipped).map(|p| p.installment_number).collect()
+    }
+
+    // Periods added beyond the original term by skipped payments
+    pub fn term_extension(&self) -> u32 {
+        (self.schedule.len() as u32).saturating_sub(self.last_period())
+    }
+
+    // Additional interest paid over the life of the loan compared with making every payment
+    pub fn skip_interest_cost(&self) -> Result<f64, AmortizationError> {
+        if self.config.skip_payments.is_none() {
+            return Ok(0.0);
+        }
+        let baseline = Amortization::from_config(CalculatorConfig {
+            skip_payments: None,
+            ..self.config.clone()
+        })?;
+        Ok(self.total_interest - baseline.total_interest)
+    }
+
+    // Balance once deferment ends and any accrued interest has been capitalized
+    pub fn repayment_balance(&self) -> f64 {
+        match self.deferred_periods() {
+            0 => self.balance,
+            deferred => self.schedule.get(deferred as usize - 1).map_or(self.balance, |p| p.remaining_balance),
         }
-        
-        Ok((payment * 100.0).round() / 100.0)
+    }
+
+    pub fn balloon_amount(&self) -> Option<f64> {
+        self.config.balloon_period
+            .and_then(|_| self.schedule.last())
+            .map(|p| p.balloon_amount)
+    }
+
+    pub fn peak_balance(&self) -> f64 {
+        self.schedule.iter()
+            .map(|p| p.beginning_balance.max(p.remaining_balance))
+            .fold(self.balance, f64::max)
+    }
+
+    pub fn total_capitalized_interest(&self) -> f64 {
+        self.schedule.iter().map(|p| p.capitalized_interest).sum()
+    }
+
+    pub fn is_negatively_amortizing(&self) -> bool {
+        self.schedule.iter().any(|p| p.capitalized_interest > 0.0)
+    }
+
+    pub fn total_escrow(&self) -> f64 {
+        self.schedule.iter().map(|p| p.escrow_amount()).sum()
+    }
+
+    pub fn total_mortgage_insurance(&self) -> f64 {
+        self.schedule.iter().filter_map(|p| p.escrow).map(|escrow| escrow.mortgage_insurance).sum()
+    }
+
+    // First installment in which mortgage insurance is no longer charged
+    pub fn mortgage_insurance_drop_off(&self) -> Option<u32> {
+        self.config.escrow?.mortgage_insurance?;
+        self.schedule.iter()
+            .find(|p| p.escrow.is_some_and(|escrow| escrow.mortgage_insurance == 0.0))
+            .map(|p| p.installment_number)
+    }
+
+    pub fn initial_ltv(&self) -> Option<f64> {
+        self.config.property.map(|property| self.balance / property.value * 100.0)
+    }
+
+    // First installment after which the loan-to-value ratio is at or below `threshold` percent
+    pub fn ltv_crossing(&self, threshold: f64) -> Option<u32> {
+        self.schedule.iter()
+            .find(|p| p.ltv.is_some_and(|ltv| ltv <= threshold))
+            .map(|p| p.installment_number)
+    }
+
+    // Full first-period payment including escrow
+    pub fn periodic_piti(&self) -> f64 {
+        let first_escrow = self.schedule.iter().find_map(|p| p.escrow).map_or(0.0, |escrow| escrow.total());
+        self.periodic_payment + first_escrow
+    }
+
+    pub fn total_piti(&self) -> f64 {
+        self.total_payment + self.total_escrow()
+    }
+
+    pub fn calculate_periodic_payment_amount(&self) -> Result<f64, AmortizationError> {
+        self.initial_payment(self.periodic_interest, self.balance, self.config.payment_timing)
     }
     
+    // Whether a level loan paid off in `installments` would still settle a cent lower
+    pub(crate) fn settles_early(&self, installments: usize) -> bool {
+        let level = self.config.extra_payment == 0.0
+            && self.config.prepayments.is_empty()
+            && self.config.loan_type != LoanType::EqualPrincipal
+            && self.config.graduated_payment.is_none()
+            && self.config.fixed_payment.is_none()
+            && self.config.skip_payments.is_none()
+            && self.config.interest_method == InterestMethod::Actuarial
+            && self.config.interest_calculator.is_none()
+            && self.rate_schedule.len() == 1;
+        level
+            && covers_interest(self.periodic_payment - self.config.currency.minor_unit(), self.periodic_interest, self.balance, self.config.payment_timing)
+            && (installments as u32) < self.last_period()
+    }
+
+    // Whether the last installment of a schedule at the current payment stays within about one
+    // level installment, i.e. retires no more principal than a level installment would
+    pub(crate) fn final_within_level(&self, amount: f64, interest: f64) -> bool {
+        amount <= self.periodic_payment + interest + self.config.currency.minor_unit() / 2.0
+    }
+
+    fn ends_within_level(&self, last: Option<&Payment>) -> bool {
+        last.is_none_or(|p| self.final_within_level(p.installment_amount - p.fees, p.interest))
+    }
+
+    // Installment of the first amortizing period, which differs from the payment on the
+    // original balance when interest was capitalized during deferment
+    fn repayment_payment_amount(&self) -> f64 {
+        let first_amortizing = self.periods - self.amortizing_periods();
+        match self.config.deferment {
+            Some(_) => self.schedule.get(first_amortizing as usize).map_or(self.periodic_payment, |p| p.installment_amount - p.fees),
+            None => self.periodic_payment,
+        }
+    }
+
+    // Installment of the first amortizing period, level unless the plan is graduated
+    pub(crate) fn initial_payment<N: AmortNum>(&self, rate: N, balance: N, timing: PaymentTiming) -> Result<N, AmortizationError> {
+        let currency = self.config.currency;
+        if let Some(fixed) = self.config.fixed_payment {
+            return Ok(N::from_f64(fixed.amount).round_dp(currency.minor_units()));
+        }
+        let periods = self.amortizing_periods();
+        if self.config.loan_type == LoanType::EqualPrincipal {
+            let interest = match timing {
+                PaymentTiming::Arrears => (balance * rate).round_dp(currency.minor_units()),
+                PaymentTiming::Advance => N::zero(),
+            };
+            return Ok(equal_principal_portion(balance, periods, currency) + interest);
+        }
+        if self.config.interest_method == InterestMethod::Flat {
+            let n = N::from_f64(periods as f64);
+            return Ok((balance * (N::one() + rate * n) / n).round_dp(currency.minor_units()));
+        }
+        match self.config.graduated_payment {
+            Some(graduated) => graduated_periodic_payment(rate, periods, balance, graduated, self.config.frequency.periods_per_year(), timing, currency),
+            None => timed_periodic_payment(rate, periods, balance, timing, currency),
+        }
+    }
+
+    pub fn calculate_final_payment_amount(&self) -> f64 {
+        self.schedule.last().map_or(0.0, |p| p.installment_amount)
+    }
+
     pub fn calculate_total_payment(&self) -> f64 {
-        self.periods as f64 * self.periodic_payment
+        self.total_payment_money().to_f64()
+    }
+
+    // Sums the installments once for both totals. Once this has succeeded the other sums of
+    // the schedule, which are no larger, fit too
+    pub(crate) fn refresh_totals(&mut self) -> Result<(), AmortizationError> {
+        let total_payment = self.checked_total_payment_money().ok_or(AmortizationError::Overflow("total payment"))?;
+        self.total_payment = total_payment.to_f64();
+        self.total_interest = (total_payment - self.money(self.balance) - self.total_fees_money()).to_f64();
+        Ok(())
     }
 
     pub fn calculate_total_interest(&self) -> f64 {
-        self.total_payment - self.balance
+        self.total_interest_money().to_f64()
     }
 
-    pub fn calculate_payment(&self, balance: f64, installment_number: u32, beginning_balance: f64) ->  Result<Payment, AmortizationError> {
-        let interest = balance * self.periodic_interest;
+    pub fn money(&self, amount: f64) -> Money {
+        Money::from_f64_with(amount, self.config.currency, self.config.rounding.mode)
+    }
 
-        if interest.is_nan() || interest.is_infinite() {
-            return Err(AmortizationError::CalculationError(
-                "Invalid interest calculation".to_string()
-            ));
-        }
+    // Installments are rounded to minor units one by one and summed as integers. Panics when
+    // the sum overflows, which `build()` rules out for the loans it returns
+    pub fn total_payment_money(&self) -> Money {
+        self.checked_total_payment_money().expect("total payment overflows the currency's minor units")
+    }
 
-        let principal = if balance < self.periodic_payment {
-            balance
-        } else {
-            self.periodic_payment - interest
-        };
+    // None when an installment or the sum is beyond the range of `i64` minor units
+    pub fn checked_total_payment_money(&self) -> Option<Money> {
+        let currency = self.config.currency;
+        let installments = self.schedule.iter()
+            .map(|p| Money::checked_from_f64_with(p.installment_amount, currency, self.config.rounding.mode))
+            .collect::<Option<Vec<_>>>()?;
+        Money::checked_total(currency, installments)
+    }
 
-        if principal.is_nan() || principal.is_infinite() {
-            return Err(AmortizationError::CalculationError(
-                "Invalid principal calculation".to_string()
-            ));
-        }
+    // Fees are paid but never interest. Panics in the same cases as `total_payment_money`
+    pub fn total_interest_money(&self) -> Money {
+        self.checked_total_interest_money().expect("total interest overflows the currency's minor units")
+    }
 
-        let remaining_balance = if balance < self.periodic_payment {
-            0.0 
-        } else {
-            balance - principal
-        };
-        let ending_balance = beginning_balance - principal;
+    pub fn checked_total_interest_money(&self) -> Option<Money> {
+        let fees = Money::checked_total(self.config.currency, self.schedule.iter().map(|p| self.money(p.fees)))?;
+        self.checked_total_payment_money()?.checked_sub(self.money(self.balance))?.checked_sub(fees)
+    }
+
+    pub fn money_schedule(&self) -> Vec<PaymentAmounts> {
+        self.schedule.iter().map(|p| p.in_currency(self.config.currency, self.config.rounding.mode)).collect()
+    }
+
+    pub fn calculate_payment(&self, balance: f64, installment_number: u32) ->  Result<Payment, AmortizationError> {
+        let payments = self.payments();
+        let row = payments.schedule_row(balance, installment_number, self.periodic_interest)?;
+        // A shortfall is capitalized straight away
+        let remaining_balance = balance - row.principal + row.unpaid;
 
         Ok(Payment {
             installment_number,
-            beginning_balance,
-            ending_balance,
-            installment_amount: self.periodic_payment,
-            interest,
-            principal,
+            beginning_balance: balance,
+            installment_amount: row.amount,
+            interest: row.interest,
+            principal: row.principal,
             remaining_balance,
             date: None, 
+            balloon_amount: row.balloon,
+            capitalized_interest: row.unpaid,
+            accrued_interest: 0.0,
+            deferred_amount: 0.0,
+            fees: 0.0,
+            skipped: false,
+            escrow: None,
+            ltv: None,
+            real: None,
+            accrual: None,
         })
     }
 
+    pub(crate) fn last_period(&self) -> u32 {
+        self.config.balloon_period.unwrap_or(self.periods)
+    }
+
+    // Interest is rounded each period only when the rounding policy asks for it
+    pub fn payments(&self) -> Payments<'_> {
+        let rounding = self.config.rounding;
+        Payments::new(self, rounding.per_period.then_some(rounding.mode))
+    }
+
+    // Same as `payments` but computed in backend `N`, rounding every period's interest
+    // to cents so the final installment absorbs any residual
+    pub fn payments_with<N: AmortNum>(&self) -> Payments<'_, N> {
+        Payments::new(self, Some(self.config.rounding.mode))
+    }
+
     pub fn calculate_schedule(&mut self) -> Result<Vec<Payment>, AmortizationError> {
-        let mut balance = self.balance;
-        let mut schedule = Vec::new();
-        let mut current_date = self.start_date;
-        let mut installment_number = 1;
-        let mut beginning_balance = self.balance;
-        
-        while balance > 0.0 {
-            let mut payment = self.calculate_payment(balance, installment_number, beginning_balance)?;
-            balance = payment.remaining_balance;
-            installment_number += 1;
+        let (schedule, _, end_date) = collect_schedule(self.payments())?;
+        self.end_date = end_date;
+        Ok(schedule)
+    }
 
-            if let Some(ref mut end_date) = current_date {
-                payment.date = Some(*end_date);
-                *end_date = end_date.checked_add_months(chrono::Months::new(1))
-                    .ok_or_else(|| AmortizationError::CalculationError(
-                        "Invalid date calculation".to_string()
-                    ))?;
-            }
+    pub fn calculate_schedule_with<N: AmortNum>(&mut self) -> Result<(Vec<Payment>, N), AmortizationError> {
+        let (schedule, total_payment, end_date) = collect_schedule(self.payments_with::<N>())?;
+        self.end_date = end_date;
+        Ok((schedule, total_payment))
+    }
+}
 
-            schedule.push(payment.clone());
+type CollectedSchedule<N> = (Vec<Payment>, N, Option<NaiveDate>);
+
+fn collect_schedule<N: AmortNum>(mut payments: Payments<'_, N>) -> Result<CollectedSchedule<N>, AmortizationError> {
+    let mut schedule = Vec::with_capacity(payments.size_hint().1.unwrap_or(0));
+    while let Some(payment) = payments.try_next()? {
+        schedule.push(payment);
+    }
+    Ok((schedule, payments.total_payment(), payments.next_date()))
+}
+
+fn adjustable_config(balance: f64, rate_schedule: Vec<RatePeriod>, periods: u32, start_date: Option<NaiveDate>) -> Result<CalculatorConfig, AmortizationError> {
+    let apr = rate_schedule.first().map(|r| r.apr).ok_or_else(|| AmortizationError::InvalidRateSchedule(
+        "Rate schedule must contain at least one rate period".to_string()
+    ))?;
+    Ok(CalculatorConfig {
+        rate_schedule,
+        ..CalculatorConfig::new(balance, apr, periods, start_date)
+    })
+}
 
-            beginning_balance = beginning_balance - payment.principal;
+pub(crate) fn backend_periodic_rate<N: AmortNum>(rate_period: &RatePeriod, config: &CalculatorConfig) -> N {
+    let apr = config.rate_convention.nominal_apr(rate_period.apr, config.frequency);
+    N::from_f64(apr) / N::from_f64(100.0 * config.frequency.periods_per_year() as f64)
+}
+
+fn validate_rate_schedule(rate_schedule: &[RatePeriod], periods: u32) -> Result<(), AmortizationError> {
+    let first = rate_schedule.first().ok_or_else(|| AmortizationError::InvalidRateSchedule(
+        "Rate schedule must contain at least one rate period".to_string()
+    ))?;
+    if first.start_period != 1 {
+        return Err(AmortizationError::InvalidRateSchedule(
+            format!("First rate period must start at period 1, got {}", first.start_period)
+        ));
+    }
+    for window in rate_schedule.windows(2) {
+        if window[1].start_period <= window[0].start_period {
+            return Err(AmortizationError::InvalidRateSchedule(
+                format!("Rate periods must be in increasing order, got {} after {}", window[1].start_period, window[0].start_period)
+            ));
+        }
+    }
+    for rate_period in rate_schedule {
+        if rate_period.apr < 0.0 || !rate_period.apr.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(rate_period.apr));
+        }
+        if rate_period.start_period > periods {
+            return Err(AmortizationError::InvalidRateSchedule(
+                format!("Rate period starts at {} but the loan only has {} periods", rate_period.start_period, periods)
+            ));
         }
+    }
+    Ok(())
+}
 
-        self.end_date = current_date;
-        Ok(schedule)
+// Payments recalculated partway through a loan are always in arrears: the next installment
+// pays the interest accrued since the previous one, whatever the loan's timing
+pub(crate) fn periodic_payment<N: AmortNum>(rate: N, periods: u32, balance: N, currency: Currency) -> Result<N, AmortizationError> {
+    timed_periodic_payment(rate, periods, balance, PaymentTiming::Arrears, currency)
+}
+
+// Rounded to the currency's minor units
+pub(crate) fn timed_periodic_payment<N: AmortNum>(rate: N, periods: u32, balance: N, timing: PaymentTiming, currency: Currency) -> Result<N, AmortizationError> {
+    let payment = N::zero() - tvm::pmt(rate, periods, balance, N::zero(), timing)?;
+
+    // Past `max_amount` a cent less is the same f64, so settling the installment would never end
+    if !payment.to_f64().is_finite() || payment.to_f64().abs() > currency.max_amount() {
+        return Err(AmortizationError::Overflow("periodic payment"));
     }
-}
\ No newline at end of file
+
+    // Rounding down past the interest would leave the balance growing
+    let rounded = payment.round_dp(currency.minor_units());
+    if covers_interest(payment, rate, balance, timing) && !covers_interest(rounded, rate, balance, timing) {
+        return Ok((rounded + N::from_f64(currency.minor_unit())).round_dp(currency.minor_units()));
+    }
+    Ok(rounded)
+}
+
+// Whether a level installment pays down the balance instead of only its interest. Paid in
+// advance, the first installment comes off the balance before any interest accrues
+pub(crate) fn covers_interest<N: AmortNum>(payment: N, rate: N, balance: N, timing: PaymentTiming) -> bool {
+    match timing {
+        PaymentTiming::Arrears => payment > balance * rate,
+        PaymentTiming::Advance => payment * (N::one() + rate) > balance * rate,
+    }
+}
+
+pub(crate) fn equal_principal_portion<N: AmortNum>(balance: N, periods: u32, currency: Currency) -> N {
+    (balance / N::from_f64(periods as f64)).round_dp(currency.minor_units())
+}
+
+// Initial installment of a graduated plan: the balance divided by the present value of the
+// stepped payment stream per unit of initial payment
+pub(crate) fn graduated_periodic_payment<N: AmortNum>(rate: N, periods: u32, balance: N, graduated: GraduatedPayment, periods_per_year: u32, timing: PaymentTiming, currency: Currency) -> Result<N, AmortizationError> {
+    let discount = N::one() / (N::one() + rate);
+    let mut factor = match timing {
+        PaymentTiming::Arrears => discount,
+        PaymentTiming::Advance => N::one(),
+    };
+    let mut annuity = N::zero();
+    for period in 0..periods {
+        annuity = annuity + N::from_f64(graduated.step_factor(period, periods_per_year)) * factor;
+        factor = factor * discount;
+    }
+
+    let payment = balance / annuity;
+    if !payment.to_f64().is_finite() {
+        return Err(AmortizationError::Overflow("periodic payment"));
+    }
+    Ok(payment.round_dp(currency.minor_units()))
+}
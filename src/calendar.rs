@@ -0,0 +1,174 @@
+use std::fmt;
+use chrono::{Datelike, Months, NaiveDate, Weekday};
+
+// How monthly due dates are placed when the start day doesn't exist in every month. Each
+// date is derived from the start date rather than the previous installment, so a short
+// month never drags the rest of the schedule earlier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateRollConvention {
+    // The start date's day of month, or the last day of months too short for it
+    #[default]
+    SameDay,
+    // A start date on the last day of its month keeps every due date on the month end
+    EndOfMonth,
+    // Always the given day; where a month is too short the date rolls forward to the 1st
+    // of the next month. The start date only selects the month of the first installment
+    DayOfMonth(u32),
+    // Always the given day, or the last day of months too short for it
+    DayOfMonthOrLast(u32),
+}
+
+fn month_offset(date: NaiveDate, months: i32) -> Option<NaiveDate> {
+    let first = date.with_day(1)?;
+    if months >= 0 {
+        first.checked_add_months(Months::new(months as u32))
+    } else {
+        first.checked_sub_months(Months::new(months.unsigned_abs()))
+    }
+}
+
+fn last_day_of_month(first: NaiveDate) -> Option<NaiveDate> {
+    first.checked_add_months(Months::new(1))?.pred_opt()
+}
+
+pub(crate) fn is_month_end(date: NaiveDate) -> bool {
+    date.succ_opt().is_some_and(|next| next.day() == 1)
+}
+
+impl DateRollConvention {
+    // Due date `months` calendar months after the start date's month
+    pub fn roll(&self, start: NaiveDate, months: i32) -> Option<NaiveDate> {
+        let first = month_offset(start, months)?;
+        let last = last_day_of_month(first)?;
+        let day = match *self {
+            DateRollConvention::EndOfMonth if is_month_end(start) => return Some(last),
+            DateRollConvention::DayOfMonth(day) if day > last.day() => return last.succ_opt(),
+            DateRollConvention::SameDay | DateRollConvention::EndOfMonth => start.day(),
+            DateRollConvention::DayOfMonth(day) | DateRollConvention::DayOfMonthOrLast(day) => day,
+        };
+        first.with_day(day.min(last.day()))
+    }
+
+    // The same convention with the start day made explicit, so a schedule can be continued
+    // from a later due date without losing the original day of month
+    pub(crate) fn anchored(&self, start: NaiveDate) -> DateRollConvention {
+        match *self {
+            DateRollConvention::SameDay => DateRollConvention::DayOfMonthOrLast(start.day()),
+            DateRollConvention::EndOfMonth if !is_month_end(start) => DateRollConvention::DayOfMonthOrLast(start.day()),
+            other => other,
+        }
+    }
+}
+
+// Decides which dates besides weekends are not business days
+pub trait HolidayCalendar: fmt::Debug + Send + Sync {
+    fn is_holiday(&self, date: NaiveDate) -> bool;
+
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.is_holiday(date)
+    }
+}
+
+// An explicit list of holiday dates
+impl HolidayCalendar for Vec<NaiveDate> {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.contains(&date)
+    }
+}
+
+// Weekends are the only non-business days
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WeekendsOnly;
+
+impl HolidayCalendar for WeekendsOnly {
+    fn is_holiday(&self, _date: NaiveDate) -> bool {
+        false
+    }
+}
+
+// Federal Reserve holidays, when ACH and Fedwire don't settle. A holiday falling on a Sunday
+// is observed the following Monday; one falling on a Saturday is not observed at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FederalReserveHolidays;
+
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: u8) -> Option<NaiveDate> {
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, n)
+}
+
+fn last_weekday(year: i32, month: u32, weekday: Weekday) -> Option<NaiveDate> {
+    let mut date = last_day_of_month(NaiveDate::from_ymd_opt(year, month, 1)?)?;
+    while date.weekday() != weekday {
+        date = date.pred_opt()?;
+    }
+    Some(date)
+}
+
+impl HolidayCalendar for FederalReserveHolidays {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        let year = date.year();
+        let fixed = |month, day| NaiveDate::from_ymd_opt(year, month, day).map(|holiday| {
+            if holiday.weekday() == Weekday::Sun { holiday.succ_opt() } else { Some(holiday) }
+        });
+        let mut holidays = vec![
+            fixed(1, 1).flatten(),
+            nth_weekday(year, 1, Weekday::Mon, 3),
+            nth_weekday(year, 2, Weekday::Mon, 3),
+            last_weekday(year, 5, Weekday::Mon),
+            fixed(7, 4).flatten(),
+            nth_weekday(year, 9, Weekday::Mon, 1),
+            nth_weekday(year, 10, Weekday::Mon, 2),
+            fixed(11, 11).flatten(),
+            nth_weekday(year, 11, Weekday::Thu, 4),
+            fixed(12, 25).flatten(),
+        ];
+        if year >= 2022 {
+            holidays.push(fixed(6, 19).flatten());
+        }
+        holidays.contains(&Some(date))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BusinessDayConvention {
+    // Payments fall on the scheduled date even on weekends and holidays
+    #[default]
+    Unadjusted,
+    // The next business day
+    Following,
+    // The next business day, unless that is in the next month, then the previous one
+    ModifiedFollowing,
+    // The previous business day
+    Preceding,
+}
+
+// Give up looking for a business day after a year of holidays
+const MAX_ADJUSTMENT_DAYS: u32 = 366;
+
+fn roll_to_business_day(date: NaiveDate, calendar: &dyn HolidayCalendar, forward: bool) -> Option<NaiveDate> {
+    let mut adjusted = date;
+    for _ in 0..MAX_ADJUSTMENT_DAYS {
+        if calendar.is_business_day(adjusted) {
+            return Some(adjusted);
+        }
+        adjusted = if forward { adjusted.succ_opt()? } else { adjusted.pred_opt()? };
+    }
+    None
+}
+
+impl BusinessDayConvention {
+    // Moves `date` onto a business day of `calendar`; None if none is found within a year
+    pub fn adjust(&self, date: NaiveDate, calendar: &dyn HolidayCalendar) -> Option<NaiveDate> {
+        match self {
+            BusinessDayConvention::Unadjusted => Some(date),
+            BusinessDayConvention::Following => roll_to_business_day(date, calendar, true),
+            BusinessDayConvention::ModifiedFollowing => {
+                let following = roll_to_business_day(date, calendar, true);
+                match following {
+                    Some(following) if following.month() == date.month() => Some(following),
+                    _ => roll_to_business_day(date, calendar, false),
+                }
+            }
+            BusinessDayConvention::Preceding => roll_to_business_day(date, calendar, false),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// Discounted cash flow functions. Like the spreadsheet functions they mirror, `npv` discounts
+// the first cash flow by one full period, while `irr` and the date-aware functions treat the
+// first cash flow as occurring at time zero
+use chrono::NaiveDate;
+use crate::error::AmortizationError;
+use crate::solver;
+
+// Rates are per period, so anything below -100% is meaningless
+const MIN_RATE: f64 = -0.999_999;
+const MAX_RATE: f64 = 1e6;
+
+pub fn npv(rate: f64, cash_flows: &[f64]) -> f64 {
+    let mut discount = 1.0;
+    cash_flows.iter().map(|flow| {
+        discount /= 1.0 + rate;
+        flow * discount
+    }).sum()
+}
+
+fn present_value_at_zero(rate: f64, cash_flows: &[f64]) -> f64 {
+    npv(rate, cash_flows) * (1.0 + rate)
+}
+
+pub fn irr(cash_flows: &[f64]) -> Result<f64, AmortizationError> {
+    check_sign_change(cash_flows.iter().copied())?;
+    if sign_changes(cash_flows.iter().copied()) > 1 {
+        return solve_nearest_rate(|rate| present_value_at_zero(rate, cash_flows));
+    }
+    solve_rate(|rate| present_value_at_zero(rate, cash_flows))
+}
+
+fn year_fraction(from: NaiveDate, to: NaiveDate) -> f64 {
+    (to - from).num_days() as f64 / 365.0
+}
+
+pub fn xnpv(rate: f64, cash_flows: &[(NaiveDate, f64)]) -> Result<f64, AmortizationError> {
+    let (first_date, _) = cash_flows.first().ok_or(AmortizationError::MissingParameter("cash_flows"))?;
+    if rate <= -1.0 {
+        return Err(AmortizationError::InvalidInterestRate(rate));
+    }
+    Ok(cash_flows.iter()
+        .map(|&(date, flow)| flow / (1.0 + rate).powf(year_fraction(*first_date, date)))
+        .sum())
+}
+
+// Annual rate at which the dated cash flows have zero net present value
+pub fn xirr(cash_flows: &[(NaiveDate, f64)]) -> Result<f64, AmortizationError> {
+    check_sign_change(cash_flows.iter().map(|&(_, flow)| flow))?;
+    let first_date = cash_flows[0].0;
+    let net_present_value = |rate: f64| cash_flows.iter()
+        .map(|&(date, flow)| flow / (1.0 + rate).powf(year_fraction(first_date, date)))
+        .sum();
+    if sign_changes(cash_flows.iter().map(|&(_, flow)| flow)) > 1 {
+        return solve_nearest_rate(net_present_value);
+    }
+    solve_rate(net_present_value)
+}
+
+pub(crate) fn check_sign_change(mut flows: impl Iterator<Item = f64> + Clone) -> Result<(), AmortizationError> {
+    let has_positive = flows.clone().any(|flow| flow > 0.0);
+    let has_negative = flows.any(|flow| flow < 0.0);
+    if has_positive && has_negative {
+        Ok(())
+    } else {
+        Err(AmortizationError::NoSolution(
+            "Cash flows need at least one positive and one negative value".to_string()
+        ))
+    }
+}
+
+fn sign_changes(flows: impl Iterator<Item = f64>) -> usize {
+    let signs: Vec<f64> = flows.filter(|&flow| flow != 0.0).map(f64::signum).collect();
+    signs.windows(2).filter(|pair| pair[0] != pair[1]).count()
+}
+
+// Cash flows that change sign more than once can have a rate for each change, so this steps
+// outwards from 0% on both sides, doubling the step in ln(1 + rate) each time, and bisects
+// the first bracket found, i.e. returns the rate nearest 0%
+fn solve_nearest_rate(net_present_value: impl Fn(f64) -> f64) -> Result<f64, AmortizationError> {
+    let mut above = 0.0;
+    let mut below = 0.0;
+    let mut step: f64 = 0.01;
+    while step <= MAX_RATE.ln_1p() {
+        for (inner, outer) in [(&mut above, step.exp_m1()), (&mut below, (-step).exp_m1())] {
+            let (inner_value, outer_value) = (net_present_value(*inner), net_present_value(outer));
+            if inner_value.is_finite() && outer_value.is_finite() && inner_value * outer_value <= 0.0 {
+                return solver::goal_seek(|rate| Ok(net_present_value(rate)), 0.0, (*inner, outer));
+            }
+            *inner = outer;
+        }
+        step *= 2.0;
+    }
+    Err(AmortizationError::NoSolution(
+        "Cash flows change sign more than once and no rate gives them zero net present value".to_string()
+    ))
+}
+
+// Bisection on the net present value. The bracket starts at [0%, 100%] and widens towards
+// whichever end is closer to a root until the values on either side differ in sign, then
+// `goal_seek` narrows it
+pub(crate) fn solve_rate(net_present_value: impl Fn(f64) -> f64) -> Result<f64, AmortizationError> {
+    let mut low = 0.0;
+    let mut high = 1.0;
+    let mut low_value = net_present_value(low);
+    let mut high_value = net_present_value(high);
+    while low_value.signum() == high_value.signum() {
+        if high_value.abs() < low_value.abs() {
+            high *= 2.0;
+            high_value = net_present_value(high);
+        } else {
+            low = (low - 1.0) / 2.0;
+            low_value = net_present_value(low);
+        }
+        if high > MAX_RATE || low < MIN_RATE || !low_value.is_finite() || !high_value.is_finite() {
+            return Err(AmortizationError::NoSolution(
+                "Cash flows imply an unbounded rate".to_string()
+            ));
+        }
+    }
+    solver::goal_seek(|rate| Ok(net_present_value(rate)), 0.0, (low, high))
+}
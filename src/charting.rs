@@ -0,0 +1,89 @@
+// Ready-to-plot series keyed by installment number, plus a dependency-free SVG line chart
+use std::fmt::Write;
+use crate::calculator::Amortization;
+use crate::payment::Payment;
+
+const SERIES_COLORS: [&str; 3] = ["#1f77b4", "#d62728", "#2ca02c"];
+const MARGIN: f64 = 40.0;
+
+impl Amortization {
+    // Outstanding balance, starting from the original amount at installment 0
+    pub fn balance_series(&self) -> Vec<(f64, f64)> {
+        let mut series = vec![(0.0, self.balance)];
+        series.extend(self.schedule.iter().map(|p| (p.installment_number as f64, p.remaining_balance)));
+        series
+    }
+
+    pub fn cumulative_interest_series(&self) -> Vec<(f64, f64)> {
+        self.cumulative_series(|p| p.interest)
+    }
+
+    pub fn cumulative_principal_series(&self) -> Vec<(f64, f64)> {
+        self.cumulative_series(|p| p.principal)
+    }
+
+    pub fn interest_series(&self) -> Vec<(f64, f64)> {
+        self.schedule.iter().map(|p| (p.installment_number as f64, p.interest)).collect()
+    }
+
+    pub fn principal_series(&self) -> Vec<(f64, f64)> {
+        self.schedule.iter().map(|p| (p.installment_number as f64, p.principal)).collect()
+    }
+
+    fn cumulative_series(&self, amount: impl Fn(&Payment) -> f64) -> Vec<(f64, f64)> {
+        let mut total = 0.0;
+        self.schedule.iter().map(|p| {
+            total += amount(p);
+            (p.installment_number as f64, total)
+        }).collect()
+    }
+
+    // Balance, cumulative interest and cumulative principal on shared axes
+    pub fn to_svg_chart(&self, width: u32, height: u32) -> String {
+        let series = [
+            ("Balance", self.balance_series()),
+            ("Cumulative interest", self.cumulative_interest_series()),
+            ("Cumulative principal", self.cumulative_principal_series()),
+        ];
+        svg_line_chart(&series, width, height)
+    }
+}
+
+pub fn svg_line_chart(series: &[(&str, Vec<(f64, f64)>)], width: u32, height: u32) -> String {
+    let points = series.iter().flat_map(|(_, points)| points.iter());
+    let max_x = points.clone().map(|&(x, _)| x).fold(1.0, f64::max);
+    let max_y = points.map(|&(_, y)| y).fold(1.0, f64::max);
+    let (width, height) = (width as f64, height as f64);
+    let plot_width = (width - 2.0 * MARGIN).max(1.0);
+    let plot_height = (height - 2.0 * MARGIN).max(1.0);
+    let scale = |(x, y): (f64, f64)| (MARGIN + x / max_x * plot_width, height - MARGIN - y / max_y * plot_height);
+
+    let mut svg = String::new();
+    let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#, width, height, width, height);
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+    let _ = writeln!(
+        svg,
+        r#"<path d="M{m} {m} V{b} H{r}" fill="none" stroke="black"/>"#,
+        m = MARGIN, b = height - MARGIN, r = width - MARGIN
+    );
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" font-size="10">{:.2}</text>"#, 2.0, MARGIN - 5.0, max_y);
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" font-size="10" text-anchor="end">{}</text>"#, width - MARGIN, height - MARGIN + 15.0, max_x);
+
+    for (index, (label, points)) in series.iter().enumerate() {
+        let color = SERIES_COLORS[index % SERIES_COLORS.len()];
+        let path: Vec<String> = points.iter()
+            .map(|&point| {
+                let (x, y) = scale(point);
+                format!("{:.1},{:.1}", x, y)
+            })
+            .collect();
+        let _ = writeln!(svg, r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="1.5"/>"#, path.join(" "), color);
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="10" fill="{}">{}</text>"#,
+            MARGIN + 5.0, MARGIN + 12.0 * (index as f64 + 1.0), color, label
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -0,0 +1,280 @@
+// Argument handling for the `amortize` binary, kept in the library so it can be tested
+use std::fmt;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use chrono::NaiveDate;
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::compare::LoanComparison;
+use crate::error::AmortizationError;
+#[cfg(feature = "i18n")]
+use crate::i18n::Language;
+use crate::json::JsonValue;
+use crate::loan_type::FixedPayment;
+use crate::refinance::Refinance;
+#[cfg(feature = "i18n")]
+use crate::table::TableOptions;
+use crate::units::Rate;
+
+pub const USAGE: &str = "\
+Usage: amortize <schedule|compare|payoff> [options]
+
+Options:
+  --config <path>           TOML or JSON file defining the loans, instead of the options below
+  --principal <amount>      Loan amount
+  --apr <percent>           Annual rate; repeat to compare several loans
+  --term <months>           Term in months; repeat to compare several loans
+  --start-date <YYYY-MM-DD> Date of the first payment
+  --extra-payment <amount>  Extra principal paid every period
+  --payment <amount>        Fixed installment; the term follows from it when --term is left out
+  --period <n>              Payments made before the payoff quote (payoff only)
+  --format <csv|json|table|ics> Output format, table by default; ics (schedule only) needs --start-date
+  --lang <code>             Language of table headers: en, es, fr, de or ur (i18n builds)";
+
+#[derive(Debug)]
+pub struct CliError(pub String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<AmortizationError> for CliError {
+    fn from(error: AmortizationError) -> Self {
+        CliError(error.to_string())
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(error: io::Error) -> Self {
+        CliError(error.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    Csv,
+    Json,
+    #[default]
+    Table,
+    // iCalendar payment reminders
+    Ics,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Schedule,
+    Compare,
+    Payoff,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CliArgs {
+    pub command: Command,
+    pub config: Option<PathBuf>,
+    pub principal: Option<f64>,
+    pub aprs: Vec<f64>,
+    pub terms: Vec<u32>,
+    pub start_date: Option<NaiveDate>,
+    pub extra_payment: f64,
+    pub payment: Option<f64>,
+    pub period: Option<u32>,
+    pub format: OutputFormat,
+    #[cfg(feature = "i18n")]
+    pub language: Language,
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, CliError> {
+    let value = value.ok_or_else(|| CliError(format!("Missing value for {}", flag)))?;
+    value.parse().map_err(|_| CliError(format!("Invalid value for {}: {}", flag, value)))
+}
+
+impl CliArgs {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<CliArgs, CliError> {
+        let mut args = args.into_iter();
+        let command = match args.next().as_deref() {
+            Some("schedule") => Command::Schedule,
+            Some("compare") => Command::Compare,
+            Some("payoff") => Command::Payoff,
+            Some(other) => return Err(CliError(format!("Unknown command: {}\n\n{}", other, USAGE))),
+            None => return Err(CliError(USAGE.to_string())),
+        };
+
+        let mut parsed = CliArgs {
+            command,
+            config: None,
+            principal: None,
+            aprs: Vec::new(),
+            terms: Vec::new(),
+            start_date: None,
+            extra_payment: 0.0,
+            payment: None,
+            period: None,
+            format: OutputFormat::default(),
+            #[cfg(feature = "i18n")]
+            language: Language::default(),
+        };
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "--config" => parsed.config = Some(PathBuf::from(parse_value::<String>(&flag, args.next())?)),
+                "--principal" => parsed.principal = Some(parse_value(&flag, args.next())?),
+                "--apr" => parsed.aprs.push(parse_value(&flag, args.next())?),
+                "--term" => parsed.terms.push(parse_value(&flag, args.next())?),
+                "--extra-payment" => parsed.extra_payment = parse_value(&flag, args.next())?,
+                "--payment" => parsed.payment = Some(parse_value(&flag, args.next())?),
+                "--period" => parsed.period = Some(parse_value(&flag, args.next())?),
+                "--start-date" => {
+                    let value: String = parse_value(&flag, args.next())?;
+                    let date = NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+                        .map_err(|_| CliError(format!("Invalid value for --start-date: {}", value)))?;
+                    parsed.start_date = Some(date);
+                }
+                "--format" => {
+                    parsed.format = match parse_value::<String>(&flag, args.next())?.as_str() {
+                        "csv" => OutputFormat::Csv,
+                        "json" => OutputFormat::Json,
+                        "table" => OutputFormat::Table,
+                        "ics" => OutputFormat::Ics,
+                        other => return Err(CliError(format!("Unknown format: {}", other))),
+                    }
+                }
+                #[cfg(feature = "i18n")]
+                "--lang" => {
+                    let code: String = parse_value(&flag, args.next())?;
+                    parsed.language = Language::from_code(&code).ok_or_else(|| CliError(format!("Unknown language: {}", code)))?;
+                }
+                other => return Err(CliError(format!("Unknown option: {}\n\n{}", other, USAGE))),
+            }
+        }
+        Ok(parsed)
+    }
+
+    // Rates read as a percent that were more likely typed as a fraction
+    pub fn warnings(&self) -> Vec<String> {
+        self.aprs.iter()
+            .filter(|&&apr| Rate::percent(apr).is_likely_fraction())
+            .map(|apr| format!("--apr {} is read as {}%; pass {} for {}%", apr, apr, apr * 100.0, apr * 100.0))
+            .collect()
+    }
+
+    // The loans in the `--config` file, or one per `--apr`/`--term` pair where a single value
+    // is shared by every loan
+    pub fn loans(&self) -> Result<Vec<Amortization>, CliError> {
+        if let Some(path) = &self.config {
+            if self.principal.is_some() || !self.aprs.is_empty() || !self.terms.is_empty() {
+                return Err(CliError("--config cannot be combined with --principal, --apr or --term".to_string()));
+            }
+            let loans = CalculatorConfig::all_from_path(path)?.into_iter()
+                .map(Amortization::from_config)
+                .collect::<Result<Vec<_>, _>>()?;
+            if loans.is_empty() {
+                return Err(CliError(format!("{} defines no loans", path.display())));
+            }
+            return Ok(loans);
+        }
+        let principal = self.principal.ok_or_else(|| CliError("Missing required option --principal".to_string()))?;
+        if self.aprs.is_empty() {
+            return Err(CliError("Missing required option --apr".to_string()));
+        }
+        if self.terms.is_empty() && self.payment.is_none() {
+            return Err(CliError("Missing required option --term".to_string()));
+        }
+        let count = self.aprs.len().max(self.terms.len());
+        let pick = |len: usize, index: usize| if len == 1 { 0 } else { index };
+        if (self.aprs.len() != 1 && self.aprs.len() != count) || (self.terms.len() > 1 && self.terms.len() != count) {
+            return Err(CliError("Give either one --apr/--term or one per loan".to_string()));
+        }
+
+        (0..count).map(|index| {
+            let mut builder = Amortization::builder()
+                .principal(principal)
+                .rate(Rate::percent(self.aprs[pick(self.aprs.len(), index)]))
+                .extra_payment(self.extra_payment);
+            if !self.terms.is_empty() {
+                builder = builder.term_months(self.terms[pick(self.terms.len(), index)]);
+            }
+            if let Some(payment) = self.payment {
+                builder = builder.fixed_payment(FixedPayment::new(payment));
+            }
+            if let Some(date) = self.start_date {
+                builder = builder.start_date(date);
+            }
+            Ok(builder.build()?)
+        }).collect()
+    }
+}
+
+// Output goes to `out` and warnings about the arguments to `err`
+pub fn run<I: IntoIterator<Item = String>, W: Write, E: Write>(args: I, out: &mut W, err: &mut E) -> Result<(), CliError> {
+    let args = CliArgs::parse(args)?;
+    for warning in args.warnings() {
+        writeln!(err, "warning: {}", warning)?;
+    }
+    let loans = args.loans()?;
+    match args.command {
+        Command::Schedule => write_schedule(&loans[0], &args, out)?,
+        Command::Compare => write_comparison(LoanComparison::new(loans), args.format, out)?,
+        Command::Payoff => {
+            let period = args.period.ok_or_else(|| CliError("Missing required option --period".to_string()))?;
+            write_payoff(&loans[0], period, args.format, out)?
+        }
+    }
+    Ok(())
+}
+
+fn write_schedule<W: Write>(loan: &Amortization, args: &CliArgs, out: &mut W) -> Result<(), CliError> {
+    match args.format {
+        OutputFormat::Csv => loan.to_csv(out)?,
+        OutputFormat::Json => writeln!(out, "{}", loan.to_json())?,
+        OutputFormat::Ics => write!(out, "{}", loan.to_ics()?)?,
+        #[cfg(feature = "i18n")]
+        OutputFormat::Table => write!(out, "{}", loan.to_table_in(&TableOptions::default(), &args.language))?,
+        #[cfg(not(feature = "i18n"))]
+        OutputFormat::Table => write!(out, "{}", loan.to_table())?,
+    }
+    Ok(())
+}
+
+fn write_comparison<W: Write>(comparison: LoanComparison, format: OutputFormat, out: &mut W) -> Result<(), CliError> {
+    let summaries = comparison.summaries();
+    match format {
+        OutputFormat::Ics => return Err(CliError("--format ics is only available for schedule".to_string())),
+        OutputFormat::Csv => {
+            writeln!(out, "APR,Periods,Payment,Total Payment,Total Interest,Interest Delta")?;
+            for s in &summaries {
+                writeln!(out, "{},{},{:.2},{:.2},{:.2},{:.2}", s.apr, s.periods, s.periodic_payment, s.total_payment, s.total_interest, s.interest_delta)?;
+            }
+        }
+        OutputFormat::Json => writeln!(out, "{}", comparison.summaries_json())?,
+        OutputFormat::Table => {
+            writeln!(out, "{:>6}  {:>7}  {:>10}  {:>14}  {:>14}  {:>14}  Payoff", "APR", "Periods", "Payment", "Total Payment", "Total Interest", "Delta")?;
+            for s in &summaries {
+                writeln!(out, "{:>6.3}  {:>7}  {:>10.2}  {:>14.2}  {:>14.2}  {:>14.2}  {}",
+                    s.apr, s.periods, s.periodic_payment, s.total_payment, s.total_interest, s.interest_delta, s.term_change)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_payoff<W: Write>(loan: &Amortization, period: u32, format: OutputFormat, out: &mut W) -> Result<(), CliError> {
+    let payoff = Refinance::payoff_balance(loan, period)?;
+    let paid: f64 = loan.schedule.iter().take(period as usize).map(|p| p.installment_amount).sum();
+    let interest_saved = loan.total_payment - paid - payoff;
+    match format {
+        OutputFormat::Ics => return Err(CliError("--format ics is only available for schedule".to_string())),
+        OutputFormat::Csv => writeln!(out, "Period,Payoff Amount,Interest Saved\n{},{:.2},{:.2}", period, payoff, interest_saved)?,
+        OutputFormat::Json => writeln!(out, "{}", JsonValue::object(vec![
+            ("period", period.into()),
+            ("payoff_amount", JsonValue::money(payoff, loan.config.currency)),
+            ("interest_saved", JsonValue::money(interest_saved, loan.config.currency)),
+        ]))?,
+        OutputFormat::Table => {
+            writeln!(out, "Payoff after {} payments: {:.2}", period, payoff)?;
+            writeln!(out, "Interest saved: {:.2}", interest_saved)?;
+        }
+    }
+    Ok(())
+}
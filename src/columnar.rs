@@ -0,0 +1,265 @@
+// Schedules stored a column at a time, for holding many loans in memory at once. Each field
+// of `Payment` lives in its own vector, so the amounts sit contiguously for aggregation, and
+// columns that the schedule never uses (escrow, LTV, real values, accrual dates, accrued
+// interest) take no space at all. Rows are rebuilt as `Payment` values on access
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::escrow::EscrowPayment;
+use crate::inflation::RealValues;
+use crate::payment::{AccrualPeriod, Payment};
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColumnarSchedule {
+    installment_number: Vec<u32>,
+    date: Vec<Option<NaiveDate>>,
+    beginning_balance: Vec<f64>,
+    interest: Vec<f64>,
+    principal: Vec<f64>,
+    remaining_balance: Vec<f64>,
+    installment_amount: Vec<f64>,
+    balloon_amount: Vec<f64>,
+    capitalized_interest: Vec<f64>,
+    deferred_amount: Vec<f64>,
+    fees: Vec<f64>,
+    skipped: Vec<bool>,
+    // Left empty until a row has a value
+    escrow: Vec<Option<EscrowPayment>>,
+    ltv: Vec<Option<f64>>,
+    real: Vec<Option<RealValues>>,
+    accrual: Vec<Option<AccrualPeriod>>,
+    // Only non-zero amounts are kept
+    accrued_interest: Vec<Option<f64>>,
+}
+
+// Sums in four independent lanes, which lets the compiler vectorize the loop
+pub(crate) fn sum(values: &[f64]) -> f64 {
+    let mut lanes = [0.0; 4];
+    let chunks = values.chunks_exact(4);
+    let rest: f64 = chunks.remainder().iter().sum();
+    for chunk in chunks {
+        for (lane, value) in lanes.iter_mut().zip(chunk) {
+            *lane += value;
+        }
+    }
+    lanes.iter().sum::<f64>() + rest
+}
+
+fn push_sparse<T: Copy>(column: &mut Vec<Option<T>>, row: usize, value: Option<T>) {
+    if column.is_empty() && value.is_none() {
+        return;
+    }
+    column.resize(row, None);
+    column.push(value);
+}
+
+fn sparse<T: Copy>(column: &[Option<T>], row: usize) -> Option<T> {
+    column.get(row).copied().flatten()
+}
+
+impl ColumnarSchedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(rows: usize) -> Self {
+        ColumnarSchedule {
+            installment_number: Vec::with_capacity(rows),
+            date: Vec::with_capacity(rows),
+            beginning_balance: Vec::with_capacity(rows),
+            interest: Vec::with_capacity(rows),
+            principal: Vec::with_capacity(rows),
+            remaining_balance: Vec::with_capacity(rows),
+            installment_amount: Vec::with_capacity(rows),
+            balloon_amount: Vec::with_capacity(rows),
+            capitalized_interest: Vec::with_capacity(rows),
+            deferred_amount: Vec::with_capacity(rows),
+            fees: Vec::with_capacity(rows),
+            skipped: Vec::with_capacity(rows),
+            ..Self::default()
+        }
+    }
+
+    pub fn push(&mut self, payment: &Payment) {
+        let row = self.len();
+        self.installment_number.push(payment.installment_number);
+        self.date.push(payment.date);
+        self.beginning_balance.push(payment.beginning_balance);
+        self.interest.push(payment.interest);
+        self.principal.push(payment.principal);
+        self.remaining_balance.push(payment.remaining_balance);
+        self.installment_amount.push(payment.installment_amount);
+        self.balloon_amount.push(payment.balloon_amount);
+        self.capitalized_interest.push(payment.capitalized_interest);
+        self.deferred_amount.push(payment.deferred_amount);
+        self.fees.push(payment.fees);
+        self.skipped.push(payment.skipped);
+        push_sparse(&mut self.escrow, row, payment.escrow);
+        push_sparse(&mut self.ltv, row, payment.ltv);
+        push_sparse(&mut self.real, row, payment.real);
+        push_sparse(&mut self.accrual, row, payment.accrual);
+        push_sparse(&mut self.accrued_interest, row, (payment.accrued_interest != 0.0).then_some(payment.accrued_interest));
+    }
+
+    pub fn len(&self) -> usize {
+        self.installment_number.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.installment_number.is_empty()
+    }
+
+    pub fn get(&self, row: usize) -> Option<Payment> {
+        Some(Payment {
+            installment_number: *self.installment_number.get(row)?,
+            beginning_balance: self.beginning_balance[row],
+            interest: self.interest[row],
+            principal: self.principal[row],
+            date: self.date[row],
+            remaining_balance: self.remaining_balance[row],
+            installment_amount: self.installment_amount[row],
+            balloon_amount: self.balloon_amount[row],
+            capitalized_interest: self.capitalized_interest[row],
+            accrued_interest: sparse(&self.accrued_interest, row).unwrap_or(0.0),
+            deferred_amount: self.deferred_amount[row],
+            fees: self.fees[row],
+            skipped: self.skipped[row],
+            escrow: sparse(&self.escrow, row),
+            ltv: sparse(&self.ltv, row),
+            real: sparse(&self.real, row),
+            accrual: sparse(&self.accrual, row),
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Payment> + '_ {
+        (0..self.len()).filter_map(|row| self.get(row))
+    }
+
+    pub fn to_payments(&self) -> Vec<Payment> {
+        self.iter().collect()
+    }
+
+    pub fn installment_numbers(&self) -> &[u32] {
+        &self.installment_number
+    }
+
+    pub fn dates(&self) -> &[Option<NaiveDate>] {
+        &self.date
+    }
+
+    pub fn beginning_balances(&self) -> &[f64] {
+        &self.beginning_balance
+    }
+
+    pub fn interest(&self) -> &[f64] {
+        &self.interest
+    }
+
+    pub fn principal(&self) -> &[f64] {
+        &self.principal
+    }
+
+    pub fn remaining_balances(&self) -> &[f64] {
+        &self.remaining_balance
+    }
+
+    pub fn installment_amounts(&self) -> &[f64] {
+        &self.installment_amount
+    }
+
+    pub fn fees(&self) -> &[f64] {
+        &self.fees
+    }
+
+    pub fn total_interest(&self) -> f64 {
+        sum(&self.interest)
+    }
+
+    pub fn total_principal(&self) -> f64 {
+        sum(&self.principal)
+    }
+
+    pub fn total_payment(&self) -> f64 {
+        sum(&self.installment_amount)
+    }
+
+    pub fn total_fees(&self) -> f64 {
+        sum(&self.fees)
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.installment_number.shrink_to_fit();
+        self.date.shrink_to_fit();
+        self.beginning_balance.shrink_to_fit();
+        self.interest.shrink_to_fit();
+        self.principal.shrink_to_fit();
+        self.remaining_balance.shrink_to_fit();
+        self.installment_amount.shrink_to_fit();
+        self.balloon_amount.shrink_to_fit();
+        self.capitalized_interest.shrink_to_fit();
+        self.deferred_amount.shrink_to_fit();
+        self.fees.shrink_to_fit();
+        self.skipped.shrink_to_fit();
+        self.escrow.shrink_to_fit();
+        self.ltv.shrink_to_fit();
+        self.real.shrink_to_fit();
+        self.accrual.shrink_to_fit();
+        self.accrued_interest.shrink_to_fit();
+    }
+
+    // Bytes allocated for the columns, to compare against `len() * size_of::<Payment>()`
+    pub fn heap_size(&self) -> usize {
+        fn bytes<T>(column: &Vec<T>) -> usize {
+            column.capacity() * std::mem::size_of::<T>()
+        }
+        bytes(&self.installment_number)
+            + bytes(&self.date)
+            + bytes(&self.beginning_balance)
+            + bytes(&self.interest)
+            + bytes(&self.principal)
+            + bytes(&self.remaining_balance)
+            + bytes(&self.installment_amount)
+            + bytes(&self.balloon_amount)
+            + bytes(&self.capitalized_interest)
+            + bytes(&self.deferred_amount)
+            + bytes(&self.fees)
+            + bytes(&self.skipped)
+            + bytes(&self.escrow)
+            + bytes(&self.ltv)
+            + bytes(&self.real)
+            + bytes(&self.accrual)
+            + bytes(&self.accrued_interest)
+    }
+}
+
+impl<'a> FromIterator<&'a Payment> for ColumnarSchedule {
+    fn from_iter<I: IntoIterator<Item = &'a Payment>>(payments: I) -> Self {
+        let payments = payments.into_iter();
+        let (lower, upper) = payments.size_hint();
+        let mut schedule = ColumnarSchedule::with_capacity(upper.unwrap_or(lower));
+        payments.for_each(|payment| schedule.push(payment));
+        schedule
+    }
+}
+
+// Collecting `Amortization::payments()` fills the columns without a `Vec<Payment>` in between
+impl FromIterator<Payment> for ColumnarSchedule {
+    fn from_iter<I: IntoIterator<Item = Payment>>(payments: I) -> Self {
+        let payments = payments.into_iter();
+        let (lower, upper) = payments.size_hint();
+        let mut schedule = ColumnarSchedule::with_capacity(upper.unwrap_or(lower));
+        payments.for_each(|payment| schedule.push(&payment));
+        schedule
+    }
+}
+
+impl From<&[Payment]> for ColumnarSchedule {
+    fn from(payments: &[Payment]) -> Self {
+        payments.iter().collect()
+    }
+}
+
+impl Amortization {
+    pub fn columnar_schedule(&self) -> ColumnarSchedule {
+        ColumnarSchedule::from(self.schedule.as_slice())
+    }
+}
@@ -0,0 +1,120 @@
+use crate::calculator::Amortization;
+use crate::json::JsonValue;
+use crate::money::Currency;
+use crate::units::Term;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoanSummary {
+    pub principal: f64,
+    pub apr: f64,
+    pub periods: u32,
+    pub term: Term,
+    pub periodic_payment: f64,
+    pub total_payment: f64,
+    pub total_interest: f64,
+    pub upfront_cost: f64,
+    // Total interest and time to payoff relative to the first loan in the comparison
+    pub interest_delta: f64,
+    pub term_change: String,
+    pub currency: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceRow {
+    pub period: u32,
+    pub balances: Vec<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LoanComparison {
+    pub loans: Vec<Amortization>,
+    pub upfront_costs: Vec<f64>,
+}
+
+impl LoanComparison {
+    pub fn new(loans: Vec<Amortization>) -> Self {
+        let upfront_costs = vec![0.0; loans.len()];
+        LoanComparison { loans, upfront_costs }
+    }
+
+    pub fn with_upfront_costs(mut self, upfront_costs: Vec<f64>) -> Self {
+        self.upfront_costs = upfront_costs;
+        self.upfront_costs.resize(self.loans.len(), 0.0);
+        self
+    }
+
+    fn upfront_cost(&self, index: usize) -> f64 {
+        self.upfront_costs.get(index).copied().unwrap_or(0.0)
+    }
+
+    pub fn summaries(&self) -> Vec<LoanSummary> {
+        let baseline_interest = self.loans.first().map_or(0.0, |loan| loan.total_interest);
+        let baseline_term = self.loans.first().map_or(Term::default(), |loan| loan.payoff_term());
+        self.loans.iter().enumerate().map(|(index, loan)| LoanSummary {
+            principal: loan.balance,
+            apr: loan.rate_schedule[0].apr,
+            periods: loan.schedule.len() as u32,
+            term: loan.payoff_term(),
+            periodic_payment: loan.periodic_payment,
+            total_payment: loan.total_payment,
+            total_interest: loan.total_interest,
+            upfront_cost: self.upfront_cost(index),
+            interest_delta: loan.total_interest - baseline_interest,
+            term_change: loan.payoff_term().change_from(baseline_term),
+            currency: loan.config.currency,
+        }).collect()
+    }
+
+    pub fn summaries_json(&self) -> JsonValue {
+        JsonValue::Array(self.summaries().iter().map(|s| JsonValue::object(vec![
+            ("apr", s.apr.into()),
+            ("periods", s.periods.into()),
+            ("periodic_payment", JsonValue::money(s.periodic_payment, s.currency)),
+            ("total_payment", JsonValue::money(s.total_payment, s.currency)),
+            ("total_interest", JsonValue::money(s.total_interest, s.currency)),
+            ("interest_delta", JsonValue::money(s.interest_delta, s.currency)),
+            ("term", s.term.to_string().into()),
+            ("term_change", s.term_change.as_str().into()),
+        ])).collect())
+    }
+
+    pub fn lowest_total_cost(&self) -> Option<usize> {
+        (0..self.loans.len()).min_by(|&a, &b| {
+            let cost_a = self.loans[a].total_payment + self.upfront_cost(a);
+            let cost_b = self.loans[b].total_payment + self.upfront_cost(b);
+            cost_a.total_cmp(&cost_b)
+        })
+    }
+
+    // First period at which the cumulative cost (upfront plus payments) of `first`
+    // drops to or below that of `second`, e.g. when paying points starts to pay off
+    pub fn break_even_month(&self, first: usize, second: usize) -> Option<u32> {
+        let a = self.loans.get(first)?;
+        let b = self.loans.get(second)?;
+        let mut cost_a = self.upfront_cost(first);
+        let mut cost_b = self.upfront_cost(second);
+        if cost_a <= cost_b {
+            return Some(0);
+        }
+
+        let periods = a.schedule.len().max(b.schedule.len());
+        for period in 0..periods {
+            cost_a += a.schedule.get(period).map_or(0.0, |p| p.installment_amount);
+            cost_b += b.schedule.get(period).map_or(0.0, |p| p.installment_amount);
+            if cost_a <= cost_b {
+                return Some(period as u32 + 1);
+            }
+        }
+        None
+    }
+
+    pub fn balance_table(&self) -> Vec<BalanceRow> {
+        let periods = self.loans.iter().map(|loan| loan.schedule.len()).max().unwrap_or(0);
+        (0..periods).map(|period| BalanceRow {
+            period: period as u32 + 1,
+            balances: self.loans.iter()
+                .map(|loan| loan.schedule.get(period).map_or(0.0, |p| p.remaining_balance))
+                .collect(),
+        }).collect()
+    }
+}
@@ -0,0 +1,78 @@
+// Spreadsheet-compatible versions of the `tvm` functions. They take the arguments in the
+// order Excel and Google Sheets do, including the numeric `type`, and every result is rounded
+// to the cent the way `ROUND(PMT(...), 2)` is, so a schedule built from them matches a
+// spreadsheet cell for cell
+use crate::decimal::Decimal;
+use crate::error::AmortizationError;
+use crate::money::RoundingMode;
+use crate::tvm::{self, PaymentTiming};
+
+// Spreadsheets keep 15 significant digits of a result before rounding it
+const SIGNIFICANT_DIGITS: i32 = 15;
+const CENTS: u32 = 2;
+
+// Rounds like the spreadsheet ROUND function: the value is first cut to 15 significant digits,
+// then ties go away from zero. Plain binary rounding turns 2.675 into 2.67; this gives 2.68
+pub fn round(value: f64, digits: u32) -> f64 {
+    if !value.is_finite() || value == 0.0 {
+        return value;
+    }
+    let magnitude = value.abs().log10().floor() as i32;
+    let fraction_digits = (SIGNIFICANT_DIGITS - 1 - magnitude).clamp(0, 12) as usize;
+    match format!("{:.*}", fraction_digits, value).parse::<Decimal>() {
+        Ok(decimal) => decimal.round_dp_with(digits, RoundingMode::HalfUp).to_f64(),
+        Err(_) => {
+            let scale = 10f64.powi(digits as i32);
+            (value * scale).round() / scale
+        }
+    }
+}
+
+// Any nonzero `type` means payments at the beginning of each period, as in the spreadsheet
+pub fn timing(payment_type: u32) -> PaymentTiming {
+    if payment_type == 0 {
+        PaymentTiming::Arrears
+    } else {
+        PaymentTiming::Advance
+    }
+}
+
+pub fn pmt(rate: f64, nper: u32, pv: f64, fv: f64, payment_type: u32) -> Result<f64, AmortizationError> {
+    Ok(round(tvm::pmt(rate, nper, pv, fv, timing(payment_type))?, CENTS))
+}
+
+pub fn ipmt(rate: f64, per: u32, nper: u32, pv: f64, fv: f64, payment_type: u32) -> Result<f64, AmortizationError> {
+    Ok(round(tvm::ipmt(rate, per, nper, pv, fv, timing(payment_type))?, CENTS))
+}
+
+pub fn ppmt(rate: f64, per: u32, nper: u32, pv: f64, fv: f64, payment_type: u32) -> Result<f64, AmortizationError> {
+    Ok(round(tvm::ppmt(rate, per, nper, pv, fv, timing(payment_type))?, CENTS))
+}
+
+pub fn fv(rate: f64, nper: u32, pmt: f64, pv: f64, payment_type: u32) -> Result<f64, AmortizationError> {
+    Ok(round(tvm::fv(rate, nper, pmt, pv, timing(payment_type))?, CENTS))
+}
+
+pub fn pv(rate: f64, nper: u32, pmt: f64, fv: f64, payment_type: u32) -> Result<f64, AmortizationError> {
+    Ok(round(tvm::pv(rate, nper, pmt, fv, timing(payment_type))?, CENTS))
+}
+
+// One row of the usual spreadsheet amortization table, where each column is its own rounded
+// formula rather than a running balance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpreadsheetRow {
+    pub per: u32,
+    pub payment: f64,
+    pub interest: f64,
+    pub principal: f64,
+}
+
+pub fn schedule(rate: f64, nper: u32, pv: f64, fv: f64, payment_type: u32) -> Result<Vec<SpreadsheetRow>, AmortizationError> {
+    let payment = pmt(rate, nper, pv, fv, payment_type)?;
+    (1..=nper).map(|per| Ok(SpreadsheetRow {
+        per,
+        payment,
+        interest: ipmt(rate, per, nper, pv, fv, payment_type)?,
+        principal: ppmt(rate, per, nper, pv, fv, payment_type)?,
+    })).collect()
+}
@@ -0,0 +1,446 @@
+// Loan definitions read from TOML or JSON, for the CLI and batch runs. Both formats hold the
+// same fields:
+//
+//   principal = 250000
+//   apr = 6.5                  # percent
+//   term_months = 360          # or term_years = 30
+//   start_date = 2024-01-01
+//
+// A file with several loans lists them under `loans`, as [[loans]] tables in TOML or an array
+// in JSON. Keys outside the list are defaults that every loan can override
+use std::path::Path;
+use std::str::FromStr;
+use chrono::NaiveDate;
+use crate::calculator::CalculatorConfig;
+use crate::error::AmortizationError;
+use crate::frequency::PaymentFrequency;
+use crate::json::{JsonValue, MAX_DEPTH};
+use crate::tvm::PaymentTiming;
+
+const LOANS_KEY: &str = "loans";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+impl ConfigFormat {
+    pub fn from_path(path: &Path) -> Option<ConfigFormat> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "toml" => Some(ConfigFormat::Toml),
+            "json" => Some(ConfigFormat::Json),
+            _ => None,
+        }
+    }
+
+    // A JSON document opens with an object or array; a TOML one never starts with `{`, and
+    // a leading `[` is only a table header when it is followed by a name
+    pub fn detect(input: &str) -> ConfigFormat {
+        let trimmed = input.trim_start();
+        let json_array = trimmed.strip_prefix('[')
+            .is_some_and(|rest| matches!(rest.trim_start().chars().next(), Some('{' | ']')));
+        if trimmed.starts_with('{') || json_array {
+            ConfigFormat::Json
+        } else {
+            ConfigFormat::Toml
+        }
+    }
+
+    fn parse(self, input: &str) -> Result<JsonValue, AmortizationError> {
+        let document = match self {
+            ConfigFormat::Toml => parse_toml(input),
+            ConfigFormat::Json => JsonValue::parse(input),
+        };
+        document.map_err(AmortizationError::InvalidConfig)
+    }
+}
+
+impl CalculatorConfig {
+    // A file holding exactly one loan; the format follows the extension, or the contents when
+    // the extension is neither .toml nor .json
+    pub fn from_path(path: impl AsRef<Path>) -> Result<CalculatorConfig, AmortizationError> {
+        single(CalculatorConfig::all_from_path(path)?)
+    }
+
+    pub fn all_from_path(path: impl AsRef<Path>) -> Result<Vec<CalculatorConfig>, AmortizationError> {
+        let path = path.as_ref();
+        let input = std::fs::read_to_string(path)
+            .map_err(|error| AmortizationError::InvalidConfig(format!("{}: {}", path.display(), error)))?;
+        let format = ConfigFormat::from_path(path).unwrap_or_else(|| ConfigFormat::detect(&input));
+        CalculatorConfig::all_from_str(&input, format)
+    }
+
+    pub fn all_from_str(input: &str, format: ConfigFormat) -> Result<Vec<CalculatorConfig>, AmortizationError> {
+        let document = format.parse(input)?;
+        let (defaults, loans) = match document {
+            JsonValue::Array(loans) => (Vec::new(), loans),
+            JsonValue::Object(mut entries) => match entries.iter().position(|(key, _)| key == LOANS_KEY) {
+                Some(index) => match entries.remove(index).1 {
+                    JsonValue::Array(loans) => (entries, loans),
+                    _ => return Err(AmortizationError::InvalidConfig(format!("'{}' must be a list of loans", LOANS_KEY))),
+                },
+                None => (Vec::new(), vec![JsonValue::Object(entries)]),
+            },
+            _ => return Err(AmortizationError::InvalidConfig("Expected a loan or a list of loans".to_string())),
+        };
+
+        loans.into_iter().enumerate().map(|(index, loan)| {
+            let JsonValue::Object(fields) = loan else {
+                return Err(AmortizationError::InvalidConfig(format!("Loan {} is not a table", index + 1)));
+            };
+            // A loan's own term replaces the default whichever unit either is given in
+            let mut merged: Vec<_> = defaults.iter()
+                .filter(|(default, _)| !fields.iter().any(|(key, _)| key == default || (is_term(key) && is_term(default))))
+                .cloned()
+                .collect();
+            merged.extend(fields);
+            loan_config(&merged)
+        }).collect()
+    }
+}
+
+// Reads a single loan, detecting whether the text is TOML or JSON
+impl FromStr for CalculatorConfig {
+    type Err = AmortizationError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        single(CalculatorConfig::all_from_str(input, ConfigFormat::detect(input))?)
+    }
+}
+
+fn is_term(key: &str) -> bool {
+    key == "term_months" || key == "term_years"
+}
+
+fn single(mut configs: Vec<CalculatorConfig>) -> Result<CalculatorConfig, AmortizationError> {
+    if configs.len() != 1 {
+        return Err(AmortizationError::InvalidConfig(format!("Expected one loan, found {}", configs.len())));
+    }
+    Ok(configs.remove(0))
+}
+
+fn invalid(key: &str, expected: &str) -> AmortizationError {
+    AmortizationError::InvalidConfig(format!("'{}' must be {}", key, expected))
+}
+
+fn number(key: &str, value: &JsonValue) -> Result<f64, AmortizationError> {
+    match value {
+        JsonValue::Number(n) => Ok(*n),
+        _ => Err(invalid(key, "a number")),
+    }
+}
+
+fn whole_number(key: &str, value: &JsonValue) -> Result<u32, AmortizationError> {
+    let n = number(key, value)?;
+    if n < 0.0 || n.fract() != 0.0 || n > u32::MAX as f64 {
+        return Err(invalid(key, "a whole number"));
+    }
+    Ok(n as u32)
+}
+
+fn text<'a>(key: &str, value: &'a JsonValue) -> Result<&'a str, AmortizationError> {
+    match value {
+        JsonValue::String(s) => Ok(s),
+        _ => Err(invalid(key, "a string")),
+    }
+}
+
+fn date(key: &str, value: &JsonValue) -> Result<NaiveDate, AmortizationError> {
+    NaiveDate::parse_from_str(text(key, value)?, "%Y-%m-%d").map_err(|_| invalid(key, "a YYYY-MM-DD date"))
+}
+
+fn frequency(key: &str, value: &JsonValue) -> Result<PaymentFrequency, AmortizationError> {
+    PaymentFrequency::from_name(&text(key, value)?.to_ascii_lowercase())
+        .ok_or_else(|| invalid(key, "weekly, biweekly, monthly, quarterly, semiannually or annually"))
+}
+
+fn loan_config(fields: &[(String, JsonValue)]) -> Result<CalculatorConfig, AmortizationError> {
+    let field = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+    let principal = number("principal", field("principal").ok_or(AmortizationError::MissingParameter("principal"))?)?;
+    let apr = number("apr", field("apr").ok_or(AmortizationError::MissingParameter("apr"))?)?;
+    let loan_term = match (field("term_months"), field("term_years")) {
+        (Some(months), None) => whole_number("term_months", months)?,
+        (None, Some(years)) => whole_number("term_years", years)?.saturating_mul(12),
+        (Some(_), Some(_)) => return Err(AmortizationError::ConflictingOptions("term_months", "term_years")),
+        (None, None) => return Err(AmortizationError::MissingParameter("term_months")),
+    };
+    let start_date = field("start_date").map(|value| date("start_date", value)).transpose()?;
+    let mut config = CalculatorConfig::new(principal, apr, loan_term, start_date);
+
+    for (key, value) in fields {
+        match key.as_str() {
+            "principal" | "apr" | "term_months" | "term_years" | "start_date" => {}
+            "frequency" => config.frequency = frequency(key, value)?,
+            "extra_payment" => config.extra_payment = number(key, value)?,
+            "balloon_period" => config.balloon_period = Some(whole_number(key, value)?),
+            "funding_date" => config.funding_date = Some(date(key, value)?),
+            "inflation_rate" => config.inflation_rate = Some(number(key, value)?),
+            "payment_timing" => {
+                config.payment_timing = match text(key, value)? {
+                    "arrears" => PaymentTiming::Arrears,
+                    "advance" => PaymentTiming::Advance,
+                    _ => return Err(invalid(key, "arrears or advance")),
+                }
+            }
+            // Unknown keys are usually typos, which would otherwise silently fall back to a default
+            other => return Err(AmortizationError::InvalidConfig(format!("Unknown key '{}'", other))),
+        }
+    }
+    Ok(config)
+}
+
+// The subset of TOML loan files need: comments, `key = value` pairs, [tables], [[arrays of
+// tables]], strings, numbers, booleans, local dates, arrays and inline tables. Dates are kept
+// as strings, which is how JSON carries them too
+pub(crate) fn parse_toml(input: &str) -> Result<JsonValue, String> {
+    let mut root: Vec<(String, JsonValue)> = Vec::new();
+    // Where `key = value` lines go: the root, a [table] or the last entry of an [[array]]
+    let mut section: Option<String> = None;
+    let mut lines = input.lines().enumerate();
+
+    while let Some((index, raw)) = lines.next() {
+        let error = |message: &str| format!("line {}: {}", index + 1, message);
+        let line = strip_comment(raw).trim().to_string();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix("[[").and_then(|rest| rest.strip_suffix("]]")) {
+            let name = name.trim().to_string();
+            match root.iter_mut().find(|(key, _)| *key == name) {
+                Some((_, JsonValue::Array(items))) => items.push(JsonValue::Object(Vec::new())),
+                Some(_) => return Err(error(&format!("'{}' is already defined", name))),
+                None => root.push((name.clone(), JsonValue::Array(vec![JsonValue::Object(Vec::new())]))),
+            }
+            section = Some(name);
+        } else if let Some(name) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+            let name = name.trim().to_string();
+            if root.iter().any(|(key, _)| *key == name) {
+                return Err(error(&format!("'{}' is already defined", name)));
+            }
+            root.push((name.clone(), JsonValue::Object(Vec::new())));
+            section = Some(name);
+        } else {
+            let (key, value) = line.split_once('=').ok_or_else(|| error("expected key = value"))?;
+            let key = toml_key(key.trim()).ok_or_else(|| error("invalid key"))?;
+            // Arrays may spread over several lines
+            let mut value = value.trim().to_string();
+            while bracket_depth(&value) > 0 {
+                let (_, next) = lines.next().ok_or_else(|| error("unterminated array"))?;
+                value.push(' ');
+                value.push_str(strip_comment(next).trim());
+            }
+            let mut parser = TomlValueParser { chars: value.chars().collect(), pos: 0, depth: 0 };
+            let parsed = parser.value().map_err(|message| error(&message))?;
+            parser.skip_whitespace();
+            if parser.pos < parser.chars.len() {
+                return Err(error("unexpected characters after value"));
+            }
+
+            let table = match &section {
+                None => &mut root,
+                Some(name) => match root.iter_mut().find(|(k, _)| k == name) {
+                    Some((_, JsonValue::Object(entries))) => entries,
+                    Some((_, JsonValue::Array(items))) => match items.last_mut() {
+                        Some(JsonValue::Object(entries)) => entries,
+                        _ => return Err(error("invalid table")),
+                    },
+                    _ => return Err(error("invalid table")),
+                },
+            };
+            if table.iter().any(|(k, _)| *k == key) {
+                return Err(error(&format!("'{}' is defined twice", key)));
+            }
+            table.push((key, parsed));
+        }
+    }
+    Ok(JsonValue::Object(root))
+}
+
+fn toml_key(key: &str) -> Option<String> {
+    if let Some(quoted) = key.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')) {
+        return Some(quoted.to_string());
+    }
+    let bare = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    bare.then(|| key.to_string())
+}
+
+// Drops a trailing `# comment`, leaving any `#` inside a string alone
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '#') => return &line[..index],
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            _ => {}
+        }
+    }
+    line
+}
+
+fn bracket_depth(value: &str) -> i32 {
+    let mut depth = 0;
+    let mut quote = None;
+    for c in value.chars() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '[' | '{') => depth += 1,
+            (None, ']' | '}') => depth -= 1,
+            _ => {}
+        }
+    }
+    depth
+}
+
+struct TomlValueParser {
+    chars: Vec<char>,
+    pos: usize,
+    // Arrays and inline tables currently open
+    depth: usize,
+}
+
+impl TomlValueParser {
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.get(self.pos).copied()
+    }
+
+    fn value(&mut self) -> Result<JsonValue, String> {
+        match self.peek() {
+            Some('"') => self.basic_string().map(JsonValue::String),
+            Some('\'') => self.literal_string().map(JsonValue::String),
+            Some('[') => self.nested(Self::array),
+            Some('{') => self.nested(Self::inline_table),
+            Some(_) => self.scalar(),
+            None => Err("missing value".to_string()),
+        }
+    }
+
+    // Same cap as the JSON parser, so deep input is an error rather than a stack overflow
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<JsonValue, String>) -> Result<JsonValue, String> {
+        if self.depth >= MAX_DEPTH {
+            return Err("nesting too deep".to_string());
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn basic_string(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let mut value = String::new();
+        loop {
+            let c = *self.chars.get(self.pos).ok_or("unterminated string")?;
+            self.pos += 1;
+            match c {
+                '"' => return Ok(value),
+                '\\' => {
+                    let escape = *self.chars.get(self.pos).ok_or("unterminated string")?;
+                    self.pos += 1;
+                    value.push(match escape {
+                        '"' => '"',
+                        '\\' => '\\',
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        _ => return Err(format!("unsupported escape \\{}", escape)),
+                    });
+                }
+                c => value.push(c),
+            }
+        }
+    }
+
+    fn literal_string(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let start = self.pos;
+        while self.chars.get(self.pos).is_some_and(|&c| c != '\'') {
+            self.pos += 1;
+        }
+        if self.pos >= self.chars.len() {
+            return Err("unterminated string".to_string());
+        }
+        self.pos += 1;
+        Ok(self.chars[start..self.pos - 1].iter().collect())
+    }
+
+    fn array(&mut self) -> Result<JsonValue, String> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        loop {
+            // A trailing comma before the closing bracket is allowed
+            if self.peek() == Some(']') {
+                self.pos += 1;
+                return Ok(JsonValue::Array(items));
+            }
+            items.push(self.value()?);
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {}
+                _ => return Err("expected ',' or ']'".to_string()),
+            }
+        }
+    }
+
+    fn inline_table(&mut self) -> Result<JsonValue, String> {
+        self.pos += 1;
+        let mut entries = Vec::new();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            let start = self.pos;
+            while self.chars.get(self.pos).is_some_and(|&c| c != '=') {
+                self.pos += 1;
+            }
+            let key: String = self.chars[start..self.pos].iter().collect();
+            let key = toml_key(key.trim()).ok_or("invalid key")?;
+            if self.peek() != Some('=') {
+                return Err("expected '='".to_string());
+            }
+            self.pos += 1;
+            entries.push((key, self.value()?));
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(entries));
+                }
+                _ => return Err("expected ',' or '}'".to_string()),
+            }
+        }
+    }
+
+    // Booleans, numbers with optional `_` separators, and local dates
+    fn scalar(&mut self) -> Result<JsonValue, String> {
+        let start = self.pos;
+        while self.chars.get(self.pos).is_some_and(|&c| !matches!(c, ',' | ']' | '}') && !c.is_whitespace()) {
+            self.pos += 1;
+        }
+        let token: String = self.chars[start..self.pos].iter().collect();
+        match token.as_str() {
+            "true" => return Ok(JsonValue::Bool(true)),
+            "false" => return Ok(JsonValue::Bool(false)),
+            _ => {}
+        }
+        if NaiveDate::parse_from_str(&token, "%Y-%m-%d").is_ok() {
+            return Ok(JsonValue::String(token));
+        }
+        token.replace('_', "").parse::<f64>().ok()
+            .filter(|n| n.is_finite())
+            .map(JsonValue::Number)
+            .ok_or_else(|| format!("invalid value '{}'", token))
+    }
+}
@@ -0,0 +1,135 @@
+// Consolidating several loans into one at their balance-weighted average rate, compared with
+// keeping them separate
+use chrono::NaiveDate;
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::compare::LoanComparison;
+use crate::error::AmortizationError;
+use crate::portfolio::{LoanPool, Portfolio};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConsolidationRounding {
+    #[default]
+    Exact,
+    // Up to the next 1/8 of a percent, as for federal Direct Consolidation Loans
+    UpToEighth,
+}
+
+impl ConsolidationRounding {
+    pub fn apply(&self, rate: f64) -> f64 {
+        match self {
+            ConsolidationRounding::Exact => rate,
+            // A rate already on an eighth shouldn't move up for float noise
+            ConsolidationRounding::UpToEighth => (rate * 8.0 - 1e-9).ceil() / 8.0,
+        }
+    }
+}
+
+// The existing loans as they stand today: each balance is what is still owed
+#[derive(Debug, Clone)]
+pub struct Consolidation {
+    pub loans: Vec<CalculatorConfig>,
+    pub term_months: u32,
+    pub rounding: ConsolidationRounding,
+    // Highest rate the consolidated loan may carry, in percent
+    pub rate_cap: Option<f64>,
+    pub start_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConsolidationAnalysis {
+    pub separate: LoanPool,
+    pub weighted_average_rate: f64,
+    // The weighted average after rounding and the cap
+    pub consolidated_rate: f64,
+    pub consolidated: Amortization,
+    // First installments of the separate loans added up
+    pub separate_payment: f64,
+    pub consolidated_payment: f64,
+    // Positive when consolidating lowers the installment
+    pub payment_savings: f64,
+    // Positive when consolidating costs more interest, usually because of a longer term
+    pub additional_interest: f64,
+}
+
+impl Consolidation {
+    pub fn new(loans: Vec<CalculatorConfig>, term_months: u32) -> Self {
+        Consolidation {
+            loans,
+            term_months,
+            rounding: ConsolidationRounding::Exact,
+            rate_cap: None,
+            start_date: None,
+        }
+    }
+
+    pub fn with_rounding(mut self, rounding: ConsolidationRounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    pub fn with_rate_cap(mut self, rate_cap: f64) -> Self {
+        self.rate_cap = Some(rate_cap);
+        self
+    }
+
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    // Longest standard repayment term for a federal consolidation loan of `balance`
+    pub fn federal_term_months(balance: f64) -> u32 {
+        match balance {
+            b if b < 7_500.0 => 120,
+            b if b < 10_000.0 => 144,
+            b if b < 20_000.0 => 180,
+            b if b < 40_000.0 => 240,
+            b if b < 60_000.0 => 300,
+            _ => 360,
+        }
+    }
+
+    pub fn analyze(&self) -> Result<ConsolidationAnalysis, AmortizationError> {
+        let separate = Portfolio::generate(self.loans.clone())?;
+        let weighted_average_rate = separate.weighted_average_rate;
+        let rounded = self.rounding.apply(weighted_average_rate);
+        let consolidated_rate = self.rate_cap.map_or(rounded, |cap| rounded.min(cap));
+        let consolidated = Amortization::new(separate.total_balance, consolidated_rate, self.term_months, self.start_date)?;
+
+        let separate_payment: f64 = separate.loans.iter()
+            .filter_map(|loan| loan.schedule.first())
+            .map(|p| p.installment_amount)
+            .sum();
+        let consolidated_payment = consolidated.periodic_payment;
+        Ok(ConsolidationAnalysis {
+            weighted_average_rate,
+            consolidated_rate,
+            separate_payment,
+            consolidated_payment,
+            payment_savings: separate_payment - consolidated_payment,
+            additional_interest: consolidated.total_interest - separate.total_interest,
+            consolidated,
+            separate,
+        })
+    }
+}
+
+impl ConsolidationAnalysis {
+    // The consolidated loan first, then each separate loan, for a side-by-side table
+    pub fn comparison(&self) -> LoanComparison {
+        let mut loans = vec![self.consolidated.clone()];
+        loans.extend(self.separate.loans.iter().cloned());
+        LoanComparison::new(loans)
+    }
+
+    // Month from which the separate loans cost less per month than the consolidated one,
+    // as the shorter of them are paid off; None when they never do
+    pub fn crossover_month(&self) -> Option<u32> {
+        (0..self.consolidated.schedule.len())
+            .find(|&month| {
+                let separate = self.separate.cash_flows.get(month).copied().unwrap_or(0.0);
+                separate < self.consolidated.schedule[month].installment_amount
+            })
+            .map(|month| month as u32 + 1)
+    }
+}
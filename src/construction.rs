@@ -0,0 +1,141 @@
+use chrono::NaiveDate;
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::calendar::DateRollConvention;
+use crate::error::AmortizationError;
+use crate::frequency::PaymentFrequency;
+use crate::money::Currency;
+
+// Funds disbursed at the start of draw period `period`, accruing interest from then on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Draw {
+    pub period: u32,
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstructionLoan {
+    pub apr: f64,
+    // Monthly interest-only periods during which draws can be taken
+    pub draw_periods: u32,
+    // Term of the permanent loan the drawn balance converts into
+    pub amortization_months: u32,
+    pub draws: Vec<Draw>,
+    pub start_date: Option<NaiveDate>,
+    pub currency: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawRow {
+    pub period: u32,
+    pub date: Option<NaiveDate>,
+    pub draw: f64,
+    pub drawn_balance: f64,
+    // Interest-only payment on the drawn balance
+    pub interest: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstructionSchedule {
+    pub draw_phase: Vec<DrawRow>,
+    pub total_drawn: f64,
+    pub draw_interest: f64,
+    // Fully amortizing loan on the final drawn balance, numbered after the draw periods
+    pub permanent: Amortization,
+    pub total_interest: f64,
+    pub total_payment: f64,
+}
+
+impl Draw {
+    pub fn new(period: u32, amount: f64) -> Self {
+        Draw { period, amount }
+    }
+}
+
+impl ConstructionLoan {
+    pub fn new(apr: f64, draw_periods: u32, amortization_months: u32) -> Self {
+        ConstructionLoan {
+            apr,
+            draw_periods,
+            amortization_months,
+            draws: Vec::new(),
+            start_date: None,
+            currency: Currency::default(),
+        }
+    }
+
+    pub fn with_draw(mut self, period: u32, amount: f64) -> Self {
+        self.draws.push(Draw::new(period, amount));
+        self
+    }
+
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.apr < 0.0 || !self.apr.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(self.apr));
+        }
+        if self.draw_periods == 0 {
+            return Err(AmortizationError::InvalidPeriods(self.draw_periods));
+        }
+        if self.draws.is_empty() {
+            return Err(AmortizationError::MissingParameter("draws"));
+        }
+        for draw in &self.draws {
+            if draw.period == 0 || draw.period > self.draw_periods {
+                return Err(AmortizationError::PeriodOutOfRange(draw.period));
+            }
+            if draw.amount <= 0.0 || !draw.amount.is_finite() {
+                return Err(AmortizationError::InvalidLoanAmount(draw.amount));
+            }
+        }
+        Ok(())
+    }
+
+    fn due_date(&self, period: u32) -> Option<NaiveDate> {
+        let start_date = self.start_date?;
+        PaymentFrequency::Monthly.due_date(start_date, period as i32 - 1, DateRollConvention::SameDay)
+    }
+
+    pub fn schedule(&self) -> Result<ConstructionSchedule, AmortizationError> {
+        self.validate()?;
+        let monthly_rate = self.apr / 100.0 / 12.0;
+        let mut drawn_balance = 0.0;
+        let draw_phase: Vec<DrawRow> = (1..=self.draw_periods).map(|period| {
+            let draw: f64 = self.draws.iter().filter(|d| d.period == period).map(|d| d.amount).sum();
+            drawn_balance += draw;
+            DrawRow {
+                period,
+                date: self.due_date(period),
+                draw,
+                drawn_balance,
+                interest: self.currency.round(drawn_balance * monthly_rate),
+            }
+        }).collect();
+
+        let mut permanent = Amortization::from_config(CalculatorConfig {
+            currency: self.currency,
+            ..CalculatorConfig::new(drawn_balance, self.apr, self.amortization_months, self.due_date(self.draw_periods + 1))
+        })?;
+        for payment in &mut permanent.schedule {
+            payment.installment_number += self.draw_periods;
+        }
+
+        let draw_interest: f64 = draw_phase.iter().map(|row| row.interest).sum();
+        Ok(ConstructionSchedule {
+            total_drawn: drawn_balance,
+            draw_interest,
+            total_interest: draw_interest + permanent.total_interest,
+            total_payment: draw_interest + permanent.total_payment,
+            draw_phase,
+            permanent,
+        })
+    }
+}
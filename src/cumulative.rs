@@ -0,0 +1,43 @@
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CumulativePayment {
+    pub installment_number: u32,
+    pub date: Option<NaiveDate>,
+    // Running totals through this installment
+    pub cumulative_interest: f64,
+    pub cumulative_principal: f64,
+    pub cumulative_paid: f64,
+    // Principal repaid so far as a percentage of everything borrowed, including interest
+    // capitalized up to this installment
+    pub percent_paid: f64,
+    pub remaining_balance: f64,
+}
+
+impl Amortization {
+    // Running totals for every installment, so charts and summaries don't have to re-scan
+    // the schedule
+    pub fn cumulative_schedule(&self) -> Vec<CumulativePayment> {
+        let mut interest = 0.0;
+        let mut principal = 0.0;
+        let mut paid = 0.0;
+        let mut borrowed = self.balance;
+
+        self.schedule.iter().map(|payment| {
+            interest += payment.interest;
+            principal += payment.principal;
+            paid += payment.installment_amount;
+            borrowed += payment.capitalized_interest;
+            CumulativePayment {
+                installment_number: payment.installment_number,
+                date: payment.date,
+                cumulative_interest: interest,
+                cumulative_principal: principal,
+                cumulative_paid: paid,
+                percent_paid: if borrowed > 0.0 { principal / borrowed * 100.0 } else { 100.0 },
+                remaining_balance: payment.remaining_balance,
+            }
+        }).collect()
+    }
+}
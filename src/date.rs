@@ -0,0 +1,69 @@
+// Dates in and out of schedules as plain year, month and day, for projects whose dates are
+// another crate's type (such as `time::Date`): implement `ScheduleDate` on a newtype over it.
+// Schedules still do their calendar arithmetic with chrono internally
+use chrono::{Datelike, NaiveDate};
+use crate::builder::AmortizationBuilder;
+use crate::calculator::Amortization;
+use crate::payment::Payment;
+
+pub trait ScheduleDate: Sized {
+    // None when the year, month and day don't make a date the type can hold
+    fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self>;
+
+    fn ymd(&self) -> (i32, u32, u32);
+}
+
+impl ScheduleDate for NaiveDate {
+    fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        NaiveDate::from_ymd_opt(year, month, day)
+    }
+
+    fn ymd(&self) -> (i32, u32, u32) {
+        (self.year(), self.month(), self.day())
+    }
+}
+
+// Only valid calendar dates convert back from a schedule
+impl ScheduleDate for (i32, u32, u32) {
+    fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        NaiveDate::from_ymd_opt(year, month, day).map(|_| (year, month, day))
+    }
+
+    fn ymd(&self) -> (i32, u32, u32) {
+        *self
+    }
+}
+
+fn convert<From: ScheduleDate, To: ScheduleDate>(date: &From) -> Option<To> {
+    let (year, month, day) = date.ymd();
+    To::from_ymd(year, month, day)
+}
+
+impl AmortizationBuilder {
+    // As `start_date`. A date that isn't a real calendar date within chrono's range of years
+    // makes `build()` fail with `InvalidStartDate`
+    pub fn start_on<D: ScheduleDate>(self, date: D) -> Self {
+        let (year, month, day) = date.ymd();
+        match NaiveDate::from_ymd_opt(year, month, day) {
+            Some(date) => self.start_date(date),
+            None => self.invalid_start_date(year, month, day),
+        }
+    }
+}
+
+impl Payment {
+    // None for undated schedules, or a date `D` can't hold
+    pub fn date_as<D: ScheduleDate>(&self) -> Option<D> {
+        self.date.as_ref().and_then(convert)
+    }
+}
+
+impl Amortization {
+    pub fn start_date_as<D: ScheduleDate>(&self) -> Option<D> {
+        self.start_date.as_ref().and_then(convert)
+    }
+
+    pub fn end_date_as<D: ScheduleDate>(&self) -> Option<D> {
+        self.end_date.as_ref().and_then(convert)
+    }
+}
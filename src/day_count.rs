@@ -0,0 +1,40 @@
+use chrono::{Datelike, NaiveDate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayCount {
+    Thirty360,
+    Actual365,
+    Actual360,
+}
+
+impl DayCount {
+    pub fn days_between(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        match self {
+            DayCount::Thirty360 => {
+                let mut d1 = start.day() as i64;
+                let mut d2 = end.day() as i64;
+                if d1 == 31 {
+                    d1 = 30;
+                }
+                if d2 == 31 && d1 == 30 {
+                    d2 = 30;
+                }
+                360 * (end.year() as i64 - start.year() as i64)
+                    + 30 * (end.month() as i64 - start.month() as i64)
+                    + (d2 - d1)
+            }
+            DayCount::Actual365 | DayCount::Actual360 => (end - start).num_days(),
+        }
+    }
+
+    pub fn days_in_year(&self) -> f64 {
+        match self {
+            DayCount::Thirty360 | DayCount::Actual360 => 360.0,
+            DayCount::Actual365 => 365.0,
+        }
+    }
+
+    pub fn year_fraction(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        self.days_between(start, end) as f64 / self.days_in_year()
+    }
+}
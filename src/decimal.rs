@@ -0,0 +1,344 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use crate::money::RoundingMode;
+use crate::num::AmortNum;
+
+const SCALE_DIGITS: u32 = 12;
+const SCALE: i128 = 10i128.pow(SCALE_DIGITS);
+// Digits an f64 is trusted to before truncating, so a sum of cents that lands a hair short in
+// binary doesn't lose one
+const TRUNCATE_DIGITS: u32 = 9;
+
+// Fixed-point decimal with 12 fractional digits, stored as a scaled i128
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Decimal {
+    raw: i128,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDecimalError(String);
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid decimal literal: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseDecimalError {}
+
+impl Decimal {
+    pub const ZERO: Decimal = Decimal { raw: 0 };
+    pub const ONE: Decimal = Decimal { raw: SCALE };
+
+    pub fn new(mantissa: i64, scale: u32) -> Self {
+        assert!(scale <= SCALE_DIGITS, "Decimal scale must be at most {}", SCALE_DIGITS);
+        Decimal { raw: mantissa as i128 * 10i128.pow(SCALE_DIGITS - scale) }
+    }
+
+    pub fn try_from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        // Go through the shortest round-trip representation so 0.1 becomes exactly 0.1
+        value.to_string().parse().ok()
+    }
+
+    // `try_from_f64` for a value about to be rounded with `mode`. Truncation first settles it
+    // on the nearest billionth, since an f64 within one below a boundary is on it but for
+    // binary error
+    pub(crate) fn try_from_f64_with(value: f64, mode: RoundingMode) -> Option<Self> {
+        let decimal = Decimal::try_from_f64(value)?;
+        Some(match mode {
+            RoundingMode::Truncate => decimal.round_dp(TRUNCATE_DIGITS),
+            _ => decimal,
+        })
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.raw as f64 / SCALE as f64
+    }
+
+    pub fn round_dp(self, dp: u32) -> Self {
+        if dp >= SCALE_DIGITS {
+            return self;
+        }
+        let unit = 10i128.pow(SCALE_DIGITS - dp);
+        Decimal { raw: div_round(self.raw, unit) * unit }
+    }
+
+    pub fn round_dp_with(self, dp: u32, mode: RoundingMode) -> Self {
+        if dp >= SCALE_DIGITS {
+            return self;
+        }
+        let unit = 10i128.pow(SCALE_DIGITS - dp);
+        Decimal { raw: div_round_with(self.raw, unit, mode) * unit }
+    }
+
+    // The value as a whole number of `10^-dp` units, e.g. cents for `dp = 2`
+    pub fn to_minor_units(self, dp: u32, mode: RoundingMode) -> i128 {
+        if dp >= SCALE_DIGITS {
+            return self.raw * 10i128.pow(dp - SCALE_DIGITS);
+        }
+        div_round_with(self.raw, 10i128.pow(SCALE_DIGITS - dp), mode)
+    }
+
+    pub fn abs(self) -> Self {
+        Decimal { raw: self.raw.abs() }
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.raw == 0
+    }
+
+    pub fn is_sign_negative(self) -> bool {
+        self.raw < 0
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.raw.checked_add(rhs.raw).map(|raw| Decimal { raw })
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.raw.checked_sub(rhs.raw).map(|raw| Decimal { raw })
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let whole = self.raw / SCALE;
+        let fraction = self.raw % SCALE;
+        let whole_part = whole.checked_mul(rhs.raw)?;
+        let fraction_part = match fraction.checked_mul(rhs.raw) {
+            Some(product) => div_round(product, SCALE),
+            // A large multiplier is split so its whole units never need scaling back down
+            None => fraction.checked_mul(rhs.raw / SCALE)?.checked_add(div_round(fraction * (rhs.raw % SCALE), SCALE))?,
+        };
+        whole_part.checked_add(fraction_part).map(|raw| Decimal { raw })
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.raw == 0 {
+            return None;
+        }
+        let quotient = self.raw / rhs.raw;
+        let remainder = self.raw % rhs.raw;
+        let whole_part = quotient.checked_mul(SCALE)?;
+        let fraction_part = match remainder.checked_mul(SCALE) {
+            Some(scaled) => div_round(scaled, rhs.raw),
+            None => long_division_fraction(remainder, rhs.raw)?,
+        };
+        whole_part.checked_add(fraction_part).map(|raw| Decimal { raw })
+    }
+}
+
+// `remainder / denominator` in units of 1/SCALE, a digit at a time, for divisors too large
+// for the remainder to be scaled in one step
+fn long_division_fraction(remainder: i128, denominator: i128) -> Option<i128> {
+    let divisor = denominator.unsigned_abs();
+    let mut left = remainder.unsigned_abs();
+    let mut fraction = 0u128;
+    for _ in 0..SCALE_DIGITS {
+        left = left.checked_mul(10)?;
+        fraction = fraction * 10 + left / divisor;
+        left %= divisor;
+    }
+    if left >= divisor - left {
+        fraction += 1;
+    }
+    let fraction = fraction as i128;
+    Some(if (remainder < 0) == (denominator < 0) { fraction } else { -fraction })
+}
+
+// Integer division rounding half away from zero
+fn div_round(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    if remainder.abs() * 2 >= denominator.abs() {
+        if (numerator < 0) == (denominator < 0) {
+            quotient + 1
+        } else {
+            quotient - 1
+        }
+    } else {
+        quotient
+    }
+}
+
+fn div_round_with(numerator: i128, denominator: i128, mode: RoundingMode) -> i128 {
+    match mode {
+        RoundingMode::HalfUp => div_round(numerator, denominator),
+        RoundingMode::HalfEven => {
+            let quotient = numerator / denominator;
+            let remainder = (numerator % denominator).abs() * 2;
+            let away = if (numerator < 0) == (denominator < 0) { quotient + 1 } else { quotient - 1 };
+            match remainder.cmp(&denominator.abs()) {
+                std::cmp::Ordering::Greater => away,
+                std::cmp::Ordering::Equal if quotient % 2 != 0 => away,
+                _ => quotient,
+            }
+        }
+        RoundingMode::Truncate => numerator / denominator,
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = ParseDecimalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseDecimalError(s.to_string());
+        let trimmed = s.trim();
+        let (negative, digits) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if whole.is_empty() && fraction.is_empty() {
+            return Err(error());
+        }
+        if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+            return Err(error());
+        }
+
+        let whole_value: i128 = if whole.is_empty() { 0 } else { whole.parse().map_err(|_| error())? };
+        let mut raw = whole_value.checked_mul(SCALE).ok_or_else(error)?;
+
+        let kept = &fraction[..fraction.len().min(SCALE_DIGITS as usize)];
+        if !kept.is_empty() {
+            let fraction_value: i128 = kept.parse().map_err(|_| error())?;
+            raw += fraction_value * 10i128.pow(SCALE_DIGITS - kept.len() as u32);
+        }
+        if fraction[kept.len()..].chars().next().is_some_and(|c| c >= '5') {
+            raw += 1;
+        }
+
+        Ok(Decimal { raw: if negative { -raw } else { raw } })
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match f.precision() {
+            Some(dp) => self.round_dp(dp as u32),
+            None => *self,
+        };
+        let sign = if value.raw < 0 { "-" } else { "" };
+        let whole = (value.raw / SCALE).abs();
+        let fraction = format!("{:0width$}", (value.raw % SCALE).abs(), width = SCALE_DIGITS as usize);
+
+        let fraction = match f.precision() {
+            Some(dp) => fraction[..dp.min(SCALE_DIGITS as usize)].to_string(),
+            None => fraction.trim_end_matches('0').to_string(),
+        };
+
+        if fraction.is_empty() {
+            write!(f, "{}{}", sign, whole)
+        } else {
+            write!(f, "{}{}.{}", sign, whole, fraction)
+        }
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Self {
+        Decimal { raw: value as i128 * SCALE }
+    }
+}
+
+impl From<u32> for Decimal {
+    fn from(value: u32) -> Self {
+        Decimal { raw: value as i128 * SCALE }
+    }
+}
+
+impl Add for Decimal {
+    type Output = Decimal;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("Decimal addition overflow")
+    }
+}
+
+impl Sub for Decimal {
+    type Output = Decimal;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs).expect("Decimal subtraction overflow")
+    }
+}
+
+impl Mul for Decimal {
+    type Output = Decimal;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs).expect("Decimal multiplication overflow")
+    }
+}
+
+impl Div for Decimal {
+    type Output = Decimal;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        assert!(rhs.raw != 0, "Decimal division by zero");
+        self.checked_div(rhs).expect("Decimal division overflow")
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Self::Output {
+        Decimal { raw: -self.raw }
+    }
+}
+
+impl AddAssign for Decimal {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Decimal {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sum for Decimal {
+    fn sum<I: Iterator<Item = Decimal>>(iter: I) -> Self {
+        iter.fold(Decimal::ZERO, |acc, value| acc + value)
+    }
+}
+
+impl AmortNum for Decimal {
+    fn zero() -> Self {
+        Decimal::ZERO
+    }
+
+    fn one() -> Self {
+        Decimal::ONE
+    }
+
+    fn from_f64(value: f64) -> Self {
+        Decimal::try_from_f64(value).expect("Decimal cannot represent a non-finite value")
+    }
+
+    fn checked_from_f64(value: f64) -> Option<Self> {
+        Decimal::try_from_f64(value)
+    }
+
+    fn to_f64(self) -> f64 {
+        Decimal::to_f64(self)
+    }
+
+    fn round_dp(self, places: u32) -> Self {
+        Decimal::round_dp(self, places)
+    }
+
+    fn round_dp_with(self, places: u32, mode: RoundingMode) -> Self {
+        Decimal::round_dp_with(self, places, mode)
+    }
+
+    fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Decimal::checked_mul(self, rhs)
+    }
+}
@@ -0,0 +1,209 @@
+// Depreciation of fixed assets and amortization of intangibles: the cost less salvage value is
+// expensed over the useful life, one row per period like a loan schedule
+use chrono::NaiveDate;
+use crate::calendar::DateRollConvention;
+use crate::error::AmortizationError;
+use crate::frequency::PaymentFrequency;
+use crate::money::Currency;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum DepreciationMethod {
+    #[default]
+    StraightLine,
+    // `factor` times the straight-line rate on the remaining book value, 2.0 for double
+    // declining balance. With the switch, straight line takes over once it expenses more
+    DecliningBalance { factor: f64, switch_to_straight_line: bool },
+    SumOfYearsDigits,
+    // Expense in proportion to the units produced each period out of `total_units` over the
+    // asset's life; one period per entry of `units`
+    UnitsOfProduction { total_units: f64, units: Vec<f64> },
+}
+
+impl DepreciationMethod {
+    pub fn double_declining() -> Self {
+        DepreciationMethod::DecliningBalance { factor: 2.0, switch_to_straight_line: true }
+    }
+
+    pub fn units_of_production(total_units: f64, units: Vec<f64>) -> Self {
+        DepreciationMethod::UnitsOfProduction { total_units, units }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Asset {
+    pub cost: f64,
+    pub salvage_value: f64,
+    pub useful_life_years: u32,
+    pub method: DepreciationMethod,
+    pub frequency: PaymentFrequency,
+    pub start_date: Option<NaiveDate>,
+    pub currency: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepreciationRow {
+    pub period: u32,
+    // End of the period
+    pub date: Option<NaiveDate>,
+    pub beginning_book_value: f64,
+    pub depreciation: f64,
+    pub accumulated_depreciation: f64,
+    pub ending_book_value: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct DepreciationSchedule {
+    pub rows: Vec<DepreciationRow>,
+    pub total_depreciation: f64,
+    pub final_book_value: f64,
+}
+
+fn asset_error(message: &str) -> AmortizationError {
+    AmortizationError::InvalidAsset(message.to_string())
+}
+
+impl Asset {
+    pub fn new(cost: f64, salvage_value: f64, useful_life_years: u32) -> Self {
+        Asset {
+            cost,
+            salvage_value,
+            useful_life_years,
+            method: DepreciationMethod::StraightLine,
+            frequency: PaymentFrequency::Annually,
+            start_date: None,
+            currency: Currency::default(),
+        }
+    }
+
+    pub fn with_method(mut self, method: DepreciationMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    // Monthly rows for intangibles amortized on the books each month; annual by default
+    pub fn with_frequency(mut self, frequency: PaymentFrequency) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    // Date the asset is placed in service
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    pub fn periods(&self) -> u32 {
+        match &self.method {
+            DepreciationMethod::UnitsOfProduction { units, .. } => units.len() as u32,
+            _ => self.useful_life_years.saturating_mul(self.frequency.periods_per_year()),
+        }
+    }
+
+    pub fn depreciable_amount(&self) -> f64 {
+        self.cost - self.salvage_value
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.cost <= 0.0 || !self.cost.is_finite() {
+            return Err(asset_error(&format!("cost must be greater than 0, got {}", self.cost)));
+        }
+        if self.salvage_value < 0.0 || self.salvage_value > self.cost || !self.salvage_value.is_finite() {
+            return Err(asset_error(&format!("salvage value must be between 0 and the cost, got {}", self.salvage_value)));
+        }
+        if self.periods() == 0 {
+            return Err(AmortizationError::InvalidPeriods(self.periods()));
+        }
+        match &self.method {
+            DepreciationMethod::DecliningBalance { factor, .. } if *factor <= 0.0 || !factor.is_finite() => {
+                Err(asset_error(&format!("declining balance factor must be greater than 0, got {}", factor)))
+            }
+            DepreciationMethod::UnitsOfProduction { total_units, units } => {
+                if *total_units <= 0.0 || !total_units.is_finite() {
+                    return Err(asset_error(&format!("total units must be greater than 0, got {}", total_units)));
+                }
+                match units.iter().find(|u| **u < 0.0 || !u.is_finite()) {
+                    Some(invalid) => Err(asset_error(&format!("units produced must not be negative, got {}", invalid))),
+                    None => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Expense for `period` on a book value of `book_value`, before capping at the salvage value
+    fn period_depreciation(&self, period: u32, book_value: f64) -> f64 {
+        let periods = self.periods();
+        let remaining = periods - period + 1;
+        let straight_line = (book_value - self.salvage_value) / remaining as f64;
+        match &self.method {
+            DepreciationMethod::StraightLine => self.depreciable_amount() / periods as f64,
+            DepreciationMethod::DecliningBalance { factor, switch_to_straight_line } => {
+                let declining = book_value * factor / periods as f64;
+                if *switch_to_straight_line { declining.max(straight_line) } else { declining }
+            }
+            DepreciationMethod::SumOfYearsDigits => {
+                let digits = periods as f64 * (periods as f64 + 1.0) / 2.0;
+                self.depreciable_amount() * remaining as f64 / digits
+            }
+            DepreciationMethod::UnitsOfProduction { total_units, units } => {
+                self.depreciable_amount() * units[period as usize - 1] / total_units
+            }
+        }
+    }
+
+    // Depreciation rounded to the currency's minor units each period. Methods that fully
+    // depreciate the asset take the rounding difference in the last period so it ends exactly
+    // at the salvage value
+    pub fn schedule(&self) -> Result<DepreciationSchedule, AmortizationError> {
+        self.validate()?;
+        let periods = self.periods();
+        let fully_depreciates = match &self.method {
+            DepreciationMethod::DecliningBalance { switch_to_straight_line, .. } => *switch_to_straight_line,
+            DepreciationMethod::UnitsOfProduction { .. } => false,
+            _ => true,
+        };
+
+        let mut book_value = self.cost;
+        let mut accumulated = 0.0;
+        let rows: Vec<DepreciationRow> = (1..=periods).map(|period| {
+            let beginning_book_value = book_value;
+            let remaining = self.currency.round(beginning_book_value - self.salvage_value);
+            let depreciation = if period == periods && fully_depreciates {
+                remaining
+            } else {
+                self.currency.round(self.period_depreciation(period, beginning_book_value)).min(remaining)
+            };
+            accumulated = self.currency.round(accumulated + depreciation);
+            book_value = self.currency.round(beginning_book_value - depreciation);
+            DepreciationRow {
+                period,
+                date: self.start_date.and_then(|start| self.frequency.due_date(start, period as i32, DateRollConvention::SameDay)),
+                beginning_book_value,
+                depreciation,
+                accumulated_depreciation: accumulated,
+                ending_book_value: book_value,
+            }
+        }).collect();
+
+        Ok(DepreciationSchedule {
+            rows,
+            total_depreciation: accumulated,
+            final_book_value: book_value,
+        })
+    }
+}
+
+impl DepreciationSchedule {
+    // Book value once `periods` periods have been expensed
+    pub fn book_value_after(&self, periods: u32) -> Option<f64> {
+        match periods {
+            0 => self.rows.first().map(|row| row.beginning_book_value),
+            n => self.rows.get(n as usize - 1).map(|row| row.ending_book_value),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// Comparing schedules amount by amount, e.g. against a lender's statement, where exact
+// float equality is too strict
+use std::fmt;
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::payment::Payment;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScheduleDiff {
+    Length { expected: usize, actual: usize },
+    Date { installment_number: u32, expected: Option<NaiveDate>, actual: Option<NaiveDate> },
+    Amount { installment_number: u32, field: &'static str, expected: f64, actual: f64 },
+}
+
+impl fmt::Display for ScheduleDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScheduleDiff::Length { expected, actual } => write!(f, "Expected {} installments, got {}", expected, actual),
+            ScheduleDiff::Date { installment_number, expected, actual } => {
+                write!(f, "Installment {} date: expected {:?}, got {:?}", installment_number, expected, actual)
+            }
+            ScheduleDiff::Amount { installment_number, field, expected, actual } => {
+                write!(f, "Installment {} {}: expected {:.2}, got {:.2}", installment_number, field, expected, actual)
+            }
+        }
+    }
+}
+
+impl Payment {
+    fn amounts(&self) -> [(&'static str, f64); 8] {
+        [
+            ("beginning_balance", self.beginning_balance),
+            ("installment_amount", self.installment_amount),
+            ("interest", self.interest),
+            ("principal", self.principal),
+            ("remaining_balance", self.remaining_balance),
+            ("balloon_amount", self.balloon_amount),
+            ("capitalized_interest", self.capitalized_interest),
+            ("deferred_amount", self.deferred_amount),
+        ]
+    }
+
+    // First field that differs by more than `tolerance`, treating `self` as the expected row
+    pub fn diff(&self, other: &Payment, tolerance: f64) -> Option<ScheduleDiff> {
+        if self.date != other.date {
+            return Some(ScheduleDiff::Date { installment_number: self.installment_number, expected: self.date, actual: other.date });
+        }
+        self.amounts().into_iter().zip(other.amounts())
+            .find(|((_, expected), (_, actual))| (expected - actual).abs() > tolerance || expected.is_nan() != actual.is_nan())
+            .map(|((field, expected), (_, actual))| ScheduleDiff::Amount {
+                installment_number: self.installment_number,
+                field,
+                expected,
+                actual,
+            })
+    }
+
+    pub fn approx_eq(&self, other: &Payment, tolerance: f64) -> bool {
+        self.diff(other, tolerance).is_none()
+    }
+}
+
+// First difference between two schedules, row by row, then in length
+pub fn diff_schedules(expected: &[Payment], actual: &[Payment], tolerance: f64) -> Option<ScheduleDiff> {
+    expected.iter().zip(actual)
+        .find_map(|(expected, actual)| expected.diff(actual, tolerance))
+        .or_else(|| (expected.len() != actual.len()).then_some(ScheduleDiff::Length { expected: expected.len(), actual: actual.len() }))
+}
+
+impl Amortization {
+    pub fn diff(&self, other: &[Payment], tolerance: f64) -> Option<ScheduleDiff> {
+        diff_schedules(&self.schedule, other, tolerance)
+    }
+
+    pub fn approx_eq(&self, other: &Amortization, tolerance: f64) -> bool {
+        self.diff(&other.schedule, tolerance).is_none()
+    }
+}
@@ -0,0 +1,337 @@
+// Collection files for lenders that take installments by direct debit: ISO 20022 pain.008
+// customer direct debit initiations, as SEPA banks accept them, or fixed-width records in
+// whatever layout the lender's bank asks for. One collection per installment with an amount due
+use chrono::{NaiveDate, NaiveDateTime};
+use crate::calculator::Amortization;
+use crate::error::AmortizationError;
+use crate::money::Money;
+
+const PAIN_008_NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:pain.008.001.02";
+// Longest identifier the message allows
+const MAX_ID_LENGTH: usize = 35;
+
+// The lender collecting or the borrower paying
+#[derive(Debug, Clone, PartialEq)]
+pub struct Party {
+    pub name: String,
+    pub iban: String,
+    pub bic: Option<String>,
+}
+
+impl Party {
+    pub fn new(name: &str, iban: &str) -> Self {
+        Party { name: name.to_string(), iban: iban.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_ascii_uppercase(), bic: None }
+    }
+
+    pub fn with_bic(mut self, bic: &str) -> Self {
+        self.bic = Some(bic.to_ascii_uppercase());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectDebit {
+    pub creditor: Party,
+    // The creditor identifier the lender's bank issued, e.g. a SEPA creditor ID
+    pub creditor_id: String,
+    pub debtor: Party,
+    pub mandate_id: String,
+    pub mandate_signed: NaiveDate,
+    pub message_id: String,
+    pub created_at: NaiveDateTime,
+    // First and last installment to collect; the whole schedule when not set
+    pub installments: Option<(u32, u32)>,
+    // Collect escrow along with the installment
+    pub include_escrow: bool,
+}
+
+impl DirectDebit {
+    pub fn new(creditor: Party, creditor_id: &str, debtor: Party, mandate_id: &str, mandate_signed: NaiveDate) -> Self {
+        DirectDebit {
+            creditor,
+            creditor_id: creditor_id.to_string(),
+            debtor,
+            mandate_id: mandate_id.to_string(),
+            mandate_signed,
+            message_id: mandate_id.to_string(),
+            created_at: mandate_signed.and_hms_opt(0, 0, 0).expect("midnight is a valid time"),
+            installments: None,
+            include_escrow: false,
+        }
+    }
+
+    pub fn with_message_id(mut self, message_id: &str) -> Self {
+        self.message_id = message_id.to_string();
+        self
+    }
+
+    pub fn with_created_at(mut self, created_at: NaiveDateTime) -> Self {
+        self.created_at = created_at;
+        self
+    }
+
+    pub fn with_installments(mut self, first: u32, last: u32) -> Self {
+        self.installments = Some((first, last));
+        self
+    }
+
+    pub fn with_escrow(mut self, include_escrow: bool) -> Self {
+        self.include_escrow = include_escrow;
+        self
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        let invalid = |message: String| Err(AmortizationError::InvalidDirectDebit(message));
+        for party in [&self.creditor, &self.debtor] {
+            if party.name.trim().is_empty() {
+                return invalid("every party needs a name".to_string());
+            }
+            if !is_valid_iban(&party.iban) {
+                return invalid(format!("invalid IBAN '{}'", party.iban));
+            }
+            if let Some(bic) = party.bic.as_ref().filter(|bic| !is_valid_bic(bic)) {
+                return invalid(format!("invalid BIC '{}'", bic));
+            }
+        }
+        for (field, id) in [("creditor ID", &self.creditor_id), ("mandate ID", &self.mandate_id), ("message ID", &self.message_id)] {
+            if id.is_empty() || id.len() > MAX_ID_LENGTH {
+                return invalid(format!("the {} must be 1 to {} characters", field, MAX_ID_LENGTH));
+            }
+        }
+        if let Some((first, last)) = self.installments.filter(|(first, last)| *first == 0 || first > last) {
+            return invalid(format!("invalid installment range {} to {}", first, last));
+        }
+        Ok(())
+    }
+}
+
+// Two letters of country, two check digits and up to 30 letters and digits, checked mod 97
+pub fn is_valid_iban(iban: &str) -> bool {
+    let bytes = iban.as_bytes();
+    if !(15..=34).contains(&bytes.len()) || !bytes.iter().all(u8::is_ascii_alphanumeric)
+        || !bytes[..2].iter().all(u8::is_ascii_uppercase) || !bytes[2..4].iter().all(u8::is_ascii_digit) {
+        return false;
+    }
+    let remainder = bytes[4..].iter().chain(&bytes[..4]).fold(0u32, |remainder, &byte| match byte {
+        b'0'..=b'9' => (remainder * 10 + (byte - b'0') as u32) % 97,
+        _ => (remainder * 100 + (byte.to_ascii_uppercase() - b'A' + 10) as u32) % 97,
+    });
+    remainder == 1
+}
+
+fn is_valid_bic(bic: &str) -> bool {
+    (bic.len() == 8 || bic.len() == 11) && bic.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        && bic.bytes().take(4).all(|b| b.is_ascii_uppercase())
+}
+
+// Where a collection falls in the mandate's run of debits, which banks handle differently
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceType {
+    First,
+    Recurring,
+    Final,
+}
+
+impl SequenceType {
+    pub fn code(&self) -> &'static str {
+        match self {
+            SequenceType::First => "FRST",
+            SequenceType::Recurring => "RCUR",
+            SequenceType::Final => "FNAL",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Collection {
+    pub installment_number: u32,
+    pub due_date: NaiveDate,
+    pub amount: Money,
+    pub sequence: SequenceType,
+    pub end_to_end_id: String,
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn format_money(money: Money) -> String {
+    format!("{:.*}", money.currency().minor_units() as usize, money.to_f64())
+}
+
+fn agent(party: &Party) -> String {
+    match &party.bic {
+        Some(bic) => format!("<FinInstnId><BIC>{}</BIC></FinInstnId>", escape(bic)),
+        None => "<FinInstnId><Othr><Id>NOTPROVIDED</Id></Othr></FinInstnId>".to_string(),
+    }
+}
+
+// A field of a fixed-width record. Text is left aligned and cut to its width; numbers are
+// zero padded and must fit
+#[derive(Debug, Clone, PartialEq)]
+pub enum FixedWidthField {
+    Installment(usize),
+    // A chrono format string such as "%Y%m%d"; the field is as wide as the date it writes
+    DueDate(String),
+    // In minor units, e.g. cents
+    Amount(usize),
+    Currency,
+    Sequence,
+    MandateId(usize),
+    EndToEndId(usize),
+    DebtorName(usize),
+    DebtorIban(usize),
+    DebtorBic(usize),
+    CreditorId(usize),
+    Literal(String),
+    Filler(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedWidthLayout {
+    pub fields: Vec<FixedWidthField>,
+    pub line_ending: String,
+}
+
+impl Default for FixedWidthLayout {
+    fn default() -> Self {
+        FixedWidthLayout {
+            fields: vec![
+                FixedWidthField::Literal("D".to_string()),
+                FixedWidthField::DueDate("%Y%m%d".to_string()),
+                FixedWidthField::Sequence,
+                FixedWidthField::MandateId(35),
+                FixedWidthField::DebtorIban(34),
+                FixedWidthField::DebtorName(70),
+                FixedWidthField::Currency,
+                FixedWidthField::Amount(12),
+                FixedWidthField::Installment(4),
+            ],
+            line_ending: "\r\n".to_string(),
+        }
+    }
+}
+
+impl FixedWidthLayout {
+    pub fn new() -> Self {
+        FixedWidthLayout { fields: Vec::new(), line_ending: "\n".to_string() }
+    }
+
+    pub fn field(mut self, field: FixedWidthField) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    pub fn with_line_ending(mut self, line_ending: &str) -> Self {
+        self.line_ending = line_ending.to_string();
+        self
+    }
+
+    fn record(&self, collection: &Collection, direct_debit: &DirectDebit) -> Result<String, AmortizationError> {
+        let text = |value: &str, width: usize| format!("{:<width$}", value.chars().take(width).collect::<String>(), width = width);
+        let number = |value: i64, width: usize, field: &str| {
+            let digits = format!("{:0width$}", value, width = width);
+            if digits.len() > width || value < 0 {
+                return Err(AmortizationError::InvalidDirectDebit(format!(
+                    "{} {} of installment {} does not fit {} digits", field, value, collection.installment_number, width
+                )));
+            }
+            Ok(digits)
+        };
+        let mut record = String::new();
+        for field in &self.fields {
+            record.push_str(&match field {
+                FixedWidthField::Installment(width) => number(collection.installment_number as i64, *width, "installment")?,
+                FixedWidthField::DueDate(format) => collection.due_date.format(format).to_string(),
+                FixedWidthField::Amount(width) => number(collection.amount.minor(), *width, "amount")?,
+                FixedWidthField::Currency => collection.amount.currency().code().to_string(),
+                FixedWidthField::Sequence => collection.sequence.code().to_string(),
+                FixedWidthField::MandateId(width) => text(&direct_debit.mandate_id, *width),
+                FixedWidthField::EndToEndId(width) => text(&collection.end_to_end_id, *width),
+                FixedWidthField::DebtorName(width) => text(&direct_debit.debtor.name, *width),
+                FixedWidthField::DebtorIban(width) => text(&direct_debit.debtor.iban, *width),
+                FixedWidthField::DebtorBic(width) => text(direct_debit.debtor.bic.as_deref().unwrap_or_default(), *width),
+                FixedWidthField::CreditorId(width) => text(&direct_debit.creditor_id, *width),
+                FixedWidthField::Literal(literal) => literal.clone(),
+                FixedWidthField::Filler(width) => " ".repeat(*width),
+            });
+        }
+        Ok(record)
+    }
+}
+
+impl Amortization {
+    // Installments with nothing due, such as deferred or skipped ones, are not collected
+    pub fn direct_debit_collections(&self, direct_debit: &DirectDebit) -> Result<Vec<Collection>, AmortizationError> {
+        direct_debit.validate()?;
+        let due: Vec<_> = self.schedule.iter()
+            .map(|p| (p, p.installment_amount + if direct_debit.include_escrow { p.escrow_amount() } else { 0.0 }))
+            .filter(|(_, amount)| *amount > 0.0)
+            .collect();
+        let first = due.first().map(|(p, _)| p.installment_number);
+        let last = due.last().map(|(p, _)| p.installment_number);
+        let (from, to) = direct_debit.installments.unwrap_or((1, u32::MAX));
+        due.into_iter()
+            .filter(|(p, _)| (from..=to).contains(&p.installment_number))
+            .map(|(payment, amount)| {
+                let number = payment.installment_number;
+                Ok(Collection {
+                    installment_number: number,
+                    due_date: payment.date.ok_or(AmortizationError::MissingParameter("start_date"))?,
+                    amount: self.money(amount),
+                    sequence: match Some(number) {
+                        n if n == first => SequenceType::First,
+                        n if n == last => SequenceType::Final,
+                        _ => SequenceType::Recurring,
+                    },
+                    end_to_end_id: format!("{}-{}", direct_debit.mandate_id, number).chars().take(MAX_ID_LENGTH).collect(),
+                })
+            })
+            .collect()
+    }
+
+    // A pain.008.001.02 message with a payment information block per due date
+    pub fn to_pain008(&self, direct_debit: &DirectDebit) -> Result<String, AmortizationError> {
+        let collections = self.direct_debit_collections(direct_debit)?;
+        let total = collections.iter().fold(self.money(0.0), |total, c| total + c.amount);
+        let (creditor, debtor) = (&direct_debit.creditor, &direct_debit.debtor);
+
+        let mut xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<Document xmlns=\"{}\">\n<CstmrDrctDbtInitn>\n", PAIN_008_NAMESPACE);
+        xml.push_str(&format!(
+            "<GrpHdr><MsgId>{}</MsgId><CreDtTm>{}</CreDtTm><NbOfTxs>{}</NbOfTxs><CtrlSum>{}</CtrlSum><InitgPty><Nm>{}</Nm></InitgPty></GrpHdr>\n",
+            escape(&direct_debit.message_id), direct_debit.created_at.format("%Y-%m-%dT%H:%M:%S"), collections.len(),
+            format_money(total), escape(&creditor.name)
+        ));
+        for collection in &collections {
+            let amount = format_money(collection.amount);
+            xml.push_str(&format!(
+                "<PmtInf><PmtInfId>{}</PmtInfId><PmtMtd>DD</PmtMtd><NbOfTxs>1</NbOfTxs><CtrlSum>{}</CtrlSum>\
+                <PmtTpInf><SvcLvl><Cd>SEPA</Cd></SvcLvl><LclInstrm><Cd>CORE</Cd></LclInstrm><SeqTp>{}</SeqTp></PmtTpInf>\
+                <ReqdColltnDt>{}</ReqdColltnDt><Cdtr><Nm>{}</Nm></Cdtr><CdtrAcct><Id><IBAN>{}</IBAN></Id></CdtrAcct><CdtrAgt>{}</CdtrAgt>\
+                <CdtrSchmeId><Id><PrvtId><Othr><Id>{}</Id><SchmeNm><Prtry>SEPA</Prtry></SchmeNm></Othr></PrvtId></Id></CdtrSchmeId>\n",
+                escape(&collection.end_to_end_id), amount, collection.sequence.code(), collection.due_date.format("%Y-%m-%d"),
+                escape(&creditor.name), escape(&creditor.iban), agent(creditor), escape(&direct_debit.creditor_id)
+            ));
+            xml.push_str(&format!(
+                "<DrctDbtTxInf><PmtId><EndToEndId>{}</EndToEndId></PmtId><InstdAmt Ccy=\"{}\">{}</InstdAmt>\
+                <DrctDbtTx><MndtRltdInf><MndtId>{}</MndtId><DtOfSgntr>{}</DtOfSgntr></MndtRltdInf></DrctDbtTx>\
+                <DbtrAgt>{}</DbtrAgt><Dbtr><Nm>{}</Nm></Dbtr><DbtrAcct><Id><IBAN>{}</IBAN></Id></DbtrAcct>\
+                <RmtInf><Ustrd>Installment {} of {}</Ustrd></RmtInf></DrctDbtTxInf></PmtInf>\n",
+                escape(&collection.end_to_end_id), collection.amount.currency().code(), amount,
+                escape(&direct_debit.mandate_id), direct_debit.mandate_signed.format("%Y-%m-%d"),
+                agent(debtor), escape(&debtor.name), escape(&debtor.iban), collection.installment_number, self.schedule.len()
+            ));
+        }
+        xml.push_str("</CstmrDrctDbtInitn>\n</Document>\n");
+        Ok(xml)
+    }
+
+    pub fn to_fixed_width(&self, direct_debit: &DirectDebit, layout: &FixedWidthLayout) -> Result<String, AmortizationError> {
+        let mut file = String::new();
+        for collection in self.direct_debit_collections(direct_debit)? {
+            file.push_str(&layout.record(&collection, direct_debit)?);
+            file.push_str(&layout.line_ending);
+        }
+        Ok(file)
+    }
+}
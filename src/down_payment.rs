@@ -0,0 +1,110 @@
+use chrono::NaiveDate;
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::error::AmortizationError;
+use crate::escrow::{Escrow, MortgageInsurance};
+use crate::property::PropertyValue;
+
+// Lenders require mortgage insurance when less than 20% is put down
+const MORTGAGE_INSURANCE_LTV: f64 = 80.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DownPaymentGrid {
+    pub purchase_price: f64,
+    pub apr: f64,
+    pub term_months: u32,
+    // Percentages of the purchase price
+    pub down_payments: Vec<f64>,
+    pub mortgage_insurance: MortgageInsurance,
+    pub start_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DownPaymentScenario {
+    pub down_payment_percent: f64,
+    pub down_payment: f64,
+    pub loan_amount: f64,
+    pub ltv: f64,
+    pub requires_mortgage_insurance: bool,
+    // Installment from which mortgage insurance is no longer charged
+    pub mortgage_insurance_drop_off: Option<u32>,
+    // First installment including mortgage insurance, if any
+    pub periodic_payment: f64,
+    pub total_interest: f64,
+    pub total_mortgage_insurance: f64,
+    // Down payment, installments and mortgage insurance over the life of the loan
+    pub total_cost: f64,
+    pub loan: Amortization,
+}
+
+impl DownPaymentGrid {
+    pub fn new(purchase_price: f64, apr: f64, term_months: u32) -> Self {
+        DownPaymentGrid {
+            purchase_price,
+            apr,
+            term_months,
+            down_payments: vec![5.0, 10.0, 15.0, 20.0],
+            // A typical private mortgage insurance premium
+            mortgage_insurance: MortgageInsurance::new(0.5),
+            start_date: None,
+        }
+    }
+
+    pub fn with_down_payments(mut self, down_payments: &[f64]) -> Self {
+        self.down_payments = down_payments.to_vec();
+        self
+    }
+
+    pub fn with_mortgage_insurance(mut self, mortgage_insurance: MortgageInsurance) -> Self {
+        self.mortgage_insurance = mortgage_insurance;
+        self
+    }
+
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.purchase_price <= 0.0 || !self.purchase_price.is_finite() {
+            return Err(AmortizationError::InvalidPropertyValue(self.purchase_price));
+        }
+        if self.down_payments.is_empty() {
+            return Err(AmortizationError::MissingParameter("down_payments"));
+        }
+        if let Some(&invalid) = self.down_payments.iter().find(|&&p| !(0.0..100.0).contains(&p)) {
+            return Err(AmortizationError::InvalidPaymentAmount(invalid));
+        }
+        Ok(())
+    }
+
+    pub fn scenarios(&self) -> Result<Vec<DownPaymentScenario>, AmortizationError> {
+        self.validate()?;
+        self.down_payments.iter().map(|&percent| {
+            let down_payment = self.purchase_price * percent / 100.0;
+            let loan_amount = self.purchase_price - down_payment;
+            let ltv = loan_amount / self.purchase_price * 100.0;
+            let requires_mortgage_insurance = ltv > MORTGAGE_INSURANCE_LTV;
+            let loan = Amortization::from_config(CalculatorConfig {
+                escrow: requires_mortgage_insurance.then(|| {
+                    Escrow::new(self.purchase_price, 0.0, 0.0).with_mortgage_insurance(self.mortgage_insurance)
+                }),
+                property: Some(PropertyValue::new(self.purchase_price)),
+                ..CalculatorConfig::new(loan_amount, self.apr, self.term_months, self.start_date)
+            })?;
+            let total_mortgage_insurance = loan.total_mortgage_insurance();
+            Ok(DownPaymentScenario {
+                down_payment_percent: percent,
+                down_payment,
+                loan_amount,
+                ltv,
+                requires_mortgage_insurance,
+                mortgage_insurance_drop_off: loan.mortgage_insurance_drop_off(),
+                periodic_payment: loan.periodic_piti(),
+                total_interest: loan.total_interest,
+                total_mortgage_insurance,
+                total_cost: down_payment + loan.total_payment + total_mortgage_insurance,
+                loan,
+            })
+        }).collect()
+    }
+}
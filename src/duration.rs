@@ -0,0 +1,61 @@
+// Fixed-income risk measures on the schedule's installments. Times are in years, with
+// installment n falling n periods after the loan starts
+use crate::calculator::Amortization;
+use crate::error::AmortizationError;
+
+impl Amortization {
+    // Average time until principal is repaid, in years
+    pub fn weighted_average_life(&self) -> f64 {
+        let periods_per_year = self.config.frequency.periods_per_year() as f64;
+        let total_principal: f64 = self.schedule.iter().map(|p| p.principal).sum();
+        if total_principal == 0.0 {
+            return 0.0;
+        }
+        self.schedule.iter()
+            .map(|p| p.installment_number as f64 / periods_per_year * p.principal)
+            .sum::<f64>() / total_principal
+    }
+
+    // Present-value-weighted average time of the installments, in years, at an annual
+    // discount rate in percent compounded once per payment period
+    pub fn macaulay_duration(&self, discount_rate: f64) -> Result<f64, AmortizationError> {
+        let (rate, present_value) = self.discounted(discount_rate)?;
+        let weighted: f64 = self.schedule.iter()
+            .map(|p| p.installment_number as f64 * p.installment_amount / (1.0 + rate).powi(p.installment_number as i32))
+            .sum();
+        Ok(weighted / present_value / self.config.frequency.periods_per_year() as f64)
+    }
+
+    // Percentage change in present value for a one point change in the discount rate
+    pub fn modified_duration(&self, discount_rate: f64) -> Result<f64, AmortizationError> {
+        let (rate, _) = self.discounted(discount_rate)?;
+        Ok(self.macaulay_duration(discount_rate)? / (1.0 + rate))
+    }
+
+    pub fn convexity(&self, discount_rate: f64) -> Result<f64, AmortizationError> {
+        let (rate, present_value) = self.discounted(discount_rate)?;
+        let periods_per_year = self.config.frequency.periods_per_year() as f64;
+        let weighted: f64 = self.schedule.iter()
+            .map(|p| {
+                let n = p.installment_number as f64;
+                n * (n + 1.0) * p.installment_amount / (1.0 + rate).powi(p.installment_number as i32 + 2)
+            })
+            .sum();
+        Ok(weighted / present_value / (periods_per_year * periods_per_year))
+    }
+
+    // Periodic discount rate and the present value of the installments at it
+    fn discounted(&self, discount_rate: f64) -> Result<(f64, f64), AmortizationError> {
+        let rate = discount_rate / 100.0 / self.config.frequency.periods_per_year() as f64;
+        if rate <= -1.0 || !rate.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(discount_rate));
+        }
+        let present_value: f64 = self.schedule.iter()
+            .map(|p| p.installment_amount / (1.0 + rate).powi(p.installment_number as i32))
+            .sum();
+        if present_value <= 0.0 {
+            return Err(AmortizationError::NoSolution("Schedule has no cash flows to discount".to_string()));
+        }
+        Ok((rate, present_value))
+    }
+}
@@ -0,0 +1,128 @@
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::cashflow;
+use crate::error::AmortizationError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UpfrontCosts {
+    pub origination_fees: f64,
+    // Discount points as a percentage of the loan amount
+    pub points: f64,
+    pub closing_costs: f64,
+}
+
+impl UpfrontCosts {
+    pub fn total(&self, principal: f64) -> f64 {
+        self.origination_fees + principal * self.points / 100.0 + self.closing_costs
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeeTreatment {
+    // Costs are paid at closing out of the borrower's pocket or the loan proceeds
+    #[default]
+    PaidUpfront,
+    // Costs are added to the principal and amortized with it
+    Financed,
+}
+
+// Truth in Lending style disclosure figures. Either way the costs are prepaid finance
+// charges: the amount financed is the loan amount less the costs, and the finance charge is
+// the costs plus all interest paid. Interim interest collected at closing is prepaid too;
+// carried by the installments, it is part of the payments instead, and waived it is neither.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EffectiveApr {
+    pub note_rate: f64,
+    pub apr: f64,
+    pub treatment: FeeTreatment,
+    // Principal on the note, including any financed costs
+    pub loan_amount: f64,
+    pub amount_financed: f64,
+    pub finance_charge: f64,
+    pub total_of_payments: f64,
+}
+
+impl EffectiveApr {
+    pub fn calculate(loan: &Amortization, costs: &UpfrontCosts) -> Result<Self, AmortizationError> {
+        Self::calculate_with(loan, costs, FeeTreatment::PaidUpfront)
+    }
+
+    // With financed costs `loan` is the amount the borrower asked for; the disclosure is for
+    // the larger loan that includes the costs
+    pub fn calculate_with(loan: &Amortization, costs: &UpfrontCosts, treatment: FeeTreatment) -> Result<Self, AmortizationError> {
+        validate_costs(costs)?;
+        match treatment {
+            FeeTreatment::PaidUpfront => Self::disclose(loan, costs.total(loan.balance) + loan.closing_interest(), treatment),
+            FeeTreatment::Financed => {
+                let financed = loan.with_financed_costs(costs)?;
+                let upfront = costs.total(loan.balance) + financed.closing_interest();
+                Self::disclose(&financed, upfront, treatment)
+            }
+        }
+    }
+
+    fn disclose(loan: &Amortization, upfront: f64, treatment: FeeTreatment) -> Result<Self, AmortizationError> {
+        let amount_financed = loan.balance - upfront;
+        if amount_financed <= 0.0 {
+            return Err(AmortizationError::NoSolution(format!(
+                "Upfront costs of {:.2} exceed the loan amount of {:.2}", upfront, loan.balance
+            )));
+        }
+
+        let payments: Vec<f64> = loan.schedule.iter().map(|p| p.installment_amount).collect();
+        let periodic_rate = periodic_irr(amount_financed, &payments)?;
+        let periods_per_year = loan.config.frequency.periods_per_year() as f64;
+
+        Ok(EffectiveApr {
+            note_rate: loan.rate_schedule[0].apr,
+            apr: periodic_rate * periods_per_year * 100.0,
+            treatment,
+            loan_amount: loan.balance,
+            amount_financed,
+            finance_charge: loan.total_interest + upfront,
+            total_of_payments: loan.total_payment,
+        })
+    }
+}
+
+impl Amortization {
+    pub fn effective_apr(&self, costs: &UpfrontCosts) -> Result<EffectiveApr, AmortizationError> {
+        EffectiveApr::calculate(self, costs)
+    }
+
+    pub fn effective_apr_with(&self, costs: &UpfrontCosts, treatment: FeeTreatment) -> Result<EffectiveApr, AmortizationError> {
+        EffectiveApr::calculate_with(self, costs, treatment)
+    }
+
+    // The same loan with the costs rolled into the principal
+    pub fn with_financed_costs(&self, costs: &UpfrontCosts) -> Result<Amortization, AmortizationError> {
+        validate_costs(costs)?;
+        Amortization::from_config(CalculatorConfig {
+            balance: self.balance + costs.total(self.balance),
+            ..self.config.clone()
+        })
+    }
+}
+
+fn validate_costs(costs: &UpfrontCosts) -> Result<(), AmortizationError> {
+    for cost in [costs.origination_fees, costs.points, costs.closing_costs] {
+        if cost < 0.0 || !cost.is_finite() {
+            return Err(AmortizationError::InvalidUpfrontCost(cost));
+        }
+    }
+    Ok(())
+}
+
+// Rate at which the discounted payments equal the amount actually received
+fn periodic_irr(amount_financed: f64, payments: &[f64]) -> Result<f64, AmortizationError> {
+    let total: f64 = payments.iter().sum();
+    if total < amount_financed {
+        return Err(AmortizationError::NoSolution(
+            "Payments do not repay the amount financed".to_string()
+        ));
+    }
+
+    let mut cash_flows = Vec::with_capacity(payments.len() + 1);
+    cash_flows.push(-amount_financed);
+    cash_flows.extend_from_slice(payments);
+    cashflow::irr(&cash_flows)
+}
@@ -0,0 +1,50 @@
+// What a loan costs over its life, beside the rate on the note: the total cost of credit, how
+// large it is against the principal, and the annual rate the borrower effectively pays once
+// extra payments, fees and interest collected at closing are counted
+use crate::calculator::Amortization;
+use crate::cashflow;
+use crate::error::AmortizationError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EffectiveCost {
+    pub principal: f64,
+    // Everything paid beyond the principal: interest, fees and interim interest at closing
+    pub total_cost_of_credit: f64,
+    pub total_interest: f64,
+    pub total_fees: f64,
+    // Total interest as a percentage of the principal
+    pub interest_percent_of_principal: f64,
+    // Total cost of credit for every 1,000 of principal
+    pub cost_per_thousand: f64,
+    // Annual rate on the note, in percent
+    pub nominal_rate: f64,
+    // Effective annual rate, in percent, at which the installments as scheduled repay the
+    // principal less what was paid at closing
+    pub effective_rate: f64,
+}
+
+impl Amortization {
+    pub fn effective_cost(&self) -> Result<EffectiveCost, AmortizationError> {
+        let principal = self.money(self.balance);
+        let closing = self.money(self.closing_interest());
+        let total_cost_of_credit = self.total_payment_money() - principal + closing;
+        let per_principal = |amount: f64| if principal.minor() == 0 { 0.0 } else { amount / principal.to_f64() };
+
+        let mut cash_flows = Vec::with_capacity(self.schedule.len() + 1);
+        cash_flows.push(-(principal - closing).to_f64());
+        cash_flows.extend(self.schedule.iter().map(|p| p.installment_amount));
+        let periodic_rate = cashflow::irr(&cash_flows)?;
+        let periods_per_year = self.config.frequency.periods_per_year() as i32;
+
+        Ok(EffectiveCost {
+            principal: principal.to_f64(),
+            total_cost_of_credit: total_cost_of_credit.to_f64(),
+            total_interest: self.total_interest,
+            total_fees: self.total_fees(),
+            interest_percent_of_principal: per_principal(self.total_interest) * 100.0,
+            cost_per_thousand: per_principal(total_cost_of_credit.to_f64()) * 1_000.0,
+            nominal_rate: self.rate_schedule[0].apr,
+            effective_rate: ((1.0 + periodic_rate).powi(periods_per_year) - 1.0) * 100.0,
+        })
+    }
+}
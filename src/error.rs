@@ -1,13 +1,158 @@
 use std::fmt;
+use chrono::NaiveDate;
+use crate::frequency::PaymentFrequency;
+use crate::interest::InterestMethod;
+use crate::money::Currency;
 
 #[derive(Debug)]
 pub enum AmortizationError {
     InvalidPeriods(u32),
     InvalidInterestRate(f64),
     InvalidLoanAmount(f64),
+    InvalidRateSchedule(String),
+    InvalidExtraPayment(f64),
+    InvalidInterestOnlyPeriods(u32),
+    InvalidBalloonPeriod(u32),
+    InvalidDefermentPeriods(u32),
+    InvalidSkippedPayment(u32),
+    InvalidPaymentCap(f64),
+    InvalidGraduatedPayment(f64),
+    InvalidEscrow(f64),
+    InvalidPropertyValue(f64),
+    InvalidPaymentAmount(f64),
+    InvalidDueDay(u32),
+    InvalidFundingDate(NaiveDate),
+    // A year, month and day that make no calendar date
+    InvalidStartDate(i32, u32, u32),
+    InvalidProjectionRate(f64),
+    UnsupportedFrequency(PaymentFrequency),
+    UnsupportedInterestMethod(InterestMethod),
+    PeriodOutOfRange(u32),
+    InvalidUpfrontCost(f64),
+    InvalidPrepaymentPenalty(f64),
+    InvalidFee(f64),
+    InsufficientPayment(f64),
+    CreditLimitExceeded(f64),
+    InsufficientCapacity(usize),
+    // Two options that each make sense alone but not together
+    ConflictingOptions(&'static str, &'static str),
+    // A computed amount, named here, is too large to represent
+    Overflow(&'static str),
+    // The due date of this installment is beyond the supported calendar
+    DateOutOfRange(u32),
+    // A loan definition file that can't be read or doesn't describe a loan
+    InvalidConfig(String),
+    // A row of an imported payment history that can't be read
+    InvalidPaymentHistory(String),
+    // A lender's schedule that can't be read
+    InvalidStatement(String),
+    // Direct debit details a collection file can't be written with
+    InvalidDirectDebit(String),
+    // A saved loan state that can't be read or resumed
+    InvalidLoanState(String),
+    // A database row that doesn't hold a stored loan or payment
+    InvalidDatabaseRow(String),
+    // A tranche a waterfall can't pay: no balance, or a negative coupon
+    InvalidTranche(String),
+    // Asset terms that can't be depreciated
+    InvalidAsset(String),
+    // Income-driven repayment terms outside their valid range
+    InvalidRepaymentPlan(String),
+    NoSolution(String),
+    // The schedule hit the period cap with `balance` still owed; `stalled_at` is the
+    // installment from which the balance stopped declining, None when it was still falling
+    NonAmortizing { max_periods: u32, stalled_at: Option<u32>, balance: f64 },
+    MissingParameter(&'static str),
+    // No rate to convert from the first currency to the second on the date
+    MissingExchangeRate(Currency, Currency, NaiveDate),
     CalculationError(String),
 }
 
+// Broad category of an error, for mapping to user-facing messages without matching every
+// variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    // A single value is out of its valid range
+    InvalidInput,
+    // Values that are valid alone contradict each other, e.g. interest-only periods that
+    // outlast the term
+    InconsistentConfig,
+    UnsupportedCombination,
+    Overflow,
+    DateArithmetic,
+    NoSolution,
+    MissingParameter,
+    Calculation,
+}
+
+impl AmortizationError {
+    pub fn kind(&self) -> ErrorKind {
+        use AmortizationError::*;
+        match self {
+            InvalidPeriods(_) | InvalidInterestRate(_) | InvalidLoanAmount(_) | InvalidExtraPayment(_)
+            | InvalidPaymentCap(_) | InvalidEscrow(_) | InvalidPropertyValue(_) | InvalidPaymentAmount(_)
+            | InvalidDueDay(_) | InvalidStartDate(..) | InvalidProjectionRate(_) | InvalidUpfrontCost(_) | PeriodOutOfRange(_)
+            | InvalidPrepaymentPenalty(_) | InvalidFee(_) | InsufficientCapacity(_) | InvalidConfig(_)
+            | InvalidPaymentHistory(_) | InvalidStatement(_) | InvalidDirectDebit(_) | InvalidLoanState(_) | InvalidDatabaseRow(_) | InvalidTranche(_) | InvalidAsset(_)
+            | InvalidRepaymentPlan(_) => ErrorKind::InvalidInput,
+            InvalidRateSchedule(_) | InvalidInterestOnlyPeriods(_) | InvalidBalloonPeriod(_) | InvalidDefermentPeriods(_)
+            | InvalidSkippedPayment(_) | InvalidGraduatedPayment(_) | InvalidFundingDate(_) | InsufficientPayment(_)
+            | CreditLimitExceeded(_) | NonAmortizing { .. } => ErrorKind::InconsistentConfig,
+            UnsupportedFrequency(_) | UnsupportedInterestMethod(_) | ConflictingOptions(..) => ErrorKind::UnsupportedCombination,
+            Overflow(_) => ErrorKind::Overflow,
+            DateOutOfRange(_) => ErrorKind::DateArithmetic,
+            NoSolution(_) => ErrorKind::NoSolution,
+            MissingParameter(_) | MissingExchangeRate(..) => ErrorKind::MissingParameter,
+            CalculationError(_) => ErrorKind::Calculation,
+        }
+    }
+
+    // The `CalculatorConfig` field at fault, where there is one
+    pub fn parameter(&self) -> Option<&'static str> {
+        use AmortizationError::*;
+        match self {
+            InvalidPeriods(_) => Some("loan_term"),
+            InvalidInterestRate(_) => Some("apr"),
+            InvalidLoanAmount(_) => Some("balance"),
+            InvalidRateSchedule(_) => Some("rate_schedule"),
+            InvalidExtraPayment(_) => Some("extra_payment"),
+            InvalidInterestOnlyPeriods(_) => Some("loan_type"),
+            InvalidBalloonPeriod(_) => Some("balloon_period"),
+            InvalidDefermentPeriods(_) => Some("deferment"),
+            InvalidSkippedPayment(_) => Some("skip_payments"),
+            InvalidPaymentCap(_) => Some("payment_cap"),
+            InvalidGraduatedPayment(_) => Some("graduated_payment"),
+            InvalidEscrow(_) => Some("escrow"),
+            InvalidPropertyValue(_) => Some("property"),
+            InvalidDueDay(_) => Some("date_roll"),
+            InvalidFundingDate(_) => Some("funding_date"),
+            InvalidStartDate(..) => Some("start_date"),
+            InvalidPrepaymentPenalty(_) => Some("prepayment_penalty"),
+            InvalidFee(_) => Some("fees"),
+            NonAmortizing { .. } => Some("max_periods"),
+            UnsupportedFrequency(_) => Some("frequency"),
+            UnsupportedInterestMethod(_) => Some("interest_method"),
+            ConflictingOptions(option, _) | MissingParameter(option) => Some(option),
+            _ => None,
+        }
+    }
+
+    // The offending number, for errors that carry one
+    pub fn value(&self) -> Option<f64> {
+        use AmortizationError::*;
+        match *self {
+            InvalidInterestRate(v) | InvalidLoanAmount(v) | InvalidExtraPayment(v) | InvalidPaymentCap(v)
+            | InvalidGraduatedPayment(v) | InvalidEscrow(v) | InvalidPropertyValue(v) | InvalidPaymentAmount(v)
+            | InvalidProjectionRate(v) | InvalidUpfrontCost(v) | InvalidPrepaymentPenalty(v) | InvalidFee(v) | InsufficientPayment(v) | CreditLimitExceeded(v) => Some(v),
+            InvalidPeriods(n) | InvalidInterestOnlyPeriods(n) | InvalidBalloonPeriod(n) | InvalidDefermentPeriods(n)
+            | InvalidSkippedPayment(n) | InvalidDueDay(n) | PeriodOutOfRange(n) | DateOutOfRange(n) => Some(n as f64),
+            InsufficientCapacity(n) => Some(n as f64),
+            NonAmortizing { balance, .. } => Some(balance),
+            _ => None,
+        }
+    }
+}
+
 impl std::error::Error for AmortizationError {}
 
 impl fmt::Display for AmortizationError {
@@ -16,6 +161,52 @@ impl fmt::Display for AmortizationError {
             AmortizationError::InvalidPeriods(p) => write!(f, "Number of periods must be greater than 0, got {}", p),
             AmortizationError::InvalidInterestRate(r) => write!(f, "Interest rate must be greater than 0, got {}", r),
             AmortizationError::InvalidLoanAmount(a) => write!(f, "Loan amount must be greater than 0, got {}", a),
+            AmortizationError::InvalidRateSchedule(msg) => write!(f, "Invalid rate schedule: {}", msg),
+            AmortizationError::InvalidExtraPayment(a) => write!(f, "Extra payment must not be negative, got {}", a),
+            AmortizationError::InvalidInterestOnlyPeriods(p) => write!(f, "Interest-only periods must be less than the number of periods, got {}", p),
+            AmortizationError::InvalidBalloonPeriod(p) => write!(f, "Balloon period must be between 1 and the amortization term, got {}", p),
+            AmortizationError::InvalidDefermentPeriods(p) => write!(f, "Deferment periods must be less than the number of periods, got {}", p),
+            AmortizationError::InvalidSkippedPayment(n) => write!(f, "Skipped installment must be before the final period, got {}", n),
+            AmortizationError::InvalidPaymentCap(c) => write!(f, "Payment cap must be a non-negative percentage with a recast limit above 100%, got {}", c),
+            AmortizationError::InvalidGraduatedPayment(g) => write!(f, "Graduated payment growth must not be negative and must end before the final period, got {}", g),
+            AmortizationError::InvalidEscrow(a) => write!(f, "Escrow amounts must not be negative and the property value must be positive, got {}", a),
+            AmortizationError::InvalidPropertyValue(v) => write!(f, "Property value must be positive with appreciation above -100%, got {}", v),
+            AmortizationError::InvalidPaymentAmount(p) => write!(f, "Payment amount must be greater than 0, got {}", p),
+            AmortizationError::InvalidDueDay(d) => write!(f, "Due day must be between 1 and 31, got {}", d),
+            AmortizationError::InvalidFundingDate(d) => write!(f, "Funding date must come before the first due date of an in-arrears loan, got {}", d),
+            AmortizationError::InvalidStartDate(year, month, day) => write!(f, "Start date must be a calendar date, got {}-{:02}-{:02}", year, month, day),
+            AmortizationError::InvalidProjectionRate(r) => write!(f, "Projection rates must be between 0 and 100%, got {}", r),
+            AmortizationError::UnsupportedFrequency(freq) => write!(f, "Operation is not supported for {:?} payments", freq),
+            AmortizationError::UnsupportedInterestMethod(method) => write!(f, "{:?} interest requires a fixed-rate, fully amortizing loan without extra payments", method),
+            AmortizationError::PeriodOutOfRange(p) => write!(f, "Period {} is outside the loan schedule", p),
+            AmortizationError::InvalidUpfrontCost(c) => write!(f, "Upfront costs must not be negative, got {}", c),
+            AmortizationError::InvalidPrepaymentPenalty(v) => write!(f, "Prepayment penalty rates must not be negative and steps must be in increasing period order, got {}", v),
+            AmortizationError::InvalidFee(a) => write!(f, "Fee amounts must not be negative, got {}", a),
+            AmortizationError::InsufficientPayment(p) => write!(f, "Payment of {} does not cover the periodic interest", p),
+            AmortizationError::CreditLimitExceeded(b) => write!(f, "Balance of {} exceeds the credit limit", b),
+            AmortizationError::InsufficientCapacity(c) => write!(f, "Schedule has more installments than the {} the buffer holds", c),
+            AmortizationError::ConflictingOptions(option, other) => write!(f, "{} cannot be combined with {}", option, other),
+            AmortizationError::Overflow(quantity) => write!(f, "The {} is too large to calculate", quantity),
+            AmortizationError::DateOutOfRange(n) => write!(f, "Due date of installment {} is outside the supported calendar", n),
+            AmortizationError::InvalidConfig(msg) => write!(f, "Invalid loan definition: {}", msg),
+            AmortizationError::InvalidPaymentHistory(msg) => write!(f, "Invalid payment history: {}", msg),
+            AmortizationError::InvalidStatement(msg) => write!(f, "Invalid lender statement: {}", msg),
+            AmortizationError::InvalidDirectDebit(msg) => write!(f, "Invalid direct debit: {}", msg),
+            AmortizationError::InvalidLoanState(msg) => write!(f, "Invalid loan state: {}", msg),
+            AmortizationError::InvalidDatabaseRow(msg) => write!(f, "Invalid database row: {}", msg),
+            AmortizationError::InvalidTranche(msg) => write!(f, "Invalid tranche: {}", msg),
+            AmortizationError::InvalidAsset(msg) => write!(f, "Invalid asset: {}", msg),
+            AmortizationError::InvalidRepaymentPlan(msg) => write!(f, "Invalid repayment plan: {}", msg),
+            AmortizationError::NoSolution(msg) => write!(f, "No solution: {}", msg),
+            AmortizationError::NonAmortizing { max_periods, stalled_at, balance } => {
+                write!(f, "Balance of {:.2} is still owed after {} periods", balance, max_periods)?;
+                match stalled_at {
+                    Some(period) => write!(f, "; it stopped declining at installment {}", period),
+                    None => write!(f, "; it declines too slowly to be repaid"),
+                }
+            }
+            AmortizationError::MissingParameter(name) => write!(f, "Missing required parameter: {}", name),
+            AmortizationError::MissingExchangeRate(from, to, date) => write!(f, "No exchange rate from {} to {} on {}", from, to, date),
             AmortizationError::CalculationError(msg) => write!(f, "Calculation error: {}", msg),
         }
     }
@@ -0,0 +1,77 @@
+use crate::money::Currency;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MortgageInsurance {
+    // Annual premium as a percentage of the original loan amount
+    pub annual_rate: f64,
+    // Loan-to-value percentage at which the premium stops
+    pub drop_off_ltv: f64,
+}
+
+impl MortgageInsurance {
+    pub fn new(annual_rate: f64) -> Self {
+        MortgageInsurance { annual_rate, drop_off_ltv: 78.0 }
+    }
+
+    pub fn drop_off_at(mut self, ltv: f64) -> Self {
+        self.drop_off_ltv = ltv;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Escrow {
+    pub annual_property_tax: f64,
+    pub annual_insurance: f64,
+    pub mortgage_insurance: Option<MortgageInsurance>,
+    // Original appraised value, the basis for mortgage insurance cancellation
+    pub property_value: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EscrowPayment {
+    pub property_tax: f64,
+    pub insurance: f64,
+    pub mortgage_insurance: f64,
+}
+
+impl Escrow {
+    pub fn new(property_value: f64, annual_property_tax: f64, annual_insurance: f64) -> Self {
+        Escrow {
+            annual_property_tax,
+            annual_insurance,
+            mortgage_insurance: None,
+            property_value,
+        }
+    }
+
+    pub fn with_mortgage_insurance(mut self, mortgage_insurance: MortgageInsurance) -> Self {
+        self.mortgage_insurance = Some(mortgage_insurance);
+        self
+    }
+
+    pub fn ltv(&self, balance: f64) -> f64 {
+        balance / self.property_value * 100.0
+    }
+
+    // Escrow portion of one installment, given the balance before it is paid, in whole minor
+    // units of `currency`
+    pub fn payment(&self, original_balance: f64, balance: f64, periods_per_year: u32, currency: Currency) -> EscrowPayment {
+        let per_period = |annual: f64| currency.round(annual / periods_per_year as f64);
+        let mortgage_insurance = match self.mortgage_insurance {
+            Some(mi) if self.ltv(balance) > mi.drop_off_ltv => per_period(original_balance * mi.annual_rate / 100.0),
+            _ => 0.0,
+        };
+        EscrowPayment {
+            property_tax: per_period(self.annual_property_tax),
+            insurance: per_period(self.annual_insurance),
+            mortgage_insurance,
+        }
+    }
+}
+
+impl EscrowPayment {
+    pub fn total(&self) -> f64 {
+        self.property_tax + self.insurance + self.mortgage_insurance
+    }
+}
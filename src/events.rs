@@ -0,0 +1,113 @@
+// Provenance of a schedule: one event per transform applied to it, with the terms it changed
+// and their previous values, so the schedule can be explained and reproduced later
+use chrono::NaiveDate;
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::json::JsonValue;
+use crate::money::Currency;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleEvent {
+    // Order in which the transforms were applied, from 1
+    pub sequence: u32,
+    pub transform: &'static str,
+    // First installment the change takes effect on, and its due date
+    pub effective_period: Option<u32>,
+    pub effective_date: Option<NaiveDate>,
+    pub changes: Vec<FieldChange>,
+    pub previous_payment: f64,
+    pub payment: f64,
+    pub previous_periods: u32,
+    pub periods: u32,
+    pub previous_total_interest: f64,
+    pub total_interest: f64,
+    pub currency: Currency,
+}
+
+// Terms a transform can change, written the way they print with `{:?}`
+fn terms(config: &CalculatorConfig) -> Vec<(&'static str, String)> {
+    vec![
+        ("balance", format!("{:?}", config.balance)),
+        ("loan_term", format!("{:?}", config.loan_term)),
+        ("apr", format!("{:?}", config.apr)),
+        ("frequency", format!("{:?}", config.frequency)),
+        ("extra_payment", format!("{:?}", config.extra_payment)),
+        ("rate_schedule", format!("{:?}", config.rate_schedule)),
+        ("rate_caps", format!("{:?}", config.rate_caps)),
+        ("loan_type", format!("{:?}", config.loan_type)),
+        ("balloon_period", format!("{:?}", config.balloon_period)),
+        ("payment_cap", format!("{:?}", config.payment_cap)),
+        ("deferment", format!("{:?}", config.deferment)),
+        ("skip_payments", format!("{:?}", config.skip_payments)),
+        ("prepayments", format!("{:?}", config.prepayments)),
+        ("fixed_payment", format!("{:?}", config.fixed_payment)),
+        ("fees", format!("{:?}", config.fees)),
+    ]
+}
+
+pub(crate) fn config_changes(before: &CalculatorConfig, after: &CalculatorConfig) -> Vec<FieldChange> {
+    terms(before).into_iter()
+        .zip(terms(after))
+        .filter(|((_, before), (_, after))| before != after)
+        .map(|((field, before), (_, after))| FieldChange { field, before, after })
+        .collect()
+}
+
+impl ScheduleEvent {
+    pub(crate) fn record(transform: &'static str, effective_period: Option<u32>, previous: &Amortization, loan: &Amortization) -> Self {
+        ScheduleEvent {
+            sequence: previous.events.len() as u32 + 1,
+            transform,
+            effective_period,
+            effective_date: effective_period.and_then(|period| loan.due_date(period)),
+            changes: config_changes(&previous.config, &loan.config),
+            previous_payment: previous.periodic_payment,
+            payment: loan.periodic_payment,
+            previous_periods: previous.schedule.len() as u32,
+            periods: loan.schedule.len() as u32,
+            previous_total_interest: previous.total_interest,
+            total_interest: loan.total_interest,
+            currency: loan.config.currency,
+        }
+    }
+
+    pub fn to_json_value(&self) -> JsonValue {
+        let changes = self.changes.iter()
+            .map(|change| JsonValue::object(vec![
+                ("field", change.field.into()),
+                ("before", change.before.clone().into()),
+                ("after", change.after.clone().into()),
+            ]))
+            .collect();
+        JsonValue::object(vec![
+            ("sequence", self.sequence.into()),
+            ("transform", self.transform.into()),
+            ("effective_period", self.effective_period.into()),
+            ("effective_date", self.effective_date.map(|d| d.format("%Y-%m-%d").to_string()).into()),
+            ("changes", JsonValue::Array(changes)),
+            ("previous_payment", JsonValue::money(self.previous_payment, self.currency)),
+            ("payment", JsonValue::money(self.payment, self.currency)),
+            ("previous_periods", self.previous_periods.into()),
+            ("periods", self.periods.into()),
+            ("previous_total_interest", JsonValue::money(self.previous_total_interest, self.currency)),
+            ("total_interest", JsonValue::money(self.total_interest, self.currency)),
+        ])
+    }
+}
+
+impl Amortization {
+    pub fn event_log_json(&self) -> String {
+        JsonValue::Array(self.events.iter().map(ScheduleEvent::to_json_value).collect()).to_string()
+    }
+
+    // Events that changed `field`, oldest first
+    pub fn events_changing(&self, field: &str) -> Vec<&ScheduleEvent> {
+        self.events.iter().filter(|event| event.changes.iter().any(|change| change.field == field)).collect()
+    }
+}
@@ -0,0 +1,152 @@
+// A plain-language account of a schedule for borrowers: how the installment comes about, how
+// much of the early installments is interest, when principal overtakes interest and what one
+// extra installment would save. Each milestone is also kept as a field for apps to lay out
+use std::fmt;
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::loan_type::LoanType;
+use crate::milestone::MilestoneKind;
+use crate::payment::Payment;
+use crate::prepayment::PrepaymentTreatment;
+use crate::units::Term;
+
+// One extra installment paid together with the first, taken off the term
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtraPaymentEffect {
+    pub amount: f64,
+    pub interest_saved: f64,
+    pub periods_saved: u32,
+    pub time_saved: Term,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub periodic_payment: f64,
+    pub periods: u32,
+    pub term: Term,
+    pub first_payment_interest: f64,
+    // Share of the first installment that is interest, in percent
+    pub first_payment_interest_share: f64,
+    // Paid over the first year of installments
+    pub first_year_interest: f64,
+    pub first_year_principal: f64,
+    // First installment repaying more principal than it pays interest; None when none does
+    pub crossover_period: Option<u32>,
+    pub crossover_date: Option<NaiveDate>,
+    // Installment after which half the original balance has been repaid
+    pub halfway_period: Option<u32>,
+    pub halfway_date: Option<NaiveDate>,
+    pub total_payment: f64,
+    pub total_interest: f64,
+    // None when the extra installment can't be applied to the loan
+    pub extra_payment: Option<ExtraPaymentEffect>,
+    // The narrative, one paragraph per topic in the order above
+    pub paragraphs: Vec<String>,
+}
+
+fn when(period: u32, date: Option<NaiveDate>) -> String {
+    match date {
+        Some(date) => format!("installment {} ({})", period, date.format("%B %Y")),
+        None => format!("installment {}", period),
+    }
+}
+
+impl Explanation {
+    pub fn to_text(&self) -> String {
+        self.paragraphs.join("\n\n")
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_text())
+    }
+}
+
+impl Amortization {
+    pub fn explain(&self) -> Explanation {
+        let money = |amount: f64| self.money(amount).to_string();
+        let periods = self.schedule.len() as u32;
+        let per_year = self.config.frequency.periods_per_year();
+        let first = self.schedule.first();
+        let first_payment = first.map_or(0.0, |p| p.installment_amount);
+        let first_payment_interest = first.map_or(0.0, |p| p.interest);
+        let first_payment_interest_share = if first_payment > 0.0 { first_payment_interest / first_payment * 100.0 } else { 0.0 };
+        let first_year: Vec<&Payment> = self.schedule.iter().filter(|p| p.installment_number <= per_year).collect();
+        let first_year_interest = first_year.iter().map(|p| p.interest).sum();
+        let first_year_principal = first_year.iter().map(|p| p.principal).sum();
+        let crossover = self.milestone(MilestoneKind::Crossover);
+        let halfway = self.milestone(MilestoneKind::HalfPrincipalRepaid);
+        let extra_payment = self.with_lumpsum(1, self.periodic_payment, PrepaymentTreatment::ReduceTerm)
+            .ok()
+            .map(|scenario| ExtraPaymentEffect {
+                amount: self.periodic_payment,
+                interest_saved: scenario.interest_saved,
+                periods_saved: scenario.periods_saved,
+                time_saved: scenario.time_saved,
+            });
+
+        let frequency = self.config.frequency.name();
+        let rates = format!("{:.2}% a year ({:.4}% per installment)", self.config.apr, self.periodic_interest * 100.0);
+        let derivation = if let Some(fixed) = &self.config.fixed_payment {
+            format!("The installment of {} was agreed up front. At {} it repays {} over {} {} installments.",
+                money(fixed.amount), rates, money(self.balance), periods, frequency)
+        } else {
+            match self.config.loan_type {
+                LoanType::InterestOnly { io_periods } if io_periods > 0 => format!(
+                    "For the first {} installments only the interest, {}, is due. After that the installment becomes {}, \
+                    the level amount that repays {} at {} over the remaining {} installments.",
+                    io_periods, money(first_payment), money(self.periodic_payment), money(self.balance), rates,
+                    periods.saturating_sub(io_periods)),
+                LoanType::EqualPrincipal => format!(
+                    "Each installment repays the same {} of principal plus the interest on what is still owed at {}, \
+                    so the installment starts at {} and falls to {}.",
+                    money(self.balance / periods.max(1) as f64), rates, money(first_payment), money(self.final_payment_amount)),
+                _ => format!(
+                    "The installment of {} is the level amount that repays {} over {} {} installments at {}, \
+                    so that the last installment leaves nothing owing.",
+                    money(self.periodic_payment), money(self.balance), periods, frequency, rates),
+            }
+        };
+        let mut paragraphs = vec![
+            derivation,
+            format!("Of the first installment, {} ({:.0}%) is interest and {} goes toward the balance. \
+                Over the first year, {} is paid in interest and the balance falls by {}.",
+                money(first_payment_interest), first_payment_interest_share, money(first.map_or(0.0, |p| p.principal)),
+                money(first_year_interest), money(first_year_principal)),
+        ];
+        paragraphs.push(match crossover {
+            Some(m) if m.installment_number == 1 => "More of every installment goes to principal than to interest from the start.".to_string(),
+            Some(m) => format!("From {} on, more of each installment goes to principal than to interest.", when(m.installment_number, m.date)),
+            None => "Interest makes up most of every installment until the loan is repaid.".to_string(),
+        });
+        if let Some(m) = halfway {
+            paragraphs.push(format!("Half of the original balance is repaid by {}.", when(m.installment_number, m.date)));
+        }
+        paragraphs.push(format!("Over {} the loan costs {} in total, {} of it interest.",
+            self.payoff_term(), money(self.total_payment), money(self.total_interest)));
+        if let Some(effect) = &extra_payment {
+            let sooner = if effect.periods_saved > 0 { format!(" and ends the loan {} early", effect.time_saved) } else { String::new() };
+            paragraphs.push(format!("Paying one extra installment of {} along with the first saves {} in interest{}.",
+                money(effect.amount), money(effect.interest_saved), sooner));
+        }
+
+        Explanation {
+            periodic_payment: self.periodic_payment,
+            periods,
+            term: self.payoff_term(),
+            first_payment_interest,
+            first_payment_interest_share,
+            first_year_interest,
+            first_year_principal,
+            crossover_period: crossover.map(|m| m.installment_number),
+            crossover_date: crossover.and_then(|m| m.date),
+            halfway_period: halfway.map(|m| m.installment_number),
+            halfway_date: halfway.and_then(|m| m.date),
+            total_payment: self.total_payment,
+            total_interest: self.total_interest,
+            extra_payment,
+            paragraphs,
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use std::io::{self, Write};
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::fees::FeeCharge;
+use crate::json::JsonValue;
+use crate::money::Currency;
+use crate::payment::Payment;
+use crate::sink::CsvSink;
+use crate::table::FormatOptions;
+
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+impl Amortization {
+    pub fn to_csv<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.to_csv_with(writer, &FormatOptions::csv(self))
+    }
+
+    pub fn to_csv_with<W: Write>(&self, writer: W, options: &FormatOptions) -> io::Result<()> {
+        self.write_into(&mut CsvSink::new(writer, options.clone()))
+    }
+
+    pub fn schedule_csv(&self) -> String {
+        let mut buffer = Vec::new();
+        self.to_csv(&mut buffer).expect("writing to a Vec never fails");
+        String::from_utf8(buffer).expect("CSV output is always valid UTF-8")
+    }
+
+    pub fn to_json_value(&self) -> JsonValue {
+        let mut entries = vec![
+            ("schema_version", JSON_SCHEMA_VERSION.into()),
+            ("summary", self.json_summary()),
+            ("schedule", JsonValue::Array(self.schedule.iter().map(|p| self.payment_json(p)).collect())),
+        ];
+        if !self.config.fees.is_empty() {
+            entries.push(("fees", JsonValue::Array(self.fee_charges().iter().map(|charge| fee_json(charge, self.config.currency)).collect())));
+        }
+        // Left out for schedules built directly, so their output is unchanged
+        if !self.events.is_empty() {
+            entries.push(("events", JsonValue::Array(self.events.iter().map(|e| e.to_json_value()).collect())));
+        }
+        JsonValue::object(entries)
+    }
+
+    pub fn to_json(&self) -> String {
+        self.to_json_value().to_string()
+    }
+
+    fn json_summary(&self) -> JsonValue {
+        let mut entries = vec![
+            ("principal", JsonValue::money(self.balance, self.config.currency)),
+            ("periodic_rate", self.periodic_interest.into()),
+            ("periods", self.periods.into()),
+            ("periodic_payment", JsonValue::money(self.periodic_payment, self.config.currency)),
+            ("final_payment", JsonValue::money(self.final_payment_amount, self.config.currency)),
+            ("total_payment", JsonValue::money(self.total_payment, self.config.currency)),
+            ("total_interest", JsonValue::money(self.total_interest, self.config.currency)),
+            ("start_date", json_date(self.start_date)),
+            ("end_date", json_date(self.end_date)),
+        ];
+        if !self.config.fees.is_empty() {
+            entries.push(("total_fees", JsonValue::money(self.total_fees(), self.config.currency)));
+        }
+        JsonValue::object(entries)
+    }
+
+    fn payment_json(&self, payment: &Payment) -> JsonValue {
+        let mut entries = vec![
+            ("installment_number", payment.installment_number.into()),
+            ("date", json_date(payment.date)),
+            ("beginning_balance", JsonValue::money(payment.beginning_balance, self.config.currency)),
+            ("installment_amount", JsonValue::money(payment.installment_amount, self.config.currency)),
+            ("interest", JsonValue::money(payment.interest, self.config.currency)),
+            ("principal", JsonValue::money(payment.principal, self.config.currency)),
+            ("balloon_amount", JsonValue::money(payment.balloon_amount, self.config.currency)),
+            ("remaining_balance", JsonValue::money(payment.remaining_balance, self.config.currency)),
+        ];
+        if !self.config.fees.is_empty() {
+            entries.push(("fees", JsonValue::money(payment.fees, self.config.currency)));
+        }
+        JsonValue::object(entries)
+    }
+}
+
+fn json_date(date: Option<NaiveDate>) -> JsonValue {
+    date.map(|d| d.format("%Y-%m-%d").to_string()).into()
+}
+
+fn fee_json(charge: &FeeCharge, currency: Currency) -> JsonValue {
+    JsonValue::object(vec![
+        ("installment_number", charge.installment_number.into()),
+        ("name", charge.name.as_str().into()),
+        ("amount", JsonValue::money(charge.amount, currency)),
+    ])
+}
@@ -0,0 +1,73 @@
+// Charges collected with installments that are neither interest nor principal: a monthly
+// servicing fee or insurance add-on, or a one-off charge such as a late or modification fee.
+// They raise the installment and the total paid but never accrue interest or reduce the balance
+use crate::calculator::Amortization;
+use crate::money::{Currency, Money};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fee {
+    pub name: String,
+    pub amount: f64,
+    // The one installment it is charged with; None charges it with every installment paid
+    pub period: Option<u32>,
+}
+
+// One fee charged with one installment
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeCharge {
+    pub installment_number: u32,
+    pub name: String,
+    pub amount: f64,
+}
+
+impl Fee {
+    pub fn recurring(name: &str, amount: f64) -> Self {
+        Fee { name: name.to_string(), amount, period: None }
+    }
+
+    pub fn one_off(name: &str, period: u32, amount: f64) -> Self {
+        Fee { name: name.to_string(), amount, period: Some(period) }
+    }
+
+    pub fn is_recurring(&self) -> bool {
+        self.period.is_none()
+    }
+
+    // Deferred and skipped installments collect no recurring fee; a one-off charge falls due
+    // with its installment regardless
+    pub(crate) fn applies_to(&self, installment_number: u32, paid: bool) -> bool {
+        match self.period {
+            Some(period) => period == installment_number,
+            None => paid,
+        }
+    }
+}
+
+// Total of the fees charged with one installment, in whole minor units of `currency`
+pub(crate) fn fees_due(fees: &[Fee], installment_number: u32, paid: bool, currency: Currency) -> f64 {
+    let total: f64 = fees.iter().filter(|fee| fee.applies_to(installment_number, paid)).map(|fee| fee.amount).sum();
+    currency.round(total)
+}
+
+impl Amortization {
+    pub fn total_fees(&self) -> f64 {
+        self.total_fees_money().to_f64()
+    }
+
+    pub fn total_fees_money(&self) -> Money {
+        Money::total(self.config.currency, self.schedule.iter().map(|p| self.money(p.fees)))
+    }
+
+    // Every fee itemized against the installment it was charged with
+    pub fn fee_charges(&self) -> Vec<FeeCharge> {
+        self.schedule.iter()
+            .filter(|payment| payment.fees > 0.0)
+            .flat_map(|payment| {
+                let paid = !payment.skipped && !self.is_deferred(payment.installment_number);
+                self.config.fees.iter()
+                    .filter(move |fee| fee.applies_to(payment.installment_number, paid))
+                    .map(|fee| FeeCharge { installment_number: payment.installment_number, name: fee.name.clone(), amount: fee.amount })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,92 @@
+// C ABI for embedding the calculator in C, C++, Swift and Kotlin apps; include/amortize.h
+// declares the same items. Build the shared library with
+// `cargo rustc --release --features ffi --crate-type cdylib`.
+//
+// Loans are opaque pointers owned by the caller and released with `amortize_free`. Pointer
+// arguments are taken as `Option<&T>`, which has the layout of a nullable C pointer, so a NULL
+// from C is rejected instead of dereferenced and none of these functions need `unsafe`
+use chrono::{Datelike, NaiveDate};
+use crate::calculator::Amortization;
+
+pub struct AmortizeLoan {
+    inner: Amortization,
+}
+
+// One installment; the date fields are all zero on an undated schedule
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AmortizePayment {
+    pub installment_number: u32,
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub beginning_balance: f64,
+    pub installment_amount: f64,
+    pub interest: f64,
+    pub principal: f64,
+    pub remaining_balance: f64,
+}
+
+fn boxed(loan: Option<Amortization>) -> Option<Box<AmortizeLoan>> {
+    loan.map(|inner| Box::new(AmortizeLoan { inner }))
+}
+
+// Returns NULL when the loan terms are invalid
+#[no_mangle]
+pub extern "C" fn amortize_new(principal: f64, apr: f64, term_months: u32) -> Option<Box<AmortizeLoan>> {
+    boxed(Amortization::new(principal, apr, term_months, None).ok())
+}
+
+// Same as `amortize_new` with the first payment due on the given date; NULL when the date
+// doesn't exist
+#[no_mangle]
+pub extern "C" fn amortize_new_dated(principal: f64, apr: f64, term_months: u32, year: i32, month: u32, day: u32) -> Option<Box<AmortizeLoan>> {
+    let start_date = NaiveDate::from_ymd_opt(year, month, day)?;
+    boxed(Amortization::new(principal, apr, term_months, Some(start_date)).ok())
+}
+
+// Zero for a NULL loan
+#[no_mangle]
+pub extern "C" fn amortize_schedule_len(loan: Option<&AmortizeLoan>) -> usize {
+    loan.map_or(0, |loan| loan.inner.schedule.len())
+}
+
+#[no_mangle]
+pub extern "C" fn amortize_periodic_payment(loan: Option<&AmortizeLoan>) -> f64 {
+    loan.map_or(0.0, |loan| loan.inner.periodic_payment)
+}
+
+#[no_mangle]
+pub extern "C" fn amortize_total_interest(loan: Option<&AmortizeLoan>) -> f64 {
+    loan.map_or(0.0, |loan| loan.inner.total_interest)
+}
+
+// Writes installment `index` (0-based) to `out`. Returns false, leaving `out` untouched, for a
+// NULL argument or an index past the end of the schedule
+#[no_mangle]
+pub extern "C" fn amortize_payment_at(loan: Option<&AmortizeLoan>, index: usize, out: Option<&mut AmortizePayment>) -> bool {
+    let (Some(loan), Some(out)) = (loan, out) else {
+        return false;
+    };
+    let Some(payment) = loan.inner.schedule.get(index) else {
+        return false;
+    };
+    *out = AmortizePayment {
+        installment_number: payment.installment_number,
+        year: payment.date.map_or(0, |d| d.year()),
+        month: payment.date.map_or(0, |d| d.month()),
+        day: payment.date.map_or(0, |d| d.day()),
+        beginning_balance: payment.beginning_balance,
+        installment_amount: payment.installment_amount,
+        interest: payment.interest,
+        principal: payment.principal,
+        remaining_balance: payment.remaining_balance,
+    };
+    true
+}
+
+// Releases a loan from `amortize_new`; NULL is ignored
+#[no_mangle]
+pub extern "C" fn amortize_free(loan: Option<Box<AmortizeLoan>>) {
+    drop(loan);
+}
@@ -0,0 +1,75 @@
+// Schedules written into storage the caller owns, for short loans on targets where the
+// schedule shouldn't be allocated on the heap
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::error::AmortizationError;
+use crate::payment::Payment;
+
+// Up to `N` installments held inline
+#[derive(Debug, Clone)]
+pub struct FixedSchedule<const N: usize> {
+    payments: [Option<Payment>; N],
+    len: usize,
+    pub total_payment: f64,
+    pub total_interest: f64,
+}
+
+impl Amortization {
+    // Generates the installments of `config` into `buffer`, clearing any slots left over, and
+    // returns how many were written. Fails when the buffer is too short for the schedule
+    pub fn schedule_into(config: CalculatorConfig, buffer: &mut [Option<Payment>]) -> Result<usize, AmortizationError> {
+        let mut loan = Amortization::unscheduled(config)?;
+        loan.periodic_payment = loan.calculate_periodic_payment_amount()?;
+        let mut payments = loan.payments();
+        let capacity = buffer.len();
+        let mut written = 0;
+        while let Some(payment) = payments.try_next()? {
+            let slot = buffer.get_mut(written).ok_or(AmortizationError::InsufficientCapacity(capacity))?;
+            *slot = Some(payment);
+            written += 1;
+        }
+        for slot in &mut buffer[written..] {
+            *slot = None;
+        }
+        Ok(written)
+    }
+}
+
+impl<const N: usize> FixedSchedule<N> {
+    pub fn new(config: CalculatorConfig) -> Result<Self, AmortizationError> {
+        let balance = config.balance;
+        let mut payments = [const { None }; N];
+        let len = Amortization::schedule_into(config, &mut payments)?;
+        let total_payment: f64 = payments.iter().flatten().map(|p| p.installment_amount).sum();
+        let total_fees: f64 = payments.iter().flatten().map(|p| p.fees).sum();
+        Ok(FixedSchedule {
+            payments,
+            len,
+            total_payment,
+            total_interest: total_payment - balance - total_fees,
+        })
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Payment> {
+        self.payments.get(index)?.as_ref()
+    }
+
+    pub fn last(&self) -> Option<&Payment> {
+        self.len.checked_sub(1).and_then(|index| self.get(index))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Payment> {
+        self.payments.iter().flatten()
+    }
+}
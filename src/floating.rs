@@ -0,0 +1,148 @@
+// Floating rates set from an index plus a margin, e.g. SOFR + 2.75%, reset periodically and
+// held within the loan's caps and floor. The index follows a projected forward curve, and the
+// resulting rates become an ordinary rate schedule
+use crate::error::AmortizationError;
+use crate::rate::{RateAdjustment, RateCaps, RatePeriod};
+
+// Projected index level, in percent, from each period on; periods before the first point use
+// its level
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForwardCurve {
+    pub points: Vec<RatePeriod>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatingRate {
+    pub index: ForwardCurve,
+    pub margin: f64,
+    // Periods between resets
+    pub reset_every: u32,
+    // Periods at `initial_apr` before the first reset, as in a 5/1 ARM. Without an initial
+    // rate the loan starts at the index plus margin and first resets after `reset_every`
+    pub fixed_periods: u32,
+    pub initial_apr: Option<f64>,
+    pub caps: RateCaps,
+}
+
+fn curve_error(message: String) -> AmortizationError {
+    AmortizationError::InvalidRateSchedule(message)
+}
+
+impl ForwardCurve {
+    pub fn new(points: Vec<RatePeriod>) -> Self {
+        ForwardCurve { points }
+    }
+
+    pub fn flat(rate: f64) -> Self {
+        ForwardCurve { points: vec![RatePeriod::new(1, rate)] }
+    }
+
+    // Index level `start` plus `step` at every `every` periods, up to period `until`
+    pub fn stepped(start: f64, step: f64, every: u32, until: u32) -> Self {
+        let points = (0..)
+            .map(|n| n * every.max(1) + 1)
+            .take_while(|&period| period <= until.max(1))
+            .enumerate()
+            .map(|(n, period)| RatePeriod::new(period, start + step * n as f64))
+            .collect();
+        ForwardCurve { points }
+    }
+
+    // A parallel move of the whole curve, for stress testing
+    pub fn shifted(&self, basis_points: f64) -> Self {
+        ForwardCurve {
+            points: self.points.iter().map(|p| RatePeriod::new(p.start_period, p.apr + basis_points / 100.0)).collect(),
+        }
+    }
+
+    pub fn rate_at(&self, period: u32) -> f64 {
+        self.points.iter()
+            .filter(|p| p.start_period <= period)
+            .max_by_key(|p| p.start_period)
+            .or(self.points.first())
+            .map_or(0.0, |p| p.apr)
+    }
+}
+
+impl FloatingRate {
+    pub fn new(index: ForwardCurve, margin: f64, reset_every: u32) -> Self {
+        FloatingRate {
+            index,
+            margin,
+            reset_every,
+            fixed_periods: reset_every,
+            initial_apr: None,
+            caps: RateCaps::new(),
+        }
+    }
+
+    pub fn with_fixed_period(mut self, periods: u32, apr: f64) -> Self {
+        self.fixed_periods = periods;
+        self.initial_apr = Some(apr);
+        self
+    }
+
+    // The usual ARM cap structure, e.g. 2/2/5 or 5/2/5
+    pub fn with_caps(mut self, initial: f64, periodic: f64, lifetime: f64) -> Self {
+        self.caps = RateCaps { floor: self.caps.floor, ..RateCaps::structure(initial, periodic, lifetime) };
+        self
+    }
+
+    pub fn with_floor(mut self, floor: f64) -> Self {
+        self.caps.floor = Some(floor);
+        self
+    }
+
+    pub fn with_rate_caps(mut self, caps: RateCaps) -> Self {
+        self.caps = caps;
+        self
+    }
+
+    // The same loan against an index moved by `basis_points`, caps still applying
+    pub fn shocked(&self, basis_points: f64) -> Self {
+        FloatingRate { index: self.index.shifted(basis_points), ..self.clone() }
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.index.points.is_empty() {
+            return Err(curve_error("Forward curve needs at least one point".to_string()));
+        }
+        if let Some(point) = self.index.points.iter().find(|p| !p.apr.is_finite()) {
+            return Err(curve_error(format!("Index rate from period {} is not a number", point.start_period)));
+        }
+        if self.reset_every == 0 || self.fixed_periods == 0 {
+            return Err(curve_error("Floating rate must reset every one or more periods".to_string()));
+        }
+        if !self.margin.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(self.margin));
+        }
+        if let Some(apr) = self.initial_apr.filter(|apr| *apr < 0.0 || !apr.is_finite()) {
+            return Err(AmortizationError::InvalidInterestRate(apr));
+        }
+        match self.caps.invalid_value() {
+            Some(invalid) => Err(AmortizationError::InvalidRateSchedule(format!("Rate caps and floor must not be negative, got {}", invalid))),
+            None => Ok(()),
+        }
+    }
+
+    // The index plus margin at every reset of a loan of `periods` installments, before caps
+    pub fn uncapped_schedule(&self, periods: u32) -> Result<Vec<RatePeriod>, AmortizationError> {
+        self.validate()?;
+        let initial = self.initial_apr.unwrap_or_else(|| self.index.rate_at(1) + self.margin);
+        let resets = (0..)
+            .map(|n| self.fixed_periods + 1 + n * self.reset_every)
+            .take_while(|&period| period <= periods)
+            .map(|period| RatePeriod::new(period, self.index.rate_at(period) + self.margin));
+        Ok(std::iter::once(RatePeriod::new(1, initial)).chain(resets).collect())
+    }
+
+    pub fn adjustments(&self, periods: u32) -> Result<Vec<RateAdjustment>, AmortizationError> {
+        Ok(self.caps.apply(&self.uncapped_schedule(periods)?))
+    }
+
+    // Rates charged over a loan of `periods` installments; a reset that leaves the rate
+    // unchanged adds no entry
+    pub fn rate_schedule(&self, periods: u32) -> Result<Vec<RatePeriod>, AmortizationError> {
+        Ok(self.caps.capped_schedule(&self.uncapped_schedule(periods)?))
+    }
+}
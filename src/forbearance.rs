@@ -0,0 +1,64 @@
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::error::AmortizationError;
+use crate::skip::SkipTreatment;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CapitalizationPolicy {
+    // Accrued interest is added to the balance and the payment re-amortized over the original term
+    #[default]
+    Capitalize,
+    // Accrued interest is added to the balance and the maturity moves out by the forbearance length
+    ExtendTerm,
+    // The missed installments become a non-interest-bearing balance due at payoff (a partial
+    // claim), and the regular schedule resumes unchanged
+    DeferredBalance,
+}
+
+impl Amortization {
+    // Pauses `length` installments starting with `start_period`
+    pub fn forbearance(&self, start_period: u32, length: u32, policy: CapitalizationPolicy) -> Result<Amortization, AmortizationError> {
+        let end_period = start_period + length;
+        if start_period == 0 || length == 0 || end_period >= self.periods.min(self.schedule.len() as u32) {
+            return Err(AmortizationError::PeriodOutOfRange(start_period));
+        }
+
+        let treatment = match policy {
+            CapitalizationPolicy::Capitalize => SkipTreatment::Capitalize,
+            CapitalizationPolicy::ExtendTerm => SkipTreatment::ExtendTerm,
+            CapitalizationPolicy::DeferredBalance => return self.with_deferred_balance(start_period, end_period),
+        };
+        let mut skip_payments = self.config.skip_payments.clone().unwrap_or_default();
+        skip_payments.treatment = treatment;
+        skip_payments.installments.extend(start_period..end_period);
+        Amortization::from_config(CalculatorConfig {
+            skip_payments: Some(skip_payments),
+            ..self.config.clone()
+        })
+    }
+
+    // Non-interest-bearing balance built up by forbearance, repaid with the final installment
+    pub fn deferred_balance(&self) -> f64 {
+        self.schedule.iter().map(|p| p.deferred_amount).filter(|&amount| amount > 0.0).sum()
+    }
+
+    fn with_deferred_balance(&self, start_period: u32, end_period: u32) -> Result<Amortization, AmortizationError> {
+        let mut loan = self.clone();
+        let mut deferred = 0.0;
+        for payment in &mut loan.schedule[start_period as usize - 1..end_period as usize - 1] {
+            payment.deferred_amount = payment.installment_amount;
+            payment.installment_amount = 0.0;
+            payment.skipped = true;
+            deferred += payment.deferred_amount;
+        }
+        let deferred = loan.config.currency.round(deferred);
+        let last = loan.schedule.last_mut().ok_or(AmortizationError::PeriodOutOfRange(end_period))?;
+        last.deferred_amount -= deferred;
+        last.installment_amount += deferred;
+        last.balloon_amount += deferred;
+
+        loan.refresh_real_values();
+        loan.final_payment_amount = loan.calculate_final_payment_amount();
+        loan.refresh_totals()?;
+        Ok(loan)
+    }
+}
@@ -0,0 +1,167 @@
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::money::Money;
+#[cfg(feature = "i18n")]
+use crate::i18n::{Label, StringTable};
+use crate::table::{Column, FormatOptions, MISSING_DATE};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolPosition {
+    Prefix,
+    Suffix,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    pub decimal_separator: char,
+    pub thousands_separator: Option<char>,
+    pub symbol_position: SymbolPosition,
+    pub date_format: &'static str,
+}
+
+impl Locale {
+    pub const EN_US: Locale = Locale {
+        decimal_separator: '.',
+        thousands_separator: Some(','),
+        symbol_position: SymbolPosition::Prefix,
+        date_format: "%m/%d/%Y",
+    };
+    pub const EN_GB: Locale = Locale {
+        decimal_separator: '.',
+        thousands_separator: Some(','),
+        symbol_position: SymbolPosition::Prefix,
+        date_format: "%d/%m/%Y",
+    };
+    pub const DE_DE: Locale = Locale {
+        decimal_separator: ',',
+        thousands_separator: Some('.'),
+        symbol_position: SymbolPosition::Suffix,
+        date_format: "%d.%m.%Y",
+    };
+    // French groups digits with a no-break space
+    pub const FR_FR: Locale = Locale {
+        decimal_separator: ',',
+        thousands_separator: Some('\u{a0}'),
+        symbol_position: SymbolPosition::Suffix,
+        date_format: "%d/%m/%Y",
+    };
+    pub const ISO: Locale = Locale {
+        decimal_separator: '.',
+        thousands_separator: None,
+        symbol_position: SymbolPosition::Suffix,
+        date_format: "%Y-%m-%d",
+    };
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::EN_US
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ScheduleFormatter {
+    pub locale: Locale,
+    pub currency_symbol: Option<String>,
+    pub date_format: Option<String>,
+    pub decimals: Option<usize>,
+}
+
+impl ScheduleFormatter {
+    pub fn new(locale: Locale) -> Self {
+        ScheduleFormatter { locale, ..Default::default() }
+    }
+
+    pub fn with_currency_symbol(mut self, symbol: &str) -> Self {
+        self.currency_symbol = Some(symbol.to_string());
+        self
+    }
+
+    // A chrono format string overriding the locale's date format
+    pub fn with_date_format(mut self, date_format: &str) -> Self {
+        self.date_format = Some(date_format.to_string());
+        self
+    }
+
+    pub fn with_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    pub fn format_number(&self, amount: f64) -> String {
+        self.format_number_with(amount, self.decimals.unwrap_or(2), true)
+    }
+
+    fn format_number_with(&self, amount: f64, decimals: usize, group: bool) -> String {
+        let formatted = format!("{:.*}", decimals, amount);
+        let (sign, digits) = formatted.strip_prefix('-').map_or(("", formatted.as_str()), |rest| ("-", rest));
+        let (integer, fraction) = digits.split_once('.').map_or((digits, None), |(i, f)| (i, Some(f)));
+
+        let mut result = String::from(sign);
+        for (index, digit) in integer.chars().enumerate() {
+            if let Some(separator) = self.locale.thousands_separator.filter(|_| group) {
+                if index > 0 && (integer.len() - index) % 3 == 0 {
+                    result.push(separator);
+                }
+            }
+            result.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            result.push(self.locale.decimal_separator);
+            result.push_str(fraction);
+        }
+        result
+    }
+
+    pub fn format_amount(&self, amount: f64) -> String {
+        self.with_symbol(self.format_number(amount))
+    }
+
+    // Uses the currency's own minor units, and its code when no symbol is set
+    pub fn format_money(&self, money: Money) -> String {
+        let number = self.format_number_with(money.to_f64(), money.currency().minor_units() as usize, true);
+        match self.currency_symbol {
+            Some(_) => self.with_symbol(number),
+            None => format!("{} {}", number, money.currency().code()),
+        }
+    }
+
+    fn with_symbol(&self, number: String) -> String {
+        match (&self.currency_symbol, self.locale.symbol_position) {
+            (Some(symbol), SymbolPosition::Prefix) => match number.strip_prefix('-') {
+                Some(positive) => format!("-{}{}", symbol, positive),
+                None => format!("{}{}", symbol, number),
+            },
+            (Some(symbol), SymbolPosition::Suffix) => format!("{}\u{a0}{}", number, symbol),
+            (None, _) => number,
+        }
+    }
+
+    pub fn format_date(&self, date: Option<NaiveDate>) -> String {
+        let format = self.date_format.as_deref().unwrap_or(self.locale.date_format);
+        date.map_or_else(|| MISSING_DATE.to_string(), |date| date.format(format).to_string())
+    }
+
+    // The formatter's own decimals, when set, win over the options'
+    pub fn table(&self, loan: &Amortization, options: &FormatOptions) -> String {
+        let headers: Vec<&str> = options.columns.iter().map(Column::header).collect();
+        self.table_with_headers(loan, options, &headers)
+    }
+
+    // Headers in the labels' language; any they don't translate stay in English
+    #[cfg(feature = "i18n")]
+    pub fn table_in(&self, loan: &Amortization, options: &FormatOptions, labels: &dyn StringTable) -> String {
+        let headers: Vec<&str> = options.columns.iter().map(|&column| labels.text(Label::from(column))).collect();
+        self.table_with_headers(loan, options, &headers)
+    }
+
+    fn table_with_headers(&self, loan: &Amortization, options: &FormatOptions, headers: &[&str]) -> String {
+        let decimals = self.decimals.unwrap_or(options.decimals);
+        loan.render_table(
+            options,
+            headers,
+            |amount| self.with_symbol(self.format_number_with(amount, decimals, options.thousands_separator)),
+            |date| self.format_date(date),
+        )
+    }
+}
@@ -0,0 +1,98 @@
+use chrono::{Days, Months, NaiveDate};
+use crate::calendar::DateRollConvention;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaymentFrequency {
+    Weekly,
+    BiWeekly,
+    #[default]
+    Monthly,
+    Quarterly,
+    SemiAnnually,
+    Annually,
+}
+
+impl PaymentFrequency {
+    pub const ALL: [PaymentFrequency; 6] = [
+        PaymentFrequency::Weekly, PaymentFrequency::BiWeekly, PaymentFrequency::Monthly,
+        PaymentFrequency::Quarterly, PaymentFrequency::SemiAnnually, PaymentFrequency::Annually,
+    ];
+
+    // Lowercase name used in loan files, saved states and database rows
+    pub fn name(&self) -> &'static str {
+        match self {
+            PaymentFrequency::Weekly => "weekly",
+            PaymentFrequency::BiWeekly => "biweekly",
+            PaymentFrequency::Monthly => "monthly",
+            PaymentFrequency::Quarterly => "quarterly",
+            PaymentFrequency::SemiAnnually => "semiannually",
+            PaymentFrequency::Annually => "annually",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<PaymentFrequency> {
+        PaymentFrequency::ALL.into_iter().find(|frequency| frequency.name() == name)
+    }
+
+    pub fn periods_per_year(&self) -> u32 {
+        match self {
+            PaymentFrequency::Weekly => 52,
+            PaymentFrequency::BiWeekly => 26,
+            PaymentFrequency::Monthly => 12,
+            PaymentFrequency::Quarterly => 4,
+            PaymentFrequency::SemiAnnually => 2,
+            PaymentFrequency::Annually => 1,
+        }
+    }
+
+    // Saturates at `u32::MAX` rather than wrapping, so a term too long to count fails as one
+    // instead of becoming a short loan
+    pub fn periods_for_months(&self, months: u32) -> u32 {
+        let ppy = self.periods_per_year() as u64;
+        u32::try_from((months as u64 * ppy + 6) / 12).unwrap_or(u32::MAX)
+    }
+
+    pub fn next_date(&self, date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            PaymentFrequency::Weekly => date.checked_add_days(Days::new(7)),
+            PaymentFrequency::BiWeekly => date.checked_add_days(Days::new(14)),
+            PaymentFrequency::Monthly => date.checked_add_months(Months::new(1)),
+            PaymentFrequency::Quarterly => date.checked_add_months(Months::new(3)),
+            PaymentFrequency::SemiAnnually => date.checked_add_months(Months::new(6)),
+            PaymentFrequency::Annually => date.checked_add_months(Months::new(12)),
+        }
+    }
+
+    // Due date `periods` installments after `start`; month-based frequencies place the
+    // date with `convention`
+    pub fn due_date(&self, start: NaiveDate, periods: i32, convention: DateRollConvention) -> Option<NaiveDate> {
+        let days = match self {
+            PaymentFrequency::Weekly => 7,
+            PaymentFrequency::BiWeekly => 14,
+            _ => return convention.roll(start, periods * self.months_per_period() as i32),
+        };
+        start.checked_add_signed(chrono::Duration::try_days(days * periods as i64)?)
+    }
+
+    // Calendar months covered by one installment, zero for day-based frequencies
+    pub fn months_per_period(&self) -> u32 {
+        match self {
+            PaymentFrequency::Weekly | PaymentFrequency::BiWeekly => 0,
+            PaymentFrequency::Monthly => 1,
+            PaymentFrequency::Quarterly => 3,
+            PaymentFrequency::SemiAnnually => 6,
+            PaymentFrequency::Annually => 12,
+        }
+    }
+
+    pub fn previous_date(&self, date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            PaymentFrequency::Weekly => date.checked_sub_days(Days::new(7)),
+            PaymentFrequency::BiWeekly => date.checked_sub_days(Days::new(14)),
+            PaymentFrequency::Monthly => date.checked_sub_months(Months::new(1)),
+            PaymentFrequency::Quarterly => date.checked_sub_months(Months::new(3)),
+            PaymentFrequency::SemiAnnually => date.checked_sub_months(Months::new(6)),
+            PaymentFrequency::Annually => date.checked_sub_months(Months::new(12)),
+        }
+    }
+}
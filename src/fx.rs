@@ -0,0 +1,77 @@
+// Exchange rates for reporting loans in different currencies in one currency. A provider is
+// asked for the rate on each payment date, so a projection can use a single static table or
+// rates that move over time; any `Fn(Currency, Currency, NaiveDate) -> Option<f64>` is one too
+use std::collections::BTreeMap;
+use chrono::NaiveDate;
+use crate::money::Currency;
+
+pub trait FxRateProvider {
+    // Units of `to` bought by one unit of `from` on `date`, or None when the rate is unknown
+    fn rate(&self, from: Currency, to: Currency, date: NaiveDate) -> Option<f64>;
+}
+
+impl<F: Fn(Currency, Currency, NaiveDate) -> Option<f64>> FxRateProvider for F {
+    fn rate(&self, from: Currency, to: Currency, date: NaiveDate) -> Option<f64> {
+        self(from, to, date)
+    }
+}
+
+// One rate per currency pair whatever the date. A pair quoted one way also converts the other
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StaticRates {
+    rates: BTreeMap<(Currency, Currency), f64>,
+}
+
+impl StaticRates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rate(mut self, from: Currency, to: Currency, rate: f64) -> Self {
+        self.rates.insert((from, to), rate);
+        self
+    }
+}
+
+impl FxRateProvider for StaticRates {
+    fn rate(&self, from: Currency, to: Currency, _date: NaiveDate) -> Option<f64> {
+        if from == to {
+            return Some(1.0);
+        }
+        match self.rates.get(&(from, to)) {
+            Some(&rate) => Some(rate),
+            None => self.rates.get(&(to, from)).map(|&rate| 1.0 / rate),
+        }
+    }
+}
+
+// Rates quoted on given dates, each holding until the pair's next quote; there is no rate
+// before a pair's first quote
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DatedRates {
+    rates: BTreeMap<(Currency, Currency), BTreeMap<NaiveDate, f64>>,
+}
+
+impl DatedRates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rate(mut self, from: Currency, to: Currency, date: NaiveDate, rate: f64) -> Self {
+        self.rates.entry((from, to)).or_default().insert(date, rate);
+        self
+    }
+
+    fn quoted(&self, from: Currency, to: Currency, date: NaiveDate) -> Option<f64> {
+        self.rates.get(&(from, to))?.range(..=date).next_back().map(|(_, &rate)| rate)
+    }
+}
+
+impl FxRateProvider for DatedRates {
+    fn rate(&self, from: Currency, to: Currency, date: NaiveDate) -> Option<f64> {
+        if from == to {
+            return Some(1.0);
+        }
+        self.quoted(from, to, date).or_else(|| self.quoted(to, from, date).map(|rate| 1.0 / rate))
+    }
+}
@@ -0,0 +1,230 @@
+use chrono::NaiveDate;
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::calendar::DateRollConvention;
+use crate::error::AmortizationError;
+use crate::floating::FloatingRate;
+use crate::frequency::PaymentFrequency;
+use crate::money::Currency;
+use crate::rate::RatePeriod;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelocPhase {
+    Draw,
+    Repayment,
+}
+
+// Money moved in draw period `period`: positive amounts are draws, negative amounts are
+// principal paid back on top of the interest-only minimum
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HelocActivity {
+    pub period: u32,
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heloc {
+    pub credit_limit: f64,
+    pub apr: f64,
+    pub draw_months: u32,
+    pub repayment_months: u32,
+    pub activity: Vec<HelocActivity>,
+    // Rate changes over the whole life of the line, numbered from the first draw period
+    pub rate_schedule: Vec<RatePeriod>,
+    pub start_date: Option<NaiveDate>,
+    pub currency: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HelocRow {
+    pub period: u32,
+    pub phase: HelocPhase,
+    pub date: Option<NaiveDate>,
+    pub apr: f64,
+    pub draw: f64,
+    pub interest: f64,
+    pub principal: f64,
+    pub payment: f64,
+    pub balance: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct HelocSchedule {
+    pub rows: Vec<HelocRow>,
+    pub balance_at_conversion: f64,
+    pub peak_balance: f64,
+    // The amortizing loan the line converts into; None when nothing is owed at the end of
+    // the draw period
+    pub repayment: Option<Amortization>,
+    pub total_interest: f64,
+    pub total_paid: f64,
+}
+
+impl Heloc {
+    pub fn new(credit_limit: f64, apr: f64, draw_months: u32, repayment_months: u32) -> Self {
+        Heloc {
+            credit_limit,
+            apr,
+            draw_months,
+            repayment_months,
+            activity: Vec::new(),
+            rate_schedule: Vec::new(),
+            start_date: None,
+            currency: Currency::default(),
+        }
+    }
+
+    pub fn with_draw(mut self, period: u32, amount: f64) -> Self {
+        self.activity.push(HelocActivity { period, amount });
+        self
+    }
+
+    pub fn with_repayment(mut self, period: u32, amount: f64) -> Self {
+        self.activity.push(HelocActivity { period, amount: -amount });
+        self
+    }
+
+    pub fn with_rate_change(mut self, period: u32, apr: f64) -> Self {
+        self.rate_schedule.push(RatePeriod::new(period, apr));
+        self
+    }
+
+    // Replaces the APR and rate changes with rates projected off `floating` over the whole
+    // life of the line
+    pub fn with_floating_rate(mut self, floating: &FloatingRate) -> Result<Self, AmortizationError> {
+        let rate_schedule = floating.rate_schedule(self.draw_months + self.repayment_months)?;
+        self.apr = rate_schedule[0].apr;
+        self.rate_schedule = rate_schedule[1..].to_vec();
+        Ok(self)
+    }
+
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    pub fn apr_for(&self, period: u32) -> f64 {
+        self.rate_schedule.iter()
+            .filter(|r| r.start_period <= period)
+            .max_by_key(|r| r.start_period)
+            .map_or(self.apr, |r| r.apr)
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.credit_limit <= 0.0 || !self.credit_limit.is_finite() {
+            return Err(AmortizationError::InvalidLoanAmount(self.credit_limit));
+        }
+        if let Some(apr) = std::iter::once(self.apr).chain(self.rate_schedule.iter().map(|r| r.apr)).find(|apr| *apr < 0.0 || !apr.is_finite()) {
+            return Err(AmortizationError::InvalidInterestRate(apr));
+        }
+        if self.draw_months == 0 {
+            return Err(AmortizationError::InvalidPeriods(self.draw_months));
+        }
+        if self.repayment_months == 0 {
+            return Err(AmortizationError::InvalidPeriods(self.repayment_months));
+        }
+        for activity in &self.activity {
+            if activity.period == 0 || activity.period > self.draw_months {
+                return Err(AmortizationError::PeriodOutOfRange(activity.period));
+            }
+            if activity.amount == 0.0 || !activity.amount.is_finite() {
+                return Err(AmortizationError::InvalidPaymentAmount(activity.amount));
+            }
+        }
+        Ok(())
+    }
+
+    fn due_date(&self, period: u32) -> Option<NaiveDate> {
+        let start_date = self.start_date?;
+        PaymentFrequency::Monthly.due_date(start_date, period as i32 - 1, DateRollConvention::SameDay)
+    }
+
+    pub fn schedule(&self) -> Result<HelocSchedule, AmortizationError> {
+        self.validate()?;
+        let mut rows = Vec::new();
+        let mut balance: f64 = 0.0;
+        let mut peak_balance: f64 = 0.0;
+        for period in 1..=self.draw_months {
+            let activity = self.activity.iter().filter(|a| a.period == period);
+            let draw: f64 = activity.clone().filter(|a| a.amount > 0.0).map(|a| a.amount).sum();
+            let principal: f64 = activity.filter(|a| a.amount < 0.0).map(|a| -a.amount).sum();
+            let drawn_balance = self.currency.round(balance + draw);
+            if drawn_balance > self.credit_limit {
+                return Err(AmortizationError::CreditLimitExceeded(drawn_balance));
+            }
+            if principal > drawn_balance {
+                return Err(AmortizationError::InvalidPaymentAmount(principal));
+            }
+            // Draws are funded at the start of the period and accrue its full interest
+            let apr = self.apr_for(period);
+            let interest = self.currency.round(drawn_balance * apr / 100.0 / 12.0);
+            balance = self.currency.round(drawn_balance - principal);
+            peak_balance = peak_balance.max(drawn_balance);
+            rows.push(HelocRow {
+                period,
+                phase: HelocPhase::Draw,
+                date: self.due_date(period),
+                apr,
+                draw,
+                interest,
+                principal,
+                payment: interest + principal,
+                balance,
+            });
+        }
+
+        let balance_at_conversion = balance;
+        let repayment = if balance > 0.0 {
+            Some(self.repayment_loan(balance)?)
+        } else {
+            None
+        };
+        if let Some(loan) = &repayment {
+            rows.extend(loan.schedule.iter().map(|payment| HelocRow {
+                period: payment.installment_number,
+                phase: HelocPhase::Repayment,
+                date: payment.date,
+                apr: self.apr_for(payment.installment_number),
+                draw: 0.0,
+                interest: payment.interest,
+                principal: payment.principal,
+                payment: payment.installment_amount,
+                balance: payment.remaining_balance,
+            }));
+        }
+
+        Ok(HelocSchedule {
+            total_interest: rows.iter().map(|row| row.interest).sum(),
+            total_paid: rows.iter().map(|row| row.payment).sum(),
+            balance_at_conversion,
+            peak_balance,
+            repayment,
+            rows,
+        })
+    }
+
+    // Amortizes the balance over the repayment period; rate changes re-amortize it over the
+    // periods that remain
+    fn repayment_loan(&self, balance: f64) -> Result<Amortization, AmortizationError> {
+        let first_period = self.draw_months + 1;
+        let mut rate_schedule = vec![RatePeriod::new(1, self.apr_for(first_period))];
+        rate_schedule.extend(self.rate_schedule.iter()
+            .filter(|r| r.start_period > first_period)
+            .map(|r| RatePeriod::new(r.start_period - self.draw_months, r.apr)));
+        rate_schedule.sort_by_key(|r| r.start_period);
+
+        let mut loan = Amortization::from_config(CalculatorConfig {
+            rate_schedule,
+            currency: self.currency,
+            ..CalculatorConfig::new(balance, self.apr_for(first_period), self.repayment_months, self.due_date(first_period))
+        })?;
+        for payment in &mut loan.schedule {
+            payment.installment_number += self.draw_months;
+        }
+        Ok(loan)
+    }
+}
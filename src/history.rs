@@ -0,0 +1,169 @@
+// Import of an actual payment history from CSV, one `date,amount` row per payment received.
+// Rows are read one at a time, so a long history never has to be held as text
+use std::io::BufRead;
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::error::AmortizationError;
+use crate::servicing::{ActualPayment, PaymentLedger};
+
+const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%m/%d/%Y", "%Y/%m/%d"];
+
+pub struct PaymentHistoryReader<R> {
+    reader: R,
+    line_number: usize,
+    // Column positions, taken from the header when there is one
+    date_column: Option<usize>,
+    amount_column: usize,
+    started: bool,
+    line: String,
+}
+
+fn history_error(line_number: usize, message: &str) -> AmortizationError {
+    AmortizationError::InvalidPaymentHistory(format!("line {}: {}", line_number, message))
+}
+
+// Splits a CSV line on commas outside double quotes; quotes are dropped and `""` is a quote
+pub(crate) fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.iter().map(|f| f.trim().to_string()).collect()
+}
+
+// Accepts the way banks tend to export amounts: "$1,234.56", with the sign or in parentheses
+pub(crate) fn parse_amount(field: &str) -> Option<f64> {
+    let (negative, field) = match field.strip_prefix('(').and_then(|f| f.strip_suffix(')')) {
+        Some(inner) => (true, inner),
+        None => (false, field),
+    };
+    let cleaned: String = field.chars().filter(|&c| c != '$' && c != ',' && !c.is_whitespace()).collect();
+    let amount: f64 = cleaned.parse().ok().filter(|a: &f64| a.is_finite())?;
+    Some(if negative { -amount } else { amount })
+}
+
+pub(crate) fn parse_date(field: &str) -> Option<NaiveDate> {
+    DATE_FORMATS.iter().find_map(|format| NaiveDate::parse_from_str(field, format).ok())
+}
+
+impl<R: BufRead> PaymentHistoryReader<R> {
+    pub fn new(reader: R) -> Self {
+        PaymentHistoryReader {
+            reader,
+            line_number: 0,
+            date_column: Some(0),
+            amount_column: 1,
+            started: false,
+            line: String::new(),
+        }
+    }
+
+    // A header is recognised by naming an `amount` column; an `amount`-only file has no dates
+    fn read_header(&mut self, fields: &[String]) -> bool {
+        let position = |name: &str| fields.iter().position(|f| f.eq_ignore_ascii_case(name));
+        let Some(amount_column) = position("amount") else {
+            return false;
+        };
+        self.amount_column = amount_column;
+        self.date_column = position("date");
+        true
+    }
+
+    fn parse_row(&self, fields: &[String]) -> Result<ActualPayment, AmortizationError> {
+        let field = |column: usize| fields.get(column).map(String::as_str).unwrap_or("");
+        let amount_field = field(self.amount_column);
+        let amount = parse_amount(amount_field)
+            .ok_or_else(|| history_error(self.line_number, &format!("invalid amount '{}'", amount_field)))?;
+        if amount < 0.0 {
+            return Err(history_error(self.line_number, &format!("negative amount {}", amount)));
+        }
+        let mut payment = ActualPayment::new(amount);
+        if let Some(column) = self.date_column {
+            let date_field = field(column);
+            if !date_field.is_empty() {
+                let date = parse_date(date_field)
+                    .ok_or_else(|| history_error(self.line_number, &format!("invalid date '{}'", date_field)))?;
+                payment = payment.with_date(date);
+            }
+        }
+        Ok(payment)
+    }
+}
+
+impl<R: BufRead> Iterator for PaymentHistoryReader<R> {
+    type Item = Result<ActualPayment, AmortizationError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            self.line_number += 1;
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(error) => return Some(Err(history_error(self.line_number, &error.to_string()))),
+            }
+            let fields = split_fields(self.line.trim_end_matches(['\r', '\n']));
+            if fields.iter().all(String::is_empty) {
+                continue;
+            }
+            if !self.started {
+                self.started = true;
+                if self.read_header(&fields) {
+                    continue;
+                }
+            }
+            return Some(self.parse_row(&fields));
+        }
+    }
+}
+
+impl Amortization {
+    // Reconciles a CSV payment history against the schedule. On a dated schedule every payment
+    // counts towards the first installment due on or after it, so split and late payments
+    // land in the right period and installments with no payment are recorded as missed.
+    // Undated payments, or any payment on an undated schedule, cover one installment each
+    pub fn reconcile_history<R: BufRead>(&self, reader: R) -> Result<PaymentLedger, AmortizationError> {
+        let mut payments: Vec<ActualPayment> = Vec::new();
+        // Installment the next dated payment counts towards, with what it has received so far
+        let mut installment = 1;
+        let mut pending: Option<ActualPayment> = None;
+        let mut last_date: Option<NaiveDate> = None;
+
+        for (index, row) in PaymentHistoryReader::new(reader).enumerate() {
+            let payment = row?;
+            let (Some(date), Some(_)) = (payment.date, self.start_date) else {
+                payments.extend(pending.take());
+                payments.push(payment);
+                installment = payments.len() as u32 + 1;
+                continue;
+            };
+            if last_date.is_some_and(|last| date < last) {
+                return Err(AmortizationError::InvalidPaymentHistory(format!("payment {} is dated before the one above it", index + 1)));
+            }
+            last_date = Some(date);
+
+            while self.payment_date(installment).is_some_and(|due| date > due) {
+                payments.push(pending.take().unwrap_or(ActualPayment::new(0.0)));
+                installment += 1;
+            }
+            pending = Some(match pending {
+                Some(received) => ActualPayment::new(received.amount + payment.amount).with_date(date),
+                None => payment,
+            });
+        }
+        payments.extend(pending);
+        self.apply_payments(&payments)
+    }
+}
@@ -0,0 +1,241 @@
+// Translated labels for tables, Markdown and HTML reports behind the `i18n` feature. A
+// `StringTable` maps each `Label` to text; `Language` ships Spanish, French, German and Urdu,
+// and a `BTreeMap<Label, String>` serves any other language or house wording
+use std::collections::BTreeMap;
+use crate::report::SUMMARY_LABELS;
+use crate::table::Column;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Label {
+    // Schedule columns
+    Installment,
+    Date,
+    BeginningBalance,
+    Payment,
+    Interest,
+    Principal,
+    CapitalizedInterest,
+    Escrow,
+    Fees,
+    RemainingBalance,
+    // Summary fields
+    LoanAmount,
+    Apr,
+    Periods,
+    PeriodicPayment,
+    TotalPayment,
+    TotalInterest,
+    StartDate,
+    EndDate,
+    // Headers of the Markdown summary
+    Field,
+    Value,
+}
+
+impl Label {
+    // In the order reports list them
+    pub const SUMMARY: [Label; 8] = [
+        Label::LoanAmount, Label::Apr, Label::Periods, Label::PeriodicPayment,
+        Label::TotalPayment, Label::TotalInterest, Label::StartDate, Label::EndDate,
+    ];
+
+    pub fn english(&self) -> &'static str {
+        match self {
+            Label::Installment => Column::Installment.header(),
+            Label::Date => Column::Date.header(),
+            Label::BeginningBalance => Column::BeginningBalance.header(),
+            Label::Payment => Column::Payment.header(),
+            Label::Interest => Column::Interest.header(),
+            Label::Principal => Column::Principal.header(),
+            Label::CapitalizedInterest => Column::CapitalizedInterest.header(),
+            Label::Escrow => Column::Escrow.header(),
+            Label::Fees => Column::Fees.header(),
+            Label::RemainingBalance => Column::RemainingBalance.header(),
+            Label::LoanAmount => SUMMARY_LABELS[0],
+            Label::Apr => SUMMARY_LABELS[1],
+            Label::Periods => SUMMARY_LABELS[2],
+            Label::PeriodicPayment => SUMMARY_LABELS[3],
+            Label::TotalPayment => SUMMARY_LABELS[4],
+            Label::TotalInterest => SUMMARY_LABELS[5],
+            Label::StartDate => SUMMARY_LABELS[6],
+            Label::EndDate => SUMMARY_LABELS[7],
+            Label::Field => "Field",
+            Label::Value => "Value",
+        }
+    }
+}
+
+impl From<Column> for Label {
+    fn from(column: Column) -> Self {
+        match column {
+            Column::Installment => Label::Installment,
+            Column::Date => Label::Date,
+            Column::BeginningBalance => Label::BeginningBalance,
+            Column::Payment => Label::Payment,
+            Column::Interest => Label::Interest,
+            Column::Principal => Label::Principal,
+            Column::CapitalizedInterest => Label::CapitalizedInterest,
+            Column::Escrow => Label::Escrow,
+            Column::Fees => Label::Fees,
+            Column::RemainingBalance => Label::RemainingBalance,
+        }
+    }
+}
+
+pub trait StringTable {
+    fn get(&self, label: Label) -> Option<&str>;
+
+    // Labels the table leaves out are written in English
+    fn text(&self, label: Label) -> &str {
+        self.get(label).unwrap_or_else(|| label.english())
+    }
+}
+
+impl StringTable for BTreeMap<Label, String> {
+    fn get(&self, label: Label) -> Option<&str> {
+        BTreeMap::get(self, &label).map(String::as_str)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    French,
+    German,
+    Urdu,
+}
+
+impl Language {
+    // ISO 639-1 code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::French => "fr",
+            Language::German => "de",
+            Language::Urdu => "ur",
+        }
+    }
+
+    // Accepts region-qualified tags such as `es-MX` or `fr_CA`
+    pub fn from_code(code: &str) -> Option<Language> {
+        let primary = code.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+        [Language::English, Language::Spanish, Language::French, Language::German, Language::Urdu]
+            .into_iter()
+            .find(|language| language.code() == primary)
+    }
+
+    fn spanish(label: Label) -> &'static str {
+        match label {
+            Label::Installment => "N.º",
+            Label::Date => "Fecha",
+            Label::BeginningBalance => "Saldo inicial",
+            Label::Payment => "Pago",
+            Label::Interest => "Interés",
+            Label::Principal => "Capital",
+            Label::CapitalizedInterest => "Capitalizado",
+            Label::Escrow => "Depósito",
+            Label::Fees => "Comisiones",
+            Label::RemainingBalance => "Saldo",
+            Label::LoanAmount => "Importe del préstamo",
+            Label::Apr => "TAE",
+            Label::Periods => "Períodos",
+            Label::PeriodicPayment => "Cuota periódica",
+            Label::TotalPayment => "Total pagado",
+            Label::TotalInterest => "Intereses totales",
+            Label::StartDate => "Fecha de inicio",
+            Label::EndDate => "Fecha de fin",
+            Label::Field => "Campo",
+            Label::Value => "Valor",
+        }
+    }
+
+    fn french(label: Label) -> &'static str {
+        match label {
+            Label::Installment => "N°",
+            Label::Date => "Date",
+            Label::BeginningBalance => "Solde initial",
+            Label::Payment => "Échéance",
+            Label::Interest => "Intérêts",
+            Label::Principal => "Capital",
+            Label::CapitalizedInterest => "Capitalisés",
+            Label::Escrow => "Séquestre",
+            Label::Fees => "Frais",
+            Label::RemainingBalance => "Solde",
+            Label::LoanAmount => "Montant du prêt",
+            Label::Apr => "TAEG",
+            Label::Periods => "Périodes",
+            Label::PeriodicPayment => "Échéance périodique",
+            Label::TotalPayment => "Total payé",
+            Label::TotalInterest => "Total des intérêts",
+            Label::StartDate => "Date de début",
+            Label::EndDate => "Date de fin",
+            Label::Field => "Champ",
+            Label::Value => "Valeur",
+        }
+    }
+
+    fn german(label: Label) -> &'static str {
+        match label {
+            Label::Installment => "Nr.",
+            Label::Date => "Datum",
+            Label::BeginningBalance => "Anfangssaldo",
+            Label::Payment => "Rate",
+            Label::Interest => "Zinsen",
+            Label::Principal => "Tilgung",
+            Label::CapitalizedInterest => "Kapitalisiert",
+            Label::Escrow => "Treuhand",
+            Label::Fees => "Gebühren",
+            Label::RemainingBalance => "Restschuld",
+            Label::LoanAmount => "Darlehensbetrag",
+            Label::Apr => "Effektivzins",
+            Label::Periods => "Perioden",
+            Label::PeriodicPayment => "Periodische Rate",
+            Label::TotalPayment => "Gesamtbetrag",
+            Label::TotalInterest => "Zinsen gesamt",
+            Label::StartDate => "Beginn",
+            Label::EndDate => "Ende",
+            Label::Field => "Feld",
+            Label::Value => "Wert",
+        }
+    }
+
+    fn urdu(label: Label) -> &'static str {
+        match label {
+            Label::Installment => "نمبر",
+            Label::Date => "تاریخ",
+            Label::BeginningBalance => "ابتدائی بیلنس",
+            Label::Payment => "ادائیگی",
+            Label::Interest => "سود",
+            Label::Principal => "اصل رقم",
+            Label::CapitalizedInterest => "سرمایہ شدہ سود",
+            Label::Escrow => "ایسکرو",
+            Label::Fees => "فیس",
+            Label::RemainingBalance => "بقایا",
+            Label::LoanAmount => "قرض کی رقم",
+            Label::Apr => "سالانہ شرح",
+            Label::Periods => "مدتیں",
+            Label::PeriodicPayment => "قسط",
+            Label::TotalPayment => "کل ادائیگی",
+            Label::TotalInterest => "کل سود",
+            Label::StartDate => "آغاز کی تاریخ",
+            Label::EndDate => "اختتام کی تاریخ",
+            Label::Field => "مد",
+            Label::Value => "قدر",
+        }
+    }
+}
+
+impl StringTable for Language {
+    fn get(&self, label: Label) -> Option<&str> {
+        Some(match self {
+            Language::English => label.english(),
+            Language::Spanish => Language::spanish(label),
+            Language::French => Language::french(label),
+            Language::German => Language::german(label),
+            Language::Urdu => Language::urdu(label),
+        })
+    }
+}
@@ -0,0 +1,149 @@
+// iCalendar (RFC 5545) payment reminders: one all-day event per installment with an amount due,
+// the amount and its split in the description, for loading into Google, Apple or Outlook
+// calendars. An optional alarm reminds the borrower a few days before each due date
+use chrono::{Days, NaiveDate, NaiveDateTime};
+use crate::calculator::Amortization;
+use crate::error::AmortizationError;
+
+// Longest content line the format allows, in octets, before it has to be folded
+const MAX_LINE_OCTETS: usize = 75;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcsOptions {
+    // Event title, followed by the amount due
+    pub summary: String,
+    // Start of every event UID; give each loan its own so calendars don't merge their events
+    pub uid_prefix: String,
+    // Days before the due date to raise an alarm; no alarm when not set
+    pub reminder_days: Option<u32>,
+    // Written as every event's DTSTAMP, in UTC; midnight on the first due date when not set,
+    // so the same schedule always produces the same file
+    pub created_at: Option<NaiveDateTime>,
+}
+
+impl Default for IcsOptions {
+    fn default() -> Self {
+        IcsOptions {
+            summary: "Loan payment".to_string(),
+            uid_prefix: "loan".to_string(),
+            reminder_days: None,
+            created_at: None,
+        }
+    }
+}
+
+impl IcsOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_summary(mut self, summary: &str) -> Self {
+        self.summary = summary.to_string();
+        self
+    }
+
+    pub fn with_uid_prefix(mut self, uid_prefix: &str) -> Self {
+        self.uid_prefix = uid_prefix.to_string();
+        self
+    }
+
+    pub fn with_reminder_days(mut self, days: u32) -> Self {
+        self.reminder_days = Some(days);
+        self
+    }
+
+    pub fn with_created_at(mut self, created_at: NaiveDateTime) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+}
+
+// Line breaks of any kind become `\n`, and other control characters, which TEXT values can't
+// hold, are dropped, so no value can start a property of its own
+fn escape(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+        .replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,").replace('\n', "\\n")
+        .chars().filter(|c| !c.is_control() || *c == '\t').collect()
+}
+
+// Splits a content line into 75-octet pieces, continuing each on a line starting with a space
+fn fold(line: &str, ics: &mut String) {
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            ics.push_str("\r\n ");
+            octets = 1;
+        }
+        ics.push(c);
+        octets += c.len_utf8();
+    }
+    ics.push_str("\r\n");
+}
+
+fn ics_date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+impl Amortization {
+    pub fn to_ics(&self) -> Result<String, AmortizationError> {
+        self.to_ics_with(&IcsOptions::default())
+    }
+
+    pub fn to_ics_with(&self, options: &IcsOptions) -> Result<String, AmortizationError> {
+        let due: Vec<_> = self.schedule.iter().filter(|p| self.money(p.installment_amount).minor() != 0).collect();
+        let dates = due.iter()
+            .map(|p| p.date.ok_or(AmortizationError::MissingParameter("start_date")))
+            .collect::<Result<Vec<_>, _>>()?;
+        let created_at = options.created_at
+            .or_else(|| dates.first().and_then(|d| d.and_hms_opt(0, 0, 0)))
+            .ok_or(AmortizationError::MissingParameter("start_date"))?;
+
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//amortize-rs//Payment Schedule//EN".to_string(),
+            "CALSCALE:GREGORIAN".to_string(),
+            "METHOD:PUBLISH".to_string(),
+        ];
+        for (payment, date) in due.iter().zip(dates) {
+            let amount = self.money(payment.installment_amount);
+            let mut description = format!("Installment {} of {}: {} (interest {}, principal {}",
+                payment.installment_number, self.schedule.len(), amount, self.money(payment.interest), self.money(payment.principal));
+            if payment.fees > 0.0 {
+                description.push_str(&format!(", fees {}", self.money(payment.fees)));
+            }
+            if payment.escrow_amount() > 0.0 {
+                description.push_str(&format!(", escrow {}", self.money(payment.escrow_amount())));
+            }
+            description.push_str(&format!(")\nRemaining balance: {}", self.money(payment.remaining_balance)));
+
+            lines.extend([
+                "BEGIN:VEVENT".to_string(),
+                format!("UID:{}-{}-{}@amortize-rs", escape(&options.uid_prefix), payment.installment_number, ics_date(date)),
+                format!("DTSTAMP:{}", created_at.format("%Y%m%dT%H%M%SZ")),
+                format!("DTSTART;VALUE=DATE:{}", ics_date(date)),
+                format!("DTEND;VALUE=DATE:{}", ics_date(date + Days::new(1))),
+                format!("SUMMARY:{}", escape(&format!("{}: {}", options.summary, amount))),
+                format!("DESCRIPTION:{}", escape(&description)),
+                "TRANSP:TRANSPARENT".to_string(),
+            ]);
+            if let Some(days) = options.reminder_days {
+                lines.extend([
+                    "BEGIN:VALARM".to_string(),
+                    "ACTION:DISPLAY".to_string(),
+                    format!("DESCRIPTION:{}", escape(&format!("{} of {} due", options.summary, amount))),
+                    format!("TRIGGER:-P{}D", days),
+                    "END:VALARM".to_string(),
+                ]);
+            }
+            lines.push("END:VEVENT".to_string());
+        }
+        lines.push("END:VCALENDAR".to_string());
+
+        let mut ics = String::new();
+        for line in &lines {
+            fold(line, &mut ics);
+        }
+        Ok(ics)
+    }
+}
@@ -0,0 +1,273 @@
+// Income-driven repayment of student loans: the monthly payment is a share of discretionary
+// income (income above a multiple of the poverty line), recalculated once a year, and whatever
+// is still owed after 20 or 25 years is forgiven
+use chrono::NaiveDate;
+use crate::calendar::DateRollConvention;
+use crate::error::AmortizationError;
+use crate::frequency::PaymentFrequency;
+use crate::money::Currency;
+use crate::tvm::{self, PaymentTiming};
+
+// 2024 HHS poverty guideline for the 48 contiguous states
+const POVERTY_LINE: f64 = 15_060.0;
+const POVERTY_LINE_PER_PERSON: f64 = 5_380.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdrPlan {
+    pub balance: f64,
+    pub apr: f64,
+    pub annual_income: f64,
+    // Yearly growth of income and of the poverty line, in percent
+    pub income_growth: f64,
+    pub poverty_line_growth: f64,
+    pub family_size: u32,
+    // Poverty line for a household of one and the amount added per additional person
+    pub poverty_line: f64,
+    pub poverty_line_per_person: f64,
+    // Income up to this percent of the poverty line is protected, e.g. 150 or 225
+    pub poverty_multiple: f64,
+    // Share of discretionary income paid each year, e.g. 10 or 15
+    pub payment_percent: f64,
+    pub forgiveness_years: u32,
+    // Payment stops rising at the 10-year standard installment on the original balance
+    pub standard_payment_cap: bool,
+    // Percent of the interest a payment leaves unpaid that is waived
+    pub interest_subsidy: f64,
+    pub start_date: Option<NaiveDate>,
+    pub currency: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdrMonth {
+    pub month: u32,
+    pub date: Option<NaiveDate>,
+    pub annual_income: f64,
+    pub payment: f64,
+    pub interest: f64,
+    // Interest left unpaid by the payment and waived
+    pub subsidy: f64,
+    pub principal: f64,
+    // Interest accrued but not yet paid; it does not itself accrue interest
+    pub unpaid_interest: f64,
+    pub balance: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct IdrProjection {
+    pub months: Vec<IdrMonth>,
+    pub total_paid: f64,
+    pub total_interest: f64,
+    pub total_subsidy: f64,
+    // Month the loan is repaid before forgiveness, if it is
+    pub payoff_month: Option<u32>,
+    // Balance and unpaid interest forgiven at the end of the plan
+    pub forgiven_amount: f64,
+    pub forgiveness_date: Option<NaiveDate>,
+}
+
+fn plan_error(message: String) -> AmortizationError {
+    AmortizationError::InvalidRepaymentPlan(message)
+}
+
+impl IdrPlan {
+    // 10% of income above 150% of the poverty line with forgiveness after 20 years, as under
+    // PAYE and IBR for new borrowers
+    pub fn new(balance: f64, apr: f64, annual_income: f64) -> Self {
+        IdrPlan {
+            balance,
+            apr,
+            annual_income,
+            income_growth: 0.0,
+            poverty_line_growth: 0.0,
+            family_size: 1,
+            poverty_line: POVERTY_LINE,
+            poverty_line_per_person: POVERTY_LINE_PER_PERSON,
+            poverty_multiple: 150.0,
+            payment_percent: 10.0,
+            forgiveness_years: 20,
+            standard_payment_cap: false,
+            interest_subsidy: 0.0,
+            start_date: None,
+            currency: Currency::default(),
+        }
+    }
+
+    pub fn with_income_growth(mut self, income_growth: f64) -> Self {
+        self.income_growth = income_growth;
+        self
+    }
+
+    pub fn with_poverty_line_growth(mut self, poverty_line_growth: f64) -> Self {
+        self.poverty_line_growth = poverty_line_growth;
+        self
+    }
+
+    pub fn with_family_size(mut self, family_size: u32) -> Self {
+        self.family_size = family_size;
+        self
+    }
+
+    pub fn with_poverty_line(mut self, poverty_line: f64, per_person: f64) -> Self {
+        self.poverty_line = poverty_line;
+        self.poverty_line_per_person = per_person;
+        self
+    }
+
+    pub fn with_poverty_multiple(mut self, poverty_multiple: f64) -> Self {
+        self.poverty_multiple = poverty_multiple;
+        self
+    }
+
+    pub fn with_payment_percent(mut self, payment_percent: f64) -> Self {
+        self.payment_percent = payment_percent;
+        self
+    }
+
+    pub fn with_forgiveness_years(mut self, forgiveness_years: u32) -> Self {
+        self.forgiveness_years = forgiveness_years;
+        self
+    }
+
+    pub fn with_standard_payment_cap(mut self) -> Self {
+        self.standard_payment_cap = true;
+        self
+    }
+
+    pub fn with_interest_subsidy(mut self, interest_subsidy: f64) -> Self {
+        self.interest_subsidy = interest_subsidy;
+        self
+    }
+
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.balance <= 0.0 || !self.balance.is_finite() {
+            return Err(AmortizationError::InvalidLoanAmount(self.balance));
+        }
+        if self.apr < 0.0 || !self.apr.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(self.apr));
+        }
+        if self.forgiveness_years == 0 {
+            return Err(AmortizationError::InvalidPeriods(0));
+        }
+        let amounts = [
+            ("annual income", self.annual_income),
+            ("poverty line", self.poverty_line),
+            ("poverty line per person", self.poverty_line_per_person),
+            ("poverty multiple", self.poverty_multiple),
+        ];
+        if let Some((name, value)) = amounts.iter().find(|(_, value)| *value < 0.0 || !value.is_finite()) {
+            return Err(plan_error(format!("{} must not be negative, got {}", name, value)));
+        }
+        let percents = [("payment percent", self.payment_percent), ("interest subsidy", self.interest_subsidy)];
+        if let Some((name, value)) = percents.iter().find(|(_, value)| !(0.0..=100.0).contains(value)) {
+            return Err(plan_error(format!("{} must be between 0 and 100, got {}", name, value)));
+        }
+        for growth in [self.income_growth, self.poverty_line_growth] {
+            if growth <= -100.0 || !growth.is_finite() {
+                return Err(AmortizationError::InvalidProjectionRate(growth));
+            }
+        }
+        Ok(())
+    }
+
+    // Poverty line for the household in plan year `year`, counting from 0
+    pub fn poverty_line_in(&self, year: u32) -> f64 {
+        let line = self.poverty_line + self.poverty_line_per_person * self.family_size.saturating_sub(1) as f64;
+        line * (1.0 + self.poverty_line_growth / 100.0).powi(year as i32)
+    }
+
+    pub fn income_in(&self, year: u32) -> f64 {
+        self.annual_income * (1.0 + self.income_growth / 100.0).powi(year as i32)
+    }
+
+    // Monthly payment in plan year `year`, before the standard cap
+    pub fn payment_in(&self, year: u32) -> f64 {
+        let discretionary = self.income_in(year) - self.poverty_line_in(year) * self.poverty_multiple / 100.0;
+        self.currency.round(discretionary.max(0.0) * self.payment_percent / 100.0 / 12.0)
+    }
+
+    pub fn project(&self) -> Result<IdrProjection, AmortizationError> {
+        self.validate()?;
+        let rate = self.apr / 100.0 / 12.0;
+        // Rounded up so the capped payment repays the loan within the ten years
+        let cap = if self.standard_payment_cap {
+            let scale = 10f64.powi(self.currency.minor_units() as i32);
+            Some((-tvm::pmt(rate, 120, self.balance, 0.0, PaymentTiming::Arrears)? * scale).ceil() / scale)
+        } else {
+            None
+        };
+
+        let mut balance = self.balance;
+        let mut unpaid_interest = 0.0;
+        let mut months = Vec::new();
+        let mut payoff_month = None;
+        for month in 1..=self.forgiveness_years * 12 {
+            let year = (month - 1) / 12;
+            let scheduled = self.payment_in(year);
+            let scheduled = cap.map_or(scheduled, |cap| scheduled.min(cap));
+
+            let interest = self.currency.round(balance * rate);
+            let owed = self.currency.round(balance + unpaid_interest + interest);
+            let payment = scheduled.min(owed);
+            let shortfall = (interest - payment).max(0.0);
+            let subsidy = self.currency.round(shortfall * self.interest_subsidy / 100.0);
+
+            // Interest is paid before principal
+            let interest_due = self.currency.round(unpaid_interest + interest - subsidy);
+            let interest_paid = payment.min(interest_due);
+            let principal = self.currency.round(payment - interest_paid);
+            unpaid_interest = self.currency.round(interest_due - interest_paid);
+            balance = self.currency.round(balance - principal);
+
+            months.push(IdrMonth {
+                month,
+                date: self.start_date.and_then(|start| PaymentFrequency::Monthly.due_date(start, month as i32 - 1, DateRollConvention::SameDay)),
+                annual_income: self.income_in(year),
+                payment,
+                interest,
+                subsidy,
+                principal,
+                unpaid_interest,
+                balance,
+            });
+            if balance <= 0.0 && unpaid_interest <= 0.0 {
+                payoff_month = Some(month);
+                break;
+            }
+        }
+
+        let forgiven_amount = if payoff_month.is_some() { 0.0 } else { self.currency.round(balance + unpaid_interest) };
+        Ok(IdrProjection {
+            total_paid: self.currency.round(months.iter().map(|m| m.payment).sum()),
+            total_interest: self.currency.round(months.iter().map(|m| m.interest).sum()),
+            total_subsidy: self.currency.round(months.iter().map(|m| m.subsidy).sum()),
+            payoff_month,
+            forgiven_amount,
+            forgiveness_date: match payoff_month {
+                Some(_) => None,
+                None => months.last().and_then(|m| m.date),
+            },
+            months,
+        })
+    }
+}
+
+impl IdrProjection {
+    // Months in which the payment left interest unpaid, so the amount owed grew
+    pub fn negative_amortization_months(&self) -> u32 {
+        self.months.iter().filter(|m| m.payment + m.subsidy < m.interest).count() as u32
+    }
+
+    pub fn peak_amount_owed(&self) -> f64 {
+        self.months.iter().map(|m| m.balance + m.unpaid_interest).fold(0.0, f64::max)
+    }
+}
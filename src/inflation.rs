@@ -0,0 +1,50 @@
+use crate::calculator::Amortization;
+use crate::payment::Payment;
+
+// Installment amounts restated in money of the loan's start date, deflated by an assumed
+// annual inflation rate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RealValues {
+    // What one unit of money paid with this installment is worth at the start of the loan
+    pub discount_factor: f64,
+    pub installment_amount: f64,
+    pub interest: f64,
+    pub principal: f64,
+    pub remaining_balance: f64,
+}
+
+impl RealValues {
+    pub(crate) fn of(payment: &Payment, inflation_rate: f64, periods_per_year: u32) -> Self {
+        let years = payment.installment_number as f64 / periods_per_year as f64;
+        let discount_factor = (1.0 + inflation_rate / 100.0).powf(-years);
+        RealValues {
+            discount_factor,
+            installment_amount: payment.installment_amount * discount_factor,
+            interest: payment.interest * discount_factor,
+            principal: payment.principal * discount_factor,
+            remaining_balance: payment.remaining_balance * discount_factor,
+        }
+    }
+}
+
+impl Amortization {
+    // Total interest in today's money; None unless the loan has an inflation rate
+    pub fn real_total_interest(&self) -> Option<f64> {
+        self.config.inflation_rate?;
+        Some(self.schedule.iter().filter_map(|p| p.real).map(|real| real.interest).sum())
+    }
+
+    pub fn real_total_payment(&self) -> Option<f64> {
+        self.config.inflation_rate?;
+        Some(self.schedule.iter().filter_map(|p| p.real).map(|real| real.installment_amount).sum())
+    }
+
+    // Recomputes the real values after installments have been renumbered or changed
+    pub(crate) fn refresh_real_values(&mut self) {
+        let periods_per_year = self.config.frequency.periods_per_year();
+        let inflation_rate = self.config.inflation_rate;
+        for payment in &mut self.schedule {
+            payment.real = inflation_rate.map(|rate| RealValues::of(payment, rate, periods_per_year));
+        }
+    }
+}
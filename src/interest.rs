@@ -0,0 +1,175 @@
+use std::fmt;
+use crate::calculator::Amortization;
+use crate::day_count::DayCount;
+use crate::error::AmortizationError;
+use crate::frequency::PaymentFrequency;
+use crate::money::Currency;
+use crate::payment::AccrualPeriod;
+use crate::tvm::{self, PaymentTiming};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InterestMethod {
+    // Interest accrues on the outstanding balance each period
+    #[default]
+    Actuarial,
+    // The finance charge of the level payment schedule is earned in proportion to the sum
+    // of the digits of the remaining periods, front-loading interest more than actuarial
+    RuleOf78,
+    // Add-on interest: the original principal times the rate for the whole term, split
+    // evenly across the installments
+    Flat,
+}
+
+// What an `InterestCalculator` knows about the installment it is charging interest for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InterestPeriod {
+    pub installment_number: u32,
+    // Scheduled installments, before any extension
+    pub periods: u32,
+    // Amount originally borrowed
+    pub principal: f64,
+    // Nominal annual rate in effect, in percent, and its share for one period
+    pub apr: f64,
+    pub periodic_rate: f64,
+    pub installment_amount: f64,
+    // The loan's currency, whose minor units interest is rounded to
+    pub currency: Currency,
+}
+
+// Interest charged for one installment. The schedule engine calls it for every installment,
+// deferred and skipped ones included, in place of the loan's `InterestMethod`; `dates` is
+// None for undated schedules. Rounding to the currency's minor units is up to the calculator,
+// though the loan's `RoundingPolicy` is applied on top
+pub trait InterestCalculator: fmt::Debug + Send + Sync {
+    fn interest_for_period(&self, balance: f64, period: &InterestPeriod, dates: Option<AccrualPeriod>) -> f64;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ActuarialInterest;
+
+impl InterestCalculator for ActuarialInterest {
+    fn interest_for_period(&self, balance: f64, period: &InterestPeriod, _dates: Option<AccrualPeriod>) -> f64 {
+        balance * period.periodic_rate
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FlatInterest;
+
+impl InterestCalculator for FlatInterest {
+    fn interest_for_period(&self, _balance: f64, period: &InterestPeriod, _dates: Option<AccrualPeriod>) -> f64 {
+        period.currency.round(period.principal * period.periodic_rate)
+    }
+}
+
+// Earns the finance charge of the level installment by the sum of the digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RuleOf78Interest;
+
+impl InterestCalculator for RuleOf78Interest {
+    fn interest_for_period(&self, _balance: f64, period: &InterestPeriod, _dates: Option<AccrualPeriod>) -> f64 {
+        if period.installment_number > period.periods {
+            return 0.0;
+        }
+        let finance_charge = period.installment_amount * period.periods as f64 - period.principal;
+        period.currency.round(finance_charge * rule_of_78_weight(period.installment_number, period.periods))
+    }
+}
+
+// Simple interest for the days actually elapsed between due dates, by `day_count`. Undated
+// schedules fall back to the periodic rate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DailySimpleInterest {
+    pub day_count: DayCount,
+}
+
+impl DailySimpleInterest {
+    pub fn new(day_count: DayCount) -> Self {
+        DailySimpleInterest { day_count }
+    }
+}
+
+impl Default for DailySimpleInterest {
+    fn default() -> Self {
+        DailySimpleInterest::new(DayCount::Actual365)
+    }
+}
+
+impl InterestCalculator for DailySimpleInterest {
+    fn interest_for_period(&self, balance: f64, period: &InterestPeriod, dates: Option<AccrualPeriod>) -> f64 {
+        match dates {
+            Some(dates) => balance * period.apr / 100.0 * self.day_count.year_fraction(dates.start, dates.end),
+            None => balance * period.periodic_rate,
+        }
+    }
+}
+
+impl InterestMethod {
+    // The built-in calculator charging interest the way this method does
+    pub fn calculator(&self) -> &'static dyn InterestCalculator {
+        match self {
+            InterestMethod::Actuarial => &ActuarialInterest,
+            InterestMethod::RuleOf78 => &RuleOf78Interest,
+            InterestMethod::Flat => &FlatInterest,
+        }
+    }
+}
+
+// n (n + 1) / 2: the sum of the digits 1..=n, which is 78 for a twelve month loan
+fn sum_of_digits(periods: u32) -> f64 {
+    let n = periods as f64;
+    n * (n + 1.0) / 2.0
+}
+
+// Share of the finance charge earned by installment `installment_number` of `periods`
+pub(crate) fn rule_of_78_weight(installment_number: u32, periods: u32) -> f64 {
+    (periods + 1 - installment_number) as f64 / sum_of_digits(periods)
+}
+
+// Reducing-balance APR, in percent, with the same installments as a flat rate loan
+pub fn flat_rate_to_apr(flat_rate: f64, periods: u32, frequency: PaymentFrequency) -> Result<f64, AmortizationError> {
+    if periods == 0 {
+        return Err(AmortizationError::InvalidPeriods(periods));
+    }
+    if flat_rate < 0.0 || !flat_rate.is_finite() {
+        return Err(AmortizationError::InvalidInterestRate(flat_rate));
+    }
+    let periods_per_year = frequency.periods_per_year() as f64;
+    let installment = (1.0 + flat_rate / 100.0 / periods_per_year * periods as f64) / periods as f64;
+    Ok(tvm::rate(periods, -installment, 1.0, 0.0, PaymentTiming::Arrears)? * periods_per_year * 100.0)
+}
+
+impl Amortization {
+    // Reducing-balance APR implied by the actual installments, comparable across interest methods
+    pub fn equivalent_apr(&self) -> Result<f64, AmortizationError> {
+        let rate = tvm::rate(self.periods, -self.periodic_payment, self.balance, 0.0, PaymentTiming::Arrears)?;
+        Ok(rate * self.config.frequency.periods_per_year() as f64 * 100.0)
+    }
+
+    // Total interest of the level payment schedule before any prepayment
+    pub fn finance_charge(&self) -> f64 {
+        self.periodic_payment * self.periods as f64 - self.balance
+    }
+
+    // Interest not yet earned when the loan is paid off after `payments_made` installments,
+    // which the borrower no longer owes
+    pub fn interest_rebate(&self, payments_made: u32) -> Result<f64, AmortizationError> {
+        if payments_made as usize > self.schedule.len() {
+            return Err(AmortizationError::PeriodOutOfRange(payments_made));
+        }
+        let rebate = match self.config.interest_method {
+            InterestMethod::Actuarial => self.schedule[payments_made as usize..].iter().map(|p| p.interest).sum(),
+            InterestMethod::RuleOf78 => {
+                let remaining = self.periods - payments_made;
+                self.finance_charge() * sum_of_digits(remaining) / sum_of_digits(self.periods)
+            }
+            InterestMethod::Flat => self.finance_charge() * (self.periods - payments_made) as f64 / self.periods as f64,
+        };
+        Ok(self.config.currency.round(rebate))
+    }
+
+    // Amount that settles the loan right after installment `payments_made`
+    pub fn early_payoff_amount(&self, payments_made: u32) -> Result<f64, AmortizationError> {
+        self.balance_after(payments_made).ok_or(AmortizationError::PeriodOutOfRange(payments_made))
+    }
+}
@@ -0,0 +1,272 @@
+// Profit-based structures that schedule a sale or a co-ownership rather than a loan, so
+// their rows carry profit and rent instead of interest
+use chrono::NaiveDate;
+use crate::calculator::periodic_payment;
+use crate::calendar::DateRollConvention;
+use crate::error::AmortizationError;
+use crate::frequency::PaymentFrequency;
+use crate::interest::rule_of_78_weight;
+use crate::money::Currency;
+use crate::tvm::{self, PaymentTiming};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProfitRecognition {
+    // The markup is earned evenly across the installments
+    #[default]
+    StraightLine,
+    // Sum of the digits of the remaining installments, front-loading the markup
+    SumOfDigits,
+    // Earned on the unrecovered cost at the rate implied by the installments
+    EffectiveRate,
+}
+
+// Cost-plus sale: the financier buys the asset and sells it on at a markup agreed up front,
+// paid in equal installments
+#[derive(Debug, Clone, PartialEq)]
+pub struct Murabaha {
+    pub cost_price: f64,
+    // Annual markup on the financed cost in percent, charged flat for the whole term
+    pub profit_rate: f64,
+    pub term_months: u32,
+    pub down_payment: f64,
+    pub recognition: ProfitRecognition,
+    pub start_date: Option<NaiveDate>,
+    // Amounts are rounded to its minor units
+    pub currency: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MurabahaRow {
+    pub installment_number: u32,
+    pub date: Option<NaiveDate>,
+    pub installment: f64,
+    pub profit: f64,
+    pub cost_recovered: f64,
+    pub outstanding_cost: f64,
+    pub deferred_profit: f64,
+    // Sale price still owed
+    pub receivable: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct MurabahaSchedule {
+    pub financed_cost: f64,
+    pub markup: f64,
+    // Deferred sale price, excluding the down payment
+    pub sale_price: f64,
+    pub installment: f64,
+    pub rows: Vec<MurabahaRow>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MusharakahPayment {
+    // The financier's share is bought back in equal units, so payments fall with the rent
+    #[default]
+    EqualUnits,
+    // Rent plus units bought add up to the same amount every month
+    Level,
+}
+
+// Diminishing co-ownership: the customer rents the financier's share of the asset and buys
+// it out unit by unit until they own it outright
+#[derive(Debug, Clone, PartialEq)]
+pub struct Musharakah {
+    pub asset_value: f64,
+    pub customer_contribution: f64,
+    // Annual rent on the financier's share, in percent of its value
+    pub rental_rate: f64,
+    pub term_months: u32,
+    pub payment: MusharakahPayment,
+    pub start_date: Option<NaiveDate>,
+    // Amounts are rounded to its minor units
+    pub currency: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MusharakahRow {
+    pub period: u32,
+    pub date: Option<NaiveDate>,
+    pub rent: f64,
+    pub unit_purchase: f64,
+    pub payment: f64,
+    pub financier_share: f64,
+    pub customer_share: f64,
+    // Percentage of the asset the customer owns after the payment
+    pub customer_ownership: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct MusharakahSchedule {
+    pub rows: Vec<MusharakahRow>,
+    pub total_rent: f64,
+    pub total_payment: f64,
+}
+
+fn monthly_date(start_date: Option<NaiveDate>, period: u32) -> Option<NaiveDate> {
+    start_date.and_then(|start| PaymentFrequency::Monthly.due_date(start, period as i32 - 1, DateRollConvention::SameDay))
+}
+
+fn validate_terms(value: f64, contribution: f64, rate: f64, term_months: u32) -> Result<(), AmortizationError> {
+    if value <= 0.0 || !value.is_finite() {
+        return Err(AmortizationError::InvalidLoanAmount(value));
+    }
+    if contribution < 0.0 || contribution >= value || !contribution.is_finite() {
+        return Err(AmortizationError::InvalidPaymentAmount(contribution));
+    }
+    if rate < 0.0 || !rate.is_finite() {
+        return Err(AmortizationError::InvalidInterestRate(rate));
+    }
+    if term_months == 0 {
+        return Err(AmortizationError::InvalidPeriods(term_months));
+    }
+    Ok(())
+}
+
+impl Murabaha {
+    pub fn new(cost_price: f64, profit_rate: f64, term_months: u32) -> Self {
+        Murabaha {
+            cost_price,
+            profit_rate,
+            term_months,
+            down_payment: 0.0,
+            recognition: ProfitRecognition::StraightLine,
+            start_date: None,
+            currency: Currency::default(),
+        }
+    }
+
+    pub fn with_down_payment(mut self, down_payment: f64) -> Self {
+        self.down_payment = down_payment;
+        self
+    }
+
+    pub fn with_recognition(mut self, recognition: ProfitRecognition) -> Self {
+        self.recognition = recognition;
+        self
+    }
+
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    pub fn schedule(&self) -> Result<MurabahaSchedule, AmortizationError> {
+        validate_terms(self.cost_price, self.down_payment, self.profit_rate, self.term_months)?;
+        let periods = self.term_months;
+        let currency = self.currency;
+        let financed_cost = self.cost_price - self.down_payment;
+        let markup = currency.round(financed_cost * self.profit_rate / 100.0 * periods as f64 / 12.0);
+        let sale_price = financed_cost + markup;
+        let installment = currency.round(sale_price / periods as f64);
+        let effective_rate = match self.recognition {
+            ProfitRecognition::EffectiveRate if markup > 0.0 => {
+                tvm::rate(periods, -sale_price / periods as f64, financed_cost, 0.0, PaymentTiming::Arrears)?
+            }
+            _ => 0.0,
+        };
+
+        let mut receivable = sale_price;
+        let mut outstanding_cost = financed_cost;
+        let mut deferred_profit = markup;
+        let rows = (1..=periods).map(|n| {
+            // The last installment settles whatever rounding left behind
+            let (amount, profit) = if n == periods {
+                (receivable, deferred_profit)
+            } else {
+                let profit = match self.recognition {
+                    ProfitRecognition::StraightLine => markup / periods as f64,
+                    ProfitRecognition::SumOfDigits => markup * rule_of_78_weight(n, periods),
+                    ProfitRecognition::EffectiveRate => outstanding_cost * effective_rate,
+                };
+                (installment, currency.round(profit))
+            };
+            receivable = currency.round(receivable - amount);
+            deferred_profit = currency.round(deferred_profit - profit);
+            outstanding_cost = currency.round(outstanding_cost - (amount - profit));
+            MurabahaRow {
+                installment_number: n,
+                date: monthly_date(self.start_date, n),
+                installment: amount,
+                profit,
+                cost_recovered: amount - profit,
+                outstanding_cost,
+                deferred_profit,
+                receivable,
+            }
+        }).collect();
+
+        Ok(MurabahaSchedule { financed_cost, markup, sale_price, installment, rows })
+    }
+}
+
+impl Musharakah {
+    pub fn new(asset_value: f64, customer_contribution: f64, rental_rate: f64, term_months: u32) -> Self {
+        Musharakah {
+            asset_value,
+            customer_contribution,
+            rental_rate,
+            term_months,
+            payment: MusharakahPayment::EqualUnits,
+            start_date: None,
+            currency: Currency::default(),
+        }
+    }
+
+    pub fn with_payment(mut self, payment: MusharakahPayment) -> Self {
+        self.payment = payment;
+        self
+    }
+
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    pub fn schedule(&self) -> Result<MusharakahSchedule, AmortizationError> {
+        validate_terms(self.asset_value, self.customer_contribution, self.rental_rate, self.term_months)?;
+        let periods = self.term_months;
+        let monthly_rent = self.rental_rate / 100.0 / 12.0;
+        let initial_share = self.asset_value - self.customer_contribution;
+        let currency = self.currency;
+        let level_payment = currency.round(periodic_payment(monthly_rent, periods, initial_share, currency)?);
+        let unit = currency.round(initial_share / periods as f64);
+
+        let mut financier_share = initial_share;
+        let rows: Vec<MusharakahRow> = (1..=periods).map(|period| {
+            let rent = currency.round(financier_share * monthly_rent);
+            let unit_purchase = match self.payment {
+                _ if period == periods => financier_share,
+                MusharakahPayment::EqualUnits => unit,
+                MusharakahPayment::Level => level_payment - rent,
+            }.min(financier_share);
+            financier_share = currency.round(financier_share - unit_purchase);
+            let customer_share = self.asset_value - financier_share;
+            MusharakahRow {
+                period,
+                date: monthly_date(self.start_date, period),
+                rent,
+                unit_purchase,
+                payment: rent + unit_purchase,
+                financier_share,
+                customer_share,
+                customer_ownership: customer_share / self.asset_value * 100.0,
+            }
+        }).collect();
+
+        Ok(MusharakahSchedule {
+            total_rent: rows.iter().map(|r| r.rent).sum(),
+            total_payment: rows.iter().map(|r| r.payment).sum(),
+            rows,
+        })
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::NaiveDate;
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::error::AmortizationError;
+use crate::frequency::PaymentFrequency;
+use crate::money::Currency;
+use crate::tvm::{self, PaymentTiming};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lease {
+    pub payment: f64,
+    // Annual discount rate in percent (the rate implicit in the lease or the incremental borrowing rate)
+    pub discount_rate: f64,
+    pub term_months: u32,
+    pub frequency: PaymentFrequency,
+    pub timing: PaymentTiming,
+    pub initial_direct_costs: f64,
+    pub start_date: Option<NaiveDate>,
+    pub currency: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouAssetRow {
+    pub period: u32,
+    pub date: Option<NaiveDate>,
+    pub amortization: f64,
+    pub carrying_value: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct LeaseSchedule {
+    pub initial_liability: f64,
+    pub initial_rou_asset: f64,
+    // Interest and principal reduction of the lease liability, one row per lease payment
+    pub liability: Amortization,
+    // Straight-line amortization of the right-of-use asset
+    pub rou_asset: Vec<RouAssetRow>,
+}
+
+impl Lease {
+    pub fn new(payment: f64, discount_rate: f64, term_months: u32) -> Self {
+        Lease {
+            payment,
+            discount_rate,
+            term_months,
+            frequency: PaymentFrequency::Monthly,
+            // Lease payments are usually due at the start of each period
+            timing: PaymentTiming::Advance,
+            initial_direct_costs: 0.0,
+            start_date: None,
+            currency: Currency::default(),
+        }
+    }
+
+    pub fn with_frequency(mut self, frequency: PaymentFrequency) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    pub fn with_timing(mut self, timing: PaymentTiming) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    pub fn with_initial_direct_costs(mut self, initial_direct_costs: f64) -> Self {
+        self.initial_direct_costs = initial_direct_costs;
+        self
+    }
+
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    pub fn periods(&self) -> u32 {
+        self.frequency.periods_for_months(self.term_months)
+    }
+
+    // Present value of the remaining lease payments at commencement
+    pub fn initial_liability(&self) -> Result<f64, AmortizationError> {
+        if self.payment <= 0.0 || !self.payment.is_finite() {
+            return Err(AmortizationError::InvalidPaymentAmount(self.payment));
+        }
+        if self.discount_rate <= 0.0 || !self.discount_rate.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(self.discount_rate));
+        }
+        let rate = self.discount_rate / 100.0 / self.frequency.periods_per_year() as f64;
+        let liability = tvm::pv(rate, self.periods(), -self.payment, 0.0, self.timing)?;
+        Ok(self.currency.round(liability))
+    }
+
+    pub fn schedule(&self) -> Result<LeaseSchedule, AmortizationError> {
+        if self.initial_direct_costs < 0.0 || !self.initial_direct_costs.is_finite() {
+            return Err(AmortizationError::InvalidUpfrontCost(self.initial_direct_costs));
+        }
+        let initial_liability = self.initial_liability()?;
+        let liability = Amortization::from_config(CalculatorConfig {
+            frequency: self.frequency,
+            payment_timing: self.timing,
+            currency: self.currency,
+            ..CalculatorConfig::new(initial_liability, self.discount_rate, self.term_months, self.start_date)
+        })?;
+
+        let initial_rou_asset = initial_liability + self.initial_direct_costs;
+        let periods = liability.periods;
+        let straight_line = self.currency.round(initial_rou_asset / periods as f64);
+        let mut carrying_value = initial_rou_asset;
+        let rou_asset = liability.schedule.iter().map(|payment| {
+            // The last period writes off whatever rounding left behind
+            let amortization = if payment.installment_number == periods { carrying_value } else { straight_line };
+            carrying_value -= amortization;
+            RouAssetRow {
+                period: payment.installment_number,
+                date: payment.date,
+                amortization,
+                carrying_value,
+            }
+        }).collect();
+
+        Ok(LeaseSchedule {
+            initial_liability,
+            initial_rou_asset,
+            liability,
+            rou_asset,
+        })
+    }
+}
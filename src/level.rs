@@ -0,0 +1,111 @@
+// Level monthly payments and their schedule using only `core` and `alloc`, for builds without
+// `std` such as embedded point-of-sale terminals. The results match `Amortization::new` for
+// an undated loan: the payment is rounded to the cent, interest is kept at full precision and
+// the final installment clears the balance, rounded to a payable amount
+use alloc::vec::Vec;
+
+// Same cap as `DEFAULT_MAX_PERIODS`, which needs `std`
+const MAX_MONTHS: u32 = 6000;
+// From here on every f64 is a whole number of cents
+const WHOLE_CENTS: f64 = 4_503_599_627_370_496.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelRow {
+    pub installment_number: u32,
+    pub interest: f64,
+    pub principal: f64,
+    pub installment_amount: f64,
+    pub remaining_balance: f64,
+}
+
+// Monthly installment for `principal` at `apr` percent over `months`; None for a principal
+// that isn't positive, a negative rate or a term outside 1 to 6000 months
+pub fn periodic_payment(principal: f64, apr: f64, months: u32) -> Option<f64> {
+    if !(principal > 0.0 && principal.is_finite() && apr >= 0.0 && apr.is_finite()) || months == 0 || months > MAX_MONTHS {
+        return None;
+    }
+    let rate = monthly_rate(apr);
+    let payment = if rate == 0.0 {
+        principal / months as f64
+    } else {
+        let growth = powi(1.0 + rate, months);
+        principal * growth * rate / (growth - 1.0)
+    };
+    if !payment.is_finite() {
+        return None;
+    }
+    // Rounding down past the interest would leave the balance growing
+    let rounded = round_cents(payment);
+    if payment > principal * rate && rounded <= principal * rate {
+        return Some(round_cents(rounded + 0.01));
+    }
+    Some(rounded)
+}
+
+pub fn schedule(principal: f64, apr: f64, months: u32) -> Option<Vec<LevelRow>> {
+    let payment = periodic_payment(principal, apr, months)?;
+    let rate = monthly_rate(apr);
+    let mut rows = Vec::with_capacity(months as usize);
+    let mut balance = principal;
+    for installment_number in 1..=months {
+        let interest = balance * rate;
+        let scheduled = payment - interest;
+        if installment_number < months && scheduled < balance {
+            balance -= scheduled;
+            rows.push(LevelRow { installment_number, interest, principal: scheduled, installment_amount: interest + scheduled, remaining_balance: balance });
+            continue;
+        }
+        // The residual of rounding the last installment stays in its interest
+        let amount = interest + balance;
+        let payable = round_cents(amount);
+        rows.push(LevelRow {
+            installment_number,
+            interest: interest + payable - amount,
+            principal: balance,
+            installment_amount: payable,
+            remaining_balance: 0.0,
+        });
+        break;
+    }
+    Some(rows)
+}
+
+// Worked out the way `backend_periodic_rate` does, whose quotient can differ from
+// `apr / 100.0 / 12.0` in the last bit
+fn monthly_rate(apr: f64) -> f64 {
+    apr / (100.0 * 12.0)
+}
+
+// Repeated squaring in place of the `powf` that needs `std`; the two can part in the last
+// bits, which rounding the payment to the cent absorbs
+fn powi(base: f64, exp: u32) -> f64 {
+    let mut result = 1.0;
+    let mut base = base;
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result *= base;
+        }
+        base *= base;
+        exp >>= 1;
+    }
+    result
+}
+
+// Half away from zero, like `f64::round`, which `core` doesn't have
+fn round_cents(amount: f64) -> f64 {
+    let cents = amount * 100.0;
+    if cents.is_nan() || cents.abs() >= WHOLE_CENTS {
+        return amount;
+    }
+    let whole = cents as i64;
+    let fraction = cents - whole as f64;
+    let rounded = if fraction >= 0.5 {
+        whole + 1
+    } else if fraction <= -0.5 {
+        whole - 1
+    } else {
+        whole
+    };
+    rounded as f64 / 100.0
+}
@@ -1,10 +1,227 @@
-pub mod calculator;
-pub mod payment;
-pub mod error;
+#![cfg_attr(not(feature = "std"), no_std)]
 
-#[cfg(test)]
-mod tests;
+extern crate alloc;
 
-pub use calculator::Amortization;
-pub use payment::Payment;
-pub use error::AmortizationError;
\ No newline at end of file
+// Level-payment math and schedules, the only part of the crate that builds without `std`
+pub mod level;
+
+// Marks every item as needing the `std` feature, which brings in chrono dates, I/O and the
+// float functions `core` lacks
+macro_rules! std_only {
+    ($($item:item)*) => {
+        $(#[cfg(feature = "std")] $item)*
+    };
+}
+
+std_only! {
+    pub mod accelerated;
+    pub mod annual;
+    pub mod aprc;
+    pub mod auto;
+    pub mod batch;
+    pub mod bond;
+    pub mod builder;
+    pub mod calculator;
+    pub mod calendar;
+    pub mod cashflow;
+    #[cfg(feature = "charting")]
+    pub mod charting;
+    #[cfg(feature = "cli")]
+    pub mod cli;
+    pub mod columnar;
+    pub mod compare;
+    pub mod compat;
+    pub mod consolidation;
+    #[cfg(feature = "config")]
+    pub mod config;
+    pub mod construction;
+    pub mod cumulative;
+    pub mod day_count;
+    pub mod depreciation;
+    pub mod direct_debit;
+    pub mod diff;
+    pub mod down_payment;
+    pub mod duration;
+    pub mod payment;
+    pub mod payoff;
+    pub mod penalty;
+    pub mod personal_finance;
+    pub mod points;
+    pub mod portfolio;
+    pub mod error;
+    pub mod escrow;
+    pub mod events;
+    pub mod explain;
+    pub mod export;
+    pub mod fees;
+    #[cfg(feature = "ffi")]
+    pub mod ffi;
+    pub mod fixed;
+    pub mod floating;
+    pub mod format;
+    pub mod forbearance;
+    pub mod frequency;
+    pub mod fx;
+    pub mod heloc;
+    pub mod ics;
+    #[cfg(feature = "i18n")]
+    pub mod i18n;
+    pub mod idr;
+    pub mod history;
+    pub mod inflation;
+    pub mod interest;
+    pub mod islamic;
+    pub mod json;
+    pub mod lease;
+    pub mod loan_type;
+    pub mod modification;
+    pub mod milestone;
+    pub mod money;
+    #[cfg(feature = "rand")]
+    pub mod monte_carlo;
+    pub mod num;
+    pub mod odd_days;
+    pub mod date;
+    pub mod decimal;
+    pub mod effective_apr;
+    pub mod effective_cost;
+    pub mod prepayment;
+    pub mod progress;
+    pub mod projection;
+    pub mod property;
+    pub mod query;
+    pub mod rate;
+    pub mod refinance;
+    pub mod rent_vs_buy;
+    pub mod reverse_mortgage;
+    pub mod revolving;
+    pub mod report;
+    pub mod savings;
+    pub mod scenario;
+    #[cfg(feature = "schema")]
+    pub mod schema;
+    #[cfg(feature = "server")]
+    pub mod server;
+    pub mod schedule;
+    pub mod sensitivity;
+    pub mod servicing;
+    pub mod sink;
+    pub mod skip;
+    #[cfg(feature = "sql")]
+    pub mod sql;
+    pub mod statement;
+    pub mod state;
+    pub mod summary;
+    pub mod table;
+    pub mod tax;
+    pub mod teaser;
+    pub mod timeline;
+    pub mod transform;
+    pub mod solver;
+    pub mod tvm;
+    pub mod units;
+    pub mod validate;
+    pub mod valuation;
+    pub mod waterfall;
+    #[cfg(feature = "wasm")]
+    pub mod wasm;
+    #[cfg(feature = "xlsx")]
+    pub mod xlsx;
+
+    #[cfg(test)]
+    mod tests;
+
+    pub use accelerated::BiweeklyAcceleration;
+    pub use annual::AnnualSummary;
+    pub use aprc::aprc;
+    pub use auto::{AutoLoan, AutoPurchase, EquityRow};
+    pub use bond::{Bond, BondAmortizationRow, BondSchedule};
+    pub use batch::{BatchLoan, BatchQuote};
+    pub use builder::AmortizationBuilder;
+    pub use calculator::{Amortization, CalculatorConfig, DEFAULT_MAX_PERIODS};
+    pub use calendar::{BusinessDayConvention, DateRollConvention, FederalReserveHolidays, HolidayCalendar, WeekendsOnly};
+    pub use columnar::ColumnarSchedule;
+    pub use compare::LoanComparison;
+    pub use consolidation::{Consolidation, ConsolidationAnalysis, ConsolidationRounding};
+    pub use construction::{ConstructionLoan, ConstructionSchedule, Draw, DrawRow};
+    pub use cumulative::CumulativePayment;
+    pub use day_count::DayCount;
+    pub use depreciation::{Asset, DepreciationMethod, DepreciationRow, DepreciationSchedule};
+    pub use diff::{diff_schedules, ScheduleDiff};
+    pub use direct_debit::{Collection, DirectDebit, FixedWidthField, FixedWidthLayout, Party, SequenceType};
+    pub use down_payment::{DownPaymentGrid, DownPaymentScenario};
+    pub use payment::{AccrualPeriod, Payment, PaymentAmounts};
+    pub use points::{PointsAnalysis, PointsOption, PointsRecommendation};
+    pub use payoff::PayoffQuote;
+    pub use penalty::{PenaltyStep, PrepaymentPenalty};
+    pub use personal_finance::FinanceAppOptions;
+    pub use ics::IcsOptions;
+    pub use portfolio::{Debt, DebtPayment, LoanPool, PayoffPlan, PayoffStrategy, Portfolio, PortfolioMonth, ProjectedCashFlow, StrategyComparison};
+    pub use projection::{CashFlowAssumptions, CashFlowProjection, ExpectedCashFlow, PrepaymentSpeed};
+    #[cfg(feature = "config")]
+    pub use config::ConfigFormat;
+    pub use error::{AmortizationError, ErrorKind};
+    pub use escrow::{Escrow, EscrowPayment, MortgageInsurance};
+    pub use events::{FieldChange, ScheduleEvent};
+    pub use explain::{Explanation, ExtraPaymentEffect};
+    pub use fees::{Fee, FeeCharge};
+    pub use fixed::FixedSchedule;
+    pub use floating::{FloatingRate, ForwardCurve};
+    pub use format::{Locale, ScheduleFormatter, SymbolPosition};
+    pub use forbearance::CapitalizationPolicy;
+    pub use frequency::PaymentFrequency;
+    pub use fx::{DatedRates, FxRateProvider, StaticRates};
+    pub use heloc::{Heloc, HelocActivity, HelocPhase, HelocRow, HelocSchedule};
+    #[cfg(feature = "i18n")]
+    pub use i18n::{Label, Language, StringTable};
+    pub use idr::{IdrMonth, IdrPlan, IdrProjection};
+    pub use history::PaymentHistoryReader;
+    pub use inflation::RealValues;
+    pub use interest::{flat_rate_to_apr, ActuarialInterest, DailySimpleInterest, FlatInterest, InterestCalculator, InterestMethod, InterestPeriod, RuleOf78Interest};
+    pub use islamic::{Murabaha, MurabahaRow, MurabahaSchedule, Musharakah, MusharakahPayment, MusharakahRow, MusharakahSchedule, ProfitRecognition};
+    pub use lease::{Lease, LeaseSchedule, RouAssetRow};
+    pub use loan_type::{CapitalizationFrequency, Deferment, FixedPayment, GraduatedPayment, LoanType};
+    pub use odd_days::{FirstPeriodPolicy, OddDaysTreatment};
+    pub use prepayment::{LumpSumOptions, LumpSumScenario, Prepayment, PrepaymentTreatment};
+    pub use progress::LoanProgress;
+    pub use property::PropertyValue;
+    pub use rate::{effective_to_nominal, nominal_to_effective, Jurisdiction, PaymentCap, RateAdjustment, RateCaps, RateConvention, RateLimit, RatePeriod};
+    pub use refinance::{Refinance, RefinanceAnalysis};
+    pub use rent_vs_buy::{RentVsBuy, RentVsBuyAnalysis, RentVsBuyYear};
+    pub use reverse_mortgage::{ReverseMortgage, ReverseMortgageProjection, ReverseMortgageRow};
+    pub use revolving::{RevolvingDebt, RevolvingPayment, RevolvingPayments, RevolvingPayoff};
+    pub use savings::{SavingsPlan, SavingsRow, SavingsSchedule};
+    pub use scenario::{RatePath, RateScenario, ScenarioAnalysis, ScenarioResult};
+    #[cfg(feature = "schema")]
+    pub use schema::SchemaType;
+    pub use schedule::Payments;
+    pub use sensitivity::{GridValue, PaymentGrid};
+    pub use servicing::{ActualPayment, ApplicationOrder, LedgerEntry, PaymentLedger};
+    pub use sink::{CsvSink, ScheduleSink, StreamError};
+    pub use skip::{SkipPayments, SkipTreatment};
+    #[cfg(feature = "sql")]
+    pub use sql::{LoanRecord, SqlDialect, SqlRow, SqlStatement, SqlValue};
+    pub use statement::{read_statement, ConventionHint, FieldDifference, LenderConvention, ReconcileOptions, Reconciliation, RowReconciliation, StatementRow};
+    pub use state::LoanState;
+    pub use solver::goal_seek;
+    pub use summary::AmortizationSummary;
+    pub use table::{Column, DisplayOptions, FormatOptions, PaymentDisplay, TableOptions};
+    pub use tax::{AnnualTaxSavings, TaxAnalysis, TaxAssumptions};
+    pub use teaser::{PaymentChange, TeaserRate};
+    pub use timeline::{ScheduleTimeline, TimelineSegment};
+    pub use transform::{ExtraPayment, LumpSum, RateChange, ScheduleTransform, SkipPayment};
+    pub use num::AmortNum;
+    pub use modification::{ModificationTreatment, RateModification};
+    pub use milestone::{Milestone, MilestoneKind};
+    pub use money::{Currency, Money, RoundingMode, RoundingPolicy};
+    #[cfg(feature = "rand")]
+    pub use monte_carlo::{Distribution, MonteCarlo, RandomSource, RateModel, SeededRng, SimulationResult};
+    pub use date::ScheduleDate;
+    pub use decimal::Decimal;
+    pub use tvm::PaymentTiming;
+    pub use units::{Principal, Rate, Term};
+    pub use validate::{Invariant, ValidationIssue, ValidationReport, Violation};
+    pub use valuation::ValuationPoint;
+    pub use waterfall::{Tranche, TrancheCashFlows, TranchePeriod, Waterfall, WaterfallResult};
+    pub use effective_apr::{EffectiveApr, FeeTreatment, UpfrontCosts};
+}
@@ -0,0 +1,133 @@
+use crate::error::AmortizationError;
+use crate::portfolio::month_of;
+use crate::tvm::{self, PaymentTiming};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoanType {
+    #[default]
+    Amortizing,
+    InterestOnly { io_periods: u32 },
+    // Constant amortization: the same principal every period, so the installment
+    // declines as the interest shrinks
+    EqualPrincipal,
+}
+
+impl LoanType {
+    pub fn interest_only_periods(&self) -> u32 {
+        match self {
+            LoanType::Amortizing | LoanType::EqualPrincipal => 0,
+            LoanType::InterestOnly { io_periods } => *io_periods,
+        }
+    }
+}
+
+// How often interest that accrues without being paid, during deferment, skipped or forborne
+// installments or negative amortization, is added to the balance. Calendar frequencies count
+// from the start of the loan; whatever is still uncapitalized when the event ends is
+// capitalized with its last installment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapitalizationFrequency {
+    EveryPeriod,
+    Monthly,
+    Quarterly,
+    Annually,
+    AtEndOfEvent,
+}
+
+impl CapitalizationFrequency {
+    // Whether installment `installment_number` is the last to fall in its capitalization period
+    pub fn capitalizes_at(&self, installment_number: u32, periods_per_year: u32) -> bool {
+        let months = match self {
+            CapitalizationFrequency::EveryPeriod => return true,
+            CapitalizationFrequency::AtEndOfEvent => return false,
+            CapitalizationFrequency::Monthly => 1,
+            CapitalizationFrequency::Quarterly => 3,
+            CapitalizationFrequency::Annually => 12,
+        };
+        let period = |n: u32| (month_of(n, periods_per_year) - 1) / months;
+        period(installment_number + 1) > period(installment_number)
+    }
+}
+
+// Initial periods with no payments due, e.g. a student loan while in school
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Deferment {
+    // Interest is not charged to the borrower during deferment
+    Subsidized { periods: u32 },
+    // Interest accrues on the principal and is capitalized when repayment starts
+    Unsubsidized { periods: u32 },
+}
+
+impl Deferment {
+    pub fn periods(&self) -> u32 {
+        match self {
+            Deferment::Subsidized { periods } | Deferment::Unsubsidized { periods } => *periods,
+        }
+    }
+
+    pub fn accrues_interest(&self) -> bool {
+        matches!(self, Deferment::Unsubsidized { .. })
+    }
+}
+
+// Graduated payment plan: the installment grows by `growth_rate` percent once a year for
+// the first `graduation_years` years and then stays level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GraduatedPayment {
+    pub growth_rate: f64,
+    pub graduation_years: u32,
+}
+
+impl GraduatedPayment {
+    pub fn new(growth_rate: f64, graduation_years: u32) -> Self {
+        GraduatedPayment { growth_rate, graduation_years }
+    }
+
+    // Multiplier applied to the initial installment `period` periods into repayment
+    pub fn step_factor(&self, period: u32, periods_per_year: u32) -> f64 {
+        let steps = (period / periods_per_year).min(self.graduation_years);
+        (1.0 + self.growth_rate / 100.0).powi(steps as i32)
+    }
+}
+
+// The borrower pays `amount` every period instead of an installment derived from the term.
+// The schedule ends once the balance is repaid, with a short final payment, or at the end of
+// the term, whose last installment settles what is left. An installment that doesn't cover
+// the interest is an error unless negative amortization is allowed, in which case the
+// shortfall is added to the balance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedPayment {
+    pub amount: f64,
+    pub allow_negative_amortization: bool,
+}
+
+impl FixedPayment {
+    pub fn new(amount: f64) -> Self {
+        FixedPayment { amount, allow_negative_amortization: false }
+    }
+
+    pub fn with_negative_amortization(mut self) -> Self {
+        self.allow_negative_amortization = true;
+        self
+    }
+
+    // Installments needed to repay `balance` when the rate per period changes to `rate` from
+    // each `start_period` on, as in a rate schedule
+    pub fn periods_to_repay(&self, balance: f64, rates: &[(u32, f64)], timing: PaymentTiming) -> Result<u32, AmortizationError> {
+        if self.amount <= 0.0 || !self.amount.is_finite() {
+            return Err(AmortizationError::InvalidPaymentAmount(self.amount));
+        }
+        let mut balance = balance;
+        for (index, &(start_period, rate)) in rates.iter().enumerate() {
+            let periods = tvm::nper(rate, -self.amount, balance, 0.0, timing)
+                .map_err(|_| AmortizationError::InsufficientPayment(self.amount))? - 1e-9;
+            match rates.get(index + 1) {
+                Some(&(next_start, _)) if periods > (next_start - start_period) as f64 => {
+                    balance = -tvm::fv(rate, next_start - start_period, -self.amount, balance, timing)?;
+                }
+                _ => return Ok(start_period - 1 + periods.ceil().max(1.0) as u32),
+            }
+        }
+        Err(AmortizationError::MissingParameter("apr"))
+    }
+}
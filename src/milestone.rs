@@ -0,0 +1,94 @@
+// Points in a schedule borrowers care about, found once so charts can annotate them without
+// scanning the rows again
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::payment::Payment;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MilestoneKind {
+    // First installment repaying more principal than it pays interest
+    Crossover,
+    // Installment after which half the original balance has been repaid
+    HalfPrincipalRepaid,
+    // Installment after which the balance is 80% of the estimated property value or less;
+    // only for loans with a property value that start above it
+    Ltv80,
+    // First installment without mortgage insurance after ones with it
+    MortgageInsuranceDropOff,
+    // First installment on or after the calendar midpoint between the first and last due dates
+    HalfwayByDate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Milestone {
+    pub kind: MilestoneKind,
+    pub installment_number: u32,
+    pub date: Option<NaiveDate>,
+    // Balance left after the installment
+    pub remaining_balance: f64,
+}
+
+impl MilestoneKind {
+    pub const ALL: [MilestoneKind; 5] = [
+        MilestoneKind::Crossover,
+        MilestoneKind::HalfPrincipalRepaid,
+        MilestoneKind::Ltv80,
+        MilestoneKind::MortgageInsuranceDropOff,
+        MilestoneKind::HalfwayByDate,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MilestoneKind::Crossover => "Principal exceeds interest",
+            MilestoneKind::HalfPrincipalRepaid => "Half of principal repaid",
+            MilestoneKind::Ltv80 => "80% loan-to-value",
+            MilestoneKind::MortgageInsuranceDropOff => "Mortgage insurance ends",
+            MilestoneKind::HalfwayByDate => "Halfway through the term",
+        }
+    }
+}
+
+impl Milestone {
+    fn at(kind: MilestoneKind, payment: &Payment) -> Self {
+        Milestone {
+            kind,
+            installment_number: payment.installment_number,
+            date: payment.date,
+            remaining_balance: payment.remaining_balance,
+        }
+    }
+}
+
+impl Amortization {
+    // Milestones the schedule reaches, in installment order; kinds that don't apply to the loan
+    // or are never reached are left out
+    pub fn milestones(&self) -> Vec<Milestone> {
+        let mut milestones: Vec<Milestone> = MilestoneKind::ALL.iter().filter_map(|&kind| self.milestone(kind)).collect();
+        milestones.sort_by_key(|m| (m.installment_number, m.kind));
+        milestones
+    }
+
+    pub fn milestone(&self, kind: MilestoneKind) -> Option<Milestone> {
+        let payment = match kind {
+            MilestoneKind::Crossover => self.schedule.iter().find(|p| p.principal > p.interest),
+            MilestoneKind::HalfPrincipalRepaid => self.schedule.iter().find(|p| p.remaining_balance <= self.balance / 2.0),
+            MilestoneKind::Ltv80 => {
+                let property = self.config.property?;
+                if property.ltv(self.balance, 0, self.config.frequency.periods_per_year()) <= 80.0 {
+                    return None;
+                }
+                self.schedule.iter().find(|p| p.ltv.is_some_and(|ltv| ltv <= 80.0))
+            }
+            MilestoneKind::MortgageInsuranceDropOff => {
+                let insured = |p: &Payment| p.escrow.is_some_and(|e| e.mortgage_insurance > 0.0);
+                self.schedule.windows(2).find(|pair| insured(&pair[0]) && !insured(&pair[1])).map(|pair| &pair[1])
+            }
+            MilestoneKind::HalfwayByDate => {
+                let (first, last) = (self.schedule.first()?.date?, self.schedule.last()?.date?);
+                let midpoint = first + (last - first) / 2;
+                self.schedule.iter().find(|p| p.date.is_some_and(|date| date >= midpoint))
+            }
+        };
+        payment.map(|p| Milestone::at(kind, p))
+    }
+}
@@ -0,0 +1,195 @@
+use chrono::{Datelike, Months};
+use crate::calculator::{periodic_payment, Amortization, CalculatorConfig};
+use crate::calendar::DateRollConvention;
+use crate::error::AmortizationError;
+use crate::fees::Fee;
+use crate::frequency::PaymentFrequency;
+use crate::loan_type::LoanType;
+use crate::payment::Payment;
+use crate::prepayment::Prepayment;
+use crate::rate::RatePeriod;
+use crate::skip::SkipPayments;
+use crate::tvm::{self, PaymentTiming};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModificationTreatment {
+    // Keep the installment; the term grows or shrinks with the new rate
+    KeepPayment,
+    // Keep the maturity; the installment is recalculated at the new rate
+    #[default]
+    KeepTerm,
+}
+
+#[derive(Debug, Clone)]
+pub struct RateModification {
+    // First installment charged at the new rate
+    pub effective_period: u32,
+    pub new_apr: f64,
+    pub new_payment: f64,
+    // The loan from the effective period on, numbered after the installments already paid
+    pub new_loan: Amortization,
+    // Installments paid under the original terms followed by the modified ones
+    pub schedule: Vec<Payment>,
+    pub total_interest: f64,
+    // Positive when the modification costs more interest than the original terms
+    pub interest_change: f64,
+}
+
+// Loan terms are held in months, so the period count has to convert back exactly
+fn term_months(frequency: PaymentFrequency, periods: u32) -> Result<u32, AmortizationError> {
+    let periods_per_year = frequency.periods_per_year();
+    let months = (periods * 12 + periods_per_year / 2) / periods_per_year;
+    if frequency.periods_for_months(months) != periods {
+        return Err(AmortizationError::UnsupportedFrequency(frequency));
+    }
+    Ok(months)
+}
+
+impl Amortization {
+    pub fn modify_rate(&self, effective_period: u32, new_apr: f64, treatment: ModificationTreatment) -> Result<RateModification, AmortizationError> {
+        if new_apr < 0.0 || !new_apr.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(new_apr));
+        }
+        if effective_period < 2 {
+            return Err(AmortizationError::PeriodOutOfRange(effective_period));
+        }
+        let paid = effective_period - 1;
+        let mut config = CalculatorConfig {
+            apr: new_apr,
+            rate_schedule: vec![RatePeriod::new(1, new_apr)],
+            ..self.continuation_config(paid)?
+        };
+
+        if treatment == ModificationTreatment::KeepPayment {
+            let last_paid = &self.schedule[paid as usize - 1];
+            let installment = last_paid.installment_amount - last_paid.fees;
+            let rate = config.rate_convention.nominal_apr(new_apr, config.frequency) / 100.0 / config.frequency.periods_per_year() as f64;
+            if config.balance * rate >= installment {
+                return Err(AmortizationError::InsufficientPayment(installment));
+            }
+            let periods = tvm::nper(rate, -installment, config.balance, 0.0, PaymentTiming::Arrears)?
+                .ceil()
+                .max(1.0) as u32;
+            config.loan_term = term_months(config.frequency, periods)?;
+            // The level payment over the rounded-up term is a little lower; the difference is
+            // paid as extra principal so the installment stays where it was
+            let level = periodic_payment(rate, periods, config.balance, config.currency)?;
+            config.extra_payment = (installment - level).max(0.0);
+        }
+
+        let new_loan = self.continue_from(paid, config)?;
+        let mut schedule = self.schedule[..paid as usize].to_vec();
+        schedule.extend(new_loan.schedule.iter().cloned());
+        let total_interest = schedule.iter().map(|p| p.interest).sum();
+
+        Ok(RateModification {
+            effective_period,
+            new_apr,
+            new_payment: new_loan.schedule.first().map_or(0.0, |p| p.installment_amount),
+            total_interest,
+            interest_change: total_interest - self.total_interest,
+            schedule,
+            new_loan,
+        })
+    }
+
+    // Keeps the remaining term and recomputes the installment from the balance left after
+    // installment `period`
+    pub fn recast_at(&self, period: u32) -> Result<Amortization, AmortizationError> {
+        self.recast_with_principal(period, 0.0)
+    }
+
+    // Same as `recast_at`, after first paying down `extra_principal`
+    pub fn recast_with_principal(&self, period: u32, extra_principal: f64) -> Result<Amortization, AmortizationError> {
+        if extra_principal < 0.0 || !extra_principal.is_finite() {
+            return Err(AmortizationError::InvalidExtraPayment(extra_principal));
+        }
+        let mut config = self.continuation_config(period)?;
+        config.balance -= extra_principal;
+        self.continue_from(period, config)
+    }
+
+    // Terms of the loan still to run after installment `period`, renumbered to start at 1
+    pub(crate) fn continuation_config(&self, period: u32) -> Result<CalculatorConfig, AmortizationError> {
+        if period == 0 || period as usize >= self.schedule.len() || period < self.deferred_periods() {
+            return Err(AmortizationError::PeriodOutOfRange(period));
+        }
+        let config = &self.config;
+        let loan_term = term_months(config.frequency, self.schedule.len() as u32 - period)?;
+
+        let next_installment = period + 1;
+        let current_apr = self.rate_schedule.iter()
+            .rev()
+            .find(|r| r.start_period <= next_installment)
+            .map_or(config.apr, |r| r.apr);
+        let mut rate_schedule = vec![RatePeriod::new(1, current_apr)];
+        rate_schedule.extend(self.rate_schedule.iter()
+            .filter(|r| r.start_period > next_installment)
+            .map(|r| RatePeriod::new(r.start_period - period, r.apr)));
+
+        let io_periods = (self.deferred_periods() + config.loan_type.interest_only_periods()).saturating_sub(period);
+        let loan_type = match config.loan_type {
+            LoanType::InterestOnly { .. } if io_periods > 0 => LoanType::InterestOnly { io_periods },
+            LoanType::InterestOnly { .. } => LoanType::Amortizing,
+            other => other,
+        };
+        let skip_payments = config.skip_payments.as_ref().map(|skip| SkipPayments {
+            installments: skip.installments.iter().filter(|&&n| n > period).map(|n| n - period).collect(),
+            ..skip.clone()
+        });
+
+        // Monthly due dates keep following the original start date's day of month
+        let (start_date, date_roll) = match config.start_date {
+            Some(start) if config.frequency.months_per_period() > 0 => {
+                let date_roll = config.date_roll.anchored(start);
+                // A day-of-month convention takes only the month from its start date, which a
+                // due date rolled forward into the next month would get wrong
+                let start_date = match date_roll {
+                    DateRollConvention::DayOfMonth(_) => start.with_day(1)
+                        .and_then(|first| first.checked_add_months(Months::new(period * config.frequency.months_per_period()))),
+                    _ => self.due_date(period + 1),
+                };
+                (start_date, date_roll)
+            }
+            _ => (self.due_date(period + 1), config.date_roll),
+        };
+
+        Ok(CalculatorConfig {
+            balance: self.schedule[period as usize - 1].remaining_balance,
+            loan_term,
+            apr: current_apr,
+            start_date,
+            date_roll,
+            funding_date: None,
+            rate_schedule,
+            // Taken from the rates already charged, so the caps have been applied
+            rate_caps: None,
+            loan_type,
+            balloon_period: config.balloon_period.filter(|&p| p > next_installment).map(|p| p - period),
+            deferment: None,
+            skip_payments,
+            payment_timing: PaymentTiming::Arrears,
+            graduated_payment: None,
+            prepayments: config.prepayments.iter()
+                .filter(|p| p.period > period)
+                .map(|p| Prepayment { period: p.period - period, ..*p })
+                .collect(),
+            // Recurring fees carry on; one-off charges already paid are dropped
+            fees: config.fees.iter()
+                .filter(|fee| fee.period.is_none_or(|p| p > period))
+                .map(|fee| Fee { period: fee.period.map(|p| p - period), ..fee.clone() })
+                .collect(),
+            ..config.clone()
+        })
+    }
+
+    // Builds the continuation and numbers its installments after the `period` already paid
+    pub(crate) fn continue_from(&self, period: u32, config: CalculatorConfig) -> Result<Amortization, AmortizationError> {
+        let mut continuation = Amortization::from_config(config)?;
+        for payment in &mut continuation.schedule {
+            payment.installment_number += period;
+        }
+        continuation.refresh_real_values();
+        Ok(continuation)
+    }
+}
@@ -0,0 +1,241 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Neg, Sub};
+use crate::decimal::Decimal;
+use crate::num::AmortNum;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RoundingMode {
+    // Ties round away from zero
+    #[default]
+    HalfUp,
+    // Ties round to the nearest even digit (banker's rounding)
+    HalfEven,
+    // Digits past the minor unit are dropped, rounding toward zero, as some regulators
+    // require for interest on statements
+    Truncate,
+}
+
+// When amounts are rounded to the currency's minor units, and which way they go. Lenders'
+// statements differ on both, so reproducing one exactly means matching its policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RoundingPolicy {
+    // Round each period's interest to minor units as it accrues, as most statements do. Otherwise
+    // interest and balances carry full precision and only displayed amounts are rounded
+    pub per_period: bool,
+    pub mode: RoundingMode,
+}
+
+impl RoundingPolicy {
+    pub fn per_period(mode: RoundingMode) -> Self {
+        RoundingPolicy { per_period: true, mode }
+    }
+
+    pub fn full_precision(mode: RoundingMode) -> Self {
+        RoundingPolicy { per_period: false, mode }
+    }
+}
+
+// A bare mode keeps full precision, which is what the builder did before policies existed
+impl From<RoundingMode> for RoundingPolicy {
+    fn from(mode: RoundingMode) -> Self {
+        RoundingPolicy::full_precision(mode)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Currency {
+    code: [u8; 3],
+    minor_units: u32,
+}
+
+impl Currency {
+    pub const USD: Currency = Currency::new(*b"USD", 2);
+    pub const EUR: Currency = Currency::new(*b"EUR", 2);
+    pub const GBP: Currency = Currency::new(*b"GBP", 2);
+    pub const JPY: Currency = Currency::new(*b"JPY", 0);
+    // Most decimal places a currency may have; no ISO 4217 currency has more than four
+    pub const MAX_MINOR_UNITS: u32 = 6;
+
+    // Panics when `minor_units` is above `MAX_MINOR_UNITS`; use `try_new` for codes read
+    // from outside the program
+    pub const fn new(code: [u8; 3], minor_units: u32) -> Self {
+        assert!(minor_units <= Currency::MAX_MINOR_UNITS, "a currency has at most 6 minor units");
+        Currency { code, minor_units }
+    }
+
+    pub const fn try_new(code: [u8; 3], minor_units: u32) -> Option<Self> {
+        if minor_units > Currency::MAX_MINOR_UNITS {
+            return None;
+        }
+        Some(Currency { code, minor_units })
+    }
+
+    pub fn code(&self) -> &str {
+        std::str::from_utf8(&self.code).unwrap_or("???")
+    }
+
+    pub fn minor_units(&self) -> u32 {
+        self.minor_units
+    }
+
+    // The smallest amount of the currency, 0.01 for one with cents
+    pub fn minor_unit(&self) -> f64 {
+        10f64.powi(-(self.minor_units as i32))
+    }
+
+    // `amount` rounded half up to the currency's minor units, whole yen or cents
+    pub fn round(&self, amount: f64) -> f64 {
+        AmortNum::round_dp(amount, self.minor_units)
+    }
+
+    // Largest amount whose minor units an f64 still holds exactly, about 90 trillion for a
+    // currency with cents
+    pub fn max_amount(&self) -> f64 {
+        (1u64 << f64::MANTISSA_DIGITS) as f64 * self.minor_unit()
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Currency::USD
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+// An amount held as an integer number of the currency's minor units, so sums never drift
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Money {
+    minor: i64,
+    currency: Currency,
+}
+
+impl Money {
+    pub fn from_minor(minor: i64, currency: Currency) -> Self {
+        Money { minor, currency }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Money::from_minor(0, currency)
+    }
+
+    pub fn from_f64(amount: f64, currency: Currency) -> Self {
+        Money::from_f64_with(amount, currency, RoundingMode::HalfUp)
+    }
+
+    // NaN becomes zero and amounts beyond the range of `i64` minor units, infinities
+    // included, are held at its limit; `checked_from_f64_with` reports both instead
+    pub fn from_f64_with(amount: f64, currency: Currency, mode: RoundingMode) -> Self {
+        Money::checked_from_f64_with(amount, currency, mode).unwrap_or_else(|| {
+            let limit = if amount.is_nan() { 0 } else if amount > 0.0 { i64::MAX } else { i64::MIN };
+            Money::from_minor(limit, currency)
+        })
+    }
+
+    pub fn checked_from_f64_with(amount: f64, currency: Currency, mode: RoundingMode) -> Option<Self> {
+        // Amounts already on a whole minor unit need no rounding, which skips the decimal
+        // round trip for nearly every installment in a schedule
+        let scale = 10f64.powi(currency.minor_units as i32);
+        let scaled = (amount * scale).round();
+        if amount.abs() < 1e12 && scaled / scale == amount {
+            return Some(Money::from_minor(scaled as i64, currency));
+        }
+        let minor = Decimal::try_from_f64_with(amount, mode)?.to_minor_units(currency.minor_units, mode);
+        i64::try_from(minor).ok().map(|minor| Money::from_minor(minor, currency))
+    }
+
+    pub fn minor(&self) -> i64 {
+        self.minor
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.minor as f64 / 10f64.powi(self.currency.minor_units as i32)
+    }
+
+    pub fn checked_add(self, rhs: Money) -> Option<Money> {
+        (self.currency == rhs.currency).then_some(())?;
+        Some(Money::from_minor(self.minor.checked_add(rhs.minor)?, self.currency))
+    }
+
+    pub fn checked_sub(self, rhs: Money) -> Option<Money> {
+        (self.currency == rhs.currency).then_some(())?;
+        Some(Money::from_minor(self.minor.checked_sub(rhs.minor)?, self.currency))
+    }
+
+    // Orders amounts in the same currency; None when the currencies differ, since 100 JPY and
+    // 100 USD have no order without an exchange rate
+    pub fn checked_cmp(&self, rhs: &Money) -> Option<Ordering> {
+        (self.currency == rhs.currency).then(|| self.minor.cmp(&rhs.minor))
+    }
+
+    // None for `i64::MIN` minor units, where `from_f64` holds the most negative amounts
+    pub fn checked_neg(self) -> Option<Money> {
+        Some(Money::from_minor(self.minor.checked_neg()?, self.currency))
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    // Panics when the currencies differ or the sum overflows; use `checked_add` to handle
+    // either case
+    fn add(self, rhs: Money) -> Money {
+        assert_eq!(self.currency, rhs.currency, "Cannot add {} to {}", rhs.currency, self.currency);
+        self.checked_add(rhs).expect("Money addition overflowed")
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    // Panics when the currencies differ or the difference overflows; use `checked_sub` to
+    // handle either case
+    fn sub(self, rhs: Money) -> Money {
+        assert_eq!(self.currency, rhs.currency, "Cannot subtract {} from {}", rhs.currency, self.currency);
+        self.checked_sub(rhs).expect("Money subtraction overflowed")
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    // Panics on `i64::MIN` minor units; use `checked_neg` to handle that case
+    fn neg(self) -> Money {
+        self.checked_neg().expect("Money negation overflowed")
+    }
+}
+
+impl Money {
+    // Sum of `amounts`, all of which must be in `currency`. Panics like `+` does; use
+    // `checked_total` to handle either case
+    pub fn total(currency: Currency, amounts: impl IntoIterator<Item = Money>) -> Money {
+        amounts.into_iter().fold(Money::zero(currency), Add::add)
+    }
+
+    // None when the sum overflows or an amount is in another currency
+    pub fn checked_total(currency: Currency, amounts: impl IntoIterator<Item = Money>) -> Option<Money> {
+        amounts.into_iter().try_fold(Money::zero(currency), Money::checked_add)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let units = self.currency.minor_units;
+        let sign = if self.minor < 0 { "-" } else { "" };
+        let minor = self.minor.unsigned_abs();
+        if units == 0 {
+            return write!(f, "{}{} {}", sign, minor, self.currency);
+        }
+        let scale = 10u64.pow(units);
+        write!(f, "{}{}.{:0width$} {}", sign, minor / scale, minor % scale, self.currency, width = units as usize)
+    }
+}
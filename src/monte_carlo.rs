@@ -0,0 +1,234 @@
+// Stochastic rate paths for adjustable loans. Each path draws the rate at every reset from a
+// rate model, runs the loan along it through the scenario engine, and keeps only its total
+// interest and peak installment, so thousands of paths need no more memory than their
+// totals. Draws come from a seeded generator, so a simulation can be reproduced exactly
+use crate::calculator::Amortization;
+use crate::error::AmortizationError;
+use crate::floating::{FloatingRate, ForwardCurve};
+use crate::rate::RatePeriod;
+use crate::scenario::{RatePath, RateScenario, ScenarioAnalysis};
+
+// Uniform draws in [0, 1); implement it to drive a simulation from another generator
+pub trait RandomSource {
+    fn next_f64(&mut self) -> f64;
+}
+
+// SplitMix64: small, fast and good enough for rate paths, though not for cryptography
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        SeededRng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl RandomSource for SeededRng {
+    fn next_f64(&mut self) -> f64 {
+        // The top 53 bits, the precision of an f64
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+// Standard normal draw by Box-Muller
+fn standard_normal(rng: &mut dyn RandomSource) -> f64 {
+    let u1 = 1.0 - rng.next_f64();
+    let u2 = rng.next_f64();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+// Rates in percent; every model keeps its rates within `min..=max`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateModel {
+    // Up or down by `step` at each reset with equal odds
+    RandomWalk { step: f64, min: f64, max: f64 },
+    // Mean-reverting: pulled toward `mean` at `speed` per year, with `volatility` the annual
+    // standard deviation of the shocks
+    Vasicek { mean: f64, speed: f64, volatility: f64, min: f64, max: f64 },
+}
+
+impl RateModel {
+    fn bounds(&self) -> (f64, f64) {
+        match *self {
+            RateModel::RandomWalk { min, max, .. } | RateModel::Vasicek { min, max, .. } => (min, max),
+        }
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        let (min, max) = self.bounds();
+        let parameters = match *self {
+            RateModel::RandomWalk { step, .. } => vec![step],
+            RateModel::Vasicek { mean, speed, volatility, .. } => vec![mean, speed, volatility],
+        };
+        if !(min >= 0.0 && max.is_finite() && min <= max) {
+            return Err(AmortizationError::InvalidRateSchedule(format!("Rate bounds {} to {} are not a range", min, max)));
+        }
+        match parameters.into_iter().find(|value| *value < 0.0 || !value.is_finite()) {
+            Some(invalid) => Err(AmortizationError::InvalidRateSchedule(format!("Rate model parameters must not be negative, got {}", invalid))),
+            None => Ok(()),
+        }
+    }
+
+    // The rate `years` after one at `rate`
+    fn step(&self, rate: f64, years: f64, rng: &mut dyn RandomSource) -> f64 {
+        let next = match *self {
+            RateModel::RandomWalk { step, .. } => if rng.next_f64() < 0.5 { rate - step } else { rate + step },
+            RateModel::Vasicek { mean, speed, volatility, .. } => {
+                rate + speed * (mean - rate) * years + volatility * years.sqrt() * standard_normal(rng)
+            }
+        };
+        let (min, max) = self.bounds();
+        next.clamp(min, max)
+    }
+}
+
+// Sorted outcomes of every path
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    pub values: Vec<f64>,
+}
+
+impl Distribution {
+    fn new(mut values: Vec<f64>) -> Self {
+        values.sort_by(f64::total_cmp);
+        Distribution { values }
+    }
+
+    // Linear between the nearest ranks, `percent` from 0 to 100
+    pub fn percentile(&self, percent: f64) -> f64 {
+        let Some(&last) = self.values.last() else { return 0.0 };
+        let rank = percent.clamp(0.0, 100.0) / 100.0 * (self.values.len() - 1) as f64;
+        let lower = rank.floor() as usize;
+        let upper = self.values.get(lower + 1).copied().unwrap_or(last);
+        self.values[lower] + (upper - self.values[lower]) * rank.fract()
+    }
+
+    pub fn p5(&self) -> f64 {
+        self.percentile(5.0)
+    }
+
+    pub fn p50(&self) -> f64 {
+        self.percentile(50.0)
+    }
+
+    pub fn p95(&self) -> f64 {
+        self.percentile(95.0)
+    }
+
+    pub fn mean(&self) -> f64 {
+        if self.values.is_empty() {
+            return 0.0;
+        }
+        self.values.iter().sum::<f64>() / self.values.len() as f64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationResult {
+    pub paths: u32,
+    pub total_interest: Distribution,
+    pub peak_payment: Distribution,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonteCarlo {
+    pub model: RateModel,
+    pub paths: u32,
+    pub seed: u64,
+    // Periods between resets of a loan without its own resets
+    pub reset_every: u32,
+}
+
+impl MonteCarlo {
+    pub fn new(model: RateModel, paths: u32) -> Self {
+        MonteCarlo { model, paths, seed: 0, reset_every: 12 }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_reset_every(mut self, reset_every: u32) -> Self {
+        self.reset_every = reset_every;
+        self
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.paths == 0 {
+            return Err(AmortizationError::InvalidConfig("Simulation needs at least one path".to_string()));
+        }
+        if self.reset_every == 0 {
+            return Err(AmortizationError::InvalidRateSchedule("Simulated rates must reset every one or more periods".to_string()));
+        }
+        self.model.validate()
+    }
+
+    // One path of rates from `initial`, drawn at each of `resets`
+    pub fn rate_path(&self, initial: f64, resets: &[u32], periods_per_year: u32, rng: &mut dyn RandomSource) -> Vec<RatePeriod> {
+        let mut rate = initial;
+        let mut previous = 1;
+        let mut path = vec![RatePeriod::new(1, initial)];
+        for &reset in resets {
+            let years = reset.saturating_sub(previous) as f64 / periods_per_year as f64;
+            rate = self.model.step(rate, years, rng);
+            path.push(RatePeriod::new(reset, rate));
+            previous = reset;
+        }
+        path
+    }
+
+    fn run(&self, base: &Amortization, mut curve: impl FnMut(&mut SeededRng) -> Result<Vec<RatePeriod>, AmortizationError>) -> Result<SimulationResult, AmortizationError> {
+        let analysis = ScenarioAnalysis::new(base.clone());
+        let mut rng = SeededRng::new(self.seed);
+        let mut total_interest = Vec::with_capacity(self.paths as usize);
+        let mut peak_payment = Vec::with_capacity(self.paths as usize);
+        for path in 1..=self.paths {
+            let scenario = RateScenario::new(&format!("path {}", path), RatePath::Curve(curve(&mut rng)?));
+            let result = analysis.run_scenario(&scenario)?;
+            total_interest.push(result.total_interest);
+            peak_payment.push(result.max_payment);
+        }
+        Ok(SimulationResult {
+            paths: self.paths,
+            total_interest: Distribution::new(total_interest),
+            peak_payment: Distribution::new(peak_payment),
+        })
+    }
+
+    // The loan's rate replaced at each of its resets, or every `reset_every` periods when it
+    // has none, starting from its initial rate. Rate caps on the loan hold the simulated rates
+    pub fn simulate(&self, base: &Amortization) -> Result<SimulationResult, AmortizationError> {
+        self.validate()?;
+        let schedule = base.config.uncapped_rate_schedule();
+        let resets: Vec<u32> = match schedule.get(1) {
+            Some(first) => (first.start_period..=base.periods).step_by(self.reset_every as usize).collect(),
+            None => (self.reset_every + 1..=base.periods).step_by(self.reset_every as usize).collect(),
+        };
+        let periods_per_year = base.config.frequency.periods_per_year();
+        self.run(base, |rng| Ok(self.rate_path(schedule[0].apr, &resets, periods_per_year, rng)))
+    }
+
+    // The index of a floating-rate loan simulated from its first level, with the loan's margin,
+    // resets, caps and floor applied to each path
+    pub fn simulate_floating(&self, base: &Amortization, floating: &FloatingRate) -> Result<SimulationResult, AmortizationError> {
+        self.validate()?;
+        let resets: Vec<u32> = floating.uncapped_schedule(base.periods)?.iter().skip(1).map(|r| r.start_period).collect();
+        let periods_per_year = base.config.frequency.periods_per_year();
+        let initial = floating.index.rate_at(1);
+        self.run(base, |rng| {
+            let index = ForwardCurve::new(self.rate_path(initial, &resets, periods_per_year, rng));
+            FloatingRate { index, ..floating.clone() }.rate_schedule(base.periods)
+        })
+    }
+}
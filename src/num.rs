@@ -0,0 +1,93 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+use crate::decimal::Decimal;
+use crate::money::RoundingMode;
+
+pub trait AmortNum:
+    Copy
+    + PartialOrd
+    + fmt::Debug
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn from_f64(value: f64) -> Self;
+    // None for a value the backend can't hold, such as NaN or one past `Decimal`'s range
+    fn checked_from_f64(value: f64) -> Option<Self>;
+    fn to_f64(self) -> f64;
+    // Rounds to `places` decimal places, e.g. a currency's minor units
+    fn round_dp(self, places: u32) -> Self;
+    // Rounds the decimal value with an explicit tie rule
+    fn round_dp_with(self, places: u32, mode: RoundingMode) -> Self;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    fn round_cents(self) -> Self {
+        self.round_dp(2)
+    }
+
+    fn round_cents_with(self, mode: RoundingMode) -> Self {
+        self.round_dp_with(2, mode)
+    }
+
+    fn checked_powi(self, exp: u32) -> Option<Self> {
+        let mut result = Self::one();
+        let mut base = self;
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+        Some(result)
+    }
+}
+
+impl AmortNum for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn one() -> Self {
+        1.0
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn checked_from_f64(value: f64) -> Option<Self> {
+        value.is_finite().then_some(value)
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn round_dp(self, places: u32) -> Self {
+        let scale = 10f64.powi(places as i32);
+        (self * scale).round() / scale
+    }
+
+    // Goes through the shortest decimal representation, so 10.005 is a tie rather than the
+    // binary value just below it
+    fn round_dp_with(self, places: u32, mode: RoundingMode) -> Self {
+        Decimal::try_from_f64_with(self, mode).map_or(self, |value| value.round_dp_with(places, mode).to_f64())
+    }
+
+    fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let product = self * rhs;
+        product.is_finite().then_some(product)
+    }
+
+    fn checked_powi(self, exp: u32) -> Option<Self> {
+        let result = self.powf(exp as f64);
+        result.is_finite().then_some(result)
+    }
+}
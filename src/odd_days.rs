@@ -0,0 +1,88 @@
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::day_count::DayCount;
+use crate::money::Currency;
+
+// How interest for the odd days between funding and the first regular period is charged,
+// when the first period is longer or shorter than the rest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FirstPeriodPolicy {
+    // Interim interest is settled at closing; a funding date inside the first period gives
+    // the borrower a credit instead
+    #[default]
+    CollectAtClosing,
+    // The first installment carries the interim interest, so its interest covers the whole
+    // stretch from funding to the first due date
+    AddToFirstPayment,
+    // The interim interest is divided evenly, to the cent, between the installments due after
+    // any deferment; the last carries the remainder. A loan paid off early forgoes the shares
+    // not yet due
+    SpreadOverTerm,
+    // The first period is charged as a regular one and the odd days are ignored
+    Waive,
+}
+
+// The earlier name for `FirstPeriodPolicy`
+pub type OddDaysTreatment = FirstPeriodPolicy;
+
+impl FirstPeriodPolicy {
+    // Whether the installments, rather than closing, carry the interim interest
+    pub fn charges_installments(&self) -> bool {
+        matches!(self, FirstPeriodPolicy::AddToFirstPayment | FirstPeriodPolicy::SpreadOverTerm)
+    }
+}
+
+impl Amortization {
+    // Days from funding to the start of the first regular period, negative when the loan is
+    // funded part way through it
+    pub fn odd_days(&self) -> Option<i64> {
+        let funding_date = self.config.funding_date?;
+        Some(self.odd_days_count().days_between(funding_date, self.due_date(0)?))
+    }
+
+    // Per diem interest on the original balance for the odd days, using the loan's day count
+    // (actual/365 by default)
+    pub fn odd_days_interest(&self) -> f64 {
+        let (Some(funding_date), Some(period_start)) = (self.config.funding_date, self.due_date(0)) else {
+            return 0.0;
+        };
+        let apr = self.config.rate_convention.nominal_apr(self.rate_schedule[0].apr, self.config.frequency);
+        let interest = self.balance * apr / 100.0 * self.odd_days_count().year_fraction(funding_date, period_start);
+        self.config.currency.round(interest)
+    }
+
+    // Interim interest due at closing, negative for a credit
+    pub fn closing_interest(&self) -> f64 {
+        match self.config.odd_days_treatment {
+            FirstPeriodPolicy::CollectAtClosing => self.odd_days_interest(),
+            _ => 0.0,
+        }
+    }
+
+    // Share of the interim interest installment `installment_number` carries
+    pub fn interim_interest_in(&self, installment_number: u32) -> f64 {
+        split_interim_interest(self.config.odd_days_treatment, self.odd_days_interest(), self.deferred_periods(), self.periods, installment_number, self.config.currency)
+    }
+
+    fn odd_days_count(&self) -> DayCount {
+        self.config.day_count.unwrap_or(DayCount::Actual365)
+    }
+}
+
+// `total` shared out under `policy` between the installments after `deferred` up to `periods`,
+// in whole minor units of `currency`
+pub(crate) fn split_interim_interest(policy: FirstPeriodPolicy, total: f64, deferred: u32, periods: u32, installment_number: u32, currency: Currency) -> f64 {
+    match policy {
+        FirstPeriodPolicy::AddToFirstPayment if installment_number == 1 => total,
+        FirstPeriodPolicy::SpreadOverTerm if installment_number > deferred && installment_number <= periods => {
+            let count = periods - deferred;
+            let share = currency.round(total / count as f64);
+            if installment_number == periods { total - share * (count - 1) as f64 } else { share }
+        }
+        _ => 0.0,
+    }
+}
+
+pub(crate) fn is_valid_funding_date(funding_date: NaiveDate, first_due_date: Option<NaiveDate>) -> bool {
+    first_due_date.is_some_and(|due| funding_date < due)
+}
@@ -1,24 +1,98 @@
 use std::fmt;
 use chrono::NaiveDate;
+use crate::escrow::EscrowPayment;
+use crate::inflation::RealValues;
+use crate::money::{Currency, Money, RoundingMode};
+use crate::table::FormatOptions;
 
-#[derive(Debug, Clone)]
+// One row of the ledger. Each row starts from the previous row's remaining balance:
+// remaining_balance = beginning_balance - principal + capitalized_interest
+#[derive(Debug, Clone, PartialEq)]
 pub struct Payment {
     pub installment_number: u32,
     pub beginning_balance: f64,
-    pub ending_balance: f64,
     pub interest: f64,
     pub principal: f64,
     pub date: Option<NaiveDate>,
     pub remaining_balance: f64,
-    pub installment_amount: f64
+    pub installment_amount: f64,
+    pub balloon_amount: f64,
+    pub capitalized_interest: f64,
+    // Interest accrued but neither paid nor yet capitalized, after this installment
+    pub accrued_interest: f64,
+    // Installment moved to a non-interest-bearing balance during forbearance; negative in the
+    // final row, where that balance is repaid
+    pub deferred_amount: f64,
+    // Fees and charges included in the installment; neither interest nor principal
+    pub fees: f64,
+    pub skipped: bool,
+    pub escrow: Option<EscrowPayment>,
+    // Remaining balance over the estimated property value, in percent
+    pub ltv: Option<f64>,
+    // Amounts in today's money when the loan has an inflation assumption
+    pub real: Option<RealValues>,
+    // Dates interest accrued between; None for undated schedules
+    pub accrual: Option<AccrualPeriod>,
 }
 
+// From the previous due date to this one, before any business-day adjustment. `days` is
+// counted by the loan's day count convention, actual days when it has none
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccrualPeriod {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub days: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaymentAmounts {
+    pub installment_amount: Money,
+    pub interest: Money,
+    pub principal: Money,
+    pub remaining_balance: Money,
+}
+
+impl Payment {
+    pub fn in_currency(&self, currency: Currency, mode: RoundingMode) -> PaymentAmounts {
+        let money = |amount| Money::from_f64_with(amount, currency, mode);
+        PaymentAmounts {
+            installment_amount: money(self.installment_amount),
+            interest: money(self.interest),
+            principal: money(self.principal),
+            remaining_balance: money(self.remaining_balance),
+        }
+    }
+
+    pub fn accrual_start(&self) -> Option<NaiveDate> {
+        self.accrual.map(|accrual| accrual.start)
+    }
+
+    pub fn accrual_end(&self) -> Option<NaiveDate> {
+        self.accrual.map(|accrual| accrual.end)
+    }
+
+    pub fn days_in_period(&self) -> Option<i64> {
+        self.accrual.map(|accrual| accrual.days)
+    }
+
+    // The balance the row closes on; the same value as `remaining_balance`
+    pub fn ending_balance(&self) -> f64 {
+        self.remaining_balance
+    }
+
+    pub fn escrow_amount(&self) -> f64 {
+        self.escrow.map_or(0.0, |escrow| escrow.total())
+    }
+
+    // Principal, interest, taxes and insurance
+    pub fn piti(&self) -> f64 {
+        self.installment_amount + self.escrow_amount()
+    }
+}
+
+// Uses `FormatOptions::payment_display()`; `display_with` picks the columns
 impl fmt::Display for Payment {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "Date: {:?}, Interest: {:.2}, Principal: {:.2}, Remaining Balance: {:.2}, Beginning Balance: {:.2} Ending Balance: {:.2}",
-            self.date.unwrap(), self.interest, self.principal, self.remaining_balance, self.beginning_balance, self.ending_balance
-        )
+        self.display_with(&FormatOptions::payment_display()).fmt(f)
     }
 }
\ No newline at end of file
@@ -0,0 +1,109 @@
+// Payoff quotes: what it takes to close the loan on a given day, which is rarely a due date
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::day_count::DayCount;
+use crate::error::AmortizationError;
+use crate::money::Currency;
+
+// The figures a servicer prints on a payoff letter. Installments due on or before the quote
+// date are taken as paid
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayoffQuote {
+    pub date: NaiveDate,
+    pub installments_paid: u32,
+    pub principal: f64,
+    // Interest accrued since the last installment
+    pub accrued_interest: f64,
+    // Interest added for each day the payoff is delayed
+    pub per_diem: f64,
+    // Charged by the loan's prepayment penalty clause on the principal repaid early
+    pub prepayment_penalty: f64,
+    pub fees: f64,
+    pub total: f64,
+    // The loan's currency, whose minor units every figure is rounded to
+    pub currency: Currency,
+}
+
+impl PayoffQuote {
+    // Adds a payoff, recording or prepayment fee to the amount due
+    pub fn with_fee(mut self, fee: f64) -> Self {
+        self.fees = self.currency.round(self.fees + fee);
+        self.total = self.currency.round(self.principal + self.accrued_interest + self.prepayment_penalty + self.fees);
+        self
+    }
+
+    // Amount due if the payoff arrives `days` after the quote date, before the next installment
+    pub fn good_through(&self, days: u32) -> f64 {
+        self.currency.round(self.total + self.per_diem * days as f64)
+    }
+}
+
+impl Amortization {
+    // Nominal annual rate, in percent, charged on installment `installment_number`
+    pub(crate) fn accrual_apr(&self, installment_number: u32) -> f64 {
+        let apr = self.rate_schedule.iter()
+            .rev()
+            .find(|r| r.start_period <= installment_number)
+            .map_or(self.config.apr, |r| r.apr);
+        self.config.rate_convention.nominal_apr(apr, self.config.frequency)
+    }
+
+    fn accrual_day_count(&self) -> DayCount {
+        self.config.day_count.unwrap_or(DayCount::Actual365)
+    }
+
+    // Interest accruing on the outstanding balance from `from` to `to`, per diem using the
+    // loan's day count (actual/365 by default). The balance steps down on each due date in
+    // between and a rate reset applies from its installment on. None when the loan has no
+    // payment dates
+    pub fn accrued_interest(&self, from: NaiveDate, to: NaiveDate) -> Option<f64> {
+        self.start_date?;
+        let day_count = self.accrual_day_count();
+        let mut paid = self.payments_made_by(from);
+        let mut start = from;
+        let mut interest = 0.0;
+        while start < to {
+            let balance = self.balance_after(paid)?;
+            let end = self.schedule.get(paid as usize)
+                .and_then(|p| p.date)
+                .filter(|&due| due < to)
+                .unwrap_or(to);
+            interest += balance * self.accrual_apr(paid + 1) / 100.0 * day_count.year_fraction(start, end);
+            start = end;
+            paid += 1;
+        }
+        Some(self.config.currency.round(interest))
+    }
+
+    pub fn payoff_quote(&self, date: NaiveDate) -> Result<PayoffQuote, AmortizationError> {
+        self.start_date.ok_or(AmortizationError::MissingParameter("start_date"))?;
+        let currency = self.config.currency;
+        let paid = self.payments_made_by(date);
+        let principal = currency.round(self.balance_after(paid).unwrap_or(0.0).max(0.0));
+
+        let (accrued_interest, per_diem) = if principal > 0.0 {
+            let accrual_start = match paid {
+                0 => self.due_date(0),
+                n => self.schedule[n as usize - 1].date,
+            }.ok_or(AmortizationError::DateOutOfRange(paid))?;
+            let accrued = self.accrued_interest(accrual_start, date).unwrap_or(0.0);
+            let daily_rate = self.accrual_apr(paid + 1) / 100.0 / self.accrual_day_count().days_in_year();
+            (accrued, currency.round(principal * daily_rate))
+        } else {
+            (0.0, 0.0)
+        };
+
+        let prepayment_penalty = self.prepayment_penalty(paid, principal);
+        Ok(PayoffQuote {
+            date,
+            installments_paid: paid,
+            principal,
+            accrued_interest,
+            per_diem,
+            prepayment_penalty,
+            fees: 0.0,
+            total: currency.round(principal + accrued_interest + prepayment_penalty),
+            currency,
+        })
+    }
+}
@@ -0,0 +1,105 @@
+// Prepayment penalty clauses and what they charge when principal is repaid ahead of schedule.
+// Windows count installments already paid, so a clause for years 1-3 of a monthly loan covers
+// prepayments made before the 36th installment has been paid
+use crate::calculator::Amortization;
+use crate::frequency::PaymentFrequency;
+
+// Percent of the prepaid amount charged while fewer than `through_period` installments are paid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PenaltyStep {
+    pub through_period: u32,
+    pub percent: f64,
+}
+
+impl PenaltyStep {
+    pub fn new(through_period: u32, percent: f64) -> Self {
+        PenaltyStep { through_period, percent }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrepaymentPenalty {
+    // One rate for the whole window, e.g. 2% of the amount prepaid in years 1-3
+    PercentOfPrepaid(PenaltyStep),
+    // Rates that fall as the loan ages, e.g. 5-4-3-2-1; steps in increasing `through_period`
+    StepDown(Vec<PenaltyStep>),
+    // The lender is made whole for reinvesting the prepaid principal at `treasury_rate`
+    // (annual percent) instead of the note rate until maturity
+    YieldMaintenance { treasury_rate: f64 },
+}
+
+impl PrepaymentPenalty {
+    pub fn percent_of_prepaid(percent: f64, through_period: u32) -> Self {
+        PrepaymentPenalty::PercentOfPrepaid(PenaltyStep::new(through_period, percent))
+    }
+
+    pub fn step_down(steps: Vec<PenaltyStep>) -> Self {
+        PrepaymentPenalty::StepDown(steps)
+    }
+
+    // One step per loan year, e.g. `&[5.0, 4.0, 3.0, 2.0, 1.0]`
+    pub fn yearly(percents: &[f64], frequency: PaymentFrequency) -> Self {
+        let periods_per_year = frequency.periods_per_year();
+        PrepaymentPenalty::StepDown(percents.iter()
+            .enumerate()
+            .map(|(year, &percent)| PenaltyStep::new((year as u32 + 1) * periods_per_year, percent))
+            .collect())
+    }
+
+    pub fn yield_maintenance(treasury_rate: f64) -> Self {
+        PrepaymentPenalty::YieldMaintenance { treasury_rate }
+    }
+
+    pub fn steps(&self) -> &[PenaltyStep] {
+        match self {
+            PrepaymentPenalty::PercentOfPrepaid(step) => std::slice::from_ref(step),
+            PrepaymentPenalty::StepDown(steps) => steps,
+            PrepaymentPenalty::YieldMaintenance { .. } => &[],
+        }
+    }
+
+    // The first invalid percent, rate or out-of-order step, for validation
+    pub(crate) fn invalid_value(&self) -> Option<f64> {
+        if let PrepaymentPenalty::YieldMaintenance { treasury_rate } = *self {
+            return (treasury_rate < 0.0 || !treasury_rate.is_finite()).then_some(treasury_rate);
+        }
+        let steps = self.steps();
+        steps.iter()
+            .find(|step| step.percent < 0.0 || !step.percent.is_finite())
+            .map(|step| step.percent)
+            .or_else(|| steps.windows(2)
+                .find(|pair| pair[1].through_period <= pair[0].through_period)
+                .map(|pair| pair[1].through_period as f64))
+    }
+}
+
+impl Amortization {
+    // Penalty for prepaying `amount` of principal once `payments_made` installments are paid,
+    // rounded to the currency's minor units; zero without a clause or outside its window
+    pub fn prepayment_penalty(&self, payments_made: u32, amount: f64) -> f64 {
+        let Some(penalty) = &self.config.prepayment_penalty else {
+            return 0.0;
+        };
+        let charge = match *penalty {
+            PrepaymentPenalty::YieldMaintenance { treasury_rate } => self.yield_maintenance(payments_made, amount, treasury_rate),
+            _ => penalty.steps().iter()
+                .find(|step| payments_made < step.through_period)
+                .map_or(0.0, |step| amount * step.percent / 100.0),
+        };
+        self.config.currency.round(charge.max(0.0))
+    }
+
+    // The prepaid share of the remaining installments, valued at the treasury rate, less the
+    // principal it repays. Zero once the treasury rate reaches the note rate
+    fn yield_maintenance(&self, payments_made: u32, amount: f64, treasury_rate: f64) -> f64 {
+        let Some(balance) = self.balance_after(payments_made).filter(|&b| b > 0.0) else {
+            return 0.0;
+        };
+        let rate = treasury_rate / 100.0 / self.config.frequency.periods_per_year() as f64;
+        let value: f64 = self.schedule[payments_made as usize..].iter()
+            .enumerate()
+            .map(|(k, payment)| payment.installment_amount / (1.0 + rate).powi(k as i32 + 1))
+            .sum();
+        amount.min(balance) / balance * (value - balance)
+    }
+}
@@ -0,0 +1,160 @@
+// Planned payments for personal finance apps (GnuCash, YNAB, Quicken): QIF with each
+// installment split into interest, principal and any fees or escrow, or an OFX statement of
+// the same transactions, splits given in the memo since OFX has none
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::error::AmortizationError;
+use crate::money::Money;
+use crate::payment::Payment;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinanceAppOptions {
+    pub payee: String,
+    // Liability account principal is transferred to; QIF writes it as `[account]`
+    pub loan_account: String,
+    pub interest_category: String,
+    pub fees_category: String,
+    pub escrow_category: String,
+    // Account identifier OFX statements carry
+    pub account_id: String,
+    pub include_escrow: bool,
+}
+
+impl Default for FinanceAppOptions {
+    fn default() -> Self {
+        FinanceAppOptions {
+            payee: "Loan Payment".to_string(),
+            loan_account: "Loan".to_string(),
+            interest_category: "Interest Exp".to_string(),
+            fees_category: "Bank Charge".to_string(),
+            escrow_category: "Escrow".to_string(),
+            account_id: "LOAN".to_string(),
+            include_escrow: false,
+        }
+    }
+}
+
+impl FinanceAppOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_payee(mut self, payee: &str) -> Self {
+        self.payee = payee.to_string();
+        self
+    }
+
+    pub fn with_loan_account(mut self, loan_account: &str) -> Self {
+        self.loan_account = loan_account.to_string();
+        self
+    }
+
+    pub fn with_interest_category(mut self, interest_category: &str) -> Self {
+        self.interest_category = interest_category.to_string();
+        self
+    }
+
+    pub fn with_account_id(mut self, account_id: &str) -> Self {
+        self.account_id = account_id.to_string();
+        self
+    }
+
+    pub fn with_escrow(mut self, include_escrow: bool) -> Self {
+        self.include_escrow = include_escrow;
+        self
+    }
+}
+
+// One planned payment; the splits sum to the amount to the cent
+struct Transaction {
+    installment_number: u32,
+    date: NaiveDate,
+    amount: Money,
+    splits: Vec<(String, &'static str, Money)>,
+}
+
+fn amount(money: Money) -> String {
+    format!("{:.*}", money.currency().minor_units() as usize, money.to_f64())
+}
+
+fn memo(transaction: &Transaction, periods: usize) -> String {
+    let splits: Vec<String> = transaction.splits.iter().map(|(_, label, money)| format!("{} {}", label, amount(*money))).collect();
+    format!("Installment {} of {}: {}", transaction.installment_number, periods, splits.join(", "))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+impl Amortization {
+    fn transactions(&self, options: &FinanceAppOptions) -> Result<Vec<Transaction>, AmortizationError> {
+        let split = |payment: &Payment| {
+            let escrow = if options.include_escrow { payment.escrow_amount() } else { 0.0 };
+            [
+                (options.interest_category.clone(), "interest", payment.interest - payment.capitalized_interest),
+                (format!("[{}]", options.loan_account), "principal", payment.principal),
+                (options.fees_category.clone(), "fees", payment.fees),
+                (options.escrow_category.clone(), "escrow", escrow),
+            ].into_iter()
+                .map(|(category, label, value)| (category, label, self.money(value)))
+                .filter(|(_, _, money)| money.minor() != 0)
+                .collect::<Vec<_>>()
+        };
+        self.schedule.iter()
+            .map(|payment| (payment, split(payment)))
+            .filter(|(_, splits)| !splits.is_empty())
+            .map(|(payment, splits)| Ok(Transaction {
+                installment_number: payment.installment_number,
+                date: payment.date.ok_or(AmortizationError::MissingParameter("start_date"))?,
+                amount: splits.iter().fold(self.money(0.0), |total, (_, _, money)| total + *money),
+                splits,
+            }))
+            .collect()
+    }
+
+    // Payments leave the bank account, so amounts are negative
+    pub fn to_qif(&self, options: &FinanceAppOptions) -> Result<String, AmortizationError> {
+        let mut qif = String::from("!Type:Bank\n");
+        for transaction in self.transactions(options)? {
+            qif.push_str(&format!("D{}\nT-{}\nP{}\nM{}\n", transaction.date.format("%m/%d/%Y"), amount(transaction.amount),
+                options.payee, memo(&transaction, self.schedule.len())));
+            for (category, label, money) in &transaction.splits {
+                qif.push_str(&format!("S{}\nE{}\n$-{}\n", category, label, amount(*money)));
+            }
+            qif.push_str("^\n");
+        }
+        Ok(qif)
+    }
+
+    // An OFX 2.2 bank statement listing the planned payments
+    pub fn to_ofx(&self, options: &FinanceAppOptions) -> Result<String, AmortizationError> {
+        let transactions = self.transactions(options)?;
+        let (Some(first), Some(last)) = (transactions.first(), transactions.last()) else {
+            return Err(AmortizationError::MissingParameter("start_date"));
+        };
+        let (start, end) = (first.date.format("%Y%m%d").to_string(), last.date.format("%Y%m%d").to_string());
+        let status = "<STATUS><CODE>0</CODE><SEVERITY>INFO</SEVERITY></STATUS>";
+        let mut ofx = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n\
+            <?OFX OFXHEADER=\"200\" VERSION=\"220\" SECURITY=\"NONE\" OLDFILEUID=\"NONE\" NEWFILEUID=\"NONE\"?>\n<OFX>\n");
+        ofx.push_str(&format!("<SIGNONMSGSRSV1><SONRS>{}<DTSERVER>{}</DTSERVER><LANGUAGE>ENG</LANGUAGE></SONRS></SIGNONMSGSRSV1>\n", status, start));
+        ofx.push_str(&format!(
+            "<BANKMSGSRSV1><STMTTRNRS><TRNUID>1</TRNUID>{}<STMTRS><CURDEF>{}</CURDEF>\
+            <BANKACCTFROM><BANKID>{}</BANKID><ACCTID>{}</ACCTID><ACCTTYPE>CHECKING</ACCTTYPE></BANKACCTFROM>\n\
+            <BANKTRANLIST><DTSTART>{}</DTSTART><DTEND>{}</DTEND>\n",
+            status, self.config.currency.code(), escape(&options.loan_account), escape(&options.account_id), start, end
+        ));
+        for transaction in &transactions {
+            ofx.push_str(&format!(
+                "<STMTTRN><TRNTYPE>PAYMENT</TRNTYPE><DTPOSTED>{}</DTPOSTED><TRNAMT>-{}</TRNAMT><FITID>{}-{}</FITID><NAME>{}</NAME><MEMO>{}</MEMO></STMTTRN>\n",
+                transaction.date.format("%Y%m%d"), amount(transaction.amount), escape(&options.account_id), transaction.installment_number,
+                escape(&options.payee), escape(&memo(transaction, self.schedule.len()))
+            ));
+        }
+        let total = transactions.iter().fold(self.money(0.0), |total, t| total + t.amount);
+        ofx.push_str(&format!(
+            "</BANKTRANLIST><LEDGERBAL><BALAMT>-{}</BALAMT><DTASOF>{}</DTASOF></LEDGERBAL></STMTRS></STMTTRNRS></BANKMSGSRSV1>\n</OFX>\n",
+            amount(total), end
+        ));
+        Ok(ofx)
+    }
+}
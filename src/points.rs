@@ -0,0 +1,101 @@
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::compare::LoanComparison;
+use crate::effective_apr::UpfrontCosts;
+use crate::error::AmortizationError;
+
+// One entry of a lender's rate sheet: pay `points` percent of the loan amount for `apr`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointsOption {
+    pub points: f64,
+    pub apr: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct PointsAnalysis {
+    pub option: PointsOption,
+    pub points_cost: f64,
+    pub periodic_payment: f64,
+    // Installment at which the points have paid for themselves against the option with the
+    // fewest points; Some(0) for that option itself
+    pub break_even_month: Option<u32>,
+    // Points, installments and the payoff balance when the loan is closed after the holding period
+    pub holding_cost: f64,
+    pub lifetime_cost: f64,
+    pub effective_apr: f64,
+    pub loan: Amortization,
+}
+
+#[derive(Debug, Clone)]
+pub struct PointsRecommendation {
+    pub holding_periods: u32,
+    pub options: Vec<PointsAnalysis>,
+    // Index of the option with the lowest holding cost
+    pub recommended: usize,
+}
+
+impl PointsOption {
+    pub fn new(points: f64, apr: f64) -> Self {
+        PointsOption { points, apr }
+    }
+}
+
+impl PointsRecommendation {
+    pub fn best(&self) -> &PointsAnalysis {
+        &self.options[self.recommended]
+    }
+}
+
+impl Amortization {
+    // Prices each rate sheet option on this loan's terms, assuming the loan is paid off after
+    // `holding_periods` installments (for instance when the home is sold or refinanced)
+    pub fn optimize_points(&self, options: &[PointsOption], holding_periods: u32) -> Result<PointsRecommendation, AmortizationError> {
+        if options.is_empty() {
+            return Err(AmortizationError::MissingParameter("options"));
+        }
+        if holding_periods == 0 {
+            return Err(AmortizationError::InvalidPeriods(holding_periods));
+        }
+        if let Some(invalid) = options.iter().find(|o| o.points < 0.0 || !o.points.is_finite()) {
+            return Err(AmortizationError::InvalidUpfrontCost(invalid.points));
+        }
+
+        let loans = options.iter()
+            .map(|option| Amortization::from_config(CalculatorConfig {
+                apr: option.apr,
+                rate_schedule: Vec::new(),
+                ..self.config.clone()
+            }))
+            .collect::<Result<Vec<_>, _>>()?;
+        let points_costs: Vec<f64> = options.iter().map(|o| self.balance * o.points / 100.0).collect();
+        let baseline = (0..options.len())
+            .min_by(|&a, &b| options[a].points.total_cmp(&options[b].points))
+            .unwrap_or(0);
+        let comparison = LoanComparison::new(loans).with_upfront_costs(points_costs.clone());
+
+        let analyses = options.iter().zip(&comparison.loans).enumerate().map(|(index, (option, loan))| {
+            let held = (holding_periods as usize).min(loan.schedule.len());
+            let payments: f64 = loan.schedule[..held].iter().map(|p| p.installment_amount).sum();
+            let payoff = loan.balance_after(held as u32).unwrap_or(0.0);
+            let costs = UpfrontCosts { points: option.points, ..Default::default() };
+            Ok(PointsAnalysis {
+                option: *option,
+                points_cost: points_costs[index],
+                periodic_payment: loan.periodic_payment,
+                break_even_month: comparison.break_even_month(index, baseline),
+                holding_cost: points_costs[index] + payments + payoff,
+                lifetime_cost: points_costs[index] + loan.total_payment,
+                effective_apr: loan.effective_apr(&costs)?.apr,
+                loan: loan.clone(),
+            })
+        }).collect::<Result<Vec<_>, AmortizationError>>()?;
+
+        let recommended = (0..analyses.len())
+            .min_by(|&a, &b| analyses[a].holding_cost.total_cmp(&analyses[b].holding_cost))
+            .unwrap_or(0);
+        Ok(PointsRecommendation {
+            holding_periods,
+            options: analyses,
+            recommended,
+        })
+    }
+}
@@ -0,0 +1,415 @@
+use std::collections::BTreeMap;
+use chrono::{Datelike, Months, NaiveDate};
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::error::AmortizationError;
+use crate::fx::FxRateProvider;
+use crate::money::Currency;
+use crate::payment::Payment;
+
+const MAX_MONTHS: u32 = 1200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayoffStrategy {
+    // Smallest balance first
+    Snowball,
+    // Highest rate first
+    Avalanche,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Debt {
+    pub name: String,
+    pub balance: f64,
+    pub apr: f64,
+    pub minimum_payment: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct DebtPayment {
+    // Index of the debt in the portfolio
+    pub debt: usize,
+    pub payment: Payment,
+}
+
+#[derive(Debug, Clone)]
+pub struct PortfolioMonth {
+    pub month: u32,
+    pub date: Option<NaiveDate>,
+    pub payments: Vec<DebtPayment>,
+    pub remaining_balance: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct PayoffPlan {
+    pub strategy: PayoffStrategy,
+    pub schedule: Vec<PortfolioMonth>,
+    pub total_interest: f64,
+    pub total_paid: f64,
+    // Month in which each debt is paid off, in portfolio order
+    pub payoff_months: Vec<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StrategyComparison {
+    pub snowball: PayoffPlan,
+    pub avalanche: PayoffPlan,
+    // Positive when avalanche pays less interest than snowball
+    pub interest_saved_by_avalanche: f64,
+}
+
+// Schedules for a pool of loans, such as a loan tape, with balance-weighted statistics
+#[derive(Debug, Clone)]
+pub struct LoanPool {
+    pub loans: Vec<Amortization>,
+    pub total_balance: f64,
+    pub weighted_average_rate: f64,
+    // Remaining term in months
+    pub weighted_average_term: f64,
+    pub total_interest: f64,
+    pub total_payment: f64,
+    // Installments received in each month after the loans start, month 1 first
+    pub cash_flows: Vec<f64>,
+}
+
+// Cash received across a set of loans in one calendar month
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectedCashFlow {
+    // First day of the month
+    pub month: NaiveDate,
+    pub principal: f64,
+    // Interest actually paid, so capitalized or skipped interest and fees are left out
+    pub interest: f64,
+    pub total: f64,
+    // Installments that fall in the month
+    pub payments: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Portfolio {
+    pub debts: Vec<Debt>,
+    pub monthly_budget: f64,
+    pub start_date: Option<NaiveDate>,
+    // What the debts and the budget are in
+    pub currency: Currency,
+}
+
+impl Debt {
+    pub fn new(name: &str, balance: f64, apr: f64, minimum_payment: f64) -> Self {
+        Debt { name: name.to_string(), balance, apr, minimum_payment }
+    }
+
+    // Uses the loan's regular installment as the minimum payment
+    pub fn from_loan(name: &str, loan: &Amortization) -> Self {
+        Debt::new(name, loan.balance, loan.config.apr, loan.periodic_payment)
+    }
+}
+
+impl LoanPool {
+    pub fn cash_flow_projection(&self) -> Result<Vec<ProjectedCashFlow>, AmortizationError> {
+        Portfolio::project_cash_flows(&self.loans)
+    }
+
+    pub fn cash_flow_projection_in(&self, reporting: Currency, fx: &dyn FxRateProvider) -> Result<Vec<ProjectedCashFlow>, AmortizationError> {
+        Portfolio::project_cash_flows_in(&self.loans, reporting, fx)
+    }
+
+    // Outstanding principal in each currency the pool's loans are written in
+    pub fn balance_by_currency(&self) -> BTreeMap<Currency, f64> {
+        let mut balances = BTreeMap::new();
+        for loan in &self.loans {
+            *balances.entry(loan.config.currency).or_insert(0.0) += loan.balance;
+        }
+        balances
+    }
+}
+
+impl PayoffPlan {
+    pub fn months(&self) -> u32 {
+        self.schedule.len() as u32
+    }
+
+    pub fn debt_free_date(&self) -> Option<NaiveDate> {
+        self.schedule.last().and_then(|month| month.date)
+    }
+}
+
+// Month after the start in which installment `n` falls, for any payment frequency
+pub(crate) fn month_of(installment_number: u32, periods_per_year: u32) -> usize {
+    (installment_number * 12).div_ceil(periods_per_year) as usize
+}
+
+#[cfg(not(feature = "parallel"))]
+fn build_loans(configs: Vec<CalculatorConfig>) -> Result<Vec<Amortization>, AmortizationError> {
+    configs.into_iter().map(Amortization::from_config).collect()
+}
+
+// Splits the pool into one chunk per available core
+#[cfg(feature = "parallel")]
+fn build_loans(configs: Vec<CalculatorConfig>) -> Result<Vec<Amortization>, AmortizationError> {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = configs.len().div_ceil(threads).max(1);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = configs.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || {
+                chunk.iter().cloned().map(Amortization::from_config).collect::<Result<Vec<_>, _>>()
+            }))
+            .collect();
+        let mut loans = Vec::with_capacity(configs.len());
+        for handle in handles {
+            loans.extend(handle.join().expect("schedule generation panicked")?);
+        }
+        Ok(loans)
+    })
+}
+
+// Months of payments across `loans`, each amount multiplied by `rate` for its loan and date
+fn merge_cash_flows(loans: &[Amortization], rate: impl Fn(&Amortization, NaiveDate) -> Result<f64, AmortizationError>) -> Result<Vec<ProjectedCashFlow>, AmortizationError> {
+    if loans.is_empty() {
+        return Err(AmortizationError::MissingParameter("loans"));
+    }
+    let mut months: BTreeMap<NaiveDate, ProjectedCashFlow> = BTreeMap::new();
+    for loan in loans {
+        for payment in &loan.schedule {
+            let date = payment.date.ok_or(AmortizationError::MissingParameter("start_date"))?;
+            let rate = rate(loan, date)?;
+            let month = date.with_day(1).expect("the first is always a valid day");
+            let entry = months.entry(month).or_insert_with(|| ProjectedCashFlow {
+                month,
+                principal: 0.0,
+                interest: 0.0,
+                total: 0.0,
+                payments: 0,
+            });
+            entry.principal += payment.principal * rate;
+            entry.interest += (payment.installment_amount - payment.principal - payment.fees) * rate;
+            entry.total += payment.installment_amount * rate;
+            entry.payments += 1;
+        }
+    }
+
+    let (Some(&first), Some(&last)) = (months.keys().next(), months.keys().next_back()) else {
+        return Ok(Vec::new());
+    };
+    let mut projection = Vec::with_capacity(months.len());
+    let mut month = first;
+    while month <= last {
+        projection.push(months.remove(&month).unwrap_or(ProjectedCashFlow {
+            month,
+            principal: 0.0,
+            interest: 0.0,
+            total: 0.0,
+            payments: 0,
+        }));
+        month = month + Months::new(1);
+    }
+    Ok(projection)
+}
+
+impl Portfolio {
+    // Builds every schedule in the pool; the first invalid configuration fails the whole batch
+    pub fn generate(configs: Vec<CalculatorConfig>) -> Result<LoanPool, AmortizationError> {
+        if configs.is_empty() {
+            return Err(AmortizationError::MissingParameter("configs"));
+        }
+        let loans = build_loans(configs)?;
+
+        let total_balance: f64 = loans.iter().map(|loan| loan.balance).sum();
+        let weighted = |value: fn(&Amortization) -> f64| {
+            loans.iter().map(|loan| value(loan) * loan.balance).sum::<f64>() / total_balance
+        };
+        let weighted_average_rate = weighted(|loan| loan.config.apr);
+        let weighted_average_term = weighted(|loan| loan.config.loan_term as f64);
+
+        let mut cash_flows = Vec::new();
+        for loan in &loans {
+            let periods_per_year = loan.config.frequency.periods_per_year();
+            for payment in &loan.schedule {
+                let month = month_of(payment.installment_number, periods_per_year).max(1);
+                if cash_flows.len() < month {
+                    cash_flows.resize(month, 0.0);
+                }
+                cash_flows[month - 1] += payment.installment_amount;
+            }
+        }
+
+        Ok(LoanPool {
+            total_balance,
+            weighted_average_rate,
+            weighted_average_term,
+            total_interest: loans.iter().map(|loan| loan.total_interest).sum(),
+            total_payment: loans.iter().map(|loan| loan.total_payment).sum(),
+            cash_flows,
+            loans,
+        })
+    }
+
+    // Merges the schedules month by month on their payment dates, from the earliest payment to
+    // the latest; months in between without any payments are included with zero amounts.
+    // Amounts are added as they are, whatever the loans' currencies
+    pub fn project_cash_flows(loans: &[Amortization]) -> Result<Vec<ProjectedCashFlow>, AmortizationError> {
+        merge_cash_flows(loans, |_, _| Ok(1.0))
+    }
+
+    // As `project_cash_flows`, with each payment converted to `reporting` at the rate on its
+    // due date
+    pub fn project_cash_flows_in(loans: &[Amortization], reporting: Currency, fx: &dyn FxRateProvider) -> Result<Vec<ProjectedCashFlow>, AmortizationError> {
+        merge_cash_flows(loans, |loan, date| {
+            let currency = loan.config.currency;
+            fx.rate(currency, reporting, date)
+                .filter(|rate| *rate > 0.0 && rate.is_finite())
+                .ok_or(AmortizationError::MissingExchangeRate(currency, reporting, date))
+        })
+    }
+
+    pub fn new(debts: Vec<Debt>, monthly_budget: f64) -> Self {
+        Portfolio { debts, monthly_budget, start_date: None, currency: Currency::default() }
+    }
+
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.debts.is_empty() {
+            return Err(AmortizationError::MissingParameter("debts"));
+        }
+        for debt in &self.debts {
+            if debt.balance <= 0.0 || !debt.balance.is_finite() {
+                return Err(AmortizationError::InvalidLoanAmount(debt.balance));
+            }
+            if debt.apr < 0.0 || !debt.apr.is_finite() {
+                return Err(AmortizationError::InvalidInterestRate(debt.apr));
+            }
+            if debt.minimum_payment < 0.0 || !debt.minimum_payment.is_finite() {
+                return Err(AmortizationError::InvalidPaymentAmount(debt.minimum_payment));
+            }
+        }
+        let minimums: f64 = self.debts.iter().map(|d| d.minimum_payment).sum();
+        if self.monthly_budget < minimums || !self.monthly_budget.is_finite() {
+            return Err(AmortizationError::InsufficientPayment(self.monthly_budget));
+        }
+        Ok(())
+    }
+
+    // Order in which surplus budget is directed at the debts
+    fn priority(&self, strategy: PayoffStrategy) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.debts.len()).collect();
+        let debts = &self.debts;
+        match strategy {
+            PayoffStrategy::Snowball => order.sort_by(|&a, &b| {
+                debts[a].balance.total_cmp(&debts[b].balance).then(debts[b].apr.total_cmp(&debts[a].apr))
+            }),
+            PayoffStrategy::Avalanche => order.sort_by(|&a, &b| {
+                debts[b].apr.total_cmp(&debts[a].apr).then(debts[a].balance.total_cmp(&debts[b].balance))
+            }),
+        }
+        order
+    }
+
+    pub fn plan(&self, strategy: PayoffStrategy) -> Result<PayoffPlan, AmortizationError> {
+        self.validate()?;
+        let order = self.priority(strategy);
+        let mut balances: Vec<f64> = self.debts.iter().map(|d| d.balance).collect();
+        let mut payoff_months = vec![0; self.debts.len()];
+        let mut schedule = Vec::new();
+
+        while balances.iter().any(|&b| b > 0.0) {
+            let month = schedule.len() as u32 + 1;
+            if month > MAX_MONTHS {
+                return Err(AmortizationError::NoSolution(format!(
+                    "Debts are not repaid within {} months", MAX_MONTHS
+                )));
+            }
+            let date = self.start_date.and_then(|start| start.checked_add_months(Months::new(month - 1)));
+
+            let interest: Vec<f64> = self.debts.iter().zip(&balances)
+                .map(|(debt, &balance)| self.currency.round(balance * debt.apr / 100.0 / 12.0))
+                .collect();
+            let owed: Vec<f64> = balances.iter().zip(&interest).map(|(b, i)| b + i).collect();
+            let mut paid: Vec<f64> = self.debts.iter().zip(&owed)
+                .map(|(debt, &owed)| debt.minimum_payment.min(owed))
+                .collect();
+
+            // Budget left after minimums, plus the minimums freed up by debts already repaid,
+            // rolls onto the highest priority debt still outstanding
+            let mut surplus = self.currency.round(self.monthly_budget - paid.iter().sum::<f64>());
+            for &index in &order {
+                if surplus <= 0.0 {
+                    break;
+                }
+                let extra = surplus.min(self.currency.round(owed[index] - paid[index]));
+                paid[index] += extra;
+                surplus = self.currency.round(surplus - extra);
+            }
+
+            let mut payments = Vec::new();
+            for (index, &beginning_balance) in balances.iter().enumerate() {
+                if beginning_balance <= 0.0 {
+                    continue;
+                }
+                let remaining_balance = self.currency.round(owed[index] - paid[index]).max(0.0);
+                if remaining_balance == 0.0 {
+                    payoff_months[index] = month;
+                }
+                payments.push(DebtPayment {
+                    debt: index,
+                    payment: Payment {
+                        installment_number: month,
+                        beginning_balance,
+                        interest: interest[index],
+                        principal: paid[index] - interest[index],
+                        date,
+                        remaining_balance,
+                        installment_amount: paid[index],
+                        balloon_amount: 0.0,
+                        capitalized_interest: 0.0,
+                        accrued_interest: 0.0,
+                        deferred_amount: 0.0,
+                        fees: 0.0,
+                        skipped: false,
+                        escrow: None,
+                        ltv: None,
+                        real: None,
+                        accrual: None,
+                    },
+                });
+            }
+            for payment in &payments {
+                balances[payment.debt] = payment.payment.remaining_balance;
+            }
+            schedule.push(PortfolioMonth {
+                month,
+                date,
+                payments,
+                remaining_balance: balances.iter().sum(),
+            });
+        }
+
+        let all_payments = || schedule.iter().flat_map(|month| month.payments.iter().map(|p| &p.payment));
+        let total_interest = all_payments().map(|p| p.interest).sum();
+        let total_paid = all_payments().map(|p| p.installment_amount).sum();
+        Ok(PayoffPlan {
+            strategy,
+            total_interest,
+            total_paid,
+            payoff_months,
+            schedule,
+        })
+    }
+
+    pub fn compare_strategies(&self) -> Result<StrategyComparison, AmortizationError> {
+        let snowball = self.plan(PayoffStrategy::Snowball)?;
+        let avalanche = self.plan(PayoffStrategy::Avalanche)?;
+        Ok(StrategyComparison {
+            interest_saved_by_avalanche: snowball.total_interest - avalanche.total_interest,
+            snowball,
+            avalanche,
+        })
+    }
+}
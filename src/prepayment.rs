@@ -0,0 +1,89 @@
+use chrono::NaiveDate;
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::error::AmortizationError;
+use crate::units::Term;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrepaymentTreatment {
+    // Keep the installment and pay the loan off sooner
+    #[default]
+    ReduceTerm,
+    // Keep the maturity and re-amortize the lower balance into a smaller installment
+    ReducePayment,
+}
+
+// One-time principal paydown made together with installment `period`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Prepayment {
+    pub period: u32,
+    pub amount: f64,
+    pub treatment: PrepaymentTreatment,
+}
+
+impl Prepayment {
+    pub fn new(period: u32, amount: f64, treatment: PrepaymentTreatment) -> Self {
+        Prepayment { period, amount, treatment }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LumpSumScenario {
+    pub treatment: PrepaymentTreatment,
+    pub loan: Amortization,
+    pub interest_saved: f64,
+    // Charged on the lump sum by the loan's prepayment penalty clause
+    pub prepayment_penalty: f64,
+    // Interest saved less the penalty
+    pub net_savings: f64,
+    pub periods_saved: u32,
+    // `periods_saved` as a duration, e.g. "5 years, 3 months"
+    pub time_saved: Term,
+    // Installment due after the lump sum has been applied
+    pub new_payment: f64,
+    pub end_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LumpSumOptions {
+    pub reduce_term: LumpSumScenario,
+    pub reduce_payment: LumpSumScenario,
+}
+
+impl Amortization {
+    pub fn with_lumpsum(&self, period: u32, amount: f64, treatment: PrepaymentTreatment) -> Result<LumpSumScenario, AmortizationError> {
+        let mut prepayments = self.config.prepayments.clone();
+        prepayments.push(Prepayment::new(period, amount, treatment));
+        let loan = Amortization::from_config(CalculatorConfig {
+            prepayments,
+            ..self.config.clone()
+        })?;
+
+        let interest_saved = self.total_interest - loan.total_interest;
+        let prepayment_penalty = self.prepayment_penalty(period, amount);
+        Ok(LumpSumScenario {
+            treatment,
+            interest_saved,
+            prepayment_penalty,
+            net_savings: interest_saved - prepayment_penalty,
+            periods_saved: (self.schedule.len() as u32).saturating_sub(loan.schedule.len() as u32),
+            time_saved: Term::months(self.payoff_term().in_months().saturating_sub(loan.payoff_term().in_months())),
+            new_payment: loan.schedule.get(period as usize).map_or(0.0, |p| p.installment_amount),
+            end_date: loan.end_date,
+            loan,
+        })
+    }
+
+    pub fn lumpsum_options(&self, period: u32, amount: f64) -> Result<LumpSumOptions, AmortizationError> {
+        Ok(LumpSumOptions {
+            reduce_term: self.with_lumpsum(period, amount, PrepaymentTreatment::ReduceTerm)?,
+            reduce_payment: self.with_lumpsum(period, amount, PrepaymentTreatment::ReducePayment)?,
+        })
+    }
+
+    pub fn prepayment_at(&self, installment_number: u32) -> f64 {
+        self.config.prepayments.iter()
+            .filter(|p| p.period == installment_number)
+            .map(|p| p.amount)
+            .sum()
+    }
+}
@@ -0,0 +1,70 @@
+// How far along a loan is at a point in its schedule: the share of principal repaid and of the
+// term elapsed, and the interest paid and still to come. Amounts are rounded to the currency's
+// minor units with the loan's rounding mode and summed as whole units, so they add up to the
+// totals a statement shows
+use crate::calculator::Amortization;
+use crate::error::AmortizationError;
+use crate::money::Money;
+use crate::payment::Payment;
+use crate::valuation::ValuationPoint;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoanProgress {
+    pub installments_paid: u32,
+    pub installments_remaining: u32,
+    pub principal_repaid: f64,
+    pub remaining_balance: f64,
+    // Share of the original balance repaid, in percent; negative while the balance is above it
+    pub percent_principal_repaid: f64,
+    // Share of the term behind the loan, in percent. At a date this counts days from one
+    // period before the first due date to the last; at a period it counts installments
+    pub percent_term_elapsed: f64,
+    pub interest_paid: f64,
+    pub interest_remaining: f64,
+}
+
+impl LoanProgress {
+    pub fn is_complete(&self) -> bool {
+        self.installments_remaining == 0
+    }
+}
+
+impl Amortization {
+    fn interest_money(&self, payments: &[Payment]) -> Money {
+        Money::total(self.config.currency, payments.iter().map(|p| self.money(p.interest)))
+    }
+
+    pub fn progress_at(&self, at: impl Into<ValuationPoint>) -> Result<LoanProgress, AmortizationError> {
+        let periods = self.schedule.len() as u32;
+        let (paid, percent_term_elapsed) = match at.into() {
+            ValuationPoint::Period(period) => {
+                if period > periods {
+                    return Err(AmortizationError::PeriodOutOfRange(period));
+                }
+                (period, if periods == 0 { 100.0 } else { period as f64 / periods as f64 * 100.0 })
+            }
+            ValuationPoint::Date(date) => {
+                let missing = || AmortizationError::MissingParameter("start_date");
+                let origination = self.due_date(0).ok_or_else(missing)?;
+                let end = self.schedule.last().and_then(|p| p.date).ok_or_else(missing)?;
+                let elapsed = (date - origination).num_days() as f64 / (end - origination).num_days().max(1) as f64;
+                (self.payments_made_by(date), elapsed.clamp(0.0, 1.0) * 100.0)
+            }
+        };
+
+        let original = self.money(self.balance);
+        let remaining = self.money(self.balance_after(paid).unwrap_or(0.0));
+        let repaid = original - remaining;
+        let (before, after) = self.schedule.split_at(paid as usize);
+        Ok(LoanProgress {
+            installments_paid: paid,
+            installments_remaining: periods - paid,
+            principal_repaid: repaid.to_f64(),
+            remaining_balance: remaining.to_f64(),
+            percent_principal_repaid: if original.minor() == 0 { 0.0 } else { repaid.minor() as f64 / original.minor() as f64 * 100.0 },
+            percent_term_elapsed,
+            interest_paid: self.interest_money(before).to_f64(),
+            interest_remaining: self.interest_money(after).to_f64(),
+        })
+    }
+}
@@ -0,0 +1,246 @@
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::error::AmortizationError;
+use crate::portfolio::{month_of, LoanPool};
+
+// Annualized (CPR) or monthly (SMM) share of the outstanding balance prepaid, in percent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrepaymentSpeed {
+    Cpr(f64),
+    Smm(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CashFlowAssumptions {
+    pub prepayment: PrepaymentSpeed,
+    // Annualized share of the performing balance defaulting (CDR), in percent
+    pub default_rate: f64,
+    // Share of a defaulted balance that is not recovered, in percent
+    pub loss_severity: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpectedCashFlow {
+    // Installment number for a single loan, month number for a pool
+    pub period: u32,
+    pub date: Option<NaiveDate>,
+    pub beginning_balance: f64,
+    pub interest: f64,
+    pub scheduled_principal: f64,
+    pub prepaid_principal: f64,
+    // Balance defaulting at the start of the period, split into what is recovered and lost
+    pub defaulted_principal: f64,
+    pub recovery: f64,
+    pub loss: f64,
+    // Interest, principal and recoveries received
+    pub cash_flow: f64,
+    pub remaining_balance: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct CashFlowProjection {
+    pub rows: Vec<ExpectedCashFlow>,
+    pub total_interest: f64,
+    pub total_scheduled_principal: f64,
+    pub total_prepaid_principal: f64,
+    pub total_defaults: f64,
+    pub total_recoveries: f64,
+    // Cumulative loss
+    pub total_losses: f64,
+    pub total_cash_flow: f64,
+    // Periods in a year, for converting row periods into years
+    pub periods_per_year: u32,
+}
+
+fn periodic_rate(annual_rate: f64, periods_per_year: u32) -> f64 {
+    1.0 - (1.0 - annual_rate / 100.0).powf(1.0 / periods_per_year as f64)
+}
+
+impl PrepaymentSpeed {
+    pub fn cpr(&self) -> f64 {
+        match *self {
+            PrepaymentSpeed::Cpr(cpr) => cpr,
+            PrepaymentSpeed::Smm(smm) => (1.0 - (1.0 - smm / 100.0).powi(12)) * 100.0,
+        }
+    }
+
+    pub fn smm(&self) -> f64 {
+        match *self {
+            PrepaymentSpeed::Cpr(cpr) => (1.0 - (1.0 - cpr / 100.0).powf(1.0 / 12.0)) * 100.0,
+            PrepaymentSpeed::Smm(smm) => smm,
+        }
+    }
+
+    // Fraction of the balance prepaid each period, for any payment frequency
+    pub(crate) fn periodic_rate(&self, periods_per_year: u32) -> f64 {
+        periodic_rate(self.cpr(), periods_per_year)
+    }
+
+    fn rate(&self) -> f64 {
+        match *self {
+            PrepaymentSpeed::Cpr(rate) | PrepaymentSpeed::Smm(rate) => rate,
+        }
+    }
+}
+
+impl Default for CashFlowAssumptions {
+    fn default() -> Self {
+        CashFlowAssumptions::new()
+    }
+}
+
+impl CashFlowAssumptions {
+    pub fn new() -> Self {
+        CashFlowAssumptions {
+            prepayment: PrepaymentSpeed::Cpr(0.0),
+            default_rate: 0.0,
+            loss_severity: 0.0,
+        }
+    }
+
+    pub fn with_prepayment(mut self, prepayment: PrepaymentSpeed) -> Self {
+        self.prepayment = prepayment;
+        self
+    }
+
+    pub fn with_defaults(mut self, default_rate: f64, loss_severity: f64) -> Self {
+        self.default_rate = default_rate;
+        self.loss_severity = loss_severity;
+        self
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        for rate in [self.prepayment.rate(), self.default_rate, self.loss_severity] {
+            if !(0.0..=100.0).contains(&rate) {
+                return Err(AmortizationError::InvalidProjectionRate(rate));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CashFlowProjection {
+    fn from_rows(rows: Vec<ExpectedCashFlow>, periods_per_year: u32) -> Self {
+        CashFlowProjection {
+            total_interest: rows.iter().map(|r| r.interest).sum(),
+            total_scheduled_principal: rows.iter().map(|r| r.scheduled_principal).sum(),
+            total_prepaid_principal: rows.iter().map(|r| r.prepaid_principal).sum(),
+            total_defaults: rows.iter().map(|r| r.defaulted_principal).sum(),
+            total_recoveries: rows.iter().map(|r| r.recovery).sum(),
+            total_losses: rows.iter().map(|r| r.loss).sum(),
+            total_cash_flow: rows.iter().map(|r| r.cash_flow).sum(),
+            periods_per_year,
+            rows,
+        }
+    }
+
+    // Average time in years until principal is returned, weighting each period by the
+    // scheduled principal, prepayments and recoveries received in it
+    pub fn weighted_average_life(&self) -> f64 {
+        let principal = |row: &ExpectedCashFlow| row.scheduled_principal + row.prepaid_principal + row.recovery;
+        let total: f64 = self.rows.iter().map(principal).sum();
+        if total == 0.0 {
+            return 0.0;
+        }
+        self.rows.iter()
+            .map(|row| row.period as f64 / self.periods_per_year as f64 * principal(row))
+            .sum::<f64>() / total
+    }
+}
+
+impl Amortization {
+    // Expected cash flows when a share of the borrowers default at the start of every period
+    // and a share of the rest prepay in full at its end. The surviving share pays exactly as
+    // scheduled, so each scheduled row is scaled by it
+    pub fn project(&self, assumptions: &CashFlowAssumptions) -> Result<CashFlowProjection, AmortizationError> {
+        assumptions.validate()?;
+        let periods_per_year = self.config.frequency.periods_per_year();
+        let prepayment_rate = assumptions.prepayment.periodic_rate(periods_per_year);
+        let default_rate = periodic_rate(assumptions.default_rate, periods_per_year);
+        let mut surviving = 1.0;
+        let rows = self.schedule.iter().map(|payment| {
+            let beginning_balance = payment.beginning_balance * surviving;
+            let defaulted_principal = beginning_balance * default_rate;
+            let loss = defaulted_principal * assumptions.loss_severity / 100.0;
+            let recovery = defaulted_principal - loss;
+            surviving *= 1.0 - default_rate;
+
+            let interest = (payment.installment_amount - payment.principal) * surviving;
+            let scheduled_principal = payment.principal * surviving;
+            let balance = payment.remaining_balance * surviving;
+            let prepaid_principal = balance * prepayment_rate;
+            let row = ExpectedCashFlow {
+                period: payment.installment_number,
+                date: payment.date,
+                beginning_balance,
+                interest,
+                scheduled_principal,
+                prepaid_principal,
+                defaulted_principal,
+                recovery,
+                loss,
+                cash_flow: interest + scheduled_principal + prepaid_principal + recovery,
+                remaining_balance: balance - prepaid_principal,
+            };
+            surviving *= 1.0 - prepayment_rate;
+            row
+        }).collect();
+        Ok(CashFlowProjection::from_rows(rows, periods_per_year))
+    }
+}
+
+impl LoanPool {
+    // Projects every loan and merges them by month after the start
+    pub fn project(&self, assumptions: &CashFlowAssumptions) -> Result<CashFlowProjection, AmortizationError> {
+        let projections = self.loans.iter().map(|loan| loan.project(assumptions)).collect::<Result<Vec<_>, _>>()?;
+        let months = self.loans.iter().zip(&projections)
+            .filter_map(|(loan, projection)| {
+                let periods_per_year = loan.config.frequency.periods_per_year();
+                projection.rows.last().map(|row| month_of(row.period, periods_per_year))
+            })
+            .max()
+            .unwrap_or(0);
+
+        // Each loan's balance carries over the months in which it has no installment
+        let mut balances: Vec<f64> = self.loans.iter().map(|loan| loan.balance).collect();
+        let mut cursors = vec![0; self.loans.len()];
+        let mut rows = Vec::with_capacity(months);
+        for month in 1..=months {
+            let mut row = ExpectedCashFlow {
+                period: month as u32,
+                date: None,
+                beginning_balance: balances.iter().sum(),
+                interest: 0.0,
+                scheduled_principal: 0.0,
+                prepaid_principal: 0.0,
+                defaulted_principal: 0.0,
+                recovery: 0.0,
+                loss: 0.0,
+                cash_flow: 0.0,
+                remaining_balance: 0.0,
+            };
+            for (index, (loan, projection)) in self.loans.iter().zip(&projections).enumerate() {
+                let periods_per_year = loan.config.frequency.periods_per_year();
+                while let Some(loan_row) = projection.rows.get(cursors[index])
+                    .filter(|r| month_of(r.period, periods_per_year) == month) {
+                    row.date = match (row.date, loan_row.date) {
+                        (Some(earliest), Some(date)) => Some(earliest.min(date)),
+                        (earliest, date) => earliest.or(date),
+                    };
+                    row.interest += loan_row.interest;
+                    row.scheduled_principal += loan_row.scheduled_principal;
+                    row.prepaid_principal += loan_row.prepaid_principal;
+                    row.defaulted_principal += loan_row.defaulted_principal;
+                    row.recovery += loan_row.recovery;
+                    row.loss += loan_row.loss;
+                    row.cash_flow += loan_row.cash_flow;
+                    balances[index] = loan_row.remaining_balance;
+                    cursors[index] += 1;
+                }
+            }
+            row.remaining_balance = balances.iter().sum();
+            rows.push(row);
+        }
+        Ok(CashFlowProjection::from_rows(rows, 12))
+    }
+}
@@ -0,0 +1,27 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PropertyValue {
+    pub value: f64,
+    // Annual appreciation in percent, negative for depreciation
+    pub annual_appreciation: f64,
+}
+
+impl PropertyValue {
+    pub fn new(value: f64) -> Self {
+        PropertyValue { value, annual_appreciation: 0.0 }
+    }
+
+    pub fn appreciating(mut self, annual_appreciation: f64) -> Self {
+        self.annual_appreciation = annual_appreciation;
+        self
+    }
+
+    // Estimated value `period` periods after origination
+    pub fn value_at(&self, period: u32, periods_per_year: u32) -> f64 {
+        let years = period as f64 / periods_per_year as f64;
+        self.value * (1.0 + self.annual_appreciation / 100.0).powf(years)
+    }
+
+    pub fn ltv(&self, balance: f64, period: u32, periods_per_year: u32) -> f64 {
+        balance / self.value_at(period, periods_per_year) * 100.0
+    }
+}
@@ -0,0 +1,75 @@
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::payment::Payment;
+
+impl Amortization {
+    // Balance once `periods` installments have been paid
+    pub fn balance_after(&self, periods: u32) -> Option<f64> {
+        match periods {
+            0 => Some(self.balance),
+            n => self.schedule.get(n as usize - 1).map(|p| p.remaining_balance),
+        }
+    }
+
+    // Balance outstanding at the end of `date`, counting installments due on or before it.
+    // None when the loan has no payment dates.
+    pub fn balance_at(&self, date: NaiveDate) -> Option<f64> {
+        self.start_date?;
+        self.balance_after(self.payments_made_by(date))
+    }
+
+    // Amount needed to close the loan on `date`: the balance plus interest accrued since the
+    // last installment. See `payoff_quote` for the itemised figures
+    pub fn payoff_amount(&self, date: NaiveDate) -> Option<f64> {
+        self.payoff_quote(date).ok().map(|quote| quote.total)
+    }
+
+    // Installment `n`, counting from 1
+    pub fn nth_payment(&self, n: u32) -> Option<&Payment> {
+        self.schedule.get((n as usize).checked_sub(1)?)
+    }
+
+    // Installments `from` through `to`, inclusive; clamped to the schedule
+    pub fn payments_in_periods(&self, from: u32, to: u32) -> &[Payment] {
+        let start = (from.max(1) as usize - 1).min(self.schedule.len());
+        let end = (to as usize).clamp(start, self.schedule.len());
+        &self.schedule[start..end]
+    }
+
+    // Installments due from `from` through `to`, inclusive. Empty when the loan has no
+    // payment dates
+    pub fn schedule_between(&self, from: NaiveDate, to: NaiveDate) -> &[Payment] {
+        if self.start_date.is_none() {
+            return &[];
+        }
+        let start = self.payments_made_by(from.pred_opt().unwrap_or(from)) as usize;
+        let end = (self.payments_made_by(to) as usize).max(start);
+        &self.schedule[start..end]
+    }
+
+    // Installments due in a calendar year, or in the 1-based loan year when the schedule
+    // has no dates
+    pub fn payments_in_year(&self, year: i32) -> &[Payment] {
+        match (self.start_date, NaiveDate::from_ymd_opt(year, 1, 1), NaiveDate::from_ymd_opt(year, 12, 31)) {
+            (Some(_), Some(first), Some(last)) => self.schedule_between(first, last),
+            (Some(_), _, _) => &[],
+            (None, _, _) if year < 1 => &[],
+            (None, _, _) => {
+                let periods_per_year = self.config.frequency.periods_per_year();
+                let first = (year as u32 - 1).saturating_mul(periods_per_year).saturating_add(1);
+                self.payments_in_periods(first, first.saturating_add(periods_per_year - 1))
+            }
+        }
+    }
+
+    // The first installment due on or after `date`
+    pub fn next_payment_due(&self, date: NaiveDate) -> Option<&Payment> {
+        self.start_date?;
+        self.schedule.get(self.payments_made_by(date.pred_opt()?) as usize)
+    }
+
+    // Due dates only increase, so the count is a binary search
+    pub(crate) fn payments_made_by(&self, date: NaiveDate) -> u32 {
+        self.schedule.partition_point(|p| p.date.is_some_and(|d| d <= date)) as u32
+    }
+}
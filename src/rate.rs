@@ -0,0 +1,237 @@
+use crate::frequency::PaymentFrequency;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RatePeriod {
+    pub start_period: u32,
+    pub apr: f64,
+}
+
+impl RatePeriod {
+    pub fn new(start_period: u32, apr: f64) -> Self {
+        RatePeriod { start_period, apr }
+    }
+
+    pub fn periodic_rate(&self, frequency: PaymentFrequency) -> f64 {
+        self.apr / 100.0 / frequency.periods_per_year() as f64
+    }
+
+    pub fn periodic_rate_with(&self, frequency: PaymentFrequency, convention: RateConvention) -> f64 {
+        convention.nominal_apr(self.apr, frequency) / 100.0 / frequency.periods_per_year() as f64
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateConvention {
+    // Nominal rate compounded once per payment period
+    #[default]
+    Nominal,
+    // Nominal rate compounded at its own frequency, independent of the payment frequency
+    NominalCompounded(PaymentFrequency),
+    // Effective annual rate
+    EffectiveAnnual,
+}
+
+impl RateConvention {
+    // Equivalent nominal annual rate, in percent, compounded at the payment frequency
+    pub fn nominal_apr(&self, apr: f64, frequency: PaymentFrequency) -> f64 {
+        let payment_periods = frequency.periods_per_year();
+        match self {
+            RateConvention::Nominal => apr,
+            RateConvention::NominalCompounded(compounding) if *compounding == frequency => apr,
+            RateConvention::NominalCompounded(compounding) => {
+                effective_to_nominal(nominal_to_effective(apr, compounding.periods_per_year()), payment_periods)
+            }
+            RateConvention::EffectiveAnnual => effective_to_nominal(apr, payment_periods),
+        }
+    }
+
+    pub fn effective_annual_rate(&self, apr: f64, frequency: PaymentFrequency) -> f64 {
+        nominal_to_effective(self.nominal_apr(apr, frequency), frequency.periods_per_year())
+    }
+}
+
+// Statutory compounding conventions for quoted mortgage rates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Jurisdiction {
+    // Quoted rates compound once per payment period
+    #[default]
+    UnitedStates,
+    // The Interest Act has fixed-rate mortgages quote a rate compounded semi-annually,
+    // whatever the payment frequency. Variable-rate mortgages usually compound monthly and
+    // are better modelled with the nominal convention
+    Canada,
+}
+
+impl Jurisdiction {
+    pub fn rate_convention(&self) -> RateConvention {
+        match self {
+            Jurisdiction::UnitedStates => RateConvention::Nominal,
+            Jurisdiction::Canada => RateConvention::NominalCompounded(PaymentFrequency::SemiAnnually),
+        }
+    }
+}
+
+// Both helpers take and return rates in percent
+pub fn nominal_to_effective(nominal: f64, compounding_periods: u32) -> f64 {
+    let m = compounding_periods as f64;
+    ((1.0 + nominal / 100.0 / m).powf(m) - 1.0) * 100.0
+}
+
+pub fn effective_to_nominal(effective: f64, compounding_periods: u32) -> f64 {
+    let m = compounding_periods as f64;
+    ((1.0 + effective / 100.0).powf(1.0 / m) - 1.0) * m * 100.0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PaymentCap {
+    // Largest change of the installment allowed at a rate reset, in percent
+    pub max_change_percent: f64,
+    // Force a fully amortizing recast once the balance exceeds this percentage of the original
+    pub recast_balance_percent: Option<f64>,
+}
+
+impl PaymentCap {
+    pub fn new(max_change_percent: f64) -> Self {
+        PaymentCap { max_change_percent, recast_balance_percent: None }
+    }
+
+    pub fn with_recast_at(mut self, recast_balance_percent: f64) -> Self {
+        self.recast_balance_percent = Some(recast_balance_percent);
+        self
+    }
+
+    pub fn limit(&self, previous_payment: f64, new_payment: f64) -> f64 {
+        let change = self.max_change_percent / 100.0;
+        new_payment.clamp(previous_payment * (1.0 - change), previous_payment * (1.0 + change))
+    }
+}
+
+// Limits on how far an adjustable rate may move at its resets, in percentage points
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RateCaps {
+    // Largest move at the first reset, and at each one after it
+    pub initial_cap: Option<f64>,
+    pub periodic_cap: Option<f64>,
+    // Highest the rate may go above the initial rate
+    pub lifetime_cap: Option<f64>,
+    // Lowest rate the loan may carry
+    pub floor: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimit {
+    InitialCap,
+    PeriodicCap,
+    LifetimeCap,
+    Floor,
+}
+
+// The rate a reset called for and the rate charged after the caps
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateAdjustment {
+    pub period: u32,
+    pub uncapped_apr: f64,
+    pub apr: f64,
+    // Limit that held the rate back, if any
+    pub limit: Option<RateLimit>,
+}
+
+impl RateAdjustment {
+    pub fn is_capped(&self) -> bool {
+        self.limit.is_some()
+    }
+}
+
+impl RateCaps {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The usual ARM structure, e.g. 2/2/5 or 5/2/5
+    pub fn structure(initial: f64, periodic: f64, lifetime: f64) -> Self {
+        RateCaps { initial_cap: Some(initial), periodic_cap: Some(periodic), lifetime_cap: Some(lifetime), floor: None }
+    }
+
+    pub fn with_initial_cap(mut self, initial_cap: f64) -> Self {
+        self.initial_cap = Some(initial_cap);
+        self
+    }
+
+    pub fn with_periodic_cap(mut self, periodic_cap: f64) -> Self {
+        self.periodic_cap = Some(periodic_cap);
+        self
+    }
+
+    pub fn with_lifetime_cap(mut self, lifetime_cap: f64) -> Self {
+        self.lifetime_cap = Some(lifetime_cap);
+        self
+    }
+
+    pub fn with_floor(mut self, floor: f64) -> Self {
+        self.floor = Some(floor);
+        self
+    }
+
+    // The first cap or floor that is negative or not a number
+    pub(crate) fn invalid_value(&self) -> Option<f64> {
+        [self.initial_cap, self.periodic_cap, self.lifetime_cap, self.floor]
+            .into_iter()
+            .flatten()
+            .find(|value| *value < 0.0 || !value.is_finite())
+    }
+
+    // One adjustment per entry of `uncapped`, the first being the initial rate. Each reset
+    // moves from the rate actually charged before it, so a capped shortfall is made up at
+    // later resets
+    pub fn apply(&self, uncapped: &[RatePeriod]) -> Vec<RateAdjustment> {
+        let Some(first) = uncapped.first() else { return Vec::new() };
+        let floor = self.floor.unwrap_or(0.0);
+        let initial = first.apr.max(floor);
+        let mut current = initial;
+        let mut adjustments = vec![RateAdjustment {
+            period: first.start_period,
+            uncapped_apr: first.apr,
+            apr: initial,
+            limit: (initial != first.apr).then_some(RateLimit::Floor),
+        }];
+        for (n, rate) in uncapped.iter().skip(1).enumerate() {
+            let (cap, cap_limit) = if n == 0 {
+                (self.initial_cap, RateLimit::InitialCap)
+            } else {
+                (self.periodic_cap, RateLimit::PeriodicCap)
+            };
+            let mut apr = rate.apr;
+            let mut limit = None;
+            if let Some(cap) = cap {
+                if (apr - current).abs() > cap {
+                    apr = apr.clamp(current - cap, current + cap);
+                    limit = Some(cap_limit);
+                }
+            }
+            if let Some(lifetime) = self.lifetime_cap {
+                if apr > initial + lifetime {
+                    apr = initial + lifetime;
+                    limit = Some(RateLimit::LifetimeCap);
+                }
+            }
+            if apr < floor {
+                apr = floor;
+                limit = Some(RateLimit::Floor);
+            }
+            adjustments.push(RateAdjustment { period: rate.start_period, uncapped_apr: rate.apr, apr, limit });
+            current = apr;
+        }
+        adjustments
+    }
+
+    // The capped rates as a rate schedule, leaving out resets that don't change the rate
+    pub fn capped_schedule(&self, uncapped: &[RatePeriod]) -> Vec<RatePeriod> {
+        let mut schedule: Vec<RatePeriod> = Vec::new();
+        for adjustment in self.apply(uncapped) {
+            if schedule.last().is_none_or(|last| (last.apr - adjustment.apr).abs() > 1e-9) {
+                schedule.push(RatePeriod::new(adjustment.period, adjustment.apr));
+            }
+        }
+        schedule
+    }
+}
@@ -0,0 +1,79 @@
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::error::AmortizationError;
+use crate::payment::Payment;
+
+#[derive(Debug, Clone)]
+pub struct RefinanceAnalysis {
+    pub remaining_balance: f64,
+    pub current_payment: f64,
+    pub new_payment: f64,
+    pub monthly_savings: f64,
+    // Charged by the existing loan's prepayment penalty clause for paying it off, counted
+    // with the closing costs
+    pub prepayment_penalty: f64,
+    pub break_even_month: Option<u32>,
+    pub remaining_interest: f64,
+    pub new_interest: f64,
+    // Positive when the new loan costs less interest than keeping the existing one
+    pub lifetime_interest_difference: f64,
+    pub net_savings: f64,
+    pub new_loan: Amortization,
+    pub blended_schedule: Vec<Payment>,
+}
+
+pub struct Refinance;
+
+impl Refinance {
+    pub fn analyze(existing: &Amortization, current_period: u32, new_terms: CalculatorConfig, closing_costs: f64) -> Result<RefinanceAnalysis, AmortizationError> {
+        if current_period as usize >= existing.schedule.len() {
+            return Err(AmortizationError::PeriodOutOfRange(current_period));
+        }
+        if closing_costs < 0.0 || !closing_costs.is_finite() {
+            return Err(AmortizationError::InvalidUpfrontCost(closing_costs));
+        }
+
+        let (paid, remaining) = existing.schedule.split_at(current_period as usize);
+        let remaining_balance = remaining[0].beginning_balance;
+        let current_payment = remaining[0].installment_amount;
+        let remaining_interest: f64 = remaining.iter().map(|p| p.interest).sum();
+
+        let new_loan = Amortization::from_config(new_terms)?;
+        let new_payment = new_loan.periodic_payment;
+        let monthly_savings = current_payment - new_payment;
+        let prepayment_penalty = existing.prepayment_penalty(current_period, remaining_balance);
+        let upfront_cost = closing_costs + prepayment_penalty;
+        let break_even_month = if monthly_savings > 0.0 {
+            Some((upfront_cost / monthly_savings).ceil() as u32)
+        } else {
+            None
+        };
+        let lifetime_interest_difference = remaining_interest - new_loan.total_interest;
+
+        let mut blended_schedule = paid.to_vec();
+        blended_schedule.extend(new_loan.schedule.iter().map(|payment| Payment {
+            installment_number: payment.installment_number + current_period,
+            ..payment.clone()
+        }));
+
+        Ok(RefinanceAnalysis {
+            remaining_balance,
+            current_payment,
+            new_payment,
+            monthly_savings,
+            prepayment_penalty,
+            break_even_month,
+            remaining_interest,
+            new_interest: new_loan.total_interest,
+            lifetime_interest_difference,
+            net_savings: lifetime_interest_difference - upfront_cost,
+            new_loan,
+            blended_schedule,
+        })
+    }
+
+    pub fn payoff_balance(existing: &Amortization, current_period: u32) -> Result<f64, AmortizationError> {
+        existing.schedule.get(current_period as usize)
+            .map(|p| p.beginning_balance)
+            .ok_or(AmortizationError::PeriodOutOfRange(current_period))
+    }
+}
@@ -0,0 +1,184 @@
+use crate::calculator::Amortization;
+use crate::error::AmortizationError;
+use crate::property::PropertyValue;
+
+// Rates are annual percentages; property tax and maintenance are charged on the home's
+// value at the start of each year, and rent steps up once a year.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RentVsBuy {
+    pub home_price: f64,
+    pub down_payment: f64,
+    pub apr: f64,
+    pub term_months: u32,
+    pub closing_costs: f64,
+    pub property_tax_rate: f64,
+    pub annual_insurance: f64,
+    pub maintenance_rate: f64,
+    pub appreciation: f64,
+    pub selling_cost_rate: f64,
+    pub monthly_rent: f64,
+    pub rent_growth: f64,
+    // Return the down payment and closing costs would have earned if invested instead
+    pub investment_return: f64,
+    pub years: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RentVsBuyYear {
+    pub year: u32,
+    pub home_value: f64,
+    pub loan_balance: f64,
+    // Sale price less selling costs and the loan payoff
+    pub home_equity: f64,
+    pub cumulative_ownership_cost: f64,
+    pub cumulative_rent: f64,
+    // Money spent owning, less the equity recovered by selling at the end of the year
+    pub net_buy_cost: f64,
+    // Rent paid, less what the down payment and closing costs earned invested
+    pub net_rent_cost: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RentVsBuyAnalysis {
+    pub loan: Amortization,
+    pub years: Vec<RentVsBuyYear>,
+    // First year at whose end buying has cost no more than renting
+    pub break_even_year: Option<u32>,
+}
+
+impl RentVsBuy {
+    pub fn new(home_price: f64, down_payment: f64, apr: f64, term_months: u32, monthly_rent: f64) -> Self {
+        RentVsBuy {
+            home_price,
+            down_payment,
+            apr,
+            term_months,
+            closing_costs: 0.0,
+            property_tax_rate: 0.0,
+            annual_insurance: 0.0,
+            maintenance_rate: 0.0,
+            appreciation: 0.0,
+            selling_cost_rate: 0.0,
+            monthly_rent,
+            rent_growth: 0.0,
+            investment_return: 0.0,
+            years: term_months / 12,
+        }
+    }
+
+    pub fn with_closing_costs(mut self, closing_costs: f64) -> Self {
+        self.closing_costs = closing_costs;
+        self
+    }
+
+    pub fn with_property_tax_rate(mut self, property_tax_rate: f64) -> Self {
+        self.property_tax_rate = property_tax_rate;
+        self
+    }
+
+    pub fn with_insurance(mut self, annual_insurance: f64) -> Self {
+        self.annual_insurance = annual_insurance;
+        self
+    }
+
+    pub fn with_maintenance_rate(mut self, maintenance_rate: f64) -> Self {
+        self.maintenance_rate = maintenance_rate;
+        self
+    }
+
+    pub fn with_appreciation(mut self, appreciation: f64) -> Self {
+        self.appreciation = appreciation;
+        self
+    }
+
+    pub fn with_selling_cost_rate(mut self, selling_cost_rate: f64) -> Self {
+        self.selling_cost_rate = selling_cost_rate;
+        self
+    }
+
+    pub fn with_rent_growth(mut self, rent_growth: f64) -> Self {
+        self.rent_growth = rent_growth;
+        self
+    }
+
+    pub fn with_investment_return(mut self, investment_return: f64) -> Self {
+        self.investment_return = investment_return;
+        self
+    }
+
+    pub fn with_years(mut self, years: u32) -> Self {
+        self.years = years;
+        self
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.home_price <= 0.0 || !self.home_price.is_finite() {
+            return Err(AmortizationError::InvalidPropertyValue(self.home_price));
+        }
+        if self.down_payment < 0.0 || self.down_payment >= self.home_price {
+            return Err(AmortizationError::InvalidPaymentAmount(self.down_payment));
+        }
+        for amount in [self.closing_costs, self.annual_insurance, self.monthly_rent] {
+            if amount < 0.0 || !amount.is_finite() {
+                return Err(AmortizationError::InvalidPaymentAmount(amount));
+            }
+        }
+        for rate in [self.property_tax_rate, self.maintenance_rate, self.selling_cost_rate] {
+            if rate < 0.0 || !rate.is_finite() {
+                return Err(AmortizationError::InvalidInterestRate(rate));
+            }
+        }
+        for rate in [self.appreciation, self.rent_growth, self.investment_return] {
+            if rate <= -100.0 || !rate.is_finite() {
+                return Err(AmortizationError::InvalidInterestRate(rate));
+            }
+        }
+        if self.years == 0 {
+            return Err(AmortizationError::InvalidPeriods(self.years));
+        }
+        Ok(())
+    }
+
+    pub fn compare(&self) -> Result<RentVsBuyAnalysis, AmortizationError> {
+        self.validate()?;
+        let loan = Amortization::new(self.home_price - self.down_payment, self.apr, self.term_months, None)?;
+        let property = PropertyValue::new(self.home_price).appreciating(self.appreciation);
+        let upfront = self.down_payment + self.closing_costs;
+
+        let mut ownership_cost = upfront;
+        let mut cumulative_rent = 0.0;
+        let years: Vec<RentVsBuyYear> = (1..=self.years).map(|year| {
+            let start_value = property.value_at(year - 1, 1);
+            let mortgage: f64 = loan.schedule.iter()
+                .skip((year as usize - 1) * 12)
+                .take(12)
+                .map(|p| p.installment_amount)
+                .sum();
+            ownership_cost += mortgage
+                + start_value * (self.property_tax_rate + self.maintenance_rate) / 100.0
+                + self.annual_insurance;
+            cumulative_rent += self.monthly_rent * 12.0 * (1.0 + self.rent_growth / 100.0).powi(year as i32 - 1);
+
+            let home_value = property.value_at(year, 1);
+            let loan_balance = loan.balance_after(year * 12).unwrap_or(0.0);
+            let home_equity = home_value * (1.0 - self.selling_cost_rate / 100.0) - loan_balance;
+            let investment_gain = upfront * ((1.0 + self.investment_return / 100.0).powi(year as i32) - 1.0);
+            RentVsBuyYear {
+                year,
+                home_value,
+                loan_balance,
+                home_equity,
+                cumulative_ownership_cost: ownership_cost,
+                cumulative_rent,
+                net_buy_cost: ownership_cost - home_equity,
+                net_rent_cost: cumulative_rent - investment_gain,
+            }
+        }).collect();
+
+        Ok(RentVsBuyAnalysis {
+            break_even_year: years.iter().find(|y| y.net_buy_cost <= y.net_rent_cost).map(|y| y.year),
+            years,
+            loan,
+        })
+    }
+}
@@ -0,0 +1,115 @@
+// Markdown and HTML renderings of the loan summary and schedule for report generators
+use crate::calculator::Amortization;
+#[cfg(feature = "i18n")]
+use crate::i18n::{Label, StringTable};
+use crate::table::{Column, FormatOptions};
+
+pub(crate) const SUMMARY_LABELS: [&str; 8] = [
+    "Loan Amount", "APR", "Periods", "Periodic Payment", "Total Payment", "Total Interest", "Start Date", "End Date",
+];
+
+// The words a report is written with: summary field names, then column headers
+struct ReportText<'a> {
+    field: &'a str,
+    value: &'a str,
+    summary: [&'a str; 8],
+    headers: Vec<&'a str>,
+}
+
+impl<'a> ReportText<'a> {
+    fn english(options: &FormatOptions) -> Self {
+        ReportText { field: "Field", value: "Value", summary: SUMMARY_LABELS, headers: options.columns.iter().map(Column::header).collect() }
+    }
+
+    #[cfg(feature = "i18n")]
+    fn translated(options: &FormatOptions, labels: &'a dyn StringTable) -> Self {
+        ReportText {
+            field: labels.text(Label::Field),
+            value: labels.text(Label::Value),
+            summary: Label::SUMMARY.map(|label| labels.text(label)),
+            headers: options.columns.iter().map(|&column| labels.text(Label::from(column))).collect(),
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+impl Amortization {
+    fn summary_values(&self, options: &FormatOptions) -> [String; 8] {
+        [
+            options.format_amount(self.balance),
+            format!("{:.3}%", self.config.apr),
+            self.periods.to_string(),
+            options.format_amount(self.periodic_payment),
+            options.format_amount(self.total_payment),
+            options.format_amount(self.total_interest),
+            options.format_date(self.start_date),
+            options.format_date(self.end_date),
+        ]
+    }
+
+    pub fn to_markdown(&self) -> String {
+        self.to_markdown_with(&FormatOptions::default())
+    }
+
+    pub fn to_markdown_with(&self, options: &FormatOptions) -> String {
+        self.markdown(options, &ReportText::english(options))
+    }
+
+    #[cfg(feature = "i18n")]
+    pub fn to_markdown_in(&self, options: &FormatOptions, labels: &dyn StringTable) -> String {
+        self.markdown(options, &ReportText::translated(options, labels))
+    }
+
+    fn markdown(&self, options: &FormatOptions, text: &ReportText) -> String {
+        let mut markdown = format!("| {} | {} |\n| --- | ---: |\n", text.field, text.value);
+        for (field, value) in text.summary.iter().zip(self.summary_values(options)) {
+            markdown.push_str(&format!("| {} | {} |\n", field, value));
+        }
+        markdown.push('\n');
+
+        markdown.push_str(&format!("| {} |\n", text.headers.join(" | ")));
+        let alignment: Vec<&str> = options.columns.iter().map(|_| "---:").collect();
+        markdown.push_str(&format!("| {} |\n", alignment.join(" | ")));
+        for row in self.table_cells(options, |amount| options.format_amount(amount), |date| options.format_date(date)) {
+            markdown.push_str(&format!("| {} |\n", row.join(" | ")));
+        }
+        markdown
+    }
+
+    pub fn to_html(&self) -> String {
+        self.to_html_with(&FormatOptions::default())
+    }
+
+    pub fn to_html_with(&self, options: &FormatOptions) -> String {
+        self.html(options, &ReportText::english(options))
+    }
+
+    #[cfg(feature = "i18n")]
+    pub fn to_html_in(&self, options: &FormatOptions, labels: &dyn StringTable) -> String {
+        self.html(options, &ReportText::translated(options, labels))
+    }
+
+    fn html(&self, options: &FormatOptions, text: &ReportText) -> String {
+        let mut html = String::from("<table class=\"amortization-summary\">\n<tbody>\n");
+        for (field, value) in text.summary.iter().zip(self.summary_values(options)) {
+            html.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", escape_html(field), escape_html(&value)));
+        }
+        html.push_str("</tbody>\n</table>\n<table class=\"amortization-schedule\">\n<thead>\n<tr>");
+        for header in &text.headers {
+            html.push_str(&format!("<th>{}</th>", escape_html(header)));
+        }
+        html.push_str("</tr>\n</thead>\n<tbody>\n");
+        for row in self.table_cells(options, |amount| options.format_amount(amount), |date| options.format_date(date)) {
+            html.push_str("<tr>");
+            for cell in row {
+                html.push_str(&format!("<td>{}</td>", escape_html(&cell)));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</tbody>\n</table>\n");
+        html
+    }
+}
@@ -0,0 +1,142 @@
+use chrono::NaiveDate;
+use crate::calendar::DateRollConvention;
+use crate::error::AmortizationError;
+use crate::frequency::PaymentFrequency;
+use crate::money::Currency;
+use crate::property::PropertyValue;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReverseMortgage {
+    // Amount owed at closing: any lump sum taken plus financed closing costs
+    pub initial_balance: f64,
+    pub apr: f64,
+    // Annual mortgage insurance premium in percent of the balance, accrued like interest
+    pub mortgage_insurance_rate: f64,
+    pub monthly_draw: f64,
+    pub property: PropertyValue,
+    pub months: u32,
+    pub start_date: Option<NaiveDate>,
+    pub currency: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReverseMortgageRow {
+    pub month: u32,
+    pub date: Option<NaiveDate>,
+    pub draw: f64,
+    pub interest: f64,
+    pub mortgage_insurance: f64,
+    pub balance: f64,
+    pub home_value: f64,
+    // Home value less the balance, negative once the loan is under water
+    pub equity: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReverseMortgageProjection {
+    pub rows: Vec<ReverseMortgageRow>,
+    // First month in which the balance reaches the projected home value
+    pub crossover_month: Option<u32>,
+    pub crossover_date: Option<NaiveDate>,
+    pub total_drawn: f64,
+    pub total_interest: f64,
+    pub total_mortgage_insurance: f64,
+    pub final_balance: f64,
+}
+
+impl ReverseMortgage {
+    pub fn new(initial_balance: f64, apr: f64, property: PropertyValue, months: u32) -> Self {
+        ReverseMortgage {
+            initial_balance,
+            apr,
+            mortgage_insurance_rate: 0.0,
+            monthly_draw: 0.0,
+            property,
+            months,
+            start_date: None,
+            currency: Currency::default(),
+        }
+    }
+
+    pub fn with_monthly_draw(mut self, monthly_draw: f64) -> Self {
+        self.monthly_draw = monthly_draw;
+        self
+    }
+
+    pub fn with_mortgage_insurance(mut self, annual_rate: f64) -> Self {
+        self.mortgage_insurance_rate = annual_rate;
+        self
+    }
+
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.initial_balance < 0.0 || !self.initial_balance.is_finite() {
+            return Err(AmortizationError::InvalidLoanAmount(self.initial_balance));
+        }
+        if self.apr < 0.0 || !self.apr.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(self.apr));
+        }
+        if self.mortgage_insurance_rate < 0.0 || !self.mortgage_insurance_rate.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(self.mortgage_insurance_rate));
+        }
+        if self.monthly_draw < 0.0 || !self.monthly_draw.is_finite() {
+            return Err(AmortizationError::InvalidPaymentAmount(self.monthly_draw));
+        }
+        if self.initial_balance == 0.0 && self.monthly_draw == 0.0 {
+            return Err(AmortizationError::InvalidLoanAmount(self.initial_balance));
+        }
+        if self.property.value <= 0.0 || self.property.annual_appreciation <= -100.0 {
+            return Err(AmortizationError::InvalidPropertyValue(self.property.value));
+        }
+        if self.months == 0 {
+            return Err(AmortizationError::InvalidPeriods(self.months));
+        }
+        Ok(())
+    }
+
+    // No payments are made: each month's draw is added to the balance, which then grows by
+    // the month's interest and mortgage insurance
+    pub fn projection(&self) -> Result<ReverseMortgageProjection, AmortizationError> {
+        self.validate()?;
+        let mut balance = self.initial_balance;
+        let rows: Vec<ReverseMortgageRow> = (1..=self.months).map(|month| {
+            let drawn_balance = balance + self.monthly_draw;
+            let interest = self.currency.round(drawn_balance * self.apr / 100.0 / 12.0);
+            let mortgage_insurance = self.currency.round(drawn_balance * self.mortgage_insurance_rate / 100.0 / 12.0);
+            balance = self.currency.round(drawn_balance + interest + mortgage_insurance);
+            let home_value = self.currency.round(self.property.value_at(month, 12));
+            ReverseMortgageRow {
+                month,
+                date: self.start_date.and_then(|start| {
+                    PaymentFrequency::Monthly.due_date(start, month as i32 - 1, DateRollConvention::SameDay)
+                }),
+                draw: self.monthly_draw,
+                interest,
+                mortgage_insurance,
+                balance,
+                home_value,
+                equity: self.currency.round(home_value - balance),
+            }
+        }).collect();
+
+        let crossover = rows.iter().find(|row| row.balance >= row.home_value);
+        Ok(ReverseMortgageProjection {
+            crossover_month: crossover.map(|row| row.month),
+            crossover_date: crossover.and_then(|row| row.date),
+            total_drawn: self.initial_balance + self.monthly_draw * self.months as f64,
+            total_interest: rows.iter().map(|row| row.interest).sum(),
+            total_mortgage_insurance: rows.iter().map(|row| row.mortgage_insurance).sum(),
+            final_balance: balance,
+            rows,
+        })
+    }
+}
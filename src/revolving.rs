@@ -0,0 +1,186 @@
+use chrono::{Months, NaiveDate};
+use crate::error::AmortizationError;
+use crate::money::Currency;
+use crate::payment::Payment;
+
+// Stop simulating a balance that is still outstanding after 100 years
+const MAX_PERIODS: u32 = 1200;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RevolvingPayment {
+    // Percentage of the statement balance, but never less than the floor
+    Minimum { percent: f64, floor: f64 },
+    Fixed(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevolvingDebt {
+    pub balance: f64,
+    pub apr: f64,
+    pub payment: RevolvingPayment,
+    // New charges added to the balance every month
+    pub monthly_spending: f64,
+    pub start_date: Option<NaiveDate>,
+    pub currency: Currency,
+}
+
+#[derive(Debug, Clone)]
+pub struct RevolvingPayoff {
+    pub months: u32,
+    pub payoff_date: Option<NaiveDate>,
+    pub total_interest: f64,
+    pub total_paid: f64,
+    pub schedule: Vec<Payment>,
+}
+
+impl RevolvingDebt {
+    pub fn new(balance: f64, apr: f64) -> Self {
+        RevolvingDebt {
+            balance,
+            apr,
+            // A typical card minimum: 2% of the balance or $25, whichever is greater
+            payment: RevolvingPayment::Minimum { percent: 2.0, floor: 25.0 },
+            monthly_spending: 0.0,
+            start_date: None,
+            currency: Currency::default(),
+        }
+    }
+
+    pub fn with_minimum_payment(mut self, percent: f64, floor: f64) -> Self {
+        self.payment = RevolvingPayment::Minimum { percent, floor };
+        self
+    }
+
+    pub fn with_fixed_payment(mut self, amount: f64) -> Self {
+        self.payment = RevolvingPayment::Fixed(amount);
+        self
+    }
+
+    pub fn with_monthly_spending(mut self, monthly_spending: f64) -> Self {
+        self.monthly_spending = monthly_spending;
+        self
+    }
+
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    pub fn monthly_rate(&self) -> f64 {
+        self.apr / 100.0 / 12.0
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.balance <= 0.0 || !self.balance.is_finite() {
+            return Err(AmortizationError::InvalidLoanAmount(self.balance));
+        }
+        if self.apr < 0.0 || !self.apr.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(self.apr));
+        }
+        if self.monthly_spending < 0.0 || !self.monthly_spending.is_finite() {
+            return Err(AmortizationError::InvalidPaymentAmount(self.monthly_spending));
+        }
+        match self.payment {
+            RevolvingPayment::Minimum { percent, .. } if percent <= 0.0 || percent > 100.0 || !percent.is_finite() => {
+                Err(AmortizationError::InvalidPaymentAmount(percent))
+            }
+            RevolvingPayment::Minimum { floor, .. } if floor < 0.0 || !floor.is_finite() => {
+                Err(AmortizationError::InvalidPaymentAmount(floor))
+            }
+            RevolvingPayment::Fixed(amount) if amount <= 0.0 || !amount.is_finite() => {
+                Err(AmortizationError::InvalidPaymentAmount(amount))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Payments are generated lazily because the payoff month isn't known up front
+    pub fn payments(&self) -> RevolvingPayments<'_> {
+        RevolvingPayments {
+            debt: self,
+            balance: self.balance,
+            installment_number: 1,
+        }
+    }
+
+    pub fn payoff(&self) -> Result<RevolvingPayoff, AmortizationError> {
+        self.validate()?;
+        if let RevolvingPayment::Fixed(amount) = self.payment {
+            if amount <= self.currency.round(self.balance * self.monthly_rate()) + self.monthly_spending {
+                return Err(AmortizationError::InsufficientPayment(amount));
+            }
+        }
+
+        let schedule: Vec<Payment> = self.payments().collect();
+        let remaining = schedule.last().map_or(self.balance, |p| p.remaining_balance);
+        if remaining > 0.0 {
+            return Err(AmortizationError::NoSolution(format!(
+                "Balance is not repaid within {} months", MAX_PERIODS
+            )));
+        }
+
+        Ok(RevolvingPayoff {
+            months: schedule.len() as u32,
+            payoff_date: schedule.last().and_then(|p| p.date),
+            total_interest: schedule.iter().map(|p| p.interest).sum(),
+            total_paid: schedule.iter().map(|p| p.installment_amount).sum(),
+            schedule,
+        })
+    }
+}
+
+pub struct RevolvingPayments<'a> {
+    debt: &'a RevolvingDebt,
+    balance: f64,
+    installment_number: u32,
+}
+
+impl Iterator for RevolvingPayments<'_> {
+    type Item = Payment;
+
+    fn next(&mut self) -> Option<Payment> {
+        if self.balance <= 0.0 || self.installment_number > MAX_PERIODS {
+            return None;
+        }
+
+        let debt = self.debt;
+        let beginning_balance = self.balance;
+        let interest = debt.currency.round(beginning_balance * debt.monthly_rate());
+        let statement_balance = beginning_balance + interest + debt.monthly_spending;
+        let due = match debt.payment {
+            RevolvingPayment::Minimum { percent, floor } => debt.currency.round(statement_balance * percent / 100.0).max(floor),
+            RevolvingPayment::Fixed(amount) => amount,
+        };
+        let installment_amount = due.min(debt.currency.round(statement_balance));
+        let remaining_balance = debt.currency.round(statement_balance - installment_amount).max(0.0);
+        let date = debt.start_date.and_then(|start| start.checked_add_months(Months::new(self.installment_number - 1)));
+
+        let payment = Payment {
+            installment_number: self.installment_number,
+            beginning_balance,
+            interest,
+            principal: installment_amount - interest,
+            date,
+            remaining_balance,
+            installment_amount,
+            balloon_amount: 0.0,
+            capitalized_interest: 0.0,
+            accrued_interest: 0.0,
+            deferred_amount: 0.0,
+            fees: 0.0,
+            skipped: false,
+            escrow: None,
+            ltv: None,
+            real: None,
+            accrual: None,
+        };
+        self.balance = remaining_balance;
+        self.installment_number += 1;
+        Some(payment)
+    }
+}
@@ -0,0 +1,138 @@
+use chrono::NaiveDate;
+use crate::calendar::DateRollConvention;
+use crate::error::AmortizationError;
+use crate::frequency::PaymentFrequency;
+use crate::tvm::{self, PaymentTiming};
+
+// Regular deposits growing at compound interest, the mirror image of a loan. Deposits in
+// arrears are made at the end of each period; in advance they earn that period's interest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavingsPlan {
+    pub deposit: f64,
+    pub apr: f64,
+    pub term_months: u32,
+    pub frequency: PaymentFrequency,
+    pub timing: PaymentTiming,
+    pub initial_balance: f64,
+    pub start_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavingsRow {
+    pub period: u32,
+    pub date: Option<NaiveDate>,
+    pub beginning_balance: f64,
+    pub deposit: f64,
+    pub interest: f64,
+    pub ending_balance: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SavingsSchedule {
+    pub rows: Vec<SavingsRow>,
+    pub total_deposits: f64,
+    pub total_interest: f64,
+    pub future_value: f64,
+}
+
+impl SavingsPlan {
+    pub fn new(deposit: f64, apr: f64, term_months: u32) -> Self {
+        SavingsPlan {
+            deposit,
+            apr,
+            term_months,
+            frequency: PaymentFrequency::Monthly,
+            timing: PaymentTiming::Arrears,
+            initial_balance: 0.0,
+            start_date: None,
+        }
+    }
+
+    pub fn with_frequency(mut self, frequency: PaymentFrequency) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    pub fn with_timing(mut self, timing: PaymentTiming) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    pub fn with_initial_balance(mut self, initial_balance: f64) -> Self {
+        self.initial_balance = initial_balance;
+        self
+    }
+
+    pub fn with_start_date(mut self, start_date: NaiveDate) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    pub fn periods(&self) -> u32 {
+        self.frequency.periods_for_months(self.term_months)
+    }
+
+    pub fn periodic_rate(&self) -> f64 {
+        self.apr / 100.0 / self.frequency.periods_per_year() as f64
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.deposit < 0.0 || !self.deposit.is_finite() {
+            return Err(AmortizationError::InvalidPaymentAmount(self.deposit));
+        }
+        if self.apr < 0.0 || !self.apr.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(self.apr));
+        }
+        if self.initial_balance < 0.0 || !self.initial_balance.is_finite() {
+            return Err(AmortizationError::InvalidLoanAmount(self.initial_balance));
+        }
+        if self.periods() == 0 {
+            return Err(AmortizationError::InvalidPeriods(self.periods()));
+        }
+        Ok(())
+    }
+
+    pub fn future_value(&self) -> Result<f64, AmortizationError> {
+        self.validate()?;
+        tvm::fv(self.periodic_rate(), self.periods(), -self.deposit, -self.initial_balance, self.timing)
+    }
+
+    // Deposit needed on this plan's rate, term and starting balance to accumulate `target`
+    pub fn required_deposit(&self, target: f64) -> Result<f64, AmortizationError> {
+        self.validate()?;
+        if target <= 0.0 || !target.is_finite() {
+            return Err(AmortizationError::InvalidLoanAmount(target));
+        }
+        let deposit = -tvm::pmt(self.periodic_rate(), self.periods(), -self.initial_balance, target, self.timing)?;
+        Ok(deposit.max(0.0))
+    }
+
+    pub fn schedule(&self) -> Result<SavingsSchedule, AmortizationError> {
+        self.validate()?;
+        let rate = self.periodic_rate();
+        let mut balance = self.initial_balance;
+        let rows: Vec<SavingsRow> = (1..=self.periods()).map(|period| {
+            let beginning_balance = balance;
+            let interest = match self.timing {
+                PaymentTiming::Arrears => beginning_balance * rate,
+                PaymentTiming::Advance => (beginning_balance + self.deposit) * rate,
+            };
+            balance = beginning_balance + self.deposit + interest;
+            SavingsRow {
+                period,
+                date: self.start_date.and_then(|start| self.frequency.due_date(start, period as i32 - 1, DateRollConvention::SameDay)),
+                beginning_balance,
+                deposit: self.deposit,
+                interest,
+                ending_balance: balance,
+            }
+        }).collect();
+
+        Ok(SavingsSchedule {
+            total_deposits: self.deposit * rows.len() as f64,
+            total_interest: rows.iter().map(|row| row.interest).sum(),
+            future_value: balance,
+            rows,
+        })
+    }
+}
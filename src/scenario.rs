@@ -0,0 +1,137 @@
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::error::AmortizationError;
+use crate::floating::FloatingRate;
+use crate::rate::RatePeriod;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RatePath {
+    // Every rate in effect from `from_period` on moves by the same amount, floored at 0%
+    Shift { from_period: u32, basis_points: f64 },
+    // Replaces the loan's rate schedule outright
+    Curve(Vec<RatePeriod>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateScenario {
+    pub name: String,
+    pub path: RatePath,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScenarioResult {
+    pub name: String,
+    pub initial_payment: f64,
+    pub max_payment: f64,
+    pub total_payment: f64,
+    pub total_interest: f64,
+    // Peak installment and total interest relative to the base loan
+    pub payment_change: f64,
+    pub interest_change: f64,
+    pub loan: Amortization,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScenarioAnalysis {
+    pub base: Amortization,
+    pub scenarios: Vec<RateScenario>,
+}
+
+impl RatePath {
+    // Shock applied at the first rate reset, or from the start when the rate never resets
+    pub fn shock(base: &Amortization, basis_points: f64) -> Self {
+        let from_period = base.rate_schedule.get(1).map_or(1, |r| r.start_period);
+        RatePath::Shift { from_period, basis_points }
+    }
+
+    fn apply(&self, rate_schedule: &[RatePeriod]) -> Vec<RatePeriod> {
+        match self {
+            RatePath::Shift { from_period, basis_points } => {
+                let mut shifted = rate_schedule.to_vec();
+                // Split the rate period running at `from_period` so the shift starts exactly there
+                if let Some(current) = shifted.iter().rev().find(|r| r.start_period <= *from_period).copied() {
+                    if current.start_period != *from_period {
+                        let index = shifted.iter().position(|r| r.start_period > *from_period).unwrap_or(shifted.len());
+                        shifted.insert(index, RatePeriod::new(*from_period, current.apr));
+                    }
+                }
+                for rate in shifted.iter_mut().filter(|r| r.start_period >= *from_period) {
+                    rate.apr = (rate.apr + basis_points / 100.0).max(0.0);
+                }
+                shifted
+            }
+            RatePath::Curve(curve) => curve.clone(),
+        }
+    }
+}
+
+impl RateScenario {
+    pub fn new(name: &str, path: RatePath) -> Self {
+        RateScenario { name: name.to_string(), path }
+    }
+}
+
+fn result(name: &str, loan: Amortization, base: &Amortization) -> ScenarioResult {
+    let max_payment = loan.schedule.iter().map(|p| p.installment_amount).fold(0.0, f64::max);
+    let initial_payment = loan.schedule.first().map_or(0.0, |p| p.installment_amount);
+    ScenarioResult {
+        name: name.to_string(),
+        initial_payment,
+        max_payment,
+        total_payment: loan.total_payment,
+        total_interest: loan.total_interest,
+        payment_change: max_payment - base.schedule.iter().map(|p| p.installment_amount).fold(0.0, f64::max),
+        interest_change: loan.total_interest - base.total_interest,
+        loan,
+    }
+}
+
+impl ScenarioAnalysis {
+    pub fn new(base: Amortization) -> Self {
+        ScenarioAnalysis { base, scenarios: Vec::new() }
+    }
+
+    pub fn with_scenario(mut self, name: &str, path: RatePath) -> Self {
+        self.scenarios.push(RateScenario::new(name, path));
+        self
+    }
+
+    // The usual parallel shocks, in basis points, applied at the first rate reset
+    pub fn with_shocks(mut self, basis_points: &[f64]) -> Self {
+        for &shock in basis_points {
+            let path = RatePath::shock(&self.base, shock);
+            self.scenarios.push(RateScenario::new(&format!("{:+}bp", shock), path));
+        }
+        self
+    }
+
+    // Parallel moves of a floating-rate loan's index, with its caps and floor applied to the
+    // shocked rates rather than to the base schedule
+    pub fn with_index_shocks(mut self, floating: &FloatingRate, basis_points: &[f64]) -> Result<Self, AmortizationError> {
+        for &shock in basis_points {
+            let curve = floating.shocked(shock).rate_schedule(self.base.periods)?;
+            self.scenarios.push(RateScenario::new(&format!("index {:+}bp", shock), RatePath::Curve(curve)));
+        }
+        Ok(self)
+    }
+
+    // The base loan first, then one stressed schedule per scenario. A loan with rate caps is
+    // stressed before its caps, which then apply to the stressed rates
+    pub fn run(&self) -> Result<Vec<ScenarioResult>, AmortizationError> {
+        let mut results = vec![result("base", self.base.clone(), &self.base)];
+        for scenario in &self.scenarios {
+            results.push(self.run_scenario(scenario)?);
+        }
+        Ok(results)
+    }
+
+    // The base loan along one scenario's rate path, whether or not it is in `scenarios`
+    pub fn run_scenario(&self, scenario: &RateScenario) -> Result<ScenarioResult, AmortizationError> {
+        let rate_schedule = scenario.path.apply(&self.base.config.uncapped_rate_schedule());
+        let loan = Amortization::from_config(CalculatorConfig {
+            apr: rate_schedule.first().map_or(self.base.config.apr, |r| r.apr),
+            rate_schedule,
+            ..self.base.config.clone()
+        })?;
+        Ok(result(&scenario.name, loan, &self.base))
+    }
+}
@@ -0,0 +1,461 @@
+use chrono::NaiveDate;
+use crate::calculator::{backend_periodic_rate, equal_principal_portion, periodic_payment, Amortization};
+use crate::error::AmortizationError;
+use crate::fees::fees_due;
+use crate::inflation::RealValues;
+use crate::interest::{InterestMethod, InterestPeriod};
+use crate::loan_type::{CapitalizationFrequency, LoanType};
+use crate::money::RoundingMode;
+use crate::num::AmortNum;
+use crate::odd_days::{split_interim_interest, FirstPeriodPolicy};
+use crate::payment::{AccrualPeriod, Payment};
+use crate::prepayment::PrepaymentTreatment;
+use crate::skip::SkipTreatment;
+use crate::tvm::PaymentTiming;
+
+pub(crate) struct ScheduleRow<N> {
+    pub(crate) interest: N,
+    pub(crate) principal: N,
+    pub(crate) amount: N,
+    pub(crate) balloon: N,
+    pub(crate) capitalized: N,
+    // Interest accrued in the period and left unpaid
+    pub(crate) unpaid: N,
+    // Interest accrued in earlier periods that the installment pays
+    pub(crate) accrued_paid: N,
+}
+
+// Lazily computes installments one at a time; `Amortization::calculate_schedule`
+// simply collects this iterator
+pub struct Payments<'a, N: AmortNum = f64> {
+    loan: &'a Amortization,
+    // Tie rule for rounding each period's interest to the currency's minor units, or None to
+    // carry full precision
+    interest_rounding: Option<RoundingMode>,
+    balance: N,
+    rate: N,
+    apr: f64,
+    installment_amount: N,
+    graduation_base: N,
+    // Fixed principal repaid each period by an equal principal loan
+    principal_portion: N,
+    // Interim interest carried by the installments, when the first period policy has them carry it
+    odd_days_interest: f64,
+    installment_number: u32,
+    current_date: Option<NaiveDate>,
+    accrual_start: Option<NaiveDate>,
+    total_payment: N,
+    // Interest accrued and unpaid but not yet capitalized
+    accrued_interest: N,
+    extension: u32,
+    recast_pending: bool,
+    // First installment of the current run in which the balance hasn't declined
+    stalled_at: Option<u32>,
+    error: Option<AmortizationError>,
+}
+
+
+impl<'a, N: AmortNum> Payments<'a, N> {
+    pub(crate) fn new(loan: &'a Amortization, interest_rounding: Option<RoundingMode>) -> Self {
+        let frequency = loan.config.frequency;
+        let accrual_start = loan.due_date(0);
+        let current_date = loan.due_date(1);
+        let error = (loan.start_date.is_some() && (accrual_start.is_none() || current_date.is_none()))
+            .then_some(AmortizationError::DateOutOfRange(1));
+
+        Payments {
+            loan,
+            interest_rounding,
+            balance: N::from_f64(loan.balance),
+            rate: backend_periodic_rate::<N>(&loan.rate_schedule[0], &loan.config),
+            apr: loan.config.rate_convention.nominal_apr(loan.rate_schedule[0].apr, frequency),
+            installment_amount: N::from_f64(loan.periodic_payment),
+            graduation_base: N::from_f64(loan.periodic_payment),
+            principal_portion: equal_principal_portion(N::from_f64(loan.balance), loan.amortizing_periods(), loan.config.currency),
+            odd_days_interest: if loan.config.odd_days_treatment.charges_installments() { loan.odd_days_interest() } else { 0.0 },
+            installment_number: 1,
+            current_date,
+            accrual_start,
+            total_payment: N::zero(),
+            accrued_interest: N::zero(),
+            extension: 0,
+            recast_pending: false,
+            stalled_at: None,
+            error,
+        }
+    }
+
+    pub fn total_payment(&self) -> N {
+        self.total_payment
+    }
+
+    pub fn remaining_balance(&self) -> N {
+        self.balance
+    }
+
+    // Due date of the next installment, or the date after the final one once exhausted
+    pub fn next_date(&self) -> Option<NaiveDate> {
+        self.current_date
+    }
+
+    pub fn error(&self) -> Option<&AmortizationError> {
+        self.error.as_ref()
+    }
+
+    pub fn try_next(&mut self) -> Result<Option<Payment>, AmortizationError> {
+        if let Some(error) = self.error.take() {
+            self.balance = N::zero();
+            return Err(error);
+        }
+        if self.balance <= N::zero() {
+            return Ok(None);
+        }
+
+        let loan = self.loan;
+        let frequency = loan.config.frequency;
+        let installment_number = self.installment_number;
+        let balance = self.balance;
+        // Extensions and negative amortization can keep a schedule going indefinitely
+        if installment_number > loan.config.max_periods {
+            return Err(AmortizationError::NonAmortizing {
+                max_periods: loan.config.max_periods,
+                stalled_at: self.stalled_at,
+                balance: balance.to_f64(),
+            });
+        }
+
+        let remaining_periods = (loan.periods + self.extension).saturating_sub(installment_number - 1).max(1);
+        let deferred = loan.is_deferred(installment_number);
+        let skipped = !deferred
+            && installment_number < self.last_period()
+            && loan.is_skipped(installment_number, self.current_date);
+        let reset = loan.rate_schedule.iter().find(|r| r.start_period == installment_number && installment_number > 1);
+        if let Some(reset) = reset {
+            self.rate = backend_periodic_rate::<N>(reset, &loan.config);
+            self.apr = loan.config.rate_convention.nominal_apr(reset.apr, frequency);
+        }
+        // A fixed payment stays as set through rate resets and recasts
+        let recalculates = loan.config.fixed_payment.is_none();
+        let currency = loan.config.currency;
+        if reset.is_some() && !deferred && !skipped && recalculates {
+            let recalculated = periodic_payment(self.rate, remaining_periods, balance, currency)?;
+            self.installment_amount = match loan.config.payment_cap {
+                Some(cap) => N::from_f64(cap.limit(self.installment_amount.to_f64(), recalculated.to_f64())).round_dp(currency.minor_units()),
+                None => recalculated,
+            };
+        }
+        let recast_limit = loan.config.payment_cap.and_then(|cap| cap.recast_balance_percent);
+        if let Some(limit) = recast_limit.filter(|_| recalculates) {
+            if balance.to_f64() > loan.balance * limit / 100.0 {
+                self.installment_amount = periodic_payment(self.rate, remaining_periods, balance, currency)?;
+            }
+        }
+        if self.recast_pending && !skipped && recalculates {
+            self.installment_amount = periodic_payment(self.rate, remaining_periods, balance, currency)?;
+            self.principal_portion = equal_principal_portion(balance, remaining_periods, currency);
+            self.recast_pending = false;
+        }
+        let first_amortizing = loan.periods - loan.amortizing_periods() + 1;
+        if installment_number > 1 && installment_number == first_amortizing {
+            self.installment_amount = loan.initial_payment(self.rate, balance, PaymentTiming::Arrears)?;
+            self.graduation_base = self.installment_amount;
+            self.principal_portion = equal_principal_portion(balance, loan.amortizing_periods(), currency);
+        }
+        if let Some(graduated) = loan.config.graduated_payment {
+            let period = installment_number.saturating_sub(first_amortizing);
+            let periods_per_year = frequency.periods_per_year();
+            if installment_number > first_amortizing && period.is_multiple_of(periods_per_year) && period / periods_per_year <= graduated.graduation_years {
+                self.installment_amount = (self.graduation_base * N::from_f64(graduated.step_factor(period, periods_per_year))).round_dp(currency.minor_units());
+            }
+        }
+
+        let accrual_rate = match (loan.config.day_count, self.accrual_start, self.current_date) {
+            (Some(day_count), Some(from), Some(to)) => N::from_f64(self.apr / 100.0 * day_count.year_fraction(from, to)),
+            _ => self.rate,
+        };
+        let row = if deferred {
+            self.deferment_row(balance, installment_number, accrual_rate)
+        } else if skipped {
+            self.skipped_row(balance, installment_number, accrual_rate)
+        } else {
+            self.schedule_row(balance, installment_number, accrual_rate)?
+        };
+        let mut row = row;
+        let accrued = self.accrued_interest + row.unpaid - row.accrued_paid;
+        // Deferred interest waits for the end of the deferment unless told otherwise
+        let capitalization = loan.config.capitalization.unwrap_or(if deferred {
+            CapitalizationFrequency::AtEndOfEvent
+        } else {
+            CapitalizationFrequency::EveryPeriod
+        });
+        let continues = if deferred || skipped { self.accrues_next(installment_number) } else { row.unpaid > N::zero() };
+        if accrued > N::zero() && (capitalization.capitalizes_at(installment_number, frequency.periods_per_year()) || !continues) {
+            row.capitalized = row.capitalized + accrued;
+            self.accrued_interest = N::zero();
+        } else {
+            self.accrued_interest = accrued;
+        }
+        let remaining_balance = balance - row.principal + row.capitalized;
+        if remaining_balance < balance {
+            self.stalled_at = None;
+        } else if self.stalled_at.is_none() {
+            self.stalled_at = Some(installment_number);
+        }
+        if !deferred && !skipped && loan.config.prepayments.iter()
+            .any(|p| p.period == installment_number && p.treatment == PrepaymentTreatment::ReducePayment) {
+            self.recast_pending = true;
+        }
+
+        let fees = fees_due(&loan.config.fees, installment_number, !deferred && !skipped, loan.config.currency);
+        let mut payment = Payment {
+            installment_number,
+            beginning_balance: balance.to_f64(),
+            installment_amount: (row.amount + N::from_f64(fees)).to_f64(),
+            interest: row.interest.to_f64(),
+            principal: row.principal.to_f64(),
+            remaining_balance: remaining_balance.to_f64(),
+            date: match self.current_date {
+                Some(date) => Some(loan.adjust_to_business_day(date).ok_or(AmortizationError::DateOutOfRange(installment_number))?),
+                None => None,
+            },
+            balloon_amount: row.balloon.to_f64(),
+            capitalized_interest: row.capitalized.to_f64(),
+            accrued_interest: self.accrued_interest.to_f64(),
+            deferred_amount: 0.0,
+            fees,
+            skipped,
+            escrow: match (loan.config.escrow, deferred) {
+                (Some(escrow), false) => Some(escrow.payment(loan.balance, balance.to_f64(), frequency.periods_per_year(), loan.config.currency)),
+                _ => None,
+            },
+            ltv: loan.config.property
+                .map(|property| property.ltv(remaining_balance.to_f64(), installment_number, frequency.periods_per_year())),
+            real: None,
+            accrual: self.accrual_period(installment_number),
+        };
+        if let Some(inflation_rate) = loan.config.inflation_rate {
+            payment.real = Some(RealValues::of(&payment, inflation_rate, frequency.periods_per_year()));
+        }
+
+        if let Some(date) = self.current_date {
+            self.accrual_start = Some(date);
+            self.current_date = Some(loan.due_date(installment_number + 1).ok_or(AmortizationError::DateOutOfRange(installment_number + 1))?);
+        }
+
+        self.total_payment = self.total_payment + row.amount + N::from_f64(fees);
+        self.balance = remaining_balance;
+        self.installment_number += 1;
+
+        Ok(Some(payment))
+    }
+
+    fn accrual_period(&self, installment_number: u32) -> Option<AccrualPeriod> {
+        let config = &self.loan.config;
+        let start = match (installment_number, config.odd_days_treatment, config.funding_date) {
+            // Interim interest carried by the first installment accrues from funding
+            (1, FirstPeriodPolicy::AddToFirstPayment, Some(funding_date)) => funding_date,
+            _ => self.accrual_start?,
+        };
+        let end = self.current_date?;
+        let days = config.day_count.map_or((end - start).num_days(), |day_count| day_count.days_between(start, end));
+        Some(AccrualPeriod { start, end, days })
+    }
+
+    // Whether the installment after `installment_number` is deferred or skipped, so interest
+    // keeps accruing unpaid
+    fn accrues_next(&self, installment_number: u32) -> bool {
+        let next = installment_number + 1;
+        let date = self.current_date.and_then(|_| self.loan.due_date(next));
+        self.loan.is_deferred(next) || (next < self.last_period() && self.loan.is_skipped(next, date))
+    }
+
+    // No payment is due while deferred; unsubsidized interest accrues on the principal
+    // and is capitalized by the loan's capitalization frequency
+    fn deferment_row(&self, balance: N, installment_number: u32, rate: N) -> ScheduleRow<N> {
+        let accrues = self.loan.config.deferment.is_some_and(|d| d.accrues_interest());
+        let interest = if accrues { self.accrued_interest(balance, installment_number, rate) } else { N::zero() };
+
+        ScheduleRow {
+            interest,
+            principal: N::zero(),
+            amount: N::zero(),
+            balloon: N::zero(),
+            capitalized: N::zero(),
+            unpaid: interest,
+            accrued_paid: N::zero(),
+        }
+    }
+
+    // A skipped installment pays nothing; its interest accrues unpaid and the shortfall is made
+    // up by a recalculated payment or by extending the term
+    fn skipped_row(&mut self, balance: N, installment_number: u32, rate: N) -> ScheduleRow<N> {
+        let interest = self.accrued_interest(balance, installment_number, rate);
+        match self.loan.config.skip_payments.as_ref().map(|skip| skip.treatment) {
+            Some(SkipTreatment::ExtendTerm) => self.extension += 1,
+            _ => self.recast_pending = true,
+        }
+
+        ScheduleRow {
+            interest,
+            principal: N::zero(),
+            amount: N::zero(),
+            balloon: N::zero(),
+            capitalized: N::zero(),
+            unpaid: interest,
+            accrued_paid: N::zero(),
+        }
+    }
+
+    // Payments made in advance fall due on the start date, before any interest has accrued
+    fn accrued_interest(&self, balance: N, installment_number: u32, rate: N) -> N {
+        if installment_number == 1 && self.loan.config.payment_timing == PaymentTiming::Advance {
+            return N::zero();
+        }
+        let interest = match &self.loan.config.interest_calculator {
+            Some(calculator) => {
+                let period = self.interest_period(installment_number, rate);
+                N::from_f64(calculator.interest_for_period(balance.to_f64(), &period, self.accrual_period(installment_number)))
+            }
+            None => balance * rate,
+        };
+        let interest = match self.interest_rounding {
+            Some(mode) => interest.round_dp_with(self.loan.config.currency.minor_units(), mode),
+            None => interest,
+        };
+        interest + self.interim_interest(installment_number)
+    }
+
+    fn interest_period(&self, installment_number: u32, rate: N) -> InterestPeriod {
+        InterestPeriod {
+            installment_number,
+            periods: self.loan.periods,
+            principal: self.loan.balance,
+            apr: self.apr,
+            periodic_rate: rate.to_f64(),
+            installment_amount: self.installment_amount.to_f64(),
+            currency: self.loan.config.currency,
+        }
+    }
+
+    // Interim interest is paid on top of the installments carrying it rather than out of their
+    // principal
+    fn interim_interest(&self, installment_number: u32) -> N {
+        let loan = self.loan;
+        N::from_f64(split_interim_interest(loan.config.odd_days_treatment, self.odd_days_interest, loan.deferred_periods(), loan.periods, installment_number, loan.config.currency))
+    }
+
+    fn last_period(&self) -> u32 {
+        self.loan.last_period() + self.extension
+    }
+
+    // Splits one installment into interest and principal. The last scheduled period (or a
+    // payment that would overshoot the balance) pays off whatever is left, which absorbs
+    // the cents lost to rounding the periodic payment and carries any balloon.
+    pub(crate) fn schedule_row(&self, balance: N, installment_number: u32, rate: N) -> Result<ScheduleRow<N>, AmortizationError> {
+        let loan = self.loan;
+
+        let interest = match loan.config.interest_method {
+            InterestMethod::Actuarial => self.accrued_interest(balance, installment_number, rate),
+            method => {
+                let period = self.interest_period(installment_number, self.rate);
+                N::from_f64(method.calculator().interest_for_period(balance.to_f64(), &period, None))
+            }
+        };
+        if !interest.to_f64().is_finite() {
+            return Err(AmortizationError::Overflow("interest"));
+        }
+
+        let extra_payment = N::from_f64(loan.config.extra_payment + loan.prepayment_at(installment_number));
+        let scheduled_principal = if loan.is_interest_only(installment_number) {
+            if extra_payment < balance { extra_payment } else { balance }
+        } else if loan.config.loan_type == LoanType::EqualPrincipal {
+            self.principal_portion + extra_payment
+        } else {
+            self.installment_amount + extra_payment + self.interim_interest(installment_number) - interest
+        };
+
+        if !scheduled_principal.to_f64().is_finite() {
+            return Err(AmortizationError::Overflow("principal"));
+        }
+
+        let owed = balance + self.accrued_interest;
+        let is_final = installment_number >= self.last_period() || scheduled_principal >= owed;
+        if !is_final && scheduled_principal < N::zero() {
+            if loan.config.fixed_payment.is_some_and(|fixed| !fixed.allow_negative_amortization) {
+                return Err(AmortizationError::InsufficientPayment(self.installment_amount.to_f64()));
+            }
+            // The installment does not cover the interest; the shortfall accrues unpaid
+            return Ok(ScheduleRow {
+                interest,
+                principal: N::zero(),
+                amount: interest + scheduled_principal,
+                balloon: N::zero(),
+                capitalized: N::zero(),
+                unpaid: N::zero() - scheduled_principal,
+                accrued_paid: N::zero(),
+            });
+        }
+        // Interest accrued unpaid in earlier periods is paid before any principal
+        let (principal, balloon, accrued_paid) = if is_final {
+            let balloon = if loan.config.balloon_period.map(|p| p + self.extension) == Some(installment_number) && scheduled_principal < owed {
+                owed - scheduled_principal
+            } else {
+                N::zero()
+            };
+            (balance, balloon, self.accrued_interest)
+        } else {
+            let accrued_paid = if scheduled_principal < self.accrued_interest { scheduled_principal } else { self.accrued_interest };
+            (scheduled_principal - accrued_paid, N::zero(), accrued_paid)
+        };
+
+        // The final installment clears the balance to the last fraction of a cent, so it is
+        // rounded to a payable amount. At full precision the residual is left in its interest;
+        // interest rounded each period is already in whole cents and the residual is noise
+        let amount = interest + principal + accrued_paid;
+        let (interest, amount) = if is_final {
+            let payable = amount.round_dp_with(loan.config.currency.minor_units(), loan.config.rounding.mode);
+            match self.interest_rounding {
+                Some(_) => (interest, payable),
+                None => (interest + payable - amount, payable),
+            }
+        } else {
+            (interest, amount)
+        };
+
+        Ok(ScheduleRow {
+            interest,
+            principal,
+            amount,
+            balloon,
+            capitalized: N::zero(),
+            unpaid: N::zero(),
+            accrued_paid,
+        })
+    }
+}
+
+impl<N: AmortNum> Iterator for Payments<'_, N> {
+    type Item = Payment;
+
+    fn next(&mut self) -> Option<Payment> {
+        match self.try_next() {
+            Ok(payment) => payment,
+            Err(error) => {
+                self.error = Some(error);
+                self.balance = N::zero();
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.balance <= N::zero() {
+            return (0, Some(0));
+        }
+        // Every loan is paid off by its last scheduled period at the latest
+        let last = self.last_period().min(self.loan.config.max_periods);
+        let remaining = last.saturating_sub(self.installment_number) as usize + 1;
+        let extends = self.loan.config.skip_payments.as_ref().is_some_and(|skip| skip.treatment == SkipTreatment::ExtendTerm);
+        (1, if extends { None } else { Some(remaining) })
+    }
+}
@@ -0,0 +1,220 @@
+// JSON Schemas (draft 2020-12, which OpenAPI 3.1 uses as is) for the documents the crate reads
+// and writes: loan definitions, the JSON export and its parts, and summaries. Written by hand
+// against the exporters rather than derived, so the crate stays free of dependencies; the
+// tests check every exported key is described
+use crate::export::JSON_SCHEMA_VERSION;
+use crate::json::JsonValue;
+
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaType {
+    // A loan definition, as read from a config file or a server request
+    LoanConfig,
+    Payment,
+    FeeCharge,
+    ScheduleEvent,
+    // `AmortizationSummary::to_json_value`
+    Summary,
+    // `Amortization::to_json_value`
+    Amortization,
+}
+
+fn typed(kind: &str, description: &str) -> JsonValue {
+    JsonValue::object(vec![("type", kind.into()), ("description", description.into())])
+}
+
+fn with(schema: JsonValue, key: &str, value: JsonValue) -> JsonValue {
+    match schema {
+        JsonValue::Object(mut entries) => {
+            entries.push((key.to_string(), value));
+            JsonValue::Object(entries)
+        }
+        other => other,
+    }
+}
+
+fn number(description: &str) -> JsonValue {
+    typed("number", description)
+}
+
+fn amount(description: &str) -> JsonValue {
+    with(number(description), "minimum", 0.0.into())
+}
+
+fn integer(description: &str, minimum: u32) -> JsonValue {
+    with(typed("integer", description), "minimum", minimum.into())
+}
+
+fn text(description: &str) -> JsonValue {
+    typed("string", description)
+}
+
+fn date(description: &str) -> JsonValue {
+    with(text(description), "format", "date".into())
+}
+
+// A date that is null for undated schedules
+fn optional_date(description: &str) -> JsonValue {
+    JsonValue::object(vec![
+        ("type", JsonValue::Array(vec!["string".into(), "null".into()])),
+        ("format", "date".into()),
+        ("description", description.into()),
+    ])
+}
+
+fn one_of(description: &str, values: &[&str]) -> JsonValue {
+    with(text(description), "enum", JsonValue::Array(values.iter().map(|&value| value.into()).collect()))
+}
+
+fn array(items: JsonValue) -> JsonValue {
+    JsonValue::object(vec![("type", "array".into()), ("items", items)])
+}
+
+fn object(properties: Vec<(&str, JsonValue)>, required: &[&str]) -> JsonValue {
+    JsonValue::object(vec![
+        ("type", "object".into()),
+        ("properties", JsonValue::object(properties)),
+        ("required", JsonValue::Array(required.iter().map(|&key| key.into()).collect())),
+        ("additionalProperties", false.into()),
+    ])
+}
+
+// Keys `Amortization::to_json_value` and `AmortizationSummary::to_json_value` share
+fn totals() -> Vec<(&'static str, JsonValue)> {
+    vec![
+        ("principal", amount("Amount borrowed")),
+        ("periodic_rate", number("Interest rate per period as a fraction")),
+        ("periods", integer("Scheduled number of installments", 0)),
+        ("periodic_payment", amount("Regular installment of principal and interest")),
+        ("final_payment", amount("Last installment, which settles any rounding")),
+        ("total_payment", amount("Sum of every installment, fees included")),
+        ("total_interest", number("Interest paid over the loan; fees are not interest")),
+        ("start_date", optional_date("Date the schedule starts from")),
+        ("end_date", optional_date("Date after the final installment")),
+    ]
+}
+
+const TOTALS_REQUIRED: [&str; 9] = [
+    "principal", "periodic_rate", "periods", "periodic_payment", "final_payment",
+    "total_payment", "total_interest", "start_date", "end_date",
+];
+
+impl SchemaType {
+    pub const ALL: [SchemaType; 6] = [
+        SchemaType::LoanConfig,
+        SchemaType::Payment,
+        SchemaType::FeeCharge,
+        SchemaType::ScheduleEvent,
+        SchemaType::Summary,
+        SchemaType::Amortization,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SchemaType::LoanConfig => "LoanConfig",
+            SchemaType::Payment => "Payment",
+            SchemaType::FeeCharge => "FeeCharge",
+            SchemaType::ScheduleEvent => "ScheduleEvent",
+            SchemaType::Summary => "AmortizationSummary",
+            SchemaType::Amortization => "Amortization",
+        }
+    }
+
+    // Self-contained: nested types are written out in place rather than referenced
+    pub fn schema(&self) -> JsonValue {
+        match self {
+            SchemaType::LoanConfig => with(object(vec![
+                ("principal", with(number("Amount borrowed"), "exclusiveMinimum", 0.0.into())),
+                ("apr", amount("Annual rate in percent, e.g. 6.5")),
+                ("term_months", integer("Term in months", 1)),
+                ("term_years", integer("Term in years, instead of term_months", 1)),
+                ("start_date", date("Date the first period starts")),
+                ("frequency", one_of("Payment frequency", &["weekly", "biweekly", "monthly", "quarterly", "semiannually", "annually"])),
+                ("extra_payment", amount("Extra principal paid with every installment")),
+                ("balloon_period", integer("Installment the remaining balance falls due with", 1)),
+                ("funding_date", date("Date the loan is disbursed, when not one period before the first due date")),
+                ("inflation_rate", number("Annual inflation in percent for today's-money values")),
+                ("payment_timing", one_of("Whether installments are due at the end or the start of each period", &["arrears", "advance"])),
+            ], &["principal", "apr"]), "oneOf", JsonValue::Array(vec![
+                JsonValue::object(vec![("required", JsonValue::Array(vec!["term_months".into()]))]),
+                JsonValue::object(vec![("required", JsonValue::Array(vec!["term_years".into()]))]),
+            ])),
+            SchemaType::Payment => object(vec![
+                ("installment_number", integer("Installment, from 1", 1)),
+                ("date", optional_date("Due date")),
+                ("beginning_balance", number("Balance before the installment")),
+                ("installment_amount", amount("Amount due, fees included")),
+                ("interest", number("Interest charged for the period")),
+                ("principal", number("Principal repaid")),
+                ("balloon_amount", amount("Balloon included in the installment")),
+                ("remaining_balance", number("Balance after the installment")),
+                ("fees", amount("Fees included in the installment; only for loans with fees")),
+            ], &["installment_number", "date", "beginning_balance", "installment_amount", "interest", "principal", "balloon_amount", "remaining_balance"]),
+            SchemaType::FeeCharge => object(vec![
+                ("installment_number", integer("Installment the fee is charged with", 1)),
+                ("name", text("Name of the fee")),
+                ("amount", amount("Amount charged")),
+            ], &["installment_number", "name", "amount"]),
+            SchemaType::ScheduleEvent => object(vec![
+                ("sequence", integer("Order in which the transform was applied, from 1", 1)),
+                ("transform", text("Name of the transform")),
+                ("effective_period", JsonValue::object(vec![
+                    ("type", JsonValue::Array(vec!["integer".into(), "null".into()])),
+                    ("description", "First installment affected, when the transform has one".into()),
+                ])),
+                ("effective_date", optional_date("Due date of the effective period")),
+                ("changes", array(object(vec![
+                    ("field", text("Term that changed")),
+                    ("before", text("Previous value")),
+                    ("after", text("New value")),
+                ], &["field", "before", "after"]))),
+                ("previous_payment", amount("Installment before the transform")),
+                ("payment", amount("Installment after the transform")),
+                ("previous_periods", integer("Installments before the transform", 0)),
+                ("periods", integer("Installments after the transform", 0)),
+                ("previous_total_interest", number("Total interest before the transform")),
+                ("total_interest", number("Total interest after the transform")),
+            ], &["sequence", "transform", "effective_period", "effective_date", "changes", "previous_payment", "payment", "previous_periods", "periods", "previous_total_interest", "total_interest"]),
+            SchemaType::Summary => {
+                let mut properties = totals();
+                properties.extend([
+                    ("payoff_period", integer("Installment the loan is paid off with", 0)),
+                    ("interest_to_principal", number("Interest paid per unit of principal")),
+                    ("total_fees", amount("Fees paid over the loan")),
+                ]);
+                let mut required = TOTALS_REQUIRED.to_vec();
+                required.extend(["payoff_period", "interest_to_principal", "total_fees"]);
+                object(properties, &required)
+            }
+            SchemaType::Amortization => {
+                let mut summary = totals();
+                summary.push(("total_fees", amount("Fees paid over the loan; only for loans with fees")));
+                object(vec![
+                    ("schema_version", with(integer("Version of this document's layout", 1), "const", JSON_SCHEMA_VERSION.into())),
+                    ("summary", object(summary, &TOTALS_REQUIRED)),
+                    ("schedule", array(SchemaType::Payment.schema())),
+                    ("fees", array(SchemaType::FeeCharge.schema())),
+                    ("events", array(SchemaType::ScheduleEvent.schema())),
+                ], &["schema_version", "summary", "schedule"])
+            }
+        }
+    }
+
+    // A standalone schema document, e.g. to publish next to an API
+    pub fn document(&self) -> JsonValue {
+        let mut entries = vec![("$schema".to_string(), DIALECT.into()), ("title".to_string(), self.name().into())];
+        if let JsonValue::Object(schema) = self.schema() {
+            entries.extend(schema);
+        }
+        JsonValue::Object(entries)
+    }
+}
+
+// Every schema by name, for the `components` object of an OpenAPI document
+pub fn openapi_components() -> JsonValue {
+    JsonValue::object(vec![(
+        "schemas",
+        JsonValue::object(SchemaType::ALL.iter().map(|schema| (schema.name(), schema.schema())).collect()),
+    )])
+}
@@ -0,0 +1,123 @@
+// The "what if rates move" matrix of mortgage tools: payments on one loan amount for every
+// combination of a list of rates and a list of terms, priced together through `batch`
+use crate::batch::{self, BatchLoan, BatchQuote};
+use crate::error::AmortizationError;
+use crate::json::JsonValue;
+use crate::money::Currency;
+use crate::table::format_amount_with;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GridValue {
+    #[default]
+    Payment,
+    TotalInterest,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentGrid {
+    pub principal: f64,
+    pub rates: Vec<f64>,
+    pub terms: Vec<u32>,
+    // One row per rate, one column per term
+    pub payments: Vec<Vec<f64>>,
+    pub total_interest: Vec<Vec<f64>>,
+    pub currency: Currency,
+}
+
+pub fn payment_grid(principal: f64, rates: &[f64], terms: &[u32], currency: Currency) -> Result<PaymentGrid, AmortizationError> {
+    if rates.is_empty() {
+        return Err(AmortizationError::MissingParameter("rates"));
+    }
+    if terms.is_empty() {
+        return Err(AmortizationError::MissingParameter("terms"));
+    }
+    let loans: Vec<BatchLoan> = rates.iter()
+        .flat_map(|&apr| terms.iter().map(move |&term_months| BatchLoan::new(principal, apr, term_months).with_currency(currency)))
+        .collect();
+    let quotes = batch::quotes(&loans)?;
+    let rows = |value: fn(&BatchQuote) -> f64| -> Vec<Vec<f64>> {
+        quotes.chunks(terms.len()).map(|row| row.iter().map(value).collect()).collect()
+    };
+    Ok(PaymentGrid {
+        principal,
+        rates: rates.to_vec(),
+        terms: terms.to_vec(),
+        payments: rows(|quote| quote.payment),
+        total_interest: rows(|quote| quote.total_interest),
+        currency,
+    })
+}
+
+// Whole years where the term allows, e.g. "30 yr" rather than "360 mo"
+fn term_label(term_months: u32) -> String {
+    if term_months.is_multiple_of(12) { format!("{} yr", term_months / 12) } else { format!("{} mo", term_months) }
+}
+
+impl PaymentGrid {
+    pub fn values(&self, value: GridValue) -> &[Vec<f64>] {
+        match value {
+            GridValue::Payment => &self.payments,
+            GridValue::TotalInterest => &self.total_interest,
+        }
+    }
+
+    pub fn payment(&self, apr: f64, term_months: u32) -> Option<f64> {
+        let row = self.rates.iter().position(|&rate| rate == apr)?;
+        let column = self.terms.iter().position(|&term| term == term_months)?;
+        Some(self.payments[row][column])
+    }
+
+    fn cells(&self, value: GridValue) -> Vec<Vec<String>> {
+        let header = std::iter::once("APR".to_string()).chain(self.terms.iter().map(|&term| term_label(term)));
+        let rows = self.rates.iter().zip(self.values(value)).map(|(rate, row)| {
+            std::iter::once(format!("{}%", rate)).chain(row.iter().map(|&amount| format_amount_with(amount, self.currency.minor_units() as usize, true))).collect()
+        });
+        std::iter::once(header.collect()).chain(rows).collect()
+    }
+
+    // Rates down the side, terms across the top
+    pub fn to_table(&self, value: GridValue) -> String {
+        let cells = self.cells(value);
+        let widths: Vec<usize> = (0..cells[0].len())
+            .map(|column| cells.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+            .collect();
+        let mut table = String::new();
+        for (index, row) in cells.iter().enumerate() {
+            let line: Vec<String> = row.iter().zip(&widths)
+                .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+                .collect();
+            table.push_str(&line.join("  "));
+            table.push('\n');
+            if index == 0 {
+                let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+                table.push_str(&rule.join("  "));
+                table.push('\n');
+            }
+        }
+        table
+    }
+
+    pub fn to_markdown(&self, value: GridValue) -> String {
+        let cells = self.cells(value);
+        let mut markdown = format!("| {} |\n", cells[0].join(" | "));
+        let alignment: Vec<&str> = cells[0].iter().map(|_| "---:").collect();
+        markdown.push_str(&format!("| {} |\n", alignment.join(" | ")));
+        for row in &cells[1..] {
+            markdown.push_str(&format!("| {} |\n", row.join(" | ")));
+        }
+        markdown
+    }
+
+    pub fn to_json_value(&self) -> JsonValue {
+        let matrix = |rows: &[Vec<f64>]| JsonValue::Array(rows.iter()
+            .map(|row| JsonValue::Array(row.iter().map(|&amount| JsonValue::money(amount, self.currency)).collect()))
+            .collect());
+        JsonValue::object(vec![
+            ("principal", JsonValue::money(self.principal, self.currency)),
+            ("rates", JsonValue::Array(self.rates.iter().map(|&rate| rate.into()).collect())),
+            ("terms", JsonValue::Array(self.terms.iter().map(|&term| term.into()).collect())),
+            ("payments", matrix(&self.payments)),
+            ("total_interest", matrix(&self.total_interest)),
+        ])
+    }
+}
@@ -0,0 +1,225 @@
+// JSON API over HTTP for running the calculator as a service:
+//
+//   POST /amortize   a loan definition, answered with the schedule export
+//   POST /summary    a loan definition, answered with its totals only, without a schedule
+//   POST /compare    {"loans": [...]}, answered with one summary per loan
+//
+// Bodies use the same fields as loan files (see `config`). Routing lives in `handle`, which
+// takes and returns plain values, so the endpoints can also be mounted in an async framework;
+// `serve` is a small blocking HTTP/1.1 server with a thread per connection, up to a limit, for
+// deployments that need nothing more
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::compare::LoanComparison;
+use crate::config::ConfigFormat;
+use crate::error::AmortizationError;
+use crate::json::JsonValue;
+use crate::summary::AmortizationSummary;
+
+// Even a long portfolio comparison is far below this
+const MAX_BODY_BYTES: usize = 1 << 20;
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+// Request line and each header; longer lines are refused rather than buffered
+const MAX_HEADER_LINE_BYTES: usize = 8 << 10;
+const MAX_HEADERS: usize = 100;
+// Connections answered at once; more are turned away with 503 until one finishes
+const MAX_CONNECTIONS: usize = 64;
+// Loans in one comparison; each is built in full, so the body limit alone allows thousands
+const MAX_COMPARE_LOANS: usize = 100;
+// Pause after a failed accept, so running out of file descriptors doesn't spin the listener
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: JsonValue,
+}
+
+impl Response {
+    fn ok(body: JsonValue) -> Self {
+        Response { status: 200, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Response { status, body: JsonValue::object(vec![("error", JsonValue::from(message))]) }
+    }
+
+    // Invalid loans answer 400 with the error's kind and field, so clients can point at the input
+    fn invalid(error: AmortizationError) -> Self {
+        Response {
+            status: 400,
+            body: JsonValue::object(vec![
+                ("error", JsonValue::from(error.to_string())),
+                ("kind", JsonValue::from(format!("{:?}", error.kind()))),
+                ("parameter", JsonValue::from(error.parameter())),
+            ]),
+        }
+    }
+}
+
+fn status_text(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+fn single_loan(body: &str) -> Result<CalculatorConfig, AmortizationError> {
+    let mut configs = CalculatorConfig::all_from_str(body, ConfigFormat::Json)?;
+    if configs.len() != 1 {
+        return Err(AmortizationError::InvalidConfig(format!("Expected one loan, found {}", configs.len())));
+    }
+    Ok(configs.remove(0))
+}
+
+fn amortize(body: &str) -> Result<JsonValue, AmortizationError> {
+    Ok(Amortization::from_config(single_loan(body)?)?.to_json_value())
+}
+
+fn summary(body: &str) -> Result<JsonValue, AmortizationError> {
+    Ok(AmortizationSummary::from_config(single_loan(body)?)?.to_json_value())
+}
+
+fn compare(body: &str) -> Result<JsonValue, AmortizationError> {
+    let configs = CalculatorConfig::all_from_str(body, ConfigFormat::Json)?;
+    if configs.is_empty() {
+        return Err(AmortizationError::MissingParameter("loans"));
+    }
+    if configs.len() > MAX_COMPARE_LOANS {
+        return Err(AmortizationError::InvalidConfig(format!("Compare at most {} loans, found {}", MAX_COMPARE_LOANS, configs.len())));
+    }
+    let loans = configs.into_iter().map(Amortization::from_config).collect::<Result<Vec<_>, _>>()?;
+    let comparison = LoanComparison::new(loans);
+    Ok(JsonValue::object(vec![
+        ("summaries", comparison.summaries_json()),
+        ("lowest_total_cost", JsonValue::from(comparison.lowest_total_cost().map(|index| index as u32))),
+    ]))
+}
+
+pub fn handle(method: &str, path: &str, body: &str) -> Response {
+    let path = path.split('?').next().unwrap_or(path);
+    let endpoint: fn(&str) -> Result<JsonValue, AmortizationError> = match path {
+        "/amortize" => amortize,
+        "/compare" => compare,
+        "/summary" => summary,
+        _ => return Response::error(404, "Not found"),
+    };
+    if method != "POST" {
+        return Response::error(405, "Use POST");
+    }
+    endpoint(body).map_or_else(Response::invalid, Response::ok)
+}
+
+// Frees its connection's place when the thread answering it ends, however it ends
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// Listens for good once bound, answering each connection on its own thread. A failed accept,
+// such as a client that gave up or no file descriptors to spare, only skips that connection
+pub fn serve(addr: impl ToSocketAddrs) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    let open = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let Ok(mut stream) = stream else {
+            thread::sleep(ACCEPT_RETRY_DELAY);
+            continue;
+        };
+        if open.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            open.fetch_sub(1, Ordering::SeqCst);
+            // A client that hangs up mid-request has no one left to tell
+            let _ = stream.set_write_timeout(Some(Duration::from_secs(1)))
+                .and_then(|()| write_response(&mut stream, &Response::error(503, "Too many connections")));
+            continue;
+        }
+        let slot = ConnectionSlot(Arc::clone(&open));
+        thread::spawn(move || {
+            let _slot = slot;
+            let _ = handle_connection(stream);
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(mut stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let response = read_request(&stream)?;
+    write_response(&mut stream, &response)
+}
+
+fn write_response(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    let body = response.body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status, status_text(response.status), body.len(), body
+    )?;
+    stream.flush()
+}
+
+// One line of the request head, or None when it runs past `MAX_HEADER_LINE_BYTES`
+fn read_header_line<R: BufRead>(reader: &mut R) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    reader.take(MAX_HEADER_LINE_BYTES as u64 + 1).read_line(&mut line)?;
+    Ok((line.len() <= MAX_HEADER_LINE_BYTES).then_some(line))
+}
+
+fn read_request(stream: &TcpStream) -> io::Result<Response> {
+    let mut reader = BufReader::new(stream);
+    let too_large = || Response::error(431, "Request header too large");
+    let Some(request_line) = read_header_line(&mut reader)? else {
+        return Ok(too_large());
+    };
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Ok(Response::error(400, "Malformed request line"));
+    };
+
+    let mut content_length = 0;
+    let mut headers = 0;
+    loop {
+        let Some(header) = read_header_line(&mut reader)? else {
+            return Ok(too_large());
+        };
+        if header.trim().is_empty() {
+            break;
+        }
+        headers += 1;
+        if headers > MAX_HEADERS {
+            return Ok(too_large());
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                match value.trim().parse() {
+                    Ok(length) => content_length = length,
+                    Err(_) => return Ok(Response::error(400, "Invalid Content-Length")),
+                }
+            }
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Ok(Response::error(413, "Request body too large"));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    match String::from_utf8(body) {
+        Ok(body) => Ok(handle(method, path, &body)),
+        Err(_) => Ok(Response::error(400, "Request body is not UTF-8")),
+    }
+}
@@ -0,0 +1,198 @@
+use chrono::NaiveDate;
+use crate::calculator::{backend_periodic_rate, Amortization};
+use crate::error::AmortizationError;
+
+// How far past the scheduled term a projection keeps looking for the payoff
+const MAX_PROJECTION_PERIODS: u32 = 1200;
+
+// A payment actually received, applied to the next installment in order. Missed
+// installments are recorded as payments of zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActualPayment {
+    pub amount: f64,
+    pub date: Option<NaiveDate>,
+}
+
+// The order a payment settles what is due. Whatever is left over after the last bucket goes to
+// principal; interest left unpaid is capitalized and fees left unpaid carry to the next
+// installment
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ApplicationOrder {
+    #[default]
+    FeesInterestPrincipal,
+    InterestFeesPrincipal,
+    // The installment's scheduled principal comes before fees
+    InterestPrincipalFees,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LedgerEntry {
+    pub installment_number: u32,
+    pub scheduled_date: Option<NaiveDate>,
+    pub paid_date: Option<NaiveDate>,
+    pub scheduled_amount: f64,
+    pub actual_amount: f64,
+    // Positive for an overpayment
+    pub variance: f64,
+    pub cumulative_variance: f64,
+    pub interest: f64,
+    pub principal: f64,
+    // Fees paid, including any carried from earlier installments
+    pub fees: f64,
+    // Interest the payment didn't cover, added to the balance
+    pub capitalized_interest: f64,
+    // Fees still owed after the payment
+    pub unpaid_fees: f64,
+    pub remaining_balance: f64,
+    pub scheduled_balance: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct PaymentLedger {
+    pub entries: Vec<LedgerEntry>,
+    pub remaining_balance: f64,
+    // Balance the original schedule expects after the same number of installments
+    pub scheduled_balance: f64,
+    pub cumulative_variance: f64,
+    pub unpaid_fees: f64,
+    // Installment that pays the loan off if the scheduled payments are made from here on;
+    // None when they no longer cover the interest
+    pub projected_payoff_period: Option<u32>,
+    pub projected_payoff_date: Option<NaiveDate>,
+}
+
+impl ActualPayment {
+    pub fn new(amount: f64) -> Self {
+        ActualPayment { amount, date: None }
+    }
+
+    pub fn with_date(mut self, date: NaiveDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+}
+
+impl PaymentLedger {
+    pub fn is_paid_off(&self) -> bool {
+        self.remaining_balance <= 0.0
+    }
+
+    // Installments gained (positive) or lost against the scheduled maturity
+    pub fn periods_ahead(&self, loan: &Amortization) -> Option<i64> {
+        self.projected_payoff_period.map(|period| loan.schedule.len() as i64 - period as i64)
+    }
+}
+
+impl Amortization {
+    // Replays the payments actually received against the schedule, settling fees, then
+    // interest, then principal
+    pub fn apply_payments(&self, payments: &[ActualPayment]) -> Result<PaymentLedger, AmortizationError> {
+        self.apply_payments_in_order(payments, ApplicationOrder::default())
+    }
+
+    pub fn apply_payments_in_order(&self, payments: &[ActualPayment], order: ApplicationOrder) -> Result<PaymentLedger, AmortizationError> {
+        if let Some(invalid) = payments.iter().find(|p| p.amount < 0.0 || !p.amount.is_finite()) {
+            return Err(AmortizationError::InvalidPaymentAmount(invalid.amount));
+        }
+
+        let currency = self.config.currency;
+        let mut balance = self.balance;
+        let mut cumulative_variance = 0.0;
+        let mut unpaid_fees = 0.0;
+        let mut entries = Vec::with_capacity(payments.len());
+        for (index, payment) in payments.iter().enumerate() {
+            let installment_number = index as u32 + 1;
+            let scheduled = self.schedule.get(index);
+            let scheduled_amount = scheduled.map_or(0.0, |p| p.installment_amount);
+            let interest = currency.round(balance * self.servicing_rate(installment_number));
+            let fees_due = currency.round(unpaid_fees + scheduled.map_or(0.0, |p| p.fees));
+            let mut available = payment.amount;
+            let mut take = |due: f64| {
+                let paid = currency.round(due.min(available).max(0.0));
+                available = currency.round(available - paid);
+                paid
+            };
+            let (interest_paid, fees, principal) = match order {
+                ApplicationOrder::FeesInterestPrincipal => {
+                    let fees = take(fees_due);
+                    (take(interest), fees, take(balance))
+                }
+                ApplicationOrder::InterestFeesPrincipal => {
+                    let interest_paid = take(interest);
+                    (interest_paid, take(fees_due), take(balance))
+                }
+                ApplicationOrder::InterestPrincipalFees => {
+                    let interest_paid = take(interest);
+                    let scheduled_principal = take(scheduled.map_or(balance, |p| p.principal).min(balance));
+                    let fees = take(fees_due);
+                    (interest_paid, fees, currency.round(scheduled_principal + take(balance - scheduled_principal)))
+                }
+            };
+            let capitalized_interest = currency.round(interest - interest_paid);
+            unpaid_fees = currency.round(fees_due - fees);
+            balance = currency.round(balance + capitalized_interest - principal);
+            let variance = currency.round(payment.amount - scheduled_amount);
+            cumulative_variance = currency.round(cumulative_variance + variance);
+
+            entries.push(LedgerEntry {
+                installment_number,
+                scheduled_date: scheduled.and_then(|p| p.date),
+                paid_date: payment.date,
+                scheduled_amount,
+                actual_amount: payment.amount,
+                variance,
+                cumulative_variance,
+                interest,
+                principal,
+                fees,
+                capitalized_interest,
+                unpaid_fees,
+                remaining_balance: balance,
+                scheduled_balance: scheduled.map_or(0.0, |p| p.remaining_balance),
+            });
+        }
+
+        let applied = payments.len() as u32;
+        let projected_payoff_period = self.project_payoff(applied, balance);
+        Ok(PaymentLedger {
+            remaining_balance: balance,
+            scheduled_balance: self.balance_after(applied).unwrap_or(0.0),
+            cumulative_variance,
+            unpaid_fees,
+            projected_payoff_date: projected_payoff_period.and_then(|period| self.payment_date(period)),
+            projected_payoff_period,
+            entries,
+        })
+    }
+
+    // Keeps paying the scheduled installments, then the regular payment past the original
+    // term, until `balance` is gone
+    fn project_payoff(&self, applied: u32, mut balance: f64) -> Option<u32> {
+        if balance <= 0.0 {
+            return Some(applied);
+        }
+        let currency = self.config.currency;
+        let last_period = self.schedule.len() as u32 + MAX_PROJECTION_PERIODS;
+        for installment_number in applied + 1..=last_period {
+            let due = self.schedule.get(installment_number as usize - 1)
+                .map_or(self.periodic_payment, |p| p.installment_amount - p.fees);
+            let interest = currency.round(balance * self.servicing_rate(installment_number));
+            if installment_number as usize > self.schedule.len() && due <= interest {
+                return None;
+            }
+            balance = currency.round(balance + interest - due);
+            if balance <= 0.0 {
+                return Some(installment_number);
+            }
+        }
+        None
+    }
+
+    fn servicing_rate(&self, installment_number: u32) -> f64 {
+        let rate_period = self.rate_schedule.iter()
+            .rev()
+            .find(|r| r.start_period <= installment_number)
+            .unwrap_or(&self.rate_schedule[0]);
+        backend_periodic_rate::<f64>(rate_period, &self.config)
+    }
+}
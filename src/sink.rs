@@ -0,0 +1,135 @@
+// Streams a schedule into a consumer one installment at a time, so rows can go straight into
+// a database or a response body without the schedule ever being held in memory
+use std::fmt;
+use std::io::{self, Write};
+use chrono::NaiveDate;
+use crate::builder::AmortizationBuilder;
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::error::AmortizationError;
+use crate::payment::Payment;
+use crate::summary::{settled, AmortizationSummary};
+use crate::table::{payment_cells, Column, FormatOptions};
+
+// Closures `FnMut(&Payment) -> Result<(), E>` are sinks too
+pub trait ScheduleSink {
+    type Error;
+
+    fn on_payment(&mut self, payment: &Payment) -> Result<(), Self::Error>;
+
+    // Called once after the last installment, with the schedule's totals
+    fn finish(&mut self, _summary: &AmortizationSummary) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<F, E> ScheduleSink for F
+where
+    F: FnMut(&Payment) -> Result<(), E>,
+{
+    type Error = E;
+
+    fn on_payment(&mut self, payment: &Payment) -> Result<(), E> {
+        self(payment)
+    }
+}
+
+// Why streaming stopped: the schedule couldn't be computed, or the sink refused a row
+#[derive(Debug)]
+pub enum StreamError<E> {
+    Schedule(AmortizationError),
+    Sink(E),
+}
+
+impl<E> From<AmortizationError> for StreamError<E> {
+    fn from(error: AmortizationError) -> Self {
+        StreamError::Schedule(error)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for StreamError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Schedule(error) => write!(f, "{}", error),
+            StreamError::Sink(error) => write!(f, "Schedule sink failed: {}", error),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for StreamError<E> {}
+
+// Writes the rows `to_csv_with` would, header first, as they arrive
+pub struct CsvSink<W: Write> {
+    writer: W,
+    options: FormatOptions,
+    header_written: bool,
+}
+
+impl<W: Write> CsvSink<W> {
+    pub fn new(writer: W, options: FormatOptions) -> Self {
+        CsvSink { writer, options, header_written: false }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if !self.header_written {
+            let header: Vec<&str> = self.options.columns.iter().map(Column::label).collect();
+            writeln!(self.writer, "{}", header.join(","))?;
+            self.header_written = true;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> ScheduleSink for CsvSink<W> {
+    type Error = io::Error;
+
+    // Undated schedules leave the date empty. Cells with a comma, from grouped thousands or
+    // the date format, are quoted
+    fn on_payment(&mut self, payment: &Payment) -> io::Result<()> {
+        self.write_header()?;
+        let options = &self.options;
+        let format_date = |date: Option<NaiveDate>| date.map(|d| d.format(&options.date_format).to_string()).unwrap_or_default();
+        let cells: Vec<String> = payment_cells(options, payment, &|amount| options.format_amount(amount), &format_date)
+            .into_iter()
+            .map(|cell| if cell.contains(',') { format!("\"{}\"", cell) } else { cell })
+            .collect();
+        writeln!(self.writer, "{}", cells.join(","))
+    }
+
+    fn finish(&mut self, _summary: &AmortizationSummary) -> io::Result<()> {
+        self.write_header()?;
+        self.writer.flush()
+    }
+}
+
+impl Amortization {
+    // Computes the schedule of `config` straight into `sink`. The installment is settled in
+    // a first pass that keeps only totals, so memory use doesn't grow with the term
+    pub fn stream<S: ScheduleSink>(config: CalculatorConfig, sink: &mut S) -> Result<AmortizationSummary, StreamError<S::Error>> {
+        let (loan, tally) = settled(config)?;
+        let mut payments = loan.payments();
+        while let Some(payment) = payments.try_next()? {
+            sink.on_payment(&payment).map_err(StreamError::Sink)?;
+        }
+        let summary = AmortizationSummary::of(&loan, &tally);
+        sink.finish(&summary).map_err(StreamError::Sink)?;
+        Ok(summary)
+    }
+
+    // Replays an already computed schedule into `sink`
+    pub fn write_into<S: ScheduleSink>(&self, sink: &mut S) -> Result<(), S::Error> {
+        for payment in &self.schedule {
+            sink.on_payment(payment)?;
+        }
+        sink.finish(&self.summary())
+    }
+}
+
+impl AmortizationBuilder {
+    pub fn stream<S: ScheduleSink>(&self, sink: &mut S) -> Result<AmortizationSummary, StreamError<S::Error>> {
+        Amortization::stream(self.config()?, sink)
+    }
+}
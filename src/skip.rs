@@ -0,0 +1,39 @@
+use chrono::NaiveDate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SkipTreatment {
+    // Accrued interest is added to the balance and the payment is recalculated over the original term
+    #[default]
+    Capitalize,
+    // Accrued interest is added to the balance and the term grows by one period per skip
+    ExtendTerm,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SkipPayments {
+    pub installments: Vec<u32>,
+    pub date_ranges: Vec<(NaiveDate, NaiveDate)>,
+    pub treatment: SkipTreatment,
+}
+
+impl SkipPayments {
+    pub fn new(treatment: SkipTreatment) -> Self {
+        SkipPayments { treatment, ..Default::default() }
+    }
+
+    pub fn installment(mut self, installment_number: u32) -> Self {
+        self.installments.push(installment_number);
+        self
+    }
+
+    // Skips every installment due between `from` and `to`, inclusive
+    pub fn between(mut self, from: NaiveDate, to: NaiveDate) -> Self {
+        self.date_ranges.push((from, to));
+        self
+    }
+
+    pub fn is_skipped(&self, installment_number: u32, date: Option<NaiveDate>) -> bool {
+        self.installments.contains(&installment_number)
+            || date.is_some_and(|date| self.date_ranges.iter().any(|(from, to)| *from <= date && date <= *to))
+    }
+}
@@ -0,0 +1,139 @@
+use crate::calculator::{Amortization, DEFAULT_MAX_PERIODS};
+use crate::error::AmortizationError;
+use crate::money::Currency;
+use crate::tvm::{self, PaymentTiming};
+
+const RATE_TOLERANCE: f64 = 1e-12;
+// Relative to the input, and absolute below 1
+const SEEK_TOLERANCE: f64 = 1e-12;
+const MAX_SEEK_ITERATIONS: u32 = 200;
+
+fn validate_payment(payment: f64) -> Result<(), AmortizationError> {
+    if payment <= 0.0 || !payment.is_finite() {
+        return Err(AmortizationError::InvalidPaymentAmount(payment));
+    }
+    Ok(())
+}
+
+// The input within `bounds` at which `metric` reaches `target`, by bisection, e.g. the APR
+// that makes a loan's total interest $50,000. The metric has to be on either side of the
+// target at the two bounds; for a stepped metric, such as a total rounded to cents or a term
+// in whole months, the result is where it crosses the target
+pub fn goal_seek(
+    mut metric: impl FnMut(f64) -> Result<f64, AmortizationError>,
+    target: f64,
+    bounds: (f64, f64),
+) -> Result<f64, AmortizationError> {
+    let (mut low, mut high) = if bounds.0 <= bounds.1 { bounds } else { (bounds.1, bounds.0) };
+    if !low.is_finite() || !high.is_finite() {
+        return Err(AmortizationError::NoSolution(format!("Bounds {} to {} are not finite", low, high)));
+    }
+    let mut gap = |input: f64| -> Result<f64, AmortizationError> {
+        let value = metric(input)?;
+        if !value.is_finite() {
+            return Err(AmortizationError::NoSolution(format!("Metric is not a number at {}", input)));
+        }
+        Ok(value - target)
+    };
+    let low_gap = gap(low)?;
+    let high_gap = gap(high)?;
+    if low_gap == 0.0 {
+        return Ok(low);
+    }
+    if high_gap == 0.0 {
+        return Ok(high);
+    }
+    if low_gap.signum() == high_gap.signum() {
+        return Err(AmortizationError::NoSolution(format!(
+            "Target {} is outside {} to {}, the metric at {} and {}", target, low_gap + target, high_gap + target, low, high
+        )));
+    }
+
+    for _ in 0..MAX_SEEK_ITERATIONS {
+        let mid = (low + high) / 2.0;
+        let mid_gap = gap(mid)?;
+        if mid_gap == 0.0 {
+            return Ok(mid);
+        }
+        if mid_gap.signum() == low_gap.signum() {
+            low = mid;
+        } else {
+            high = mid;
+        }
+        if high - low < SEEK_TOLERANCE * mid.abs().max(1.0) {
+            break;
+        }
+    }
+    Ok((low + high) / 2.0)
+}
+
+impl Amortization {
+    pub fn solve_principal(apr: f64, periods: u32, payment: f64) -> Result<f64, AmortizationError> {
+        Self::solve_principal_in(apr, periods, payment, Currency::default())
+    }
+
+    // The principal rounded to `currency`'s minor units
+    pub fn solve_principal_in(apr: f64, periods: u32, payment: f64, currency: Currency) -> Result<f64, AmortizationError> {
+        if periods == 0 {
+            return Err(AmortizationError::InvalidPeriods(periods));
+        }
+        if apr < 0.0 {
+            return Err(AmortizationError::InvalidInterestRate(apr));
+        }
+        validate_payment(payment)?;
+
+        let rate = apr / 100.0 / 12.0;
+        let principal = tvm::pv(rate, periods, -payment, 0.0, PaymentTiming::Arrears)?;
+        Ok(currency.round(principal))
+    }
+
+    pub fn solve_term(principal: f64, apr: f64, payment: f64) -> Result<u32, AmortizationError> {
+        if principal <= 0.0 {
+            return Err(AmortizationError::InvalidLoanAmount(principal));
+        }
+        if apr < 0.0 {
+            return Err(AmortizationError::InvalidInterestRate(apr));
+        }
+        validate_payment(payment)?;
+
+        let rate = apr / 100.0 / 12.0;
+        let periods = if rate == 0.0 {
+            (principal / payment).ceil()
+        } else {
+            if payment <= principal * rate {
+                return Err(AmortizationError::InsufficientPayment(payment));
+            }
+            let periods = tvm::nper(rate, -payment, principal, 0.0, PaymentTiming::Arrears)?;
+            // Guard against 359.9999999 becoming 360 extra periods of float noise
+            (periods - 1e-9).ceil().max(1.0)
+        };
+        if periods > DEFAULT_MAX_PERIODS as f64 {
+            let balance = -tvm::fv(rate, DEFAULT_MAX_PERIODS, -payment, principal, PaymentTiming::Arrears)?;
+            return Err(AmortizationError::NonAmortizing { max_periods: DEFAULT_MAX_PERIODS, stalled_at: None, balance });
+        }
+        Ok(periods as u32)
+    }
+
+    pub fn solve_rate(principal: f64, payment: f64, periods: u32) -> Result<f64, AmortizationError> {
+        if principal <= 0.0 {
+            return Err(AmortizationError::InvalidLoanAmount(principal));
+        }
+        if periods == 0 {
+            return Err(AmortizationError::InvalidPeriods(periods));
+        }
+        validate_payment(payment)?;
+
+        let nper = periods as f64;
+        if payment * nper < principal {
+            return Err(AmortizationError::NoSolution(format!(
+                "{} payments of {:.2} cannot repay {:.2} at a non-negative rate", periods, payment, principal
+            )));
+        }
+        if (payment * nper - principal).abs() < RATE_TOLERANCE {
+            return Ok(0.0);
+        }
+
+        let rate = tvm::rate(periods, -payment, principal, 0.0, PaymentTiming::Arrears)?;
+        Ok(rate * 12.0 * 100.0)
+    }
+}
@@ -0,0 +1,315 @@
+// Storing loans and their schedules in Postgres or SQLite behind the `sql` feature: a
+// reference schema, parameterized inserts (the schedule in as few statements as the
+// database's parameter limit allows) and rows read back through `SqlRow`, which adapts the
+// row type of whichever driver the app uses, sqlx, rusqlite or postgres, so the crate keeps
+// no extra dependencies. Amounts are stored in minor units, as exact integers
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::error::AmortizationError;
+use crate::escrow::EscrowPayment;
+use crate::frequency::PaymentFrequency;
+use crate::money::{Currency, Money};
+use crate::payment::Payment;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    Postgres,
+    Sqlite,
+}
+
+impl SqlDialect {
+    // `$1` for Postgres, `?1` for SQLite
+    pub fn placeholder(&self, index: usize) -> String {
+        match self {
+            SqlDialect::Postgres => format!("${}", index),
+            SqlDialect::Sqlite => format!("?{}", index),
+        }
+    }
+
+    // Bound parameters one statement may carry
+    pub fn max_parameters(&self) -> usize {
+        match self {
+            SqlDialect::Postgres => 65_535,
+            SqlDialect::Sqlite => 32_766,
+        }
+    }
+
+    // Integer, real, date and boolean column types
+    fn types(&self) -> (&'static str, &'static str, &'static str, &'static str) {
+        match self {
+            SqlDialect::Postgres => ("BIGINT", "DOUBLE PRECISION", "DATE", "BOOLEAN"),
+            SqlDialect::Sqlite => ("INTEGER", "REAL", "TEXT", "INTEGER"),
+        }
+    }
+}
+
+// A value to bind to a placeholder. Dates bind as the driver's date type, or ISO 8601 text
+// for SQLite
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Date(NaiveDate),
+    Bool(bool),
+}
+
+impl From<Option<NaiveDate>> for SqlValue {
+    fn from(date: Option<NaiveDate>) -> Self {
+        date.map_or(SqlValue::Null, SqlValue::Date)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlStatement {
+    pub sql: String,
+    // In placeholder order
+    pub params: Vec<SqlValue>,
+}
+
+const LOAN_COLUMNS: [&str; 12] = [
+    "id", "currency", "minor_units", "principal", "apr", "periods", "frequency",
+    "periodic_payment", "total_payment", "total_interest", "start_date", "end_date",
+];
+
+const PAYMENT_COLUMNS: [&str; 15] = [
+    "loan_id", "installment_number", "due_date", "beginning_balance", "installment_amount",
+    "interest", "principal", "remaining_balance", "capitalized_interest", "balloon_amount",
+    "fees", "skipped", "escrow_property_tax", "escrow_insurance", "escrow_mortgage_insurance",
+];
+
+// The reference `amortization_loans` and `amortization_payments` tables; payments are
+// removed with their loan
+pub fn schema(dialect: SqlDialect) -> String {
+    let (key, real, date, boolean) = dialect.types();
+    format!(
+        "CREATE TABLE IF NOT EXISTS amortization_loans (\n\
+        \x20   id {key} PRIMARY KEY,\n\
+        \x20   currency CHAR(3) NOT NULL,\n\
+        \x20   minor_units INTEGER NOT NULL,\n\
+        \x20   principal {key} NOT NULL,\n\
+        \x20   apr {real} NOT NULL,\n\
+        \x20   periods INTEGER NOT NULL,\n\
+        \x20   frequency TEXT NOT NULL,\n\
+        \x20   periodic_payment {key} NOT NULL,\n\
+        \x20   total_payment {key} NOT NULL,\n\
+        \x20   total_interest {key} NOT NULL,\n\
+        \x20   start_date {date},\n\
+        \x20   end_date {date}\n\
+        );\n\
+        CREATE TABLE IF NOT EXISTS amortization_payments (\n\
+        \x20   loan_id {key} NOT NULL REFERENCES amortization_loans (id) ON DELETE CASCADE,\n\
+        \x20   installment_number INTEGER NOT NULL,\n\
+        \x20   due_date {date},\n\
+        \x20   beginning_balance {key} NOT NULL,\n\
+        \x20   installment_amount {key} NOT NULL,\n\
+        \x20   interest {key} NOT NULL,\n\
+        \x20   principal {key} NOT NULL,\n\
+        \x20   remaining_balance {key} NOT NULL,\n\
+        \x20   capitalized_interest {key} NOT NULL,\n\
+        \x20   balloon_amount {key} NOT NULL,\n\
+        \x20   fees {key} NOT NULL,\n\
+        \x20   skipped {boolean} NOT NULL,\n\
+        \x20   escrow_property_tax {key},\n\
+        \x20   escrow_insurance {key},\n\
+        \x20   escrow_mortgage_insurance {key},\n\
+        \x20   PRIMARY KEY (loan_id, installment_number)\n\
+        );\n"
+    )
+}
+
+fn insert(dialect: SqlDialect, table: &str, columns: &[&str], rows: &[Vec<SqlValue>]) -> SqlStatement {
+    let mut index = 0;
+    let values: Vec<String> = rows.iter().map(|row| {
+        let placeholders: Vec<String> = row.iter().map(|_| {
+            index += 1;
+            dialect.placeholder(index)
+        }).collect();
+        format!("({})", placeholders.join(", "))
+    }).collect();
+    SqlStatement {
+        sql: format!("INSERT INTO {} ({}) VALUES {}", table, columns.join(", "), values.join(", ")),
+        params: rows.iter().flatten().cloned().collect(),
+    }
+}
+
+impl Amortization {
+    fn minor(&self, amount: f64) -> SqlValue {
+        SqlValue::Integer(self.money(amount).minor())
+    }
+
+    pub fn sql_insert_loan(&self, dialect: SqlDialect, loan_id: i64) -> SqlStatement {
+        let currency = self.config.currency;
+        let row = vec![
+            SqlValue::Integer(loan_id),
+            SqlValue::Text(currency.code().to_string()),
+            SqlValue::Integer(currency.minor_units() as i64),
+            self.minor(self.balance),
+            SqlValue::Real(self.config.apr),
+            SqlValue::Integer(self.periods as i64),
+            SqlValue::Text(self.config.frequency.name().to_string()),
+            self.minor(self.periodic_payment),
+            self.minor(self.total_payment),
+            self.minor(self.total_interest),
+            self.start_date.into(),
+            self.end_date.into(),
+        ];
+        insert(dialect, "amortization_loans", &LOAN_COLUMNS, &[row])
+    }
+
+    fn payment_row(&self, loan_id: i64, payment: &Payment) -> Vec<SqlValue> {
+        let escrow = |part: fn(&EscrowPayment) -> f64| payment.escrow.as_ref().map_or(SqlValue::Null, |escrow| self.minor(part(escrow)));
+        vec![
+            SqlValue::Integer(loan_id),
+            SqlValue::Integer(payment.installment_number as i64),
+            payment.date.into(),
+            self.minor(payment.beginning_balance),
+            self.minor(payment.installment_amount),
+            self.minor(payment.interest),
+            self.minor(payment.principal),
+            self.minor(payment.remaining_balance),
+            self.minor(payment.capitalized_interest),
+            self.minor(payment.balloon_amount),
+            self.minor(payment.fees),
+            SqlValue::Bool(payment.skipped),
+            escrow(|escrow| escrow.property_tax),
+            escrow(|escrow| escrow.insurance),
+            escrow(|escrow| escrow.mortgage_insurance),
+        ]
+    }
+
+    // The whole schedule as multi-row inserts, split only where a statement would pass the
+    // dialect's parameter limit; a 30 year monthly loan is a single statement
+    pub fn sql_insert_schedule(&self, dialect: SqlDialect, loan_id: i64) -> Vec<SqlStatement> {
+        let rows_per_statement = dialect.max_parameters() / PAYMENT_COLUMNS.len();
+        let rows: Vec<Vec<SqlValue>> = self.schedule.iter().map(|payment| self.payment_row(loan_id, payment)).collect();
+        rows.chunks(rows_per_statement)
+            .map(|chunk| insert(dialect, "amortization_payments", &PAYMENT_COLUMNS, chunk))
+            .collect()
+    }
+
+    // The loan followed by its schedule, to run in one transaction
+    pub fn sql_inserts(&self, dialect: SqlDialect, loan_id: i64) -> Vec<SqlStatement> {
+        let mut statements = vec![self.sql_insert_loan(dialect, loan_id)];
+        statements.extend(self.sql_insert_schedule(dialect, loan_id));
+        statements
+    }
+}
+
+// Column access on a driver's row type; implement it for `sqlx::postgres::PgRow`,
+// `rusqlite::Row` and the like. Each getter returns None for NULL
+pub trait SqlRow {
+    fn integer(&self, column: &str) -> Result<Option<i64>, AmortizationError>;
+    fn real(&self, column: &str) -> Result<Option<f64>, AmortizationError>;
+    fn text(&self, column: &str) -> Result<Option<String>, AmortizationError>;
+
+    // SQLite keeps dates as text; drivers with a date type can override this
+    fn date(&self, column: &str) -> Result<Option<NaiveDate>, AmortizationError> {
+        self.text(column)?
+            .map(|text| NaiveDate::parse_from_str(&text, "%Y-%m-%d").map_err(|_| invalid_row(column, "a YYYY-MM-DD date")))
+            .transpose()
+    }
+
+    // Postgres booleans arrive as integers from SQLite
+    fn boolean(&self, column: &str) -> Result<Option<bool>, AmortizationError> {
+        Ok(self.integer(column)?.map(|value| value != 0))
+    }
+}
+
+fn invalid_row(column: &str, expected: &str) -> AmortizationError {
+    AmortizationError::InvalidDatabaseRow(format!("'{}' must be {}", column, expected))
+}
+
+fn required<T>(column: &str, value: Option<T>) -> Result<T, AmortizationError> {
+    value.ok_or_else(|| invalid_row(column, "set"))
+}
+
+fn count(row: &impl SqlRow, column: &str) -> Result<u32, AmortizationError> {
+    u32::try_from(required(column, row.integer(column)?)?).map_err(|_| invalid_row(column, "a whole number"))
+}
+
+fn amount(row: &impl SqlRow, column: &str, currency: Currency) -> Result<f64, AmortizationError> {
+    Ok(Money::from_minor(required(column, row.integer(column)?)?, currency).to_f64())
+}
+
+fn optional_amount(row: &impl SqlRow, column: &str, currency: Currency) -> Result<Option<f64>, AmortizationError> {
+    Ok(row.integer(column)?.map(|minor| Money::from_minor(minor, currency).to_f64()))
+}
+
+// A row of `amortization_loans`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoanRecord {
+    pub id: i64,
+    pub currency: Currency,
+    pub principal: f64,
+    pub apr: f64,
+    pub periods: u32,
+    pub frequency: PaymentFrequency,
+    pub periodic_payment: f64,
+    pub total_payment: f64,
+    pub total_interest: f64,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+}
+
+impl LoanRecord {
+    pub fn from_row(row: &impl SqlRow) -> Result<LoanRecord, AmortizationError> {
+        let code = required("currency", row.text("currency")?)?;
+        let code: [u8; 3] = code.as_bytes().try_into().map_err(|_| invalid_row("currency", "a three letter code"))?;
+        let currency = Currency::try_new(code, count(row, "minor_units")?)
+            .ok_or_else(|| invalid_row("minor_units", "at most 6"))?;
+        let frequency = required("frequency", row.text("frequency")?)?;
+        Ok(LoanRecord {
+            id: required("id", row.integer("id")?)?,
+            currency,
+            principal: amount(row, "principal", currency)?,
+            apr: required("apr", row.real("apr")?)?,
+            periods: count(row, "periods")?,
+            frequency: PaymentFrequency::from_name(&frequency).ok_or_else(|| invalid_row("frequency", "a payment frequency"))?,
+            periodic_payment: amount(row, "periodic_payment", currency)?,
+            total_payment: amount(row, "total_payment", currency)?,
+            total_interest: amount(row, "total_interest", currency)?,
+            start_date: row.date("start_date")?,
+            end_date: row.date("end_date")?,
+        })
+    }
+}
+
+impl Payment {
+    // A row of `amortization_payments` for a loan in `currency`. Columns the table doesn't
+    // keep (forbearance, LTV, inflation and accrual details) are left empty
+    pub fn from_row(row: &impl SqlRow, currency: Currency) -> Result<Payment, AmortizationError> {
+        let escrow = match (
+            optional_amount(row, "escrow_property_tax", currency)?,
+            optional_amount(row, "escrow_insurance", currency)?,
+            optional_amount(row, "escrow_mortgage_insurance", currency)?,
+        ) {
+            (None, None, None) => None,
+            (property_tax, insurance, mortgage_insurance) => Some(EscrowPayment {
+                property_tax: property_tax.unwrap_or(0.0),
+                insurance: insurance.unwrap_or(0.0),
+                mortgage_insurance: mortgage_insurance.unwrap_or(0.0),
+            }),
+        };
+        Ok(Payment {
+            installment_number: count(row, "installment_number")?,
+            beginning_balance: amount(row, "beginning_balance", currency)?,
+            interest: amount(row, "interest", currency)?,
+            principal: amount(row, "principal", currency)?,
+            date: row.date("due_date")?,
+            remaining_balance: amount(row, "remaining_balance", currency)?,
+            installment_amount: amount(row, "installment_amount", currency)?,
+            balloon_amount: amount(row, "balloon_amount", currency)?,
+            capitalized_interest: amount(row, "capitalized_interest", currency)?,
+            accrued_interest: 0.0,
+            deferred_amount: 0.0,
+            fees: amount(row, "fees", currency)?,
+            skipped: required("skipped", row.boolean("skipped")?)?,
+            escrow,
+            ltv: None,
+            real: None,
+            accrual: None,
+        })
+    }
+}
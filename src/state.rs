@@ -0,0 +1,419 @@
+// Where a loan stands between installments, for servicing apps that persist it between runs
+// and pick the schedule up again later without regenerating the history behind it
+use chrono::{Datelike, NaiveDate};
+use crate::calculator::{Amortization, DEFAULT_MAX_PERIODS};
+use crate::calendar::{BusinessDayConvention, DateRollConvention};
+use crate::day_count::DayCount;
+use crate::error::AmortizationError;
+use crate::frequency::PaymentFrequency;
+use crate::interest::InterestMethod;
+use crate::json::JsonValue;
+use crate::loan_type::{CapitalizationFrequency, Deferment, FixedPayment, LoanType};
+use crate::money::{Currency, RoundingMode, RoundingPolicy};
+use crate::rate::{RateConvention, RatePeriod};
+use crate::tvm::PaymentTiming;
+use crate::units::Rate;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoanState {
+    pub installments_paid: u32,
+    pub balance: f64,
+    // Interest owed but neither paid nor capitalized; resuming capitalizes it
+    pub accrued_interest: f64,
+    // None for undated schedules
+    pub next_due_date: Option<NaiveDate>,
+    // Day of the month installments fall due on at month-based frequencies. A short month
+    // moves its installment to the last day, so `next_due_date` can fall earlier than this
+    pub due_day: Option<u32>,
+    pub remaining_periods: u32,
+    pub periodic_payment: f64,
+    pub frequency: PaymentFrequency,
+    // Rates still to apply, numbered from the next installment as 1
+    pub rate_schedule: Vec<RatePeriod>,
+    // Interest-only periods still to come when the loan has them
+    pub loan_type: LoanType,
+    pub day_count: Option<DayCount>,
+    pub payment_timing: PaymentTiming,
+    pub extra_payment: f64,
+    // Deferred periods still to come
+    pub deferment: Option<Deferment>,
+    pub capitalization: Option<CapitalizationFrequency>,
+    pub currency: Currency,
+    pub rounding: RoundingPolicy,
+}
+
+fn invalid(key: &str, expected: &str) -> AmortizationError {
+    AmortizationError::InvalidLoanState(format!("'{}' must be {}", key, expected))
+}
+
+fn too_many_periods(remaining_periods: u32) -> AmortizationError {
+    AmortizationError::InvalidLoanState(format!("the remaining term must be at most {} periods, got {}", DEFAULT_MAX_PERIODS, remaining_periods))
+}
+
+fn field<'a>(value: &'a JsonValue, key: &'static str) -> Result<&'a JsonValue, AmortizationError> {
+    value.get(key).ok_or(AmortizationError::MissingParameter(key))
+}
+
+// Settings a state saved before they were recorded leaves out take their defaults
+fn optional<'a>(value: &'a JsonValue, key: &str) -> Option<&'a JsonValue> {
+    value.get(key).filter(|v| !matches!(v, JsonValue::Null))
+}
+
+fn number(value: &JsonValue, key: &'static str) -> Result<f64, AmortizationError> {
+    match field(value, key)? {
+        JsonValue::Number(n) => Ok(*n),
+        _ => Err(invalid(key, "a number")),
+    }
+}
+
+fn whole_number(value: &JsonValue, key: &'static str) -> Result<u32, AmortizationError> {
+    let n = number(value, key)?;
+    if n < 0.0 || n.fract() != 0.0 || n > u32::MAX as f64 {
+        return Err(invalid(key, "a whole number"));
+    }
+    Ok(n as u32)
+}
+
+const DAY_COUNTS: [(DayCount, &str); 3] = [
+    (DayCount::Thirty360, "30/360"),
+    (DayCount::Actual365, "actual/365"),
+    (DayCount::Actual360, "actual/360"),
+];
+
+const PAYMENT_TIMINGS: [(PaymentTiming, &str); 2] = [(PaymentTiming::Arrears, "arrears"), (PaymentTiming::Advance, "advance")];
+
+const CAPITALIZATIONS: [(CapitalizationFrequency, &str); 5] = [
+    (CapitalizationFrequency::EveryPeriod, "every_period"),
+    (CapitalizationFrequency::Monthly, "monthly"),
+    (CapitalizationFrequency::Quarterly, "quarterly"),
+    (CapitalizationFrequency::Annually, "annually"),
+    (CapitalizationFrequency::AtEndOfEvent, "at_end_of_event"),
+];
+
+const ROUNDING_MODES: [(RoundingMode, &str); 3] = [
+    (RoundingMode::HalfUp, "half_up"),
+    (RoundingMode::HalfEven, "half_even"),
+    (RoundingMode::Truncate, "truncate"),
+];
+
+fn name_of<T: PartialEq>(names: &[(T, &'static str)], item: &T) -> &'static str {
+    names.iter().find(|(t, _)| t == item).map_or("", |(_, name)| name)
+}
+
+fn named<T: Copy>(value: &JsonValue, key: &'static str, names: &[(T, &str)]) -> Result<Option<T>, AmortizationError> {
+    let found = match optional(value, key) {
+        None => return Ok(None),
+        Some(JsonValue::String(name)) => names.iter().find(|(_, n)| n == name).map(|(t, _)| *t),
+        Some(_) => None,
+    };
+    found.map(Some).ok_or_else(|| {
+        let names: Vec<_> = names.iter().map(|(_, n)| *n).collect();
+        invalid(key, &format!("one of {}", names.join(", ")))
+    })
+}
+
+// Settings `resume` can't rebuild the rest of the schedule with; a state taken from a loan
+// that has one would resume as a different loan
+fn unsupported(loan: &Amortization, period: u32) -> Option<&'static str> {
+    let config = &loan.config;
+    let resets_ahead = loan.rate_schedule.iter().any(|r| r.start_period > period + 1);
+    [
+        (config.balloon_period.is_some(), "a balloon payment"),
+        (config.payment_cap.is_some(), "a payment cap"),
+        (config.skip_payments.is_some(), "skipped payments"),
+        (config.graduated_payment.is_some(), "a graduated payment"),
+        (config.escrow.is_some(), "escrow"),
+        (!config.fees.is_empty(), "fees"),
+        (!config.prepayments.is_empty(), "prepayments"),
+        (config.property.is_some(), "a property value"),
+        (config.inflation_rate.is_some(), "an inflation rate"),
+        (config.prepayment_penalty.is_some(), "a prepayment penalty"),
+        (config.rate_convention != RateConvention::Nominal, "a rate convention other than nominal"),
+        (config.interest_method != InterestMethod::Actuarial || config.interest_calculator.is_some(), "an interest method other than actuarial"),
+        (!matches!(config.date_roll, DateRollConvention::SameDay | DateRollConvention::DayOfMonthOrLast(_)), "a date roll convention"),
+        (config.business_day_convention != BusinessDayConvention::Unadjusted, "a business day convention"),
+        (period == 0 && config.funding_date.is_some(), "a funding date"),
+        (config.fixed_payment.is_some_and(|f| f.allow_negative_amortization || resets_ahead), "a fixed payment through rate resets"),
+    ].into_iter().find(|(set, _)| *set).map(|(_, name)| name)
+}
+
+impl LoanState {
+    pub fn with_accrued_interest(mut self, accrued_interest: f64) -> Self {
+        self.accrued_interest = accrued_interest;
+        self
+    }
+
+    pub fn is_paid_off(&self) -> bool {
+        self.remaining_periods == 0 || self.balance + self.accrued_interest <= 0.0
+    }
+
+    pub fn to_json_value(&self) -> JsonValue {
+        let loan_type = match self.loan_type {
+            LoanType::Amortizing => "amortizing",
+            LoanType::InterestOnly { .. } => "interest_only",
+            LoanType::EqualPrincipal => "equal_principal",
+        };
+        JsonValue::object(vec![
+            ("installments_paid", self.installments_paid.into()),
+            ("balance", self.balance.into()),
+            ("accrued_interest", self.accrued_interest.into()),
+            ("next_due_date", self.next_due_date.map(|d| d.format("%Y-%m-%d").to_string()).into()),
+            ("due_day", self.due_day.into()),
+            ("remaining_periods", self.remaining_periods.into()),
+            ("periodic_payment", self.periodic_payment.into()),
+            ("frequency", self.frequency.name().into()),
+            ("rate_schedule", JsonValue::Array(self.rate_schedule.iter().map(|r| JsonValue::object(vec![
+                ("start_period", r.start_period.into()),
+                ("apr", r.apr.into()),
+            ])).collect())),
+            ("loan_type", loan_type.into()),
+            ("interest_only_periods", self.loan_type.interest_only_periods().into()),
+            ("day_count", self.day_count.map(|d| name_of(&DAY_COUNTS, &d)).into()),
+            ("payment_timing", name_of(&PAYMENT_TIMINGS, &self.payment_timing).into()),
+            ("extra_payment", self.extra_payment.into()),
+            ("deferment", self.deferment.map(|d| JsonValue::object(vec![
+                ("type", if d.accrues_interest() { "unsubsidized" } else { "subsidized" }.into()),
+                ("periods", d.periods().into()),
+            ])).into()),
+            ("capitalization", self.capitalization.map(|c| name_of(&CAPITALIZATIONS, &c)).into()),
+            ("currency", self.currency.code().into()),
+            ("minor_units", self.currency.minor_units().into()),
+            ("rounding", JsonValue::object(vec![
+                ("per_period", self.rounding.per_period.into()),
+                ("mode", name_of(&ROUNDING_MODES, &self.rounding.mode).into()),
+            ])),
+        ])
+    }
+
+    pub fn to_json(&self) -> String {
+        self.to_json_value().to_string()
+    }
+
+    // Reads the document `to_json` writes. Amounts are kept at full precision so a state read
+    // back resumes exactly where it was saved
+    pub fn from_json(input: &str) -> Result<LoanState, AmortizationError> {
+        let value = JsonValue::parse(input).map_err(AmortizationError::InvalidLoanState)?;
+        let next_due_date = match field(&value, "next_due_date")? {
+            JsonValue::Null => None,
+            JsonValue::String(date) => Some(NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| invalid("next_due_date", "a YYYY-MM-DD date"))?),
+            _ => return Err(invalid("next_due_date", "a YYYY-MM-DD date or null")),
+        };
+        let due_day = match optional(&value, "due_day") {
+            None => None,
+            Some(_) => match whole_number(&value, "due_day")? {
+                day @ 1..=31 => Some(day),
+                _ => return Err(invalid("due_day", "a day of the month")),
+            },
+        };
+        let frequency = match field(&value, "frequency")? {
+            JsonValue::String(name) => PaymentFrequency::from_name(name),
+            _ => None,
+        }.ok_or_else(|| invalid("frequency", "weekly, biweekly, monthly, quarterly, semiannually or annually"))?;
+        let rate_schedule = match field(&value, "rate_schedule")? {
+            JsonValue::Array(rates) => rates.iter()
+                .map(|rate| Ok(RatePeriod::new(whole_number(rate, "start_period")?, number(rate, "apr")?)))
+                .collect::<Result<Vec<_>, AmortizationError>>()?,
+            _ => return Err(invalid("rate_schedule", "an array")),
+        };
+        if rate_schedule.first().is_none_or(|r| r.start_period != 1) {
+            return Err(invalid("rate_schedule", "a rate from period 1"));
+        }
+
+        let loan_type = match optional(&value, "loan_type") {
+            None => LoanType::Amortizing,
+            Some(JsonValue::String(name)) if name == "amortizing" => LoanType::Amortizing,
+            Some(JsonValue::String(name)) if name == "equal_principal" => LoanType::EqualPrincipal,
+            Some(JsonValue::String(name)) if name == "interest_only" => LoanType::InterestOnly { io_periods: whole_number(&value, "interest_only_periods")? },
+            Some(_) => return Err(invalid("loan_type", "amortizing, interest_only or equal_principal")),
+        };
+        let deferment = optional(&value, "deferment").map(|deferment| {
+            let periods = whole_number(deferment, "periods")?;
+            match field(deferment, "type")? {
+                JsonValue::String(name) if name == "subsidized" => Ok(Deferment::Subsidized { periods }),
+                JsonValue::String(name) if name == "unsubsidized" => Ok(Deferment::Unsubsidized { periods }),
+                _ => Err(invalid("type", "subsidized or unsubsidized")),
+            }
+        }).transpose()?;
+        let currency = match optional(&value, "currency") {
+            None => Currency::default(),
+            Some(JsonValue::String(code)) => {
+                let code: [u8; 3] = code.as_bytes().try_into().map_err(|_| invalid("currency", "a three letter code"))?;
+                Currency::try_new(code, whole_number(&value, "minor_units")?)
+                    .ok_or_else(|| invalid("minor_units", "at most 6"))?
+            }
+            Some(_) => return Err(invalid("currency", "a three letter code")),
+        };
+        let rounding = match optional(&value, "rounding") {
+            None => RoundingPolicy::default(),
+            Some(rounding) => RoundingPolicy {
+                per_period: match field(rounding, "per_period")? {
+                    JsonValue::Bool(per_period) => *per_period,
+                    _ => return Err(invalid("per_period", "true or false")),
+                },
+                mode: named(rounding, "mode", &ROUNDING_MODES)?.unwrap_or_default(),
+            },
+        };
+
+        let remaining_periods = whole_number(&value, "remaining_periods")?;
+        if remaining_periods > DEFAULT_MAX_PERIODS {
+            return Err(too_many_periods(remaining_periods));
+        }
+
+        Ok(LoanState {
+            installments_paid: whole_number(&value, "installments_paid")?,
+            balance: number(&value, "balance")?,
+            accrued_interest: number(&value, "accrued_interest")?,
+            next_due_date,
+            due_day,
+            remaining_periods,
+            periodic_payment: number(&value, "periodic_payment")?,
+            frequency,
+            rate_schedule,
+            loan_type,
+            day_count: named(&value, "day_count", &DAY_COUNTS)?,
+            payment_timing: named(&value, "payment_timing", &PAYMENT_TIMINGS)?.unwrap_or_default(),
+            extra_payment: match optional(&value, "extra_payment") {
+                Some(_) => number(&value, "extra_payment")?,
+                None => 0.0,
+            },
+            deferment,
+            capitalization: named(&value, "capitalization", &CAPITALIZATIONS)?,
+            currency,
+            rounding,
+        })
+    }
+}
+
+impl Amortization {
+    // The loan once `period` installments have been paid; 0 is the loan as originated. Loans
+    // with settings a state doesn't carry, such as fees, escrow or prepayments, can't be
+    // snapshotted
+    pub fn snapshot_at(&self, period: u32) -> Result<LoanState, AmortizationError> {
+        let balance = self.balance_after(period).ok_or(AmortizationError::PeriodOutOfRange(period))?;
+        if let Some(setting) = unsupported(self, period) {
+            return Err(AmortizationError::InvalidLoanState(format!("a loan with {} can't be resumed from a saved state", setting)));
+        }
+        let next = self.schedule.get(period as usize);
+        let current = self.rate_schedule.iter()
+            .rev()
+            .find(|r| r.start_period <= period + 1)
+            .map_or(self.config.apr, |r| r.apr);
+        let mut rate_schedule = vec![RatePeriod::new(1, current)];
+        rate_schedule.extend(self.rate_schedule.iter()
+            .filter(|r| r.start_period > period + 1)
+            .map(|r| RatePeriod::new(r.start_period - period, r.apr)));
+        // The installment without the extra payment, or all of it once the extra alone would
+        // repay what is left
+        let periodic_payment = next.map_or(0.0, |p| {
+            let level = p.installment_amount - p.fees - p.escrow_amount() - self.config.extra_payment;
+            if level > p.interest { level } else { p.installment_amount }
+        });
+        let loan_type = match self.config.loan_type {
+            LoanType::InterestOnly { io_periods } if io_periods > period => LoanType::InterestOnly { io_periods: io_periods - period },
+            LoanType::InterestOnly { .. } => LoanType::Amortizing,
+            other => other,
+        };
+        let deferment = self.config.deferment
+            .filter(|d| d.periods() > period)
+            .map(|d| match d {
+                Deferment::Subsidized { periods } => Deferment::Subsidized { periods: periods - period },
+                Deferment::Unsubsidized { periods } => Deferment::Unsubsidized { periods: periods - period },
+            });
+        let due_day = match self.config.date_roll {
+            _ if self.config.frequency.months_per_period() == 0 => None,
+            DateRollConvention::DayOfMonthOrLast(day) => Some(day),
+            _ => self.config.start_date.map(|start| start.day()),
+        };
+        Ok(LoanState {
+            installments_paid: period,
+            balance,
+            accrued_interest: period.checked_sub(1).and_then(|n| self.schedule.get(n as usize)).map_or(0.0, |p| p.accrued_interest),
+            next_due_date: next.and_then(|p| p.date),
+            due_day,
+            remaining_periods: self.schedule.len() as u32 - period,
+            periodic_payment,
+            frequency: self.config.frequency,
+            rate_schedule,
+            loan_type,
+            day_count: self.config.day_count,
+            // Past the first installment, each one falls due at the end of a period the balance
+            // has accrued interest over, whenever the first was paid
+            payment_timing: if period == 0 { self.config.payment_timing } else { PaymentTiming::Arrears },
+            extra_payment: self.config.extra_payment,
+            deferment,
+            capitalization: self.config.capitalization,
+            currency: self.config.currency,
+            rounding: self.config.rounding,
+        })
+    }
+
+    // The rest of the schedule from a saved state, numbered from the next installment as 1.
+    // The term is kept when it is a whole number of months at the loan's frequency; otherwise
+    // it follows from the installment, which only a level installment can do
+    pub fn resume(state: LoanState) -> Result<Amortization, AmortizationError> {
+        if state.is_paid_off() {
+            return Err(AmortizationError::InvalidLoanState("the loan is paid off".to_string()));
+        }
+        if state.remaining_periods > DEFAULT_MAX_PERIODS {
+            return Err(too_many_periods(state.remaining_periods));
+        }
+        let (first, later) = state.rate_schedule.split_first()
+            .ok_or_else(|| invalid("rate_schedule", "a rate from period 1"))?;
+        let per_year = state.frequency.periods_per_year();
+        // Calendar capitalization counts its periods from the first installment, so resuming
+        // part way through one would capitalize on different installments
+        let capitalization_aligned = state.installments_paid == 0
+            || state.capitalization.is_none_or(|c| c.capitalizes_at(state.installments_paid, per_year));
+        if state.deferment.is_some_and(|d| d.accrues_interest()) && !capitalization_aligned {
+            return Err(AmortizationError::InvalidLoanState("the state falls part way through a capitalization period".to_string()));
+        }
+        let mut builder = Amortization::builder()
+            .principal(state.balance + state.accrued_interest)
+            .rate(Rate::percent(first.apr))
+            .frequency(state.frequency)
+            .loan_type(state.loan_type)
+            .payment_timing(state.payment_timing)
+            .extra_payment(state.extra_payment)
+            .currency(state.currency)
+            .rounding(state.rounding);
+        if !later.is_empty() {
+            builder = builder.rate_schedule(state.rate_schedule.clone());
+        }
+        if let Some(date) = state.next_due_date {
+            builder = builder.start_date(date);
+        }
+        // Due dates, and the day interest starts accruing for the next installment, follow the
+        // original day of the month rather than a short month's last day
+        if let Some(day) = state.due_day {
+            builder = builder.date_roll(DateRollConvention::DayOfMonthOrLast(day));
+        }
+        if let Some(day_count) = state.day_count {
+            builder = builder.day_count(day_count);
+        }
+        if let Some(deferment) = state.deferment {
+            builder = builder.deferment(deferment);
+        }
+        if let Some(capitalization) = state.capitalization {
+            builder = builder.capitalization(capitalization);
+        }
+        let months = state.remaining_periods.checked_mul(12)
+            .ok_or_else(|| too_many_periods(state.remaining_periods))?
+            .div_ceil(per_year);
+        let whole_months = state.frequency.periods_for_months(months) == state.remaining_periods;
+        if whole_months {
+            builder = builder.term_months(months);
+        }
+        let level = state.loan_type == LoanType::Amortizing && state.deferment.is_none();
+        // An installment recomputed from the balance can be a cent off the one being paid, but
+        // rate resets still need to recompute it, and so do installments that aren't level yet.
+        // Extra payments shorten the schedule, so recomputing over what is left would raise it
+        if level && (later.is_empty() || !whole_months) {
+            builder = builder.fixed_payment(FixedPayment::new(state.periodic_payment));
+        } else if !whole_months {
+            return Err(AmortizationError::InvalidLoanState("the remaining term must be a whole number of months".to_string()));
+        } else if state.extra_payment > 0.0 {
+            return Err(AmortizationError::InvalidLoanState("extra payments can only resume at a level installment without rate resets".to_string()));
+        }
+        builder.build()
+    }
+}
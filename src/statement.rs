@@ -0,0 +1,382 @@
+// Reconciliation against a schedule the lender provides, for working out why our numbers
+// don't match the bank's. The lender's CSV is read by its header, whatever the column order
+// or naming, each installment is compared field by field within a tolerance, and patterns in
+// the differences are flagged as the convention the lender most likely follows instead
+use std::fmt;
+use std::io::BufRead;
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::error::AmortizationError;
+use crate::history::{parse_amount, parse_date, split_fields};
+use crate::payment::Payment;
+
+// Largest difference still put down to rounding
+const PENNIES: f64 = 0.05;
+// Spread of interest ratios beyond which they follow the period length rather than a rate
+const RATIO_SPREAD: f64 = 0.001;
+
+// One installment as the lender states it; columns the statement lacks are None
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatementRow {
+    pub installment_number: Option<u32>,
+    pub date: Option<NaiveDate>,
+    pub payment: Option<f64>,
+    pub interest: Option<f64>,
+    pub principal: Option<f64>,
+    pub fees: Option<f64>,
+    pub balance: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReconcileOptions {
+    // Amounts further apart than this differ
+    pub tolerance: f64,
+    // Due dates further apart than this many days differ
+    pub date_tolerance_days: i64,
+}
+
+impl Default for ReconcileOptions {
+    fn default() -> Self {
+        ReconcileOptions { tolerance: 0.01, date_tolerance_days: 0 }
+    }
+}
+
+impl ReconcileOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_date_tolerance(mut self, days: i64) -> Self {
+        self.date_tolerance_days = days;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldDifference {
+    pub field: &'static str,
+    pub ours: f64,
+    pub theirs: f64,
+}
+
+impl FieldDifference {
+    // Positive when the lender's figure is higher
+    pub fn difference(&self) -> f64 {
+        self.theirs - self.ours
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowReconciliation {
+    pub installment_number: u32,
+    pub our_date: Option<NaiveDate>,
+    pub their_date: Option<NaiveDate>,
+    // Days the lender's due date is after ours, when beyond the date tolerance
+    pub date_difference_days: Option<i64>,
+    pub differences: Vec<FieldDifference>,
+}
+
+impl RowReconciliation {
+    pub fn is_match(&self) -> bool {
+        self.date_difference_days.is_none() && self.differences.is_empty()
+    }
+
+    pub fn difference(&self, field: &str) -> Option<&FieldDifference> {
+        self.differences.iter().find(|difference| difference.field == field)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LenderConvention {
+    Rounding,
+    DayCount,
+    Rate,
+    ApplicationOrder,
+}
+
+impl LenderConvention {
+    pub fn description(&self) -> &'static str {
+        match self {
+            LenderConvention::Rounding => "differences of a few cents: the lender rounds interest or payments differently",
+            LenderConvention::DayCount => "interest on the same balance differs with the length of the period: the lender counts days differently",
+            LenderConvention::Rate => "interest on the same balance differs in a fixed proportion: the lender uses a different periodic rate or compounding",
+            LenderConvention::ApplicationOrder => "the payment and interest agree but the principal does not: the lender applied part of the payment elsewhere first, such as fees or arrears",
+        }
+    }
+}
+
+// A convention the differences point to, with the installments that show it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConventionHint {
+    pub convention: LenderConvention,
+    pub installments: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reconciliation {
+    pub rows: Vec<RowReconciliation>,
+    // Installments in our schedule the statement leaves out
+    pub missing: Vec<u32>,
+    // Statement rows with no installment of ours to compare against
+    pub unexpected: Vec<u32>,
+    pub hints: Vec<ConventionHint>,
+}
+
+impl Reconciliation {
+    pub fn is_match(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty() && self.rows.iter().all(RowReconciliation::is_match)
+    }
+
+    pub fn mismatched_rows(&self) -> impl Iterator<Item = &RowReconciliation> {
+        self.rows.iter().filter(|row| !row.is_match())
+    }
+
+    pub fn has_hint(&self, convention: LenderConvention) -> bool {
+        self.hints.iter().any(|hint| hint.convention == convention)
+    }
+}
+
+impl fmt::Display for Reconciliation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Compared {} installments, {} differ", self.rows.len(), self.mismatched_rows().count())?;
+        for row in self.mismatched_rows() {
+            write!(f, "Installment {}:", row.installment_number)?;
+            if let Some(days) = row.date_difference_days {
+                write!(f, " date {:?} vs {:?} ({:+} days);", row.our_date, row.their_date, days)?;
+            }
+            for difference in &row.differences {
+                write!(f, " {} ours {:.2}, theirs {:.2} ({:+.2});", difference.field, difference.ours, difference.theirs, difference.difference())?;
+            }
+            writeln!(f)?;
+        }
+        if !self.missing.is_empty() {
+            writeln!(f, "Missing from the statement: {:?}", self.missing)?;
+        }
+        if !self.unexpected.is_empty() {
+            writeln!(f, "Not in our schedule: {:?}", self.unexpected)?;
+        }
+        for hint in &self.hints {
+            writeln!(f, "Likely {:?}: {} ({} installments from {})", hint.convention, hint.convention.description(),
+                hint.installments.len(), hint.installments[0])?;
+        }
+        Ok(())
+    }
+}
+
+fn statement_error(line_number: usize, message: &str) -> AmortizationError {
+    AmortizationError::InvalidStatement(format!("line {}: {}", line_number, message))
+}
+
+#[derive(Debug, Default)]
+struct Columns {
+    installment_number: Option<usize>,
+    date: Option<usize>,
+    payment: Option<usize>,
+    interest: Option<usize>,
+    principal: Option<usize>,
+    fees: Option<usize>,
+    balance: Option<usize>,
+}
+
+impl Columns {
+    // Lenders name columns all sorts of ways; case, spaces and punctuation are ignored
+    fn from_header(fields: &[String]) -> Self {
+        let mut columns = Columns::default();
+        for (index, field) in fields.iter().enumerate() {
+            let name: String = field.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_ascii_lowercase();
+            let column = match name.as_str() {
+                _ if field == "#" => &mut columns.installment_number,
+                "installment" | "installmentnumber" | "paymentnumber" | "period" | "no" | "number" => &mut columns.installment_number,
+                "date" | "duedate" | "paymentdate" => &mut columns.date,
+                "payment" | "amount" | "paymentamount" | "installmentamount" | "totalpayment" => &mut columns.payment,
+                "interest" | "interestpaid" => &mut columns.interest,
+                "principal" | "principalpaid" => &mut columns.principal,
+                "fees" | "fee" | "charges" => &mut columns.fees,
+                "balance" | "remainingbalance" | "endingbalance" | "closingbalance" | "principalbalance" => &mut columns.balance,
+                _ => continue,
+            };
+            column.get_or_insert(index);
+        }
+        columns
+    }
+
+    fn has_amounts(&self) -> bool {
+        [self.payment, self.interest, self.principal, self.fees, self.balance].iter().any(Option::is_some)
+    }
+}
+
+// The rows of a lender's CSV schedule, which must open with a header naming its columns
+pub fn read_statement<R: BufRead>(reader: R) -> Result<Vec<StatementRow>, AmortizationError> {
+    let mut columns: Option<Columns> = None;
+    let mut rows = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.map_err(|error| statement_error(line_number, &error.to_string()))?;
+        let fields = split_fields(&line);
+        if fields.iter().all(String::is_empty) {
+            continue;
+        }
+        let Some(columns) = &columns else {
+            let header = Columns::from_header(&fields);
+            if !header.has_amounts() {
+                return Err(statement_error(line_number, "the header names no payment, interest, principal, fees or balance column"));
+            }
+            columns = Some(header);
+            continue;
+        };
+        let field = |column: Option<usize>| column.and_then(|c| fields.get(c)).map(String::as_str).filter(|f| !f.is_empty());
+        let amount = |column: Option<usize>| field(column)
+            .map(|f| parse_amount(f).ok_or_else(|| statement_error(line_number, &format!("invalid amount '{}'", f))))
+            .transpose();
+        rows.push(StatementRow {
+            installment_number: field(columns.installment_number)
+                .map(|f| f.parse().map_err(|_| statement_error(line_number, &format!("invalid installment '{}'", f))))
+                .transpose()?,
+            date: field(columns.date)
+                .map(|f| parse_date(f).ok_or_else(|| statement_error(line_number, &format!("invalid date '{}'", f))))
+                .transpose()?,
+            payment: amount(columns.payment)?,
+            interest: amount(columns.interest)?,
+            principal: amount(columns.principal)?,
+            fees: amount(columns.fees)?,
+            balance: amount(columns.balance)?,
+        });
+    }
+    if columns.is_none() {
+        return Err(AmortizationError::InvalidStatement("the statement is empty".to_string()));
+    }
+    Ok(rows)
+}
+
+fn compare_row(ours: &Payment, theirs: &StatementRow, options: &ReconcileOptions) -> RowReconciliation {
+    let date_difference_days = match (ours.date, theirs.date) {
+        (Some(our_date), Some(their_date)) => Some((their_date - our_date).num_days()).filter(|days| days.abs() > options.date_tolerance_days),
+        _ => None,
+    };
+    let fields = [
+        ("payment", ours.installment_amount, theirs.payment),
+        ("interest", ours.interest, theirs.interest),
+        ("principal", ours.principal, theirs.principal),
+        ("fees", ours.fees, theirs.fees),
+        ("balance", ours.remaining_balance, theirs.balance),
+    ];
+    RowReconciliation {
+        installment_number: ours.installment_number,
+        our_date: ours.date,
+        their_date: theirs.date,
+        date_difference_days,
+        differences: fields.into_iter()
+            .filter_map(|(field, ours, theirs)| Some(FieldDifference { field, ours, theirs: theirs? }))
+            // A small allowance so amounts that print the same never differ
+            .filter(|difference| difference.difference().abs() > options.tolerance + 1e-9)
+            .collect(),
+    }
+}
+
+// What a row's differences point to. Each row is judged on the lender's own figures, so
+// differences carried over from earlier rows aren't put down to the row again
+enum Finding {
+    // The lender's interest over the interest our rate gives on the lender's balance
+    Interest(f64),
+    ApplicationOrder,
+    Rounding,
+}
+
+fn classify(row: &RowReconciliation, ours: &Payment, theirs: &StatementRow, their_opening: f64) -> Option<Finding> {
+    if row.differences.is_empty() {
+        return None;
+    }
+    if let Some(interest) = theirs.interest.filter(|_| ours.beginning_balance != 0.0 && their_opening != 0.0) {
+        let expected = ours.interest / ours.beginning_balance * their_opening;
+        if expected != 0.0 && (interest - expected).abs() > PENNIES {
+            return Some(Finding::Interest(interest / expected));
+        }
+    }
+    if let (Some(payment), Some(interest), Some(principal)) = (theirs.payment, theirs.interest, theirs.principal) {
+        // Principal is what's left of the payment after fees and interest
+        if (payment - interest - theirs.fees.unwrap_or(ours.fees) - principal).abs() > PENNIES {
+            return Some(Finding::ApplicationOrder);
+        }
+    }
+    row.differences.iter().all(|d| d.difference().abs() <= PENNIES).then_some(Finding::Rounding)
+}
+
+// Interest in a fixed proportion to ours is a rate convention, unless the proportion is the
+// one between a 360 and a 365 day year; a proportion that moves follows the period length
+fn interest_convention(ratios: &[f64]) -> LenderConvention {
+    let min = ratios.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = ratios.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let mean = ratios.iter().sum::<f64>() / ratios.len() as f64;
+    let year_basis = [365.0 / 360.0, 360.0 / 365.0].iter().any(|basis| (mean - basis).abs() < RATIO_SPREAD);
+    if max - min > RATIO_SPREAD || year_basis {
+        LenderConvention::DayCount
+    } else {
+        LenderConvention::Rate
+    }
+}
+
+fn hints(findings: Vec<(u32, Finding)>) -> Vec<ConventionHint> {
+    let mut interest = Vec::new();
+    let mut ratios = Vec::new();
+    let mut order = Vec::new();
+    let mut rounding = Vec::new();
+    for (installment, finding) in findings {
+        match finding {
+            Finding::Interest(ratio) => {
+                interest.push(installment);
+                ratios.push(ratio);
+            }
+            Finding::ApplicationOrder => order.push(installment),
+            Finding::Rounding => rounding.push(installment),
+        }
+    }
+    let mut hints = Vec::new();
+    if !interest.is_empty() {
+        hints.push(ConventionHint { convention: interest_convention(&ratios), installments: interest });
+    }
+    if !order.is_empty() {
+        hints.push(ConventionHint { convention: LenderConvention::ApplicationOrder, installments: order });
+    }
+    if !rounding.is_empty() {
+        hints.push(ConventionHint { convention: LenderConvention::Rounding, installments: rounding });
+    }
+    hints
+}
+
+impl Amortization {
+    pub fn reconcile_statement<R: BufRead>(&self, reader: R, options: &ReconcileOptions) -> Result<Reconciliation, AmortizationError> {
+        Ok(self.reconcile_rows(&read_statement(reader)?, options))
+    }
+
+    // Statement rows are matched to installments by number, or in order when the statement
+    // doesn't number them
+    pub fn reconcile_rows(&self, statement: &[StatementRow], options: &ReconcileOptions) -> Reconciliation {
+        let mut rows = Vec::new();
+        let mut unexpected = Vec::new();
+        let mut findings = Vec::new();
+        let mut their_balance = None;
+        for (index, theirs) in statement.iter().enumerate() {
+            let number = theirs.installment_number.unwrap_or(index as u32 + 1);
+            let Some(ours) = self.schedule.iter().find(|p| p.installment_number == number) else {
+                unexpected.push(number);
+                continue;
+            };
+            let row = compare_row(ours, theirs, options);
+            if let Some(finding) = classify(&row, ours, theirs, their_balance.unwrap_or(ours.beginning_balance)) {
+                findings.push((number, finding));
+            }
+            their_balance = theirs.balance;
+            rows.push(row);
+        }
+        let missing = self.schedule.iter()
+            .map(|p| p.installment_number)
+            .filter(|&n| !rows.iter().any(|row| row.installment_number == n))
+            .collect();
+        Reconciliation { rows, missing, unexpected, hints: hints(findings) }
+    }
+}
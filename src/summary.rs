@@ -0,0 +1,183 @@
+// Headline numbers of a loan without its schedule. Installments are streamed one at a time
+// and only their running totals kept, so a summary costs no allocation per installment; for
+// servers that answer with totals only
+use chrono::NaiveDate;
+use crate::builder::AmortizationBuilder;
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::error::AmortizationError;
+use crate::json::JsonValue;
+use crate::money::{Currency, Money};
+use crate::num::AmortNum;
+use crate::units::Term;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmortizationSummary {
+    pub principal: f64,
+    pub periodic_rate: f64,
+    // Scheduled term, and the installment the loan is actually paid off with
+    pub periods: u32,
+    pub payoff_period: u32,
+    // Time to payoff, for reporting as "29 years, 2 months"
+    pub payoff_term: Term,
+    pub periodic_payment: f64,
+    pub final_payment_amount: f64,
+    pub total_payment: f64,
+    pub total_interest: f64,
+    pub total_fees: f64,
+    pub start_date: Option<NaiveDate>,
+    // As on `Amortization`, the date after the final installment
+    pub end_date: Option<NaiveDate>,
+    pub currency: Currency,
+}
+
+pub(crate) struct Tally {
+    installments: usize,
+    payoff_period: u32,
+    total_payment: Money,
+    total_fees: Money,
+    final_payment_amount: f64,
+    // Interest and fees in the final installment, for judging how much principal it retires
+    final_interest: f64,
+    final_fees: f64,
+    // First amortizing installment, which sets the payment after a deferment
+    repayment_amount: Option<f64>,
+    end_date: Option<NaiveDate>,
+}
+
+impl Tally {
+    fn of(loan: &Amortization) -> Result<Self, AmortizationError> {
+        let first_amortizing = (loan.periods - loan.amortizing_periods()) as usize;
+        let mut payments = loan.payments();
+        let mut tally = Tally {
+            installments: 0,
+            payoff_period: 0,
+            total_payment: Money::zero(loan.config.currency),
+            total_fees: Money::zero(loan.config.currency),
+            final_payment_amount: 0.0,
+            final_interest: 0.0,
+            final_fees: 0.0,
+            repayment_amount: None,
+            end_date: None,
+        };
+        while let Some(payment) = payments.try_next()? {
+            if tally.installments == first_amortizing {
+                tally.repayment_amount = Some(payment.installment_amount - payment.fees);
+            }
+            tally.installments += 1;
+            tally.payoff_period = payment.installment_number;
+            let overflow = || AmortizationError::Overflow("total payment");
+            let installment = Money::checked_from_f64_with(payment.installment_amount, loan.config.currency, loan.config.rounding.mode).ok_or_else(overflow)?;
+            tally.total_payment = tally.total_payment.checked_add(installment).ok_or_else(overflow)?;
+            tally.total_fees = tally.total_fees + loan.money(payment.fees);
+            tally.final_payment_amount = payment.installment_amount;
+            tally.final_interest = payment.interest;
+            tally.final_fees = payment.fees;
+        }
+        tally.end_date = payments.next_date();
+        Ok(tally)
+    }
+}
+
+// A loan without its schedule, its installment settled the way `from_config` settles it,
+// and the totals of the schedule it streams
+pub(crate) fn settled(config: CalculatorConfig) -> Result<(Amortization, Tally), AmortizationError> {
+    let mut loan = Amortization::unscheduled(config)?;
+    loan.periodic_payment = loan.calculate_periodic_payment_amount()?;
+    let mut tally = Tally::of(&loan)?;
+    // Settled a cent at a time as in `from_config`, keeping the higher installment once a lower
+    // one would leave too large a final payment
+    while loan.settles_early(tally.installments) {
+        let higher = loan.periodic_payment;
+        let currency = loan.config.currency;
+        loan.periodic_payment = AmortNum::round_dp(higher - currency.minor_unit(), currency.minor_units());
+        let lower = Tally::of(&loan)?;
+        if !loan.final_within_level(lower.final_payment_amount - lower.final_fees, lower.final_interest) {
+            loan.periodic_payment = higher;
+            break;
+        }
+        tally = lower;
+    }
+    Ok((loan, tally))
+}
+
+impl AmortizationSummary {
+    // Matches `Amortization::from_config(config)?.summary()`
+    pub fn from_config(config: CalculatorConfig) -> Result<Self, AmortizationError> {
+        let (loan, tally) = settled(config)?;
+        Ok(AmortizationSummary::of(&loan, &tally))
+    }
+
+    pub(crate) fn of(loan: &Amortization, tally: &Tally) -> Self {
+        let periodic_payment = match loan.config.deferment {
+            Some(_) => tally.repayment_amount.unwrap_or(loan.periodic_payment),
+            None => loan.periodic_payment,
+        };
+        AmortizationSummary {
+            principal: loan.balance,
+            periodic_rate: loan.periodic_interest,
+            periods: loan.periods,
+            payoff_period: tally.payoff_period,
+            payoff_term: Term::of_periods(tally.installments as u32, loan.config.frequency),
+            periodic_payment,
+            final_payment_amount: tally.final_payment_amount,
+            total_payment: tally.total_payment.to_f64(),
+            total_interest: (tally.total_payment - loan.money(loan.balance) - tally.total_fees).to_f64(),
+            total_fees: tally.total_fees.to_f64(),
+            start_date: loan.start_date,
+            end_date: tally.end_date,
+            currency: loan.config.currency,
+        }
+    }
+
+    // Interest paid per unit of principal borrowed
+    pub fn interest_to_principal(&self) -> f64 {
+        if self.principal == 0.0 {
+            return 0.0;
+        }
+        self.total_interest / self.principal
+    }
+
+    pub fn to_json_value(&self) -> JsonValue {
+        let date = |date: Option<NaiveDate>| JsonValue::from(date.map(|d| d.format("%Y-%m-%d").to_string()));
+        JsonValue::object(vec![
+            ("principal", JsonValue::money(self.principal, self.currency)),
+            ("periodic_rate", self.periodic_rate.into()),
+            ("periods", self.periods.into()),
+            ("payoff_period", self.payoff_period.into()),
+            ("periodic_payment", JsonValue::money(self.periodic_payment, self.currency)),
+            ("final_payment", JsonValue::money(self.final_payment_amount, self.currency)),
+            ("total_payment", JsonValue::money(self.total_payment, self.currency)),
+            ("total_interest", JsonValue::money(self.total_interest, self.currency)),
+            ("total_fees", JsonValue::money(self.total_fees, self.currency)),
+            ("interest_to_principal", self.interest_to_principal().into()),
+            ("start_date", date(self.start_date)),
+            ("end_date", date(self.end_date)),
+        ])
+    }
+}
+
+impl Amortization {
+    pub fn summary(&self) -> AmortizationSummary {
+        AmortizationSummary {
+            principal: self.balance,
+            periodic_rate: self.periodic_interest,
+            periods: self.periods,
+            payoff_period: self.schedule.last().map_or(0, |p| p.installment_number),
+            payoff_term: self.payoff_term(),
+            periodic_payment: self.periodic_payment,
+            final_payment_amount: self.final_payment_amount,
+            total_payment: self.total_payment,
+            total_interest: self.total_interest,
+            total_fees: self.total_fees(),
+            start_date: self.start_date,
+            end_date: self.end_date,
+            currency: self.config.currency,
+        }
+    }
+}
+
+impl AmortizationBuilder {
+    pub fn summary(&self) -> Result<AmortizationSummary, AmortizationError> {
+        AmortizationSummary::from_config(self.config()?)
+    }
+}
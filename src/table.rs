@@ -0,0 +1,253 @@
+use std::fmt;
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::format::{Locale, ScheduleFormatter};
+#[cfg(feature = "i18n")]
+use crate::i18n::StringTable;
+use crate::payment::Payment;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Installment,
+    Date,
+    BeginningBalance,
+    Payment,
+    Interest,
+    Principal,
+    CapitalizedInterest,
+    Escrow,
+    Fees,
+    RemainingBalance,
+}
+
+impl Column {
+    pub fn header(&self) -> &'static str {
+        match self {
+            Column::Installment => "#",
+            Column::Date => "Date",
+            Column::BeginningBalance => "Beginning Balance",
+            Column::Payment => "Payment",
+            Column::Interest => "Interest",
+            Column::Principal => "Principal",
+            Column::CapitalizedInterest => "Capitalized",
+            Column::Escrow => "Escrow",
+            Column::Fees => "Fees",
+            Column::RemainingBalance => "Balance",
+        }
+    }
+
+    // Longer name used when a payment is written out as `label: value` pairs
+    pub fn label(&self) -> &'static str {
+        match self {
+            Column::Installment => "Installment",
+            Column::CapitalizedInterest => "Capitalized Interest",
+            Column::RemainingBalance => "Remaining Balance",
+            column => column.header(),
+        }
+    }
+
+    fn amount(&self, payment: &Payment) -> Option<f64> {
+        match self {
+            Column::Installment | Column::Date => None,
+            Column::BeginningBalance => Some(payment.beginning_balance),
+            Column::Payment => Some(payment.installment_amount),
+            Column::Interest => Some(payment.interest),
+            Column::Principal => Some(payment.principal),
+            Column::CapitalizedInterest => Some(payment.capitalized_interest),
+            Column::Escrow => Some(payment.escrow_amount()),
+            Column::Fees => Some(payment.fees),
+            Column::RemainingBalance => Some(payment.remaining_balance),
+        }
+    }
+}
+
+// Shown in place of the date for schedules without a start date
+pub const MISSING_DATE: &str = "N/A";
+
+// How a renderer writes the schedule: its columns, decimal places, date format and digit
+// grouping. Shared by tables, Markdown, HTML, CSV and `Payment`'s `Display`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatOptions {
+    pub columns: Vec<Column>,
+    pub decimals: usize,
+    pub date_format: String,
+    pub thousands_separator: bool,
+}
+
+// The names the table and display options had before they were merged
+pub type TableOptions = FormatOptions;
+pub type DisplayOptions = FormatOptions;
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            columns: vec![Column::Installment, Column::Date, Column::Payment, Column::Interest, Column::Principal, Column::RemainingBalance],
+            decimals: 2,
+            date_format: "%Y-%m-%d".to_string(),
+            thousands_separator: true,
+        }
+    }
+}
+
+impl FormatOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // What `Payment`'s `Display` writes
+    pub fn payment_display() -> Self {
+        FormatOptions {
+            columns: vec![Column::Date, Column::Interest, Column::Principal, Column::RemainingBalance, Column::BeginningBalance],
+            thousands_separator: false,
+            ..Self::default()
+        }
+    }
+
+    // What `to_csv` writes, with a fees column for loans that charge any
+    pub fn csv(loan: &Amortization) -> Self {
+        let mut columns = vec![Column::Installment, Column::Date, Column::BeginningBalance, Column::Interest, Column::Principal, Column::RemainingBalance];
+        if !loan.config.fees.is_empty() {
+            columns.push(Column::Fees);
+        }
+        FormatOptions { columns, thousands_separator: false, ..Self::default() }
+    }
+
+    pub fn with_columns(mut self, columns: Vec<Column>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    pub fn with_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    pub fn with_date_format(mut self, date_format: &str) -> Self {
+        self.date_format = date_format.to_string();
+        self
+    }
+
+    pub fn with_thousands_separator(mut self, thousands_separator: bool) -> Self {
+        self.thousands_separator = thousands_separator;
+        self
+    }
+
+    pub fn format_amount(&self, amount: f64) -> String {
+        format_amount_with(amount, self.decimals, self.thousands_separator)
+    }
+
+    pub fn format_date(&self, date: Option<NaiveDate>) -> String {
+        date.map_or_else(|| MISSING_DATE.to_string(), |date| date.format(&self.date_format).to_string())
+    }
+}
+
+pub struct PaymentDisplay<'a> {
+    payment: &'a Payment,
+    options: &'a DisplayOptions,
+}
+
+impl fmt::Display for PaymentDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let payment = self.payment;
+        for (index, column) in self.options.columns.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            match column {
+                Column::Installment => write!(f, "{}: {}", column.label(), payment.installment_number)?,
+                Column::Date => write!(f, "{}: {}", column.label(), self.options.format_date(payment.date))?,
+                column => write!(f, "{}: {}", column.label(), self.options.format_amount(column.amount(payment).unwrap_or_default()))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Payment {
+    pub fn display_with<'a>(&'a self, options: &'a FormatOptions) -> PaymentDisplay<'a> {
+        PaymentDisplay { payment: self, options }
+    }
+}
+
+// Formats to two decimals, grouping the integer part in threes when `thousands` is set
+pub fn format_amount(amount: f64, thousands: bool) -> String {
+    format_amount_with(amount, 2, thousands)
+}
+
+pub fn format_amount_with(amount: f64, decimals: usize, thousands: bool) -> String {
+    if thousands {
+        ScheduleFormatter::new(Locale::EN_US).with_decimals(decimals).format_number(amount)
+    } else {
+        format!("{:.*}", decimals, amount)
+    }
+}
+
+// One payment's formatted cells, in column order
+pub(crate) fn payment_cells<A, D>(options: &FormatOptions, payment: &Payment, format_amount: &A, format_date: &D) -> Vec<String>
+where
+    A: Fn(f64) -> String,
+    D: Fn(Option<NaiveDate>) -> String,
+{
+    options.columns.iter().map(|column| match column {
+        Column::Installment => payment.installment_number.to_string(),
+        Column::Date => format_date(payment.date),
+        column => format_amount(column.amount(payment).unwrap_or_default()),
+    }).collect()
+}
+
+impl Amortization {
+    pub fn to_table(&self) -> String {
+        self.to_table_with(&TableOptions::default())
+    }
+
+    pub fn to_table_with(&self, options: &TableOptions) -> String {
+        ScheduleFormatter::new(Locale::EN_US).with_date_format(&options.date_format).table(self, options)
+    }
+
+    #[cfg(feature = "i18n")]
+    pub fn to_table_in(&self, options: &TableOptions, labels: &dyn StringTable) -> String {
+        ScheduleFormatter::new(Locale::EN_US).with_date_format(&options.date_format).table_in(self, options, labels)
+    }
+
+    // One row of formatted cells per payment, in column order
+    pub(crate) fn table_cells<A, D>(&self, options: &TableOptions, format_amount: A, format_date: D) -> Vec<Vec<String>>
+    where
+        A: Fn(f64) -> String,
+        D: Fn(Option<NaiveDate>) -> String,
+    {
+        self.schedule.iter().map(|payment| payment_cells(options, payment, &format_amount, &format_date)).collect()
+    }
+
+    // `headers` holds one header per column, for renderers that translate them
+    pub(crate) fn render_table<A, D>(&self, options: &TableOptions, headers: &[&str], format_amount: A, format_date: D) -> String
+    where
+        A: Fn(f64) -> String,
+        D: Fn(Option<NaiveDate>) -> String,
+    {
+        let cells = self.table_cells(options, format_amount, format_date);
+
+        // Widths count characters, matching how `format!` pads
+        let widths: Vec<usize> = headers.iter().enumerate().map(|(index, header)| {
+            cells.iter().map(|row| row[index].chars().count()).fold(header.chars().count(), usize::max)
+        }).collect();
+
+        let mut table = String::new();
+        let header: Vec<String> = headers.iter().zip(&widths)
+            .map(|(header, width)| format!("{:>width$}", header, width = width))
+            .collect();
+        table.push_str(header.join("  ").trim_end());
+        table.push('\n');
+        let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        table.push_str(&rule.join("  "));
+        table.push('\n');
+
+        for row in cells {
+            let line: Vec<String> = row.iter().zip(&widths)
+                .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+                .collect();
+            table.push_str(&line.join("  "));
+            table.push('\n');
+        }
+        table
+    }
+}
@@ -0,0 +1,75 @@
+use crate::calculator::Amortization;
+use crate::error::AmortizationError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TaxAssumptions {
+    // Marginal income tax rate in percent
+    pub marginal_rate: f64,
+    // Most interest that can be deducted in one year
+    pub deduction_cap: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnualTaxSavings {
+    pub year: i32,
+    pub interest: f64,
+    pub deductible_interest: f64,
+    pub tax_savings: f64,
+    pub after_tax_interest: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaxAnalysis {
+    pub years: Vec<AnnualTaxSavings>,
+    pub total_interest: f64,
+    pub total_tax_savings: f64,
+    pub after_tax_interest: f64,
+    // Note rate scaled down by the share of interest the deduction gives back
+    pub after_tax_rate: f64,
+}
+
+impl TaxAssumptions {
+    pub fn new(marginal_rate: f64) -> Self {
+        TaxAssumptions { marginal_rate, deduction_cap: None }
+    }
+
+    pub fn with_deduction_cap(mut self, deduction_cap: f64) -> Self {
+        self.deduction_cap = Some(deduction_cap);
+        self
+    }
+}
+
+impl Amortization {
+    // Estimated savings from deducting mortgage interest, grouped like `annual_summary`
+    pub fn tax_analysis(&self, assumptions: &TaxAssumptions) -> Result<TaxAnalysis, AmortizationError> {
+        if !(0.0..=100.0).contains(&assumptions.marginal_rate) {
+            return Err(AmortizationError::InvalidInterestRate(assumptions.marginal_rate));
+        }
+        if let Some(cap) = assumptions.deduction_cap.filter(|cap| *cap < 0.0 || !cap.is_finite()) {
+            return Err(AmortizationError::InvalidPaymentAmount(cap));
+        }
+
+        let years: Vec<AnnualTaxSavings> = self.annual_summary().iter().map(|summary| {
+            let deductible_interest = assumptions.deduction_cap.map_or(summary.interest, |cap| summary.interest.min(cap));
+            let tax_savings = deductible_interest * assumptions.marginal_rate / 100.0;
+            AnnualTaxSavings {
+                year: summary.year,
+                interest: summary.interest,
+                deductible_interest,
+                tax_savings,
+                after_tax_interest: summary.interest - tax_savings,
+            }
+        }).collect();
+
+        let total_interest: f64 = years.iter().map(|year| year.interest).sum();
+        let total_tax_savings: f64 = years.iter().map(|year| year.tax_savings).sum();
+        let savings_share = if total_interest > 0.0 { total_tax_savings / total_interest } else { 0.0 };
+        Ok(TaxAnalysis {
+            total_interest,
+            total_tax_savings,
+            after_tax_interest: total_interest - total_tax_savings,
+            after_tax_rate: self.rate_schedule[0].apr * (1.0 - savings_share),
+            years,
+        })
+    }
+}
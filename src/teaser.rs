@@ -0,0 +1,78 @@
+// Introductory rates, e.g. 0% for 12 months on a card balance or a 2% teaser ARM, and the
+// installment jump when they end
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::rate::RatePeriod;
+
+// Rate charged on the first `periods` installments, after which the standard rate applies and
+// the installment is recalculated over the remaining term
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TeaserRate {
+    pub apr: f64,
+    pub periods: u32,
+}
+
+impl TeaserRate {
+    pub fn new(apr: f64, periods: u32) -> Self {
+        TeaserRate { apr, periods }
+    }
+
+    // Puts the teaser in front of `rate_schedule`; whatever rate it sets for the period right
+    // after the teaser becomes the standard rate, and later resets are kept
+    pub fn apply(&self, rate_schedule: &[RatePeriod]) -> Vec<RatePeriod> {
+        let switch = self.periods + 1;
+        let standard = rate_schedule.iter()
+            .rev()
+            .find(|r| r.start_period <= switch)
+            .map_or(self.apr, |r| r.apr);
+        let mut applied = vec![RatePeriod::new(1, self.apr), RatePeriod::new(switch, standard)];
+        applied.extend(rate_schedule.iter().filter(|r| r.start_period > switch).copied());
+        applied
+    }
+}
+
+// An installment recalculated at a rate reset, compared with the one before it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PaymentChange {
+    pub installment_number: u32,
+    pub date: Option<NaiveDate>,
+    pub previous_apr: f64,
+    pub apr: f64,
+    pub previous_payment: f64,
+    pub payment: f64,
+}
+
+impl PaymentChange {
+    pub fn amount(&self) -> f64 {
+        self.payment - self.previous_payment
+    }
+
+    // Change relative to the previous installment, in percent
+    pub fn percent(&self) -> f64 {
+        if self.previous_payment == 0.0 {
+            0.0
+        } else {
+            self.amount() / self.previous_payment * 100.0
+        }
+    }
+}
+
+impl Amortization {
+    // One entry per rate reset reached by the schedule, in order
+    pub fn payment_changes(&self) -> Vec<PaymentChange> {
+        self.rate_schedule.windows(2)
+            .filter_map(|pair| {
+                let index = pair[1].start_period as usize - 1;
+                let (previous, payment) = (self.schedule.get(index.checked_sub(1)?)?, self.schedule.get(index)?);
+                Some(PaymentChange {
+                    installment_number: payment.installment_number,
+                    date: payment.date,
+                    previous_apr: pair[0].apr,
+                    apr: pair[1].apr,
+                    previous_payment: previous.installment_amount,
+                    payment: payment.installment_amount,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,40 @@
+use crate::{Amortization, AmortizationError, PaymentFrequency, Rate};
+use super::{assert_float_eq, FLOAT_PRECISION};
+
+#[test]
+fn test_biweekly_pays_half_the_monthly_payment() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let result = loan.biweekly_accelerated().unwrap();
+    let accelerated = &result.accelerated;
+
+    assert_float_eq(result.half_payment, 599.55);
+    assert_eq!(accelerated.config.frequency, PaymentFrequency::BiWeekly);
+    assert!(accelerated.schedule[..accelerated.schedule.len() - 1]
+        .iter()
+        .all(|p| (p.installment_amount - result.half_payment).abs() < FLOAT_PRECISION));
+    assert_float_eq(accelerated.schedule.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_biweekly_shortens_term_and_saves_interest() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let result = loan.biweekly_accelerated().unwrap();
+
+    assert_float_eq(result.baseline_months, 360.0);
+    assert!(result.months_saved > 60.0 && result.months_saved < 80.0, "saved {}", result.months_saved);
+    assert!(result.interest_saved > 0.0);
+    assert_float_eq(result.interest_saved, loan.total_interest - result.accelerated.total_interest);
+}
+
+#[test]
+fn test_biweekly_requires_monthly_baseline() {
+    let loan = Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .frequency(PaymentFrequency::Quarterly)
+        .build()
+        .unwrap();
+
+    assert!(matches!(loan.biweekly_accelerated(), Err(AmortizationError::UnsupportedFrequency(PaymentFrequency::Quarterly))));
+}
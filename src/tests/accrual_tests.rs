@@ -0,0 +1,99 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationBuilder, BusinessDayConvention, DayCount, OddDaysTreatment, Rate, WeekendsOnly};
+use super::assert_float_eq;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn builder(start_date: NaiveDate) -> AmortizationBuilder {
+    Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(36)
+        .start_date(start_date)
+}
+
+#[test]
+fn test_february_in_leap_and_common_years() {
+    let loan = builder(date(2024, 1, 15)).day_count(DayCount::Actual365).build().unwrap();
+
+    assert_eq!(loan.schedule[0].accrual_start(), Some(date(2023, 12, 15)));
+    assert_eq!(loan.schedule[0].accrual_end(), Some(date(2024, 1, 15)));
+    // Jan 15 to Feb 15, then Feb 15 to Mar 15 in 2024 and 2025
+    assert_eq!(loan.schedule[1].days_in_period(), Some(31));
+    assert_eq!(loan.schedule[2].days_in_period(), Some(29));
+    assert_eq!(loan.schedule[14].days_in_period(), Some(28));
+
+    let leap_year: i64 = loan.schedule[1..13].iter().filter_map(|p| p.days_in_period()).sum();
+    let common_year: i64 = loan.schedule[13..25].iter().filter_map(|p| p.days_in_period()).sum();
+    assert_eq!(leap_year, 366);
+    assert_eq!(common_year, 365);
+
+    // Interest follows the days accrued
+    let previous_balance = loan.schedule[1].remaining_balance;
+    assert_float_eq(loan.schedule[2].interest, previous_balance * 0.06 * 29.0 / 365.0);
+}
+
+#[test]
+fn test_month_end_due_dates() {
+    let loan = builder(date(2024, 1, 31)).build().unwrap();
+
+    // Clamped to the last day of shorter months
+    assert_eq!(loan.schedule[1].accrual_end(), Some(date(2024, 2, 29)));
+    assert_eq!(loan.schedule[1].days_in_period(), Some(29));
+    assert_eq!(loan.schedule[2].accrual_start(), Some(date(2024, 2, 29)));
+    assert_eq!(loan.schedule[2].accrual_end(), Some(date(2024, 3, 31)));
+    assert_eq!(loan.schedule[2].days_in_period(), Some(31));
+    assert_eq!(loan.schedule[3].days_in_period(), Some(30));
+    assert_eq!(loan.schedule[13].accrual_end(), Some(date(2025, 2, 28)));
+    assert_eq!(loan.schedule[13].days_in_period(), Some(28));
+
+    // Every period starts where the previous one ended
+    for pair in loan.schedule.windows(2) {
+        assert_eq!(pair[1].accrual_start(), pair[0].accrual_end());
+    }
+}
+
+#[test]
+fn test_day_count_convention() {
+    let loan = builder(date(2024, 1, 15)).day_count(DayCount::Thirty360).build().unwrap();
+    assert!(loan.schedule.iter().all(|p| p.days_in_period() == Some(30)));
+}
+
+#[test]
+fn test_accrual_ignores_business_day_adjustment() {
+    let loan = builder(date(2025, 3, 1))
+        .business_day_convention(BusinessDayConvention::Following)
+        .holiday_calendar(WeekendsOnly)
+        .build()
+        .unwrap();
+
+    // March 1 2025 is a Saturday; the payment moves, the accrual does not
+    assert_eq!(loan.schedule[0].date, Some(date(2025, 3, 3)));
+    assert_eq!(loan.schedule[0].accrual_end(), Some(date(2025, 3, 1)));
+    assert_eq!(loan.schedule[1].accrual_start(), Some(date(2025, 3, 1)));
+    assert_eq!(loan.schedule[1].days_in_period(), Some(31));
+}
+
+#[test]
+fn test_first_period_from_funding() {
+    let loan = builder(date(2025, 3, 1))
+        .funding_date(date(2025, 1, 15))
+        .odd_days_treatment(OddDaysTreatment::AddToFirstPayment)
+        .build()
+        .unwrap();
+    assert_eq!(loan.schedule[0].accrual_start(), Some(date(2025, 1, 15)));
+    assert_eq!(loan.schedule[0].days_in_period(), Some(45));
+
+    // Collected at closing, the interim interest is not part of the first period
+    let collected = builder(date(2025, 3, 1)).funding_date(date(2025, 1, 15)).build().unwrap();
+    assert_eq!(collected.schedule[0].accrual_start(), Some(date(2025, 2, 1)));
+    assert_eq!(collected.schedule[0].days_in_period(), Some(28));
+}
+
+#[test]
+fn test_undated_schedule_has_no_accrual() {
+    let loan = Amortization::new(100_000.0, 6.0, 36, None).unwrap();
+    assert!(loan.schedule.iter().all(|p| p.accrual.is_none() && p.days_in_period().is_none()));
+}
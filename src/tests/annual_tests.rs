@@ -0,0 +1,47 @@
+use chrono::NaiveDate;
+use crate::{Amortization, PaymentFrequency, Rate};
+use super::assert_float_eq;
+
+#[test]
+fn test_annual_summary_by_calendar_year() {
+    let start = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+    let loan = Amortization::new(100_000.0, 6.0, 120, Some(start)).unwrap();
+    let years = loan.annual_summary();
+
+    assert_eq!(years.len(), 11);
+    assert_eq!((years[0].year, years[0].payments), (2024, 6));
+    assert_eq!((years[1].year, years[1].payments), (2025, 12));
+    assert_eq!((years[10].year, years[10].payments), (2034, 6));
+
+    let first_half: f64 = loan.schedule[..6].iter().map(|p| p.interest).sum();
+    assert_float_eq(years[0].interest, first_half);
+    assert_float_eq(years[0].ending_balance, loan.schedule[5].remaining_balance);
+    assert_float_eq(years[10].ending_balance, 0.0);
+}
+
+#[test]
+fn test_annual_totals_match_loan_totals() {
+    let start = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let loan = Amortization::new(250_000.0, 5.0, 360, Some(start)).unwrap();
+    let years = loan.annual_summary();
+
+    assert_float_eq(years.iter().map(|y| y.principal).sum(), 250_000.0);
+    assert_float_eq(years.iter().map(|y| y.interest).sum(), loan.total_interest);
+    assert_float_eq(years.iter().map(|y| y.total_paid).sum(), loan.total_payment);
+    assert_eq!(years.iter().map(|y| y.payments).sum::<u32>(), 360);
+}
+
+#[test]
+fn test_annual_summary_without_dates() {
+    let loan = Amortization::builder()
+        .principal(50_000.0)
+        .rate(Rate::percent(4.0))
+        .term_months(30)
+        .frequency(PaymentFrequency::Quarterly)
+        .build()
+        .unwrap();
+    let years = loan.annual_summary();
+
+    assert_eq!(years.iter().map(|y| y.year).collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(years.iter().map(|y| y.payments).collect::<Vec<_>>(), vec![4, 4, 2]);
+}
@@ -0,0 +1,64 @@
+use chrono::NaiveDate;
+use crate::{aprc, Amortization, AmortizationError, CalculatorConfig, UpfrontCosts};
+use super::assert_float_eq;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_single_repayment() {
+    // 1,000 borrowed and 1,200 repaid a year later
+    let rate = aprc(&[(date(2025, 3, 1), 1_000.0)], &[(date(2026, 3, 1), 1_200.0)]).unwrap();
+    assert_float_eq(rate, 20.0);
+}
+
+#[test]
+fn test_leftover_days() {
+    // Six whole months back from the repayment, then 30 days of a 365 day year
+    let rate = aprc(&[(date(2025, 1, 1), 1_000.0)], &[(date(2025, 7, 31), 1_050.0)]).unwrap();
+    let years = 0.5 + 30.0 / 365.0;
+    assert_float_eq(rate, (1.05_f64.powf(1.0 / years) - 1.0) * 100.0);
+}
+
+#[test]
+fn test_monthly_loan_is_effective_rate() {
+    let loan = Amortization::new(1_000.0, 12.0, 12, Some(date(2025, 2, 1))).unwrap();
+    let rate = loan.aprc(&UpfrontCosts::default()).unwrap();
+
+    // (1 + 1%)^12 - 1, where a US APR would show the nominal 12%
+    assert!((rate - 12.68).abs() < 0.05);
+    assert!(rate > loan.effective_apr(&UpfrontCosts::default()).unwrap().apr);
+}
+
+#[test]
+fn test_upfront_costs_raise_aprc() {
+    let loan = Amortization::new(200_000.0, 4.0, 300, Some(date(2025, 2, 1))).unwrap();
+    let costs = UpfrontCosts { origination_fees: 2_000.0, ..UpfrontCosts::default() };
+    let without = loan.aprc(&UpfrontCosts::default()).unwrap();
+    let with = loan.aprc(&costs).unwrap();
+
+    assert!(with > without);
+    assert!(without > 4.0 && without < 4.1);
+}
+
+#[test]
+fn test_funding_date_counts_odd_days() {
+    let funded_early = Amortization::from_config(CalculatorConfig {
+        funding_date: Some(date(2024, 12, 15)),
+        ..CalculatorConfig::new(10_000.0, 6.0, 24, Some(date(2025, 2, 1)))
+    }).unwrap();
+    let regular = Amortization::new(10_000.0, 6.0, 24, Some(date(2025, 2, 1))).unwrap();
+
+    // The interim interest paid at closing keeps the rate in line with the regular loan
+    let costs = UpfrontCosts::default();
+    assert!(funded_early.closing_interest() > 0.0);
+    assert!((funded_early.aprc(&costs).unwrap() - regular.aprc(&costs).unwrap()).abs() < 0.05);
+}
+
+#[test]
+fn test_requires_dates() {
+    let loan = Amortization::new(1_000.0, 12.0, 12, None).unwrap();
+    assert!(matches!(loan.aprc(&UpfrontCosts::default()), Err(AmortizationError::MissingParameter("start_date"))));
+    assert!(matches!(aprc(&[], &[]), Err(AmortizationError::MissingParameter("drawdowns"))));
+}
@@ -0,0 +1,78 @@
+use chrono::NaiveDate;
+use crate::{AmortizationError, AutoPurchase};
+use super::assert_float_eq;
+
+fn upside_down_trade() -> AutoPurchase {
+    AutoPurchase::new(30_000.0, 6.0, 60)
+        .with_sales_tax(7.0)
+        .with_fees(1_000.0)
+        .with_trade_in(5_000.0, 8_000.0)
+}
+
+#[test]
+fn test_amount_financed_rolls_in_negative_equity() {
+    let purchase = upside_down_trade();
+    assert_float_eq(purchase.negative_equity(), 3_000.0);
+    // Tax on the price less the trade-in
+    assert_float_eq(purchase.taxable_price(), 25_000.0);
+    assert_float_eq(purchase.sales_tax(), 1_750.0);
+    assert_float_eq(purchase.amount_financed(), 30_000.0 + 1_750.0 + 1_000.0 + 3_000.0);
+
+    let no_credit = purchase.clone().without_trade_in_tax_credit();
+    assert_float_eq(no_credit.sales_tax(), 2_100.0);
+
+    // Positive trade-in equity and cash lower the amount financed
+    let equity = AutoPurchase::new(30_000.0, 6.0, 60).with_trade_in(8_000.0, 5_000.0).with_cash_down(2_000.0);
+    assert_float_eq(equity.negative_equity(), 0.0);
+    assert_float_eq(equity.amount_financed(), 25_000.0);
+}
+
+#[test]
+fn test_equity_curve() {
+    let start = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+    let loan = upside_down_trade().with_start_date(start).finance().unwrap();
+    assert_float_eq(loan.loan.schedule[0].beginning_balance, loan.amount_financed);
+    assert_eq!(loan.equity.len(), 61);
+
+    let signing = &loan.equity[0];
+    assert_float_eq(signing.equity, 30_000.0 - 35_750.0);
+    assert_float_eq(loan.equity[12].vehicle_value, 24_000.0);
+    assert_float_eq(loan.equity[24].vehicle_value, 20_400.0);
+    assert_eq!(loan.equity[1].date, loan.loan.schedule[0].date);
+
+    // Underwater from signing until the balance falls below the car's value
+    let break_even = loan.break_even_installment().unwrap();
+    assert!(break_even > 12 && break_even < 60);
+    assert_eq!(loan.underwater_months(), break_even - 1);
+    assert!(loan.equity[break_even as usize - 1].equity < 0.0);
+    assert!(loan.equity[break_even as usize..].iter().all(|row| row.equity >= 0.0));
+    assert!(loan.max_negative_equity() >= 5_750.0);
+    assert_float_eq(loan.equity[60].balance, 0.0);
+}
+
+#[test]
+fn test_never_underwater_with_large_down_payment() {
+    let loan = AutoPurchase::new(30_000.0, 5.0, 36)
+        .with_cash_down(15_000.0)
+        .with_depreciation(15.0, 10.0)
+        .finance()
+        .unwrap();
+    assert_eq!(loan.break_even_installment(), Some(0));
+    assert_eq!(loan.underwater_months(), 0);
+    assert_float_eq(loan.max_negative_equity(), 0.0);
+}
+
+#[test]
+fn test_invalid_purchase() {
+    assert!(matches!(AutoPurchase::new(0.0, 6.0, 60).finance(), Err(AmortizationError::InvalidAsset(_))));
+    assert!(matches!(AutoPurchase::new(30_000.0, 6.0, 60).with_fees(-1.0).finance(), Err(AmortizationError::InvalidAsset(_))));
+    assert!(matches!(
+        AutoPurchase::new(30_000.0, 6.0, 60).with_depreciation(100.0, 15.0).finance(),
+        Err(AmortizationError::InvalidAsset(_))
+    ));
+    // Paid in full with the trade-in, nothing left to finance
+    assert!(matches!(
+        AutoPurchase::new(10_000.0, 6.0, 60).with_trade_in(10_000.0, 0.0).finance(),
+        Err(AmortizationError::InvalidLoanAmount(_))
+    ));
+}
@@ -0,0 +1,59 @@
+use crate::batch::{payments, quotes};
+use crate::{Amortization, AmortizationError, BatchLoan};
+use super::assert_float_eq;
+
+// A grid of rates and terms, with a length that leaves a partial chunk
+fn grid() -> Vec<BatchLoan> {
+    let mut loans = Vec::new();
+    // Small balances at high rates over long terms are where settling the installment a cent
+    // lower gets close calls
+    for principal in [100.0, 500.0, 1_000.0, 250_000.0] {
+        for apr in [0.0, 2.5, 3.3, 4.75, 6.0, 7.125, 9.99, 17.3, 18.0, 24.0, 24.75] {
+            for term_months in [1, 12, 60, 180, 360, 480] {
+                loans.push(BatchLoan::new(principal, apr, term_months));
+            }
+        }
+    }
+    loans
+}
+
+#[test]
+fn test_payments_match_calculator() {
+    let loans = grid();
+    let batch = payments(&loans).unwrap();
+    assert_eq!(batch.len(), loans.len());
+    for (loan, payment) in loans.iter().zip(batch) {
+        let amortization = Amortization::new(loan.principal, loan.apr, loan.term_months, None).unwrap();
+        assert_eq!(payment, amortization.periodic_payment, "{:?}", loan);
+    }
+    for (loan, quote) in loans.iter().zip(quotes(&loans).unwrap()) {
+        let amortization = Amortization::new(loan.principal, loan.apr, loan.term_months, None).unwrap();
+        assert!((quote.final_payment - amortization.final_payment_amount).abs() < 0.5, "{:?}", loan);
+        assert!((quote.total_payment - amortization.total_payment).abs() < 0.5, "{:?}", loan);
+    }
+}
+
+#[test]
+fn test_quote_totals() {
+    let quote = quotes(&[BatchLoan::new(200_000.0, 6.0, 360)]).unwrap()[0];
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    assert_eq!(quote.payment, 1199.10);
+    // Closed form against the rounded schedule, within a few cents
+    assert!((quote.total_interest - loan.total_interest).abs() < 0.5);
+    assert!((quote.final_payment - loan.final_payment_amount).abs() < 0.5);
+    assert_float_eq(quote.total_payment, quote.total_interest + 200_000.0);
+
+    let interest_free = quotes(&[(1_000.0, 0.0, 3).into()]).unwrap()[0];
+    assert_eq!(interest_free.payment, 333.33);
+    assert_float_eq(interest_free.final_payment, 333.34);
+    assert_float_eq(interest_free.total_interest, 0.0);
+}
+
+#[test]
+fn test_invalid_loans() {
+    assert!(matches!(quotes(&[BatchLoan::new(-1.0, 6.0, 360)]), Err(AmortizationError::InvalidLoanAmount(_))));
+    assert!(matches!(quotes(&[BatchLoan::new(1_000.0, f64::NAN, 360)]), Err(AmortizationError::InvalidInterestRate(_))));
+    assert!(matches!(payments(&[BatchLoan::new(1_000.0, 6.0, 0)]), Err(AmortizationError::InvalidPeriods(0))));
+    assert!(matches!(payments(&[BatchLoan::new(1_000.0, 1e6, 10_000)]), Err(AmortizationError::Overflow(_))));
+    assert!(payments(&[]).unwrap().is_empty());
+}
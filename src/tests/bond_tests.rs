@@ -0,0 +1,54 @@
+use crate::{AmortizationError, Bond, PaymentFrequency};
+use super::assert_float_eq;
+
+#[test]
+fn test_premium_bond_amortization() {
+    let schedule = Bond::new(1_000.0, 10.0, 8.0, 10).schedule().unwrap();
+    let first = &schedule.rows[0];
+
+    assert_float_eq(schedule.issue_price, 1_081.11);
+    assert_float_eq(schedule.premium, 81.11);
+    assert_float_eq(first.coupon_payment, 50.0);
+    assert_float_eq(first.interest_expense, 43.24);
+    assert_float_eq(first.amortization, 6.76);
+    assert_float_eq(first.carrying_value, 1_074.35);
+}
+
+#[test]
+fn test_discount_bond_accretes_to_face() {
+    let schedule = Bond::new(1_000.0, 10.0, 12.0, 10).schedule().unwrap();
+    let first = &schedule.rows[0];
+
+    assert_float_eq(schedule.issue_price, 926.40);
+    assert_float_eq(schedule.premium, -73.60);
+    assert_float_eq(first.interest_expense, 55.58);
+    assert_float_eq(first.amortization, -5.58);
+    assert_float_eq(first.carrying_value, 931.98);
+}
+
+#[test]
+fn test_carrying_value_ends_at_face() {
+    for market_yield in [6.0, 10.0, 14.0] {
+        let schedule = Bond::new(1_000.0, 10.0, market_yield, 20).schedule().unwrap();
+        let total_amortization: f64 = schedule.rows.iter().map(|row| row.amortization).sum();
+
+        assert_eq!(schedule.rows.len(), 20);
+        assert_float_eq(schedule.rows.last().unwrap().carrying_value, 1_000.0);
+        assert_float_eq(total_amortization, schedule.premium);
+    }
+}
+
+#[test]
+fn test_annual_coupons() {
+    let bond = Bond::new(1_000.0, 5.0, 5.0, 5).with_frequency(PaymentFrequency::Annually);
+
+    assert_float_eq(bond.coupon_payment(), 50.0);
+    assert_float_eq(bond.issue_price().unwrap(), 1_000.0);
+}
+
+#[test]
+fn test_invalid_bond() {
+    assert!(matches!(Bond::new(0.0, 5.0, 5.0, 10).schedule(), Err(AmortizationError::InvalidLoanAmount(_))));
+    assert!(matches!(Bond::new(1_000.0, 5.0, 0.0, 10).schedule(), Err(AmortizationError::InvalidInterestRate(_))));
+    assert!(matches!(Bond::new(1_000.0, 5.0, 5.0, 0).schedule(), Err(AmortizationError::InvalidPeriods(0))));
+}
@@ -0,0 +1,151 @@
+use crate::{Amortization, AmortizationError, Decimal, PaymentFrequency, Principal, Rate, Term};
+use chrono::NaiveDate;
+
+#[test]
+fn test_builder_matches_new() {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let built = Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(3.5))
+        .term_months(360)
+        .start_date(start)
+        .build()
+        .unwrap();
+    let direct = Amortization::new(200_000.0, 3.5, 360, Some(start)).unwrap();
+
+    assert_eq!(built.periodic_payment, direct.periodic_payment);
+    assert_eq!(built.schedule.len(), direct.schedule.len());
+    assert_eq!(built.end_date, direct.end_date);
+}
+
+#[test]
+fn test_builder_missing_parameters() {
+    let missing_principal = Amortization::builder().rate(Rate::percent(5.0)).term_months(12).build();
+    assert!(matches!(missing_principal, Err(AmortizationError::MissingParameter("principal"))));
+
+    let missing_apr = Amortization::builder().principal(1_000.0).term_months(12).build();
+    assert!(matches!(missing_apr, Err(AmortizationError::MissingParameter("apr"))));
+
+    let missing_term = Amortization::builder().principal(1_000.0).rate(Rate::percent(5.0)).build();
+    assert!(matches!(missing_term, Err(AmortizationError::MissingParameter("term_months"))));
+}
+
+#[test]
+fn test_builder_biweekly_frequency() {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
+    let loan = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .start_date(start)
+        .frequency(PaymentFrequency::BiWeekly)
+        .build()
+        .unwrap();
+
+    assert_eq!(loan.periods, 780);
+    assert!((loan.periodic_interest - 0.06 / 26.0).abs() < 1e-12);
+    assert_eq!(loan.schedule[1].date, NaiveDate::from_ymd_opt(2024, 1, 19));
+}
+
+#[test]
+fn test_builder_extra_payment_shortens_term() {
+    let baseline = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(360)
+        .build()
+        .unwrap();
+    let accelerated = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(360)
+        .extra_payment(200.0)
+        .build()
+        .unwrap();
+
+    assert_eq!(accelerated.periodic_payment, baseline.periodic_payment);
+    assert!(accelerated.schedule.len() < baseline.schedule.len());
+    assert!(accelerated.total_interest < baseline.total_interest);
+    assert_eq!(accelerated.schedule.last().unwrap().remaining_balance, 0.0);
+    let first = &accelerated.schedule[0];
+    assert!((first.installment_amount - (baseline.periodic_payment + 200.0)).abs() < 1e-9);
+}
+
+#[test]
+fn test_builder_rejects_negative_extra_payment() {
+    let result = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(360)
+        .extra_payment(-50.0)
+        .build();
+    assert!(matches!(result, Err(AmortizationError::InvalidExtraPayment(_))));
+}
+
+#[test]
+fn test_builder_with_decimal_backend() {
+    let loan = Amortization::builder()
+        .principal(10_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(12)
+        .extra_payment(100.0)
+        .build_with::<Decimal>()
+        .unwrap();
+    let principal: f64 = loan.schedule.iter().map(|p| p.principal).sum();
+
+    assert!(loan.schedule.len() < 12);
+    assert!((principal - 10_000.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_builder_accepts_typed_terms() {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let typed = Amortization::builder()
+        .principal(Principal::from(200_000.0))
+        .rate(Rate::fraction(0.035))
+        .term(Term::years(30))
+        .start_date(start)
+        .build()
+        .unwrap();
+    let direct = Amortization::new(200_000.0, 3.5, 360, Some(start)).unwrap();
+    let from_terms = Amortization::from_terms(Principal::new(200_000.0), Rate::percent(3.5), Term::months(360), Some(start)).unwrap();
+
+    assert_eq!(typed.periodic_payment, direct.periodic_payment);
+    assert_eq!(typed.schedule.len(), 360);
+    assert_eq!(from_terms.periodic_payment, direct.periodic_payment);
+    assert_eq!(Term::from(360), Term::years(30));
+    assert!((Rate::fraction(0.035).as_percent() - 3.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_builder_flags_rates_that_look_like_fractions() {
+    let builder = Amortization::builder().principal(200_000.0).rate(Rate::percent(0.05)).term_months(360);
+    assert_eq!(builder.suspicious_rate(), Some(0.05));
+    assert!(Rate::percent(0.05).is_likely_fraction());
+
+    assert_eq!(builder.rate(Rate::fraction(0.05)).suspicious_rate(), None);
+    assert!(!Rate::percent(0.0).is_likely_fraction());
+    assert!((Rate::percent(5.0).as_fraction() - 0.05).abs() < 1e-12);
+}
+
+#[test]
+fn test_terms_in_years_and_months() {
+    let loan = Amortization::builder()
+        .principal(20_000.0)
+        .rate(Rate::percent(7.0))
+        .term(Term::years_months(5, 6))
+        .build()
+        .unwrap();
+    assert_eq!(loan.schedule.len(), 66);
+    assert_eq!(loan.payoff_term(), Term::months(66));
+    assert_eq!(loan.summary().payoff_term, Term::months(66));
+
+    assert_eq!(Term::years_months(5, 6).to_string(), "5 years, 6 months");
+    assert_eq!(Term::years(1).to_string(), "1 year");
+    assert_eq!(Term::months(1).to_string(), "1 month");
+    assert_eq!(Term::default().to_string(), "0 months");
+    assert_eq!(Term::of_periods(26, PaymentFrequency::BiWeekly), Term::years(1));
+    assert_eq!(Term::years(23).change_from(Term::years_months(30, 3)), "7 years, 3 months sooner");
+    assert_eq!(Term::months(14).change_from(Term::years(1)), "2 months later");
+    assert_eq!(Term::years(1).change_from(Term::months(12)), "same term");
+}
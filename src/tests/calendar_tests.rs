@@ -0,0 +1,158 @@
+use chrono::NaiveDate;
+use crate::{
+    Amortization, AmortizationError, BusinessDayConvention, DateRollConvention, FederalReserveHolidays,
+    HolidayCalendar, PaymentFrequency, Rate, WeekendsOnly,
+};
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn due_dates(start: NaiveDate, date_roll: DateRollConvention) -> Vec<NaiveDate> {
+    Amortization::builder()
+        .principal(12_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(6)
+        .start_date(start)
+        .date_roll(date_roll)
+        .build()
+        .unwrap()
+        .schedule
+        .iter()
+        .map(|p| p.date.unwrap())
+        .collect()
+}
+
+#[test]
+fn test_same_day_returns_to_start_day_after_short_month() {
+    let dates = due_dates(date(2025, 1, 31), DateRollConvention::SameDay);
+
+    assert_eq!(dates[..4], [date(2025, 1, 31), date(2025, 2, 28), date(2025, 3, 31), date(2025, 4, 30)]);
+}
+
+#[test]
+fn test_end_of_month_preserved() {
+    let dates = due_dates(date(2025, 2, 28), DateRollConvention::EndOfMonth);
+    assert_eq!(dates[..3], [date(2025, 2, 28), date(2025, 3, 31), date(2025, 4, 30)]);
+
+    // A start date that isn't a month end behaves like SameDay
+    let dates = due_dates(date(2025, 2, 27), DateRollConvention::EndOfMonth);
+    assert_eq!(dates[..2], [date(2025, 2, 27), date(2025, 3, 27)]);
+}
+
+#[test]
+fn test_day_of_month_rolls_forward() {
+    let dates = due_dates(date(2025, 1, 10), DateRollConvention::DayOfMonth(31));
+
+    assert_eq!(dates[..4], [date(2025, 1, 31), date(2025, 3, 1), date(2025, 3, 31), date(2025, 5, 1)]);
+}
+
+#[test]
+fn test_day_of_month_or_last() {
+    let dates = due_dates(date(2024, 1, 5), DateRollConvention::DayOfMonthOrLast(30));
+
+    assert_eq!(dates[..3], [date(2024, 1, 30), date(2024, 2, 29), date(2024, 3, 30)]);
+}
+
+#[test]
+fn test_quarterly_and_weekly_due_dates() {
+    let start = date(2025, 1, 31);
+    let convention = DateRollConvention::SameDay;
+
+    assert_eq!(PaymentFrequency::Quarterly.due_date(start, 1, convention), Some(date(2025, 4, 30)));
+    assert_eq!(PaymentFrequency::Quarterly.due_date(start, 2, convention), Some(date(2025, 7, 31)));
+    assert_eq!(PaymentFrequency::Weekly.due_date(start, -1, convention), Some(date(2025, 1, 24)));
+}
+
+#[test]
+fn test_recast_keeps_day_of_month() {
+    let loan = Amortization::builder()
+        .principal(12_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(6)
+        .start_date(date(2025, 1, 31))
+        .build()
+        .unwrap();
+    let recast = loan.recast_at(1).unwrap();
+
+    assert_eq!(recast.schedule[0].date, Some(date(2025, 2, 28)));
+    assert_eq!(recast.schedule[1].date, Some(date(2025, 3, 31)));
+}
+
+#[test]
+fn test_invalid_due_day() {
+    let result = Amortization::builder()
+        .principal(12_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(6)
+        .date_roll(DateRollConvention::DayOfMonth(32))
+        .build();
+
+    assert!(matches!(result, Err(AmortizationError::InvalidDueDay(32))));
+}
+
+#[test]
+fn test_business_day_conventions() {
+    let calendar = WeekendsOnly;
+    // Saturday, May 31 2025
+    let saturday = date(2025, 5, 31);
+
+    assert_eq!(BusinessDayConvention::Unadjusted.adjust(saturday, &calendar), Some(saturday));
+    assert_eq!(BusinessDayConvention::Following.adjust(saturday, &calendar), Some(date(2025, 6, 2)));
+    assert_eq!(BusinessDayConvention::ModifiedFollowing.adjust(saturday, &calendar), Some(date(2025, 5, 30)));
+    assert_eq!(BusinessDayConvention::ModifiedFollowing.adjust(date(2025, 5, 3), &calendar), Some(date(2025, 5, 5)));
+    assert_eq!(BusinessDayConvention::Preceding.adjust(date(2025, 6, 1), &calendar), Some(date(2025, 5, 30)));
+}
+
+#[test]
+fn test_federal_reserve_holidays() {
+    let calendar = FederalReserveHolidays;
+
+    assert!(calendar.is_holiday(date(2025, 1, 20)));
+    assert!(calendar.is_holiday(date(2025, 5, 26)));
+    assert!(calendar.is_holiday(date(2025, 11, 27)));
+    // Juneteenth 2022 fell on a Sunday and was observed on Monday
+    assert!(calendar.is_holiday(date(2022, 6, 20)));
+    // Saturday holidays are not moved to Friday
+    assert!(!calendar.is_holiday(date(2026, 7, 3)));
+    assert!(!calendar.is_business_day(date(2025, 12, 25)));
+    assert!(calendar.is_business_day(date(2025, 12, 26)));
+}
+
+#[test]
+fn test_schedule_payment_dates_skip_holidays() {
+    let loan = Amortization::builder()
+        .principal(12_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(12)
+        .start_date(date(2025, 1, 1))
+        .business_day_convention(BusinessDayConvention::Following)
+        .holiday_calendar(FederalReserveHolidays)
+        .build()
+        .unwrap();
+
+    // New Year's Day, then a Saturday and a Sunday
+    assert_eq!(loan.schedule[0].date, Some(date(2025, 1, 2)));
+    assert_eq!(loan.schedule[1].date, Some(date(2025, 2, 3)));
+    assert_eq!(loan.schedule[2].date, Some(date(2025, 3, 3)));
+    // Later due dates still count from the unadjusted schedule
+    assert_eq!(loan.schedule[3].date, Some(date(2025, 4, 1)));
+    assert_eq!(loan.schedule[5].date, Some(date(2025, 6, 2)));
+    assert_eq!(loan.payment_date(2), Some(date(2025, 2, 3)));
+    assert_eq!(loan.due_date(2), Some(date(2025, 2, 1)));
+}
+
+#[test]
+fn test_holiday_list_calendar() {
+    let loan = Amortization::builder()
+        .principal(12_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(3)
+        .start_date(date(2025, 4, 15))
+        .business_day_convention(BusinessDayConvention::Preceding)
+        .holiday_calendar(vec![date(2025, 5, 15), date(2025, 5, 14)])
+        .build()
+        .unwrap();
+
+    assert_eq!(loan.schedule[1].date, Some(date(2025, 5, 13)));
+}
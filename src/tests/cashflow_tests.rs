@@ -0,0 +1,62 @@
+use chrono::NaiveDate;
+use crate::cashflow::{irr, npv, xirr, xnpv};
+use crate::AmortizationError;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn dated_flows() -> Vec<(NaiveDate, f64)> {
+    vec![
+        (date(2008, 1, 1), -10_000.0),
+        (date(2008, 3, 1), 2_750.0),
+        (date(2008, 10, 30), 4_250.0),
+        (date(2009, 2, 15), 3_250.0),
+        (date(2009, 4, 1), 2_750.0),
+    ]
+}
+
+#[test]
+fn test_npv_discounts_first_flow() {
+    assert!((npv(0.10, &[-10_000.0, 3_000.0, 4_200.0, 6_800.0]) - 1_188.44).abs() < 0.01);
+    assert_eq!(npv(0.0, &[100.0, 200.0]), 300.0);
+}
+
+#[test]
+fn test_irr() {
+    let flows = [-70_000.0, 12_000.0, 15_000.0, 18_000.0, 21_000.0, 26_000.0];
+    assert!((irr(&flows).unwrap() - 0.086631).abs() < 1e-6);
+
+    let losing = [-1_000.0, 300.0, 300.0, 300.0];
+    let rate = irr(&losing).unwrap();
+    assert!(rate < 0.0);
+    assert!(npv(rate, &losing).abs() < 1e-6);
+}
+
+#[test]
+fn test_irr_with_several_sign_changes() {
+    // Rates of about 11.27% and 88.68% both give zero net present value
+    let flows = [100.0, -300.0, 210.0];
+    let rate = irr(&flows).unwrap();
+    assert!((rate - 0.112702).abs() < 1e-6, "got {}", rate);
+
+    let result = irr(&[100.0, -300.0, 300.0]);
+    assert!(matches!(result, Err(AmortizationError::NoSolution(_))), "got {:?}", result);
+}
+
+#[test]
+fn test_xnpv_and_xirr() {
+    let flows = dated_flows();
+    assert!((xnpv(0.09, &flows).unwrap() - 2_086.65).abs() < 0.01);
+
+    let rate = xirr(&flows).unwrap();
+    assert!((rate - 0.373363).abs() < 1e-6);
+    assert!(xnpv(rate, &flows).unwrap().abs() < 1e-6);
+}
+
+#[test]
+fn test_cash_flows_without_sign_change() {
+    assert!(matches!(irr(&[100.0, 200.0]), Err(AmortizationError::NoSolution(_))));
+    assert!(matches!(xirr(&[]), Err(AmortizationError::NoSolution(_))));
+    assert!(matches!(xnpv(0.05, &[]), Err(AmortizationError::MissingParameter(_))));
+}
@@ -0,0 +1,50 @@
+use crate::Amortization;
+
+fn loan() -> Amortization {
+    Amortization::new(10_000.0, 6.0, 24, None).unwrap()
+}
+
+#[test]
+fn test_balance_series_starts_at_principal() {
+    let series = loan().balance_series();
+
+    assert_eq!(series.len(), 25);
+    assert_eq!(series[0], (0.0, 10_000.0));
+    assert_eq!(series[24].0, 24.0);
+    assert!(series[24].1.abs() < 0.01);
+}
+
+#[test]
+fn test_cumulative_series_reach_totals() {
+    let loan = loan();
+    let interest = loan.cumulative_interest_series();
+    let principal = loan.cumulative_principal_series();
+
+    assert!((interest.last().unwrap().1 - loan.total_interest).abs() < 0.01);
+    assert!((principal.last().unwrap().1 - 10_000.0).abs() < 0.01);
+    assert!(interest.windows(2).all(|w| w[1].1 >= w[0].1));
+}
+
+#[test]
+fn test_split_series_per_payment() {
+    let loan = loan();
+    let interest = loan.interest_series();
+    let principal = loan.principal_series();
+
+    assert_eq!(interest.len(), 24);
+    for (i, p) in interest.iter().zip(&principal) {
+        assert_eq!(i.0, p.0);
+    }
+    assert!(interest[0].1 > interest[23].1);
+    assert!(principal[0].1 < principal[23].1);
+}
+
+#[test]
+fn test_svg_chart() {
+    let svg = loan().to_svg_chart(640, 480);
+
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert_eq!(svg.matches("<polyline").count(), 3);
+    assert!(svg.contains("Cumulative interest"));
+}
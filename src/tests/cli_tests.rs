@@ -0,0 +1,123 @@
+use crate::cli::{run, CliArgs, Command, OutputFormat};
+
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(String::from).collect()
+}
+
+fn output(line: &str) -> String {
+    let mut out = Vec::new();
+    run(args(line), &mut out, &mut Vec::new()).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_parse_arguments() {
+    let parsed = CliArgs::parse(args("schedule --principal 200000 --apr 6 --term 360 --start-date 2024-01-01 --extra-payment 50 --format csv")).unwrap();
+
+    assert_eq!(parsed.command, Command::Schedule);
+    assert_eq!(parsed.principal, Some(200_000.0));
+    assert_eq!(parsed.aprs, vec![6.0]);
+    assert_eq!(parsed.terms, vec![360]);
+    assert_eq!(parsed.extra_payment, 50.0);
+    assert_eq!(parsed.format, OutputFormat::Csv);
+    assert!(parsed.start_date.is_some());
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(CliArgs::parse(args("")).unwrap_err().0.starts_with("Usage"));
+    assert!(CliArgs::parse(args("amortise")).unwrap_err().0.starts_with("Unknown command: amortise"));
+    assert_eq!(CliArgs::parse(args("schedule --apr six")).unwrap_err().0, "Invalid value for --apr: six");
+    assert_eq!(CliArgs::parse(args("schedule --format xml")).unwrap_err().0, "Unknown format: xml");
+    assert_eq!(CliArgs::parse(args("schedule --principal")).unwrap_err().0, "Missing value for --principal");
+
+    let mut out = Vec::new();
+    assert_eq!(run(args("schedule --apr 6 --term 12"), &mut out, &mut Vec::new()).unwrap_err().0, "Missing required option --principal");
+}
+
+#[test]
+fn test_schedule_formats() {
+    let csv = output("schedule --principal 1000 --apr 5 --term 12 --format csv");
+    assert!(csv.starts_with("Installment,Date,"));
+    assert_eq!(csv.lines().count(), 13);
+
+    let json = output("schedule --principal 1000 --apr 5 --term 12 --format json");
+    assert!(json.starts_with("{\"schema_version\":1"));
+
+    let table = output("schedule --principal 1000 --apr 5 --term 12");
+    assert!(table.lines().next().unwrap().contains("Principal"));
+    assert_eq!(table.lines().count(), 14);
+
+    let ics = output("schedule --principal 1000 --apr 5 --term 12 --start-date 2024-01-01 --format ics");
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 12);
+
+    let mut out = Vec::new();
+    assert_eq!(run(args("compare --principal 1000 --apr 5 --term 12 --format ics"), &mut out, &mut Vec::new()).unwrap_err().0,
+        "--format ics is only available for schedule");
+}
+
+#[test]
+fn test_fixed_payment_sets_the_term() {
+    let csv = output("schedule --principal 1000 --apr 5 --payment 100 --format csv");
+    assert_eq!(csv.lines().count(), 12);
+    assert_eq!(output("schedule --principal 1000 --apr 5 --payment 100 --term 6 --format csv").lines().count(), 7);
+
+    let mut out = Vec::new();
+    assert_eq!(run(args("schedule --principal 1000 --apr 5"), &mut out, &mut Vec::new()).unwrap_err().0, "Missing required option --term");
+}
+
+#[test]
+fn test_compare_loans() {
+    let table = output("compare --principal 200000 --apr 6 --term 360 --apr 5.5 --term 180");
+    assert_eq!(table.lines().count(), 3);
+
+    let csv = output("compare --principal 200000 --apr 6 --apr 5.5 --apr 5 --term 360 --format csv");
+    assert_eq!(csv.lines().count(), 4);
+}
+
+#[test]
+fn test_payoff_quote() {
+    let table = output("payoff --principal 10000 --apr 5 --term 12 --period 6");
+    assert!(table.starts_with("Payoff after 6 payments: "));
+
+    let mut out = Vec::new();
+    assert!(run(args("payoff --principal 10000 --apr 5 --term 12"), &mut out, &mut Vec::new()).is_err());
+}
+
+#[test]
+fn test_warns_about_fractional_rates() {
+    let parsed = CliArgs::parse(args("schedule --principal 200000 --apr 0.065 --term 360")).unwrap();
+    assert_eq!(parsed.warnings(), ["--apr 0.065 is read as 0.065%; pass 6.5 for 6.5%"]);
+
+    let parsed = CliArgs::parse(args("compare --principal 200000 --apr 6.5 --term 360 --apr 0 --term 180")).unwrap();
+    assert!(parsed.warnings().is_empty());
+
+    let (mut out, mut err) = (Vec::new(), Vec::new());
+    run(args("schedule --principal 1000 --apr 0.065 --term 12 --format csv"), &mut out, &mut err).unwrap();
+    assert_eq!(String::from_utf8(err).unwrap(), "warning: --apr 0.065 is read as 0.065%; pass 6.5 for 6.5%\n");
+    assert!(String::from_utf8(out).unwrap().starts_with("Installment,Date,"));
+}
+
+#[test]
+fn test_loans_from_config_file() {
+    let path = std::env::temp_dir().join("amortize_rs_cli_config_test.json");
+    std::fs::write(&path, r#"[{"principal": 200000, "apr": 6, "term_months": 360}, {"principal": 200000, "apr": 5, "term_months": 180}]"#).unwrap();
+    let parsed = CliArgs::parse(vec!["compare".to_string(), "--config".to_string(), path.display().to_string()]).unwrap();
+    let loans = parsed.loans();
+    let conflicting = CliArgs::parse(vec!["compare".to_string(), "--config".to_string(), path.display().to_string(), "--apr".to_string(), "5".to_string()]).unwrap().loans();
+    std::fs::remove_file(&path).unwrap();
+
+    let loans = loans.unwrap();
+    assert_eq!(loans.len(), 2);
+    assert_eq!(loans[1].schedule.len(), 180);
+    assert!(conflicting.unwrap_err().0.starts_with("--config cannot be combined"));
+}
+
+#[cfg(feature = "i18n")]
+#[test]
+fn test_language_flag() {
+    let table = output("schedule --principal 1000 --apr 5 --term 3 --lang de-DE");
+    assert!(table.lines().next().unwrap().contains("Restschuld"));
+    assert_eq!(CliArgs::parse(args("schedule --lang xx")).unwrap_err().0, "Unknown language: xx");
+}
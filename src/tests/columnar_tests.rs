@@ -0,0 +1,67 @@
+use chrono::NaiveDate;
+use crate::{Amortization, ColumnarSchedule, Payment, Rate};
+use super::assert_float_eq;
+
+fn loan() -> Amortization {
+    Amortization::new(200_000.0, 6.0, 360, NaiveDate::from_ymd_opt(2025, 1, 1)).unwrap()
+}
+
+#[test]
+fn test_round_trips_to_payments() {
+    let loan = loan();
+    let columnar = loan.columnar_schedule();
+    assert_eq!(columnar.len(), 360);
+    assert_eq!(columnar.get(0).as_ref(), loan.schedule.first());
+    assert_eq!(columnar.get(359).as_ref(), loan.schedule.last());
+    assert!(columnar.get(360).is_none());
+    assert_eq!(columnar.to_payments(), loan.schedule);
+
+    // Streamed straight from the calculator, without a `Vec<Payment>`
+    let streamed: ColumnarSchedule = loan.payments().collect();
+    assert_eq!(streamed, columnar);
+}
+
+#[test]
+fn test_sparse_columns() {
+    let loan = Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .inflation_rate(3.0)
+        .build()
+        .unwrap();
+    let columnar = loan.columnar_schedule();
+    assert_eq!(columnar.to_payments(), loan.schedule);
+    assert!(columnar.get(10).unwrap().real.is_some());
+    assert!(columnar.get(10).unwrap().accrual.is_none());
+
+    // A column first filled part way through keeps the earlier rows empty
+    let mut rows = loan.schedule[..3].to_vec();
+    rows[0].ltv = None;
+    rows[1].ltv = None;
+    rows[2].ltv = Some(80.0);
+    let columnar = ColumnarSchedule::from(rows.as_slice());
+    assert_eq!(columnar.to_payments(), rows);
+}
+
+#[test]
+fn test_column_aggregates() {
+    let loan = loan();
+    let columnar = loan.columnar_schedule();
+    assert_float_eq(columnar.total_interest(), loan.total_interest);
+    assert_float_eq(columnar.total_payment(), loan.total_payment);
+    assert_float_eq(columnar.total_principal(), 200_000.0);
+    assert_eq!(columnar.interest()[0], loan.schedule[0].interest);
+    assert_eq!(columnar.remaining_balances()[359], 0.0);
+    assert_eq!(columnar.installment_numbers()[359], 360);
+    assert_eq!(columnar.dates()[0], loan.schedule[0].date);
+}
+
+#[test]
+fn test_uses_less_memory() {
+    let mut columnar = loan().columnar_schedule();
+    columnar.shrink_to_fit();
+    let rows = 360 * std::mem::size_of::<Payment>();
+    assert!(columnar.heap_size() * 2 <= rows, "{} bytes against {}", columnar.heap_size(), rows);
+    assert_eq!(ColumnarSchedule::new().heap_size(), 0);
+}
@@ -0,0 +1,66 @@
+use crate::{Amortization, LoanComparison};
+
+fn points_vs_no_points() -> LoanComparison {
+    let with_points = Amortization::new(300_000.0, 6.5, 360, None).unwrap();
+    let without_points = Amortization::new(300_000.0, 7.0, 360, None).unwrap();
+    LoanComparison::new(vec![with_points, without_points]).with_upfront_costs(vec![3_000.0])
+}
+
+#[test]
+fn test_summaries_report_interest_deltas() {
+    let comparison = points_vs_no_points();
+    let summaries = comparison.summaries();
+
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(summaries[0].interest_delta, 0.0);
+    assert_eq!(summaries[0].upfront_cost, 3_000.0);
+    assert_eq!(summaries[1].upfront_cost, 0.0);
+    assert!(summaries[1].interest_delta > 0.0);
+    assert!((summaries[1].interest_delta - (summaries[1].total_interest - summaries[0].total_interest)).abs() < 1e-9);
+}
+
+#[test]
+fn test_break_even_month_for_points() {
+    let comparison = points_vs_no_points();
+    let monthly_savings = comparison.loans[1].periodic_payment - comparison.loans[0].periodic_payment;
+    let expected = (3_000.0 / monthly_savings).ceil() as u32;
+
+    assert_eq!(comparison.break_even_month(0, 1), Some(expected));
+    assert_eq!(comparison.break_even_month(1, 0), Some(0));
+    assert_eq!(comparison.break_even_month(0, 5), None);
+}
+
+#[test]
+fn test_break_even_never_reached() {
+    let cheap = Amortization::new(100_000.0, 5.0, 360, None).unwrap();
+    let same = Amortization::new(100_000.0, 5.0, 360, None).unwrap();
+    let comparison = LoanComparison::new(vec![cheap, same]).with_upfront_costs(vec![1_000.0, 0.0]);
+
+    assert_eq!(comparison.break_even_month(0, 1), None);
+    assert_eq!(comparison.lowest_total_cost(), Some(1));
+}
+
+#[test]
+fn test_balance_table_pads_shorter_loans() {
+    let short = Amortization::new(10_000.0, 5.0, 12, None).unwrap();
+    let long = Amortization::new(10_000.0, 5.0, 24, None).unwrap();
+    let comparison = LoanComparison::new(vec![short, long]);
+    let table = comparison.balance_table();
+
+    assert_eq!(table.len(), 24);
+    assert_eq!(table[0].period, 1);
+    assert_eq!(table[0].balances[0], comparison.loans[0].schedule[0].remaining_balance);
+    assert_eq!(table[23].balances, vec![0.0, 0.0]);
+    assert_eq!(table[18].balances[0], 0.0);
+    assert!(table[18].balances[1] > 0.0);
+}
+
+#[test]
+fn test_term_change_against_first_loan() {
+    let thirty = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let fifteen = Amortization::new(200_000.0, 5.5, 180, None).unwrap();
+    let summaries = LoanComparison::new(vec![thirty, fifteen]).summaries();
+    assert_eq!(summaries[0].term_change, "same term");
+    assert_eq!(summaries[1].term.to_string(), "15 years");
+    assert_eq!(summaries[1].term_change, "15 years sooner");
+}
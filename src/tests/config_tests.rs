@@ -0,0 +1,141 @@
+use chrono::NaiveDate;
+use crate::config::parse_toml;
+use crate::json::JsonValue;
+use crate::{Amortization, AmortizationError, CalculatorConfig, ConfigFormat, PaymentFrequency, PaymentTiming, Portfolio};
+
+const PORTFOLIO_TOML: &str = r#"
+# Shared by every loan below
+start_date = 2024-01-01
+frequency = "monthly"
+
+[[loans]]
+principal = 250_000
+apr = 6.5
+term_years = 30
+
+[[loans]]
+principal = 18_500.00   # car
+apr = 4.9
+term_months = 60
+extra_payment = 50
+"#;
+
+#[test]
+fn test_single_loan_from_toml() {
+    let config: CalculatorConfig = "principal = 200000\napr = 6\nterm_months = 360\nstart_date = 2024-02-01\n".parse().unwrap();
+    assert_eq!(config.balance, 200_000.0);
+    assert_eq!(config.apr, 6.0);
+    assert_eq!(config.loan_term, 360);
+    assert_eq!(config.start_date, NaiveDate::from_ymd_opt(2024, 2, 1));
+    let loan = Amortization::from_config(config).unwrap();
+    assert!((loan.periodic_payment - 1199.10).abs() < 0.01);
+}
+
+#[test]
+fn test_single_loan_from_json() {
+    let input = r#"{"principal": 10000, "apr": 5, "term_years": 3, "frequency": "quarterly", "payment_timing": "advance"}"#;
+    let config: CalculatorConfig = input.parse().unwrap();
+    assert_eq!(config.loan_term, 36);
+    assert_eq!(config.frequency, PaymentFrequency::Quarterly);
+    assert_eq!(config.payment_timing, PaymentTiming::Advance);
+    assert_eq!(config.start_date, None);
+}
+
+#[test]
+fn test_several_loans_share_defaults() {
+    let configs = CalculatorConfig::all_from_str(PORTFOLIO_TOML, ConfigFormat::Toml).unwrap();
+    assert_eq!(configs.len(), 2);
+    assert_eq!(configs[0].loan_term, 360);
+    assert_eq!(configs[1].loan_term, 60);
+    assert_eq!(configs[1].extra_payment, 50.0);
+    assert!(configs.iter().all(|c| c.start_date == NaiveDate::from_ymd_opt(2024, 1, 1)));
+
+    let pool = Portfolio::generate(configs).unwrap();
+    assert_eq!(pool.loans.len(), 2);
+}
+
+#[test]
+fn test_toml_and_json_agree() {
+    let json = r#"{
+        "start_date": "2024-01-01",
+        "frequency": "monthly",
+        "loans": [
+            {"principal": 250000, "apr": 6.5, "term_years": 30},
+            {"principal": 18500, "apr": 4.9, "term_months": 60, "extra_payment": 50}
+        ]
+    }"#;
+    let from_json = CalculatorConfig::all_from_str(json, ConfigFormat::Json).unwrap();
+    let from_toml = CalculatorConfig::all_from_str(PORTFOLIO_TOML, ConfigFormat::Toml).unwrap();
+    for (a, b) in from_json.into_iter().zip(from_toml) {
+        let a = Amortization::from_config(a).unwrap();
+        let b = Amortization::from_config(b).unwrap();
+        assert!(a.approx_eq(&b, 0.0));
+    }
+    assert_eq!(CalculatorConfig::all_from_str("[]", ConfigFormat::Json).unwrap().len(), 0);
+}
+
+#[test]
+fn test_detect_format() {
+    assert_eq!(ConfigFormat::detect("  {\"apr\": 5}"), ConfigFormat::Json);
+    assert_eq!(ConfigFormat::detect("[{\"apr\": 5}]"), ConfigFormat::Json);
+    assert_eq!(ConfigFormat::detect("[[loans]]\napr = 5"), ConfigFormat::Toml);
+    assert_eq!(ConfigFormat::detect("apr = 5"), ConfigFormat::Toml);
+    assert_eq!(ConfigFormat::from_path("loans.JSON".as_ref()), Some(ConfigFormat::Json));
+    assert_eq!(ConfigFormat::from_path("loans.yaml".as_ref()), None);
+}
+
+#[test]
+fn test_from_path() {
+    let path = std::env::temp_dir().join("amortize_rs_config_test.toml");
+    std::fs::write(&path, "principal = 1200\napr = 0\nterm_months = 12\n").unwrap();
+    let config = CalculatorConfig::from_path(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(config.unwrap().balance, 1200.0);
+
+    let missing = CalculatorConfig::from_path(std::env::temp_dir().join("amortize_rs_no_such_file.toml"));
+    assert!(matches!(missing, Err(AmortizationError::InvalidConfig(_))));
+}
+
+#[test]
+fn test_invalid_definitions() {
+    let parse = |input: &str| input.parse::<CalculatorConfig>();
+    assert!(matches!(parse("apr = 5\nterm_months = 12"), Err(AmortizationError::MissingParameter("principal"))));
+    assert!(matches!(parse("principal = 1\napr = 5"), Err(AmortizationError::MissingParameter("term_months"))));
+    assert!(matches!(parse("principal = 1\napr = 5\nterm_months = 12\nterm_years = 1"), Err(AmortizationError::ConflictingOptions(..))));
+    assert!(matches!(parse("principal = 1\napr = 5\nterm_months = 12\nterm = 1"), Err(AmortizationError::InvalidConfig(_))));
+    assert!(matches!(parse("principal = \"lots\"\napr = 5\nterm_months = 12"), Err(AmortizationError::InvalidConfig(_))));
+    assert!(matches!(parse("principal = 1\napr = 5\nterm_months = 12.5"), Err(AmortizationError::InvalidConfig(_))));
+    assert!(matches!(parse(PORTFOLIO_TOML), Err(AmortizationError::InvalidConfig(_))));
+
+    let error = parse("principal = 1\nprincipal = 2").unwrap_err();
+    assert_eq!(error.to_string(), "Invalid loan definition: line 2: 'principal' is defined twice");
+    assert!(parse("principal 1").unwrap_err().to_string().contains("line 1"));
+    assert!(parse("principal = [1, 2").is_err());
+}
+
+#[test]
+fn test_toml_values() {
+    let input = "
+        note = 'C:\\loans # not a comment'
+        rates = [
+            5.0,   # first
+            5.5,
+        ]
+        cap = { percent = 7.5, recast = true }
+        [escrow]
+        \"tax-rate\" = 1_200
+    ";
+    let value = parse_toml(input).unwrap();
+    assert_eq!(value.get("note"), Some(&JsonValue::from("C:\\loans # not a comment")));
+    assert_eq!(value.get("rates"), Some(&JsonValue::Array(vec![JsonValue::Number(5.0), JsonValue::Number(5.5)])));
+    assert_eq!(value.get("cap").and_then(|cap| cap.get("recast")), Some(&JsonValue::Bool(true)));
+    assert_eq!(value.get("escrow").and_then(|escrow| escrow.get("tax-rate")), Some(&JsonValue::Number(1200.0)));
+}
+
+#[test]
+fn test_deeply_nested_values_are_rejected() {
+    let toml = format!("rates = {}{}", "[".repeat(500_000), "]".repeat(500_000));
+    assert!(parse_toml(&toml).unwrap_err().contains("nesting too deep"));
+    let json = "[".repeat(500_000);
+    assert!(matches!(CalculatorConfig::all_from_str(&json, ConfigFormat::Json), Err(AmortizationError::InvalidConfig(_))));
+}
@@ -0,0 +1,64 @@
+use crate::{CalculatorConfig, Consolidation, ConsolidationRounding};
+use super::assert_float_eq;
+
+fn student_loans() -> Vec<CalculatorConfig> {
+    vec![
+        CalculatorConfig::new(10_000.0, 4.53, 120, None),
+        CalculatorConfig::new(15_000.0, 5.05, 96, None),
+        CalculatorConfig::new(5_000.0, 6.54, 60, None),
+    ]
+}
+
+#[test]
+fn test_federal_rounding_up_to_an_eighth() {
+    assert_eq!(ConsolidationRounding::UpToEighth.apply(5.01), 5.125);
+    assert_eq!(ConsolidationRounding::UpToEighth.apply(5.125), 5.125);
+    assert_eq!(ConsolidationRounding::UpToEighth.apply(5.126), 5.25);
+    assert_eq!(ConsolidationRounding::Exact.apply(5.01), 5.01);
+}
+
+#[test]
+fn test_consolidated_loan_at_the_weighted_rate() {
+    let analysis = Consolidation::new(student_loans(), 240)
+        .with_rounding(ConsolidationRounding::UpToEighth)
+        .analyze()
+        .unwrap();
+
+    let weighted = (10_000.0 * 4.53 + 15_000.0 * 5.05 + 5_000.0 * 6.54) / 30_000.0;
+    assert_float_eq(analysis.weighted_average_rate, weighted);
+    assert_eq!(analysis.consolidated_rate, 5.125);
+    assert_eq!(analysis.consolidated.balance, 30_000.0);
+    assert_eq!(analysis.consolidated.schedule.len(), 240);
+
+    // A longer term lowers the installment and raises the interest
+    let separate_payment: f64 = analysis.separate.loans.iter().map(|loan| loan.periodic_payment).sum();
+    assert_float_eq(analysis.separate_payment, separate_payment);
+    assert!(analysis.payment_savings > 0.0);
+    assert_float_eq(analysis.additional_interest, analysis.consolidated.total_interest - analysis.separate.total_interest);
+    assert!(analysis.additional_interest > 0.0);
+
+    // Once the eight-year loan is repaid only the ten-year one is left, below the new installment
+    assert_eq!(analysis.crossover_month(), Some(97));
+
+    let summaries = analysis.comparison().summaries();
+    assert_eq!(summaries.len(), 4);
+    assert_eq!(summaries[0].apr, 5.125);
+}
+
+#[test]
+fn test_rate_cap_and_federal_terms() {
+    let analysis = Consolidation::new(student_loans(), 120)
+        .with_rate_cap(5.0)
+        .analyze()
+        .unwrap();
+    assert_eq!(analysis.consolidated_rate, 5.0);
+
+    assert_eq!(Consolidation::federal_term_months(7_000.0), 120);
+    assert_eq!(Consolidation::federal_term_months(30_000.0), 240);
+    assert_eq!(Consolidation::federal_term_months(75_000.0), 360);
+}
+
+#[test]
+fn test_nothing_to_consolidate() {
+    assert!(Consolidation::new(Vec::new(), 120).analyze().is_err());
+}
@@ -0,0 +1,46 @@
+use chrono::NaiveDate;
+use crate::{AmortizationError, ConstructionLoan};
+use super::assert_float_eq;
+
+fn construction_loan() -> ConstructionLoan {
+    ConstructionLoan::new(6.0, 12, 360)
+        .with_draw(1, 100_000.0)
+        .with_draw(4, 50_000.0)
+        .with_draw(8, 50_000.0)
+        .with_start_date(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+}
+
+#[test]
+fn test_interest_only_on_drawn_balance() {
+    let schedule = construction_loan().schedule().unwrap();
+    let draws = &schedule.draw_phase;
+
+    assert_eq!(draws.len(), 12);
+    assert_float_eq(draws[0].interest, 500.0);
+    assert_float_eq(draws[3].draw, 50_000.0);
+    assert_float_eq(draws[3].interest, 750.0);
+    assert_float_eq(draws[11].drawn_balance, 200_000.0);
+    assert_float_eq(schedule.draw_interest, 3.0 * 500.0 + 4.0 * 750.0 + 5.0 * 1_000.0);
+    assert_float_eq(schedule.total_drawn, 200_000.0);
+}
+
+#[test]
+fn test_converts_to_amortizing_loan() {
+    let schedule = construction_loan().schedule().unwrap();
+    let permanent = &schedule.permanent;
+
+    assert_float_eq(permanent.balance, 200_000.0);
+    assert_float_eq(permanent.periodic_payment, 1_199.10);
+    assert_eq!(permanent.schedule[0].installment_number, 13);
+    assert_eq!(permanent.schedule[0].date, NaiveDate::from_ymd_opt(2026, 1, 1));
+    assert_float_eq(schedule.total_interest, schedule.draw_interest + permanent.total_interest);
+}
+
+#[test]
+fn test_invalid_draws() {
+    let no_draws = ConstructionLoan::new(6.0, 12, 360).schedule();
+    assert!(matches!(no_draws, Err(AmortizationError::MissingParameter("draws"))));
+
+    let late_draw = ConstructionLoan::new(6.0, 12, 360).with_draw(13, 1_000.0).schedule();
+    assert!(matches!(late_draw, Err(AmortizationError::PeriodOutOfRange(13))));
+}
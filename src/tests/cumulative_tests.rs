@@ -0,0 +1,37 @@
+use chrono::NaiveDate;
+use crate::{Amortization, CalculatorConfig, Deferment};
+use super::assert_float_eq;
+
+#[test]
+fn test_cumulative_schedule_running_totals() {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1);
+    let loan = Amortization::new(200_000.0, 6.0, 360, start).unwrap();
+    let cumulative = loan.cumulative_schedule();
+
+    assert_eq!(cumulative.len(), loan.schedule.len());
+    assert_eq!(cumulative[0].date, start);
+    assert_float_eq(cumulative[0].cumulative_interest, 1_000.0);
+    let first_year: f64 = loan.schedule[..12].iter().map(|p| p.principal).sum();
+    assert_float_eq(cumulative[11].cumulative_principal, first_year);
+    assert_float_eq(cumulative[11].percent_paid, first_year / 2_000.0);
+
+    let last = cumulative.last().unwrap();
+    assert_float_eq(last.cumulative_interest, loan.total_interest);
+    assert_float_eq(last.cumulative_paid, loan.total_payment);
+    assert_float_eq(last.percent_paid, 100.0);
+    assert_float_eq(last.remaining_balance, 0.0);
+}
+
+#[test]
+fn test_cumulative_percent_counts_capitalized_interest() {
+    let config = CalculatorConfig {
+        deferment: Some(Deferment::Unsubsidized { periods: 12 }),
+        ..CalculatorConfig::new(20_000.0, 5.0, 120, None)
+    };
+    let loan = Amortization::from_config(config).unwrap();
+    let cumulative = loan.cumulative_schedule();
+
+    assert_float_eq(cumulative[11].percent_paid, 0.0);
+    assert!(cumulative.windows(2).all(|pair| pair[1].percent_paid >= pair[0].percent_paid));
+    assert_float_eq(cumulative.last().unwrap().percent_paid, 100.0);
+}
@@ -0,0 +1,64 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, Rate, ScheduleDate};
+
+// Stands in for another crate's date type, kept as a year and day of the year
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct OrdinalDate {
+    year: i32,
+    ordinal: u32,
+}
+
+impl ScheduleDate for OrdinalDate {
+    fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        let date = <NaiveDate as ScheduleDate>::from_ymd(year, month, day)?;
+        Some(OrdinalDate { year, ordinal: chrono::Datelike::ordinal(&date) })
+    }
+
+    fn ymd(&self) -> (i32, u32, u32) {
+        let date = NaiveDate::from_yo_opt(self.year, self.ordinal).unwrap();
+        date.ymd()
+    }
+}
+
+fn loan(start: OrdinalDate) -> Amortization {
+    Amortization::builder()
+        .principal(10_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(12)
+        .start_on(start)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_schedule_with_foreign_dates() {
+    let loan = loan(OrdinalDate { year: 2025, ordinal: 32 });
+
+    assert_eq!(loan.start_date, NaiveDate::from_ymd_opt(2025, 2, 1));
+    assert_eq!(loan.schedule[1].date_as::<OrdinalDate>(), Some(OrdinalDate { year: 2025, ordinal: 60 }));
+    assert_eq!(loan.schedule[11].date_as::<(i32, u32, u32)>(), Some((2026, 1, 1)));
+    assert_eq!(loan.end_date_as::<(i32, u32, u32)>(), loan.end_date.map(|date| date.ymd()));
+    assert_eq!(loan.start_date_as::<OrdinalDate>(), Some(OrdinalDate { year: 2025, ordinal: 32 }));
+}
+
+#[test]
+fn test_tuples_must_be_calendar_dates() {
+    assert_eq!(<(i32, u32, u32)>::from_ymd(2024, 2, 29), Some((2024, 2, 29)));
+    assert_eq!(<(i32, u32, u32)>::from_ymd(2025, 2, 29), None);
+    assert_eq!(Amortization::new(1_000.0, 5.0, 12, None).unwrap().end_date_as::<NaiveDate>(), None);
+}
+
+#[test]
+fn test_start_on_invalid_date() {
+    let builder = Amortization::builder()
+        .principal(10_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(12)
+        .start_on((2024, 2, 30));
+    let error = builder.build().unwrap_err();
+
+    assert!(matches!(error, AmortizationError::InvalidStartDate(2024, 2, 30)));
+    assert_eq!(error.parameter(), Some("start_date"));
+    assert_eq!(error.to_string(), "Start date must be a calendar date, got 2024-02-30");
+    assert!(builder.start_on((2024, 2, 29)).build().is_ok());
+}
@@ -0,0 +1,88 @@
+use crate::{Amortization, DayCount, Rate};
+use chrono::NaiveDate;
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn test_thirty_360_day_counts() {
+    assert_eq!(DayCount::Thirty360.days_between(date(2024, 1, 1), date(2024, 2, 1)), 30);
+    assert_eq!(DayCount::Thirty360.days_between(date(2024, 1, 31), date(2024, 2, 29)), 29);
+    assert_eq!(DayCount::Thirty360.days_between(date(2024, 1, 30), date(2024, 3, 31)), 60);
+    assert_eq!(DayCount::Thirty360.days_between(date(2023, 12, 15), date(2024, 12, 15)), 360);
+}
+
+#[test]
+fn test_actual_day_counts() {
+    assert_eq!(DayCount::Actual365.days_between(date(2024, 2, 1), date(2024, 3, 1)), 29);
+    assert_eq!(DayCount::Actual360.days_between(date(2023, 2, 1), date(2023, 3, 1)), 28);
+    assert!((DayCount::Actual360.year_fraction(date(2024, 1, 1), date(2024, 1, 31)) - 30.0 / 360.0).abs() < 1e-12);
+    assert!((DayCount::Actual365.year_fraction(date(2024, 1, 1), date(2025, 1, 1)) - 366.0 / 365.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_actual_365_interest_follows_date_gap() {
+    let loan = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(12)
+        .start_date(date(2024, 2, 1))
+        .day_count(DayCount::Actual365)
+        .build()
+        .unwrap();
+
+    // First payment accrues from 2024-01-01, the March payment covers leap February
+    let first = &loan.schedule[0];
+    assert!((first.interest - 100_000.0 * 0.06 * 31.0 / 365.0).abs() < 1e-9);
+    let second = &loan.schedule[1];
+    assert!((second.interest - second.beginning_balance * 0.06 * 29.0 / 365.0).abs() < 1e-9);
+    assert_eq!(loan.schedule.len(), 12);
+    assert_eq!(loan.schedule.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_thirty_360_matches_nominal_monthly_interest() {
+    let start = date(2024, 1, 1);
+    let nominal = Amortization::new(50_000.0, 4.5, 60, Some(start)).unwrap();
+    let thirty_360 = Amortization::builder()
+        .principal(50_000.0)
+        .rate(Rate::percent(4.5))
+        .term_months(60)
+        .start_date(start)
+        .day_count(DayCount::Thirty360)
+        .build()
+        .unwrap();
+
+    for (a, b) in nominal.schedule.iter().zip(&thirty_360.schedule) {
+        assert!((a.interest - b.interest).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_actual_360_charges_more_interest_than_actual_365() {
+    let build = |day_count| Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(120)
+        .start_date(date(2024, 1, 15))
+        .day_count(day_count)
+        .build()
+        .unwrap();
+
+    assert!(build(DayCount::Actual360).total_interest > build(DayCount::Actual365).total_interest);
+}
+
+#[test]
+fn test_day_count_ignored_without_dates() {
+    let nominal = Amortization::new(50_000.0, 4.5, 60, None).unwrap();
+    let actual = Amortization::builder()
+        .principal(50_000.0)
+        .rate(Rate::percent(4.5))
+        .term_months(60)
+        .day_count(DayCount::Actual365)
+        .build()
+        .unwrap();
+
+    assert_eq!(nominal.total_interest, actual.total_interest);
+}
@@ -0,0 +1,98 @@
+use crate::{Amortization, Decimal, RatePeriod};
+use chrono::NaiveDate;
+
+fn dec(s: &str) -> Decimal {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_decimal_parse_and_display() {
+    assert_eq!(dec("123.45").to_string(), "123.45");
+    assert_eq!(dec("-0.5").to_string(), "-0.5");
+    assert_eq!(dec("42").to_string(), "42");
+    assert_eq!(format!("{:.2}", dec("2.345")), "2.35");
+    assert!("12a".parse::<Decimal>().is_err());
+    assert!(".".parse::<Decimal>().is_err());
+}
+
+#[test]
+fn test_decimal_arithmetic_is_exact() {
+    let sum = dec("0.1") + dec("0.2");
+    assert_eq!(sum, dec("0.3"));
+    assert_eq!(dec("1.5") * dec("2.25"), dec("3.375"));
+    assert_eq!(dec("10") / dec("4"), dec("2.5"));
+    assert_eq!(Decimal::try_from_f64(0.1).unwrap(), dec("0.1"));
+}
+
+#[test]
+fn test_decimal_large_operands_only_overflow_when_the_result_does() {
+    let large = dec("600000000000000000000000");
+    assert_eq!(dec("1.5") * large, dec("900000000000000000000000"));
+    assert_eq!(dec("-0.25") * large, dec("-150000000000000000000000"));
+    assert_eq!(dec("300000000000000000000001") / large, dec("0.5"));
+    assert_eq!(dec("-1") / dec("300000000000000"), dec("-0.000000000000003"));
+    assert_eq!(large.checked_mul(large), None);
+}
+
+#[test]
+fn test_decimal_rounding_half_away_from_zero() {
+    assert_eq!(dec("2.345").round_dp(2), dec("2.35"));
+    assert_eq!(dec("-2.345").round_dp(2), dec("-2.35"));
+    assert_eq!(dec("2.344").round_dp(2), dec("2.34"));
+}
+
+#[test]
+fn test_decimal_backend_principal_sums_to_balance() {
+    let loan = Amortization::new_with_backend::<Decimal>(280_350.0, 3.5, 60, None).unwrap();
+    let principal: Decimal = loan.schedule.iter()
+        .map(|p| Decimal::try_from_f64(p.principal).unwrap())
+        .sum();
+
+    assert_eq!(loan.schedule.len(), 60);
+    assert_eq!(principal, dec("280350"));
+    assert_eq!(loan.schedule.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_decimal_backend_totals_match_schedule() {
+    let loan = Amortization::new_with_backend::<Decimal>(200_000.0, 3.5, 360, None).unwrap();
+    let paid: Decimal = loan.schedule.iter()
+        .map(|p| Decimal::try_from_f64(p.installment_amount).unwrap())
+        .sum();
+
+    assert_eq!(loan.periodic_payment, 898.09);
+    assert_eq!(Decimal::try_from_f64(loan.total_payment).unwrap(), paid);
+    assert_eq!(
+        Decimal::try_from_f64(loan.total_interest).unwrap(),
+        paid - dec("200000")
+    );
+    for payment in &loan.schedule {
+        let interest = Decimal::try_from_f64(payment.interest).unwrap();
+        assert_eq!(interest, interest.round_dp(2));
+    }
+}
+
+#[test]
+fn test_decimal_backend_adjustable_with_dates() {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let loan = Amortization::adjustable_with_backend::<Decimal>(
+        100_000.0,
+        vec![RatePeriod::new(1, 4.0), RatePeriod::new(13, 6.0)],
+        120,
+        Some(start)
+    ).unwrap();
+
+    assert_eq!(loan.schedule.len(), 120);
+    assert_eq!(loan.schedule[0].date, Some(start));
+    assert!(loan.schedule[12].installment_amount > loan.schedule[11].installment_amount);
+    assert_eq!(loan.schedule.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_f64_backend_matches_default_payment() {
+    let exact = Amortization::new_with_backend::<f64>(10_000.0, 5.0, 12, None).unwrap();
+    let default = Amortization::new(10_000.0, 5.0, 12, None).unwrap();
+
+    assert_eq!(exact.periodic_payment, default.periodic_payment);
+    assert_eq!(exact.schedule.len(), default.schedule.len());
+}
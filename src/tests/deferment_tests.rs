@@ -0,0 +1,130 @@
+use crate::{Amortization, AmortizationError, CapitalizationFrequency, Deferment, LoanType, Rate};
+use super::assert_float_eq;
+
+fn deferred_loan(deferment: Deferment) -> Amortization {
+    Amortization::builder()
+        .principal(30_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(168)
+        .deferment(deferment)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_deferment_rows_have_no_payment() {
+    let loan = deferred_loan(Deferment::Unsubsidized { periods: 48 });
+
+    assert_eq!(loan.schedule.len(), 168);
+    assert!(loan.schedule[..48].iter().all(|p| p.installment_amount == 0.0 && p.principal == 0.0));
+    assert!(loan.is_deferred(48));
+    assert!(!loan.is_deferred(49));
+    assert_eq!(loan.amortizing_periods(), 120);
+}
+
+#[test]
+fn test_unsubsidized_interest_capitalizes_at_repayment() {
+    let loan = deferred_loan(Deferment::Unsubsidized { periods: 48 });
+    let accrued = 30_000.0 * 0.005 * 48.0;
+
+    assert_float_eq(loan.schedule[46].remaining_balance, 30_000.0);
+    assert_float_eq(loan.schedule[47].capitalized_interest, accrued);
+    assert_float_eq(loan.repayment_balance(), 30_000.0 + accrued);
+
+    let expected = Amortization::new(30_000.0 + accrued, 6.0, 120, None).unwrap();
+    assert_float_eq(loan.periodic_payment, expected.periodic_payment);
+    assert_float_eq(loan.schedule[48].installment_amount, expected.periodic_payment);
+    assert_float_eq(loan.schedule.last().unwrap().remaining_balance, 0.0);
+    assert_float_eq(loan.total_interest, expected.total_interest + accrued);
+}
+
+#[test]
+fn test_subsidized_deferment_accrues_nothing() {
+    let loan = deferred_loan(Deferment::Subsidized { periods: 48 });
+    let expected = Amortization::new(30_000.0, 6.0, 120, None).unwrap();
+
+    assert!(loan.schedule[..48].iter().all(|p| p.interest == 0.0));
+    assert_float_eq(loan.repayment_balance(), 30_000.0);
+    assert_float_eq(loan.periodic_payment, expected.periodic_payment);
+    assert_float_eq(loan.total_interest, expected.total_interest);
+}
+
+#[test]
+fn test_deferment_followed_by_interest_only() {
+    let loan = Amortization::builder()
+        .principal(30_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(168)
+        .deferment(Deferment::Subsidized { periods: 12 })
+        .loan_type(LoanType::InterestOnly { io_periods: 12 })
+        .build()
+        .unwrap();
+
+    assert!(loan.is_interest_only(13));
+    assert!(!loan.is_interest_only(12));
+    assert_float_eq(loan.schedule[12].installment_amount, 150.0);
+    assert_eq!(loan.amortizing_periods(), 144);
+}
+
+#[test]
+fn test_deferment_must_leave_repayment_periods() {
+    let result = Amortization::builder()
+        .principal(30_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(48)
+        .deferment(Deferment::Unsubsidized { periods: 48 })
+        .build();
+
+    assert!(matches!(result, Err(AmortizationError::InvalidDefermentPeriods(48))));
+}
+
+fn capitalized_loan(capitalization: CapitalizationFrequency) -> Amortization {
+    Amortization::builder()
+        .principal(30_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(168)
+        .deferment(Deferment::Unsubsidized { periods: 48 })
+        .capitalization(capitalization)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_quarterly_capitalization_compounds_deferred_interest() {
+    let loan = capitalized_loan(CapitalizationFrequency::Quarterly);
+
+    assert_float_eq(loan.schedule[0].accrued_interest, 150.0);
+    assert_float_eq(loan.schedule[1].accrued_interest, 300.0);
+    assert_float_eq(loan.schedule[2].capitalized_interest, 450.0);
+    assert_float_eq(loan.schedule[2].accrued_interest, 0.0);
+    assert_float_eq(loan.schedule[3].interest, 30_450.0 * 0.005);
+    assert_float_eq(loan.repayment_balance(), 30_000.0 * 1.015_f64.powi(16));
+    assert!(loan.schedule[48..].iter().all(|p| p.accrued_interest == 0.0 && p.capitalized_interest == 0.0));
+}
+
+#[test]
+fn test_capitalization_frequency_changes_totals() {
+    let at_end = capitalized_loan(CapitalizationFrequency::AtEndOfEvent);
+    let annually = capitalized_loan(CapitalizationFrequency::Annually);
+    let monthly = capitalized_loan(CapitalizationFrequency::Monthly);
+
+    assert_eq!(at_end.schedule, deferred_loan(Deferment::Unsubsidized { periods: 48 }).schedule);
+    assert_float_eq(at_end.schedule[46].accrued_interest, 150.0 * 47.0);
+    assert_float_eq(annually.schedule[11].capitalized_interest, 1_800.0);
+    assert!(at_end.total_interest < annually.total_interest);
+    assert!(annually.total_interest < monthly.total_interest);
+    assert_float_eq(monthly.repayment_balance(), 30_000.0 * 1.005_f64.powi(48));
+}
+
+#[test]
+fn test_capitalization_frequency_boundaries() {
+    assert!(CapitalizationFrequency::EveryPeriod.capitalizes_at(1, 26));
+    assert!(!CapitalizationFrequency::AtEndOfEvent.capitalizes_at(12, 12));
+    assert!(CapitalizationFrequency::Quarterly.capitalizes_at(3, 12));
+    assert!(!CapitalizationFrequency::Quarterly.capitalizes_at(4, 12));
+    // The sixth biweekly installment is the last in the third month
+    assert!(!CapitalizationFrequency::Quarterly.capitalizes_at(5, 26));
+    assert!(CapitalizationFrequency::Quarterly.capitalizes_at(6, 26));
+    assert!(!CapitalizationFrequency::Monthly.capitalizes_at(1, 26));
+    assert!(CapitalizationFrequency::Monthly.capitalizes_at(2, 26));
+}
@@ -0,0 +1,79 @@
+use chrono::NaiveDate;
+use crate::{AmortizationError, Asset, DepreciationMethod, PaymentFrequency};
+
+fn expenses(asset: &Asset) -> Vec<f64> {
+    asset.schedule().unwrap().rows.iter().map(|row| row.depreciation).collect()
+}
+
+#[test]
+fn test_straight_line() {
+    let schedule = Asset::new(10_000.0, 1_000.0, 5).schedule().unwrap();
+    assert!(schedule.rows.iter().all(|row| row.depreciation == 1_800.0));
+    assert_eq!(schedule.total_depreciation, 9_000.0);
+    assert_eq!(schedule.final_book_value, 1_000.0);
+    assert_eq!(schedule.book_value_after(2), Some(6_400.0));
+    assert_eq!(schedule.rows[2].accumulated_depreciation, 5_400.0);
+}
+
+#[test]
+fn test_double_declining_switches_to_straight_line() {
+    let asset = Asset::new(10_000.0, 1_000.0, 5).with_method(DepreciationMethod::double_declining());
+    assert_eq!(expenses(&asset), vec![4_000.0, 2_400.0, 1_440.0, 864.0, 296.0]);
+
+    // Without a salvage value the switch happens in year 4
+    let no_salvage = Asset::new(10_000.0, 0.0, 5).with_method(DepreciationMethod::double_declining());
+    assert_eq!(expenses(&no_salvage), vec![4_000.0, 2_400.0, 1_440.0, 1_080.0, 1_080.0]);
+
+    let pure = Asset::new(10_000.0, 0.0, 5)
+        .with_method(DepreciationMethod::DecliningBalance { factor: 2.0, switch_to_straight_line: false });
+    assert_eq!(expenses(&pure), vec![4_000.0, 2_400.0, 1_440.0, 864.0, 518.4]);
+    assert_eq!(pure.schedule().unwrap().final_book_value, 777.6);
+}
+
+#[test]
+fn test_sum_of_years_digits() {
+    let asset = Asset::new(10_000.0, 1_000.0, 5).with_method(DepreciationMethod::SumOfYearsDigits);
+    assert_eq!(expenses(&asset), vec![3_000.0, 2_400.0, 1_800.0, 1_200.0, 600.0]);
+
+    // Thirds don't divide evenly; the last year takes the rounding difference
+    let uneven = Asset::new(1_000.0, 0.0, 3).with_method(DepreciationMethod::SumOfYearsDigits);
+    assert_eq!(expenses(&uneven), vec![500.0, 333.33, 166.67]);
+}
+
+#[test]
+fn test_units_of_production() {
+    let asset = Asset::new(50_000.0, 5_000.0, 10)
+        .with_method(DepreciationMethod::units_of_production(100_000.0, vec![20_000.0, 30_000.0, 25_000.0]));
+    let schedule = asset.schedule().unwrap();
+    assert_eq!(schedule.rows.len(), 3);
+    assert_eq!(expenses(&asset), vec![9_000.0, 13_500.0, 11_250.0]);
+    assert_eq!(schedule.final_book_value, 16_250.0);
+
+    // Production beyond the estimate stops at the salvage value
+    let overrun = Asset::new(50_000.0, 5_000.0, 10)
+        .with_method(DepreciationMethod::units_of_production(100_000.0, vec![80_000.0, 40_000.0]));
+    assert_eq!(expenses(&overrun), vec![36_000.0, 9_000.0]);
+}
+
+#[test]
+fn test_monthly_amortization_of_an_intangible() {
+    let schedule = Asset::new(12_000.0, 0.0, 1)
+        .with_frequency(PaymentFrequency::Monthly)
+        .with_start_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+        .schedule()
+        .unwrap();
+    assert_eq!(schedule.rows.len(), 12);
+    assert!(schedule.rows.iter().all(|row| row.depreciation == 1_000.0));
+    assert_eq!(schedule.rows[0].date, NaiveDate::from_ymd_opt(2024, 2, 1));
+    assert_eq!(schedule.rows[11].date, NaiveDate::from_ymd_opt(2025, 1, 1));
+}
+
+#[test]
+fn test_invalid_assets() {
+    assert!(matches!(Asset::new(0.0, 0.0, 5).schedule(), Err(AmortizationError::InvalidAsset(_))));
+    assert!(matches!(Asset::new(1_000.0, 2_000.0, 5).schedule(), Err(AmortizationError::InvalidAsset(_))));
+    assert!(matches!(Asset::new(1_000.0, 0.0, 0).schedule(), Err(AmortizationError::InvalidPeriods(0))));
+    let negative_units = Asset::new(1_000.0, 0.0, 5)
+        .with_method(DepreciationMethod::units_of_production(100.0, vec![10.0, -1.0]));
+    assert!(matches!(negative_units.schedule(), Err(AmortizationError::InvalidAsset(_))));
+}
@@ -0,0 +1,50 @@
+use chrono::NaiveDate;
+use crate::{diff_schedules, Amortization, ScheduleDiff};
+
+fn loan() -> Amortization {
+    Amortization::new(200_000.0, 6.0, 360, NaiveDate::from_ymd_opt(2024, 1, 1)).unwrap()
+}
+
+#[test]
+fn test_identical_schedules_compare_equal() {
+    let (a, b) = (loan(), loan());
+
+    assert_eq!(a.schedule, b.schedule);
+    assert!(a.approx_eq(&b, 0.0));
+    assert_eq!(a.diff(&b.schedule, 0.0), None);
+}
+
+#[test]
+fn test_diff_reports_first_differing_field() {
+    let expected = loan();
+    let mut statement = expected.schedule.clone();
+    statement[11].interest += 0.004;
+    statement[11].principal -= 0.004;
+    statement[24].remaining_balance += 1.5;
+
+    assert!(expected.schedule[11].approx_eq(&statement[11], 0.005));
+    assert_eq!(
+        expected.diff(&statement, 0.01),
+        Some(ScheduleDiff::Amount {
+            installment_number: 25,
+            field: "remaining_balance",
+            expected: expected.schedule[24].remaining_balance,
+            actual: statement[24].remaining_balance,
+        })
+    );
+    let first = expected.diff(&statement, 0.001).unwrap();
+    assert!(first.to_string().starts_with("Installment 12 interest: expected 988.77"), "{}", first);
+}
+
+#[test]
+fn test_diff_reports_dates_and_length() {
+    let expected = loan();
+    let mut statement = expected.schedule.clone();
+    statement[2].date = NaiveDate::from_ymd_opt(2024, 3, 4);
+
+    assert!(matches!(expected.diff(&statement, 0.01), Some(ScheduleDiff::Date { installment_number: 3, .. })));
+    assert_eq!(
+        diff_schedules(&expected.schedule, &expected.schedule[..300], 0.01),
+        Some(ScheduleDiff::Length { expected: 360, actual: 300 })
+    );
+}
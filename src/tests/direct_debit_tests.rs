@@ -0,0 +1,116 @@
+use chrono::NaiveDate;
+use crate::direct_debit::is_valid_iban;
+use crate::{Amortization, AmortizationError, Currency, Deferment, DirectDebit, FixedWidthField, FixedWidthLayout, Party, Rate, SequenceType};
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn loan() -> Amortization {
+    Amortization::builder()
+        .principal(12_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(12)
+        .start_date(date(2025, 2, 1))
+        .currency(Currency::EUR)
+        .build()
+        .unwrap()
+}
+
+fn mandate() -> DirectDebit {
+    DirectDebit::new(
+        Party::new("Small Lender GmbH", "DE89 3704 0044 0532 0130 00").with_bic("COBADEFFXXX"),
+        "DE98ZZZ09999999999",
+        Party::new("Erika Mustermann & Co", "GB82WEST12345698765432"),
+        "LOAN-42",
+        date(2025, 1, 10),
+    )
+}
+
+#[test]
+fn test_collections_follow_the_schedule() {
+    let loan = loan();
+    let collections = loan.direct_debit_collections(&mandate()).unwrap();
+    assert_eq!(collections.len(), 12);
+    assert_eq!(collections[0].sequence, SequenceType::First);
+    assert_eq!(collections[1].sequence, SequenceType::Recurring);
+    assert_eq!(collections[11].sequence, SequenceType::Final);
+    assert_eq!(collections[0].due_date, date(2025, 2, 1));
+    assert_eq!(collections[0].end_to_end_id, "LOAN-42-1");
+    assert_eq!(collections[11].amount, loan.money(loan.final_payment_amount));
+
+    let later = loan.direct_debit_collections(&mandate().with_installments(6, 8)).unwrap();
+    let numbers: Vec<u32> = later.iter().map(|c| c.installment_number).collect();
+    assert_eq!(numbers, vec![6, 7, 8]);
+    assert!(later.iter().all(|c| c.sequence == SequenceType::Recurring));
+}
+
+#[test]
+fn test_deferred_installments_are_not_collected() {
+    let loan = Amortization::builder()
+        .principal(12_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(12)
+        .start_date(date(2025, 2, 1))
+        .deferment(Deferment::Unsubsidized { periods: 3 })
+        .build()
+        .unwrap();
+    let collections = loan.direct_debit_collections(&mandate()).unwrap();
+    assert_eq!(collections.len(), 9);
+    assert_eq!(collections[0].installment_number, 4);
+    assert_eq!(collections[0].sequence, SequenceType::First);
+}
+
+#[test]
+fn test_pain008_message() {
+    let loan = loan();
+    let xml = loan.to_pain008(&mandate().with_message_id("MSG-2025-01")).unwrap();
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<Document xmlns=\"urn:iso:std:iso:20022:tech:xsd:pain.008.001.02\">"));
+    assert!(xml.contains("<MsgId>MSG-2025-01</MsgId><CreDtTm>2025-01-10T00:00:00</CreDtTm><NbOfTxs>12</NbOfTxs>"));
+    assert!(xml.contains(&format!("<CtrlSum>{:.2}</CtrlSum>", loan.total_payment)));
+    assert_eq!(xml.matches("<PmtInf>").count(), 12);
+    assert!(xml.contains("<SeqTp>FRST</SeqTp></PmtTpInf><ReqdColltnDt>2025-02-01</ReqdColltnDt>"));
+    assert!(xml.contains(&format!("<InstdAmt Ccy=\"EUR\">{:.2}</InstdAmt>", loan.schedule[0].installment_amount)));
+    assert!(xml.contains("<IBAN>DE89370400440532013000</IBAN>"));
+    assert!(xml.contains("<BIC>COBADEFFXXX</BIC>"));
+    // The debtor has no BIC, and its name is escaped
+    assert!(xml.contains("<DbtrAgt><FinInstnId><Othr><Id>NOTPROVIDED</Id></Othr></FinInstnId></DbtrAgt><Dbtr><Nm>Erika Mustermann &amp; Co</Nm>"));
+    assert!(xml.contains("<MndtId>LOAN-42</MndtId><DtOfSgntr>2025-01-10</DtOfSgntr>"));
+    assert!(xml.ends_with("</CstmrDrctDbtInitn>\n</Document>\n"));
+}
+
+#[test]
+fn test_fixed_width_records() {
+    let loan = loan();
+    let file = loan.to_fixed_width(&mandate(), &FixedWidthLayout::default()).unwrap();
+    let lines: Vec<&str> = file.split("\r\n").filter(|line| !line.is_empty()).collect();
+    assert_eq!(lines.len(), 12);
+    assert!(lines.iter().all(|line| line.len() == 1 + 8 + 4 + 35 + 34 + 70 + 3 + 12 + 4));
+    assert!(lines[0].starts_with("D20250201FRSTLOAN-42"));
+    let cents = (loan.schedule[0].installment_amount * 100.0).round() as i64;
+    assert!(lines[0].ends_with(&format!("EUR{:012}0001", cents)));
+
+    let layout = FixedWidthLayout::new()
+        .field(FixedWidthField::DebtorName(6))
+        .field(FixedWidthField::Filler(2))
+        .field(FixedWidthField::Amount(6));
+    let file = loan.to_fixed_width(&mandate().with_installments(1, 1), &layout).unwrap();
+    assert_eq!(file, format!("Erika   {:06}\n", cents));
+    let too_narrow = FixedWidthLayout::new().field(FixedWidthField::Amount(3));
+    assert!(matches!(loan.to_fixed_width(&mandate(), &too_narrow), Err(AmortizationError::InvalidDirectDebit(_))));
+}
+
+#[test]
+fn test_invalid_direct_debits() {
+    assert!(is_valid_iban("GB82WEST12345698765432"));
+    assert!(!is_valid_iban("GB83WEST12345698765432"));
+    let loan = loan();
+    let bad_iban = DirectDebit { debtor: Party::new("Someone", "DE00 1234"), ..mandate() };
+    assert!(matches!(loan.to_pain008(&bad_iban), Err(AmortizationError::InvalidDirectDebit(_))));
+    let bad_bic = DirectDebit { debtor: Party::new("Someone", "GB82WEST12345698765432").with_bic("XX"), ..mandate() };
+    assert!(matches!(loan.to_pain008(&bad_bic), Err(AmortizationError::InvalidDirectDebit(_))));
+    assert!(loan.to_pain008(&mandate().with_installments(5, 2)).is_err());
+
+    let undated = Amortization::new(1_000.0, 5.0, 12, None).unwrap();
+    assert!(matches!(undated.to_pain008(&mandate()), Err(AmortizationError::MissingParameter("start_date"))));
+}
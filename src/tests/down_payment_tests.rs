@@ -0,0 +1,67 @@
+use crate::{AmortizationError, DownPaymentGrid, MortgageInsurance};
+use super::assert_float_eq;
+
+#[test]
+fn test_default_grid() {
+    let scenarios = DownPaymentGrid::new(500_000.0, 6.5, 360).scenarios().unwrap();
+    let percents: Vec<f64> = scenarios.iter().map(|s| s.down_payment_percent).collect();
+
+    assert_eq!(percents, vec![5.0, 10.0, 15.0, 20.0]);
+    assert_float_eq(scenarios[0].down_payment, 25_000.0);
+    assert_float_eq(scenarios[0].loan_amount, 475_000.0);
+    assert_float_eq(scenarios[0].ltv, 95.0);
+    assert_float_eq(scenarios[3].ltv, 80.0);
+}
+
+#[test]
+fn test_mortgage_insurance_below_twenty_percent() {
+    let scenarios = DownPaymentGrid::new(500_000.0, 6.5, 360).scenarios().unwrap();
+    let ten = &scenarios[1];
+    let twenty = &scenarios[3];
+
+    assert!(ten.requires_mortgage_insurance);
+    assert!(ten.mortgage_insurance_drop_off.is_some());
+    assert!(ten.total_mortgage_insurance > 0.0);
+    // 0.5% of $450,000 a year
+    assert_float_eq(ten.periodic_payment - ten.loan.periodic_payment, 187.5);
+
+    assert!(!twenty.requires_mortgage_insurance);
+    assert_eq!(twenty.mortgage_insurance_drop_off, None);
+    assert_float_eq(twenty.total_mortgage_insurance, 0.0);
+    assert_float_eq(twenty.periodic_payment, twenty.loan.periodic_payment);
+}
+
+#[test]
+fn test_larger_down_payment_costs_less() {
+    let scenarios = DownPaymentGrid::new(500_000.0, 6.5, 360).scenarios().unwrap();
+    for pair in scenarios.windows(2) {
+        assert!(pair[1].periodic_payment < pair[0].periodic_payment);
+        assert!(pair[1].total_interest < pair[0].total_interest);
+        assert!(pair[1].total_cost < pair[0].total_cost);
+    }
+    let first = &scenarios[0];
+    assert_float_eq(first.total_cost, first.down_payment + first.loan.total_payment + first.total_mortgage_insurance);
+}
+
+#[test]
+fn test_custom_down_payments_and_premium() {
+    let scenarios = DownPaymentGrid::new(300_000.0, 6.0, 360)
+        .with_down_payments(&[3.5, 25.0])
+        .with_mortgage_insurance(MortgageInsurance::new(0.85))
+        .scenarios()
+        .unwrap();
+
+    assert_eq!(scenarios.len(), 2);
+    assert_float_eq(scenarios[0].loan_amount, 289_500.0);
+    assert_float_eq(scenarios[0].periodic_payment - scenarios[0].loan.periodic_payment, 205.06);
+    assert!(!scenarios[1].requires_mortgage_insurance);
+}
+
+#[test]
+fn test_invalid_down_payment() {
+    let result = DownPaymentGrid::new(500_000.0, 6.5, 360).with_down_payments(&[10.0, 100.0]).scenarios();
+    assert!(matches!(result, Err(AmortizationError::InvalidPaymentAmount(_))));
+
+    let result = DownPaymentGrid::new(500_000.0, 6.5, 360).with_down_payments(&[]).scenarios();
+    assert!(matches!(result, Err(AmortizationError::MissingParameter("down_payments"))));
+}
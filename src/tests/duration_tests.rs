@@ -0,0 +1,60 @@
+use crate::{Amortization, AmortizationError, CalculatorConfig, PaymentFrequency};
+use super::assert_float_eq_within;
+
+// Durations are in years, far finer than the cent `assert_float_eq` allows
+const DURATION_PRECISION: f64 = 0.0001;
+
+fn annual(balance: f64, apr: f64, years: u32) -> Amortization {
+    Amortization::from_config(CalculatorConfig {
+        frequency: PaymentFrequency::Annually,
+        ..CalculatorConfig::new(balance, apr, years * 12, None)
+    }).unwrap()
+}
+
+#[test]
+fn test_weighted_average_life() {
+    let loan = Amortization::new(1_200.0, 0.0, 2, None).unwrap();
+    assert_float_eq_within(loan.weighted_average_life(), 0.125, DURATION_PRECISION);
+
+    let loan = annual(1_000.0, 0.0, 4);
+    assert_float_eq_within(loan.weighted_average_life(), 2.5, DURATION_PRECISION);
+}
+
+#[test]
+fn test_single_payment_duration() {
+    let loan = annual(1_000.0, 5.0, 1);
+
+    assert_float_eq_within(loan.macaulay_duration(5.0).unwrap(), 1.0, DURATION_PRECISION);
+    assert_float_eq_within(loan.modified_duration(5.0).unwrap(), 1.0 / 1.05, DURATION_PRECISION);
+    assert_float_eq_within(loan.convexity(5.0).unwrap(), 2.0 / 1.05_f64.powi(2), DURATION_PRECISION);
+}
+
+#[test]
+fn test_level_payment_duration() {
+    // Two equal annual installments at 10%: weights 1/1.1 and 1/1.21
+    let loan = annual(1_000.0, 10.0, 2);
+    let (first, second) = (1.0 / 1.1, 1.0 / 1.21);
+    let macaulay = (first + 2.0 * second) / (first + second);
+
+    assert_float_eq_within(loan.macaulay_duration(10.0).unwrap(), macaulay, DURATION_PRECISION);
+    assert_float_eq_within(loan.modified_duration(10.0).unwrap(), macaulay / 1.1, DURATION_PRECISION);
+    let convexity = (2.0 / 1.1_f64.powi(3) + 6.0 / 1.1_f64.powi(4)) / (first + second);
+    assert_float_eq_within(loan.convexity(10.0).unwrap(), convexity, DURATION_PRECISION);
+}
+
+#[test]
+fn test_duration_shorter_than_wal_for_mortgage() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let macaulay = loan.macaulay_duration(6.0).unwrap();
+
+    assert!(macaulay < loan.weighted_average_life());
+    assert!(loan.modified_duration(6.0).unwrap() < macaulay);
+    assert!(macaulay > loan.macaulay_duration(8.0).unwrap());
+    assert!(loan.convexity(6.0).unwrap() > 0.0);
+}
+
+#[test]
+fn test_invalid_discount_rate() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    assert!(matches!(loan.macaulay_duration(-1_200.0), Err(AmortizationError::InvalidInterestRate(_))));
+}
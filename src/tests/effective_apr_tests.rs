@@ -0,0 +1,70 @@
+use crate::{Amortization, AmortizationError, FeeTreatment, UpfrontCosts};
+
+#[test]
+fn test_effective_apr_without_costs_matches_note_rate() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let result = loan.effective_apr(&UpfrontCosts::default()).unwrap();
+
+    assert!((result.apr - 6.0).abs() < 0.001, "got {}", result.apr);
+    assert_eq!(result.note_rate, 6.0);
+    assert_eq!(result.amount_financed, 200_000.0);
+    assert!((result.finance_charge - loan.total_interest).abs() < 1e-9);
+}
+
+#[test]
+fn test_effective_apr_with_fees_and_points() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let costs = UpfrontCosts {
+        origination_fees: 1_500.0,
+        points: 1.0,
+        closing_costs: 2_500.0,
+    };
+    let result = loan.effective_apr(&costs).unwrap();
+
+    assert_eq!(costs.total(200_000.0), 6_000.0);
+    assert_eq!(result.amount_financed, 194_000.0);
+    // Roughly 3% of upfront cost on a 30-year loan adds about a quarter point
+    assert!(result.apr > 6.25 && result.apr < 6.35, "got {}", result.apr);
+    assert!((result.finance_charge - (loan.total_interest + 6_000.0)).abs() < 1e-9);
+}
+
+#[test]
+fn test_effective_apr_shorter_term_feels_fees_more() {
+    let costs = UpfrontCosts { origination_fees: 2_000.0, ..Default::default() };
+    let long = Amortization::new(100_000.0, 5.0, 360, None).unwrap().effective_apr(&costs).unwrap();
+    let short = Amortization::new(100_000.0, 5.0, 60, None).unwrap().effective_apr(&costs).unwrap();
+
+    assert!(short.apr > long.apr);
+}
+
+#[test]
+fn test_effective_apr_rejects_invalid_costs() {
+    let loan = Amortization::new(10_000.0, 5.0, 12, None).unwrap();
+
+    let negative = UpfrontCosts { closing_costs: -1.0, ..Default::default() };
+    assert!(matches!(loan.effective_apr(&negative), Err(AmortizationError::InvalidUpfrontCost(_))));
+
+    let excessive = UpfrontCosts { origination_fees: 10_000.0, ..Default::default() };
+    assert!(matches!(loan.effective_apr(&excessive), Err(AmortizationError::NoSolution(_))));
+}
+
+#[test]
+fn test_effective_apr_with_financed_fees() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let costs = UpfrontCosts { origination_fees: 1_500.0, points: 1.0, closing_costs: 2_500.0 };
+    let financed_loan = loan.with_financed_costs(&costs).unwrap();
+    let financed = loan.effective_apr_with(&costs, FeeTreatment::Financed).unwrap();
+    let upfront = loan.effective_apr_with(&costs, FeeTreatment::PaidUpfront).unwrap();
+
+    assert_eq!(financed_loan.balance, 206_000.0);
+    assert!(financed_loan.periodic_payment > loan.periodic_payment);
+    assert_eq!(financed.treatment, FeeTreatment::Financed);
+    assert_eq!(financed.loan_amount, 206_000.0);
+    assert_eq!(financed.amount_financed, 200_000.0);
+    assert!((financed.finance_charge - (financed_loan.total_interest + 6_000.0)).abs() < 1e-6);
+    assert!((financed.total_of_payments - (financed.amount_financed + financed.finance_charge)).abs() < 0.01);
+    // The same costs are a smaller share of the larger financed loan
+    assert!(financed.apr > 6.25 && financed.apr < upfront.apr, "got {}", financed.apr);
+    assert_eq!(upfront.loan_amount, 200_000.0);
+    assert_eq!(upfront.amount_financed, 194_000.0);
+}
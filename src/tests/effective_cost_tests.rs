@@ -0,0 +1,44 @@
+use crate::{Amortization, AmortizationBuilder, Fee, Rate};
+use super::assert_float_eq;
+
+fn builder() -> AmortizationBuilder {
+    Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+}
+
+#[test]
+fn test_effective_cost_of_plain_loan() {
+    let loan = builder().build().unwrap();
+    let cost = loan.effective_cost().unwrap();
+
+    assert_eq!(cost.total_cost_of_credit, loan.total_interest);
+    assert_float_eq(cost.interest_percent_of_principal, loan.total_interest / 2_000.0);
+    assert_float_eq(cost.cost_per_thousand, loan.total_interest / 200.0);
+    assert_eq!(cost.nominal_rate, 6.0);
+    // 0.5% a month compounds to 6.17% a year
+    assert_float_eq(cost.effective_rate, 6.17);
+}
+
+#[test]
+fn test_fees_raise_the_effective_rate() {
+    let plain = builder().build().unwrap().effective_cost().unwrap();
+    let loan = builder().fee(Fee::recurring("servicing", 25.0)).build().unwrap();
+    let cost = loan.effective_cost().unwrap();
+
+    assert_float_eq(cost.total_fees, 9_000.0);
+    assert_float_eq(cost.total_cost_of_credit, plain.total_cost_of_credit + 9_000.0);
+    assert_eq!(cost.total_interest, plain.total_interest);
+    assert!(cost.effective_rate > plain.effective_rate + 0.1);
+}
+
+#[test]
+fn test_extra_payments_cut_the_cost_not_the_rate() {
+    let plain = builder().build().unwrap().effective_cost().unwrap();
+    let cost = builder().extra_payment(200.0).build().unwrap().effective_cost().unwrap();
+
+    assert!(cost.total_cost_of_credit < plain.total_cost_of_credit * 0.7);
+    assert!(cost.cost_per_thousand < plain.cost_per_thousand);
+    assert_float_eq(cost.effective_rate, plain.effective_rate);
+}
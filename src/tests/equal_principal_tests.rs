@@ -0,0 +1,52 @@
+use chrono::NaiveDate;
+use crate::{Amortization, Decimal, LoanType, Rate, RatePeriod};
+use super::{assert_float_eq, FLOAT_PRECISION};
+
+fn equal_principal_loan() -> Amortization {
+    Amortization::builder()
+        .principal(120_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(120)
+        .loan_type(LoanType::EqualPrincipal)
+        .start_date(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_equal_principal_declining_payments() {
+    let loan = equal_principal_loan();
+
+    assert!(loan.schedule.iter().all(|p| (p.principal - 1_000.0).abs() < FLOAT_PRECISION));
+    assert_float_eq(loan.periodic_payment, 1_600.0);
+    assert_float_eq(loan.schedule[0].installment_amount, 1_600.0);
+    assert_float_eq(loan.schedule[1].installment_amount, 1_595.0);
+    assert_float_eq(loan.final_payment_amount, 1_005.0);
+    assert!(loan.schedule.windows(2).all(|w| w[1].installment_amount < w[0].installment_amount));
+}
+
+#[test]
+fn test_equal_principal_costs_less_interest() {
+    let loan = equal_principal_loan();
+    let level = Amortization::new(120_000.0, 6.0, 120, None).unwrap();
+
+    // Interest on an arithmetic series of balances: rate * 1000 * (120 + 119 + ... + 1)
+    assert_float_eq(loan.total_interest, 36_300.0);
+    assert!(loan.total_interest < level.total_interest);
+    assert_float_eq(loan.schedule.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_equal_principal_with_rate_reset_and_decimal() {
+    let loan = Amortization::builder()
+        .principal(12_000.0)
+        .rate_schedule(vec![RatePeriod::new(1, 4.0), RatePeriod::new(7, 8.0)])
+        .term_months(12)
+        .loan_type(LoanType::EqualPrincipal)
+        .build_with::<Decimal>()
+        .unwrap();
+
+    assert!(loan.schedule.iter().all(|p| (p.principal - 1_000.0).abs() < FLOAT_PRECISION));
+    assert_float_eq(loan.schedule[6].interest, 40.0);
+    assert_float_eq(loan.schedule[6].installment_amount, 1_040.0);
+}
@@ -0,0 +1,73 @@
+use crate::{Amortization, AmortizationError, Escrow, MortgageInsurance, Rate};
+use super::assert_float_eq;
+
+fn escrowed_loan(escrow: Escrow) -> Amortization {
+    Amortization::builder()
+        .principal(225_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .escrow(escrow)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_escrow_components_in_each_payment() {
+    let loan = escrowed_loan(Escrow::new(250_000.0, 3_000.0, 1_200.0));
+    let escrow = loan.schedule[0].escrow.unwrap();
+
+    assert_float_eq(escrow.property_tax, 250.0);
+    assert_float_eq(escrow.insurance, 100.0);
+    assert_float_eq(escrow.mortgage_insurance, 0.0);
+    assert_float_eq(loan.schedule[0].piti(), loan.periodic_payment + 350.0);
+    assert_float_eq(loan.periodic_piti(), loan.periodic_payment + 350.0);
+}
+
+#[test]
+fn test_mortgage_insurance_drops_off_at_78_percent_ltv() {
+    let loan = escrowed_loan(Escrow::new(250_000.0, 3_000.0, 1_200.0).with_mortgage_insurance(MortgageInsurance::new(0.5)));
+    let drop_off = loan.mortgage_insurance_drop_off().unwrap() as usize;
+
+    assert_float_eq(loan.schedule[0].escrow.unwrap().mortgage_insurance, 93.75);
+    assert!(loan.schedule[drop_off - 1].beginning_balance <= 195_000.0);
+    assert!(loan.schedule[drop_off - 2].beginning_balance > 195_000.0);
+    assert_float_eq(loan.total_mortgage_insurance(), 93.75 * (drop_off - 1) as f64);
+}
+
+#[test]
+fn test_custom_drop_off_threshold() {
+    let mi = MortgageInsurance::new(0.5).drop_off_at(80.0);
+    let loan = escrowed_loan(Escrow::new(250_000.0, 0.0, 0.0).with_mortgage_insurance(mi));
+    let default = escrowed_loan(Escrow::new(250_000.0, 0.0, 0.0).with_mortgage_insurance(MortgageInsurance::new(0.5)));
+
+    assert!(loan.mortgage_insurance_drop_off() < default.mortgage_insurance_drop_off());
+}
+
+#[test]
+fn test_totals_report_piti() {
+    let loan = escrowed_loan(Escrow::new(250_000.0, 3_000.0, 1_200.0));
+
+    assert_float_eq(loan.total_escrow(), 350.0 * 360.0);
+    assert_float_eq(loan.total_piti(), loan.total_payment + loan.total_escrow());
+}
+
+#[test]
+fn test_loan_without_escrow() {
+    let loan = Amortization::new(225_000.0, 6.0, 360, None).unwrap();
+
+    assert!(loan.schedule[0].escrow.is_none());
+    assert_float_eq(loan.total_piti(), loan.total_payment);
+    assert_eq!(loan.mortgage_insurance_drop_off(), None);
+}
+
+#[test]
+fn test_invalid_escrow() {
+    let result = Amortization::builder()
+        .principal(225_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .escrow(Escrow::new(250_000.0, -1.0, 0.0))
+        .build();
+
+    assert!(matches!(result, Err(AmortizationError::InvalidEscrow(_))));
+}
@@ -0,0 +1,56 @@
+use chrono::NaiveDate;
+use crate::json::JsonValue;
+use crate::{Amortization, ExtraPayment, LumpSum, RateChange};
+
+fn loan() -> Amortization {
+    Amortization::new(200_000.0, 6.0, 360, NaiveDate::from_ymd_opt(2025, 1, 1)).unwrap()
+}
+
+#[test]
+fn test_events_record_each_transform() {
+    let loan = loan();
+    assert!(loan.events.is_empty());
+
+    let scenario = loan
+        .apply(RateChange::new(61, 7.0)).unwrap()
+        .apply(LumpSum::new(120, 10_000.0)).unwrap();
+    assert_eq!(scenario.events.len(), 2);
+
+    let rate_change = &scenario.events[0];
+    assert_eq!(rate_change.sequence, 1);
+    assert_eq!(rate_change.transform, "rate_change");
+    assert_eq!(rate_change.effective_period, Some(61));
+    assert_eq!(rate_change.effective_date, loan.due_date(61));
+    assert_eq!(rate_change.changes.len(), 1);
+    assert_eq!(rate_change.changes[0].field, "rate_schedule");
+    assert_eq!(rate_change.changes[0].before, "[]");
+    assert!(rate_change.changes[0].after.contains("start_period: 61"));
+    assert_eq!(rate_change.previous_total_interest, loan.total_interest);
+    assert!(rate_change.total_interest > loan.total_interest);
+
+    let lump_sum = &scenario.events[1];
+    assert_eq!(lump_sum.sequence, 2);
+    assert_eq!(lump_sum.changes[0].field, "prepayments");
+    assert_eq!(lump_sum.previous_periods, 360);
+    assert!(lump_sum.periods < 360);
+    assert_eq!(scenario.events_changing("prepayments").len(), 1);
+    assert!(scenario.events_changing("apr").is_empty());
+}
+
+#[test]
+fn test_event_log_json() {
+    let loan = loan();
+    assert!(loan.to_json_value().get("events").is_none());
+
+    let scenario = loan.apply(ExtraPayment::new(100.0)).unwrap();
+    let log = JsonValue::parse(&scenario.event_log_json()).unwrap();
+    let JsonValue::Array(events) = log else { panic!("event log is an array") };
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].get("transform"), Some(&JsonValue::String("extra_payment".to_string())));
+    assert_eq!(events[0].get("effective_period"), Some(&JsonValue::Null));
+
+    let JsonValue::Array(changes) = events[0].get("changes").unwrap() else { panic!("changes is an array") };
+    assert_eq!(changes[0].get("before"), Some(&JsonValue::String("0.0".to_string())));
+    assert_eq!(changes[0].get("after"), Some(&JsonValue::String("100.0".to_string())));
+    assert!(scenario.to_json_value().get("events").is_some());
+}
@@ -0,0 +1,61 @@
+use chrono::NaiveDate;
+use crate::{Amortization, LoanType, Rate};
+use super::assert_float_eq;
+
+fn loan() -> Amortization {
+    Amortization::new(200_000.0, 6.0, 360, NaiveDate::from_ymd_opt(2025, 1, 1)).unwrap()
+}
+
+#[test]
+fn test_milestones() {
+    let loan = loan();
+    let explanation = loan.explain();
+
+    assert_float_eq(explanation.first_payment_interest, 1_000.0);
+    assert_float_eq(explanation.first_payment_interest_share, 1_000.0 / loan.periodic_payment * 100.0);
+    let first_year: f64 = loan.schedule[..12].iter().map(|p| p.interest).sum();
+    assert_float_eq(explanation.first_year_interest, first_year);
+
+    let crossover = explanation.crossover_period.unwrap() as usize;
+    assert!(loan.schedule[crossover - 1].principal > loan.schedule[crossover - 1].interest);
+    assert!(loan.schedule[crossover - 2].principal <= loan.schedule[crossover - 2].interest);
+    assert_eq!(explanation.crossover_date, loan.schedule[crossover - 1].date);
+    let halfway = explanation.halfway_period.unwrap();
+    assert!(halfway as usize > crossover);
+    assert!(loan.balance_after(halfway).unwrap() <= 100_000.0);
+
+    let extra = explanation.extra_payment.as_ref().unwrap();
+    assert_float_eq(extra.amount, loan.periodic_payment);
+    assert!(extra.interest_saved > 0.0);
+    assert!(extra.periods_saved >= 1);
+}
+
+#[test]
+fn test_narrative() {
+    let explanation = loan().explain();
+    let text = explanation.to_text();
+
+    assert_eq!(explanation.paragraphs.len(), 6);
+    assert!(text.starts_with("The installment of 1199.10 USD is the level amount that repays 200000.00 USD over 360 monthly installments at 6.00% a year (0.5000% per installment)"));
+    assert!(text.contains("Of the first installment, 1000.00 USD (83%) is interest"));
+    assert!(text.contains(&format!("From installment {} (", explanation.crossover_period.unwrap())));
+    assert!(text.contains("Over 30 years the loan costs"));
+    assert!(text.contains("Paying one extra installment of 1199.10 USD along with the first saves"));
+    assert_eq!(explanation.to_string(), text);
+}
+
+#[test]
+fn test_interest_only_and_undated() {
+    let loan = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(120)
+        .loan_type(LoanType::InterestOnly { io_periods: 24 })
+        .build()
+        .unwrap();
+    let explanation = loan.explain();
+
+    assert!(explanation.paragraphs[0].starts_with("For the first 24 installments only the interest, 500.00 USD, is due."));
+    assert_eq!(explanation.crossover_date, None);
+    assert!(explanation.paragraphs[2].starts_with(&format!("From installment {} on", explanation.crossover_period.unwrap())));
+}
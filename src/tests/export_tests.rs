@@ -0,0 +1,130 @@
+use crate::Amortization;
+use crate::export::JSON_SCHEMA_VERSION;
+use crate::json::JsonValue;
+use chrono::NaiveDate;
+
+#[test]
+fn test_csv_header_and_row_count() {
+    let loan = Amortization::new(10_000.0, 5.0, 12, None).unwrap();
+    let csv = loan.schedule_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines[0], "Installment,Date,Beginning Balance,Interest,Principal,Remaining Balance");
+    assert_eq!(lines.len(), loan.schedule.len() + 1);
+}
+
+#[test]
+fn test_csv_rows_with_dates() {
+    let loan = Amortization::new(
+        10_000.0,
+        5.0,
+        12,
+        Some(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+    ).unwrap();
+    let csv = loan.schedule_csv();
+    let first_row: Vec<&str> = csv.lines().nth(1).unwrap().split(',').collect();
+
+    assert_eq!(first_row[0], "1");
+    assert_eq!(first_row[1], "2024-01-01");
+    assert_eq!(first_row[2], "10000.00");
+    assert_eq!(first_row[3], format!("{:.2}", loan.schedule[0].interest));
+}
+
+#[test]
+fn test_csv_without_dates_leaves_column_empty() {
+    let loan = Amortization::new(10_000.0, 5.0, 12, None).unwrap();
+    let csv = loan.schedule_csv();
+    let first_row: Vec<&str> = csv.lines().nth(1).unwrap().split(',').collect();
+
+    assert_eq!(first_row.len(), 6);
+    assert_eq!(first_row[1], "");
+}
+
+#[test]
+fn test_to_csv_writer_matches_string() {
+    let loan = Amortization::new(10_000.0, 5.0, 12, None).unwrap();
+    let mut buffer = Vec::new();
+    loan.to_csv(&mut buffer).unwrap();
+
+    assert_eq!(String::from_utf8(buffer).unwrap(), loan.schedule_csv());
+}
+
+#[test]
+fn test_json_schema_is_stable() {
+    let loan = Amortization::new(
+        1_000.0,
+        12.0,
+        2,
+        Some(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+    ).unwrap();
+
+    let expected = concat!(
+        r#"{"schema_version":1,"#,
+        r#""summary":{"principal":1000,"periodic_rate":0.01,"periods":2,"periodic_payment":507.51,"#,
+        r#""final_payment":507.51,"total_payment":1015.02,"total_interest":15.02,"#,
+        r#""start_date":"2024-01-01","end_date":"2024-03-01"},"#,
+        r#""schedule":["#,
+        r#"{"installment_number":1,"date":"2024-01-01","beginning_balance":1000,"installment_amount":507.51,"#,
+        r#""interest":10,"principal":497.51,"balloon_amount":0,"remaining_balance":502.49},"#,
+        r#"{"installment_number":2,"date":"2024-02-01","beginning_balance":502.49,"installment_amount":507.51,"#,
+        r#""interest":5.02,"principal":502.49,"balloon_amount":0,"remaining_balance":0}"#,
+        r#"]}"#
+    );
+    assert_eq!(loan.to_json(), expected);
+}
+
+#[test]
+fn test_json_value_structure() {
+    let loan = Amortization::new(10_000.0, 5.0, 12, None).unwrap();
+    let value = loan.to_json_value();
+
+    assert_eq!(value.keys(), vec!["schema_version", "summary", "schedule"]);
+    assert_eq!(value.get("schema_version"), Some(&JsonValue::Number(JSON_SCHEMA_VERSION as f64)));
+    assert_eq!(value.get("summary").unwrap().get("start_date"), Some(&JsonValue::Null));
+    match value.get("schedule") {
+        Some(JsonValue::Array(rows)) => {
+            assert_eq!(rows.len(), 12);
+            assert_eq!(
+                rows[0].keys(),
+                vec!["installment_number", "date", "beginning_balance", "installment_amount",
+                     "interest", "principal", "balloon_amount", "remaining_balance"]
+            );
+        }
+        other => panic!("schedule should be an array, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_json_string_escaping() {
+    let value = JsonValue::object(vec![("note", JsonValue::from("say \"hi\"\n"))]);
+    assert_eq!(value.to_string(), r#"{"note":"say \"hi\"\n"}"#);
+    assert_eq!(JsonValue::Number(f64::NAN).to_string(), "null");
+}
+
+#[test]
+fn test_json_parse_round_trips_export() {
+    let value = JsonValue::object(vec![
+        ("name", JsonValue::from("café \"north\"\n")),
+        ("rows", JsonValue::Array(vec![JsonValue::Number(-1.5e3), JsonValue::Bool(true), JsonValue::Null])),
+        ("empty", JsonValue::object(Vec::<(String, JsonValue)>::new())),
+    ]);
+    assert_eq!(JsonValue::parse(&value.to_string()).unwrap(), value);
+    assert_eq!(JsonValue::parse(r#" "\u00e9\ud83d\ude00" "#).unwrap(), JsonValue::from("é😀"));
+}
+
+#[test]
+fn test_json_parse_errors() {
+    assert!(JsonValue::parse("").is_err());
+    assert!(JsonValue::parse("{\"a\": 1,}").is_err());
+    assert!(JsonValue::parse("[1, 2] 3").unwrap_err().starts_with("trailing characters"));
+    assert!(JsonValue::parse("\"open").is_err());
+}
+
+#[test]
+fn test_json_parse_depth_limit() {
+    assert!(JsonValue::parse(&"[".repeat(500_000)).unwrap_err().starts_with("nesting too deep"));
+    assert!(JsonValue::parse(&"{\"a\":".repeat(500_000)).unwrap_err().starts_with("nesting too deep"));
+    let nested = format!("{}{}", "[".repeat(128), "]".repeat(128));
+    assert!(JsonValue::parse(&nested).is_ok());
+    assert!(JsonValue::parse(&format!("[{}]", nested)).is_err());
+}
@@ -0,0 +1,125 @@
+use crate::json::JsonValue;
+use crate::{Amortization, AmortizationError, Deferment, Fee, ModificationTreatment, Rate};
+use super::assert_float_eq;
+
+fn with_fees() -> Amortization {
+    Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .fee(Fee::recurring("servicing", 25.0))
+        .fee(Fee::recurring("insurance", 12.5))
+        .fee(Fee::one_off("modification", 12, 150.0))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_fees_are_added_to_installments() {
+    let plain = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let loan = with_fees();
+    assert_eq!(loan.periodic_payment, plain.periodic_payment);
+    assert_float_eq(loan.schedule[0].fees, 37.5);
+    assert_float_eq(loan.schedule[0].installment_amount, plain.schedule[0].installment_amount + 37.5);
+    assert_float_eq(loan.schedule[11].fees, 187.5);
+    // Interest and principal are untouched
+    for (with, without) in loan.schedule.iter().zip(&plain.schedule) {
+        assert_eq!(with.interest, without.interest);
+        assert_eq!(with.principal, without.principal);
+    }
+    assert!(loan.validate().is_valid());
+}
+
+#[test]
+fn test_fees_are_excluded_from_interest() {
+    let plain = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let loan = with_fees();
+    assert_float_eq(loan.total_fees(), 37.5 * 360.0 + 150.0);
+    assert_float_eq(loan.total_interest, plain.total_interest);
+    assert_float_eq(loan.total_payment, plain.total_payment + loan.total_fees());
+    assert_float_eq(loan.calculate_total_interest(), plain.total_interest);
+
+    let summary = loan.summary();
+    assert_eq!(Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .fee(Fee::recurring("servicing", 25.0))
+        .fee(Fee::recurring("insurance", 12.5))
+        .fee(Fee::one_off("modification", 12, 150.0))
+        .summary()
+        .unwrap(), summary);
+    assert_float_eq(summary.total_fees, loan.total_fees());
+}
+
+#[test]
+fn test_fee_charges_are_itemized() {
+    let loan = with_fees();
+    let charges = loan.fee_charges();
+    assert_eq!(charges.len(), 2 * 360 + 1);
+    let modification: Vec<_> = charges.iter().filter(|charge| charge.name == "modification").collect();
+    assert_eq!(modification.len(), 1);
+    assert_eq!(modification[0].installment_number, 12);
+    assert_float_eq(charges.iter().map(|charge| charge.amount).sum(), loan.total_fees());
+}
+
+#[test]
+fn test_deferred_installments_collect_no_recurring_fee() {
+    let loan = Amortization::builder()
+        .principal(20_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(120)
+        .deferment(Deferment::Unsubsidized { periods: 6 })
+        .fee(Fee::recurring("servicing", 10.0))
+        .fee(Fee::one_off("origination", 1, 200.0))
+        .build()
+        .unwrap();
+    assert_float_eq(loan.schedule[0].fees, 200.0);
+    assert!(loan.schedule[1..6].iter().all(|p| p.fees == 0.0));
+    assert_float_eq(loan.schedule[6].fees, 10.0);
+    // The repayment installment is still quoted without fees
+    assert_float_eq(loan.periodic_payment, loan.schedule[6].installment_amount - 10.0);
+    assert!(loan.validate().is_valid());
+}
+
+#[test]
+fn test_fees_in_exports() {
+    let loan = with_fees();
+    let csv = loan.schedule_csv();
+    let mut lines = csv.lines();
+    assert!(lines.next().unwrap().ends_with(",Fees"));
+    assert!(lines.next().unwrap().ends_with(",37.50"));
+
+    let json = loan.to_json_value();
+    let total_fees = json.get("summary").and_then(|summary| summary.get("total_fees"));
+    assert_eq!(total_fees, Some(&JsonValue::money(loan.total_fees(), loan.config.currency)));
+    let Some(JsonValue::Array(rows)) = json.get("schedule") else { panic!("no schedule") };
+    assert_eq!(rows[0].get("fees"), Some(&JsonValue::Number(37.5)));
+    let Some(JsonValue::Array(charges)) = json.get("fees") else { panic!("no fees") };
+    assert_eq!(charges.len(), 721);
+    assert_eq!(charges[0].get("name"), Some(&JsonValue::from("servicing")));
+
+    // Loans without fees export as before
+    let plain = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    assert!(!plain.schedule_csv().lines().next().unwrap().contains("Fees"));
+    assert!(plain.to_json_value().get("fees").is_none());
+}
+
+#[test]
+fn test_modification_keeps_recurring_fees() {
+    let loan = with_fees();
+    let modified = loan.modify_rate(25, 4.0, ModificationTreatment::KeepPayment).unwrap();
+    let next = &modified.schedule[24];
+    assert_float_eq(next.fees, 37.5);
+    assert_float_eq(next.installment_amount, loan.schedule[23].installment_amount);
+}
+
+#[test]
+fn test_invalid_fees() {
+    let build = |fee| Amortization::builder().principal(10_000.0).rate(Rate::percent(5.0)).term_months(12).fee(fee).build();
+    assert!(matches!(build(Fee::recurring("servicing", -1.0)), Err(AmortizationError::InvalidFee(_))));
+    assert!(matches!(build(Fee::recurring("servicing", f64::NAN)), Err(AmortizationError::InvalidFee(_))));
+    assert!(matches!(build(Fee::one_off("late", 13, 25.0)), Err(AmortizationError::PeriodOutOfRange(13))));
+    assert!(matches!(build(Fee::one_off("late", 0, 25.0)), Err(AmortizationError::PeriodOutOfRange(0))));
+    assert!(build(Fee::one_off("late", 12, 25.0)).is_ok());
+}
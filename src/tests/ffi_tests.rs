@@ -0,0 +1,48 @@
+use crate::ffi::{
+    amortize_free, amortize_new, amortize_new_dated, amortize_payment_at, amortize_periodic_payment,
+    amortize_schedule_len, amortize_total_interest, AmortizePayment,
+};
+
+#[test]
+fn test_schedule_through_the_c_interface() {
+    let loan = amortize_new(200_000.0, 6.0, 360).unwrap();
+    assert_eq!(amortize_schedule_len(Some(&loan)), 360);
+    assert!((amortize_periodic_payment(Some(&loan)) - 1199.10).abs() < 0.01);
+    assert!(amortize_total_interest(Some(&loan)) > 0.0);
+
+    let mut payment = AmortizePayment::default();
+    assert!(amortize_payment_at(Some(&loan), 0, Some(&mut payment)));
+    assert_eq!(payment.installment_number, 1);
+    assert!((payment.interest - 1000.0).abs() < 0.01);
+    assert_eq!((payment.year, payment.month, payment.day), (0, 0, 0));
+
+    assert!(amortize_payment_at(Some(&loan), 359, Some(&mut payment)));
+    assert!(payment.remaining_balance.abs() < 0.01);
+    amortize_free(Some(loan));
+}
+
+#[test]
+fn test_dated_payments() {
+    let loan = amortize_new_dated(10_000.0, 5.0, 12, 2024, 1, 31).unwrap();
+    let mut payment = AmortizePayment::default();
+    assert!(amortize_payment_at(Some(&loan), 1, Some(&mut payment)));
+    assert_eq!((payment.year, payment.month, payment.day), (2024, 2, 29));
+    amortize_free(Some(loan));
+
+    assert!(amortize_new_dated(10_000.0, 5.0, 12, 2024, 2, 30).is_none());
+}
+
+#[test]
+fn test_null_and_invalid_arguments() {
+    assert!(amortize_new(-1.0, 5.0, 12).is_none());
+    assert_eq!(amortize_schedule_len(None), 0);
+
+    let loan = amortize_new(1_000.0, 5.0, 12).unwrap();
+    let mut payment = AmortizePayment::default();
+    assert!(!amortize_payment_at(Some(&loan), 12, Some(&mut payment)));
+    assert!(!amortize_payment_at(Some(&loan), 0, None));
+    assert!(!amortize_payment_at(None, 0, Some(&mut payment)));
+    assert_eq!(payment, AmortizePayment::default());
+    amortize_free(Some(loan));
+    amortize_free(None);
+}
@@ -0,0 +1,79 @@
+use crate::{Amortization, AmortizationBuilder, AmortizationError, FixedPayment, LoanType, Rate, RatePeriod};
+use super::assert_float_eq;
+
+fn paying(amount: f64) -> AmortizationBuilder {
+    Amortization::builder()
+        .principal(50_000.0)
+        .rate(Rate::percent(6.0))
+        .fixed_payment(FixedPayment::new(amount))
+}
+
+#[test]
+fn test_term_follows_from_the_payment() {
+    let loan = paying(1_000.0).build().unwrap();
+    let periods = (-(1.0 - 50_000.0 * 0.005 / 1_000.0_f64).ln() / 1.005_f64.ln()).ceil() as usize;
+
+    assert_eq!(loan.schedule.len(), periods);
+    assert_eq!(loan.periodic_payment, 1_000.0);
+    assert!(loan.schedule[..periods - 1].iter().all(|p| p.installment_amount == 1_000.0));
+    assert!(loan.final_payment_amount > 0.0 && loan.final_payment_amount < 1_000.0);
+    assert_eq!(loan.schedule.last().unwrap().remaining_balance, 0.0);
+    assert_float_eq(loan.total_payment, 1_000.0 * (periods - 1) as f64 + loan.final_payment_amount);
+}
+
+#[test]
+fn test_longer_term_ends_when_the_balance_is_repaid() {
+    let loan = paying(1_000.0).term_months(120).build().unwrap();
+    assert_eq!(loan.schedule.len(), paying(1_000.0).build().unwrap().schedule.len());
+}
+
+#[test]
+fn test_shorter_term_settles_the_rest_in_the_last_installment() {
+    let loan = paying(1_000.0).term_months(36).build().unwrap();
+    assert_eq!(loan.schedule.len(), 36);
+    assert!(loan.final_payment_amount > 1_000.0);
+    assert_eq!(loan.schedule.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_payment_is_kept_through_rate_resets() {
+    let loan = Amortization::builder()
+        .principal(50_000.0)
+        .rate_schedule(vec![RatePeriod::new(1, 5.0), RatePeriod::new(13, 8.0)])
+        .fixed_payment(FixedPayment::new(1_000.0))
+        .build()
+        .unwrap();
+    let (last, level) = loan.schedule.split_last().unwrap();
+    assert!(level.iter().all(|p| p.installment_amount == 1_000.0));
+    assert!(last.installment_amount <= 1_000.0);
+    assert_eq!(last.remaining_balance, 0.0);
+}
+
+#[test]
+fn test_payment_below_interest() {
+    // 6% on 50,000 is 250 a month
+    assert!(matches!(paying(200.0).build(), Err(AmortizationError::InsufficientPayment(_))));
+    assert!(matches!(paying(200.0).term_months(120).build(), Err(AmortizationError::InsufficientPayment(_))));
+
+    let loan = Amortization::builder()
+        .principal(50_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(120)
+        .fixed_payment(FixedPayment::new(200.0).with_negative_amortization())
+        .build()
+        .unwrap();
+    assert!(loan.is_negatively_amortizing());
+    assert!(loan.peak_balance() > 50_000.0);
+    assert_eq!(loan.schedule.len(), 120);
+    assert!(loan.final_payment_amount > 50_000.0);
+}
+
+#[test]
+fn test_invalid_fixed_payments() {
+    assert!(matches!(paying(0.0).build(), Err(AmortizationError::InvalidPaymentAmount(_))));
+    assert!(matches!(paying(-5.0).term_months(60).build(), Err(AmortizationError::InvalidPaymentAmount(_))));
+    assert!(matches!(
+        paying(1_000.0).term_months(60).loan_type(LoanType::EqualPrincipal).build(),
+        Err(AmortizationError::ConflictingOptions("fixed_payment", "loan_type"))
+    ));
+}
@@ -0,0 +1,47 @@
+use crate::{Amortization, AmortizationError, CalculatorConfig, FixedSchedule, Payment};
+use super::assert_float_eq;
+
+#[test]
+fn test_fixed_schedule_matches_allocated() {
+    let config = CalculatorConfig::new(5_000.0, 9.0, 24, None);
+    let schedule = FixedSchedule::<36>::new(config.clone()).unwrap();
+    let loan = Amortization::from_config(config).unwrap();
+
+    assert_eq!(schedule.len(), 24);
+    assert_eq!(schedule.capacity(), 36);
+    assert!(!schedule.is_empty());
+    for (fixed, allocated) in schedule.iter().zip(&loan.schedule) {
+        assert_eq!(fixed.installment_number, allocated.installment_number);
+        assert_float_eq(fixed.installment_amount, allocated.installment_amount);
+        assert_float_eq(fixed.remaining_balance, allocated.remaining_balance);
+    }
+    assert_float_eq(schedule.last().unwrap().remaining_balance, 0.0);
+    assert!(schedule.get(24).is_none());
+    assert_float_eq(schedule.total_payment, loan.total_payment);
+    assert_float_eq(schedule.total_interest, loan.total_interest);
+}
+
+#[test]
+fn test_fixed_schedule_too_small() {
+    let result = FixedSchedule::<12>::new(CalculatorConfig::new(5_000.0, 9.0, 24, None));
+    assert!(matches!(result, Err(AmortizationError::InsufficientCapacity(12))));
+}
+
+#[test]
+fn test_schedule_into_clears_leftover_slots() {
+    let mut buffer: Vec<Option<Payment>> = vec![None; 60];
+    let written = Amortization::schedule_into(CalculatorConfig::new(5_000.0, 9.0, 48, None), &mut buffer).unwrap();
+    assert_eq!(written, 48);
+
+    let written = Amortization::schedule_into(CalculatorConfig::new(5_000.0, 9.0, 12, None), &mut buffer).unwrap();
+    assert_eq!(written, 12);
+    assert!(buffer[..12].iter().all(Option::is_some));
+    assert!(buffer[12..].iter().all(Option::is_none));
+}
+
+#[test]
+fn test_schedule_into_validates_config() {
+    let mut buffer = [const { None }; 12];
+    let result = Amortization::schedule_into(CalculatorConfig::new(-5.0, 9.0, 12, None), &mut buffer);
+    assert!(matches!(result, Err(AmortizationError::InvalidLoanAmount(_))));
+}
@@ -0,0 +1,106 @@
+use crate::{Amortization, AmortizationError, FloatingRate, ForwardCurve, Heloc, RatePeriod, ScenarioAnalysis};
+use super::assert_float_eq;
+
+// SOFR + 2.75% on a 5/1 ARM with 2/2/5 caps
+fn five_one_arm(index: ForwardCurve) -> FloatingRate {
+    FloatingRate::new(index, 2.75, 12).with_fixed_period(60, 5.5).with_caps(2.0, 2.0, 5.0)
+}
+
+#[test]
+fn test_flat_index_resets_once() {
+    let floating = five_one_arm(ForwardCurve::flat(4.0));
+    assert_eq!(floating.rate_schedule(360).unwrap(), vec![RatePeriod::new(1, 5.5), RatePeriod::new(61, 6.75)]);
+}
+
+#[test]
+fn test_rising_index_held_by_caps() {
+    // One point a year from 4%
+    let floating = five_one_arm(ForwardCurve::stepped(4.0, 1.0, 12, 360));
+    let schedule = floating.rate_schedule(360).unwrap();
+    let rates: Vec<(u32, f64)> = schedule.iter().map(|r| (r.start_period, r.apr)).collect();
+    // Initial cap, then the periodic cap, then the lifetime cap of 5.5 + 5
+    assert_eq!(rates, vec![(1, 5.5), (61, 7.5), (73, 9.5), (85, 10.5)]);
+}
+
+#[test]
+fn test_index_from_the_start_with_floor() {
+    let falling = FloatingRate::new(ForwardCurve::stepped(3.0, -0.5, 12, 60), 2.0, 12);
+    let rates: Vec<f64> = falling.rate_schedule(120).unwrap().iter().map(|r| r.apr).collect();
+    assert_eq!(rates, vec![5.0, 4.5, 4.0, 3.5, 3.0]);
+
+    let floored = falling.with_floor(4.25);
+    let rates: Vec<f64> = floored.rate_schedule(120).unwrap().iter().map(|r| r.apr).collect();
+    assert_eq!(rates, vec![5.0, 4.5, 4.25]);
+}
+
+#[test]
+fn test_builder_projects_off_the_curve() {
+    let floating = five_one_arm(ForwardCurve::flat(4.0));
+    let loan = Amortization::builder()
+        .principal(300_000.0)
+        .floating_rate(floating.clone())
+        .term_months(360)
+        .build()
+        .unwrap();
+    let expected = Amortization::new_adjustable(300_000.0, floating.rate_schedule(360).unwrap(), 360, None).unwrap();
+    assert_eq!(loan.schedule, expected.schedule);
+    assert_eq!(loan.payment_changes().len(), 1);
+
+    assert!(matches!(
+        Amortization::builder().principal(300_000.0).floating_rate(floating.clone()).build(),
+        Err(AmortizationError::MissingParameter("term_months"))
+    ));
+    assert!(matches!(
+        Amortization::builder()
+            .principal(300_000.0)
+            .floating_rate(floating)
+            .rate_schedule(vec![RatePeriod::new(1, 5.0)])
+            .term_months(360)
+            .build(),
+        Err(AmortizationError::ConflictingOptions("floating_rate", "rate_schedule"))
+    ));
+}
+
+#[test]
+fn test_index_shocks_respect_caps() {
+    let floating = five_one_arm(ForwardCurve::flat(4.0));
+    let base = Amortization::new_adjustable(300_000.0, floating.rate_schedule(360).unwrap(), 360, None).unwrap();
+    let results = ScenarioAnalysis::new(base)
+        .with_index_shocks(&floating, &[100.0, 500.0])
+        .unwrap()
+        .run()
+        .unwrap();
+
+    assert_eq!(results[1].name, "index +100bp");
+    // Held to the initial cap at the first reset, reaching index plus margin at the next
+    assert_eq!(
+        results[1].loan.rate_schedule,
+        vec![RatePeriod::new(1, 5.5), RatePeriod::new(61, 7.5), RatePeriod::new(73, 7.75)]
+    );
+    // +500bp reaches the lifetime cap a year after the first reset
+    assert_eq!(
+        results[2].loan.rate_schedule,
+        vec![RatePeriod::new(1, 5.5), RatePeriod::new(61, 7.5), RatePeriod::new(73, 9.5), RatePeriod::new(85, 10.5)]
+    );
+    assert!(results[2].interest_change > results[1].interest_change);
+}
+
+#[test]
+fn test_heloc_on_prime() {
+    let prime = FloatingRate::new(ForwardCurve::new(vec![RatePeriod::new(1, 7.5), RatePeriod::new(25, 6.5)]), 1.0, 1);
+    let schedule = Heloc::new(100_000.0, 0.0, 60, 240)
+        .with_draw(1, 50_000.0)
+        .with_floating_rate(&prime)
+        .unwrap()
+        .schedule()
+        .unwrap();
+    assert_float_eq(schedule.rows[0].apr, 8.5);
+    assert_float_eq(schedule.rows[24].apr, 7.5);
+
+    let invalid = FloatingRate::new(ForwardCurve::new(Vec::new()), 1.0, 1);
+    assert!(matches!(Heloc::new(100_000.0, 0.0, 60, 240).with_floating_rate(&invalid), Err(AmortizationError::InvalidRateSchedule(_))));
+    assert!(matches!(
+        FloatingRate::new(ForwardCurve::flat(4.0), 1.0, 0).rate_schedule(360),
+        Err(AmortizationError::InvalidRateSchedule(_))
+    ));
+}
@@ -0,0 +1,51 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, CapitalizationPolicy};
+
+fn loan() -> Amortization {
+    Amortization::new(200_000.0, 6.0, 360, NaiveDate::from_ymd_opt(2020, 1, 1)).unwrap()
+}
+
+#[test]
+fn test_forbearance_capitalize() {
+    let loan = loan();
+    let forborne = loan.forbearance(4, 6, CapitalizationPolicy::Capitalize).unwrap();
+
+    assert_eq!(forborne.schedule.len(), 360);
+    assert!(forborne.schedule[3..9].iter().all(|p| p.skipped && p.installment_amount == 0.0));
+    assert!(forborne.schedule[9].installment_amount > loan.periodic_payment);
+    assert!(forborne.schedule[8].remaining_balance > forborne.schedule[3].beginning_balance);
+    assert!(forborne.validate().is_valid());
+}
+
+#[test]
+fn test_forbearance_extend_term() {
+    let loan = loan();
+    let forborne = loan.forbearance(4, 6, CapitalizationPolicy::ExtendTerm).unwrap();
+
+    assert!(forborne.schedule.len() > 360);
+    assert_eq!(forborne.term_extension(), forborne.schedule.len() as u32 - 360);
+    assert!((forborne.schedule[9].installment_amount - loan.periodic_payment).abs() < 0.01);
+    assert!(forborne.total_interest > loan.total_interest);
+}
+
+#[test]
+fn test_forbearance_deferred_balance() {
+    let loan = loan();
+    let forborne = loan.forbearance(4, 6, CapitalizationPolicy::DeferredBalance).unwrap();
+    let missed = 6.0 * loan.periodic_payment;
+
+    assert_eq!(forborne.schedule.len(), 360);
+    assert!((forborne.deferred_balance() - missed).abs() < 0.01);
+    assert!(forborne.schedule[3..9].iter().all(|p| p.installment_amount == 0.0));
+    assert_eq!(forborne.schedule[20].remaining_balance, loan.schedule[20].remaining_balance);
+    assert!((forborne.final_payment_amount - (loan.final_payment_amount + missed)).abs() < 0.01);
+    assert!((forborne.total_interest - loan.total_interest).abs() < 0.01);
+    assert!(forborne.validate().is_valid());
+}
+
+#[test]
+fn test_forbearance_window_validation() {
+    let loan = loan();
+    assert!(matches!(loan.forbearance(0, 3, CapitalizationPolicy::Capitalize), Err(AmortizationError::PeriodOutOfRange(0))));
+    assert!(matches!(loan.forbearance(355, 6, CapitalizationPolicy::DeferredBalance), Err(AmortizationError::PeriodOutOfRange(355))));
+}
@@ -0,0 +1,52 @@
+use chrono::NaiveDate;
+use crate::{Amortization, Currency, Locale, Money, ScheduleFormatter, TableOptions};
+
+#[test]
+fn test_locale_number_formatting() {
+    assert_eq!(ScheduleFormatter::new(Locale::EN_US).format_number(1234567.891), "1,234,567.89");
+    assert_eq!(ScheduleFormatter::new(Locale::DE_DE).format_number(1234.56), "1.234,56");
+    assert_eq!(ScheduleFormatter::new(Locale::FR_FR).format_number(1234.56), "1\u{a0}234,56");
+    assert_eq!(ScheduleFormatter::new(Locale::ISO).format_number(-1234.56), "-1234.56");
+    assert_eq!(ScheduleFormatter::new(Locale::DE_DE).with_decimals(0).format_number(999.5), "1.000");
+}
+
+#[test]
+fn test_currency_symbols() {
+    let usd = ScheduleFormatter::new(Locale::EN_US).with_currency_symbol("$");
+    let eur = ScheduleFormatter::new(Locale::DE_DE).with_currency_symbol("€");
+
+    assert_eq!(usd.format_amount(1234.5), "$1,234.50");
+    assert_eq!(usd.format_amount(-5.0), "-$5.00");
+    assert_eq!(eur.format_amount(1234.5), "1.234,50\u{a0}€");
+}
+
+#[test]
+fn test_format_money_uses_currency_minor_units() {
+    let formatter = ScheduleFormatter::new(Locale::EN_US);
+
+    assert_eq!(formatter.format_money(Money::from_minor(123_456, Currency::USD)), "1,234.56 USD");
+    assert_eq!(formatter.format_money(Money::from_minor(123_456, Currency::JPY)), "123,456 JPY");
+    assert_eq!(formatter.with_currency_symbol("¥").format_money(Money::from_minor(1_500, Currency::JPY)), "¥1,500");
+}
+
+#[test]
+fn test_date_formats() {
+    let date = NaiveDate::from_ymd_opt(2024, 3, 9);
+
+    assert_eq!(ScheduleFormatter::new(Locale::EN_US).format_date(date), "03/09/2024");
+    assert_eq!(ScheduleFormatter::new(Locale::DE_DE).format_date(date), "09.03.2024");
+    assert_eq!(ScheduleFormatter::new(Locale::EN_GB).with_date_format("%e %b %Y").format_date(date), " 9 Mar 2024");
+    assert_eq!(ScheduleFormatter::new(Locale::EN_GB).format_date(None), "N/A");
+}
+
+#[test]
+fn test_localized_table() {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let loan = Amortization::new(200_000.0, 6.0, 360, Some(start)).unwrap();
+    let table = ScheduleFormatter::new(Locale::DE_DE).with_currency_symbol("€").table(&loan, &TableOptions::default());
+    let first = table.lines().nth(2).unwrap();
+
+    assert!(first.contains("01.01.2024"));
+    assert!(first.contains("1.199,10\u{a0}€"));
+    assert!(first.ends_with("199.800,90\u{a0}€"));
+}
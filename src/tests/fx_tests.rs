@@ -0,0 +1,72 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, CalculatorConfig, Currency, DatedRates, FxRateProvider, Portfolio, StaticRates};
+use super::assert_float_eq;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn loan_in(currency: Currency, balance: f64) -> CalculatorConfig {
+    CalculatorConfig {
+        currency,
+        ..CalculatorConfig::new(balance, 6.0, 12, Some(date(2025, 1, 1)))
+    }
+}
+
+#[test]
+fn test_static_rates() {
+    let rates = StaticRates::new().with_rate(Currency::EUR, Currency::USD, 1.25);
+    let any_day = date(2025, 6, 1);
+    assert_eq!(rates.rate(Currency::EUR, Currency::USD, any_day), Some(1.25));
+    assert_eq!(rates.rate(Currency::USD, Currency::EUR, any_day), Some(0.8));
+    assert_eq!(rates.rate(Currency::GBP, Currency::GBP, any_day), Some(1.0));
+    assert_eq!(rates.rate(Currency::GBP, Currency::USD, any_day), None);
+}
+
+#[test]
+fn test_dated_rates_hold_until_the_next_quote() {
+    let rates = DatedRates::new()
+        .with_rate(Currency::EUR, Currency::USD, date(2025, 1, 1), 1.10)
+        .with_rate(Currency::EUR, Currency::USD, date(2025, 7, 1), 1.20);
+    assert_eq!(rates.rate(Currency::EUR, Currency::USD, date(2024, 12, 31)), None);
+    assert_eq!(rates.rate(Currency::EUR, Currency::USD, date(2025, 3, 15)), Some(1.10));
+    assert_eq!(rates.rate(Currency::EUR, Currency::USD, date(2025, 7, 1)), Some(1.20));
+    assert_float_eq(rates.rate(Currency::USD, Currency::EUR, date(2026, 1, 1)).unwrap(), 1.0 / 1.20);
+}
+
+#[test]
+fn test_projection_in_reporting_currency() {
+    let pool = Portfolio::generate(vec![loan_in(Currency::USD, 10_000.0), loan_in(Currency::EUR, 10_000.0)]).unwrap();
+    let rates = StaticRates::new().with_rate(Currency::EUR, Currency::USD, 1.5);
+    let projection = pool.cash_flow_projection_in(Currency::USD, &rates).unwrap();
+    let installment = pool.loans[0].schedule[0].installment_amount;
+    assert_eq!(projection.len(), 12);
+    assert_float_eq(projection[0].total, installment * 2.5);
+    assert_float_eq(projection.iter().map(|month| month.principal).sum(), 25_000.0);
+
+    let balances = pool.balance_by_currency();
+    assert_eq!(balances.get(&Currency::EUR), Some(&10_000.0));
+    assert_eq!(balances.get(&Currency::USD), Some(&10_000.0));
+}
+
+#[test]
+fn test_projection_with_rates_per_date() {
+    let loans = [Amortization::from_config(loan_in(Currency::GBP, 12_000.0)).unwrap()];
+    // A closure works as a provider: 1.25 for the first half of the year, 1.30 after
+    let rates = |from: Currency, to: Currency, on: NaiveDate| {
+        (from == Currency::GBP && to == Currency::USD).then_some(if on < date(2025, 7, 1) { 1.25 } else { 1.30 })
+    };
+    let projection = Portfolio::project_cash_flows_in(&loans, Currency::USD, &rates).unwrap();
+    let installment = loans[0].schedule[0].installment_amount;
+    assert_float_eq(projection[4].total, installment * 1.25);
+    assert_float_eq(projection[6].total, installment * 1.30);
+}
+
+#[test]
+fn test_missing_exchange_rate() {
+    let loans = [Amortization::from_config(loan_in(Currency::JPY, 1_000_000.0)).unwrap()];
+    let result = Portfolio::project_cash_flows_in(&loans, Currency::USD, &StaticRates::new());
+    assert!(matches!(result, Err(AmortizationError::MissingExchangeRate(Currency::JPY, Currency::USD, _))));
+    let zero = StaticRates::new().with_rate(Currency::JPY, Currency::USD, 0.0);
+    assert!(Portfolio::project_cash_flows_in(&loans, Currency::USD, &zero).is_err());
+}
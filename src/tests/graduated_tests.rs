@@ -0,0 +1,70 @@
+use crate::{Amortization, AmortizationError, Rate};
+use super::assert_float_eq;
+
+fn gpm() -> Amortization {
+    Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .graduated_payment(7.5, 5)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_graduated_payment_steps_up_annually() {
+    let loan = gpm();
+    let initial = loan.periodic_payment;
+
+    assert!(initial < Amortization::new(200_000.0, 6.0, 360, None).unwrap().periodic_payment);
+    assert_float_eq(loan.schedule[11].installment_amount, initial);
+    assert_float_eq(loan.schedule[12].installment_amount, (initial * 1.075 * 100.0).round() / 100.0);
+
+    let level = loan.schedule[60].installment_amount;
+    assert!(level > loan.schedule[48].installment_amount);
+    assert_float_eq(loan.schedule[200].installment_amount, level);
+}
+
+#[test]
+fn test_graduated_payment_still_amortizes_over_full_term() {
+    let loan = gpm();
+    let last = loan.schedule.last().unwrap();
+
+    assert_eq!(loan.schedule.len(), 360);
+    assert_float_eq(last.remaining_balance, 0.0);
+    // Only cent rounding of the installments is left for the final payment to absorb
+    assert!((last.installment_amount - loan.schedule[358].installment_amount).abs() < 10.0);
+}
+
+#[test]
+fn test_graduated_payment_can_negatively_amortize_early() {
+    let loan = gpm();
+
+    assert!(loan.is_negatively_amortizing());
+    assert!(loan.peak_balance() > 200_000.0);
+}
+
+#[test]
+fn test_zero_growth_matches_level_payment() {
+    let loan = Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .graduated_payment(0.0, 5)
+        .build()
+        .unwrap();
+
+    assert_float_eq(loan.periodic_payment, 1199.10);
+}
+
+#[test]
+fn test_graduation_must_end_before_term() {
+    let result = Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(60)
+        .graduated_payment(5.0, 5)
+        .build();
+
+    assert!(matches!(result, Err(AmortizationError::InvalidGraduatedPayment(_))));
+}
@@ -0,0 +1,70 @@
+use chrono::NaiveDate;
+use crate::{AmortizationError, Heloc, HelocPhase};
+use super::assert_float_eq;
+
+fn heloc() -> Heloc {
+    Heloc::new(100_000.0, 8.0, 120, 240)
+        .with_draw(1, 50_000.0)
+        .with_draw(13, 20_000.0)
+        .with_repayment(24, 10_000.0)
+        .with_rate_change(61, 9.0)
+        .with_start_date(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+}
+
+#[test]
+fn test_draw_period_is_interest_only_on_balance() {
+    let schedule = heloc().schedule().unwrap();
+    let rows = &schedule.rows;
+
+    assert_eq!(rows[0].phase, HelocPhase::Draw);
+    assert_float_eq(rows[0].interest, 333.33);
+    assert_float_eq(rows[0].payment, 333.33);
+    assert_float_eq(rows[12].balance, 70_000.0);
+    assert_float_eq(rows[12].interest, 466.67);
+    assert_float_eq(rows[23].principal, 10_000.0);
+    assert_float_eq(rows[23].balance, 60_000.0);
+    assert_float_eq(rows[60].apr, 9.0);
+    assert_float_eq(rows[60].interest, 450.0);
+    assert_float_eq(schedule.peak_balance, 70_000.0);
+    assert_float_eq(schedule.balance_at_conversion, 60_000.0);
+}
+
+#[test]
+fn test_repayment_period_amortizes_with_rate_changes() {
+    let schedule = heloc().with_rate_change(150, 7.0).schedule().unwrap();
+    let repayment = schedule.repayment.as_ref().unwrap();
+    let rows = &schedule.rows;
+
+    assert_eq!(rows.len(), 360);
+    assert_eq!(rows[120].phase, HelocPhase::Repayment);
+    assert_eq!(rows[120].period, 121);
+    assert_eq!(rows[120].date, NaiveDate::from_ymd_opt(2035, 1, 1));
+    assert_float_eq(rows[120].payment, 539.83);
+    // The rate drop re-amortizes the remaining balance into a lower payment
+    assert!(rows[149].payment < rows[148].payment);
+    assert_float_eq(rows[359].balance, 0.0);
+    assert_float_eq(schedule.total_interest, rows.iter().map(|r| r.interest).sum());
+    assert_float_eq(repayment.balance, 60_000.0);
+}
+
+#[test]
+fn test_no_repayment_when_line_is_paid_off() {
+    let schedule = Heloc::new(10_000.0, 8.0, 12, 60)
+        .with_draw(1, 5_000.0)
+        .with_repayment(6, 5_000.0)
+        .schedule()
+        .unwrap();
+
+    assert!(schedule.repayment.is_none());
+    assert_eq!(schedule.rows.len(), 12);
+}
+
+#[test]
+fn test_credit_limit() {
+    let result = Heloc::new(10_000.0, 8.0, 12, 60)
+        .with_draw(1, 8_000.0)
+        .with_draw(2, 3_000.0)
+        .schedule();
+
+    assert!(matches!(result, Err(AmortizationError::CreditLimitExceeded(_))));
+}
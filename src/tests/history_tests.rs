@@ -0,0 +1,90 @@
+use std::io::Cursor;
+use chrono::NaiveDate;
+use crate::{ActualPayment, Amortization, AmortizationError, PaymentHistoryReader};
+use super::assert_float_eq;
+
+fn loan() -> Amortization {
+    Amortization::new(10_000.0, 6.0, 12, NaiveDate::from_ymd_opt(2025, 1, 1)).unwrap()
+}
+
+fn read(csv: &str) -> Result<Vec<ActualPayment>, AmortizationError> {
+    PaymentHistoryReader::new(Cursor::new(csv)).collect()
+}
+
+fn date(y: i32, m: u32, d: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(y, m, d)
+}
+
+#[test]
+fn test_reads_rows_with_and_without_header() {
+    let with_header = read("Date,Amount\n2025-01-01,860.66\n2025-02-01,\"$1,000.00\"\n").unwrap();
+    assert_eq!(with_header, vec![
+        ActualPayment::new(860.66).with_date(date(2025, 1, 1).unwrap()),
+        ActualPayment::new(1000.0).with_date(date(2025, 2, 1).unwrap()),
+    ]);
+
+    let headerless = read("01/15/2025,100\r\n\r\n2025/02/15,200.5\r\n").unwrap();
+    assert_eq!(headerless.len(), 2);
+    assert_eq!(headerless[1].date, date(2025, 2, 15));
+
+    // Columns follow the header, and an amount-only file carries no dates
+    let reordered = read("memo,amount,date\n\"rent, late\",50,2025-03-01\n").unwrap();
+    assert_eq!(reordered[0], ActualPayment::new(50.0).with_date(date(2025, 3, 1).unwrap()));
+    let undated = read("amount\n10\n20\n").unwrap();
+    assert!(undated.iter().all(|p| p.date.is_none()));
+}
+
+#[test]
+fn test_invalid_rows_name_the_line() {
+    let error = read("date,amount\n2025-01-01,100\n2025-02-30,100\n").unwrap_err();
+    assert_eq!(error.to_string(), "Invalid payment history: line 3: invalid date '2025-02-30'");
+    assert!(matches!(read("2025-01-01,lots"), Err(AmortizationError::InvalidPaymentHistory(_))));
+    assert!(matches!(read("2025-01-01,(25.00)"), Err(AmortizationError::InvalidPaymentHistory(_))));
+}
+
+#[test]
+fn test_reconcile_on_time_history() {
+    let loan = loan();
+    let csv: String = loan.schedule.iter().take(6)
+        .map(|p| format!("{},{:.2}\n", p.date.unwrap(), p.installment_amount))
+        .collect();
+    let ledger = loan.reconcile_history(Cursor::new(csv)).unwrap();
+    assert_eq!(ledger.entries.len(), 6);
+    assert_float_eq(ledger.remaining_balance, loan.schedule[5].remaining_balance);
+    assert_float_eq(ledger.cumulative_variance, 0.0);
+    assert_eq!(ledger.projected_payoff_period, Some(12));
+}
+
+#[test]
+fn test_reconcile_groups_split_and_missed_payments() {
+    let loan = loan();
+    let installment = loan.schedule[0].installment_amount;
+    let half = installment / 2.0;
+    // Installment 2 is paid in two halves, then nothing arrives until just after the fourth due
+    // date, which leaves 3 and 4 missed and counts towards the fifth
+    let csv = format!(
+        "date,amount\n2025-01-01,{:.2}\n2025-01-20,{:.2}\n2025-02-01,{:.2}\n2025-04-03,{:.2}\n",
+        installment, half, installment - half, installment
+    );
+    let ledger = loan.reconcile_history(Cursor::new(csv)).unwrap();
+    let amounts: Vec<f64> = ledger.entries.iter().map(|e| e.actual_amount).collect();
+    assert_eq!(ledger.entries.len(), 5);
+    assert_float_eq(amounts[1], installment);
+    assert_eq!(amounts[2], 0.0);
+    assert_eq!(amounts[3], 0.0);
+    assert_float_eq(amounts[4], installment);
+    assert_eq!(ledger.entries[1].paid_date, date(2025, 2, 1));
+    assert_eq!(ledger.entries[4].paid_date, date(2025, 4, 3));
+    assert!(ledger.remaining_balance > ledger.scheduled_balance);
+    assert!(ledger.periods_ahead(&loan).unwrap() < 0);
+}
+
+#[test]
+fn test_reconcile_undated_history_and_ordering() {
+    let loan = loan();
+    let ledger = loan.reconcile_history(Cursor::new("amount\n860.66\n860.66\n")).unwrap();
+    assert_eq!(ledger.entries.len(), 2);
+
+    let result = loan.reconcile_history(Cursor::new("2025-03-01,100\n2025-02-01,100\n"));
+    assert!(matches!(result, Err(AmortizationError::InvalidPaymentHistory(_))));
+}
@@ -0,0 +1,58 @@
+use std::collections::BTreeMap;
+use crate::{Amortization, Column, FormatOptions, Label, Language, StringTable};
+
+fn loan() -> Amortization {
+    Amortization::new(10_000.0, 6.0, 12, None).unwrap()
+}
+
+#[test]
+fn test_translated_table_headers() {
+    let loan = loan();
+    let english = loan.to_table();
+    assert_eq!(loan.to_table_in(&FormatOptions::default(), &Language::English), english);
+
+    let spanish = loan.to_table_in(&FormatOptions::default(), &Language::Spanish);
+    let header = spanish.lines().next().unwrap();
+    assert!(header.starts_with("N.º") && header.contains("Interés"), "{}", header);
+    // Only the headers change; the cells are padded to the new widths
+    let cells = |table: &str| table.lines().skip(2).map(|line| line.split_whitespace().collect::<Vec<_>>().join(" ")).collect::<Vec<_>>();
+    assert_eq!(cells(&spanish), cells(&english));
+
+    // Widths follow the characters of non-Latin headers
+    let urdu = loan.to_table_in(&FormatOptions::default().with_columns(vec![Column::RemainingBalance]), &Language::Urdu);
+    let lines: Vec<&str> = urdu.lines().collect();
+    assert_eq!(lines[0].trim(), "بقایا");
+    assert_eq!(lines[1].chars().count(), lines[2].chars().count());
+}
+
+#[test]
+fn test_translated_reports() {
+    let loan = loan();
+    let markdown = loan.to_markdown_in(&FormatOptions::default(), &Language::French);
+    assert!(markdown.starts_with("| Champ | Valeur |\n"));
+    assert!(markdown.contains("| Montant du prêt | 10,000.00 |"));
+    assert!(markdown.contains("| N° | Date | Échéance | Intérêts |"));
+
+    let html = loan.to_html_in(&FormatOptions::default(), &Language::German);
+    assert!(html.contains("<th>Darlehensbetrag</th>") && html.contains("<th>Tilgung</th>"));
+    assert_eq!(loan.to_html_in(&FormatOptions::default(), &Language::English), loan.to_html());
+}
+
+#[test]
+fn test_custom_string_table_falls_back_to_english() {
+    let labels = BTreeMap::from([(Label::RemainingBalance, "Owed".to_string())]);
+    assert_eq!(labels.text(Label::RemainingBalance), "Owed");
+    assert_eq!(labels.text(Label::Interest), "Interest");
+    let table = loan().to_table_in(&FormatOptions::default(), &labels);
+    assert!(table.lines().next().unwrap().ends_with("Owed"));
+}
+
+#[test]
+fn test_language_codes() {
+    assert_eq!(Language::from_code("es-MX"), Some(Language::Spanish));
+    assert_eq!(Language::from_code("FR_ca"), Some(Language::French));
+    assert_eq!(Language::from_code("ur"), Some(Language::Urdu));
+    assert_eq!(Language::from_code("pt"), None);
+    assert_eq!(Language::German.code(), "de");
+    assert_eq!(Label::from(Column::Principal).english(), Column::Principal.header());
+}
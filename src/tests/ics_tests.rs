@@ -0,0 +1,78 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, Fee, IcsOptions, Rate};
+
+fn loan() -> Amortization {
+    Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .start_date(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap())
+        .build()
+        .unwrap()
+}
+
+// Undoes line folding so assertions can match whole content lines
+fn unfold(ics: &str) -> String {
+    ics.replace("\r\n ", "")
+}
+
+#[test]
+fn test_one_event_per_due_date() {
+    let ics = loan().to_ics().unwrap();
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//amortize-rs//Payment Schedule//EN\r\n"));
+    assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+    assert_eq!(ics.matches("BEGIN:VEVENT\r\n").count(), 360);
+
+    let ics = unfold(&ics);
+    assert!(ics.contains("UID:loan-1-20250131@amortize-rs\r\nDTSTAMP:20250131T000000Z\r\n\
+        DTSTART;VALUE=DATE:20250131\r\nDTEND;VALUE=DATE:20250201\r\nSUMMARY:Loan payment: 1199.10 USD\r\n"));
+    assert!(ics.contains("DESCRIPTION:Installment 1 of 360: 1199.10 USD (interest 1000.00 USD\\, principal 199.10 USD)\\n\
+        Remaining balance: 199800.90 USD\r\n"));
+    assert!(ics.contains("DTSTART;VALUE=DATE:20250228\r\n"));
+    assert!(!ics.contains("VALARM"));
+}
+
+#[test]
+fn test_lines_are_folded() {
+    let ics = loan().to_ics_with(&IcsOptions::new().with_summary("Hypothek; Zahlung fällig")).unwrap();
+    assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+    assert!(unfold(&ics).contains("SUMMARY:Hypothek\\; Zahlung fällig: 1199.10 USD\r\n"));
+}
+
+#[test]
+fn test_reminders_and_fees() {
+    let loan = Amortization::builder()
+        .principal(10_000.0)
+        .rate(Rate::percent(7.5))
+        .term_months(12)
+        .start_date(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+        .fee(Fee::recurring("servicing", 4.5))
+        .build()
+        .unwrap();
+    let created = NaiveDate::from_ymd_opt(2024, 12, 1).unwrap().and_hms_opt(9, 30, 0).unwrap();
+    let options = IcsOptions::new().with_uid_prefix("car").with_reminder_days(3).with_created_at(created);
+    let ics = unfold(&loan.to_ics_with(&options).unwrap());
+
+    assert_eq!(ics.matches("BEGIN:VALARM\r\nACTION:DISPLAY\r\n").count(), 12);
+    assert!(ics.contains("TRIGGER:-P3D\r\nEND:VALARM\r\n"));
+    assert!(ics.contains("UID:car-12-20251201@amortize-rs\r\nDTSTAMP:20241201T093000Z\r\n"));
+    assert!(ics.contains("\\, fees 4.50 USD)"));
+}
+
+#[test]
+fn test_line_breaks_cannot_inject_properties() {
+    let options = IcsOptions::new()
+        .with_summary("Rent\rATTENDEE:mailto:a@example.com")
+        .with_uid_prefix("loan\r\nATTACH:http://example.com\u{7}");
+    let ics = unfold(&loan().to_ics_with(&options).unwrap());
+    assert!(ics.contains("UID:loan\\nATTACH:http://example.com-1-20250131@amortize-rs\r\n"));
+    assert!(ics.contains("SUMMARY:Rent\\nATTENDEE:mailto:a@example.com: 1199.10 USD"));
+    assert!(!ics.lines().any(|line| line.starts_with("ATTENDEE") || line.starts_with("ATTACH")));
+    assert!(!ics.replace("\r\n", "").contains(['\r', '\n', '\u{7}']));
+}
+
+#[test]
+fn test_undated_schedule() {
+    let loan = Amortization::new(1_000.0, 5.0, 12, None).unwrap();
+    assert!(matches!(loan.to_ics(), Err(AmortizationError::MissingParameter("start_date"))));
+}
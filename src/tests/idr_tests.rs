@@ -0,0 +1,87 @@
+use chrono::NaiveDate;
+use crate::{AmortizationError, IdrPlan};
+use super::assert_float_eq;
+
+#[test]
+fn test_payment_from_discretionary_income() {
+    let plan = IdrPlan::new(30_000.0, 6.0, 50_000.0);
+    // 10% of the income above 150% of 15,060
+    assert_float_eq(plan.payment_in(0), (50_000.0 - 22_590.0) * 0.10 / 12.0);
+
+    let family = plan.clone().with_family_size(3);
+    assert_float_eq(family.poverty_line_in(0), 15_060.0 + 2.0 * 5_380.0);
+    assert!(family.payment_in(0) < plan.payment_in(0));
+
+    let growing = plan.clone().with_income_growth(3.0);
+    assert_float_eq(growing.income_in(1), 51_500.0);
+    let projection = growing.project().unwrap();
+    assert_eq!(projection.months[11].payment, projection.months[0].payment);
+    assert!(projection.months[12].payment > projection.months[11].payment);
+}
+
+#[test]
+fn test_zero_payment_accrues_interest_until_forgiveness() {
+    let projection = IdrPlan::new(30_000.0, 6.0, 20_000.0)
+        .with_start_date(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+        .project()
+        .unwrap();
+
+    assert_eq!(projection.months.len(), 240);
+    assert!(projection.months.iter().all(|m| m.payment == 0.0));
+    assert_eq!(projection.negative_amortization_months(), 240);
+    // Unpaid interest doesn't compound: 150 a month for 20 years
+    assert_float_eq(projection.months[239].unpaid_interest, 36_000.0);
+    assert_eq!(projection.payoff_month, None);
+    assert_float_eq(projection.forgiven_amount, 66_000.0);
+    assert_float_eq(projection.peak_amount_owed(), 66_000.0);
+    assert_eq!(projection.forgiveness_date, NaiveDate::from_ymd_opt(2044, 12, 1));
+}
+
+#[test]
+fn test_interest_subsidy_waives_unpaid_interest() {
+    let projection = IdrPlan::new(30_000.0, 6.0, 20_000.0)
+        .with_interest_subsidy(100.0)
+        .with_poverty_multiple(225.0)
+        .with_forgiveness_years(25)
+        .project()
+        .unwrap();
+
+    assert_eq!(projection.months.len(), 300);
+    assert_float_eq(projection.total_subsidy, 150.0 * 300.0);
+    assert_float_eq(projection.forgiven_amount, 30_000.0);
+    assert_eq!(projection.negative_amortization_months(), 0);
+}
+
+#[test]
+fn test_high_income_repays_before_forgiveness() {
+    let projection = IdrPlan::new(30_000.0, 6.0, 120_000.0).project().unwrap();
+    let month = projection.payoff_month.unwrap();
+    assert!(month < 240);
+    assert_eq!(projection.forgiven_amount, 0.0);
+    assert_eq!(projection.forgiveness_date, None);
+    let last = projection.months.last().unwrap();
+    assert_eq!((last.balance, last.unpaid_interest), (0.0, 0.0));
+    assert_float_eq(projection.total_paid, 30_000.0 + projection.total_interest);
+}
+
+#[test]
+fn test_standard_payment_cap() {
+    let projection = IdrPlan::new(30_000.0, 6.0, 200_000.0).with_standard_payment_cap().project().unwrap();
+    // The 10-year standard installment on 30,000 at 6%
+    assert_eq!(projection.months[0].payment, 333.07);
+    assert_eq!(projection.payoff_month, Some(120));
+}
+
+#[test]
+fn test_invalid_plans() {
+    assert!(matches!(IdrPlan::new(0.0, 6.0, 50_000.0).project(), Err(AmortizationError::InvalidLoanAmount(_))));
+    assert!(matches!(IdrPlan::new(30_000.0, 6.0, -1.0).project(), Err(AmortizationError::InvalidRepaymentPlan(_))));
+    assert!(matches!(
+        IdrPlan::new(30_000.0, 6.0, 50_000.0).with_payment_percent(120.0).project(),
+        Err(AmortizationError::InvalidRepaymentPlan(_))
+    ));
+    assert!(matches!(
+        IdrPlan::new(30_000.0, 6.0, 50_000.0).with_income_growth(-100.0).project(),
+        Err(AmortizationError::InvalidProjectionRate(_))
+    ));
+}
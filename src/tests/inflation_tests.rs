@@ -0,0 +1,48 @@
+use crate::{Amortization, AmortizationBuilder, AmortizationError, Rate};
+use super::assert_float_eq;
+
+fn builder() -> AmortizationBuilder {
+    Amortization::builder().principal(200_000.0).rate(Rate::percent(6.0)).term_months(360)
+}
+
+#[test]
+fn test_payments_in_real_terms() {
+    let loan = builder().inflation_rate(3.0).build().unwrap();
+    let year_one = loan.schedule[11].real.unwrap();
+
+    assert_float_eq(year_one.discount_factor, 1.0 / 1.03);
+    assert_float_eq(year_one.installment_amount, loan.schedule[11].installment_amount / 1.03);
+    assert_float_eq(year_one.interest + year_one.principal, year_one.installment_amount);
+    let last = loan.schedule[359].real.unwrap();
+    assert_float_eq(last.discount_factor, 1.03_f64.powi(-30));
+}
+
+#[test]
+fn test_real_totals() {
+    let loan = builder().inflation_rate(3.0).build().unwrap();
+    let real_interest = loan.real_total_interest().unwrap();
+
+    assert!(real_interest < loan.total_interest);
+    assert!(loan.real_total_payment().unwrap() < loan.total_payment);
+    assert_float_eq(
+        real_interest,
+        loan.schedule.iter().map(|p| p.interest / 1.03_f64.powf(p.installment_number as f64 / 12.0)).sum(),
+    );
+
+    let nominal = builder().build().unwrap();
+    assert!(nominal.schedule[0].real.is_none());
+    assert_eq!(nominal.real_total_interest(), None);
+}
+
+#[test]
+fn test_recast_keeps_original_price_level() {
+    let loan = builder().inflation_rate(3.0).build().unwrap();
+    let recast = loan.recast_with_principal(12, 10_000.0).unwrap();
+
+    assert_float_eq(recast.schedule[0].real.unwrap().discount_factor, 1.03_f64.powf(-13.0 / 12.0));
+}
+
+#[test]
+fn test_invalid_inflation_rate() {
+    assert!(matches!(builder().inflation_rate(-100.0).build(), Err(AmortizationError::InvalidInterestRate(_))));
+}
@@ -0,0 +1,204 @@
+use chrono::NaiveDate;
+use crate::{AccrualPeriod, Amortization, AmortizationError, Currency, DailySimpleInterest, DayCount, InterestCalculator, InterestMethod, InterestPeriod, PaymentFrequency, Rate, flat_rate_to_apr};
+use super::{assert_float_eq, FLOAT_PRECISION};
+
+fn rule_of_78_loan() -> Amortization {
+    Amortization::builder()
+        .principal(1_000.0)
+        .rate(Rate::percent(12.0))
+        .term_months(12)
+        .interest_method(InterestMethod::RuleOf78)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_rule_of_78_front_loads_interest() {
+    let loan = rule_of_78_loan();
+    let actuarial = Amortization::new(1_000.0, 12.0, 12, None).unwrap();
+
+    assert_float_eq(loan.periodic_payment, actuarial.periodic_payment);
+    assert_float_eq(loan.finance_charge(), 66.20);
+    assert_float_eq(loan.schedule[0].interest, 10.18);
+    assert_float_eq(loan.schedule[11].interest, 0.85);
+    assert!(loan.schedule[0].interest > actuarial.schedule[0].interest);
+    assert_float_eq(loan.total_interest, loan.finance_charge());
+    assert_float_eq(loan.schedule.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_rule_of_78_rebate() {
+    let loan = rule_of_78_loan();
+    let actuarial = Amortization::new(1_000.0, 12.0, 12, None).unwrap();
+
+    assert_float_eq(loan.interest_rebate(0).unwrap(), 66.20);
+    assert_float_eq(loan.interest_rebate(6).unwrap(), 17.82);
+    assert_float_eq(loan.interest_rebate(12).unwrap(), 0.0);
+    assert!(loan.interest_rebate(6).unwrap() < actuarial.interest_rebate(6).unwrap());
+
+    let remaining_payments = 6.0 * loan.periodic_payment;
+    let payoff = loan.early_payoff_amount(6).unwrap();
+    assert!((payoff - (remaining_payments - 17.82)).abs() < 0.05);
+    assert!(payoff > actuarial.early_payoff_amount(6).unwrap());
+}
+
+#[test]
+fn test_rule_of_78_requires_standard_loan() {
+    let result = Amortization::builder()
+        .principal(1_000.0)
+        .rate(Rate::percent(12.0))
+        .term_months(12)
+        .extra_payment(50.0)
+        .interest_method(InterestMethod::RuleOf78)
+        .build();
+
+    assert!(matches!(result, Err(AmortizationError::UnsupportedInterestMethod(InterestMethod::RuleOf78))));
+    assert!(matches!(rule_of_78_loan().interest_rebate(13), Err(AmortizationError::PeriodOutOfRange(13))));
+}
+
+fn flat_loan() -> Amortization {
+    Amortization::builder()
+        .principal(10_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(36)
+        .interest_method(InterestMethod::Flat)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_flat_rate_interest_split_evenly() {
+    let loan = flat_loan();
+
+    assert_float_eq(loan.periodic_payment, 327.78);
+    assert_float_eq(loan.total_interest, 1_800.0);
+    assert!(loan.schedule.iter().all(|p| (p.interest - 50.0).abs() < FLOAT_PRECISION));
+    assert_float_eq(loan.final_payment_amount, 327.70);
+    assert_float_eq(loan.interest_rebate(18).unwrap(), 900.04);
+}
+
+#[test]
+fn test_flat_rate_apr_equivalent() {
+    let apr = flat_rate_to_apr(6.0, 36, PaymentFrequency::Monthly).unwrap();
+    assert!((apr - 11.0829).abs() < 0.001);
+
+    let loan = flat_loan();
+    assert!((loan.equivalent_apr().unwrap() - apr).abs() < 0.01);
+
+    let reducing = Amortization::new(10_000.0, apr, 36, None).unwrap();
+    assert!((reducing.periodic_payment - loan.periodic_payment).abs() < 0.01);
+    assert!((reducing.equivalent_apr().unwrap() - apr).abs() < 0.01);
+}
+
+// A lender that charges no interest on the first $1,000 of the balance
+#[derive(Debug)]
+struct InterestFreeTranche;
+
+impl InterestCalculator for InterestFreeTranche {
+    fn interest_for_period(&self, balance: f64, period: &InterestPeriod, _dates: Option<AccrualPeriod>) -> f64 {
+        (balance - 1_000.0).max(0.0) * period.periodic_rate
+    }
+}
+
+#[test]
+fn test_custom_interest_calculator() {
+    let loan = Amortization::builder()
+        .principal(10_000.0)
+        .rate(Rate::percent(12.0))
+        .term_months(12)
+        .interest_calculator(InterestFreeTranche)
+        .build()
+        .unwrap();
+    assert_float_eq(loan.schedule[0].interest, 90.0);
+    assert_float_eq(loan.schedule.last().unwrap().remaining_balance, 0.0);
+    assert!(loan.total_interest < Amortization::new(10_000.0, 12.0, 12, None).unwrap().total_interest);
+
+    let conflicting = Amortization::builder()
+        .principal(10_000.0)
+        .rate(Rate::percent(12.0))
+        .term_months(12)
+        .interest_method(InterestMethod::Flat)
+        .interest_calculator(InterestFreeTranche)
+        .build();
+    assert!(matches!(conflicting, Err(AmortizationError::ConflictingOptions("interest_calculator", "interest_method"))));
+}
+
+#[test]
+fn test_daily_simple_interest_follows_days_elapsed() {
+    let loan = Amortization::builder()
+        .principal(36_500.0)
+        .rate(Rate::percent(10.0))
+        .term_months(12)
+        .start_date(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+        .interest_calculator(DailySimpleInterest::new(DayCount::Actual365))
+        .build()
+        .unwrap();
+    // Due February 1st for January's 31 days, then March 1st for February's 28
+    let february = &loan.schedule[1];
+    assert_float_eq(february.interest, february.beginning_balance * 0.10 * 31.0 / 365.0);
+    let march = &loan.schedule[2];
+    assert_float_eq(march.interest, march.beginning_balance * 0.10 * 28.0 / 365.0);
+}
+
+#[test]
+fn test_interest_methods_have_matching_calculators() {
+    let period = InterestPeriod { installment_number: 1, periods: 12, principal: 1_000.0, apr: 12.0, periodic_rate: 0.01, installment_amount: 88.85, currency: Currency::USD };
+    assert_float_eq(InterestMethod::Actuarial.calculator().interest_for_period(1_000.0, &period, None), 10.0);
+    assert_float_eq(InterestMethod::Flat.calculator().interest_for_period(500.0, &period, None), 10.0);
+    assert_float_eq(InterestMethod::RuleOf78.calculator().interest_for_period(1_000.0, &period, None), 10.18);
+}
+
+#[test]
+fn test_built_in_calculators_round_to_the_currency() {
+    let period = InterestPeriod { installment_number: 1, periods: 12, principal: 1_000_000.0, apr: 7.0, periodic_rate: 0.07 / 12.0, installment_amount: 86_527.0, currency: Currency::JPY };
+    assert_eq!(InterestMethod::Flat.calculator().interest_for_period(1_000_000.0, &period, None), 5_833.0);
+    assert_eq!(InterestMethod::RuleOf78.calculator().interest_for_period(1_000_000.0, &period, None), 5_896.0);
+    assert!((InterestMethod::Actuarial.calculator().interest_for_period(1_000_000.0, &period, None) - 5_833.333).abs() < 0.001);
+
+    let loan = Amortization::builder()
+        .principal(1_000_000.0)
+        .rate(Rate::percent(7.0))
+        .term_months(12)
+        .currency(Currency::JPY)
+        .interest_method(InterestMethod::RuleOf78)
+        .build()
+        .unwrap();
+    assert!(loan.schedule.iter().all(|p| p.interest.fract() == 0.0 && p.installment_amount.fract() == 0.0), "{:?}", loan.schedule);
+}
+
+#[test]
+fn test_rule_of_78_rebate_in_yen() {
+    let loan = Amortization::builder()
+        .principal(1_000_000.0)
+        .rate(Rate::percent(7.0))
+        .term_months(12)
+        .currency(Currency::JPY)
+        .interest_method(InterestMethod::RuleOf78)
+        .build()
+        .unwrap();
+    // 38,324 yen of finance charge, 28 of whose 78 digits remain after five installments
+    assert_eq!(loan.interest_rebate(5).unwrap(), 13_757.0);
+    assert_eq!(loan.interest_rebate(2).unwrap(), 27_023.0);
+}
+
+#[test]
+fn test_flat_rate_schedule_in_yen() {
+    let loan = Amortization::builder()
+        .principal(1_000_000.0)
+        .rate(Rate::percent(7.0))
+        .term_months(12)
+        .currency(Currency::JPY)
+        .interest_method(InterestMethod::Flat)
+        .build()
+        .unwrap();
+
+    assert!(loan.schedule.iter().all(|p| p.interest == 5_833.0), "{:?}", loan.schedule);
+    assert!(loan.schedule.iter().all(|p| p.installment_amount.fract() == 0.0 && p.principal.fract() == 0.0));
+    assert_eq!(loan.periodic_payment, 89_167.0);
+    assert_eq!(loan.total_interest, 69_996.0);
+    assert_eq!(loan.total_payment, 1_069_996.0);
+    assert_eq!(loan.interest_rebate(6).unwrap(), 35_002.0);
+
+    let apr = flat_rate_to_apr(7.0, 12, PaymentFrequency::Monthly).unwrap();
+    assert!((loan.equivalent_apr().unwrap() - apr).abs() < 0.01);
+}
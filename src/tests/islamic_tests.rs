@@ -0,0 +1,93 @@
+use chrono::NaiveDate;
+use crate::{AmortizationError, Murabaha, Musharakah, MusharakahPayment, ProfitRecognition};
+use super::assert_float_eq;
+
+#[test]
+fn test_murabaha_straight_line() {
+    let schedule = Murabaha::new(12_000.0, 5.0, 24)
+        .with_down_payment(2_000.0)
+        .with_start_date(NaiveDate::from_ymd_opt(2025, 1, 10).unwrap())
+        .schedule()
+        .unwrap();
+    let first = &schedule.rows[0];
+
+    assert_float_eq(schedule.financed_cost, 10_000.0);
+    assert_float_eq(schedule.markup, 1_000.0);
+    assert_float_eq(schedule.sale_price, 11_000.0);
+    assert_float_eq(schedule.installment, 458.33);
+    assert_eq!(first.date, NaiveDate::from_ymd_opt(2025, 1, 10));
+    assert_float_eq(first.profit, 41.67);
+    assert_float_eq(first.cost_recovered, 416.66);
+    assert_float_eq(first.receivable, 10_541.67);
+    assert_float_eq(first.deferred_profit, 958.33);
+
+    let last = schedule.rows.last().unwrap();
+    assert_float_eq(last.receivable, 0.0);
+    assert_float_eq(last.deferred_profit, 0.0);
+    assert_float_eq(last.outstanding_cost, 0.0);
+    assert_float_eq(schedule.rows.iter().map(|r| r.installment).sum(), 11_000.0);
+}
+
+#[test]
+fn test_murabaha_front_loaded_recognition() {
+    let straight = Murabaha::new(10_000.0, 6.0, 12).schedule().unwrap();
+    let digits = Murabaha::new(10_000.0, 6.0, 12).with_recognition(ProfitRecognition::SumOfDigits).schedule().unwrap();
+    let effective = Murabaha::new(10_000.0, 6.0, 12).with_recognition(ProfitRecognition::EffectiveRate).schedule().unwrap();
+
+    assert_float_eq(digits.rows[0].profit, 600.0 * 12.0 / 78.0);
+    assert!(effective.rows[0].profit > straight.rows[0].profit);
+    assert!(effective.rows[11].profit < straight.rows[11].profit);
+    for schedule in [&straight, &digits, &effective] {
+        assert_float_eq(schedule.rows.iter().map(|r| r.profit).sum(), 600.0);
+        assert_float_eq(schedule.rows.last().unwrap().outstanding_cost, 0.0);
+        assert_float_eq(schedule.rows[0].installment, 883.33);
+    }
+}
+
+#[test]
+fn test_musharakah_equal_units() {
+    let schedule = Musharakah::new(300_000.0, 60_000.0, 6.0, 240).schedule().unwrap();
+    let first = &schedule.rows[0];
+    let second = &schedule.rows[1];
+
+    assert_float_eq(first.rent, 1_200.0);
+    assert_float_eq(first.unit_purchase, 1_000.0);
+    assert_float_eq(first.payment, 2_200.0);
+    assert_float_eq(first.financier_share, 239_000.0);
+    assert_float_eq(first.customer_share, 61_000.0);
+    assert_float_eq(first.customer_ownership, 61_000.0 / 3_000.0);
+    assert_float_eq(second.rent, 1_195.0);
+
+    let last = schedule.rows.last().unwrap();
+    assert_float_eq(last.financier_share, 0.0);
+    assert_float_eq(last.customer_ownership, 100.0);
+    assert_float_eq(schedule.total_payment - schedule.total_rent, 240_000.0);
+}
+
+#[test]
+fn test_musharakah_level_payment() {
+    let schedule = Musharakah::new(300_000.0, 60_000.0, 6.0, 240)
+        .with_payment(MusharakahPayment::Level)
+        .schedule()
+        .unwrap();
+
+    // Same as a 240 month annuity on the financier's share at 6%
+    assert_float_eq(schedule.rows[0].payment, 1_719.43);
+    assert_float_eq(schedule.rows[100].payment, 1_719.43);
+    assert!(schedule.rows[1].unit_purchase > schedule.rows[0].unit_purchase);
+    assert_float_eq(schedule.rows.last().unwrap().financier_share, 0.0);
+    // The final payment settles the cents dropped from the rounded level payment
+    assert!((schedule.rows.last().unwrap().payment - 1_719.43).abs() < 5.0);
+}
+
+#[test]
+fn test_invalid_terms() {
+    let result = Murabaha::new(10_000.0, 5.0, 12).with_down_payment(10_000.0).schedule();
+    assert!(matches!(result, Err(AmortizationError::InvalidPaymentAmount(_))));
+
+    let result = Musharakah::new(300_000.0, 60_000.0, -1.0, 240).schedule();
+    assert!(matches!(result, Err(AmortizationError::InvalidInterestRate(_))));
+
+    let result = Musharakah::new(300_000.0, 60_000.0, 6.0, 0).schedule();
+    assert!(matches!(result, Err(AmortizationError::InvalidPeriods(0))));
+}
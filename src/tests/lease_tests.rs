@@ -0,0 +1,49 @@
+use crate::{AmortizationError, Lease, PaymentTiming};
+use super::assert_float_eq;
+
+#[test]
+fn test_initial_liability_is_present_value_of_payments() {
+    let lease = Lease::new(1_000.0, 6.0, 36);
+
+    // 1000 * (1 - 1.005^-36) / 0.005 * 1.005
+    assert_float_eq(lease.initial_liability().unwrap(), 33_035.37);
+    assert_float_eq(lease.with_timing(PaymentTiming::Arrears).initial_liability().unwrap(), 32_871.02);
+}
+
+#[test]
+fn test_liability_amortizes_with_lease_payments() {
+    let schedule = Lease::new(1_000.0, 6.0, 36).schedule().unwrap();
+    let liability = &schedule.liability;
+
+    assert_eq!(liability.schedule.len(), 36);
+    assert_float_eq(liability.periodic_payment, 1_000.0);
+    assert_float_eq(liability.schedule[0].interest, 0.0);
+    assert_float_eq(liability.schedule[0].principal, 1_000.0);
+    assert_float_eq(liability.schedule.last().unwrap().remaining_balance, 0.0);
+    assert_float_eq(liability.total_interest, 36_000.0 - schedule.initial_liability);
+}
+
+#[test]
+fn test_rou_asset_amortizes_straight_line() {
+    let schedule = Lease::new(1_000.0, 6.0, 36)
+        .with_initial_direct_costs(500.0)
+        .schedule()
+        .unwrap();
+    let straight_line = schedule.initial_rou_asset / 36.0;
+
+    assert_float_eq(schedule.initial_rou_asset, schedule.initial_liability + 500.0);
+    assert_eq!(schedule.rou_asset.len(), 36);
+    assert_float_eq(schedule.rou_asset[0].amortization, straight_line);
+    assert_float_eq(schedule.rou_asset[0].carrying_value, schedule.initial_rou_asset - straight_line);
+    assert_float_eq(schedule.rou_asset.last().unwrap().carrying_value, 0.0);
+}
+
+#[test]
+fn test_invalid_lease_inputs() {
+    assert!(matches!(Lease::new(0.0, 6.0, 36).schedule(), Err(AmortizationError::InvalidPaymentAmount(_))));
+    assert!(matches!(Lease::new(1_000.0, 0.0, 36).schedule(), Err(AmortizationError::InvalidInterestRate(_))));
+    assert!(matches!(
+        Lease::new(1_000.0, 6.0, 36).with_initial_direct_costs(-1.0).schedule(),
+        Err(AmortizationError::InvalidUpfrontCost(_))
+    ));
+}
@@ -0,0 +1,61 @@
+use crate::level::{periodic_payment, schedule};
+use crate::{Amortization, DEFAULT_MAX_PERIODS};
+
+fn assert_matches_the_full_calculator(principal: f64, apr: f64, months: u32) {
+    let loan = Amortization::new(principal, apr, months, None).unwrap();
+    let case = format!("{} at {}% over {} months", principal, apr, months);
+    assert_eq!(periodic_payment(principal, apr, months), Some(loan.periodic_payment), "{}", case);
+
+    let rows = schedule(principal, apr, months).unwrap();
+    assert_eq!(rows.len(), loan.schedule.len(), "{}", case);
+    for (row, payment) in rows.iter().zip(&loan.schedule) {
+        let expected = (payment.installment_number, payment.interest, payment.principal, payment.installment_amount, payment.remaining_balance);
+        assert_eq!((row.installment_number, row.interest, row.principal, row.installment_amount, row.remaining_balance), expected, "{}", case);
+    }
+}
+
+#[test]
+fn test_matches_the_full_calculator() {
+    for (principal, apr, months) in [(200_000.0, 6.0, 360), (10_000.0, 5.0, 12), (1_200.0, 0.0, 7), (1_000.0, 12.0, 2), (250_000.0, 3.875, 180)] {
+        assert_matches_the_full_calculator(principal, apr, months);
+    }
+}
+
+// Any change to one engine's arithmetic that the other doesn't share shows up somewhere in
+// a few hundred odd principals, rates and terms
+#[test]
+fn test_matches_the_full_calculator_across_a_sweep() {
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = |bound: u64| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed % bound
+    };
+    for _ in 0..1000 {
+        let principal = (next(100_000_000) + 1) as f64 / 100.0;
+        let apr = match next(8) {
+            0 => 0.0,
+            1 => 40.0 + next(2_000) as f64 / 100.0,
+            _ => next(1_500_000) as f64 / 100_000.0,
+        };
+        let months = match next(4) {
+            0 => next(12) as u32 + 1,
+            _ => next(480) as u32 + 1,
+        };
+        assert_matches_the_full_calculator(principal, apr, months);
+    }
+    // Payments rounded down onto the interest get a cent more, or none when they never covered it
+    assert_matches_the_full_calculator(1_000_000.0, 36.0, 6000);
+    assert_matches_the_full_calculator(0.01, 0.0, 7);
+}
+
+#[test]
+fn test_invalid_terms() {
+    assert_eq!(periodic_payment(0.0, 5.0, 12), None);
+    assert_eq!(periodic_payment(f64::NAN, 5.0, 12), None);
+    assert_eq!(periodic_payment(1_000.0, -1.0, 12), None);
+    assert_eq!(periodic_payment(1_000.0, 5.0, 0), None);
+    assert!(periodic_payment(1_000.0, 5.0, DEFAULT_MAX_PERIODS).is_some());
+    assert_eq!(schedule(1_000.0, 5.0, DEFAULT_MAX_PERIODS + 1), None);
+}
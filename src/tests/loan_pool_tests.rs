@@ -0,0 +1,128 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, CalculatorConfig, PaymentFrequency, Portfolio};
+use super::assert_float_eq;
+
+fn start() -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(2025, 1, 1)
+}
+
+#[test]
+fn test_pool_statistics() {
+    let pool = Portfolio::generate(vec![
+        CalculatorConfig::new(100_000.0, 6.0, 360, start()),
+        CalculatorConfig::new(300_000.0, 4.0, 180, start()),
+    ]).unwrap();
+    let first = Amortization::new(100_000.0, 6.0, 360, start()).unwrap();
+    let second = Amortization::new(300_000.0, 4.0, 180, start()).unwrap();
+
+    assert_eq!(pool.loans.len(), 2);
+    assert_float_eq(pool.total_balance, 400_000.0);
+    assert_float_eq(pool.weighted_average_rate, 4.5);
+    assert_float_eq(pool.weighted_average_term, 225.0);
+    assert_float_eq(pool.total_interest, first.total_interest + second.total_interest);
+    assert_float_eq(pool.total_payment, first.total_payment + second.total_payment);
+}
+
+#[test]
+fn test_monthly_cash_flows() {
+    let pool = Portfolio::generate(vec![
+        CalculatorConfig::new(100_000.0, 6.0, 360, start()),
+        CalculatorConfig::new(300_000.0, 4.0, 180, start()),
+    ]).unwrap();
+
+    assert_eq!(pool.cash_flows.len(), 360);
+    assert_float_eq(pool.cash_flows[0], pool.loans[0].periodic_payment + pool.loans[1].periodic_payment);
+    assert_float_eq(pool.cash_flows[200], pool.loans[0].periodic_payment);
+    assert_float_eq(pool.cash_flows.iter().sum(), pool.total_payment);
+}
+
+#[test]
+fn test_quarterly_loan_cash_flows() {
+    let pool = Portfolio::generate(vec![CalculatorConfig {
+        frequency: PaymentFrequency::Quarterly,
+        ..CalculatorConfig::new(50_000.0, 5.0, 24, start())
+    }]).unwrap();
+
+    assert_eq!(pool.cash_flows.len(), 24);
+    assert_float_eq(pool.cash_flows[0], 0.0);
+    assert_float_eq(pool.cash_flows[2], pool.loans[0].periodic_payment);
+}
+
+#[test]
+fn test_preserves_order_for_large_pools() {
+    let configs: Vec<CalculatorConfig> = (1..=500)
+        .map(|n| CalculatorConfig::new(1_000.0 * n as f64, 5.0, 60, start()))
+        .collect();
+    let pool = Portfolio::generate(configs).unwrap();
+
+    assert_eq!(pool.loans.len(), 500);
+    for (index, loan) in pool.loans.iter().enumerate() {
+        assert_float_eq(loan.balance, 1_000.0 * (index + 1) as f64);
+    }
+}
+
+#[test]
+fn test_invalid_config_fails_batch() {
+    let result = Portfolio::generate(vec![
+        CalculatorConfig::new(100_000.0, 6.0, 360, start()),
+        CalculatorConfig::new(-5.0, 6.0, 360, start()),
+    ]);
+    assert!(matches!(result, Err(AmortizationError::InvalidLoanAmount(_))));
+
+    let result = Portfolio::generate(Vec::new());
+    assert!(matches!(result, Err(AmortizationError::MissingParameter("configs"))));
+}
+
+#[test]
+fn test_projection_aligns_calendar_months() {
+    let january = Amortization::new(10_000.0, 6.0, 12, start()).unwrap();
+    let march = Amortization::new(20_000.0, 3.0, 6, NaiveDate::from_ymd_opt(2025, 3, 20)).unwrap();
+    let projection = Portfolio::project_cash_flows(&[january.clone(), march.clone()]).unwrap();
+
+    assert_eq!(projection.len(), 12);
+    assert_eq!(projection[0].month, NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+    assert_eq!(projection[0].payments, 1);
+    assert_eq!(projection[2].payments, 2);
+    assert_float_eq(projection[2].total, january.schedule[2].installment_amount + march.schedule[0].installment_amount);
+    assert_float_eq(projection[2].interest, january.schedule[2].interest + march.schedule[0].interest);
+    assert_eq!(projection[8].payments, 1);
+
+    let principal: f64 = projection.iter().map(|m| m.principal).sum();
+    assert_float_eq(principal, 30_000.0);
+}
+
+#[test]
+fn test_projection_fills_gaps() {
+    let annual = Amortization::from_config(CalculatorConfig {
+        frequency: PaymentFrequency::Annually,
+        ..CalculatorConfig::new(10_000.0, 5.0, 36, start())
+    }).unwrap();
+    let projection = Portfolio::project_cash_flows(&[annual]).unwrap();
+
+    assert_eq!(projection.len(), 25);
+    assert_eq!(projection[1].payments, 0);
+    assert_float_eq(projection[1].total, 0.0);
+    assert_eq!(projection[12].month, NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+    assert_eq!(projection[12].payments, 1);
+}
+
+#[test]
+fn test_pool_projection() {
+    let pool = Portfolio::generate(vec![
+        CalculatorConfig::new(100_000.0, 6.0, 360, start()),
+        CalculatorConfig::new(300_000.0, 4.0, 180, start()),
+    ]).unwrap();
+    let projection = pool.cash_flow_projection().unwrap();
+
+    assert_eq!(projection.len(), 360);
+    for (month, cash_flow) in projection.iter().zip(&pool.cash_flows) {
+        assert_float_eq(month.total, *cash_flow);
+    }
+}
+
+#[test]
+fn test_projection_requires_dates() {
+    let loan = Amortization::new(10_000.0, 6.0, 12, None).unwrap();
+    let result = Portfolio::project_cash_flows(&[loan]);
+    assert!(matches!(result, Err(AmortizationError::MissingParameter("start_date"))));
+}
@@ -0,0 +1,144 @@
+use crate::{Amortization, AmortizationError, Decimal, LoanType, Rate};
+use super::assert_float_eq;
+
+fn interest_only_loan() -> Amortization {
+    Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .loan_type(LoanType::InterestOnly { io_periods: 120 })
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_interest_only_phase_has_zero_principal() {
+    let loan = interest_only_loan();
+
+    for payment in &loan.schedule[..120] {
+        assert_eq!(payment.principal, 0.0);
+        assert_float_eq(payment.interest, 500.0);
+        assert_float_eq(payment.installment_amount, 500.0);
+        assert_float_eq(payment.remaining_balance, 100_000.0);
+    }
+    assert!(loan.is_interest_only(120));
+    assert!(!loan.is_interest_only(121));
+}
+
+#[test]
+fn test_interest_only_amortizes_over_remaining_term() {
+    let loan = interest_only_loan();
+    let amortizing = Amortization::new(100_000.0, 6.0, 240, None).unwrap();
+
+    assert_eq!(loan.amortizing_periods(), 240);
+    assert_float_eq(loan.periodic_payment, amortizing.periodic_payment);
+    assert_float_eq(loan.schedule[120].installment_amount, amortizing.periodic_payment);
+    assert!(loan.schedule[120].principal > 0.0);
+    assert_float_eq(loan.schedule.last().unwrap().remaining_balance, 0.0);
+    assert!(loan.total_interest > amortizing.total_interest);
+}
+
+#[test]
+fn test_interest_only_periods_must_be_less_than_term() {
+    let result = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(36)
+        .loan_type(LoanType::InterestOnly { io_periods: 36 })
+        .build();
+    assert!(matches!(result, Err(AmortizationError::InvalidInterestOnlyPeriods(36))));
+}
+
+#[test]
+fn test_interest_only_with_decimal_backend() {
+    let loan = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(60)
+        .loan_type(LoanType::InterestOnly { io_periods: 12 })
+        .build_with::<Decimal>()
+        .unwrap();
+    let principal: f64 = loan.schedule.iter().map(|p| p.principal).sum();
+
+    assert_eq!(loan.schedule.len(), 60);
+    assert_eq!(loan.schedule[11].interest, 500.0);
+    assert_eq!(loan.schedule[11].principal, 0.0);
+    assert!((principal - 100_000.0).abs() < 1e-6);
+}
+
+fn balloon_loan() -> Amortization {
+    Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .balloon_at(60)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_balloon_matures_early() {
+    let loan = balloon_loan();
+    let fully_amortizing = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+
+    assert_eq!(loan.schedule.len(), 60);
+    assert_float_eq(loan.periodic_payment, fully_amortizing.periodic_payment);
+    for (balloon, regular) in loan.schedule[..59].iter().zip(&fully_amortizing.schedule) {
+        assert_float_eq(balloon.principal, regular.principal);
+        assert_eq!(balloon.balloon_amount, 0.0);
+    }
+}
+
+#[test]
+fn test_balloon_final_payment_carries_remaining_balance() {
+    let loan = balloon_loan();
+    let fully_amortizing = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let last = loan.schedule.last().unwrap();
+    let expected_balloon = fully_amortizing.schedule[59].remaining_balance;
+
+    assert_float_eq(last.balloon_amount, expected_balloon);
+    assert_float_eq(loan.balloon_amount().unwrap(), expected_balloon);
+    assert_float_eq(last.installment_amount, loan.periodic_payment + expected_balloon);
+    assert_eq!(last.remaining_balance, 0.0);
+    assert_float_eq(last.ending_balance(), 0.0);
+    assert_eq!(fully_amortizing.balloon_amount(), None);
+}
+
+#[test]
+fn test_balloon_totals_include_balloon() {
+    let loan = balloon_loan();
+    let paid: f64 = loan.schedule.iter().map(|p| p.installment_amount).sum();
+    let interest: f64 = loan.schedule.iter().map(|p| p.interest).sum();
+
+    assert_float_eq(loan.total_payment, paid);
+    assert_float_eq(loan.total_interest, interest);
+}
+
+#[test]
+fn test_balloon_period_validation() {
+    for period in [0, 360, 400] {
+        let result = Amortization::builder()
+            .principal(200_000.0)
+            .rate(Rate::percent(6.0))
+            .term_months(360)
+            .balloon_at(period)
+            .build();
+        assert!(matches!(result, Err(AmortizationError::InvalidBalloonPeriod(p)) if p == period));
+    }
+}
+
+#[test]
+fn test_balloon_with_decimal_backend() {
+    let loan = Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .balloon_at(84)
+        .build_with::<Decimal>()
+        .unwrap();
+    let principal: f64 = loan.schedule.iter().map(|p| p.principal).sum();
+
+    assert_eq!(loan.schedule.len(), 84);
+    assert!(loan.balloon_amount().unwrap() > 150_000.0);
+    assert!((principal - 200_000.0).abs() < 1e-6);
+}
@@ -0,0 +1,59 @@
+use chrono::NaiveDate;
+use crate::{Amortization, Escrow, MilestoneKind, MortgageInsurance, Rate};
+
+fn mortgage() -> Amortization {
+    Amortization::builder()
+        .principal(225_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .start_date(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+        .property_value(250_000.0, 0.0)
+        .escrow(Escrow::new(250_000.0, 3_000.0, 1_200.0).with_mortgage_insurance(MortgageInsurance::new(0.5)))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_milestones_in_installment_order() {
+    let loan = mortgage();
+    let milestones = loan.milestones();
+
+    assert_eq!(milestones.len(), 5);
+    assert!(milestones.windows(2).all(|pair| pair[0].installment_number <= pair[1].installment_number));
+    for milestone in &milestones {
+        let payment = &loan.schedule[milestone.installment_number as usize - 1];
+        assert_eq!(milestone.date, payment.date);
+        assert_eq!(milestone.remaining_balance, payment.remaining_balance);
+    }
+}
+
+#[test]
+fn test_each_milestone() {
+    let loan = mortgage();
+    let row = |kind| &loan.schedule[loan.milestone(kind).unwrap().installment_number as usize - 1];
+
+    let ltv = row(MilestoneKind::Ltv80);
+    assert!(ltv.remaining_balance <= 200_000.0);
+    assert!(loan.schedule[ltv.installment_number as usize - 2].remaining_balance > 200_000.0);
+
+    // Insurance stops once the balance before an installment is at 78% of the appraisal
+    let drop_off = row(MilestoneKind::MortgageInsuranceDropOff);
+    assert_eq!(drop_off.escrow.unwrap().mortgage_insurance, 0.0);
+    assert!(drop_off.beginning_balance <= 195_000.0);
+    assert!(drop_off.installment_number > ltv.installment_number);
+
+    assert!(row(MilestoneKind::HalfPrincipalRepaid).remaining_balance <= 112_500.0);
+    let crossover = row(MilestoneKind::Crossover);
+    assert!(crossover.principal > crossover.interest);
+    assert_eq!(row(MilestoneKind::HalfwayByDate).date, NaiveDate::from_ymd_opt(2040, 1, 1));
+}
+
+#[test]
+fn test_inapplicable_milestones_left_out() {
+    let loan = Amortization::new(100_000.0, 5.0, 120, None).unwrap();
+    let kinds: Vec<MilestoneKind> = loan.milestones().iter().map(|m| m.kind).collect();
+
+    assert_eq!(kinds, vec![MilestoneKind::Crossover, MilestoneKind::HalfPrincipalRepaid]);
+    assert_eq!(loan.milestone(MilestoneKind::Crossover).unwrap().installment_number, 1);
+    assert_eq!(MilestoneKind::Ltv80.label(), "80% loan-to-value");
+}
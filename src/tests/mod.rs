@@ -1 +1,131 @@
 mod unit_tests;
+mod builder_tests;
+mod export_tests;
+mod rate_tests;
+mod decimal_tests;
+mod loan_type_tests;
+mod day_count_tests;
+mod solver_tests;
+mod effective_apr_tests;
+mod compare_tests;
+mod refinance_tests;
+mod schedule_tests;
+mod negative_amortization_tests;
+mod deferment_tests;
+mod skip_tests;
+mod accelerated_tests;
+mod tvm_tests;
+mod timing_tests;
+mod lease_tests;
+mod bond_tests;
+mod graduated_tests;
+mod escrow_tests;
+mod property_tests;
+mod money_tests;
+mod validate_tests;
+#[cfg(feature = "wasm")]
+mod wasm_tests;
+#[cfg(feature = "charting")]
+mod charting_tests;
+#[cfg(feature = "cli")]
+mod cli_tests;
+#[cfg(feature = "xlsx")]
+mod xlsx_tests;
+mod table_tests;
+mod format_tests;
+mod query_tests;
+mod annual_tests;
+mod rate_convention_tests;
+mod zero_interest_tests;
+mod revolving_tests;
+mod portfolio_tests;
+mod cashflow_tests;
+mod interest_tests;
+mod equal_principal_tests;
+mod prepayment_tests;
+mod modification_tests;
+mod forbearance_tests;
+mod report_tests;
+mod calendar_tests;
+mod odd_days_tests;
+mod servicing_tests;
+mod points_tests;
+mod construction_tests;
+mod heloc_tests;
+mod reverse_mortgage_tests;
+mod savings_tests;
+mod inflation_tests;
+mod tax_tests;
+mod rent_vs_buy_tests;
+mod down_payment_tests;
+mod loan_pool_tests;
+mod projection_tests;
+mod duration_tests;
+mod valuation_tests;
+mod scenario_tests;
+mod islamic_tests;
+mod aprc_tests;
+mod fixed_tests;
+mod property_based_tests;
+mod cumulative_tests;
+mod diff_tests;
+mod compat_tests;
+#[cfg(feature = "config")]
+mod config_tests;
+mod history_tests;
+#[cfg(feature = "ffi")]
+mod ffi_tests;
+#[cfg(feature = "server")]
+mod server_tests;
+mod penalty_tests;
+mod teaser_tests;
+mod fixed_payment_tests;
+mod depreciation_tests;
+mod consolidation_tests;
+mod idr_tests;
+mod auto_tests;
+mod timeline_tests;
+mod transform_tests;
+mod events_tests;
+mod floating_tests;
+mod rate_cap_tests;
+mod accrual_tests;
+mod summary_tests;
+mod columnar_tests;
+mod batch_tests;
+mod sensitivity_tests;
+#[cfg(feature = "rand")]
+mod monte_carlo_tests;
+mod fees_tests;
+mod fx_tests;
+#[cfg(feature = "schema")]
+mod schema_tests;
+mod statement_tests;
+mod period_cap_tests;
+mod direct_debit_tests;
+mod personal_finance_tests;
+#[cfg(feature = "i18n")]
+mod i18n_tests;
+mod state_tests;
+mod sink_tests;
+#[cfg(feature = "sql")]
+mod sql_tests;
+mod waterfall_tests;
+mod explain_tests;
+mod milestone_tests;
+mod date_tests;
+mod ics_tests;
+mod progress_tests;
+mod effective_cost_tests;
+mod level_tests;
+
+// Amounts come out rounded to the cent, so two within one are the same figure
+const FLOAT_PRECISION: f64 = 0.01;
+
+fn assert_float_eq(a: f64, b: f64) {
+    assert_float_eq_within(a, b, FLOAT_PRECISION);
+}
+
+fn assert_float_eq_within(a: f64, b: f64, precision: f64) {
+    assert!((a - b).abs() < precision, "Expected {}, got {}", b, a);
+}
@@ -0,0 +1,94 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, ModificationTreatment, PaymentFrequency, Rate, RatePeriod};
+
+fn loan() -> Amortization {
+    Amortization::new(200_000.0, 6.0, 360, NaiveDate::from_ymd_opt(2025, 1, 1)).unwrap()
+}
+
+#[test]
+fn test_recast_keeps_remaining_term() {
+    let loan = loan();
+    let recast = loan.recast_with_principal(60, 20_000.0).unwrap();
+
+    assert_eq!(recast.schedule.len(), 300);
+    assert!((recast.balance - (loan.schedule[59].remaining_balance - 20_000.0)).abs() < 0.01);
+    assert!((recast.periodic_payment - 1_070.24).abs() < 0.01);
+    assert_eq!(recast.schedule[0].installment_number, 61);
+    assert_eq!(recast.schedule[0].date, loan.schedule[60].date);
+    assert_eq!(recast.end_date, loan.end_date);
+}
+
+#[test]
+fn test_recast_without_paydown_matches_original() {
+    let loan = loan();
+    let recast = loan.recast_at(120).unwrap();
+
+    assert!((recast.periodic_payment - loan.periodic_payment).abs() < 0.02);
+    assert!((recast.schedule[0].interest - loan.schedule[120].interest).abs() < 0.01);
+    assert_eq!(recast.schedule.last().unwrap().installment_number, 360);
+}
+
+#[test]
+fn test_recast_carries_later_rate_resets() {
+    let loan = Amortization::new_adjustable(
+        200_000.0,
+        vec![RatePeriod::new(1, 4.0), RatePeriod::new(61, 6.0), RatePeriod::new(121, 7.0)],
+        360,
+        None,
+    ).unwrap();
+    let recast = loan.recast_at(90).unwrap();
+
+    assert_eq!(recast.rate_schedule, vec![RatePeriod::new(1, 6.0), RatePeriod::new(31, 7.0)]);
+    assert!((recast.schedule[30].interest - loan.schedule[120].interest).abs() < 1.0);
+}
+
+#[test]
+fn test_recast_period_validation() {
+    let loan = loan();
+    assert!(matches!(loan.recast_at(0), Err(AmortizationError::PeriodOutOfRange(0))));
+    assert!(matches!(loan.recast_at(360), Err(AmortizationError::PeriodOutOfRange(360))));
+    assert!(matches!(loan.recast_with_principal(12, -1.0), Err(AmortizationError::InvalidExtraPayment(_))));
+
+    let weekly = Amortization::builder()
+        .principal(50_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(60)
+        .frequency(PaymentFrequency::Weekly)
+        .build()
+        .unwrap();
+    assert!(matches!(weekly.recast_at(1), Err(AmortizationError::UnsupportedFrequency(PaymentFrequency::Weekly))));
+}
+
+#[test]
+fn test_rate_modification_keep_term() {
+    let loan = loan();
+    let modified = loan.modify_rate(60, 8.0, ModificationTreatment::KeepTerm).unwrap();
+
+    assert_eq!(modified.schedule.len(), 360);
+    assert!((modified.new_payment - 1_436.98).abs() < 0.01);
+    assert_eq!(modified.schedule[59].installment_number, 60);
+    assert!((modified.schedule[59].interest - modified.schedule[59].beginning_balance * 0.08 / 12.0).abs() < 0.01);
+    assert!((modified.schedule[58].interest - loan.schedule[58].interest).abs() < 1e-9);
+    assert!(modified.interest_change > 0.0);
+    assert_eq!(modified.new_loan.end_date, loan.end_date);
+}
+
+#[test]
+fn test_rate_modification_keep_payment() {
+    let loan = loan();
+    let modified = loan.modify_rate(60, 4.0, ModificationTreatment::KeepPayment).unwrap();
+
+    assert_eq!(modified.schedule.len(), 59 + 220);
+    assert!(modified.schedule[59..modified.schedule.len() - 1].iter().all(|p| (p.installment_amount - 1_199.10).abs() < 0.01));
+    assert!(modified.schedule.last().unwrap().installment_amount < 1_199.10);
+    assert!(modified.schedule.last().unwrap().remaining_balance.abs() < 0.01);
+    assert!(modified.interest_change < 0.0);
+    assert!((modified.total_interest - modified.schedule.iter().map(|p| p.interest).sum::<f64>()).abs() < 1e-6);
+}
+
+#[test]
+fn test_rate_modification_payment_too_small() {
+    let result = loan().modify_rate(60, 8.0, ModificationTreatment::KeepPayment);
+    assert!(matches!(result, Err(AmortizationError::InsufficientPayment(_))));
+    assert!(matches!(loan().modify_rate(1, 5.0, ModificationTreatment::KeepTerm), Err(AmortizationError::PeriodOutOfRange(1))));
+}
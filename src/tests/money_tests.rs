@@ -0,0 +1,339 @@
+use std::cmp::Ordering;
+use chrono::NaiveDate;
+use crate::batch::quotes;
+use crate::json::JsonValue;
+use crate::sensitivity::payment_grid;
+use crate::{
+    Amortization, AmortizationError, Asset, AutoPurchase, BatchLoan, Bond, ConstructionLoan, Currency, Debt, Decimal, DepreciationMethod, Escrow, Fee,
+    FirstPeriodPolicy, GridValue, Heloc, HelocPhase, IdrPlan, Lease, Money, PayoffStrategy, Portfolio, PropertyValue, Rate, ReverseMortgage, RevolvingDebt,
+    RoundingMode, RoundingPolicy,
+};
+
+#[test]
+fn test_money_from_f64_rounding_modes() {
+    assert_eq!(Money::from_f64(2.675, Currency::USD).minor(), 268);
+    assert_eq!(Money::from_f64_with(2.675, Currency::USD, RoundingMode::HalfEven).minor(), 268);
+    assert_eq!(Money::from_f64_with(2.665, Currency::USD, RoundingMode::HalfEven).minor(), 266);
+    assert_eq!(Money::from_f64_with(-2.665, Currency::USD, RoundingMode::HalfEven).minor(), -266);
+    assert_eq!(Money::from_f64_with(-2.665, Currency::USD, RoundingMode::HalfUp).minor(), -267);
+    assert_eq!(Money::from_f64(1234.5, Currency::JPY).minor(), 1235);
+}
+
+#[test]
+fn test_money_from_whole_minor_units() {
+    assert_eq!(Money::from_f64(1234.56, Currency::USD).minor(), 123_456);
+    assert_eq!(Money::from_f64_with(-0.07, Currency::USD, RoundingMode::HalfEven).minor(), -7);
+    assert_eq!(Money::from_f64(0.1 + 0.2, Currency::USD).minor(), 30);
+    assert_eq!(Money::from_f64(1e15 + 0.5, Currency::USD).minor(), 100_000_000_000_000_050);
+}
+
+#[test]
+fn test_money_arithmetic_and_display() {
+    let a = Money::from_f64(0.1, Currency::USD);
+    let b = Money::from_f64(0.2, Currency::USD);
+
+    assert_eq!(a + b, Money::from_f64(0.3, Currency::USD));
+    assert_eq!((a - b).to_string(), "-0.10 USD");
+    assert_eq!(Money::from_minor(123_456, Currency::EUR).to_string(), "1234.56 EUR");
+    assert_eq!(Money::from_minor(500, Currency::JPY).to_string(), "500 JPY");
+    assert!(a.checked_add(Money::from_minor(1, Currency::GBP)).is_none());
+}
+
+#[test]
+fn test_money_compares_only_within_a_currency() {
+    let (five, seven) = (Money::from_minor(500, Currency::USD), Money::from_minor(700, Currency::USD));
+    assert_eq!(five.checked_cmp(&seven), Some(Ordering::Less));
+    assert_eq!(seven.checked_cmp(&five), Some(Ordering::Greater));
+    assert_eq!(five.checked_cmp(&Money::from_f64(5.0, Currency::USD)), Some(Ordering::Equal));
+    // 500 yen is a far smaller sum than $7, so their minor units can't be compared
+    assert_eq!(Money::from_minor(500, Currency::JPY).checked_cmp(&seven), None);
+    assert_eq!(Money::from_minor(700, Currency::JPY).checked_cmp(&five), None);
+}
+
+#[test]
+fn test_money_total_never_drifts() {
+    let cents = Money::total(Currency::USD, (0..1_000).map(|_| Money::from_f64(0.1, Currency::USD)));
+    assert_eq!(cents.minor(), 10_000);
+    assert_eq!(Money::total(Currency::USD, []), Money::zero(Currency::USD));
+}
+
+#[test]
+fn test_loan_totals_are_whole_cents() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let expected = Money::total(Currency::USD, loan.schedule.iter().map(|p| Money::from_f64(p.installment_amount, Currency::USD)));
+
+    assert_eq!(loan.total_payment_money(), expected);
+    assert_eq!(loan.checked_total_payment_money(), Some(expected));
+    assert_eq!(loan.checked_total_interest_money(), Some(loan.total_interest_money()));
+    assert_eq!(loan.total_interest_money(), expected - Money::from_f64(200_000.0, Currency::USD));
+    assert_eq!(loan.total_payment, expected.to_f64());
+}
+
+#[test]
+fn test_money_schedule_uses_configured_currency() {
+    let loan = Amortization::builder()
+        .principal(1_000_000.0)
+        .rate(Rate::percent(2.0))
+        .term_months(12)
+        .currency(Currency::JPY)
+        .rounding(RoundingMode::HalfEven)
+        .build()
+        .unwrap();
+    let first = loan.money_schedule()[0];
+
+    assert_eq!(first.installment_amount.currency(), Currency::JPY);
+    assert_eq!(first.interest.minor(), 1_667);
+    assert_eq!(loan.total_interest_money().currency().code(), "JPY");
+}
+
+#[test]
+fn test_installments_round_to_the_currency_minor_units() {
+    for rounding in [RoundingPolicy::full_precision(RoundingMode::HalfUp), RoundingPolicy::per_period(RoundingMode::HalfUp)] {
+        let loan = Amortization::builder()
+            .principal(1_000_000.0)
+            .rate(Rate::percent(3.0))
+            .term_months(36)
+            .currency(Currency::JPY)
+            .rounding(rounding)
+            .build()
+            .unwrap();
+        assert_eq!(loan.periodic_payment, 29_081.0);
+        assert!(loan.schedule[..35].iter().all(|p| p.installment_amount == 29_081.0));
+        let rows: f64 = loan.schedule.iter().map(|p| p.installment_amount).sum();
+        if rounding.per_period {
+            // Whole yen throughout, so the rows add up to the total exactly
+            assert!(loan.schedule.iter().all(|p| p.interest.fract() == 0.0 && p.installment_amount.fract() == 0.0));
+            assert_eq!(rows, loan.total_payment);
+        } else {
+            assert!((rows - loan.total_payment).abs() < 1.0, "{} != {}", rows, loan.total_payment);
+        }
+    }
+}
+
+#[test]
+fn test_amounts_beyond_minor_units_are_errors() {
+    assert_eq!(Currency::try_new(*b"XXX", 25), None);
+    assert_eq!(Currency::try_new(*b"XXX", 4).map(|c| c.minor_units()), Some(4));
+    assert_eq!(Money::from_f64(1e300, Currency::USD).minor(), i64::MAX);
+    assert_eq!(Money::checked_from_f64_with(1e300, Currency::USD, RoundingMode::HalfUp), None);
+    assert_eq!(Money::checked_total(Currency::USD, [Money::from_minor(i64::MAX, Currency::USD), Money::from_minor(1, Currency::USD)]), None);
+    let most_negative = Money::from_f64(-1e300, Currency::USD);
+    assert_eq!(most_negative.minor(), i64::MIN);
+    assert_eq!(most_negative.checked_neg(), None);
+    assert_eq!(most_negative.checked_sub(Money::from_minor(1, Currency::USD)), None);
+    assert_eq!(Money::zero(Currency::USD).checked_sub(most_negative), None);
+    assert_eq!(Money::from_minor(5, Currency::USD).checked_sub(Money::from_minor(7, Currency::USD)), Some(Money::from_minor(-2, Currency::USD)));
+
+    for principal in [5e16, 1e17, 1e300] {
+        assert!(matches!(Amortization::new(principal, 5.0, 360, None), Err(AmortizationError::Overflow(_))));
+    }
+}
+
+#[test]
+#[should_panic(expected = "Money negation overflowed")]
+fn test_negating_the_most_negative_money_panics() {
+    let _ = -Money::from_f64(-1e300, Currency::USD);
+}
+
+#[test]
+fn test_decimal_half_even_rounding() {
+    let value: Decimal = "0.125".parse().unwrap();
+
+    assert_eq!(value.round_dp_with(2, RoundingMode::HalfEven).to_string(), "0.12");
+    assert_eq!(value.round_dp_with(2, RoundingMode::HalfUp).to_string(), "0.13");
+    assert_eq!(value.to_minor_units(2, RoundingMode::HalfEven), 12);
+}
+
+fn loan_with(principal: f64, apr: f64, rounding: RoundingPolicy) -> Amortization {
+    Amortization::builder()
+        .principal(principal)
+        .rate(Rate::percent(apr))
+        .term_months(12)
+        .rounding(rounding)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_full_precision_policy_carries_fractional_cents() {
+    let loan = loan_with(10_000.0, 7.0, RoundingPolicy::default());
+    assert!((loan.schedule[0].interest - 58.333_333).abs() < 1e-6);
+    assert_eq!(RoundingPolicy::from(RoundingMode::HalfEven), RoundingPolicy::full_precision(RoundingMode::HalfEven));
+
+    let loan = loan_with(10_000.0, 7.0, RoundingPolicy::per_period(RoundingMode::HalfUp));
+    for payment in &loan.schedule {
+        assert_eq!(payment.interest, (payment.interest * 100.0).round() / 100.0);
+    }
+    assert!(loan.schedule.last().unwrap().remaining_balance.abs() < 1e-9);
+}
+
+#[test]
+fn test_per_period_policy_tie_rule() {
+    // 1% of 1000.50 is exactly half a cent over 10.00
+    let half_up = loan_with(1_000.5, 12.0, RoundingPolicy::per_period(RoundingMode::HalfUp));
+    let half_even = loan_with(1_000.5, 12.0, RoundingPolicy::per_period(RoundingMode::HalfEven));
+    assert_eq!(half_up.schedule[0].interest, 10.01);
+    assert_eq!(half_even.schedule[0].interest, 10.0);
+    assert_eq!(half_up.periodic_payment, half_even.periodic_payment);
+}
+
+#[test]
+fn test_decimal_backend_follows_tie_rule() {
+    let config = loan_with(1_000.5, 12.0, RoundingPolicy::per_period(RoundingMode::HalfEven)).config;
+    let loan = Amortization::from_config_with_backend::<Decimal>(config).unwrap();
+    assert_eq!(loan.schedule[0].interest, 10.0);
+}
+
+#[test]
+fn test_truncate_drops_fractional_cents() {
+    assert_eq!(Money::from_f64_with(2.679, Currency::USD, RoundingMode::Truncate).minor(), 267);
+    assert_eq!(Money::from_f64_with(-2.679, Currency::USD, RoundingMode::Truncate).minor(), -267);
+    // Sums of cents a hair short of the next one in binary keep it
+    assert_eq!(Money::from_f64_with(0.1 + 0.2, Currency::USD, RoundingMode::Truncate).minor(), 30);
+    assert_eq!(Money::from_f64_with(287_478.299_999_999_93, Currency::USD, RoundingMode::Truncate).minor(), 28_747_830);
+
+    let value: Decimal = "0.129".parse().unwrap();
+    assert_eq!(value.round_dp_with(2, RoundingMode::Truncate).to_string(), "0.12");
+}
+
+#[test]
+fn test_truncate_is_exact_for_decimals() {
+    // One unit of the 12th decimal place short of a cent is exact in a Decimal, so it loses the cent
+    let value: Decimal = "1.229999999999".parse().unwrap();
+    assert_eq!(value.round_dp_with(2, RoundingMode::Truncate).to_string(), "1.22");
+    assert_eq!(value.to_minor_units(2, RoundingMode::Truncate), 122);
+    assert_eq!((-value).to_minor_units(2, RoundingMode::Truncate), -122);
+    assert_eq!(value.round_dp_with(2, RoundingMode::HalfUp).to_string(), "1.23");
+
+    // The same amount as an f64 is taken for binary error and keeps it
+    assert_eq!(Money::from_f64_with(1.229_999_999_999, Currency::USD, RoundingMode::Truncate).minor(), 123);
+}
+
+#[test]
+fn test_per_period_truncation_threads_through_schedule() {
+    // 1% of 1000.90 is 10.009
+    let half_up = loan_with(1_000.9, 12.0, RoundingPolicy::per_period(RoundingMode::HalfUp));
+    let truncated = loan_with(1_000.9, 12.0, RoundingPolicy::per_period(RoundingMode::Truncate));
+    assert_eq!(half_up.schedule[0].interest, 10.01);
+    assert_eq!(truncated.schedule[0].interest, 10.0);
+    assert_eq!(truncated.schedule[0].principal, truncated.schedule[0].installment_amount - 10.0);
+
+    for payment in &truncated.schedule {
+        let cents = Money::from_f64_with(payment.interest, Currency::USD, RoundingMode::Truncate);
+        assert_eq!(cents.to_f64(), payment.interest);
+    }
+    let interest = Money::total(Currency::USD, truncated.money_schedule().iter().map(|p| p.interest));
+    assert_eq!(truncated.total_interest_money(), interest);
+    assert!(truncated.total_interest < half_up.total_interest);
+    assert!(truncated.schedule.last().unwrap().remaining_balance.abs() < 1e-9);
+}
+
+#[test]
+fn test_escrow_fees_odd_days_and_payoff_round_to_the_currency_minor_units() {
+    let loan = Amortization::builder()
+        .principal(30_000_000.0)
+        .rate(Rate::percent(1.5))
+        .term_months(360)
+        .start_date(NaiveDate::from_ymd_opt(2025, 2, 1).unwrap())
+        .funding_date(NaiveDate::from_ymd_opt(2024, 12, 10).unwrap())
+        .first_period_policy(FirstPeriodPolicy::AddToFirstPayment)
+        .escrow(Escrow::new(40_000_000.0, 1_000_000.0, 1_000_000.0))
+        .fee(Fee::recurring("servicing", 100.5))
+        .currency(Currency::JPY)
+        .build()
+        .unwrap();
+    let whole = |amount: f64| amount.fract() == 0.0;
+
+    assert!(whole(loan.odd_days_interest()) && loan.odd_days_interest() > 0.0);
+    let first = &loan.schedule[0];
+    let escrow = first.escrow.unwrap();
+    assert_eq!((escrow.property_tax, escrow.insurance), (83_333.0, 83_333.0));
+    assert_eq!(first.fees, 101.0);
+    assert!(loan.schedule.iter().all(|p| whole(p.installment_amount)));
+
+    let quote = loan.payoff_quote(NaiveDate::from_ymd_opt(2026, 6, 17).unwrap()).unwrap().with_fee(2_500.5);
+    assert!([quote.principal, quote.accrued_interest, quote.per_diem, quote.fees, quote.total, quote.good_through(10)].into_iter().all(whole), "{:?}", quote);
+}
+
+fn whole(amounts: impl IntoIterator<Item = f64>) -> bool {
+    amounts.into_iter().all(|amount| amount.fract() == 0.0)
+}
+
+#[test]
+fn test_json_export_rounds_to_the_currency_minor_units() {
+    let loan = Amortization::builder()
+        .principal(3_333_333.0)
+        .rate(Rate::percent(7.3))
+        .term_months(24)
+        .currency(Currency::JPY)
+        .build()
+        .unwrap();
+    let json = loan.to_json_value();
+    let Some(JsonValue::Array(rows)) = json.get("schedule") else { panic!("no schedule") };
+    let numbers = rows.iter()
+        .flat_map(|row| ["beginning_balance", "installment_amount", "interest", "principal", "remaining_balance"].map(|key| row.get(key)))
+        .map(|value| match value {
+            Some(JsonValue::Number(number)) => *number,
+            other => panic!("not a number: {:?}", other),
+        });
+    assert!(whole(numbers));
+    assert_eq!(loan.summary().to_json_value().get("total_interest"), Some(&JsonValue::Number(loan.total_interest)));
+
+    let grid = payment_grid(3_333_333.0, &[7.3], &[24], Currency::JPY).unwrap();
+    assert!(whole(grid.payments.concat().into_iter().chain(grid.total_interest.concat())));
+    assert!(grid.to_table(GridValue::Payment).contains("149,696"), "{}", grid.to_table(GridValue::Payment));
+}
+
+#[test]
+fn test_standalone_calculators_round_to_the_currency_minor_units() {
+    let jpy = Currency::JPY;
+
+    let auto = AutoPurchase::new(3_333_333.0, 7.3, 60).with_sales_tax(8.25).with_currency(jpy).finance().unwrap();
+    assert!(whole([auto.sales_tax, auto.amount_financed]));
+    assert!(whole(auto.equity.iter().flat_map(|row| [row.vehicle_value, row.equity])));
+    assert!(whole(auto.loan.schedule.iter().map(|p| p.installment_amount)));
+
+    let bond = Bond::new(3_333_333.0, 5.3, 6.1, 10).with_currency(jpy).schedule().unwrap();
+    assert!(whole([bond.issue_price, bond.premium]));
+    assert!(whole(bond.rows.iter().flat_map(|row| [row.interest_expense, row.amortization, row.carrying_value])));
+
+    let idr = IdrPlan::new(3_333_333.0, 7.3, 5_555_555.0).with_standard_payment_cap().with_currency(jpy).project().unwrap();
+    assert!(whole(idr.months.iter().flat_map(|m| [m.payment, m.interest, m.principal, m.balance])));
+
+    let heloc = Heloc::new(5_000_000.0, 7.3, 12, 60).with_draw(1, 3_333_333.0).with_currency(jpy).schedule().unwrap();
+    let (draws, repayment): (Vec<_>, Vec<_>) = heloc.rows.iter().partition(|row| row.phase == HelocPhase::Draw);
+    assert!(whole(draws.iter().flat_map(|row| [row.interest, row.payment, row.balance])));
+    assert!(whole(repayment.iter().map(|row| row.payment)));
+
+    let card = RevolvingDebt::new(333_333.0, 19.9).with_minimum_payment(2.7, 2_500.0).with_currency(jpy).payoff().unwrap();
+    assert!(whole(card.schedule.iter().flat_map(|p| [p.interest, p.installment_amount, p.remaining_balance])));
+
+    let debts = vec![Debt::new("card", 333_333.0, 19.9, 10_000.0), Debt::new("car", 1_111_111.0, 7.3, 33_333.0)];
+    let plan = Portfolio::new(debts, 66_666.0).with_currency(jpy).plan(PayoffStrategy::Avalanche).unwrap();
+    assert!(whole(plan.schedule.iter().flat_map(|month| month.payments.iter().flat_map(|p| [p.payment.interest, p.payment.installment_amount, p.payment.remaining_balance]))));
+
+    let reverse = ReverseMortgage::new(3_333_333.0, 7.3, PropertyValue::new(55_555_555.0), 120)
+        .with_mortgage_insurance(0.5)
+        .with_currency(jpy)
+        .projection()
+        .unwrap();
+    assert!(whole(reverse.rows.iter().flat_map(|row| [row.interest, row.mortgage_insurance, row.balance, row.equity])));
+
+    let asset = Asset::new(3_333_333.0, 100_000.0, 7).with_method(DepreciationMethod::double_declining()).with_currency(jpy).schedule().unwrap();
+    assert!(whole(asset.rows.iter().flat_map(|row| [row.depreciation, row.ending_book_value])));
+
+    let lease = Lease::new(33_333.0, 7.3, 37).with_currency(jpy).schedule().unwrap();
+    assert!(whole([lease.initial_liability]));
+    assert!(whole(lease.rou_asset.iter().map(|row| row.amortization)));
+    assert!(whole(lease.liability.schedule.iter().map(|p| p.installment_amount)));
+
+    let construction = ConstructionLoan::new(7.3, 6, 360).with_draw(1, 1_111_111.0).with_draw(4, 2_222_222.0).with_currency(jpy).schedule().unwrap();
+    assert!(whole(construction.draw_phase.iter().map(|row| row.interest)));
+    assert!(whole(construction.permanent.schedule.iter().map(|p| p.installment_amount)));
+
+    assert_eq!(Amortization::solve_principal_in(7.3, 24, 149_696.0, jpy).unwrap().fract(), 0.0);
+
+    let quote = quotes(&[BatchLoan::new(3_333_333.0, 7.3, 24).with_currency(jpy)]).unwrap()[0];
+    let loan = Amortization::builder().principal(3_333_333.0).rate(Rate::percent(7.3)).term_months(24).currency(jpy).build().unwrap();
+    assert_eq!((quote.payment, quote.final_payment, quote.total_payment), (loan.periodic_payment, loan.final_payment_amount, loan.total_payment));
+}
@@ -0,0 +1,100 @@
+use crate::{Amortization, AmortizationError, FloatingRate, ForwardCurve, MonteCarlo, RandomSource, RateCaps, RateModel, RatePeriod, SeededRng};
+use super::assert_float_eq;
+
+fn vasicek() -> RateModel {
+    RateModel::Vasicek { mean: 6.0, speed: 0.3, volatility: 1.5, min: 1.0, max: 15.0 }
+}
+
+// A 5/1 ARM at 5% with 2/2/5 caps
+fn arm() -> Amortization {
+    Amortization::builder()
+        .principal(300_000.0)
+        .rate_schedule(vec![RatePeriod::new(1, 5.0), RatePeriod::new(61, 5.0)])
+        .rate_caps(RateCaps::structure(2.0, 2.0, 5.0))
+        .term_months(360)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_seeded_rng_is_reproducible() {
+    let mut first = SeededRng::new(42);
+    let mut second = SeededRng::new(42);
+    let draws: Vec<f64> = (0..1000).map(|_| first.next_f64()).collect();
+    assert!(draws.iter().all(|draw| (0.0..1.0).contains(draw)));
+    assert_eq!(draws, (0..1000).map(|_| second.next_f64()).collect::<Vec<_>>());
+    let mean = draws.iter().sum::<f64>() / 1000.0;
+    assert!((mean - 0.5).abs() < 0.05);
+}
+
+#[test]
+fn test_flat_paths_match_the_loan() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let still = RateModel::RandomWalk { step: 0.0, min: 0.0, max: 20.0 };
+    let result = MonteCarlo::new(still, 10).simulate(&loan).unwrap();
+    assert_eq!(result.paths, 10);
+    assert_eq!(result.total_interest.p5(), result.total_interest.p95());
+    // Within the last installment's rounding, which an adjustable schedule does not trim
+    assert!((result.total_interest.p50() - loan.total_interest).abs() < 1.0);
+    assert!((result.peak_payment.p50() - loan.periodic_payment).abs() < 0.05);
+}
+
+#[test]
+fn test_distribution_of_arm_outcomes() {
+    let loan = arm();
+    let simulation = MonteCarlo::new(vasicek(), 200).with_seed(7);
+    let result = simulation.simulate(&loan).unwrap();
+    let interest = &result.total_interest;
+    assert_eq!(interest.values.len(), 200);
+    assert!(interest.p5() < interest.p50() && interest.p50() < interest.p95());
+
+    // The caps bound every path: never above 10% for the last 25 years
+    let capped = Amortization::new_adjustable(300_000.0, vec![RatePeriod::new(1, 5.0), RatePeriod::new(61, 7.0), RatePeriod::new(73, 9.0), RatePeriod::new(85, 10.0)], 360, None).unwrap();
+    assert!(result.peak_payment.p95() <= capped.schedule.iter().map(|p| p.installment_amount).fold(0.0, f64::max) + 0.01);
+    // Nor below the payment of the fixed years
+    assert!(result.peak_payment.values[0] >= loan.schedule[0].installment_amount - 0.01);
+
+    assert_eq!(simulation.simulate(&loan).unwrap(), result);
+    assert_ne!(simulation.clone().with_seed(8).simulate(&loan).unwrap(), result);
+}
+
+#[test]
+fn test_floating_rate_paths() {
+    let floating = FloatingRate::new(ForwardCurve::flat(4.0), 2.5, 12).with_fixed_period(60, 6.0).with_caps(2.0, 2.0, 5.0);
+    let loan = Amortization::builder()
+        .principal(300_000.0)
+        .floating_rate(floating.clone())
+        .term_months(360)
+        .build()
+        .unwrap();
+    let walk = RateModel::RandomWalk { step: 0.5, min: 0.0, max: 12.0 };
+    let result = MonteCarlo::new(walk, 100).with_seed(1).simulate_floating(&loan, &floating).unwrap();
+    // The payment never falls below the payment on the initial rate in the first five years
+    assert!(result.peak_payment.values[0] >= loan.schedule[0].installment_amount - 0.01);
+    assert!(result.total_interest.p5() <= result.total_interest.p95());
+}
+
+#[test]
+fn test_percentiles() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let walk = RateModel::RandomWalk { step: 0.25, min: 0.0, max: 20.0 };
+    let result = MonteCarlo::new(walk, 5).simulate(&loan).unwrap();
+    let values = &result.total_interest.values;
+    assert!(values.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert_eq!(result.total_interest.percentile(0.0), values[0]);
+    assert_eq!(result.total_interest.percentile(100.0), values[4]);
+    assert_eq!(result.total_interest.p50(), values[2]);
+    assert_float_eq(result.total_interest.percentile(25.0), values[1]);
+    assert_float_eq(result.total_interest.p95(), values[3] + (values[4] - values[3]) * 0.8);
+}
+
+#[test]
+fn test_invalid_simulations() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    assert!(matches!(MonteCarlo::new(vasicek(), 0).simulate(&loan), Err(AmortizationError::InvalidConfig(_))));
+    assert!(matches!(MonteCarlo::new(vasicek(), 1).with_reset_every(0).simulate(&loan), Err(AmortizationError::InvalidRateSchedule(_))));
+    let negative = RateModel::Vasicek { mean: 6.0, speed: 0.3, volatility: -1.0, min: 1.0, max: 15.0 };
+    assert!(matches!(MonteCarlo::new(negative, 1).simulate(&loan), Err(AmortizationError::InvalidRateSchedule(_))));
+    let inverted = RateModel::RandomWalk { step: 0.25, min: 8.0, max: 2.0 };
+    assert!(matches!(MonteCarlo::new(inverted, 1).simulate(&loan), Err(AmortizationError::InvalidRateSchedule(_))));
+}
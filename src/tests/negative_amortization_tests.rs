@@ -0,0 +1,90 @@
+use crate::{Amortization, AmortizationError, CapitalizationFrequency, PaymentCap, Rate, RatePeriod};
+use super::assert_float_eq;
+
+fn capped_arm(cap: PaymentCap) -> Amortization {
+    Amortization::builder()
+        .principal(200_000.0)
+        .term_months(360)
+        .rate_schedule(vec![RatePeriod::new(1, 3.0), RatePeriod::new(13, 9.0)])
+        .payment_cap(cap)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_payment_cap_limits_reset_payment() {
+    let loan = capped_arm(PaymentCap::new(7.5));
+    let initial = loan.schedule[11].installment_amount;
+
+    assert_float_eq(loan.schedule[12].installment_amount, (initial * 1.075 * 100.0).round() / 100.0);
+}
+
+#[test]
+fn test_capped_payment_capitalizes_interest() {
+    let loan = capped_arm(PaymentCap::new(7.5));
+    let row = &loan.schedule[12];
+
+    assert!(row.interest > row.installment_amount);
+    assert_float_eq(row.principal, 0.0);
+    assert_float_eq(row.capitalized_interest, row.interest - row.installment_amount);
+    assert_float_eq(row.remaining_balance, row.beginning_balance + row.capitalized_interest);
+    assert!(loan.is_negatively_amortizing());
+    assert!(loan.peak_balance() > loan.balance);
+    assert!(loan.total_capitalized_interest() > 0.0);
+    assert_float_eq(loan.schedule.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_recast_when_balance_exceeds_limit() {
+    let loan = capped_arm(PaymentCap::new(7.5).with_recast_at(110.0));
+    let recast = loan.schedule.iter().position(|p| p.beginning_balance > 220_000.0).unwrap();
+
+    assert!(loan.peak_balance() < 220_000.0 + loan.schedule[recast].interest);
+    assert!(loan.schedule[recast..].iter().all(|p| p.capitalized_interest == 0.0));
+    assert_float_eq(loan.schedule.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_uncapped_loan_never_capitalizes() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+
+    assert!(!loan.is_negatively_amortizing());
+    assert_float_eq(loan.total_capitalized_interest(), 0.0);
+    assert_float_eq(loan.peak_balance(), 200_000.0);
+}
+
+#[test]
+fn test_invalid_payment_cap() {
+    let result = Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(360)
+        .payment_cap(PaymentCap::new(-1.0))
+        .build();
+
+    assert!(matches!(result, Err(AmortizationError::InvalidPaymentCap(_))));
+}
+
+#[test]
+fn test_unpaid_interest_capitalized_annually() {
+    let every_period = capped_arm(PaymentCap::new(7.5));
+    let annual = Amortization::builder()
+        .principal(200_000.0)
+        .term_months(360)
+        .rate_schedule(vec![RatePeriod::new(1, 3.0), RatePeriod::new(13, 9.0)])
+        .payment_cap(PaymentCap::new(7.5))
+        .capitalization(CapitalizationFrequency::Annually)
+        .build()
+        .unwrap();
+    let shortfall = every_period.schedule[12].capitalized_interest;
+
+    assert_float_eq(annual.schedule[12].capitalized_interest, 0.0);
+    assert_float_eq(annual.schedule[12].accrued_interest, shortfall);
+    assert_float_eq(annual.schedule[12].remaining_balance, annual.schedule[12].beginning_balance);
+    // Interest isn't charged on the uncapitalized shortfall until the end of the year
+    assert!(annual.schedule[13].interest < every_period.schedule[13].interest);
+    assert!(annual.schedule[23].capitalized_interest > 0.0);
+    assert_float_eq(annual.schedule[23].accrued_interest, 0.0);
+    assert!(annual.total_interest < every_period.total_interest);
+    assert_float_eq(annual.schedule.last().unwrap().remaining_balance, 0.0);
+}
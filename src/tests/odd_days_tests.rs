@@ -0,0 +1,127 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationBuilder, AmortizationError, FirstPeriodPolicy, OddDaysTreatment, Rate, UpfrontCosts};
+use super::assert_float_eq;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn builder() -> AmortizationBuilder {
+    Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .start_date(date(2025, 3, 1))
+}
+
+#[test]
+fn test_interim_interest_collected_at_closing() {
+    let plain = builder().build().unwrap();
+    let loan = builder().funding_date(date(2025, 1, 15)).build().unwrap();
+
+    // Jan 15 to Feb 1, the start of the first regular period
+    assert_eq!(loan.odd_days(), Some(17));
+    assert_float_eq(loan.odd_days_interest(), 279.45);
+    assert_float_eq(loan.closing_interest(), 279.45);
+    assert_float_eq(loan.schedule[0].installment_amount, plain.schedule[0].installment_amount);
+    assert_float_eq(loan.total_interest, plain.total_interest);
+}
+
+#[test]
+fn test_interim_interest_added_to_first_payment() {
+    let plain = builder().build().unwrap();
+    let loan = builder()
+        .funding_date(date(2025, 1, 15))
+        .odd_days_treatment(OddDaysTreatment::AddToFirstPayment)
+        .build()
+        .unwrap();
+    let first = &loan.schedule[0];
+
+    assert_float_eq(loan.closing_interest(), 0.0);
+    assert_float_eq(first.interest, 500.0 + 279.45);
+    assert_float_eq(first.principal, plain.schedule[0].principal);
+    assert_float_eq(first.installment_amount, plain.schedule[0].installment_amount + 279.45);
+    assert_float_eq(loan.schedule[1].installment_amount, plain.schedule[1].installment_amount);
+    assert_float_eq(loan.total_interest, plain.total_interest + 279.45);
+    assert!(loan.validate().is_valid());
+}
+
+#[test]
+fn test_short_first_period_prorates_interest() {
+    let loan = builder()
+        .funding_date(date(2025, 2, 10))
+        .odd_days_treatment(OddDaysTreatment::AddToFirstPayment)
+        .build()
+        .unwrap();
+
+    assert_eq!(loan.odd_days(), Some(-9));
+    assert_float_eq(loan.odd_days_interest(), -147.95);
+    assert_float_eq(loan.schedule[0].interest, 352.05);
+
+    let credited = builder().funding_date(date(2025, 2, 10)).build().unwrap();
+    assert_float_eq(credited.closing_interest(), -147.95);
+}
+
+#[test]
+fn test_invalid_funding_date() {
+    let late = builder().funding_date(date(2025, 3, 1)).build();
+    assert!(matches!(late, Err(AmortizationError::InvalidFundingDate(_))));
+
+    let undated = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .funding_date(date(2025, 1, 15))
+        .build();
+    assert!(matches!(undated, Err(AmortizationError::InvalidFundingDate(_))));
+}
+
+#[test]
+fn test_interim_interest_spread_over_term() {
+    let plain = builder().build().unwrap();
+    let loan = builder()
+        .funding_date(date(2025, 1, 15))
+        .first_period_policy(FirstPeriodPolicy::SpreadOverTerm)
+        .build()
+        .unwrap();
+
+    assert_float_eq(loan.closing_interest(), 0.0);
+    assert_float_eq(loan.interim_interest_in(1), 0.78);
+    assert_float_eq(loan.schedule[0].installment_amount, plain.schedule[0].installment_amount + 0.78);
+    assert_float_eq(loan.schedule[0].principal, plain.schedule[0].principal);
+    let carried: f64 = (1..=360).map(|n| loan.interim_interest_in(n)).sum();
+    assert_float_eq(carried, 279.45);
+    assert_float_eq(loan.total_interest, plain.total_interest + 279.45);
+}
+
+#[test]
+fn test_interim_interest_waived() {
+    let plain = builder().build().unwrap();
+    let loan = builder()
+        .funding_date(date(2025, 1, 15))
+        .first_period_policy(FirstPeriodPolicy::Waive)
+        .build()
+        .unwrap();
+
+    assert_float_eq(loan.odd_days_interest(), 279.45);
+    assert_float_eq(loan.closing_interest(), 0.0);
+    assert_eq!(loan.schedule, plain.schedule);
+}
+
+#[test]
+fn test_first_period_policy_in_effective_apr() {
+    let costs = UpfrontCosts { origination_fees: 1_000.0, ..UpfrontCosts::default() };
+    let apr = |policy| builder().funding_date(date(2025, 1, 15)).first_period_policy(policy).build().unwrap().effective_apr(&costs).unwrap();
+    let (collected, added, spread, waived) = (
+        apr(FirstPeriodPolicy::CollectAtClosing),
+        apr(FirstPeriodPolicy::AddToFirstPayment),
+        apr(FirstPeriodPolicy::SpreadOverTerm),
+        apr(FirstPeriodPolicy::Waive),
+    );
+
+    assert_float_eq(collected.amount_financed, 100_000.0 - 1_000.0 - 279.45);
+    assert_float_eq(collected.finance_charge, waived.finance_charge + 279.45);
+    assert_float_eq(added.amount_financed, waived.amount_financed);
+    assert!(waived.apr < spread.apr && spread.apr < added.apr);
+    assert!(waived.apr < collected.apr);
+}
@@ -0,0 +1,104 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, CalculatorConfig, PaymentFrequency, PenaltyStep, PrepaymentPenalty, PrepaymentTreatment, Rate, Refinance};
+use super::assert_float_eq;
+
+fn loan_with(penalty: PrepaymentPenalty) -> Amortization {
+    Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .start_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+        .prepayment_penalty(penalty)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_percent_of_prepaid_applies_inside_the_window() {
+    let loan = loan_with(PrepaymentPenalty::percent_of_prepaid(2.0, 36));
+    assert_eq!(loan.prepayment_penalty(0, 10_000.0), 200.0);
+    assert_eq!(loan.prepayment_penalty(35, 10_000.0), 200.0);
+    assert_eq!(loan.prepayment_penalty(36, 10_000.0), 0.0);
+
+    let unpenalized = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    assert_eq!(unpenalized.prepayment_penalty(0, 10_000.0), 0.0);
+}
+
+#[test]
+fn test_yearly_step_down() {
+    let loan = loan_with(PrepaymentPenalty::yearly(&[5.0, 4.0, 3.0, 2.0, 1.0], PaymentFrequency::Monthly));
+    let charged: Vec<f64> = [0, 12, 24, 36, 59, 60].iter().map(|&paid| loan.prepayment_penalty(paid, 1_000.0)).collect();
+    assert_eq!(charged, vec![50.0, 40.0, 30.0, 20.0, 10.0, 0.0]);
+    assert_eq!(
+        PrepaymentPenalty::yearly(&[3.0, 2.0], PaymentFrequency::Monthly),
+        PrepaymentPenalty::step_down(vec![PenaltyStep::new(12, 3.0), PenaltyStep::new(24, 2.0)])
+    );
+}
+
+#[test]
+fn test_yield_maintenance() {
+    // Reinvesting at the note rate loses the lender nothing
+    let at_note_rate = loan_with(PrepaymentPenalty::yield_maintenance(6.0));
+    assert_eq!(at_note_rate.prepayment_penalty(12, 50_000.0), 0.0);
+    let above_note_rate = loan_with(PrepaymentPenalty::yield_maintenance(8.0));
+    assert_eq!(above_note_rate.prepayment_penalty(12, 50_000.0), 0.0);
+
+    // At a zero treasury rate a full payoff compensates all the remaining interest
+    let zero_rate = loan_with(PrepaymentPenalty::yield_maintenance(0.0));
+    let remaining_interest: f64 = zero_rate.schedule[12..].iter().map(|p| p.interest).sum();
+    let balance = zero_rate.balance_after(12).unwrap();
+    assert_float_eq(zero_rate.prepayment_penalty(12, balance), remaining_interest);
+    assert_float_eq(zero_rate.prepayment_penalty(12, balance / 4.0), remaining_interest / 4.0);
+
+    let lower_rate = loan_with(PrepaymentPenalty::yield_maintenance(4.0));
+    let penalty = lower_rate.prepayment_penalty(12, balance);
+    assert!(penalty > 0.0 && penalty < remaining_interest);
+}
+
+#[test]
+fn test_penalty_in_payoff_quote_and_lump_sum() {
+    let loan = loan_with(PrepaymentPenalty::percent_of_prepaid(2.0, 36));
+    let quote = loan.payoff_quote(NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()).unwrap();
+    assert_float_eq(quote.prepayment_penalty, quote.principal * 0.02);
+    assert_float_eq(quote.total, quote.principal + quote.accrued_interest + quote.prepayment_penalty);
+    assert_float_eq(quote.clone().with_fee(25.0).total, quote.total + 25.0);
+
+    let scenario = loan.with_lumpsum(12, 20_000.0, PrepaymentTreatment::ReduceTerm).unwrap();
+    assert_eq!(scenario.prepayment_penalty, 400.0);
+    assert_float_eq(scenario.net_savings, scenario.interest_saved - 400.0);
+    assert_eq!(loan.with_lumpsum(48, 20_000.0, PrepaymentTreatment::ReduceTerm).unwrap().prepayment_penalty, 0.0);
+}
+
+#[test]
+fn test_refinance_counts_the_penalty_in_break_even() {
+    let existing = Amortization::builder()
+        .principal(300_000.0)
+        .rate(Rate::percent(7.0))
+        .term_months(360)
+        .prepayment_penalty(PrepaymentPenalty::percent_of_prepaid(2.0, 36))
+        .build()
+        .unwrap();
+    let payoff = Refinance::payoff_balance(&existing, 24).unwrap();
+    let analysis = Refinance::analyze(&existing, 24, CalculatorConfig::new(payoff, 5.5, 336, None), 4_000.0).unwrap();
+
+    assert_float_eq(analysis.prepayment_penalty, payoff * 0.02);
+    let upfront = 4_000.0 + analysis.prepayment_penalty;
+    assert_eq!(analysis.break_even_month, Some((upfront / analysis.monthly_savings).ceil() as u32));
+    assert_float_eq(analysis.net_savings, analysis.lifetime_interest_difference - upfront);
+}
+
+#[test]
+fn test_invalid_penalties_are_rejected() {
+    let build = |penalty| Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(120)
+        .prepayment_penalty(penalty)
+        .build();
+    assert!(matches!(build(PrepaymentPenalty::percent_of_prepaid(-1.0, 12)), Err(AmortizationError::InvalidPrepaymentPenalty(_))));
+    assert!(matches!(build(PrepaymentPenalty::yield_maintenance(f64::NAN)), Err(AmortizationError::InvalidPrepaymentPenalty(_))));
+    let out_of_order = PrepaymentPenalty::step_down(vec![PenaltyStep::new(24, 3.0), PenaltyStep::new(12, 2.0)]);
+    let error = build(out_of_order).unwrap_err();
+    assert_eq!(error.parameter(), Some("prepayment_penalty"));
+    assert_eq!(error.value(), Some(12.0));
+}
@@ -0,0 +1,64 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, ErrorKind, FixedPayment, Rate, SkipPayments, SkipTreatment, DEFAULT_MAX_PERIODS};
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_regular_loans_stay_under_the_cap() {
+    let loan = Amortization::new(300_000.0, 6.5, 360, None).unwrap();
+    assert_eq!(loan.config.max_periods, DEFAULT_MAX_PERIODS);
+    assert_eq!(loan.schedule.len(), 360);
+    let capped = Amortization::builder().principal(300_000.0).rate(Rate::percent(6.5)).term_months(360).max_periods(360).build();
+    assert!(capped.is_ok());
+}
+
+#[test]
+fn test_extended_term_that_never_ends() {
+    // Every installment for two centuries is skipped and pushed onto the end of the term
+    let skip = SkipPayments::new(SkipTreatment::ExtendTerm).between(date(2025, 1, 1), date(2225, 1, 1));
+    let result = Amortization::builder()
+        .principal(10_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(60)
+        .start_date(date(2025, 1, 1))
+        .skip_payments(skip)
+        .max_periods(240)
+        .build();
+    let Err(error) = result else { panic!("the schedule should not end") };
+    assert!(matches!(error, AmortizationError::NonAmortizing { max_periods: 240, stalled_at: Some(1), balance } if balance > 10_000.0));
+    assert_eq!(error.kind(), ErrorKind::InconsistentConfig);
+    assert_eq!(error.parameter(), Some("max_periods"));
+    assert!(error.to_string().contains("stopped declining at installment 1"));
+}
+
+#[test]
+fn test_negative_amortization_beyond_the_cap() {
+    let result = Amortization::builder()
+        .principal(50_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(1200)
+        .fixed_payment(FixedPayment::new(200.0).with_negative_amortization())
+        .max_periods(600)
+        .build();
+    assert!(matches!(result, Err(AmortizationError::NonAmortizing { stalled_at: Some(1), .. })));
+}
+
+#[test]
+fn test_payment_that_barely_covers_interest() {
+    // $500.50 against $500 of interest takes over a thousand months
+    let builder = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .fixed_payment(FixedPayment::new(500.5));
+    assert!(builder.clone().build().is_ok());
+    let result = builder.max_periods(600).build();
+    assert!(matches!(result, Err(AmortizationError::NonAmortizing { max_periods: 600, stalled_at: None, .. })));
+}
+
+#[test]
+fn test_zero_period_cap() {
+    let result = Amortization::builder().principal(1_000.0).rate(Rate::percent(5.0)).term_months(12).max_periods(0).build();
+    assert!(matches!(result, Err(AmortizationError::InvalidPeriods(0))));
+}
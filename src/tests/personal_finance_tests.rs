@@ -0,0 +1,61 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, Fee, FinanceAppOptions, Rate};
+
+fn loan() -> Amortization {
+    Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .start_date(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_qif_splits() {
+    let qif = loan().to_qif(&FinanceAppOptions::new().with_loan_account("Liabilities:Mortgage")).unwrap();
+    assert!(qif.starts_with("!Type:Bank\nD01/31/2025\nT-1199.10\nPLoan Payment\nMInstallment 1 of 360: interest 1000.00, principal 199.10\n"));
+    assert!(qif.contains("SInterest Exp\nEinterest\n$-1000.00\nS[Liabilities:Mortgage]\nEprincipal\n$-199.10\n^\n"));
+    assert_eq!(qif.matches("^\n").count(), 360);
+}
+
+#[test]
+fn test_qif_splits_sum_to_the_payment() {
+    let loan = Amortization::builder()
+        .principal(10_000.0)
+        .rate(Rate::percent(7.5))
+        .term_months(12)
+        .start_date(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+        .fee(Fee::recurring("servicing", 4.5))
+        .build()
+        .unwrap();
+    let qif = loan.to_qif(&FinanceAppOptions::default()).unwrap();
+    for entry in qif.trim_start_matches("!Type:Bank\n").split("^\n").filter(|entry| !entry.is_empty()) {
+        let cents = |line: &str| (line.parse::<f64>().unwrap() * 100.0).round() as i64;
+        let total = entry.lines().find_map(|line| line.strip_prefix('T')).map(cents).unwrap();
+        let splits: i64 = entry.lines().filter_map(|line| line.strip_prefix('$')).map(cents).sum();
+        assert_eq!(total, splits, "{}", entry);
+        assert!(entry.contains("SBank Charge\nEfees\n$-4.50\n"));
+    }
+}
+
+#[test]
+fn test_ofx_statement() {
+    let ofx = loan().to_ofx(&FinanceAppOptions::new().with_payee("First Bank & Trust").with_account_id("MTG-1")).unwrap();
+    assert!(ofx.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\"?>\n<?OFX OFXHEADER=\"200\" VERSION=\"220\""));
+    assert!(ofx.contains("<CURDEF>USD</CURDEF>"));
+    assert!(ofx.contains("<DTSTART>20250131</DTSTART>"));
+    assert!(ofx.contains(
+        "<STMTTRN><TRNTYPE>PAYMENT</TRNTYPE><DTPOSTED>20250131</DTPOSTED><TRNAMT>-1199.10</TRNAMT><FITID>MTG-1-1</FITID>\
+        <NAME>First Bank &amp; Trust</NAME><MEMO>Installment 1 of 360: interest 1000.00, principal 199.10</MEMO></STMTTRN>"
+    ));
+    assert_eq!(ofx.matches("<STMTTRN>").count(), 360);
+    assert!(ofx.ends_with("</OFX>\n"));
+}
+
+#[test]
+fn test_undated_schedule() {
+    let loan = Amortization::new(1_000.0, 5.0, 12, None).unwrap();
+    assert!(matches!(loan.to_qif(&FinanceAppOptions::default()), Err(AmortizationError::MissingParameter("start_date"))));
+    assert!(matches!(loan.to_ofx(&FinanceAppOptions::default()), Err(AmortizationError::MissingParameter("start_date"))));
+}
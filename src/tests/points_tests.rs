@@ -0,0 +1,50 @@
+use crate::{Amortization, AmortizationError, PointsOption};
+
+fn loan() -> Amortization {
+    Amortization::new(300_000.0, 7.0, 360, None).unwrap()
+}
+
+fn rate_sheet() -> Vec<PointsOption> {
+    vec![
+        PointsOption::new(0.0, 7.0),
+        PointsOption::new(1.0, 6.75),
+        PointsOption::new(2.0, 6.5),
+    ]
+}
+
+#[test]
+fn test_points_break_even() {
+    let recommendation = loan().optimize_points(&rate_sheet(), 36).unwrap();
+    let options = &recommendation.options;
+
+    assert_eq!(options[0].break_even_month, Some(0));
+    assert_eq!(options[1].break_even_month, Some(60));
+    assert_eq!(options[1].points_cost, 3_000.0);
+    assert!(options[2].periodic_payment < options[1].periodic_payment);
+    assert!(options[2].effective_apr > 6.5 && options[2].effective_apr < 7.0);
+    assert!(options[2].lifetime_cost < options[0].lifetime_cost);
+}
+
+#[test]
+fn test_recommendation_depends_on_holding_period() {
+    let short = loan().optimize_points(&rate_sheet(), 36).unwrap();
+    let long = loan().optimize_points(&rate_sheet(), 360).unwrap();
+
+    assert_eq!(short.recommended, 0);
+    assert_eq!(long.recommended, 2);
+    assert_eq!(long.best().option, PointsOption::new(2.0, 6.5));
+    // Held to maturity the holding cost is the lifetime cost
+    assert!((long.best().holding_cost - long.best().lifetime_cost).abs() < 0.01);
+}
+
+#[test]
+fn test_points_rejects_invalid_input() {
+    let loan = loan();
+
+    assert!(matches!(loan.optimize_points(&[], 36), Err(AmortizationError::MissingParameter(_))));
+    assert!(matches!(loan.optimize_points(&rate_sheet(), 0), Err(AmortizationError::InvalidPeriods(0))));
+    assert!(matches!(
+        loan.optimize_points(&[PointsOption::new(-1.0, 6.0)], 36),
+        Err(AmortizationError::InvalidUpfrontCost(_))
+    ));
+}
@@ -0,0 +1,59 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, Debt, PayoffStrategy, Portfolio};
+use super::assert_float_eq;
+
+fn portfolio() -> Portfolio {
+    Portfolio::new(vec![
+        Debt::new("card-a", 4_000.0, 24.0, 80.0),
+        Debt::new("card-b", 1_500.0, 15.0, 40.0),
+        Debt::new("car", 8_000.0, 6.0, 200.0),
+    ], 600.0)
+}
+
+#[test]
+fn test_snowball_pays_smallest_balance_first() {
+    let plan = portfolio().plan(PayoffStrategy::Snowball).unwrap();
+
+    assert_eq!(plan.payoff_months, vec![17, 5, 26]);
+    assert_eq!(plan.months(), 26);
+    assert_float_eq(plan.total_interest, 1_632.32);
+    assert_float_eq(plan.total_paid, 13_500.0 + plan.total_interest);
+}
+
+#[test]
+fn test_avalanche_pays_highest_rate_first() {
+    let plan = portfolio().plan(PayoffStrategy::Avalanche).unwrap();
+
+    assert_eq!(plan.payoff_months, vec![13, 16, 25]);
+    assert_float_eq(plan.total_interest, 1_491.91);
+}
+
+#[test]
+fn test_schedule_spends_the_budget_until_the_last_month() {
+    let plan = portfolio().plan(PayoffStrategy::Avalanche).unwrap();
+
+    for month in &plan.schedule[..plan.schedule.len() - 1] {
+        let paid: f64 = month.payments.iter().map(|p| p.payment.installment_amount).sum();
+        assert_float_eq(paid, 600.0);
+    }
+    assert_float_eq(plan.schedule.last().unwrap().remaining_balance, 0.0);
+    assert!(plan.schedule[13].payments.iter().all(|p| p.debt != 0));
+}
+
+#[test]
+fn test_compare_strategies() {
+    let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    let comparison = portfolio().with_start_date(start).compare_strategies().unwrap();
+
+    assert_float_eq(comparison.interest_saved_by_avalanche, 1_632.32 - 1_491.91);
+    assert_eq!(comparison.snowball.debt_free_date(), NaiveDate::from_ymd_opt(2027, 2, 1));
+    assert_eq!(comparison.avalanche.debt_free_date(), NaiveDate::from_ymd_opt(2027, 1, 1));
+}
+
+#[test]
+fn test_budget_below_minimums_rejected() {
+    let loan = Amortization::new(10_000.0, 5.0, 60, None).unwrap();
+    let portfolio = Portfolio::new(vec![Debt::from_loan("loan", &loan)], 100.0);
+
+    assert!(matches!(portfolio.plan(PayoffStrategy::Snowball), Err(AmortizationError::InsufficientPayment(_))));
+}
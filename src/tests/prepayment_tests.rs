@@ -0,0 +1,56 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, Prepayment, PrepaymentTreatment, Rate};
+
+fn loan() -> Amortization {
+    Amortization::new(200_000.0, 6.0, 360, NaiveDate::from_ymd_opt(2025, 1, 1)).unwrap()
+}
+
+#[test]
+fn test_lumpsum_reduce_term() {
+    let loan = loan();
+    let scenario = loan.with_lumpsum(60, 20_000.0, PrepaymentTreatment::ReduceTerm).unwrap();
+
+    assert_eq!(scenario.loan.schedule.len(), 297);
+    assert_eq!(scenario.periods_saved, 63);
+    assert_eq!(scenario.time_saved.to_string(), "5 years, 3 months");
+    assert!((scenario.new_payment - 1_199.10).abs() < 0.01);
+    assert!((scenario.interest_saved - 56_099.85).abs() < 1.0);
+    assert!((scenario.loan.schedule[59].principal - loan.schedule[59].principal - 20_000.0).abs() < 0.01);
+    assert_eq!(scenario.end_date, NaiveDate::from_ymd_opt(2049, 10, 1));
+}
+
+#[test]
+fn test_lumpsum_reduce_payment() {
+    let loan = loan();
+    let scenario = loan.with_lumpsum(60, 20_000.0, PrepaymentTreatment::ReducePayment).unwrap();
+
+    assert_eq!(scenario.loan.schedule.len(), 360);
+    assert_eq!(scenario.periods_saved, 0);
+    assert!((scenario.new_payment - 1_070.24).abs() < 0.01);
+    assert!((scenario.interest_saved - 18_658.19).abs() < 1.0);
+    assert_eq!(scenario.end_date, loan.end_date);
+    assert!(scenario.loan.schedule.last().unwrap().remaining_balance.abs() < 0.01);
+}
+
+#[test]
+fn test_lumpsum_options_compare() {
+    let options = loan().lumpsum_options(60, 20_000.0).unwrap();
+
+    assert!(options.reduce_term.interest_saved > options.reduce_payment.interest_saved);
+    assert!(options.reduce_term.end_date < options.reduce_payment.end_date);
+}
+
+#[test]
+fn test_prepayment_from_builder_and_validation() {
+    let built = Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .prepayment(Prepayment::new(60, 20_000.0, PrepaymentTreatment::ReduceTerm))
+        .build()
+        .unwrap();
+    assert_eq!(built.schedule.len(), 297);
+
+    assert!(matches!(loan().with_lumpsum(360, 1_000.0, PrepaymentTreatment::ReduceTerm), Err(AmortizationError::PeriodOutOfRange(360))));
+    assert!(matches!(loan().with_lumpsum(12, -5.0, PrepaymentTreatment::ReduceTerm), Err(AmortizationError::InvalidExtraPayment(_))));
+}
@@ -0,0 +1,74 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, Currency, Money, Rate, RoundingMode, RoundingPolicy};
+use super::assert_float_eq;
+
+fn loan() -> Amortization {
+    Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .start_date(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_progress_at_period() {
+    let loan = loan();
+    let progress = loan.progress_at(1).unwrap();
+    assert_eq!(progress.installments_paid, 1);
+    assert_eq!(progress.installments_remaining, 359);
+    assert_float_eq(progress.principal_repaid, 199.10);
+    assert_float_eq(progress.remaining_balance, 199_800.90);
+    assert_float_eq(progress.percent_principal_repaid, 0.09955);
+    assert_float_eq(progress.percent_term_elapsed, 100.0 / 360.0);
+    assert_float_eq(progress.interest_paid, 1_000.0);
+    // Each row's interest rounded to cents, as a statement lists them
+    let listed = Money::total(Currency::USD, loan.money_schedule().iter().map(|p| p.interest));
+    assert_eq!(loan.money(progress.interest_paid + progress.interest_remaining), listed);
+
+    let start = loan.progress_at(0).unwrap();
+    assert_eq!((start.principal_repaid, start.percent_term_elapsed, start.interest_paid), (0.0, 0.0, 0.0));
+    let end = loan.progress_at(360).unwrap();
+    assert!(end.is_complete());
+    assert_float_eq(end.percent_principal_repaid, 100.0);
+    assert_eq!(end.interest_remaining, 0.0);
+    assert!(matches!(loan.progress_at(361), Err(AmortizationError::PeriodOutOfRange(361))));
+}
+
+#[test]
+fn test_progress_at_date() {
+    let loan = loan();
+    let progress = loan.progress_at(NaiveDate::from_ymd_opt(2040, 1, 1).unwrap()).unwrap();
+    let by_period = loan.progress_at(180).unwrap();
+    assert_eq!(progress.installments_paid, 180);
+    assert_eq!((progress.remaining_balance, progress.interest_paid), (by_period.remaining_balance, by_period.interest_paid));
+    assert!(progress.percent_term_elapsed > 49.0 && progress.percent_term_elapsed < 51.0);
+
+    // Installments due on the date count as paid
+    assert_eq!(loan.progress_at(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()).unwrap().installments_paid, 1);
+    let before = loan.progress_at(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()).unwrap();
+    assert_eq!((before.installments_paid, before.percent_term_elapsed), (0, 0.0));
+    let after = loan.progress_at(NaiveDate::from_ymd_opt(2060, 1, 1).unwrap()).unwrap();
+    assert!(after.is_complete());
+    assert_eq!(after.percent_term_elapsed, 100.0);
+
+    let undated = Amortization::new(1_000.0, 5.0, 12, None).unwrap();
+    assert!(matches!(undated.progress_at(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()), Err(AmortizationError::MissingParameter("start_date"))));
+}
+
+#[test]
+fn test_progress_follows_rounding_mode() {
+    let with = |mode| Amortization::builder()
+        .principal(1_000.9)
+        .rate(Rate::percent(12.0))
+        .term_months(12)
+        .rounding(RoundingPolicy::full_precision(mode))
+        .build()
+        .unwrap()
+        .progress_at(1)
+        .unwrap();
+    // 10.009 of interest in the first installment
+    assert_eq!(with(RoundingMode::HalfUp).interest_paid, 10.01);
+    assert_eq!(with(RoundingMode::Truncate).interest_paid, 10.0);
+}
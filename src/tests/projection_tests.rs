@@ -0,0 +1,142 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, CalculatorConfig, CashFlowAssumptions, PaymentFrequency, Portfolio, PrepaymentSpeed};
+use super::assert_float_eq;
+
+fn loan() -> Amortization {
+    Amortization::new(200_000.0, 6.0, 360, NaiveDate::from_ymd_opt(2025, 1, 1)).unwrap()
+}
+
+#[test]
+fn test_speed_conversions() {
+    assert_float_eq(PrepaymentSpeed::Cpr(6.0).smm(), 0.5143);
+    assert_float_eq(PrepaymentSpeed::Smm(1.0).cpr(), 11.3615);
+    assert_float_eq(PrepaymentSpeed::Cpr(6.0).cpr(), 6.0);
+}
+
+#[test]
+fn test_zero_speed_matches_schedule() {
+    let loan = loan();
+    let projection = loan.project(&CashFlowAssumptions::new()).unwrap();
+
+    assert_eq!(projection.rows.len(), 360);
+    assert_float_eq(projection.total_prepaid_principal, 0.0);
+    assert_float_eq(projection.total_interest, loan.total_interest);
+    assert_float_eq(projection.total_cash_flow, loan.total_payment);
+}
+
+#[test]
+fn test_prepayments_split_out() {
+    let loan = loan();
+    let projection = loan.project(&CashFlowAssumptions::new().with_prepayment(PrepaymentSpeed::Smm(1.0))).unwrap();
+    let first = &projection.rows[0];
+    let second = &projection.rows[1];
+
+    assert_float_eq(first.interest, 1_000.0);
+    assert_float_eq(first.scheduled_principal, loan.schedule[0].principal);
+    assert_float_eq(first.prepaid_principal, loan.schedule[0].remaining_balance * 0.01);
+    assert_float_eq(first.remaining_balance, loan.schedule[0].remaining_balance * 0.99);
+    assert_float_eq(first.cash_flow, first.interest + first.scheduled_principal + first.prepaid_principal);
+
+    assert_float_eq(second.beginning_balance, first.remaining_balance);
+    assert_float_eq(second.scheduled_principal, loan.schedule[1].principal * 0.99);
+    assert_float_eq(second.interest, first.remaining_balance * 0.005);
+
+    assert_float_eq(projection.total_scheduled_principal + projection.total_prepaid_principal, 200_000.0);
+    assert!(projection.total_interest < loan.total_interest);
+    assert_float_eq(projection.rows.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_quarterly_loan_uses_period_rate() {
+    let quarterly = Amortization::from_config(CalculatorConfig {
+        frequency: PaymentFrequency::Quarterly,
+        ..CalculatorConfig::new(100_000.0, 5.0, 120, None)
+    }).unwrap();
+    let projection = quarterly.project(&CashFlowAssumptions::new().with_prepayment(PrepaymentSpeed::Cpr(10.0))).unwrap();
+    let first = &projection.rows[0];
+
+    let rate = 1.0 - 0.9_f64.powf(0.25);
+    assert_float_eq(first.prepaid_principal, quarterly.schedule[0].remaining_balance * rate);
+}
+
+#[test]
+fn test_pool_projection() {
+    let pool = Portfolio::generate(vec![
+        CalculatorConfig::new(100_000.0, 6.0, 360, NaiveDate::from_ymd_opt(2025, 1, 1)),
+        CalculatorConfig::new(50_000.0, 4.0, 120, NaiveDate::from_ymd_opt(2025, 1, 1)),
+    ]).unwrap();
+    let assumptions = CashFlowAssumptions::new().with_prepayment(PrepaymentSpeed::Cpr(8.0));
+    let projection = pool.project(&assumptions).unwrap();
+    let first = pool.loans[0].project(&assumptions).unwrap();
+    let second = pool.loans[1].project(&assumptions).unwrap();
+
+    assert_eq!(projection.rows.len(), 360);
+    assert_float_eq(projection.rows[0].beginning_balance, 150_000.0);
+    assert_float_eq(projection.rows[0].prepaid_principal, first.rows[0].prepaid_principal + second.rows[0].prepaid_principal);
+    assert_float_eq(projection.rows[200].remaining_balance, first.rows[200].remaining_balance);
+    assert_float_eq(projection.total_cash_flow, first.total_cash_flow + second.total_cash_flow);
+    assert_eq!(projection.rows[0].date, NaiveDate::from_ymd_opt(2025, 1, 1));
+}
+
+#[test]
+fn test_invalid_speed() {
+    let result = loan().project(&CashFlowAssumptions::new().with_prepayment(PrepaymentSpeed::Cpr(120.0)));
+    assert!(matches!(result, Err(AmortizationError::InvalidProjectionRate(_))));
+}
+
+#[test]
+fn test_defaults_and_losses() {
+    let loan = loan();
+    let assumptions = CashFlowAssumptions::new()
+        .with_prepayment(PrepaymentSpeed::Cpr(6.0))
+        .with_defaults(2.0, 40.0);
+    let projection = loan.project(&assumptions).unwrap();
+    let first = &projection.rows[0];
+
+    let default_rate = 1.0 - 0.98_f64.powf(1.0 / 12.0);
+    assert_float_eq(first.defaulted_principal, 200_000.0 * default_rate);
+    assert_float_eq(first.loss, first.defaulted_principal * 0.4);
+    assert_float_eq(first.recovery, first.defaulted_principal * 0.6);
+    assert_float_eq(first.interest, 1_000.0 * (1.0 - default_rate));
+    assert_float_eq(first.cash_flow, first.interest + first.scheduled_principal + first.prepaid_principal + first.recovery);
+
+    let returned = projection.total_scheduled_principal + projection.total_prepaid_principal + projection.total_defaults;
+    assert_float_eq(returned, 200_000.0);
+    assert_float_eq(projection.total_losses + projection.total_recoveries, projection.total_defaults);
+    assert_float_eq(projection.rows.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_weighted_average_life() {
+    let zero_interest = Amortization::new(1_200.0, 0.0, 2, None).unwrap();
+    let projection = zero_interest.project(&CashFlowAssumptions::new()).unwrap();
+    assert_float_eq(projection.weighted_average_life(), 0.125);
+
+    let loan = loan();
+    let scheduled = loan.project(&CashFlowAssumptions::new()).unwrap().weighted_average_life();
+    let prepaying = loan.project(&CashFlowAssumptions::new().with_prepayment(PrepaymentSpeed::Cpr(10.0))).unwrap();
+    assert!(scheduled > 19.0 && scheduled < 20.0);
+    assert!(prepaying.weighted_average_life() < scheduled / 2.0);
+}
+
+#[test]
+fn test_pool_losses() {
+    let pool = Portfolio::generate(vec![
+        CalculatorConfig::new(100_000.0, 6.0, 360, None),
+        CalculatorConfig::new(50_000.0, 4.0, 120, None),
+    ]).unwrap();
+    let assumptions = CashFlowAssumptions::new().with_defaults(3.0, 50.0);
+    let projection = pool.project(&assumptions).unwrap();
+    let first = pool.loans[0].project(&assumptions).unwrap();
+    let second = pool.loans[1].project(&assumptions).unwrap();
+
+    assert_float_eq(projection.total_losses, first.total_losses + second.total_losses);
+    assert_float_eq(projection.rows[0].recovery, first.rows[0].recovery + second.rows[0].recovery);
+    assert_eq!(projection.periods_per_year, 12);
+}
+
+#[test]
+fn test_invalid_severity() {
+    let result = loan().project(&CashFlowAssumptions::new().with_defaults(2.0, 150.0));
+    assert!(matches!(result, Err(AmortizationError::InvalidProjectionRate(_))));
+}
@@ -0,0 +1,66 @@
+use crate::{Amortization, AmortizationError, PropertyValue, Rate};
+use super::assert_float_eq;
+
+fn loan_with_property(appreciation: f64) -> Amortization {
+    Amortization::builder()
+        .principal(225_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .property_value(250_000.0, appreciation)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_property_value_appreciates() {
+    let property = PropertyValue::new(250_000.0).appreciating(3.0);
+
+    assert_float_eq(property.value_at(0, 12), 250_000.0);
+    assert_float_eq(property.value_at(12, 12), 257_500.0);
+    assert_float_eq(property.value_at(24, 12), 265_225.0);
+}
+
+#[test]
+fn test_running_ltv_in_each_payment() {
+    let loan = loan_with_property(0.0);
+    let first = &loan.schedule[0];
+
+    assert_float_eq(loan.initial_ltv().unwrap(), 90.0);
+    assert_float_eq(first.ltv.unwrap(), first.remaining_balance / 2_500.0);
+    assert_float_eq(loan.schedule.last().unwrap().ltv.unwrap(), 0.0);
+}
+
+#[test]
+fn test_ltv_crossings() {
+    let flat = loan_with_property(0.0);
+    let appreciating = loan_with_property(3.0);
+
+    let eighty = flat.ltv_crossing(80.0).unwrap();
+    let seventy_eight = flat.ltv_crossing(78.0).unwrap();
+    assert!(eighty < seventy_eight);
+    assert!(flat.schedule[eighty as usize - 1].remaining_balance <= 200_000.0);
+    assert!(flat.schedule[eighty as usize - 2].remaining_balance > 200_000.0);
+
+    assert!(appreciating.ltv_crossing(80.0).unwrap() < eighty);
+}
+
+#[test]
+fn test_no_ltv_without_property() {
+    let loan = Amortization::new(225_000.0, 6.0, 360, None).unwrap();
+
+    assert!(loan.schedule[0].ltv.is_none());
+    assert_eq!(loan.ltv_crossing(80.0), None);
+    assert_eq!(loan.initial_ltv(), None);
+}
+
+#[test]
+fn test_invalid_property_value() {
+    let result = Amortization::builder()
+        .principal(225_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .property_value(0.0, 3.0)
+        .build();
+
+    assert!(matches!(result, Err(AmortizationError::InvalidPropertyValue(_))));
+}
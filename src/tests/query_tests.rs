@@ -0,0 +1,168 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, DayCount, Rate, RatePeriod};
+use super::assert_float_eq;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn dated_loan() -> Amortization {
+    Amortization::new(100_000.0, 6.0, 120, Some(date(2024, 1, 1))).unwrap()
+}
+
+#[test]
+fn test_balance_after_periods() {
+    let loan = dated_loan();
+
+    assert_float_eq(loan.balance_after(0).unwrap(), 100_000.0);
+    assert_float_eq(loan.balance_after(2).unwrap(), loan.schedule[1].remaining_balance);
+    assert_float_eq(loan.balance_after(120).unwrap(), 0.0);
+    assert_eq!(loan.balance_after(121), None);
+}
+
+#[test]
+fn test_balance_at_date() {
+    let loan = dated_loan();
+
+    assert_float_eq(loan.balance_at(date(2023, 12, 31)).unwrap(), 100_000.0);
+    assert_float_eq(loan.balance_at(date(2024, 1, 1)).unwrap(), loan.schedule[0].remaining_balance);
+    assert_float_eq(loan.balance_at(date(2024, 3, 14)).unwrap(), loan.schedule[2].remaining_balance);
+    assert_float_eq(loan.balance_at(date(2040, 1, 1)).unwrap(), 0.0);
+}
+
+#[test]
+fn test_payoff_amount_adds_per_diem_interest() {
+    let loan = dated_loan();
+    let balance = loan.schedule[2].remaining_balance;
+    let per_diem = balance * 0.06 / 365.0;
+
+    assert_float_eq(loan.payoff_amount(date(2024, 3, 1)).unwrap(), balance);
+    assert_float_eq(loan.payoff_amount(date(2024, 3, 14)).unwrap(), balance + 13.0 * per_diem);
+    assert_float_eq(loan.payoff_amount(date(2040, 1, 1)).unwrap(), 0.0);
+}
+
+#[test]
+fn test_payoff_amount_uses_day_count() {
+    let loan = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(120)
+        .start_date(date(2024, 1, 1))
+        .day_count(DayCount::Actual360)
+        .build()
+        .unwrap();
+    let balance = loan.schedule[2].remaining_balance;
+
+    assert_float_eq(loan.payoff_amount(date(2024, 3, 11)).unwrap(), balance + balance * 0.06 * 10.0 / 360.0);
+}
+
+#[test]
+fn test_date_queries_need_start_date() {
+    let loan = Amortization::new(100_000.0, 6.0, 120, None).unwrap();
+
+    assert_eq!(loan.balance_at(date(2024, 1, 1)), None);
+    assert_eq!(loan.payoff_amount(date(2024, 1, 1)), None);
+    assert!(loan.balance_after(1).is_some());
+}
+
+#[test]
+fn test_schedule_slices_by_period() {
+    let loan = dated_loan();
+
+    assert_eq!(loan.nth_payment(1).unwrap().installment_number, 1);
+    assert_eq!(loan.nth_payment(120).unwrap().installment_number, 120);
+    assert!(loan.nth_payment(0).is_none());
+    assert!(loan.nth_payment(121).is_none());
+
+    let slice = loan.payments_in_periods(13, 24);
+    assert_eq!(slice.len(), 12);
+    assert_eq!(slice[0].installment_number, 13);
+    assert_eq!(loan.payments_in_periods(115, 200).len(), 6);
+    assert!(loan.payments_in_periods(30, 10).is_empty());
+}
+
+#[test]
+fn test_schedule_slices_by_date() {
+    let loan = dated_loan();
+
+    let slice = loan.schedule_between(date(2024, 3, 1), date(2024, 5, 31));
+    let dates: Vec<_> = slice.iter().map(|p| p.date.unwrap()).collect();
+    assert_eq!(dates, [date(2024, 3, 1), date(2024, 4, 1), date(2024, 5, 1)]);
+    assert!(loan.schedule_between(date(2024, 3, 2), date(2024, 3, 31)).is_empty());
+    assert!(loan.schedule_between(date(2024, 6, 1), date(2024, 3, 1)).is_empty());
+
+    assert_eq!(loan.payments_in_year(2024).len(), 12);
+    assert_eq!(loan.payments_in_year(2033).last().unwrap().installment_number, 120);
+    assert!(loan.payments_in_year(2034).is_empty());
+
+    assert_eq!(loan.next_payment_due(date(2024, 2, 1)).unwrap().installment_number, 2);
+    assert_eq!(loan.next_payment_due(date(2024, 2, 2)).unwrap().installment_number, 3);
+    assert!(loan.next_payment_due(date(2034, 1, 2)).is_none());
+}
+
+#[test]
+fn test_undated_schedule_slices_by_loan_year() {
+    let loan = Amortization::new(100_000.0, 6.0, 120, None).unwrap();
+
+    assert!(loan.schedule_between(date(2024, 1, 1), date(2030, 1, 1)).is_empty());
+    assert_eq!(loan.payments_in_year(2)[0].installment_number, 13);
+    assert_eq!(loan.payments_in_year(10).len(), 12);
+    assert!(loan.payments_in_year(0).is_empty());
+    assert!(loan.payments_in_year(11).is_empty());
+    assert!(loan.next_payment_due(date(2024, 1, 1)).is_none());
+}
+
+#[test]
+fn test_payoff_quote_itemises_the_letter() {
+    let loan = dated_loan();
+    let quote = loan.payoff_quote(date(2024, 3, 14)).unwrap();
+    let balance = loan.schedule[2].remaining_balance;
+
+    assert_eq!(quote.installments_paid, 3);
+    assert_float_eq(quote.principal, balance);
+    assert_float_eq(quote.per_diem, balance * 0.06 / 365.0);
+    assert_float_eq(quote.accrued_interest, 13.0 * balance * 0.06 / 365.0);
+    assert_eq!(quote.total, ((quote.principal + quote.accrued_interest) * 100.0).round() / 100.0);
+    assert_float_eq(quote.good_through(3), quote.total + 3.0 * quote.per_diem);
+
+    let with_fees = quote.clone().with_fee(30.0).with_fee(12.5);
+    assert_eq!(with_fees.fees, 42.5);
+    assert_float_eq(with_fees.total, quote.total + 42.5);
+}
+
+#[test]
+fn test_payoff_quote_after_maturity_and_undated() {
+    let quote = dated_loan().payoff_quote(date(2040, 1, 1)).unwrap();
+    assert_eq!((quote.principal, quote.accrued_interest, quote.per_diem, quote.total), (0.0, 0.0, 0.0, 0.0));
+
+    let undated = Amortization::new(100_000.0, 6.0, 120, None).unwrap();
+    assert!(matches!(undated.payoff_quote(date(2024, 1, 1)), Err(AmortizationError::MissingParameter("start_date"))));
+    assert_eq!(undated.accrued_interest(date(2024, 1, 1), date(2024, 2, 1)), None);
+}
+
+#[test]
+fn test_accrued_interest_steps_down_on_due_dates() {
+    let loan = dated_loan();
+    let within = loan.accrued_interest(date(2024, 1, 1), date(2024, 1, 11)).unwrap();
+    assert_float_eq(within, loan.schedule[0].remaining_balance * 0.06 * 10.0 / 365.0);
+
+    // Across the February due date the balance drops after 31 days
+    let across = loan.accrued_interest(date(2024, 1, 1), date(2024, 2, 11)).unwrap();
+    let expected = (loan.schedule[0].remaining_balance * 31.0 + loan.schedule[1].remaining_balance * 10.0) * 0.06 / 365.0;
+    assert_float_eq(across, expected);
+    assert_eq!(loan.accrued_interest(date(2024, 2, 11), date(2024, 1, 1)), Some(0.0));
+}
+
+#[test]
+fn test_accrued_interest_follows_rate_resets() {
+    let loan = Amortization::builder()
+        .principal(100_000.0)
+        .rate_schedule(vec![RatePeriod::new(1, 6.0), RatePeriod::new(3, 9.0)])
+        .term_months(120)
+        .start_date(date(2024, 1, 1))
+        .build()
+        .unwrap();
+    // Installment 3 accrues from the February due date at the reset rate
+    let accrued = loan.accrued_interest(date(2024, 2, 1), date(2024, 2, 11)).unwrap();
+    assert_float_eq(accrued, loan.schedule[1].remaining_balance * 0.09 * 10.0 / 365.0);
+}
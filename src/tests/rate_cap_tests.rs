@@ -0,0 +1,125 @@
+use crate::{Amortization, AmortizationError, FloatingRate, ForwardCurve, Rate, RateCaps, RateLimit, RatePath, RatePeriod, ScenarioAnalysis};
+
+fn uncapped() -> Vec<RatePeriod> {
+    vec![
+        RatePeriod::new(1, 5.0),
+        RatePeriod::new(61, 9.0),
+        RatePeriod::new(73, 9.0),
+        RatePeriod::new(85, 12.0),
+        RatePeriod::new(97, 2.0),
+        RatePeriod::new(109, 2.0),
+        RatePeriod::new(121, 2.0),
+        RatePeriod::new(133, 2.0),
+    ]
+}
+
+fn capped_arm() -> Amortization {
+    Amortization::builder()
+        .principal(300_000.0)
+        .rate(Rate::percent(5.0))
+        .rate_schedule(uncapped())
+        .rate_caps(RateCaps::structure(2.0, 2.0, 5.0).with_floor(3.0))
+        .term_months(360)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_caps_and_floor_at_each_reset() {
+    let caps = RateCaps::structure(2.0, 2.0, 5.0).with_floor(3.0);
+    let applied: Vec<(f64, Option<RateLimit>)> = caps.apply(&uncapped()).iter().map(|a| (a.apr, a.limit)).collect();
+    assert_eq!(applied, vec![
+        (5.0, None),
+        (7.0, Some(RateLimit::InitialCap)),
+        // Catches up with the uncapped rate once it is within the periodic cap
+        (9.0, None),
+        (10.0, Some(RateLimit::LifetimeCap)),
+        (8.0, Some(RateLimit::PeriodicCap)),
+        (6.0, Some(RateLimit::PeriodicCap)),
+        (4.0, Some(RateLimit::PeriodicCap)),
+        (3.0, Some(RateLimit::Floor)),
+    ]);
+    // Every reset reached changes the rate here, so none is left out
+    assert_eq!(caps.capped_schedule(&uncapped()).len(), 8);
+}
+
+#[test]
+fn test_schedule_charges_capped_rates() {
+    let loan = capped_arm();
+    let capped = RateCaps::structure(2.0, 2.0, 5.0).with_floor(3.0).capped_schedule(&uncapped());
+    assert_eq!(loan.rate_schedule, capped);
+    let expected = Amortization::new_adjustable(300_000.0, capped, 360, None).unwrap();
+    assert_eq!(loan.schedule, expected.schedule);
+
+    let adjustments = loan.rate_adjustments();
+    assert_eq!(adjustments[1].uncapped_apr, 9.0);
+    assert_eq!(adjustments[1].apr, 7.0);
+    assert_eq!(loan.binding_caps().len(), 6);
+
+    let period_rates = loan.period_rates();
+    assert_eq!(period_rates.len(), 360);
+    assert_eq!(period_rates[59].apr, 5.0);
+    assert_eq!(period_rates[60].period, 61);
+    assert_eq!(period_rates[71].limit, Some(RateLimit::InitialCap));
+    assert_eq!(period_rates[72].limit, None);
+    assert_eq!(period_rates[359].limit, Some(RateLimit::Floor));
+
+    // Without caps every reset is charged as scheduled
+    let uncapped_loan = Amortization::new_adjustable(300_000.0, uncapped(), 360, None).unwrap();
+    assert!(uncapped_loan.binding_caps().is_empty());
+    assert_eq!(uncapped_loan.period_rates()[60].apr, 9.0);
+}
+
+#[test]
+fn test_floating_rate_records_caps() {
+    let floating = FloatingRate::new(ForwardCurve::stepped(4.0, 1.0, 12, 360), 2.75, 12)
+        .with_fixed_period(60, 5.5)
+        .with_caps(2.0, 2.0, 5.0);
+    let loan = Amortization::builder()
+        .principal(300_000.0)
+        .floating_rate(floating.clone())
+        .term_months(360)
+        .build()
+        .unwrap();
+    assert_eq!(loan.rate_adjustments(), floating.adjustments(360).unwrap());
+    assert_eq!(loan.rate_schedule, floating.rate_schedule(360).unwrap());
+    assert_eq!(loan.binding_caps()[0].limit, Some(RateLimit::InitialCap));
+    assert_eq!(loan.binding_caps()[0].uncapped_apr, 11.75);
+}
+
+#[test]
+fn test_changes_keep_capped_rates() {
+    let loan = capped_arm();
+    let recast = loan.recast_at(70).unwrap();
+    assert_eq!(recast.schedule[0].interest, loan.schedule[70].interest);
+    assert!(recast.config.rate_caps.is_none());
+
+    // The caps hold the shocked rates too
+    let results = ScenarioAnalysis::new(loan)
+        .with_scenario("+300bp", RatePath::Shift { from_period: 61, basis_points: 300.0 })
+        .run()
+        .unwrap();
+    assert_eq!(results[1].loan.rate_schedule[1], RatePeriod::new(61, 7.0));
+    assert_eq!(results[1].loan.rate_schedule[2], RatePeriod::new(73, 9.0));
+    assert_eq!(results[1].loan.rate_schedule[3], RatePeriod::new(85, 10.0));
+}
+
+#[test]
+fn test_invalid_caps() {
+    let build = |caps| Amortization::builder()
+        .principal(300_000.0)
+        .rate(Rate::percent(5.0))
+        .rate_caps(caps)
+        .term_months(360)
+        .build();
+    assert!(matches!(build(RateCaps::new().with_periodic_cap(-1.0)), Err(AmortizationError::InvalidRateSchedule(_))));
+    assert!(matches!(
+        Amortization::builder()
+            .principal(300_000.0)
+            .floating_rate(FloatingRate::new(ForwardCurve::flat(4.0), 2.0, 12))
+            .rate_caps(RateCaps::new())
+            .term_months(360)
+            .build(),
+        Err(AmortizationError::ConflictingOptions("floating_rate", "rate_caps"))
+    ));
+}
@@ -0,0 +1,107 @@
+use crate::{
+    Amortization, Jurisdiction, PaymentFrequency, Rate, RateConvention, effective_to_nominal,
+    nominal_to_effective,
+};
+use super::assert_float_eq;
+
+#[test]
+fn test_rate_conversions_round_trip() {
+    assert_float_eq(nominal_to_effective(12.0, 12), 12.6825);
+    assert_float_eq(effective_to_nominal(12.6825, 12), 12.0);
+    assert_float_eq(nominal_to_effective(6.0, 1), 6.0);
+    assert_float_eq(effective_to_nominal(nominal_to_effective(5.0, 2), 2), 5.0);
+}
+
+#[test]
+fn test_default_convention_is_nominal() {
+    let default = Amortization::new(100_000.0, 6.0, 360, None).unwrap();
+    let nominal = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .rate_convention(RateConvention::NominalCompounded(PaymentFrequency::Monthly))
+        .build()
+        .unwrap();
+
+    assert_eq!(default.periodic_interest, 0.005);
+    assert_eq!(nominal.periodic_interest, default.periodic_interest);
+    assert_float_eq(nominal.periodic_payment, 599.55);
+}
+
+#[test]
+fn test_effective_annual_rate_schedule() {
+    let loan = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .rate_convention(RateConvention::EffectiveAnnual)
+        .build()
+        .unwrap();
+
+    let compounded_over_year = (1.0 + loan.periodic_interest).powi(12) - 1.0;
+    assert!((compounded_over_year - 0.06).abs() < 1e-12);
+    assert!(loan.periodic_payment < 599.55);
+    assert_float_eq(loan.schedule[0].interest, 100_000.0 * loan.periodic_interest);
+}
+
+#[test]
+fn test_canadian_semi_annual_compounding() {
+    let loan = Amortization::builder()
+        .principal(300_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(300)
+        .rate_convention(RateConvention::NominalCompounded(PaymentFrequency::SemiAnnually))
+        .build()
+        .unwrap();
+
+    let convention = loan.config.rate_convention;
+    assert_float_eq(convention.effective_annual_rate(5.0, PaymentFrequency::Monthly), 5.0625);
+    assert_float_eq(loan.periodic_interest * 1200.0, 4.9487);
+    assert_float_eq(loan.periodic_payment, 1744.81);
+}
+
+#[test]
+fn test_canadian_jurisdiction() {
+    let canadian = |principal: f64, apr: f64, term_months: u32| Amortization::builder()
+        .principal(principal)
+        .rate(Rate::percent(apr))
+        .term_months(term_months)
+        .jurisdiction(Jurisdiction::Canada)
+        .build()
+        .unwrap();
+
+    // Published broker calculator figures for 25 year amortizations
+    assert_float_eq(canadian(100_000.0, 6.0, 300).periodic_payment, 639.81);
+    assert_float_eq(canadian(300_000.0, 5.0, 300).periodic_payment, 1744.81);
+    assert_float_eq(canadian(500_000.0, 4.79, 300).periodic_payment, 2848.54);
+    assert_eq!(canadian(100_000.0, 6.0, 300).config.rate_convention, RateConvention::NominalCompounded(PaymentFrequency::SemiAnnually));
+}
+
+#[test]
+fn test_canadian_biweekly_payments() {
+    let loan = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(300)
+        .frequency(PaymentFrequency::BiWeekly)
+        .jurisdiction(Jurisdiction::Canada)
+        .build()
+        .unwrap();
+
+    let compounded_over_year = (1.0 + loan.periodic_interest).powi(26);
+    assert!((compounded_over_year - 1.03_f64.powi(2)).abs() < 1e-12);
+}
+
+#[test]
+fn test_us_jurisdiction_is_nominal() {
+    let loan = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .jurisdiction(Jurisdiction::UnitedStates)
+        .build()
+        .unwrap();
+
+    assert_float_eq(loan.periodic_interest, 0.005);
+    assert_float_eq(loan.periodic_payment, 599.55);
+}
@@ -0,0 +1,82 @@
+use crate::{Amortization, AmortizationError, RatePeriod};
+use super::assert_float_eq;
+
+fn five_one_arm() -> Amortization {
+    Amortization::new_adjustable(
+        200_000.0,
+        vec![
+            RatePeriod::new(1, 3.5),
+            RatePeriod::new(61, 5.5),
+            RatePeriod::new(73, 6.5),
+        ],
+        360,
+        None
+    ).unwrap()
+}
+
+#[test]
+fn test_adjustable_initial_payment_matches_fixed() {
+    let arm = five_one_arm();
+    let fixed = Amortization::new(200_000.0, 3.5, 360, None).unwrap();
+
+    assert!(arm.is_adjustable());
+    assert!(!fixed.is_adjustable());
+    assert_float_eq(arm.periodic_payment, fixed.periodic_payment);
+    assert_float_eq(arm.schedule[59].installment_amount, fixed.periodic_payment);
+}
+
+#[test]
+fn test_adjustable_payment_recalculated_at_reset() {
+    let arm = five_one_arm();
+    let balance_at_reset = arm.schedule[59].remaining_balance;
+    let reset_payment = &arm.schedule[60];
+    let expected = Amortization::new(balance_at_reset, 5.5, 300, None).unwrap().periodic_payment;
+
+    assert_float_eq(reset_payment.installment_amount, expected);
+    assert_float_eq(reset_payment.interest, balance_at_reset * 0.055 / 12.0);
+    assert!(arm.schedule[72].installment_amount > reset_payment.installment_amount);
+}
+
+#[test]
+fn test_adjustable_schedule_fully_amortizes() {
+    let arm = five_one_arm();
+    let total: f64 = arm.schedule.iter().map(|p| p.installment_amount).sum();
+
+    assert_eq!(arm.schedule.len(), 360);
+    assert_float_eq(arm.schedule.last().unwrap().remaining_balance, 0.0);
+    assert_float_eq(arm.total_payment, total);
+    assert_float_eq(arm.total_interest, total - 200_000.0);
+}
+
+#[test]
+fn test_adjustable_rejects_invalid_schedules() {
+    let empty = Amortization::new_adjustable(100_000.0, vec![], 360, None);
+    assert!(matches!(empty, Err(AmortizationError::InvalidRateSchedule(_))));
+
+    let late_start = Amortization::new_adjustable(100_000.0, vec![RatePeriod::new(2, 5.0)], 360, None);
+    assert!(matches!(late_start, Err(AmortizationError::InvalidRateSchedule(_))));
+
+    let unordered = Amortization::new_adjustable(
+        100_000.0,
+        vec![RatePeriod::new(1, 5.0), RatePeriod::new(60, 6.0), RatePeriod::new(30, 7.0)],
+        360,
+        None
+    );
+    assert!(matches!(unordered, Err(AmortizationError::InvalidRateSchedule(_))));
+
+    let past_term = Amortization::new_adjustable(
+        100_000.0,
+        vec![RatePeriod::new(1, 5.0), RatePeriod::new(400, 6.0)],
+        360,
+        None
+    );
+    assert!(matches!(past_term, Err(AmortizationError::InvalidRateSchedule(_))));
+
+    let negative = Amortization::new_adjustable(
+        100_000.0,
+        vec![RatePeriod::new(1, 5.0), RatePeriod::new(60, -1.0)],
+        360,
+        None
+    );
+    assert!(matches!(negative, Err(AmortizationError::InvalidInterestRate(_))));
+}
@@ -0,0 +1,75 @@
+use crate::{Amortization, AmortizationError, CalculatorConfig, Refinance};
+
+fn existing_loan() -> Amortization {
+    Amortization::new(300_000.0, 7.0, 360, None).unwrap()
+}
+
+#[test]
+fn test_refinance_to_lower_rate() {
+    let existing = existing_loan();
+    let payoff = Refinance::payoff_balance(&existing, 24).unwrap();
+    let analysis = Refinance::analyze(
+        &existing,
+        24,
+        CalculatorConfig::new(payoff, 5.5, 336, None),
+        4_000.0
+    ).unwrap();
+
+    assert_eq!(analysis.remaining_balance, payoff);
+    assert_eq!(analysis.current_payment, existing.periodic_payment);
+    assert!(analysis.monthly_savings > 0.0);
+    assert_eq!(
+        analysis.break_even_month,
+        Some((4_000.0 / analysis.monthly_savings).ceil() as u32)
+    );
+    assert!(analysis.lifetime_interest_difference > 0.0);
+    assert!((analysis.net_savings - (analysis.lifetime_interest_difference - 4_000.0)).abs() < 1e-9);
+}
+
+#[test]
+fn test_refinance_blended_schedule_is_continuous() {
+    let existing = existing_loan();
+    let payoff = Refinance::payoff_balance(&existing, 60).unwrap();
+    let analysis = Refinance::analyze(
+        &existing,
+        60,
+        CalculatorConfig::new(payoff, 5.0, 180, None),
+        0.0
+    ).unwrap();
+
+    let blended = &analysis.blended_schedule;
+    assert_eq!(blended.len(), 60 + 180);
+    for (i, payment) in blended.iter().enumerate() {
+        assert_eq!(payment.installment_number, i as u32 + 1);
+    }
+    assert!((blended[60].beginning_balance - blended[59].remaining_balance).abs() < 1e-6);
+    assert_eq!(blended.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_refinance_without_savings_never_breaks_even() {
+    let existing = existing_loan();
+    let payoff = Refinance::payoff_balance(&existing, 12).unwrap();
+    let analysis = Refinance::analyze(
+        &existing,
+        12,
+        CalculatorConfig::new(payoff, 7.5, 348, None),
+        2_000.0
+    ).unwrap();
+
+    assert!(analysis.monthly_savings < 0.0);
+    assert_eq!(analysis.break_even_month, None);
+    assert!(analysis.net_savings < 0.0);
+}
+
+#[test]
+fn test_refinance_validation() {
+    let existing = existing_loan();
+    let terms = CalculatorConfig::new(100_000.0, 5.0, 360, None);
+
+    let past_end = Refinance::analyze(&existing, 360, terms.clone(), 0.0);
+    assert!(matches!(past_end, Err(AmortizationError::PeriodOutOfRange(360))));
+
+    let negative_costs = Refinance::analyze(&existing, 12, terms, -1.0);
+    assert!(matches!(negative_costs, Err(AmortizationError::InvalidUpfrontCost(_))));
+}
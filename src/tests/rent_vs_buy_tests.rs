@@ -0,0 +1,83 @@
+use crate::{AmortizationError, RentVsBuy};
+use super::assert_float_eq;
+
+fn scenario() -> RentVsBuy {
+    RentVsBuy::new(400_000.0, 80_000.0, 6.0, 360, 2_000.0)
+        .with_closing_costs(8_000.0)
+        .with_property_tax_rate(1.0)
+        .with_insurance(1_500.0)
+        .with_maintenance_rate(1.0)
+        .with_appreciation(4.0)
+        .with_selling_cost_rate(6.0)
+        .with_rent_growth(3.0)
+        .with_investment_return(5.0)
+        .with_years(15)
+}
+
+#[test]
+fn test_first_year_costs() {
+    let analysis = scenario().compare().unwrap();
+    let first = &analysis.years[0];
+
+    assert_eq!(first.year, 1);
+    assert_eq!(analysis.years.len(), 15);
+    let mortgage: f64 = analysis.loan.schedule[..12].iter().map(|p| p.installment_amount).sum();
+    assert_float_eq(first.cumulative_ownership_cost, 88_000.0 + mortgage + 8_000.0 + 1_500.0);
+    assert_float_eq(first.cumulative_rent, 24_000.0);
+    assert_float_eq(first.home_value, 416_000.0);
+    assert_float_eq(first.loan_balance, analysis.loan.balance_after(12).unwrap());
+    assert_float_eq(first.home_equity, 416_000.0 * 0.94 - first.loan_balance);
+    assert_float_eq(first.net_buy_cost, first.cumulative_ownership_cost - first.home_equity);
+    assert_float_eq(first.net_rent_cost, 24_000.0 - 88_000.0 * 0.05);
+}
+
+#[test]
+fn test_rent_grows_yearly() {
+    let analysis = scenario().compare().unwrap();
+    assert_float_eq(analysis.years[1].cumulative_rent, 24_000.0 + 24_720.0);
+}
+
+#[test]
+fn test_break_even_year() {
+    let analysis = scenario().compare().unwrap();
+    let year = analysis.break_even_year.unwrap();
+
+    assert!(year > 1);
+    let index = year as usize - 1;
+    assert!(analysis.years[index].net_buy_cost <= analysis.years[index].net_rent_cost);
+    assert!(analysis.years[index - 1].net_buy_cost > analysis.years[index - 1].net_rent_cost);
+}
+
+#[test]
+fn test_cheap_rent_never_breaks_even() {
+    let analysis = RentVsBuy::new(400_000.0, 80_000.0, 6.0, 360, 500.0)
+        .with_investment_return(5.0)
+        .with_years(10)
+        .compare()
+        .unwrap();
+    assert_eq!(analysis.break_even_year, None);
+}
+
+#[test]
+fn test_horizon_past_loan_term() {
+    let analysis = RentVsBuy::new(200_000.0, 40_000.0, 5.0, 120, 1_500.0)
+        .with_years(12)
+        .compare()
+        .unwrap();
+    let last = analysis.years.last().unwrap();
+
+    assert_float_eq(last.loan_balance, 0.0);
+    assert_float_eq(last.cumulative_ownership_cost, analysis.years[9].cumulative_ownership_cost);
+}
+
+#[test]
+fn test_invalid_down_payment() {
+    let result = RentVsBuy::new(400_000.0, 400_000.0, 6.0, 360, 2_000.0).compare();
+    assert!(matches!(result, Err(AmortizationError::InvalidPaymentAmount(_))));
+}
+
+#[test]
+fn test_zero_year_horizon() {
+    let result = scenario().with_years(0).compare();
+    assert!(matches!(result, Err(AmortizationError::InvalidPeriods(0))));
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDate;
+use crate::{Amortization, Column, TableOptions};
+
+fn loan() -> Amortization {
+    Amortization::new(10_000.0, 6.0, 12, NaiveDate::from_ymd_opt(2025, 1, 1)).unwrap()
+}
+
+#[test]
+fn test_markdown_summary_and_schedule() {
+    let markdown = loan().to_markdown();
+    let lines: Vec<&str> = markdown.lines().collect();
+
+    assert_eq!(lines[0], "| Field | Value |");
+    assert_eq!(lines[2], "| Loan Amount | 10,000.00 |");
+    assert!(lines.contains(&"| Periodic Payment | 860.66 |"));
+    assert!(lines.contains(&"| # | Date | Payment | Interest | Principal | Balance |"));
+    assert!(lines.contains(&"| 1 | 2025-01-01 | 860.66 | 50.00 | 810.66 | 9,189.34 |"));
+    assert_eq!(lines.iter().filter(|l| l.starts_with("| ")).count(), 2 + 8 + 2 + 12);
+}
+
+#[test]
+fn test_markdown_custom_columns() {
+    let options = TableOptions::default()
+        .with_columns(vec![Column::Installment, Column::Interest])
+        .with_thousands_separator(false);
+    let markdown = loan().to_markdown_with(&options);
+
+    assert!(markdown.contains("| Loan Amount | 10000.00 |"));
+    assert!(markdown.contains("| # | Interest |\n| ---: | ---: |\n| 1 | 50.00 |"));
+}
+
+#[test]
+fn test_html_tables() {
+    let html = loan().to_html();
+
+    assert!(html.starts_with("<table class=\"amortization-summary\">"));
+    assert!(html.contains("<tr><th>Total Interest</th><td>327.97</td></tr>"));
+    assert!(html.contains("<thead>\n<tr><th>#</th><th>Date</th>"));
+    assert!(html.contains("<tr><td>12</td><td>2025-12-01</td>"));
+    assert_eq!(html.matches("<tr>").count(), 8 + 1 + 12);
+}
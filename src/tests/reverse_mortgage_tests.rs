@@ -0,0 +1,57 @@
+use chrono::NaiveDate;
+use crate::{AmortizationError, PropertyValue, ReverseMortgage};
+use super::assert_float_eq;
+
+#[test]
+fn test_balance_grows_by_accrued_interest() {
+    let projection = ReverseMortgage::new(100_000.0, 6.0, PropertyValue::new(400_000.0), 24)
+        .projection()
+        .unwrap();
+    let first = &projection.rows[0];
+
+    assert_float_eq(first.interest, 500.0);
+    assert_float_eq(first.balance, 100_500.0);
+    assert_float_eq(first.equity, 299_500.0);
+    // Monthly compounding over two years
+    assert!((projection.final_balance - 100_000.0 * 1.005_f64.powi(24)).abs() < 0.05);
+    assert_float_eq(projection.total_interest, projection.final_balance - 100_000.0);
+    assert_eq!(projection.crossover_month, None);
+}
+
+#[test]
+fn test_draws_and_mortgage_insurance() {
+    let projection = ReverseMortgage::new(0.0, 6.0, PropertyValue::new(300_000.0), 12)
+        .with_monthly_draw(1_000.0)
+        .with_mortgage_insurance(0.5)
+        .projection()
+        .unwrap();
+    let first = &projection.rows[0];
+
+    assert_float_eq(first.interest, 5.0);
+    assert_float_eq(first.mortgage_insurance, 0.42);
+    assert_float_eq(first.balance, 1_005.42);
+    assert_float_eq(projection.total_drawn, 12_000.0);
+}
+
+#[test]
+fn test_crossover_against_home_value() {
+    let projection = ReverseMortgage::new(150_000.0, 7.0, PropertyValue::new(300_000.0).appreciating(2.0), 360)
+        .with_start_date(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+        .projection()
+        .unwrap();
+    let month = projection.crossover_month.unwrap();
+    let row = &projection.rows[month as usize - 1];
+    let previous = &projection.rows[month as usize - 2];
+
+    assert!(row.balance >= row.home_value);
+    assert!(previous.balance < previous.home_value);
+    assert!(row.equity <= 0.0);
+    assert_eq!(projection.crossover_date, row.date);
+}
+
+#[test]
+fn test_invalid_reverse_mortgage() {
+    let result = ReverseMortgage::new(0.0, 6.0, PropertyValue::new(300_000.0), 12).projection();
+
+    assert!(matches!(result, Err(AmortizationError::InvalidLoanAmount(_))));
+}
@@ -0,0 +1,52 @@
+use chrono::NaiveDate;
+use crate::{AmortizationError, RevolvingDebt};
+use super::assert_float_eq;
+
+#[test]
+fn test_minimum_payment_payoff() {
+    let payoff = RevolvingDebt::new(5_000.0, 18.0).payoff().unwrap();
+
+    assert_eq!(payoff.months, 354);
+    assert_float_eq(payoff.total_interest, 11_688.80);
+    assert_float_eq(payoff.total_paid, 5_000.0 + payoff.total_interest);
+    assert!(payoff.schedule.iter().all(|p| p.installment_amount >= 25.0 || p.remaining_balance == 0.0));
+}
+
+#[test]
+fn test_fixed_payment_payoff_date() {
+    let start = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+    let payoff = RevolvingDebt::new(5_000.0, 18.0)
+        .with_fixed_payment(200.0)
+        .with_start_date(start)
+        .payoff()
+        .unwrap();
+
+    assert_eq!(payoff.months, 32);
+    assert_eq!(payoff.payoff_date, NaiveDate::from_ymd_opt(2027, 8, 15));
+    assert_float_eq(payoff.total_interest, 1_313.97);
+    assert_float_eq(payoff.schedule[0].interest, 75.0);
+    assert!(payoff.schedule.last().unwrap().installment_amount <= 200.0);
+}
+
+#[test]
+fn test_continued_spending_delays_payoff() {
+    let payoff = RevolvingDebt::new(5_000.0, 18.0)
+        .with_fixed_payment(200.0)
+        .with_monthly_spending(50.0)
+        .payoff()
+        .unwrap();
+
+    assert_eq!(payoff.months, 47);
+    assert_float_eq(payoff.total_interest, 1_983.61);
+    assert_float_eq(payoff.schedule[0].remaining_balance, 4_925.0);
+}
+
+#[test]
+fn test_payment_below_interest_never_pays_off() {
+    let debt = RevolvingDebt::new(5_000.0, 18.0).with_fixed_payment(75.0);
+    assert!(matches!(debt.payoff(), Err(AmortizationError::InsufficientPayment(_))));
+
+    let debt = RevolvingDebt::new(5_000.0, 30.0).with_minimum_payment(1.0, 0.0);
+    assert!(matches!(debt.payoff(), Err(AmortizationError::NoSolution(_))));
+    assert_eq!(debt.payments().count(), 1200);
+}
@@ -0,0 +1,41 @@
+use crate::{AmortizationError, PaymentTiming, SavingsPlan};
+use super::assert_float_eq;
+
+#[test]
+fn test_growth_schedule_matches_future_value() {
+    let plan = SavingsPlan::new(500.0, 6.0, 120);
+    let schedule = plan.schedule().unwrap();
+
+    assert_eq!(schedule.rows.len(), 120);
+    assert_float_eq(schedule.rows[0].interest, 0.0);
+    assert_float_eq(schedule.rows[1].interest, 2.5);
+    assert_float_eq(schedule.future_value, 81_939.67);
+    assert_float_eq(plan.future_value().unwrap(), schedule.future_value);
+    assert_float_eq(schedule.total_deposits, 60_000.0);
+    assert_float_eq(schedule.total_interest, schedule.future_value - 60_000.0);
+}
+
+#[test]
+fn test_deposits_in_advance_earn_an_extra_period() {
+    let arrears = SavingsPlan::new(500.0, 6.0, 120);
+    let advance = arrears.clone().with_timing(PaymentTiming::Advance);
+
+    assert_float_eq(advance.future_value().unwrap(), arrears.future_value().unwrap() * 1.005);
+    assert_float_eq(advance.schedule().unwrap().future_value, advance.future_value().unwrap());
+}
+
+#[test]
+fn test_required_deposit_for_target() {
+    let plan = SavingsPlan::new(0.0, 6.0, 120).with_initial_balance(10_000.0);
+    let deposit = plan.required_deposit(100_000.0).unwrap();
+    let funded = SavingsPlan { deposit, ..plan };
+
+    assert_float_eq(funded.schedule().unwrap().future_value, 100_000.0);
+    assert_float_eq(SavingsPlan::new(0.0, 0.0, 12).required_deposit(1_200.0).unwrap(), 100.0);
+}
+
+#[test]
+fn test_invalid_savings_plan() {
+    assert!(matches!(SavingsPlan::new(-1.0, 6.0, 12).schedule(), Err(AmortizationError::InvalidPaymentAmount(_))));
+    assert!(matches!(SavingsPlan::new(100.0, 6.0, 12).required_deposit(0.0), Err(AmortizationError::InvalidLoanAmount(_))));
+}
@@ -0,0 +1,75 @@
+use crate::{Amortization, AmortizationError, RatePath, RatePeriod, ScenarioAnalysis};
+use super::assert_float_eq;
+
+// 5/1 ARM: 5% for five years, then 6%
+fn arm() -> Amortization {
+    Amortization::new_adjustable(300_000.0, vec![RatePeriod::new(1, 5.0), RatePeriod::new(61, 6.0)], 360, None).unwrap()
+}
+
+#[test]
+fn test_shocks_apply_at_first_reset() {
+    let base = arm();
+    let results = ScenarioAnalysis::new(base.clone()).with_shocks(&[100.0, 200.0]).run().unwrap();
+    let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+
+    assert_eq!(names, vec!["base", "+100bp", "+200bp"]);
+    assert_float_eq(results[0].interest_change, 0.0);
+    assert_float_eq(results[1].initial_payment, base.periodic_payment);
+    assert_eq!(results[1].loan.rate_schedule, vec![RatePeriod::new(1, 5.0), RatePeriod::new(61, 7.0)]);
+    assert_eq!(results[2].loan.rate_schedule[1].apr, 8.0);
+    assert!(results[1].max_payment > results[0].max_payment);
+    assert!(results[2].interest_change > results[1].interest_change);
+    assert!(results[1].interest_change > 0.0);
+}
+
+#[test]
+fn test_fixed_rate_shock_from_start() {
+    let base = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let results = ScenarioAnalysis::new(base).with_shocks(&[-100.0]).run().unwrap();
+    let shocked = Amortization::new(200_000.0, 5.0, 360, None).unwrap();
+
+    assert_float_eq(results[1].initial_payment, shocked.periodic_payment);
+    assert_float_eq(results[1].total_interest, shocked.total_interest);
+    assert!(results[1].payment_change < 0.0);
+}
+
+#[test]
+fn test_shift_splits_rate_period() {
+    let results = ScenarioAnalysis::new(arm())
+        .with_scenario("late", RatePath::Shift { from_period: 120, basis_points: 50.0 })
+        .run()
+        .unwrap();
+    let expected = vec![RatePeriod::new(1, 5.0), RatePeriod::new(61, 6.0), RatePeriod::new(120, 6.5)];
+    assert_eq!(results[1].loan.rate_schedule, expected);
+}
+
+#[test]
+fn test_shift_floors_at_zero() {
+    let results = ScenarioAnalysis::new(arm())
+        .with_scenario("collapse", RatePath::Shift { from_period: 1, basis_points: -700.0 })
+        .run()
+        .unwrap();
+    assert_eq!(results[1].loan.rate_schedule[0].apr, 0.0);
+    assert_eq!(results[1].loan.rate_schedule[1].apr, 0.0);
+}
+
+#[test]
+fn test_custom_curve() {
+    let curve = vec![RatePeriod::new(1, 5.0), RatePeriod::new(61, 7.0), RatePeriod::new(73, 8.0)];
+    let results = ScenarioAnalysis::new(arm())
+        .with_scenario("rising", RatePath::Curve(curve.clone()))
+        .run()
+        .unwrap();
+    let expected = Amortization::new_adjustable(300_000.0, curve, 360, None).unwrap();
+
+    assert_float_eq(results[1].total_interest, expected.total_interest);
+    assert_float_eq(results[1].interest_change, expected.total_interest - results[0].total_interest);
+}
+
+#[test]
+fn test_invalid_curve() {
+    let result = ScenarioAnalysis::new(arm())
+        .with_scenario("bad", RatePath::Curve(vec![RatePeriod::new(2, 5.0)]))
+        .run();
+    assert!(matches!(result, Err(AmortizationError::InvalidRateSchedule(_))));
+}
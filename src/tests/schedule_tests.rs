@@ -0,0 +1,107 @@
+use crate::{
+    Amortization, CalculatorConfig, CapitalizationPolicy, Decimal, Deferment, LoanType, Payment, RatePeriod, SkipPayments,
+    SkipTreatment,
+};
+use chrono::NaiveDate;
+
+fn same_payment(a: &Payment, b: &Payment) -> bool {
+    a.installment_number == b.installment_number
+        && a.date == b.date
+        && a.interest == b.interest
+        && a.principal == b.principal
+        && a.remaining_balance == b.remaining_balance
+}
+
+#[test]
+fn test_payments_iterator_matches_schedule() {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let loan = Amortization::new(100_000.0, 5.0, 360, Some(start)).unwrap();
+    let lazy: Vec<Payment> = loan.payments().collect();
+
+    assert_eq!(lazy.len(), loan.schedule.len());
+    assert!(lazy.iter().zip(&loan.schedule).all(|(a, b)| same_payment(a, b)));
+}
+
+#[test]
+fn test_payments_iterator_is_lazy() {
+    let loan = Amortization::new(100_000.0, 5.0, 360, None).unwrap();
+    let mut payments = loan.payments();
+    let first_five: Vec<Payment> = payments.by_ref().take(5).collect();
+
+    assert_eq!(first_five.len(), 5);
+    assert_eq!(first_five[4].installment_number, 5);
+    assert!((payments.remaining_balance() - loan.schedule[4].remaining_balance).abs() < 1e-9);
+    assert_eq!(payments.size_hint(), (1, Some(355)));
+}
+
+#[test]
+fn test_payments_iterator_exhaustion() {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let loan = Amortization::new(10_000.0, 5.0, 12, Some(start)).unwrap();
+    let mut payments = loan.payments();
+
+    assert_eq!(payments.by_ref().count(), 12);
+    assert!(payments.try_next().unwrap().is_none());
+    assert!(payments.error().is_none());
+    assert_eq!(payments.next_date(), loan.end_date);
+    // The loan total is summed in whole cents, the iterator total at full precision
+    assert!((payments.total_payment() - loan.total_payment).abs() < 0.01);
+}
+
+#[test]
+fn test_payments_with_decimal_backend() {
+    let loan = Amortization::new(10_000.0, 5.0, 12, None).unwrap();
+    let mut payments = loan.payments_with::<Decimal>();
+    let schedule: Vec<Payment> = payments.by_ref().collect();
+    let principal: Decimal = schedule.iter()
+        .map(|p| Decimal::try_from_f64(p.principal).unwrap())
+        .sum();
+
+    assert_eq!(schedule.len(), 12);
+    assert_eq!(principal, Decimal::from(10_000i64));
+    assert!(schedule.iter().all(|p| ((p.interest * 100.0).round() - p.interest * 100.0).abs() < 1e-6));
+}
+
+fn assert_ledger_chains(schedule: &[Payment]) {
+    for pair in schedule.windows(2) {
+        assert_eq!(pair[1].beginning_balance, pair[0].remaining_balance, "installment {}", pair[1].installment_number);
+    }
+    for payment in schedule {
+        let closing = payment.beginning_balance - payment.principal + payment.capitalized_interest;
+        assert!((closing - payment.remaining_balance).abs() < 1e-9, "installment {}", payment.installment_number);
+        assert_eq!(payment.ending_balance(), payment.remaining_balance);
+    }
+}
+
+#[test]
+fn test_schedule_balances_form_one_chain() {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1);
+    let configs = [
+        CalculatorConfig::new(250_000.0, 6.25, 360, start),
+        CalculatorConfig {
+            loan_type: LoanType::InterestOnly { io_periods: 24 },
+            extra_payment: 150.0,
+            ..CalculatorConfig::new(250_000.0, 6.25, 360, start)
+        },
+        CalculatorConfig {
+            deferment: Some(Deferment::Unsubsidized { periods: 12 }),
+            skip_payments: Some(SkipPayments::new(SkipTreatment::ExtendTerm).installment(30)),
+            ..CalculatorConfig::new(30_000.0, 5.5, 120, start)
+        },
+        CalculatorConfig {
+            rate_schedule: vec![RatePeriod::new(1, 4.0), RatePeriod::new(61, 7.5)],
+            balloon_period: Some(120),
+            ..CalculatorConfig::new(400_000.0, 4.0, 360, start)
+        },
+    ];
+    for config in configs {
+        assert_ledger_chains(&Amortization::from_config(config).unwrap().schedule);
+    }
+
+    let loan = Amortization::new(250_000.0, 6.25, 360, start).unwrap();
+    let forborne = loan.forbearance(13, 6, CapitalizationPolicy::DeferredBalance).unwrap();
+    assert_ledger_chains(&forborne.schedule);
+    let payment = loan.calculate_payment(loan.balance, 1).unwrap();
+    assert_eq!(payment.beginning_balance, loan.balance);
+    assert_eq!(payment.remaining_balance, loan.schedule[0].remaining_balance);
+}
@@ -0,0 +1,96 @@
+use crate::json::JsonValue;
+use crate::schema::openapi_components;
+use crate::{Amortization, ExtraPayment, Fee, Rate, SchemaType};
+
+// Checks `value` against the subset of JSON Schema the schemas use: types, required and
+// unknown properties, array items and enums
+fn conforms(schema: &JsonValue, value: &JsonValue, path: &str) {
+    let types: Vec<&str> = match schema.get("type") {
+        Some(JsonValue::String(kind)) => vec![kind.as_str()],
+        Some(JsonValue::Array(kinds)) => kinds.iter().filter_map(|kind| match kind {
+            JsonValue::String(kind) => Some(kind.as_str()),
+            _ => None,
+        }).collect(),
+        _ => Vec::new(),
+    };
+    let kind = match value {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "boolean",
+        JsonValue::Number(n) if n.fract() == 0.0 && types.contains(&"integer") => "integer",
+        JsonValue::Number(_) => "number",
+        JsonValue::String(_) => "string",
+        JsonValue::Array(_) => "array",
+        JsonValue::Object(_) => "object",
+    };
+    assert!(types.is_empty() || types.contains(&kind), "{} is {} but the schema says {:?}", path, kind, types);
+    if let Some(JsonValue::Array(allowed)) = schema.get("enum") {
+        assert!(allowed.contains(value), "{} is not one of {:?}", path, allowed);
+    }
+    match value {
+        JsonValue::Object(entries) => {
+            let Some(properties) = schema.get("properties") else { return };
+            if let Some(JsonValue::Array(required)) = schema.get("required") {
+                for key in required {
+                    let JsonValue::String(key) = key else { continue };
+                    assert!(value.get(key).is_some(), "{} is missing {}", path, key);
+                }
+            }
+            for (key, item) in entries {
+                let property = properties.get(key).unwrap_or_else(|| panic!("{}.{} is not in the schema", path, key));
+                conforms(property, item, &format!("{}.{}", path, key));
+            }
+        }
+        JsonValue::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    conforms(item_schema, item, &format!("{}[{}]", path, index));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn loan() -> Amortization {
+    Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(120)
+        .start_date(chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+        .fee(Fee::recurring("servicing", 10.0))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_exports_match_their_schemas() {
+    let plain = Amortization::new(100_000.0, 5.0, 120, None).unwrap();
+    let with_events = loan().apply(ExtraPayment::new(100.0)).unwrap();
+    for loan in [plain, loan(), with_events] {
+        conforms(&SchemaType::Amortization.schema(), &loan.to_json_value(), "$");
+        conforms(&SchemaType::Summary.schema(), &loan.summary().to_json_value(), "$");
+    }
+}
+
+#[test]
+fn test_loan_config_schema() {
+    let schema = SchemaType::LoanConfig.schema();
+    let config = JsonValue::parse(r#"{"principal": 250000, "apr": 6.5, "term_years": 30, "start_date": "2024-01-01", "frequency": "biweekly", "payment_timing": "advance"}"#).unwrap();
+    conforms(&schema, &config, "$");
+    let Some(JsonValue::Object(properties)) = schema.get("properties") else { panic!("no properties") };
+    assert_eq!(properties.len(), 11);
+}
+
+#[test]
+fn test_documents_and_components() {
+    let document = SchemaType::Payment.document();
+    assert_eq!(document.keys()[..2], ["$schema", "title"]);
+    assert_eq!(document.get("title"), Some(&JsonValue::from("Payment")));
+    // The text parses back as JSON
+    assert_eq!(JsonValue::parse(&document.to_string()).unwrap(), document);
+
+    let components = openapi_components();
+    let schemas = components.get("schemas").unwrap();
+    assert_eq!(schemas.keys(), ["LoanConfig", "Payment", "FeeCharge", "ScheduleEvent", "AmortizationSummary", "Amortization"]);
+    assert_eq!(schemas.get("Payment"), Some(&SchemaType::Payment.schema()));
+}
@@ -0,0 +1,41 @@
+use crate::sensitivity::payment_grid;
+use crate::{Amortization, AmortizationError, Currency, GridValue};
+
+#[test]
+fn test_payment_grid() {
+    let grid = payment_grid(300_000.0, &[5.5, 6.0, 6.5], &[180, 360], Currency::USD).unwrap();
+    assert_eq!(grid.payments.len(), 3);
+    assert_eq!(grid.payments[0].len(), 2);
+    // Rows follow the rates and columns the terms
+    for (row, &apr) in grid.rates.iter().enumerate() {
+        for (column, &term) in grid.terms.iter().enumerate() {
+            let loan = Amortization::new(300_000.0, apr, term, None).unwrap();
+            assert_eq!(grid.payments[row][column], loan.periodic_payment);
+            assert!((grid.total_interest[row][column] - loan.total_interest).abs() < 0.5);
+        }
+    }
+    assert_eq!(grid.payment(6.0, 360), Some(1798.65));
+    assert_eq!(grid.payment(7.0, 360), None);
+    assert_eq!(grid.values(GridValue::TotalInterest), grid.total_interest.as_slice());
+}
+
+#[test]
+fn test_grid_rendering() {
+    let grid = payment_grid(300_000.0, &[6.0, 6.5], &[180, 360], Currency::USD).unwrap();
+    let table = grid.to_table(GridValue::Payment);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], " APR     15 yr     30 yr");
+    assert_eq!(lines[2], "  6%  2,531.57  1,798.65");
+
+    let markdown = grid.to_markdown(GridValue::TotalInterest);
+    assert!(markdown.starts_with("| APR | 15 yr | 30 yr |\n| ---: | ---: | ---: |\n| 6% |"));
+    assert!(grid.to_json_value().get("payments").is_some());
+}
+
+#[test]
+fn test_grid_inputs() {
+    assert!(matches!(payment_grid(300_000.0, &[], &[360], Currency::USD), Err(AmortizationError::MissingParameter("rates"))));
+    assert!(matches!(payment_grid(300_000.0, &[6.0], &[], Currency::USD), Err(AmortizationError::MissingParameter("terms"))));
+    assert!(matches!(payment_grid(300_000.0, &[6.0], &[0], Currency::USD), Err(AmortizationError::InvalidPeriods(0))));
+}
@@ -0,0 +1,97 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use crate::json::JsonValue;
+use crate::server::handle;
+
+const LOAN: &str = r#"{"principal": 200000, "apr": 6, "term_months": 360, "start_date": "2024-01-01"}"#;
+
+#[test]
+fn test_amortize_endpoint() {
+    let response = handle("POST", "/amortize", LOAN);
+    assert_eq!(response.status, 200);
+    let summary = response.body.get("summary").unwrap();
+    assert_eq!(summary.get("periodic_payment"), Some(&JsonValue::Number(1199.10)));
+    match response.body.get("schedule") {
+        Some(JsonValue::Array(rows)) => assert_eq!(rows.len(), 360),
+        other => panic!("Expected a schedule, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_summary_endpoint() {
+    let response = handle("POST", "/summary", LOAN);
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body.get("periodic_payment"), Some(&JsonValue::Number(1199.10)));
+    assert_eq!(response.body.get("payoff_period"), Some(&JsonValue::Number(360.0)));
+    assert!(response.body.get("schedule").is_none());
+}
+
+#[test]
+fn test_compare_endpoint() {
+    let body = r#"{"principal": 200000, "loans": [{"apr": 6, "term_months": 360}, {"apr": 5.5, "term_months": 180}]}"#;
+    let response = handle("POST", "/compare?verbose=1", body);
+    assert_eq!(response.status, 200);
+    match response.body.get("summaries") {
+        Some(JsonValue::Array(rows)) => assert_eq!(rows.len(), 2),
+        other => panic!("Expected summaries, got {:?}", other),
+    }
+    assert_eq!(response.body.get("lowest_total_cost"), Some(&JsonValue::Number(1.0)));
+}
+
+#[test]
+fn test_compare_limits_the_number_of_loans() {
+    let body = |count: usize| format!(r#"{{"principal": 200000, "loans": [{}]}}"#, vec![r#"{"apr": 6, "term_months": 360}"#; count].join(", "));
+    assert_eq!(handle("POST", "/compare", &body(100)).status, 200);
+
+    let response = handle("POST", "/compare", &body(101));
+    assert_eq!(response.status, 400);
+    assert_eq!(response.body.get("error"), Some(&JsonValue::from("Invalid loan definition: Compare at most 100 loans, found 101")));
+}
+
+#[test]
+fn test_errors() {
+    let invalid = handle("POST", "/amortize", r#"{"principal": -5, "apr": 6, "term_months": 360}"#);
+    assert_eq!(invalid.status, 400);
+    assert_eq!(invalid.body.get("kind"), Some(&JsonValue::from("InvalidInput")));
+    assert_eq!(invalid.body.get("parameter"), Some(&JsonValue::from("balance")));
+
+    assert_eq!(handle("POST", "/amortize", "principal = 1").status, 400);
+    assert_eq!(handle("POST", "/compare", "[]").status, 400);
+    assert_eq!(handle("GET", "/amortize", "").status, 405);
+    assert_eq!(handle("POST", "/schedule", LOAN).status, 404);
+    assert_eq!(handle("POST", "/amortize", &"[".repeat(500_000)).status, 400);
+}
+
+fn start_server() -> TcpStream {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    std::thread::spawn(move || crate::server::serve(addr));
+
+    (0..50).find_map(|_| TcpStream::connect(addr).ok().or_else(|| {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        None
+    })).unwrap()
+}
+
+#[test]
+fn test_serves_http() {
+    let mut stream = start_server();
+    write!(stream, "POST /amortize HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}", LOAN.len(), LOAN).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(head.starts_with("HTTP/1.1 200 OK"));
+    assert!(head.contains("Content-Type: application/json"));
+    assert!(JsonValue::parse(body).unwrap().get("schedule").is_some());
+}
+
+#[test]
+fn test_refuses_oversized_headers() {
+    let mut stream = start_server();
+    write!(stream, "POST /amortize HTTP/1.1\r\nX-Padding: {}\r\n\r\n", "a".repeat(10_000)).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 431 Request Header Fields Too Large"));
+}
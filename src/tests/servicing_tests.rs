@@ -0,0 +1,126 @@
+use chrono::NaiveDate;
+use crate::{ActualPayment, Amortization, AmortizationError, ApplicationOrder, Fee, Rate};
+use super::assert_float_eq;
+
+fn loan() -> Amortization {
+    Amortization::new(10_000.0, 6.0, 12, NaiveDate::from_ymd_opt(2025, 1, 1)).unwrap()
+}
+
+fn scheduled_payments(loan: &Amortization, count: usize) -> Vec<ActualPayment> {
+    loan.schedule.iter().take(count).map(|p| ActualPayment::new(p.installment_amount)).collect()
+}
+
+#[test]
+fn test_payments_as_scheduled() {
+    let loan = loan();
+    let ledger = loan.apply_payments(&scheduled_payments(&loan, 12)).unwrap();
+
+    assert!(ledger.is_paid_off());
+    assert_float_eq(ledger.cumulative_variance, 0.0);
+    assert_eq!(ledger.projected_payoff_period, Some(12));
+    assert_eq!(ledger.projected_payoff_date, loan.schedule[11].date);
+    // The ledger posts whole cents, so it only drifts from the schedule by rounding
+    for (entry, scheduled) in ledger.entries.iter().zip(&loan.schedule) {
+        assert!((entry.remaining_balance - scheduled.remaining_balance).abs() < 0.05);
+        assert_float_eq(entry.interest, scheduled.interest);
+    }
+}
+
+#[test]
+fn test_overpayment_moves_payoff_forward() {
+    let loan = loan();
+    let mut payments = scheduled_payments(&loan, 3);
+    payments[0] = ActualPayment::new(2_860.66).with_date(NaiveDate::from_ymd_opt(2025, 1, 3).unwrap());
+    let ledger = loan.apply_payments(&payments).unwrap();
+
+    let first = &ledger.entries[0];
+    assert_float_eq(first.variance, 2_000.0);
+    assert_float_eq(first.principal, 2_810.66);
+    assert_eq!(first.paid_date, NaiveDate::from_ymd_opt(2025, 1, 3));
+    assert_eq!(first.scheduled_date, NaiveDate::from_ymd_opt(2025, 1, 1));
+    assert_float_eq(ledger.cumulative_variance, 2_000.0);
+    assert!(ledger.remaining_balance < ledger.scheduled_balance - 2_000.0);
+    assert_eq!(ledger.projected_payoff_period, Some(10));
+    assert_eq!(ledger.periods_ahead(&loan), Some(2));
+    assert_eq!(ledger.projected_payoff_date, NaiveDate::from_ymd_opt(2025, 10, 1));
+}
+
+#[test]
+fn test_missed_payment_capitalizes_interest() {
+    let loan = loan();
+    let mut payments = scheduled_payments(&loan, 4);
+    payments[1] = ActualPayment::new(0.0);
+    let ledger = loan.apply_payments(&payments).unwrap();
+
+    let missed = &ledger.entries[1];
+    assert_float_eq(missed.variance, -loan.schedule[1].installment_amount);
+    assert_float_eq(missed.capitalized_interest, missed.interest);
+    assert_float_eq(missed.remaining_balance, ledger.entries[0].remaining_balance + missed.interest);
+    assert_eq!(ledger.projected_payoff_period, Some(14));
+    assert_eq!(ledger.periods_ahead(&loan), Some(-2));
+}
+
+#[test]
+fn test_negative_actual_payment() {
+    let result = loan().apply_payments(&[ActualPayment::new(-1.0)]);
+
+    assert!(matches!(result, Err(AmortizationError::InvalidPaymentAmount(_))));
+}
+
+fn loan_with_fee() -> Amortization {
+    Amortization::builder()
+        .principal(10_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(12)
+        .fee(Fee::recurring("servicing", 25.0))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_fees_are_settled_before_interest() {
+    let loan = loan_with_fee();
+    let ledger = loan.apply_payments(&scheduled_payments(&loan, 12)).unwrap();
+    assert!(ledger.is_paid_off());
+    assert!(ledger.entries.iter().all(|entry| entry.fees == 25.0 && entry.unpaid_fees == 0.0));
+
+    // $50 covers the fee and half the $50 of interest, so the rest is capitalized
+    let ledger = loan.apply_payments(&[ActualPayment::new(50.0)]).unwrap();
+    let entry = &ledger.entries[0];
+    assert_float_eq(entry.fees, 25.0);
+    assert_float_eq(entry.capitalized_interest, 25.0);
+    assert_float_eq(entry.principal, 0.0);
+    assert_float_eq(ledger.remaining_balance, 10_025.0);
+}
+
+#[test]
+fn test_interest_before_fees_carries_the_fee() {
+    let loan = loan_with_fee();
+    let installment = loan.schedule[1].installment_amount;
+    let payments = [ActualPayment::new(50.0), ActualPayment::new(installment + 25.0)];
+    let ledger = loan.apply_payments_in_order(&payments, ApplicationOrder::InterestFeesPrincipal).unwrap();
+    let first = &ledger.entries[0];
+    assert_float_eq(first.capitalized_interest, 0.0);
+    assert_float_eq(first.fees, 0.0);
+    assert_float_eq(first.unpaid_fees, 25.0);
+    // The next payment settles both months' fees
+    let second = &ledger.entries[1];
+    assert_float_eq(second.fees, 50.0);
+    assert_float_eq(ledger.unpaid_fees, 0.0);
+    assert_float_eq(second.principal, installment + 25.0 - 50.0 - second.interest);
+}
+
+#[test]
+fn test_scheduled_principal_before_fees() {
+    let loan = loan_with_fee();
+    let scheduled = &loan.schedule[0];
+    // Paying only principal and interest leaves the fee owing under this order
+    let short = ActualPayment::new(scheduled.installment_amount - 25.0);
+    let ledger = loan.apply_payments_in_order(&[short], ApplicationOrder::InterestPrincipalFees).unwrap();
+    assert_float_eq(ledger.entries[0].principal, scheduled.principal);
+    assert_float_eq(ledger.unpaid_fees, 25.0);
+
+    let ledger = loan.apply_payments(&[short]).unwrap();
+    assert_float_eq(ledger.entries[0].principal, scheduled.principal - 25.0);
+    assert_float_eq(ledger.unpaid_fees, 0.0);
+}
@@ -0,0 +1,76 @@
+use std::io;
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationBuilder, AmortizationError, AmortizationSummary, CalculatorConfig, CsvSink, Fee, FormatOptions, Payment, Rate, ScheduleSink, StreamError};
+
+fn builder() -> AmortizationBuilder {
+    Amortization::builder()
+        .principal(150_000.0)
+        .rate(Rate::percent(5.5))
+        .term_months(180)
+        .start_date(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap())
+}
+
+// Keeps the running principal and the last installment, like a consumer that never buffers
+#[derive(Default)]
+struct Totals {
+    rows: usize,
+    principal: f64,
+    finished: Option<AmortizationSummary>,
+}
+
+impl ScheduleSink for Totals {
+    type Error = AmortizationError;
+
+    fn on_payment(&mut self, payment: &Payment) -> Result<(), AmortizationError> {
+        self.rows += 1;
+        self.principal += payment.principal;
+        Ok(())
+    }
+
+    fn finish(&mut self, summary: &AmortizationSummary) -> Result<(), AmortizationError> {
+        self.finished = Some(summary.clone());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_streamed_schedule_matches_built_one() {
+    let loan = builder().build().unwrap();
+    let mut streamed = Vec::new();
+    let summary = builder().stream(&mut |payment: &Payment| -> Result<(), ()> {
+        streamed.push(payment.clone());
+        Ok(())
+    }).unwrap();
+    assert_eq!(streamed, loan.schedule);
+    assert_eq!(summary, loan.summary());
+
+    let mut totals = Totals::default();
+    Amortization::stream(CalculatorConfig::new(150_000.0, 5.5, 180, None), &mut totals).unwrap();
+    assert_eq!(totals.rows, 180);
+    assert!((totals.principal - 150_000.0).abs() < 0.01);
+    assert_eq!(totals.finished.unwrap().payoff_period, 180);
+}
+
+#[test]
+fn test_csv_sink_matches_to_csv() {
+    let builder = builder().fee(Fee::recurring("servicing", 15.0));
+    let loan = builder.build().unwrap();
+    let mut sink = CsvSink::new(Vec::new(), FormatOptions::csv(&loan));
+    builder.stream(&mut sink).unwrap();
+    assert_eq!(String::from_utf8(sink.into_inner()).unwrap(), loan.schedule_csv());
+}
+
+#[test]
+fn test_stream_errors() {
+    let mut rows = 0;
+    let result = builder().stream(&mut |_: &Payment| {
+        rows += 1;
+        if rows == 3 { Err(io::Error::other("connection closed")) } else { Ok(()) }
+    });
+    assert!(matches!(&result, Err(StreamError::Sink(error)) if error.to_string() == "connection closed"));
+    assert_eq!(rows, 3);
+
+    let mut never = |_: &Payment| -> Result<(), ()> { panic!("no rows for an invalid loan") };
+    let result = Amortization::stream(CalculatorConfig::new(150_000.0, 5.5, 0, None), &mut never);
+    assert!(matches!(result, Err(StreamError::Schedule(AmortizationError::InvalidPeriods(0)))));
+}
@@ -0,0 +1,73 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, Rate, SkipPayments, SkipTreatment};
+use super::assert_float_eq;
+
+fn loan_with_skips(skip_payments: SkipPayments) -> Amortization {
+    Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(120)
+        .start_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+        .skip_payments(skip_payments)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_skipped_installment_capitalizes_interest() {
+    let loan = loan_with_skips(SkipPayments::new(SkipTreatment::Capitalize).installment(13));
+    let skipped = &loan.schedule[12];
+
+    assert!(skipped.skipped);
+    assert_float_eq(skipped.installment_amount, 0.0);
+    assert_float_eq(skipped.capitalized_interest, skipped.interest);
+    assert_float_eq(skipped.remaining_balance, skipped.beginning_balance + skipped.interest);
+    assert_eq!(loan.skipped_installments(), vec![13]);
+}
+
+#[test]
+fn test_capitalized_skip_recalculates_payment_over_original_term() {
+    let loan = loan_with_skips(SkipPayments::new(SkipTreatment::Capitalize).installment(13));
+    let balance = loan.schedule[12].remaining_balance;
+    let expected = Amortization::new(balance, 6.0, 107, None).unwrap().periodic_payment;
+
+    assert_float_eq(loan.schedule[13].installment_amount, expected);
+    assert_eq!(loan.schedule.len(), 120);
+    assert_eq!(loan.term_extension(), 0);
+    assert_eq!(loan.end_date, NaiveDate::from_ymd_opt(2034, 1, 1));
+    assert!(loan.skip_interest_cost().unwrap() > 0.0);
+}
+
+#[test]
+fn test_extend_term_keeps_payment_and_moves_end_date() {
+    let loan = loan_with_skips(SkipPayments::new(SkipTreatment::ExtendTerm).installment(13).installment(14));
+
+    assert_float_eq(loan.schedule[14].installment_amount, loan.periodic_payment);
+    assert_eq!(loan.schedule.len(), 122);
+    assert_eq!(loan.term_extension(), 2);
+    assert_eq!(loan.end_date, NaiveDate::from_ymd_opt(2034, 3, 1));
+    assert_float_eq(loan.schedule.last().unwrap().remaining_balance, 0.0);
+    assert!(loan.skip_interest_cost().unwrap() > 0.0);
+}
+
+#[test]
+fn test_skip_date_range() {
+    let from = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+    let to = NaiveDate::from_ymd_opt(2024, 8, 31).unwrap();
+    let loan = loan_with_skips(SkipPayments::new(SkipTreatment::ExtendTerm).between(from, to));
+
+    assert_eq!(loan.skipped_installments(), vec![6, 7, 8]);
+    assert_eq!(loan.term_extension(), 3);
+}
+
+#[test]
+fn test_cannot_skip_final_installment() {
+    let result = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(120)
+        .skip_payments(SkipPayments::new(SkipTreatment::Capitalize).installment(120))
+        .build();
+
+    assert!(matches!(result, Err(AmortizationError::InvalidSkippedPayment(120))));
+}
@@ -0,0 +1,89 @@
+use crate::{goal_seek, Amortization, AmortizationError, DEFAULT_MAX_PERIODS};
+
+#[test]
+fn test_solve_principal_round_trips_payment() {
+    let principal = Amortization::solve_principal(3.5, 360, 898.09).unwrap();
+    assert!((principal - 200_000.0).abs() < 5.0, "got {}", principal);
+
+    let loan = Amortization::new(principal, 3.5, 360, None).unwrap();
+    assert!((loan.periodic_payment - 898.09).abs() < 0.011);
+}
+
+#[test]
+fn test_solve_term() {
+    assert_eq!(Amortization::solve_term(200_000.0, 3.5, 898.09).unwrap(), 360);
+    assert_eq!(Amortization::solve_term(10_000.0, 5.0, 1_000.0).unwrap(), 11);
+    assert_eq!(Amortization::solve_term(1_200.0, 0.0, 100.0).unwrap(), 12);
+}
+
+#[test]
+fn test_solve_term_rejects_payment_below_interest() {
+    let result = Amortization::solve_term(200_000.0, 6.0, 1_000.0);
+    assert!(matches!(result, Err(AmortizationError::InsufficientPayment(_))));
+}
+
+#[test]
+fn test_solve_term_rejects_terms_beyond_the_period_cap() {
+    let result = Amortization::solve_term(200_000.0, 0.0, 0.0001);
+    match result {
+        Err(AmortizationError::NonAmortizing { max_periods, stalled_at: None, balance }) => {
+            assert_eq!(max_periods, DEFAULT_MAX_PERIODS);
+            assert!((balance - 199_999.4).abs() < 1e-6, "got {}", balance);
+        }
+        other => panic!("expected NonAmortizing, got {:?}", other),
+    }
+
+    let result = Amortization::solve_term(200_000.0, 1.0, 167.0);
+    assert!(matches!(result, Err(AmortizationError::NonAmortizing { .. })), "got {:?}", result);
+}
+
+#[test]
+fn test_solve_rate() {
+    let apr = Amortization::solve_rate(200_000.0, 898.09, 360).unwrap();
+    assert!((apr - 3.5).abs() < 0.001, "got {}", apr);
+
+    let apr = Amortization::solve_rate(10_000.0, 856.07, 12).unwrap();
+    assert!((apr - 5.0).abs() < 0.01, "got {}", apr);
+
+    assert_eq!(Amortization::solve_rate(1_200.0, 100.0, 12).unwrap(), 0.0);
+}
+
+#[test]
+fn test_solve_rate_far_above_the_starting_guess() {
+    // Paying half the principal every month is a monthly rate of 50%, where Newton's method
+    // from 10% diverges
+    let apr = Amortization::solve_rate(100_000.0, 50_000.0, 360).unwrap();
+    assert!((apr - 600.0).abs() < 0.001, "got {}", apr);
+}
+
+#[test]
+fn test_solve_rate_without_solution() {
+    let result = Amortization::solve_rate(10_000.0, 100.0, 12);
+    assert!(matches!(result, Err(AmortizationError::NoSolution(_))));
+
+    let result = Amortization::solve_rate(10_000.0, -100.0, 12);
+    assert!(matches!(result, Err(AmortizationError::InvalidPaymentAmount(_))));
+}
+
+#[test]
+fn test_goal_seek_apr_for_total_interest() {
+    let total_interest = |apr| Amortization::new(200_000.0, apr, 360, None).map(|loan| loan.total_interest);
+    let apr = goal_seek(total_interest, 50_000.0, (0.0, 20.0)).unwrap();
+    // Within a cent of the target, the steps of a schedule rounded to cents
+    assert!((total_interest(apr).unwrap() - 50_000.0).abs() < 0.5, "got {}", apr);
+    assert!((apr - 1.54).abs() < 0.01, "got {}", apr);
+
+    // Bounds in either order, and a target hit at a bound
+    assert_eq!(goal_seek(|x| Ok(x * x), 4.0, (2.0, 0.0)).unwrap(), 2.0);
+    assert!((goal_seek(|x| Ok(x * x), 2.0, (0.0, 2.0)).unwrap() - 2f64.sqrt()).abs() < 1e-9);
+}
+
+#[test]
+fn test_goal_seek_failures() {
+    assert!(matches!(goal_seek(|x| Ok(x * x), -1.0, (0.0, 2.0)), Err(AmortizationError::NoSolution(_))));
+    assert!(matches!(goal_seek(Ok, 1.0, (0.0, f64::INFINITY)), Err(AmortizationError::NoSolution(_))));
+    assert!(matches!(goal_seek(|x| Ok(1.0 / x - 1.0), 0.5, (0.0, 1.0)), Err(AmortizationError::NoSolution(_))));
+    // Errors from the metric come through
+    let periodic_payment = |periods: f64| Amortization::new(200_000.0, 6.0, periods as u32, None).map(|loan| loan.periodic_payment);
+    assert!(matches!(goal_seek(periodic_payment, 1_500.0, (0.0, 360.0)), Err(AmortizationError::InvalidPeriods(0))));
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use chrono::NaiveDate;
+use crate::sql::schema;
+use crate::{Amortization, AmortizationError, Currency, Escrow, LoanRecord, Payment, Rate, SqlDialect, SqlRow, SqlStatement, SqlValue};
+
+// Rows as an in-memory "driver" would hand them back, read from the statements' parameters
+struct Row(HashMap<String, SqlValue>);
+
+impl SqlRow for Row {
+    fn integer(&self, column: &str) -> Result<Option<i64>, AmortizationError> {
+        Ok(match self.0.get(column) {
+            Some(SqlValue::Integer(n)) => Some(*n),
+            Some(SqlValue::Bool(b)) => Some(*b as i64),
+            _ => None,
+        })
+    }
+
+    fn real(&self, column: &str) -> Result<Option<f64>, AmortizationError> {
+        Ok(match self.0.get(column) {
+            Some(SqlValue::Real(n)) => Some(*n),
+            _ => None,
+        })
+    }
+
+    fn text(&self, column: &str) -> Result<Option<String>, AmortizationError> {
+        Ok(match self.0.get(column) {
+            Some(SqlValue::Text(text)) => Some(text.clone()),
+            Some(SqlValue::Date(date)) => Some(date.format("%Y-%m-%d").to_string()),
+            _ => None,
+        })
+    }
+}
+
+fn rows(statement: &SqlStatement) -> Vec<Row> {
+    let start = statement.sql.find('(').unwrap() + 1;
+    let columns: Vec<String> = statement.sql[start..statement.sql.find(')').unwrap()].split(", ").map(String::from).collect();
+    statement.params.chunks(columns.len())
+        .map(|values| Row(columns.iter().cloned().zip(values.iter().cloned()).collect()))
+        .collect()
+}
+
+fn loan() -> Amortization {
+    Amortization::builder()
+        .principal(320_000.0)
+        .rate(Rate::percent(6.25))
+        .term_months(360)
+        .start_date(NaiveDate::from_ymd_opt(2025, 6, 1).unwrap())
+        .escrow(Escrow::new(400_000.0, 4_800.0, 1_500.0))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_schedule_round_trips_through_rows() {
+    let loan = loan();
+    let statements = loan.sql_inserts(SqlDialect::Postgres, 7);
+    assert_eq!(statements.len(), 2);
+    assert!(statements[0].sql.starts_with("INSERT INTO amortization_loans (id, currency, "));
+    assert!(statements[1].sql.contains("VALUES ($1, $2, "));
+
+    let record = LoanRecord::from_row(&rows(&statements[0])[0]).unwrap();
+    assert_eq!(record.id, 7);
+    assert_eq!(record.currency, Currency::USD);
+    assert_eq!(record.periods, 360);
+    assert_eq!(record.total_interest, loan.money(loan.total_interest).to_f64());
+    assert_eq!(record.end_date, loan.end_date);
+
+    let payments: Vec<Payment> = rows(&statements[1]).iter().map(|row| Payment::from_row(row, record.currency).unwrap()).collect();
+    assert_eq!(payments.len(), 360);
+    for (read, original) in payments.iter().zip(&loan.schedule) {
+        assert_eq!(read.date, original.date);
+        assert!((read.interest - original.interest).abs() < 0.006);
+        assert!((read.escrow_amount() - original.escrow_amount()).abs() < 0.02);
+    }
+}
+
+#[test]
+fn test_bulk_insert_respects_parameter_limit() {
+    let long = Amortization::new(100_000.0, 5.0, 5_000, None).unwrap();
+    let statements = long.sql_insert_schedule(SqlDialect::Sqlite, 1);
+    assert_eq!(statements.len(), long.schedule.len().div_ceil(32_766 / 15));
+    assert!(statements.len() > 1);
+    assert!(statements.iter().all(|statement| statement.params.len() <= SqlDialect::Sqlite.max_parameters()));
+    assert!(statements[0].sql.contains("(?1, ?2, "));
+    assert_eq!(statements.iter().map(|statement| statement.params.len()).sum::<usize>(), long.schedule.len() * 15);
+}
+
+#[test]
+fn test_schema_and_invalid_rows() {
+    let postgres = schema(SqlDialect::Postgres);
+    assert!(postgres.contains("due_date DATE,") && postgres.contains("skipped BOOLEAN NOT NULL"));
+    let sqlite = schema(SqlDialect::Sqlite);
+    assert!(sqlite.contains("due_date TEXT,") && sqlite.contains("PRIMARY KEY (loan_id, installment_number)"));
+
+    let mut row = rows(&loan().sql_insert_loan(SqlDialect::Sqlite, 1)).remove(0);
+    row.0.insert("frequency".to_string(), SqlValue::Text("fortnightly".to_string()));
+    assert!(matches!(LoanRecord::from_row(&row), Err(AmortizationError::InvalidDatabaseRow(message)) if message.contains("frequency")));
+
+    let mut row = rows(&loan().sql_insert_loan(SqlDialect::Sqlite, 1)).remove(0);
+    row.0.insert("minor_units".to_string(), SqlValue::Integer(25));
+    assert!(matches!(LoanRecord::from_row(&row), Err(AmortizationError::InvalidDatabaseRow(message)) if message.contains("minor_units")));
+}
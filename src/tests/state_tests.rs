@@ -0,0 +1,203 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, CapitalizationFrequency, Currency, DayCount, Deferment, Escrow, LoanState, LoanType, PaymentFrequency, PaymentTiming, Rate, RatePeriod};
+use super::assert_float_eq;
+
+fn loan() -> Amortization {
+    Amortization::new(250_000.0, 6.0, 360, NaiveDate::from_ymd_opt(2025, 1, 1)).unwrap()
+}
+
+#[test]
+fn test_snapshot_and_resume() {
+    let loan = loan();
+    let state = loan.snapshot_at(120).unwrap();
+    assert_eq!(state.installments_paid, 120);
+    assert_float_eq(state.balance, loan.schedule[119].remaining_balance);
+    assert_eq!(state.next_due_date, loan.schedule[120].date);
+    assert_eq!(state.remaining_periods, 240);
+
+    let resumed = Amortization::resume(state).unwrap();
+    assert_eq!(resumed.schedule.len(), 240);
+    assert_eq!(resumed.start_date, loan.schedule[120].date);
+    assert_float_eq(resumed.periodic_payment, loan.periodic_payment);
+    for (ours, original) in resumed.schedule.iter().zip(&loan.schedule[120..]).step_by(40) {
+        assert_float_eq(ours.interest, original.interest);
+        assert_eq!(ours.date, original.date);
+    }
+    assert_float_eq(resumed.total_interest, loan.schedule[120..].iter().map(|p| p.interest).sum());
+}
+
+#[test]
+fn test_state_round_trips_through_json() {
+    let loan = Amortization::builder()
+        .principal(30_000.0)
+        .rate_schedule(vec![RatePeriod::new(1, 4.0), RatePeriod::new(37, 6.0)])
+        .frequency(PaymentFrequency::BiWeekly)
+        .term_months(60)
+        .build()
+        .unwrap();
+    let state = loan.snapshot_at(20).unwrap().with_accrued_interest(12.5);
+    assert_eq!(state.next_due_date, None);
+    assert_eq!(state.rate_schedule, vec![RatePeriod::new(1, 4.0), RatePeriod::new(17, 6.0)]);
+    let read = LoanState::from_json(&state.to_json()).unwrap();
+    assert_eq!(read, state);
+
+    let resumed = Amortization::resume(read).unwrap();
+    assert_float_eq(resumed.balance, state.balance + 12.5);
+    assert_eq!(resumed.config.frequency, PaymentFrequency::BiWeekly);
+
+    let adjustable = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(5.0))
+        .rate_schedule(vec![RatePeriod::new(1, 5.0), RatePeriod::new(61, 7.0)])
+        .term_months(360)
+        .build()
+        .unwrap();
+    let state = adjustable.snapshot_at(24).unwrap();
+    assert_eq!(state.rate_schedule, vec![RatePeriod::new(1, 5.0), RatePeriod::new(37, 7.0)]);
+    let resumed = Amortization::resume(state).unwrap();
+    assert_float_eq(resumed.schedule[36].interest, adjustable.schedule[60].interest);
+    assert_float_eq(resumed.schedule[100].installment_amount, adjustable.schedule[124].installment_amount);
+}
+
+#[test]
+fn test_invalid_snapshots_and_states() {
+    let loan = loan();
+    assert!(matches!(loan.snapshot_at(361), Err(AmortizationError::PeriodOutOfRange(361))));
+    let paid_off = loan.snapshot_at(360).unwrap();
+    assert!(paid_off.is_paid_off());
+    assert!(matches!(Amortization::resume(paid_off), Err(AmortizationError::InvalidLoanState(_))));
+
+    assert!(matches!(LoanState::from_json("{"), Err(AmortizationError::InvalidLoanState(_))));
+    let json = loan.snapshot_at(1).unwrap().to_json();
+    assert!(matches!(LoanState::from_json(&json.replace("\"monthly\"", "\"daily\"")), Err(AmortizationError::InvalidLoanState(_))));
+    assert!(matches!(LoanState::from_json(&json.replace("balance", "principal")), Err(AmortizationError::MissingParameter("balance"))));
+    assert!(matches!(LoanState::from_json(&json.replace("\"minor_units\":2", "\"minor_units\":25")), Err(AmortizationError::InvalidLoanState(_))));
+
+    let endless = json.replace("\"remaining_periods\":359", "\"remaining_periods\":400000000");
+    assert!(matches!(LoanState::from_json(&endless), Err(AmortizationError::InvalidLoanState(_))));
+    let state = LoanState { remaining_periods: 400_000_000, ..loan.snapshot_at(1).unwrap() };
+    assert!(matches!(Amortization::resume(state), Err(AmortizationError::InvalidLoanState(_))));
+}
+
+#[test]
+fn test_snapshot_keeps_uncapitalized_interest() {
+    let loan = Amortization::builder()
+        .principal(30_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(168)
+        .deferment(Deferment::Unsubsidized { periods: 48 })
+        .capitalization(CapitalizationFrequency::Quarterly)
+        .build()
+        .unwrap();
+    let state = loan.snapshot_at(2).unwrap();
+
+    assert_float_eq(state.balance, 30_000.0);
+    assert_float_eq(state.accrued_interest, 300.0);
+    assert_float_eq(loan.snapshot_at(3).unwrap().accrued_interest, 0.0);
+}
+
+// Resumes `loan` after `period` installments, through JSON, and checks it continues the
+// original schedule
+fn assert_resumes(loan: &Amortization, period: u32) -> Amortization {
+    let state = loan.snapshot_at(period).unwrap();
+    assert_eq!(LoanState::from_json(&state.to_json()).unwrap(), state);
+    let resumed = Amortization::resume(LoanState::from_json(&state.to_json()).unwrap()).unwrap();
+    assert_eq!(resumed.schedule.len(), loan.schedule.len() - period as usize);
+    for (ours, original) in resumed.schedule.iter().zip(&loan.schedule[period as usize..]) {
+        assert_float_eq(ours.installment_amount, original.installment_amount);
+        assert_float_eq(ours.interest, original.interest);
+        assert_eq!(ours.date, original.date);
+    }
+    resumed
+}
+
+#[test]
+fn test_resume_keeps_the_schedule_shape() {
+    let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    let interest_only = Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .start_date(start)
+        .loan_type(LoanType::InterestOnly { io_periods: 60 })
+        .build()
+        .unwrap();
+    let resumed = assert_resumes(&interest_only, 12);
+    assert_eq!(resumed.config.loan_type, LoanType::InterestOnly { io_periods: 48 });
+    assert_float_eq(resumed.schedule[47].installment_amount, 1_000.0);
+    assert_float_eq(resumed.schedule[48].installment_amount, 1_288.60);
+
+    let equal_principal = Amortization::builder()
+        .principal(120_000.0)
+        .rate(Rate::percent(5.0))
+        .term_months(360)
+        .loan_type(LoanType::EqualPrincipal)
+        .build()
+        .unwrap();
+    let resumed = assert_resumes(&equal_principal, 12);
+    assert_eq!(resumed.schedule.len(), 348);
+    assert_float_eq(resumed.schedule[0].principal, 333.33);
+
+    let actual = Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .start_date(start)
+        .day_count(DayCount::Actual365)
+        .build()
+        .unwrap();
+    let resumed = assert_resumes(&actual, 12);
+    assert_eq!(resumed.config.day_count, Some(DayCount::Actual365));
+    assert_float_eq(resumed.schedule[0].interest, actual.schedule[12].interest);
+    assert_float_eq(resumed.schedule[0].interest, 1_006.65);
+
+    let month_end = Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .start_date(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap())
+        .day_count(DayCount::Actual365)
+        .build()
+        .unwrap();
+    let resumed = assert_resumes(&month_end, 1);
+    assert_eq!(resumed.start_date, NaiveDate::from_ymd_opt(2025, 2, 28));
+    assert_eq!(resumed.schedule[1].date, NaiveDate::from_ymd_opt(2025, 3, 31));
+    assert_eq!(assert_resumes(&resumed, 13).config.date_roll, resumed.config.date_roll);
+
+    let advance = Amortization::builder()
+        .principal(20_000.0)
+        .rate(Rate::percent(7.0))
+        .term_months(60)
+        .payment_timing(PaymentTiming::Advance)
+        .extra_payment(50.0)
+        .currency(Currency::EUR)
+        .build()
+        .unwrap();
+    let resumed = assert_resumes(&advance, 10);
+    assert_eq!(resumed.config.currency, Currency::EUR);
+}
+
+#[test]
+fn test_snapshot_rejects_what_it_cannot_carry() {
+    let escrow = Amortization::builder()
+        .principal(250_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .escrow(Escrow::new(300_000.0, 3_000.0, 1_200.0))
+        .build()
+        .unwrap();
+    assert!(matches!(escrow.snapshot_at(12), Err(AmortizationError::InvalidLoanState(_))));
+
+    let deferred = Amortization::builder()
+        .principal(30_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(168)
+        .deferment(Deferment::Unsubsidized { periods: 48 })
+        .capitalization(CapitalizationFrequency::Quarterly)
+        .build()
+        .unwrap();
+    assert!(matches!(Amortization::resume(deferred.snapshot_at(2).unwrap()), Err(AmortizationError::InvalidLoanState(_))));
+    let state = deferred.snapshot_at(12).unwrap();
+    assert_eq!(state.deferment, Some(Deferment::Unsubsidized { periods: 36 }));
+    assert_eq!(Amortization::resume(state).unwrap().schedule.len(), 156);
+}
@@ -0,0 +1,96 @@
+use chrono::NaiveDate;
+use crate::{read_statement, Amortization, AmortizationBuilder, AmortizationError, DayCount, LenderConvention, Rate, ReconcileOptions, RoundingMode, RoundingPolicy};
+use super::assert_float_eq;
+
+fn builder() -> AmortizationBuilder {
+    Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(24)
+        .start_date(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap())
+}
+
+fn reconcile(ours: &Amortization, theirs: &Amortization, options: &ReconcileOptions) -> crate::Reconciliation {
+    ours.reconcile_statement(theirs.schedule_csv().as_bytes(), options).unwrap()
+}
+
+#[test]
+fn test_own_export_reconciles() {
+    let loan = builder().build().unwrap();
+    let reconciliation = reconcile(&loan, &loan, &ReconcileOptions::default());
+    assert!(reconciliation.is_match(), "{}", reconciliation);
+    assert_eq!(reconciliation.rows.len(), 24);
+    assert!(reconciliation.hints.is_empty());
+}
+
+#[test]
+fn test_day_count_difference() {
+    let ours = builder().day_count(DayCount::Actual365).build().unwrap();
+    let theirs = builder().day_count(DayCount::Thirty360).build().unwrap();
+    let reconciliation = reconcile(&ours, &theirs, &ReconcileOptions::default());
+    assert!(!reconciliation.is_match());
+    assert!(reconciliation.has_hint(LenderConvention::DayCount), "{}", reconciliation);
+    assert!(!reconciliation.has_hint(LenderConvention::Rate));
+    let first = &reconciliation.rows[0];
+    assert!(first.difference("interest").is_some());
+}
+
+#[test]
+fn test_rate_difference() {
+    let ours = Amortization::new(50_000.0, 6.0, 36, None).unwrap();
+    let theirs = Amortization::new(50_000.0, 6.25, 36, None).unwrap();
+    let reconciliation = reconcile(&ours, &theirs, &ReconcileOptions::default());
+    assert!(reconciliation.has_hint(LenderConvention::Rate), "{}", reconciliation);
+    assert!(!reconciliation.has_hint(LenderConvention::DayCount));
+}
+
+#[test]
+fn test_rounding_difference() {
+    let ours = builder().build().unwrap();
+    let theirs = builder().rounding(RoundingPolicy::full_precision(RoundingMode::HalfUp)).build().unwrap();
+    let reconciliation = reconcile(&ours, &theirs, &ReconcileOptions::new().with_tolerance(0.0));
+    assert!(!reconciliation.is_match());
+    assert!(reconciliation.hints.iter().all(|hint| hint.convention == LenderConvention::Rounding), "{}", reconciliation);
+    // The drift stays well under a dollar
+    assert!(reconcile(&ours, &theirs, &ReconcileOptions::new().with_tolerance(1.0)).is_match());
+}
+
+#[test]
+fn test_payment_applied_elsewhere_first() {
+    let loan = Amortization::new(10_000.0, 6.0, 3, None).unwrap();
+    let rows: Vec<_> = loan.schedule.iter().map(|p| (p.installment_amount, p.interest, p.principal, p.remaining_balance)).collect();
+    // The lender took a $10 late charge out of the second payment before principal
+    let statement = format!(
+        "No.,Amount,Interest,Principal,Ending Balance\n1,\"${:.2}\",{:.2},{:.2},\"${:.2}\"\n2,{:.2},{:.2},{:.2},{:.2}\n",
+        rows[0].0, rows[0].1, rows[0].2, rows[0].3,
+        rows[1].0, rows[1].1, rows[1].2 - 10.0, rows[1].3 + 10.0,
+    );
+    let reconciliation = loan.reconcile_statement(statement.as_bytes(), &ReconcileOptions::default()).unwrap();
+    assert!(reconciliation.rows[0].is_match());
+    let second = &reconciliation.rows[1];
+    assert_float_eq(second.difference("principal").unwrap().difference(), -10.0);
+    assert_float_eq(second.difference("balance").unwrap().difference(), 10.0);
+    assert!(reconciliation.has_hint(LenderConvention::ApplicationOrder));
+    assert_eq!(reconciliation.hints[0].installments, vec![2]);
+    assert_eq!(reconciliation.missing, vec![3]);
+}
+
+#[test]
+fn test_dates_and_unexpected_rows() {
+    let loan = builder().term_months(2).build().unwrap();
+    let statement = "Due Date,Interest\n01/18/2025,1000.00\n2025-02-15,\n2025-03-15,\n";
+    let options = ReconcileOptions::new().with_tolerance(5.0).with_date_tolerance(2);
+    let reconciliation = loan.reconcile_statement(statement.as_bytes(), &options).unwrap();
+    assert_eq!(reconciliation.rows[0].date_difference_days, Some(3));
+    assert_eq!(reconciliation.rows[1].date_difference_days, None);
+    assert_eq!(reconciliation.unexpected, vec![3]);
+    assert!(reconciliation.to_string().contains("Not in our schedule: [3]"));
+}
+
+#[test]
+fn test_invalid_statements() {
+    assert!(matches!(read_statement("".as_bytes()), Err(AmortizationError::InvalidStatement(_))));
+    assert!(matches!(read_statement("Date,Memo\n2025-01-01,hello\n".as_bytes()), Err(AmortizationError::InvalidStatement(_))));
+    let result = read_statement("Installment,Interest\n1,abc\n".as_bytes());
+    assert!(matches!(result, Err(AmortizationError::InvalidStatement(message)) if message.contains("line 2")));
+}
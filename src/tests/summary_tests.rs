@@ -0,0 +1,69 @@
+use chrono::NaiveDate;
+use crate::json::JsonValue;
+use crate::{Amortization, AmortizationBuilder, AmortizationSummary, CalculatorConfig, Currency, Deferment, Prepayment, PrepaymentTreatment, Rate};
+use super::assert_float_eq;
+
+fn builder() -> AmortizationBuilder {
+    Amortization::builder()
+        .principal(200_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .start_date(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+}
+
+#[test]
+fn test_summary_headline_numbers() {
+    let summary = builder().summary().unwrap();
+    assert_float_eq(summary.periodic_payment, 1199.10);
+    assert_float_eq(summary.total_interest, 231_677.06);
+    assert_eq!(summary.periods, 360);
+    assert_eq!(summary.payoff_period, 360);
+    assert_eq!(summary.end_date, NaiveDate::from_ymd_opt(2055, 1, 1));
+    assert_float_eq(summary.interest_to_principal(), summary.total_interest / 200_000.0);
+}
+
+#[test]
+fn test_summary_matches_full_schedule() {
+    let builders = vec![
+        builder(),
+        builder().extra_payment(250.0),
+        builder().prepayment(Prepayment::new(60, 20_000.0, PrepaymentTreatment::ReduceTerm)),
+        builder().deferment(Deferment::Unsubsidized { periods: 12 }),
+        builder().currency(Currency::JPY),
+        Amortization::builder().principal(10_000.0).rate(Rate::percent(0.0)).term_months(7),
+    ];
+    for builder in builders {
+        let loan = builder.build().unwrap();
+        assert_eq!(builder.summary().unwrap(), loan.summary());
+        assert_eq!(loan.summary().payoff_period, loan.schedule.len() as u32);
+    }
+
+    let undated = CalculatorConfig::new(50_000.0, 4.5, 60, None);
+    let summary = AmortizationSummary::from_config(undated.clone()).unwrap();
+    assert_eq!(summary, Amortization::from_config(undated).unwrap().summary());
+    assert!(summary.end_date.is_none());
+}
+
+#[test]
+fn test_summary_pays_off_early() {
+    let summary = builder().extra_payment(500.0).summary().unwrap();
+    assert_eq!(summary.periods, 360);
+    assert!(summary.payoff_period < 240);
+    assert!(summary.total_interest < builder().summary().unwrap().total_interest);
+}
+
+#[test]
+fn test_summary_json() {
+    let json = builder().summary().unwrap().to_json_value();
+    assert_eq!(json.get("periodic_payment"), Some(&JsonValue::Number(1199.10)));
+    assert_eq!(json.get("payoff_period"), Some(&JsonValue::Number(360.0)));
+    assert_eq!(json.get("end_date"), Some(&JsonValue::from("2055-01-01")));
+    assert!(json.get("interest_to_principal").is_some());
+    assert!(json.get("schedule").is_none());
+}
+
+#[test]
+fn test_summary_invalid_loan() {
+    let config = CalculatorConfig::new(-1.0, 6.0, 360, None);
+    assert!(AmortizationSummary::from_config(config).is_err());
+}
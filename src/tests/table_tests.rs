@@ -0,0 +1,107 @@
+use chrono::NaiveDate;
+use crate::{Amortization, Column, DisplayOptions, FormatOptions, TableOptions};
+use crate::table::format_amount;
+
+#[test]
+fn test_format_amount_thousands() {
+    assert_eq!(format_amount(1234567.891, true), "1,234,567.89");
+    assert_eq!(format_amount(999.999, true), "1,000.00");
+    assert_eq!(format_amount(-1234.5, true), "-1,234.50");
+    assert_eq!(format_amount(12.0, true), "12.00");
+    assert_eq!(format_amount(1234567.891, false), "1234567.89");
+}
+
+#[test]
+fn test_table_columns_are_aligned() {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let loan = Amortization::new(200_000.0, 6.0, 360, Some(start)).unwrap();
+    let table = loan.to_table();
+    let lines: Vec<&str> = table.lines().collect();
+
+    assert_eq!(lines.len(), 362);
+    assert!(lines[0].ends_with("Balance"));
+    assert!(lines[1].starts_with('-'));
+    assert!(lines[2].contains("2024-01-01"));
+    assert!(lines[2].contains("1,199.10"));
+    assert!(lines[2].ends_with("199,800.90"));
+    assert_eq!(lines[2].len(), lines[361].len());
+}
+
+#[test]
+fn test_table_without_dates() {
+    let loan = Amortization::new(1_000.0, 5.0, 2, None).unwrap();
+    let table = loan.to_table();
+
+    assert!(table.lines().nth(2).unwrap().contains(" N/A  "));
+}
+
+#[test]
+fn test_table_custom_columns() {
+    let loan = Amortization::new(20_000.0, 5.0, 12, None).unwrap();
+    let options = TableOptions::default()
+        .with_columns(vec![Column::Installment, Column::BeginningBalance, Column::Interest])
+        .with_thousands_separator(false);
+    let table = loan.to_table_with(&options);
+    let lines: Vec<&str> = table.lines().collect();
+
+    assert_eq!(lines[0].split_whitespace().collect::<Vec<_>>(), vec!["#", "Beginning", "Balance", "Interest"]);
+    assert!(lines[2].contains("20000.00"));
+    assert!(!table.contains("Date"));
+}
+
+#[test]
+fn test_payment_display_without_dates() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+
+    assert_eq!(
+        loan.schedule[0].to_string(),
+        "Date: N/A, Interest: 1000.00, Principal: 199.10, Remaining Balance: 199800.90, Beginning Balance: 200000.00"
+    );
+}
+
+#[test]
+fn test_payment_display_options() {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let loan = Amortization::new(200_000.0, 6.0, 360, Some(start)).unwrap();
+    let options = DisplayOptions::default()
+        .with_columns(vec![Column::Installment, Column::Date, Column::Payment, Column::RemainingBalance])
+        .with_date_format("%d/%m/%Y")
+        .with_thousands_separator(true);
+
+    assert_eq!(
+        loan.schedule[0].display_with(&options).to_string(),
+        "Installment: 1, Date: 01/01/2024, Payment: 1,199.10, Remaining Balance: 199,800.90"
+    );
+    assert!(loan.schedule[0].to_string().starts_with("Date: 2024-01-01, Interest: 1000.00"));
+}
+
+#[test]
+fn test_format_options_decimals() {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let loan = Amortization::new(200_000.0, 6.0, 360, Some(start)).unwrap();
+    let options = FormatOptions::new().with_decimals(0).with_columns(vec![Column::Installment, Column::Payment]);
+    let table = loan.to_table_with(&options);
+    assert_eq!(table.lines().nth(2).unwrap().split_whitespace().collect::<Vec<_>>(), vec!["1", "1,199"]);
+
+    let options = FormatOptions::payment_display().with_decimals(4).with_columns(vec![Column::Principal]);
+    assert_eq!(loan.schedule[0].display_with(&options).to_string(), "Principal: 199.1000");
+    assert!(loan.to_markdown_with(&options.with_date_format("%d.%m.%Y")).contains("| Start Date | 01.01.2024 |"));
+}
+
+#[test]
+fn test_csv_with_format_options() {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let loan = Amortization::new(200_000.0, 6.0, 360, Some(start)).unwrap();
+    assert!(loan.schedule_csv().starts_with("Installment,Date,Beginning Balance,Interest,Principal,Remaining Balance\n1,2024-01-01,200000.00,"));
+
+    let options = FormatOptions::new()
+        .with_columns(vec![Column::Date, Column::Payment, Column::RemainingBalance])
+        .with_date_format("%b %d, %Y")
+        .with_decimals(1);
+    let mut csv = Vec::new();
+    loan.to_csv_with(&mut csv, &options).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("Date,Payment,Remaining Balance"));
+    assert_eq!(lines.next(), Some("\"Jan 01, 2024\",\"1,199.1\",\"199,800.9\""));
+}
@@ -0,0 +1,42 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, TaxAssumptions};
+use super::assert_float_eq;
+
+fn loan() -> Amortization {
+    Amortization::new(400_000.0, 6.0, 360, NaiveDate::from_ymd_opt(2025, 1, 1)).unwrap()
+}
+
+#[test]
+fn test_uncapped_deduction() {
+    let loan = loan();
+    let analysis = loan.tax_analysis(&TaxAssumptions::new(24.0)).unwrap();
+    let first = &analysis.years[0];
+
+    assert_eq!(first.year, 2025);
+    assert_float_eq(first.deductible_interest, first.interest);
+    assert_float_eq(first.tax_savings, first.interest * 0.24);
+    assert_float_eq(analysis.total_interest, loan.total_interest);
+    assert_float_eq(analysis.after_tax_interest, loan.total_interest * 0.76);
+    assert_float_eq(analysis.after_tax_rate, 4.56);
+}
+
+#[test]
+fn test_capped_deduction() {
+    let analysis = loan().tax_analysis(&TaxAssumptions::new(24.0).with_deduction_cap(20_000.0)).unwrap();
+    let first = &analysis.years[0];
+
+    assert!(first.interest > 20_000.0);
+    assert_float_eq(first.deductible_interest, 20_000.0);
+    assert_float_eq(first.tax_savings, 4_800.0);
+    // Late years fall under the cap
+    let last = analysis.years.last().unwrap();
+    assert_float_eq(last.deductible_interest, last.interest);
+    assert!(analysis.after_tax_rate > 4.56 && analysis.after_tax_rate < 6.0);
+}
+
+#[test]
+fn test_invalid_tax_rate() {
+    let result = loan().tax_analysis(&TaxAssumptions::new(120.0));
+
+    assert!(matches!(result, Err(AmortizationError::InvalidInterestRate(_))));
+}
@@ -0,0 +1,74 @@
+use crate::{Amortization, AmortizationError, RatePeriod, Rate, TeaserRate};
+use super::assert_float_eq;
+
+#[test]
+fn test_zero_percent_promotion_recalculates_at_the_switch() {
+    let loan = Amortization::builder()
+        .principal(5_000.0)
+        .rate(Rate::percent(24.99))
+        .teaser_rate(TeaserRate::new(0.0, 12))
+        .term_months(36)
+        .build()
+        .unwrap();
+
+    assert_eq!(loan.rate_schedule, vec![RatePeriod::new(1, 0.0), RatePeriod::new(13, 24.99)]);
+    assert_float_eq(loan.schedule[0].installment_amount, 5_000.0 / 36.0);
+    assert!(loan.schedule[..12].iter().all(|p| p.interest == 0.0));
+
+    let balance = loan.balance_after(12).unwrap();
+    let standard = Amortization::new(balance, 24.99, 24, None).unwrap();
+    assert_float_eq(loan.schedule[12].installment_amount, standard.periodic_payment);
+    assert_eq!(loan.schedule.last().unwrap().remaining_balance, 0.0);
+
+    let changes = loan.payment_changes();
+    assert_eq!(changes.len(), 1);
+    let jump = changes[0];
+    assert_eq!((jump.installment_number, jump.previous_apr, jump.apr), (13, 0.0, 24.99));
+    assert_float_eq(jump.previous_payment, loan.schedule[11].installment_amount);
+    assert_float_eq(jump.amount(), standard.periodic_payment - jump.previous_payment);
+    assert_float_eq(jump.percent(), jump.amount() / jump.previous_payment * 100.0);
+    assert!(jump.amount() > 0.0);
+}
+
+#[test]
+fn test_teaser_in_front_of_an_adjustable_schedule() {
+    let loan = Amortization::builder()
+        .principal(300_000.0)
+        .rate_schedule(vec![RatePeriod::new(1, 5.0), RatePeriod::new(61, 6.5)])
+        .teaser_rate(TeaserRate::new(2.0, 12))
+        .term_months(360)
+        .build()
+        .unwrap();
+
+    assert_eq!(loan.rate_schedule, vec![RatePeriod::new(1, 2.0), RatePeriod::new(13, 5.0), RatePeriod::new(61, 6.5)]);
+    let changes = loan.payment_changes();
+    assert_eq!(changes.iter().map(|c| c.installment_number).collect::<Vec<_>>(), vec![13, 61]);
+    assert_eq!(changes[1].previous_apr, 5.0);
+    assert!(changes.iter().all(|c| c.amount() > 0.0));
+}
+
+#[test]
+fn test_teaser_supersedes_resets_it_covers() {
+    let teaser = TeaserRate::new(1.0, 24);
+    let applied = teaser.apply(&[RatePeriod::new(1, 4.0), RatePeriod::new(13, 5.0), RatePeriod::new(37, 6.0)]);
+    assert_eq!(applied, vec![RatePeriod::new(1, 1.0), RatePeriod::new(25, 5.0), RatePeriod::new(37, 6.0)]);
+    assert_eq!(teaser.apply(&applied), applied);
+}
+
+#[test]
+fn test_fixed_rate_loan_has_no_payment_changes() {
+    assert!(Amortization::new(100_000.0, 6.0, 120, None).unwrap().payment_changes().is_empty());
+}
+
+#[test]
+fn test_invalid_teasers() {
+    let build = |teaser| Amortization::builder()
+        .principal(10_000.0)
+        .rate(Rate::percent(18.0))
+        .teaser_rate(teaser)
+        .term_months(24)
+        .build();
+    assert!(matches!(build(TeaserRate::new(0.0, 0)), Err(AmortizationError::InvalidRateSchedule(_))));
+    assert!(matches!(build(TeaserRate::new(0.0, 24)), Err(AmortizationError::InvalidRateSchedule(_))));
+    assert!(matches!(build(TeaserRate::new(-1.0, 6)), Err(AmortizationError::InvalidInterestRate(_))));
+}
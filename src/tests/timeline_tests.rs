@@ -0,0 +1,72 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, ScheduleTimeline};
+use super::assert_float_eq;
+
+fn loan() -> Amortization {
+    Amortization::new(200_000.0, 6.0, 360, NaiveDate::from_ymd_opt(2025, 1, 31)).unwrap()
+}
+
+#[test]
+fn test_stitches_recast_onto_original() {
+    let original = loan();
+    let recast = original.recast_with_principal(60, 20_000.0).unwrap();
+    let timeline = ScheduleTimeline::new(original.clone()).then("recast", 61, recast.clone()).unwrap();
+
+    let schedule = timeline.schedule();
+    assert_eq!(schedule.len(), 360);
+    assert_eq!(timeline.len(), 360);
+    assert!(schedule.iter().enumerate().all(|(i, p)| p.installment_number == i as u32 + 1));
+    assert_eq!(schedule[59], original.schedule[59]);
+    assert_float_eq(schedule[60].installment_amount, recast.periodic_payment);
+    assert_eq!(timeline.last_due_date(), original.schedule[359].date);
+
+    // The lump sum is the only balance change the payments don't explain
+    let adjustments = timeline.balance_adjustments();
+    assert_eq!(adjustments.len(), 1);
+    assert_eq!(adjustments[0].0, 61);
+    assert_float_eq(adjustments[0].1, -20_000.0);
+
+    assert_eq!(timeline.segment_at(60).unwrap().label, "original");
+    assert_eq!(timeline.segment_at(61).unwrap().label, "recast");
+    assert!(timeline.segment_at(361).is_none());
+    assert_float_eq(
+        timeline.total_interest(),
+        original.schedule[..60].iter().map(|p| p.interest).sum::<f64>() + recast.total_interest,
+    );
+}
+
+#[test]
+fn test_renumbers_and_dates_undated_segments() {
+    let original = loan();
+    let balance = original.schedule[23].remaining_balance;
+    // Built on its own: numbered from 1 and without dates
+    let modified = Amortization::new(balance, 4.0, 336, None).unwrap();
+    let reset = Amortization::new(120_000.0, 7.0, 200, None).unwrap();
+    let timeline = ScheduleTimeline::new(original.clone())
+        .then("modification", 25, modified)
+        .unwrap()
+        .then("reset", 121, reset)
+        .unwrap();
+
+    let schedule = timeline.schedule();
+    assert_eq!(schedule.len(), 24 + 96 + 200);
+    assert_eq!(schedule[24].installment_number, 25);
+    assert_eq!(schedule[24].date, original.schedule[24].date);
+    // Month-end due dates don't drift after a short month
+    assert_eq!(schedule[120].date, NaiveDate::from_ymd_opt(2035, 1, 31));
+    assert_eq!(timeline.balance_adjustments().len(), 1);
+}
+
+#[test]
+fn test_switch_out_of_range() {
+    let original = loan();
+    let recast = original.recast_at(60).unwrap();
+    assert!(matches!(
+        ScheduleTimeline::new(original.clone()).then("recast", 1, recast.clone()),
+        Err(AmortizationError::PeriodOutOfRange(1))
+    ));
+    assert!(matches!(
+        ScheduleTimeline::new(original).then("recast", 362, recast),
+        Err(AmortizationError::PeriodOutOfRange(362))
+    ));
+}
@@ -0,0 +1,58 @@
+use crate::{Amortization, Decimal, PaymentTiming, Rate};
+use crate::tvm;
+use super::assert_float_eq;
+
+fn lease(timing: PaymentTiming) -> Amortization {
+    Amortization::builder()
+        .principal(30_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(36)
+        .payment_timing(timing)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_advance_payment_uses_annuity_due_formula() {
+    let loan = lease(PaymentTiming::Advance);
+    let expected = -tvm::pmt(0.005, 36, 30_000.0, 0.0, PaymentTiming::Advance).unwrap();
+
+    assert_float_eq(loan.periodic_payment, expected);
+    assert!(loan.periodic_payment < lease(PaymentTiming::Arrears).periodic_payment);
+}
+
+#[test]
+fn test_advance_first_payment_is_all_principal() {
+    let loan = lease(PaymentTiming::Advance);
+    let first = &loan.schedule[0];
+
+    assert_float_eq(first.interest, 0.0);
+    assert_float_eq(first.principal, loan.periodic_payment);
+    assert_float_eq(loan.schedule[1].interest, first.remaining_balance * 0.005);
+}
+
+#[test]
+fn test_advance_schedule_pays_off_on_time() {
+    let loan = lease(PaymentTiming::Advance);
+    let arrears = lease(PaymentTiming::Arrears);
+
+    assert_eq!(loan.schedule.len(), 36);
+    assert_float_eq(loan.schedule.last().unwrap().remaining_balance, 0.0);
+    assert!((loan.final_payment_amount - loan.periodic_payment).abs() < 0.5);
+    assert!(loan.total_interest < arrears.total_interest);
+}
+
+#[test]
+fn test_advance_with_decimal_backend() {
+    let config = Amortization::builder()
+        .principal(30_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(36)
+        .payment_timing(PaymentTiming::Advance)
+        .config()
+        .unwrap();
+    let loan = Amortization::from_config_with_backend::<Decimal>(config).unwrap();
+
+    assert_float_eq(loan.periodic_payment, lease(PaymentTiming::Advance).periodic_payment);
+    assert_float_eq(loan.schedule.last().unwrap().remaining_balance, 0.0);
+}
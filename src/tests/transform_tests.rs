@@ -0,0 +1,61 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, ExtraPayment, LumpSum, PrepaymentTreatment, RateChange, RatePeriod, SkipPayment, SkipTreatment};
+
+fn loan() -> Amortization {
+    Amortization::new(200_000.0, 6.0, 360, NaiveDate::from_ymd_opt(2025, 1, 1)).unwrap()
+}
+
+#[test]
+fn test_lump_sum_matches_with_lumpsum() {
+    let loan = loan();
+    let applied = loan.apply(LumpSum::new(60, 20_000.0)).unwrap();
+    let scenario = loan.with_lumpsum(60, 20_000.0, PrepaymentTreatment::ReduceTerm).unwrap();
+    assert_eq!(applied.schedule, scenario.loan.schedule);
+    // The original is left as it was
+    assert_eq!(loan.schedule.len(), 360);
+
+    let reduced = loan.apply(LumpSum::new(60, 20_000.0).with_treatment(PrepaymentTreatment::ReducePayment)).unwrap();
+    assert_eq!(reduced.schedule.len(), 360);
+}
+
+#[test]
+fn test_transforms_compose() {
+    let loan = loan();
+    let scenario = loan
+        .apply(ExtraPayment::new(100.0)).unwrap()
+        .apply(RateChange::new(61, 7.0)).unwrap()
+        .apply(SkipPayment::new(24).with_treatment(SkipTreatment::ExtendTerm)).unwrap()
+        .apply(LumpSum::new(120, 10_000.0)).unwrap();
+
+    assert_eq!(scenario.config.extra_payment, 100.0);
+    assert_eq!(scenario.rate_schedule, vec![RatePeriod::new(1, 6.0), RatePeriod::new(61, 7.0)]);
+    assert_eq!(scenario.skipped_installments(), vec![24]);
+    assert_eq!(scenario.config.prepayments.len(), 1);
+    assert!(scenario.schedule.len() < 360);
+
+    // Extra payments stack
+    let doubled = loan.apply(ExtraPayment::new(100.0)).unwrap().apply(ExtraPayment::new(50.0)).unwrap();
+    assert_eq!(doubled.config.extra_payment, 150.0);
+}
+
+#[test]
+fn test_rate_change_replaces_later_resets() {
+    let arm = Amortization::new_adjustable(
+        200_000.0,
+        vec![RatePeriod::new(1, 4.0), RatePeriod::new(61, 6.0), RatePeriod::new(121, 7.0)],
+        360,
+        None,
+    ).unwrap();
+    let changed = arm.apply(RateChange::new(85, 5.0)).unwrap();
+    assert_eq!(changed.rate_schedule, vec![RatePeriod::new(1, 4.0), RatePeriod::new(61, 6.0), RatePeriod::new(85, 5.0)]);
+    assert_eq!(changed.schedule[..84], arm.schedule[..84]);
+}
+
+#[test]
+fn test_invalid_transforms() {
+    let loan = loan();
+    assert!(matches!(loan.apply(ExtraPayment::new(-10.0)), Err(AmortizationError::InvalidExtraPayment(_))));
+    assert!(matches!(loan.apply(LumpSum::new(400, 1_000.0)), Err(AmortizationError::PeriodOutOfRange(400))));
+    assert!(matches!(loan.apply(SkipPayment::new(0)), Err(AmortizationError::InvalidSkippedPayment(0))));
+    assert!(matches!(loan.apply(RateChange::new(0, 5.0)), Err(AmortizationError::InvalidRateSchedule(_))));
+}
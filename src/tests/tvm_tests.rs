@@ -0,0 +1,66 @@
+use crate::{Amortization, AmortizationError, Decimal, PaymentTiming};
+use crate::tvm;
+use super::assert_float_eq;
+
+#[test]
+fn test_pmt_matches_spreadsheet() {
+    assert_float_eq(tvm::pmt(0.005, 360, 200_000.0, 0.0, PaymentTiming::Arrears).unwrap(), -1199.10);
+    assert_float_eq(tvm::pmt(0.005, 360, 200_000.0, 0.0, PaymentTiming::Advance).unwrap(), -1193.13);
+    assert_float_eq(tvm::pmt(0.0, 12, 1_200.0, 0.0, PaymentTiming::Arrears).unwrap(), -100.0);
+    assert_float_eq(tvm::pmt(0.004, 60, 0.0, 10_000.0, PaymentTiming::Arrears).unwrap(), -147.80);
+}
+
+#[test]
+fn test_fv_and_pv() {
+    assert_float_eq(tvm::fv(0.005, 12, -100.0, 0.0, PaymentTiming::Arrears).unwrap(), 1233.56);
+    assert_float_eq(tvm::fv(0.005, 12, -100.0, 0.0, PaymentTiming::Advance).unwrap(), 1239.72);
+    assert_float_eq(tvm::pv(0.005, 360, -1199.10, 0.0, PaymentTiming::Arrears).unwrap(), 199_999.82);
+    assert_float_eq(tvm::pv(0.0, 10, -50.0, 0.0, PaymentTiming::Arrears).unwrap(), 500.0);
+}
+
+#[test]
+fn test_nper_and_rate() {
+    assert_float_eq(tvm::nper(0.01, -100.0, 1_000.0, 0.0, PaymentTiming::Arrears).unwrap(), 10.59);
+    assert_float_eq(tvm::nper(0.0, -100.0, 1_000.0, 0.0, PaymentTiming::Arrears).unwrap(), 10.0);
+
+    let rate = tvm::rate(360, -1199.10, 200_000.0, 0.0, PaymentTiming::Arrears).unwrap();
+    assert!((rate - 0.005).abs() < 1e-6, "got {}", rate);
+    let rate = tvm::rate(12, -100.0, 0.0, 1_233.56, PaymentTiming::Arrears).unwrap();
+    assert!((rate - 0.005).abs() < 1e-6, "got {}", rate);
+}
+
+#[test]
+fn test_nper_without_solution() {
+    let result = tvm::nper(0.01, -5.0, 1_000.0, 0.0, PaymentTiming::Arrears);
+    assert!(matches!(result, Err(AmortizationError::NoSolution(_))));
+}
+
+#[test]
+fn test_ipmt_and_ppmt() {
+    assert_float_eq(tvm::ipmt(0.005, 1, 360, 200_000.0, 0.0, PaymentTiming::Arrears).unwrap(), -1000.0);
+    assert_float_eq(tvm::ppmt(0.005, 1, 360, 200_000.0, 0.0, PaymentTiming::Arrears).unwrap(), -199.10);
+    assert_float_eq(tvm::ipmt(0.005, 1, 360, 200_000.0, 0.0, PaymentTiming::Advance).unwrap(), 0.0);
+    assert!(matches!(
+        tvm::ipmt(0.005, 361, 360, 200_000.0, 0.0, PaymentTiming::Arrears),
+        Err(AmortizationError::PeriodOutOfRange(361))
+    ));
+}
+
+#[test]
+fn test_schedule_agrees_with_ipmt_and_ppmt() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    for per in [1, 60, 120, 240] {
+        let row = &loan.schedule[per as usize - 1];
+        let interest = tvm::ipmt(0.005, per, 360, 200_000.0, 0.0, PaymentTiming::Arrears).unwrap();
+        let principal = tvm::ppmt(0.005, per, 360, 200_000.0, 0.0, PaymentTiming::Arrears).unwrap();
+        assert_float_eq(row.interest, -interest);
+        assert!((row.principal + principal).abs() < 0.05, "period {}: {} vs {}", per, row.principal, principal);
+    }
+}
+
+#[test]
+fn test_pmt_with_decimal() {
+    let rate = Decimal::new(5, 3);
+    let payment = tvm::pmt(rate, 360, Decimal::from(200_000i64), Decimal::ZERO, PaymentTiming::Arrears).unwrap();
+    assert_eq!(format!("{:.2}", payment.round_dp(2)), "-1199.10");
+}
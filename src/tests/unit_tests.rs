@@ -1,11 +1,6 @@
-use crate::{Amortization, AmortizationError};
+use crate::{Amortization, AmortizationError, CalculatorConfig, ErrorKind, LoanType, PaymentFrequency, PaymentTiming, Rate};
 use chrono::NaiveDate;
-
-const FLOAT_PRECISION: f64 = 0.01;
-
-fn assert_float_eq(a: f64, b: f64) {
-    assert!((a - b).abs() < FLOAT_PRECISION, "Expected {}, got {}", b, a);
-}
+use super::assert_float_eq;
 
 #[test]
 fn test_new_amortization() {
@@ -39,7 +34,7 @@ fn test_monthly_payment_calculation() {
 #[test]
 fn test_total_payment_calculation() {
     let loan = Amortization::new(100_000.0, 5.0, 360, None).unwrap();
-    let expected_total = loan.periodic_payment * 360.0;
+    let expected_total = loan.periodic_payment * 359.0 + loan.final_payment_amount;
     assert_float_eq(loan.total_payment, expected_total);
 }
 
@@ -130,3 +125,97 @@ fn test_edge_cases() {
     let short_term = Amortization::new(10_000.0, 5.0, 3, None).unwrap();
     assert_eq!(short_term.schedule.len(), 3);
 }
+
+#[test]
+fn test_final_payment_balances_to_zero() {
+    for (balance, apr, periods) in [(200_000.0, 3.5, 360), (280_350.0, 3.5, 60), (10_000.0, 5.0, 12), (1_000_000.0, 7.25, 180)] {
+        let loan = Amortization::new(balance, apr, periods, None).unwrap();
+        let principal: f64 = loan.schedule.iter().map(|p| p.principal).sum();
+        let last = loan.schedule.last().unwrap();
+
+        assert_eq!(loan.schedule.len(), periods as usize);
+        assert_eq!(last.remaining_balance, 0.0);
+        assert!((principal - balance).abs() < 0.005, "principal sums to {} for {}", principal, balance);
+        assert_float_eq(loan.final_payment_amount, last.interest + last.principal);
+        assert!((loan.final_payment_amount - loan.periodic_payment).abs() < 1.0);
+    }
+}
+
+#[test]
+fn test_final_payment_absorbs_rounding_residual() {
+    let loan = Amortization::new(200_000.0, 3.5, 360, None).unwrap();
+    let last = loan.schedule.last().unwrap();
+
+    assert_float_eq(last.principal, last.beginning_balance);
+    assert_eq!(last.installment_amount, loan.final_payment_amount);
+    assert_ne!(loan.final_payment_amount, loan.periodic_payment);
+    // A payable amount, with the sub-cent residual in the interest
+    assert_eq!(loan.final_payment_amount, 897.69);
+    assert!((last.interest + last.principal - last.installment_amount).abs() < 1e-9);
+    assert_eq!(last.remaining_balance, 0.0);
+}
+
+#[test]
+fn test_schedule_length_exact_for_non_monthly_frequency() {
+    let loan = Amortization::builder()
+        .principal(100_000.0)
+        .rate(Rate::percent(6.0))
+        .term_months(360)
+        .frequency(crate::PaymentFrequency::BiWeekly)
+        .build()
+        .unwrap();
+
+    assert_eq!(loan.schedule.len(), 780);
+    assert_eq!(loan.schedule.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_weekly_term_too_long_to_count_is_rejected() {
+    // 991,146,300 months of weekly payments is 2^32 + 4 periods, which used to wrap to 4
+    assert_eq!(PaymentFrequency::Weekly.periods_for_months(991_146_300), u32::MAX);
+    assert_eq!(PaymentFrequency::Weekly.periods_for_months(u32::MAX), u32::MAX);
+    assert_eq!(PaymentFrequency::Monthly.periods_for_months(u32::MAX), u32::MAX);
+
+    let config = CalculatorConfig {
+        frequency: PaymentFrequency::Weekly,
+        ..CalculatorConfig::new(100_000.0, 6.0, 991_146_300, None)
+    };
+    assert!(matches!(Amortization::from_config(config), Err(AmortizationError::Overflow(_))));
+}
+
+#[test]
+fn test_errors_report_kind_parameter_and_value() {
+    let error = Amortization::new(100_000.0, -1.0, 360, None).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert_eq!(error.parameter(), Some("apr"));
+    assert_eq!(error.value(), Some(-1.0));
+
+    let config = CalculatorConfig {
+        loan_type: LoanType::InterestOnly { io_periods: 360 },
+        ..CalculatorConfig::new(100_000.0, 5.0, 360, None)
+    };
+    let error = Amortization::from_config(config).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InconsistentConfig);
+    assert_eq!((error.parameter(), error.value()), (Some("loan_type"), Some(360.0)));
+
+    let start = NaiveDate::from_ymd_opt(2025, 2, 1);
+    let config = CalculatorConfig {
+        payment_timing: PaymentTiming::Advance,
+        funding_date: NaiveDate::from_ymd_opt(2025, 1, 15),
+        ..CalculatorConfig::new(100_000.0, 5.0, 360, start)
+    };
+    let error = Amortization::from_config(config).unwrap_err();
+    assert!(matches!(error, AmortizationError::ConflictingOptions("funding_date", "payment_timing")));
+    assert_eq!(error.kind(), ErrorKind::UnsupportedCombination);
+    assert_eq!(error.to_string(), "funding_date cannot be combined with payment_timing");
+}
+
+#[test]
+fn test_overflow_and_date_errors() {
+    let error = Amortization::new(f64::MAX, 30.0, 360, None).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Overflow);
+
+    let error = Amortization::new(100_000.0, 5.0, 360, NaiveDate::from_ymd_opt(262_142, 1, 1)).unwrap_err();
+    assert!(matches!(error, AmortizationError::DateOutOfRange(_)));
+    assert_eq!(error.kind(), ErrorKind::DateArithmetic);
+}
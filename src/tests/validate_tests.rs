@@ -0,0 +1,111 @@
+use chrono::NaiveDate;
+use crate::{
+    Amortization, AmortizationError, CalculatorConfig, DateRollConvention, Decimal, Deferment, Escrow, Invariant, LoanType,
+    PaymentCap, PaymentFrequency, PaymentTiming, Rate, RatePeriod, SkipPayments, SkipTreatment,
+};
+
+#[test]
+fn test_every_feature_produces_a_valid_schedule() {
+    let start = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+    let base = || Amortization::builder().principal(150_000.0).rate(Rate::percent(5.5)).term_months(240).start_date(start);
+    let loans = vec![
+        base().build().unwrap(),
+        base().frequency(PaymentFrequency::BiWeekly).extra_payment(50.0).build().unwrap(),
+        base().loan_type(LoanType::InterestOnly { io_periods: 24 }).build().unwrap(),
+        base().balloon_at(84).build().unwrap(),
+        base().rate_schedule(vec![RatePeriod::new(1, 3.0), RatePeriod::new(13, 8.0)]).payment_cap(PaymentCap::new(7.5)).build().unwrap(),
+        base().deferment(Deferment::Unsubsidized { periods: 12 }).build().unwrap(),
+        base().skip_payments(SkipPayments::new(SkipTreatment::ExtendTerm).installment(5)).build().unwrap(),
+        base().payment_timing(PaymentTiming::Advance).build().unwrap(),
+        base().graduated_payment(7.5, 5).build().unwrap(),
+        base().escrow(Escrow::new(200_000.0, 2_400.0, 900.0)).build().unwrap(),
+        Amortization::new_with_backend::<crate::Decimal>(150_000.0, 5.5, 240, Some(start)).unwrap(),
+    ];
+
+    for loan in loans {
+        let report = loan.validate();
+        assert!(report.is_valid(), "{}", report);
+        assert_eq!(report.checked_payments, loan.schedule.len());
+    }
+}
+
+#[test]
+fn test_validate_reports_tampered_schedule() {
+    let mut loan = Amortization::new(10_000.0, 5.0, 12, None).unwrap();
+    loan.schedule[3].interest += 1.0;
+    loan.schedule[5].beginning_balance -= 10.0;
+
+    let report = loan.validate();
+    let invariants: Vec<_> = report.violations.iter().map(|v| (v.invariant, v.installment_number)).collect();
+
+    assert!(!report.is_valid());
+    assert!(invariants.contains(&(Invariant::InstallmentSplit, Some(4))));
+    assert!(invariants.contains(&(Invariant::BalanceChain, Some(6))));
+    assert!(invariants.contains(&(Invariant::BalanceArithmetic, Some(6))));
+}
+
+#[test]
+fn test_validate_reports_unpaid_balance() {
+    let mut loan = Amortization::new(10_000.0, 5.0, 12, None).unwrap();
+    loan.schedule.pop();
+
+    let report = loan.validate();
+    assert!(report.violations.iter().any(|v| v.invariant == Invariant::FullyRepaid));
+    assert!(report.violations.iter().any(|v| v.invariant == Invariant::PrincipalRepaid));
+    assert!(report.to_string().contains("FullyRepaid violated"));
+}
+
+#[test]
+fn test_config_validation_reports_every_field() {
+    let config = CalculatorConfig {
+        extra_payment: -50.0,
+        balloon_period: Some(400),
+        escrow: Some(Escrow::new(300_000.0, -1.0, 1_200.0)),
+        date_roll: DateRollConvention::DayOfMonth(32),
+        ..CalculatorConfig::new(-100_000.0, 5.0, 360, None)
+    };
+    let issues = config.validate();
+    let fields: Vec<&str> = issues.iter().map(|issue| issue.field).collect();
+
+    assert_eq!(fields, ["balance", "extra_payment", "balloon_period", "escrow", "date_roll"]);
+    assert!(matches!(issues[0].error, AmortizationError::InvalidLoanAmount(_)));
+    assert_eq!(issues[1].to_string(), "extra_payment: Extra payment must not be negative, got -50");
+
+    let error = Amortization::from_config(config).unwrap_err();
+    assert!(matches!(error, AmortizationError::InvalidLoanAmount(_)));
+}
+
+#[test]
+fn test_config_validation_rejects_non_finite_rates() {
+    for apr in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let config = CalculatorConfig::new(100_000.0, apr, 360, None);
+        let issues = config.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "apr");
+        assert!(matches!(issues[0].error, AmortizationError::InvalidInterestRate(_)));
+
+        let reset = CalculatorConfig {
+            rate_schedule: vec![RatePeriod::new(1, 4.0), RatePeriod::new(61, apr)],
+            ..CalculatorConfig::new(100_000.0, 4.0, 360, None)
+        };
+        assert_eq!(reset.validate().iter().map(|issue| issue.field).collect::<Vec<_>>(), ["rate_schedule"]);
+
+        for config in [config, reset] {
+            assert!(matches!(Amortization::from_config(config.clone()), Err(AmortizationError::InvalidInterestRate(_))));
+            assert!(matches!(Amortization::from_config_with_backend::<Decimal>(config), Err(AmortizationError::InvalidInterestRate(_))));
+        }
+    }
+}
+
+#[test]
+fn test_config_validation_skips_term_checks_without_a_term() {
+    let config = CalculatorConfig {
+        balloon_period: Some(60),
+        rate_schedule: vec![RatePeriod::new(1, 4.0), RatePeriod::new(61, 6.0)],
+        ..CalculatorConfig::new(100_000.0, 4.0, 0, None)
+    };
+    let fields: Vec<&str> = config.validate().iter().map(|issue| issue.field).collect();
+    assert_eq!(fields, ["loan_term"]);
+
+    assert!(CalculatorConfig::new(100_000.0, 4.0, 360, None).validate().is_empty());
+}
@@ -0,0 +1,62 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, CalculatorConfig, DayCount, ValuationPoint};
+use super::assert_float_eq;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[test]
+fn test_note_rate_values_at_balance() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+
+    assert_float_eq(loan.present_value(6.0, 0).unwrap(), 200_000.0);
+    assert_float_eq(loan.present_value(6.0, 60).unwrap(), loan.balance_after(60).unwrap());
+    assert_float_eq(loan.present_value(6.0, 360).unwrap(), 0.0);
+}
+
+#[test]
+fn test_discount_rate_moves_value() {
+    let loan = Amortization::new(200_000.0, 6.0, 360, None).unwrap();
+    let premium = loan.present_value(5.0, ValuationPoint::Period(12)).unwrap();
+    let discount = loan.present_value(7.0, ValuationPoint::Period(12)).unwrap();
+    let par = loan.balance_after(12).unwrap();
+
+    assert!(premium > par);
+    assert!(discount < par);
+    assert_float_eq(loan.present_value(0.0, 12).unwrap(), loan.schedule[12..].iter().map(|p| p.installment_amount).sum());
+}
+
+#[test]
+fn test_value_at_payment_date() {
+    let loan = Amortization::new(100_000.0, 6.0, 120, Some(date(2025, 1, 15))).unwrap();
+    let due = loan.schedule[23].date.unwrap();
+
+    let by_date = loan.present_value(6.0, due).unwrap();
+    let by_period = loan.present_value(6.0, 24).unwrap();
+    // Actual days between due dates make whole periods slightly uneven
+    assert!((by_date - by_period).abs() / by_period < 0.001);
+}
+
+#[test]
+fn test_value_between_payments_accrues() {
+    let loan = Amortization::from_config(CalculatorConfig {
+        day_count: Some(DayCount::Thirty360),
+        ..CalculatorConfig::new(100_000.0, 6.0, 120, Some(date(2025, 1, 15)))
+    }).unwrap();
+    let due = loan.schedule[23].date.unwrap();
+    let at_due = loan.present_value(6.0, due).unwrap();
+    let mid_period = loan.present_value(6.0, due + chrono::Days::new(15)).unwrap();
+
+    assert_float_eq(at_due, loan.balance_after(24).unwrap());
+    assert_float_eq(mid_period, at_due * 1.005_f64.powf(0.5));
+}
+
+#[test]
+fn test_invalid_valuation_point() {
+    let loan = Amortization::new(100_000.0, 6.0, 120, None).unwrap();
+
+    assert!(matches!(loan.present_value(6.0, 121), Err(AmortizationError::PeriodOutOfRange(121))));
+    let result = loan.present_value(6.0, date(2026, 1, 1));
+    assert!(matches!(result, Err(AmortizationError::MissingParameter("start_date"))));
+}
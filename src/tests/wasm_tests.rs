@@ -0,0 +1,58 @@
+use crate::wasm::{JsAmortization, JsAmortizationBuilder};
+
+#[test]
+fn test_js_amortization_summary() {
+    let loan = JsAmortization::new(200_000.0, 6.0, 360).unwrap();
+
+    assert!((loan.periodic_payment() - 1199.10).abs() < 0.01);
+    assert_eq!(loan.periods(), 360);
+    assert!(loan.to_json().starts_with("{\"schema_version\":1"));
+}
+
+#[test]
+fn test_js_schedule_is_array_of_objects() {
+    let loan = JsAmortization::new(1_000.0, 5.0, 2).unwrap();
+    let schedule = loan.schedule_json();
+
+    assert!(schedule.starts_with("[{\"installment_number\":1,"));
+    assert_eq!(schedule.matches("installment_number").count(), 2);
+}
+
+#[test]
+fn test_js_schedule_rows() {
+    let mut builder = JsAmortizationBuilder::new();
+    builder.principal(1_000.0);
+    builder.apr(5.0);
+    builder.term_months(2);
+    builder.start_date("2024-01-31").unwrap();
+    let schedule = builder.build().unwrap().schedule();
+
+    assert_eq!(schedule.len(), 2);
+    assert_eq!(schedule[0].installment_number, 1);
+    assert_eq!(schedule[0].date.as_deref(), Some("2024-01-31"));
+    assert_eq!(schedule[1].remaining_balance, 0.0);
+    assert_eq!(JsAmortization::new(1_000.0, 5.0, 2).unwrap().schedule()[0].date, None);
+}
+
+#[test]
+fn test_js_builder() {
+    let mut builder = JsAmortizationBuilder::new();
+    builder.principal(10_000.0);
+    builder.apr(5.0);
+    builder.term_months(12);
+    builder.start_date("2024-01-31").unwrap();
+    builder.frequency("quarterly").unwrap();
+
+    let loan = builder.build().unwrap();
+    assert_eq!(loan.periods(), 4);
+    assert!(loan.schedule_json().contains("\"date\":\"2024-01-31\""));
+}
+
+#[test]
+fn test_js_errors_are_messages() {
+    let mut builder = JsAmortizationBuilder::new();
+    assert!(builder.start_date("31/01/2024").is_err());
+    assert_eq!(builder.frequency("hourly").unwrap_err(), "Unknown payment frequency: hourly");
+    assert_eq!(builder.build().unwrap_err(), "Missing required parameter: principal");
+    assert!(JsAmortization::new(-1.0, 5.0, 12).is_err());
+}
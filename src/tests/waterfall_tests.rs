@@ -0,0 +1,66 @@
+use chrono::NaiveDate;
+use crate::{Amortization, AmortizationError, CashFlowAssumptions, CashFlowProjection, PrepaymentSpeed, Tranche, Waterfall};
+use super::assert_float_eq;
+
+fn projection(assumptions: &CashFlowAssumptions) -> CashFlowProjection {
+    Amortization::new(1_000_000.0, 6.0, 360, NaiveDate::from_ymd_opt(2025, 1, 1)).unwrap()
+        .project(assumptions)
+        .unwrap()
+}
+
+fn waterfall() -> Waterfall {
+    Waterfall::new()
+        .with_tranche(Tranche::new("A", 800_000.0, 4.0))
+        .with_tranche(Tranche::new("B", 200_000.0, 5.0))
+}
+
+#[test]
+fn test_sequential_pay() {
+    let result = projection(&CashFlowAssumptions::new().with_prepayment(PrepaymentSpeed::Cpr(10.0))).waterfall(&waterfall()).unwrap();
+    let (a, b) = (result.tranche("A").unwrap(), result.tranche("B").unwrap());
+
+    assert_float_eq(a.rows[0].interest_paid, 800_000.0 * 0.04 / 12.0);
+    assert_float_eq(b.rows[0].interest_paid, 200_000.0 * 0.05 / 12.0);
+    assert_float_eq(result.residual[0], 5_000.0 - a.rows[0].interest_paid - b.rows[0].interest_paid);
+
+    // B receives no principal until A is retired
+    let a_final = a.final_period().unwrap();
+    assert!(b.rows[..a_final as usize - 1].iter().all(|row| row.principal == 0.0));
+    assert!(b.rows[a_final as usize].principal > 0.0);
+    assert_float_eq(a.total_principal, 800_000.0);
+    assert_float_eq(b.total_principal, 200_000.0);
+    assert_float_eq(result.excess_principal, 0.0);
+    assert!(a.weighted_average_life() < b.weighted_average_life());
+    assert!(b.weighted_average_life() < 30.0);
+}
+
+#[test]
+fn test_losses_write_down_junior_first() {
+    let assumptions = CashFlowAssumptions::new().with_defaults(2.0, 40.0);
+    let pool = projection(&assumptions);
+    let result = pool.waterfall(&waterfall()).unwrap();
+    let (a, b) = (result.tranche("A").unwrap(), result.tranche("B").unwrap());
+
+    assert_float_eq(a.total_writedowns, 0.0);
+    assert_float_eq(b.total_writedowns, pool.total_losses);
+    assert_float_eq(a.total_principal + b.total_principal + b.total_writedowns, 1_000_000.0);
+}
+
+#[test]
+fn test_interest_shortfall_carries_forward() {
+    let rich = Waterfall::new().with_tranche(Tranche::new("A", 1_000_000.0, 9.0));
+    let result = projection(&CashFlowAssumptions::new()).waterfall(&rich).unwrap();
+    let a = &result.tranches[0];
+
+    assert_float_eq(a.rows[0].interest_shortfall, 7_500.0 - 5_000.0);
+    assert_float_eq(a.rows[1].interest_due, a.rows[1].beginning_balance * 0.0075 + 2_500.0);
+    assert_float_eq(result.total_residual, 0.0);
+}
+
+#[test]
+fn test_invalid_tranches() {
+    let pool = projection(&CashFlowAssumptions::new());
+    assert!(matches!(pool.waterfall(&Waterfall::new()), Err(AmortizationError::MissingParameter("tranches"))));
+    let negative = Waterfall::new().with_tranche(Tranche::new("A", 100.0, -1.0));
+    assert!(matches!(pool.waterfall(&negative), Err(AmortizationError::InvalidTranche(message)) if message.contains("'A'")));
+}
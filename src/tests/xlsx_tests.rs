@@ -0,0 +1,64 @@
+use chrono::NaiveDate;
+use crate::{Amortization, Currency, Rate};
+
+fn workbook() -> Vec<u8> {
+    let loan = Amortization::new(10_000.0, 6.0, 12, NaiveDate::from_ymd_opt(2025, 1, 1)).unwrap();
+    let mut buffer = Vec::new();
+    loan.write_xlsx(&mut buffer).unwrap();
+    buffer
+}
+
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    haystack.windows(needle.len()).any(|window| window == needle.as_bytes())
+}
+
+#[test]
+fn test_xlsx_is_a_zip_with_workbook_parts() {
+    let bytes = workbook();
+
+    assert_eq!(&bytes[..4], b"PK\x03\x04");
+    assert!(contains(&bytes, "[Content_Types].xml"));
+    assert!(contains(&bytes, "xl/worksheets/sheet2.xml"));
+    assert!(contains(&bytes, r#"<sheet name="Summary""#));
+    let end = &bytes[bytes.len() - 22..];
+    assert_eq!(&end[..4], b"PK\x05\x06");
+    assert_eq!(u16::from_le_bytes([end[10], end[11]]), 7);
+}
+
+#[test]
+fn test_xlsx_typed_cells() {
+    let bytes = workbook();
+
+    // 2025-01-01 is serial day 45658
+    assert!(contains(&bytes, r#"<c r="B2" s="2"><v>45658</v></c>"#));
+    assert!(contains(&bytes, r#"<c r="C2" s="1"><v>10000</v></c>"#));
+    assert!(contains(&bytes, r##"formatCode="#,##0.00 &quot;USD&quot;""##));
+    assert!(contains(&bytes, r#"<c r="A13"><v>12</v></c>"#));
+}
+
+#[test]
+fn test_xlsx_amounts_follow_the_currency_minor_units() {
+    let loan = Amortization::builder()
+        .principal(1_000_000.0)
+        .rate(Rate::percent(3.0))
+        .term_months(36)
+        .currency(Currency::JPY)
+        .build()
+        .unwrap();
+    let mut bytes = Vec::new();
+    loan.write_xlsx(&mut bytes).unwrap();
+
+    assert!(contains(&bytes, r##"formatCode="#,##0 &quot;JPY&quot;""##));
+    assert!(contains(&bytes, "<v>29081</v>"));
+}
+
+#[test]
+fn test_to_xlsx_writes_file() {
+    let path = std::env::temp_dir().join("amortize_rs_schedule_test.xlsx");
+    let loan = Amortization::new(10_000.0, 6.0, 12, None).unwrap();
+    loan.to_xlsx(&path).unwrap();
+
+    let written = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(&written[..2], b"PK");
+}
@@ -0,0 +1,52 @@
+use crate::{Amortization, AmortizationError, Decimal, RatePeriod};
+use super::{assert_float_eq, FLOAT_PRECISION};
+
+#[test]
+fn test_zero_interest_equal_installments() {
+    let loan = Amortization::new(12_000.0, 0.0, 24, None).unwrap();
+
+    assert_eq!(loan.periodic_interest, 0.0);
+    assert_float_eq(loan.periodic_payment, 500.0);
+    assert_eq!(loan.schedule.len(), 24);
+    assert!(loan.schedule.iter().all(|p| p.interest == 0.0 && (p.principal - 500.0).abs() < FLOAT_PRECISION));
+    assert_float_eq(loan.total_interest, 0.0);
+    assert_float_eq(loan.total_payment, 12_000.0);
+}
+
+#[test]
+fn test_zero_interest_uneven_split_settles_in_final_payment() {
+    let loan = Amortization::new(1_000.0, 0.0, 3, None).unwrap();
+
+    assert_float_eq(loan.periodic_payment, 333.33);
+    assert_float_eq(loan.final_payment_amount, 333.34);
+    assert_float_eq(loan.total_interest, 0.0);
+    assert_float_eq(loan.schedule.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_zero_interest_decimal_backend() {
+    let loan = Amortization::new_with_backend::<Decimal>(12_000.0, 0.0, 12, None).unwrap();
+
+    assert_float_eq(loan.periodic_payment, 1_000.0);
+    assert_eq!(loan.total_interest, 0.0);
+}
+
+#[test]
+fn test_promotional_period_before_rate_reset() {
+    let loan = Amortization::new_adjustable(
+        10_000.0,
+        vec![RatePeriod::new(1, 0.0), RatePeriod::new(13, 9.0)],
+        36,
+        None,
+    ).unwrap();
+
+    assert!(loan.schedule[..12].iter().all(|p| p.interest == 0.0));
+    assert!(loan.schedule[12].interest > 0.0);
+    assert_float_eq(loan.schedule.last().unwrap().remaining_balance, 0.0);
+}
+
+#[test]
+fn test_negative_rate_still_rejected() {
+    let result = Amortization::new(10_000.0, -0.5, 12, None);
+    assert!(matches!(result, Err(AmortizationError::InvalidInterestRate(_))));
+}
@@ -0,0 +1,144 @@
+// A loan whose terms changed along the way (modification, recast, ARM reset) as one schedule:
+// each segment takes over from the previous one at a given installment, and the rows are
+// renumbered and dated as a single run
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::calendar::DateRollConvention;
+use crate::error::AmortizationError;
+use crate::frequency::PaymentFrequency;
+use crate::money::Currency;
+use crate::payment::{AccrualPeriod, Payment};
+
+#[derive(Debug, Clone)]
+pub struct TimelineSegment {
+    pub label: String,
+    // Installment of the combined schedule this segment's first row becomes
+    pub first_installment: u32,
+    pub loan: Amortization,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduleTimeline {
+    pub segments: Vec<TimelineSegment>,
+}
+
+impl TimelineSegment {
+    // Rows of this segment kept in the combined schedule
+    fn rows(&self, next: Option<&TimelineSegment>) -> &[Payment] {
+        let kept = next.map_or(self.loan.schedule.len(), |next| (next.first_installment - self.first_installment) as usize);
+        &self.loan.schedule[..kept.min(self.loan.schedule.len())]
+    }
+}
+
+impl ScheduleTimeline {
+    pub fn new(original: Amortization) -> Self {
+        ScheduleTimeline {
+            segments: vec![TimelineSegment { label: "original".to_string(), first_installment: 1, loan: original }],
+        }
+    }
+
+    // Replaces the schedule from `installment` on with `loan`, whose first row is that
+    // installment however the loan itself numbers its rows. The switch must fall within the
+    // current last segment, or right after it ends
+    pub fn then(mut self, label: &str, installment: u32, loan: Amortization) -> Result<Self, AmortizationError> {
+        let (first, end) = self.segments.last()
+            .map_or((0, 1), |last| (last.first_installment, last.first_installment + last.loan.schedule.len() as u32));
+        if installment <= first || installment > end {
+            return Err(AmortizationError::PeriodOutOfRange(installment));
+        }
+        self.segments.push(TimelineSegment { label: label.to_string(), first_installment: installment, loan });
+        Ok(self)
+    }
+
+    // Segment the combined installment `installment` belongs to
+    pub fn segment_at(&self, installment: u32) -> Option<&TimelineSegment> {
+        if installment == 0 || installment > self.len() {
+            return None;
+        }
+        self.segments.iter().rev().find(|segment| segment.first_installment <= installment)
+    }
+
+    pub fn len(&self) -> u32 {
+        self.segments.iter().enumerate()
+            .map(|(i, segment)| segment.rows(self.segments.get(i + 1)).len() as u32)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Change in balance at each switch that the payments don't explain, such as a lump sum
+    // paid before a recast, paired with the first installment of the new segment
+    pub fn balance_adjustments(&self) -> Vec<(u32, f64)> {
+        self.segments.windows(2)
+            .filter_map(|pair| {
+                let previous = pair[0].rows(Some(&pair[1])).last().map_or(pair[0].loan.balance, |p| p.remaining_balance);
+                let balance = pair[1].loan.schedule.first().map_or(pair[1].loan.balance, |p| p.beginning_balance);
+                let adjustment = pair[1].loan.config.currency.round(balance - previous);
+                (adjustment != 0.0).then_some((pair[1].first_installment, adjustment))
+            })
+            .collect()
+    }
+
+    // Every row numbered from 1. Dates continue from the first segment's calendar, so a
+    // segment built without a start date, or with its own numbering, still lines up; a change
+    // of frequency starts the new calendar from the last due date before it
+    pub fn schedule(&self) -> Vec<Payment> {
+        let mut anchor: Option<(NaiveDate, u32, PaymentFrequency, DateRollConvention)> = None;
+        let mut last_date = None;
+        let mut schedule = Vec::new();
+        for (i, segment) in self.segments.iter().enumerate() {
+            let config = &segment.loan.config;
+            for (offset, payment) in segment.rows(self.segments.get(i + 1)).iter().enumerate() {
+                let installment_number = segment.first_installment + offset as u32;
+                let date = match anchor {
+                    Some((start, first, frequency, roll)) if frequency == config.frequency => {
+                        frequency.due_date(start, (installment_number - first) as i32, roll)
+                    }
+                    _ => match last_date {
+                        Some(previous) => {
+                            anchor = Some((previous, installment_number - 1, config.frequency, config.date_roll));
+                            config.frequency.due_date(previous, 1, config.date_roll)
+                        }
+                        None => {
+                            if let Some(date) = payment.date {
+                                anchor = Some((date, installment_number, config.frequency, config.date_roll));
+                            }
+                            payment.date
+                        }
+                    },
+                };
+                // Rows moved onto the timeline's calendar accrue from the previous due date
+                let accrual = match (last_date, date) {
+                    (Some(start), Some(end)) if payment.date != date => Some(AccrualPeriod {
+                        start,
+                        end,
+                        days: config.day_count.map_or((end - start).num_days(), |day_count| day_count.days_between(start, end)),
+                    }),
+                    _ => payment.accrual,
+                };
+                last_date = date;
+                schedule.push(Payment { installment_number, date, accrual, ..payment.clone() });
+            }
+        }
+        schedule
+    }
+
+    pub fn total_payment(&self) -> f64 {
+        self.currency().round(self.schedule().iter().map(|p| p.installment_amount).sum())
+    }
+
+    pub fn total_interest(&self) -> f64 {
+        self.currency().round(self.schedule().iter().map(|p| p.interest).sum())
+    }
+
+    // Currency of the original loan, which the totals are rounded to
+    fn currency(&self) -> Currency {
+        self.segments.first().map_or_else(Currency::default, |segment| segment.loan.config.currency)
+    }
+
+    pub fn last_due_date(&self) -> Option<NaiveDate> {
+        self.schedule().last().and_then(|p| p.date)
+    }
+}
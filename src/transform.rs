@@ -0,0 +1,160 @@
+// Scenario changes applied to an existing loan one at a time. Each transform rewrites the
+// loan's terms and the schedule is regenerated from them, leaving the original untouched:
+// loan.apply(LumpSum::new(12, 5_000.0))?.apply(RateChange::new(61, 7.0))?
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::error::AmortizationError;
+use crate::events::ScheduleEvent;
+use crate::prepayment::{Prepayment, PrepaymentTreatment};
+use crate::rate::RatePeriod;
+use crate::skip::{SkipPayments, SkipTreatment};
+
+pub trait ScheduleTransform {
+    fn transform(&self, config: &CalculatorConfig) -> Result<CalculatorConfig, AmortizationError>;
+
+    // Recorded in the loan's event log
+    fn name(&self) -> &'static str {
+        "custom"
+    }
+
+    fn effective_period(&self) -> Option<u32> {
+        None
+    }
+}
+
+// Added to every installment on top of any extra payment already made
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExtraPayment {
+    pub amount: f64,
+}
+
+// New rate from `period` on, replacing any resets scheduled after it. The loan's rate caps
+// still apply to it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateChange {
+    pub period: u32,
+    pub apr: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkipPayment {
+    pub installment_number: u32,
+    pub treatment: SkipTreatment,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LumpSum {
+    pub period: u32,
+    pub amount: f64,
+    pub treatment: PrepaymentTreatment,
+}
+
+impl ExtraPayment {
+    pub fn new(amount: f64) -> Self {
+        ExtraPayment { amount }
+    }
+}
+
+impl RateChange {
+    pub fn new(period: u32, apr: f64) -> Self {
+        RateChange { period, apr }
+    }
+}
+
+impl SkipPayment {
+    pub fn new(installment_number: u32) -> Self {
+        SkipPayment { installment_number, treatment: SkipTreatment::default() }
+    }
+
+    pub fn with_treatment(mut self, treatment: SkipTreatment) -> Self {
+        self.treatment = treatment;
+        self
+    }
+}
+
+impl LumpSum {
+    pub fn new(period: u32, amount: f64) -> Self {
+        LumpSum { period, amount, treatment: PrepaymentTreatment::default() }
+    }
+
+    pub fn with_treatment(mut self, treatment: PrepaymentTreatment) -> Self {
+        self.treatment = treatment;
+        self
+    }
+}
+
+impl ScheduleTransform for ExtraPayment {
+    fn transform(&self, config: &CalculatorConfig) -> Result<CalculatorConfig, AmortizationError> {
+        // Checked here as well, since a negative amount could hide inside a larger existing one
+        if self.amount < 0.0 || !self.amount.is_finite() {
+            return Err(AmortizationError::InvalidExtraPayment(self.amount));
+        }
+        Ok(CalculatorConfig { extra_payment: config.extra_payment + self.amount, ..config.clone() })
+    }
+
+    fn name(&self) -> &'static str {
+        "extra_payment"
+    }
+}
+
+impl ScheduleTransform for RateChange {
+    fn transform(&self, config: &CalculatorConfig) -> Result<CalculatorConfig, AmortizationError> {
+        let mut rate_schedule: Vec<RatePeriod> = config.uncapped_rate_schedule()
+            .into_iter()
+            .filter(|r| r.start_period < self.period)
+            .collect();
+        rate_schedule.push(RatePeriod::new(self.period, self.apr));
+        Ok(CalculatorConfig { rate_schedule, ..config.clone() })
+    }
+
+    fn name(&self) -> &'static str {
+        "rate_change"
+    }
+
+    fn effective_period(&self) -> Option<u32> {
+        Some(self.period)
+    }
+}
+
+// Skips already on the loan keep their place; the treatment of the latest skip applies to all
+impl ScheduleTransform for SkipPayment {
+    fn transform(&self, config: &CalculatorConfig) -> Result<CalculatorConfig, AmortizationError> {
+        let existing = config.skip_payments.clone().unwrap_or_default();
+        let skip_payments = SkipPayments { treatment: self.treatment, ..existing }.installment(self.installment_number);
+        Ok(CalculatorConfig { skip_payments: Some(skip_payments), ..config.clone() })
+    }
+
+    fn name(&self) -> &'static str {
+        "skip_payment"
+    }
+
+    fn effective_period(&self) -> Option<u32> {
+        Some(self.installment_number)
+    }
+}
+
+impl ScheduleTransform for LumpSum {
+    fn transform(&self, config: &CalculatorConfig) -> Result<CalculatorConfig, AmortizationError> {
+        let mut prepayments = config.prepayments.clone();
+        prepayments.push(Prepayment::new(self.period, self.amount, self.treatment));
+        Ok(CalculatorConfig { prepayments, ..config.clone() })
+    }
+
+    fn name(&self) -> &'static str {
+        "lump_sum"
+    }
+
+    fn effective_period(&self) -> Option<u32> {
+        Some(self.period)
+    }
+}
+
+impl Amortization {
+    // The new schedule carries this loan's event log with the transform added to it
+    pub fn apply<T: ScheduleTransform>(&self, transform: T) -> Result<Amortization, AmortizationError> {
+        let mut loan = Amortization::from_config(transform.transform(&self.config)?)?;
+        let event = ScheduleEvent::record(transform.name(), transform.effective_period(), self, &loan);
+        loan.events = self.events.clone();
+        loan.events.push(event);
+        Ok(loan)
+    }
+}
@@ -0,0 +1,139 @@
+// Time value of money functions following spreadsheet conventions: money paid out is
+// negative, money received is positive, and `rate` is the rate per period
+use crate::cashflow;
+use crate::error::AmortizationError;
+use crate::num::AmortNum;
+
+const RATE_TOLERANCE: f64 = 1e-12;
+const MAX_ITERATIONS: u32 = 100;
+
+// The spreadsheet `type` argument: 0 for payments at the end of each period, 1 for the beginning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaymentTiming {
+    #[default]
+    Arrears,
+    Advance,
+}
+
+impl PaymentTiming {
+    fn factor<N: AmortNum>(self, rate: N) -> N {
+        match self {
+            PaymentTiming::Arrears => N::one(),
+            PaymentTiming::Advance => N::one() + rate,
+        }
+    }
+}
+
+fn growth<N: AmortNum>(rate: N, nper: u32) -> Result<N, AmortizationError> {
+    (N::one() + rate).checked_powi(nper).ok_or(AmortizationError::Overflow("compound growth"))
+}
+
+// `value` grown by `exp`, which a huge rate can take past the backend's range
+fn grown<N: AmortNum>(value: N, exp: N) -> Result<N, AmortizationError> {
+    value.checked_mul(exp).ok_or(AmortizationError::Overflow("compound growth"))
+}
+
+fn is_zero<N: AmortNum>(rate: N) -> bool {
+    rate.to_f64() == 0.0
+}
+
+fn neg<N: AmortNum>(value: N) -> N {
+    N::zero() - value
+}
+
+pub fn pmt<N: AmortNum>(rate: N, nper: u32, pv: N, fv: N, timing: PaymentTiming) -> Result<N, AmortizationError> {
+    if nper == 0 {
+        return Err(AmortizationError::InvalidPeriods(nper));
+    }
+    if is_zero(rate) {
+        return Ok(neg(pv + fv) / N::from_f64(nper as f64));
+    }
+    let exp = growth(rate, nper)?;
+    let owed = neg(fv + grown(pv, exp)?).checked_mul(rate).ok_or(AmortizationError::Overflow("periodic payment"))?;
+    Ok(owed / (timing.factor(rate) * (exp - N::one())))
+}
+
+pub fn fv<N: AmortNum>(rate: N, nper: u32, pmt: N, pv: N, timing: PaymentTiming) -> Result<N, AmortizationError> {
+    if is_zero(rate) {
+        return Ok(neg(pv + pmt * N::from_f64(nper as f64)));
+    }
+    let exp = growth(rate, nper)?;
+    Ok(neg(grown(pv, exp)? + pmt * timing.factor(rate) * (exp - N::one()) / rate))
+}
+
+pub fn pv<N: AmortNum>(rate: N, nper: u32, pmt: N, fv: N, timing: PaymentTiming) -> Result<N, AmortizationError> {
+    if is_zero(rate) {
+        return Ok(neg(fv + pmt * N::from_f64(nper as f64)));
+    }
+    let exp = growth(rate, nper)?;
+    Ok(neg(fv + pmt * timing.factor(rate) * (exp - N::one()) / rate) / exp)
+}
+
+// Interest portion of payment `per` (1-based)
+pub fn ipmt<N: AmortNum>(rate: N, per: u32, nper: u32, pv: N, fv: N, timing: PaymentTiming) -> Result<N, AmortizationError> {
+    if per == 0 || per > nper {
+        return Err(AmortizationError::PeriodOutOfRange(per));
+    }
+    let payment = pmt(rate, nper, pv, fv, timing)?;
+    let balance = self::fv(rate, per - 1, payment, pv, timing)?;
+    match timing {
+        PaymentTiming::Arrears => Ok(balance * rate),
+        // Nothing has accrued when the first payment falls on the start date
+        PaymentTiming::Advance if per == 1 => Ok(N::zero()),
+        PaymentTiming::Advance => Ok(balance * rate / (N::one() + rate)),
+    }
+}
+
+// Principal portion of payment `per` (1-based)
+pub fn ppmt<N: AmortNum>(rate: N, per: u32, nper: u32, pv: N, fv: N, timing: PaymentTiming) -> Result<N, AmortizationError> {
+    Ok(pmt(rate, nper, pv, fv, timing)? - ipmt(rate, per, nper, pv, fv, timing)?)
+}
+
+// Number of periods, possibly fractional
+pub fn nper(rate: f64, pmt: f64, pv: f64, fv: f64, timing: PaymentTiming) -> Result<f64, AmortizationError> {
+    if rate == 0.0 {
+        if pmt == 0.0 {
+            return Err(AmortizationError::InvalidPaymentAmount(pmt));
+        }
+        return Ok(-(pv + fv) / pmt);
+    }
+    let adjusted = pmt * timing.factor(rate);
+    let periods = ((adjusted - fv * rate) / (adjusted + pv * rate)).ln() / (1.0 + rate).ln();
+    if !periods.is_finite() || periods < 0.0 {
+        return Err(AmortizationError::NoSolution(format!(
+            "A payment of {:.2} never reaches the target value", pmt
+        )));
+    }
+    Ok(periods)
+}
+
+// Rate per period, found with Newton's method from a 10% starting guess like spreadsheets do.
+// When Newton's method wanders off, e.g. for rates far above the guess, the rate is bracketed
+// and bisected instead
+pub fn rate(nper: u32, pmt: f64, pv: f64, fv: f64, timing: PaymentTiming) -> Result<f64, AmortizationError> {
+    if nper == 0 {
+        return Err(AmortizationError::InvalidPeriods(nper));
+    }
+    let residual = |rate: f64| self::fv(rate, nper, pmt, pv, timing).map(|value| value - fv);
+
+    let mut rate = 0.1;
+    for _ in 0..MAX_ITERATIONS {
+        let Some(step) = newton_step(residual, rate) else { break };
+        let next = rate - step;
+        if (next - rate).abs() < RATE_TOLERANCE {
+            return Ok(next);
+        }
+        // Keep the iteration away from -100%, where growth is undefined
+        rate = if next <= -1.0 { (rate - 1.0) / 2.0 } else { next };
+    }
+    cashflow::solve_rate(|rate| residual(rate).unwrap_or(f64::NAN))
+        .map_err(|_| AmortizationError::NoSolution("Rate did not converge".to_string()))
+}
+
+fn newton_step(residual: impl Fn(f64) -> Result<f64, AmortizationError>, rate: f64) -> Option<f64> {
+    let value = residual(rate).ok()?;
+    let step = 1e-7 * rate.abs().max(1e-3);
+    let slope = (residual(rate + step).ok()? - residual(rate - step).ok()?) / (2.0 * step);
+    let step = value / slope;
+    (slope != 0.0 && step.is_finite()).then_some(step)
+}
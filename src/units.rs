@@ -0,0 +1,143 @@
+// Newtypes for the three loan terms that are otherwise all bare numbers, so a principal
+// passed where the rate belongs no longer compiles
+use std::cmp::Ordering;
+use std::fmt;
+use chrono::NaiveDate;
+use crate::calculator::{Amortization, CalculatorConfig};
+use crate::error::AmortizationError;
+use crate::frequency::PaymentFrequency;
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Principal(f64);
+
+impl Principal {
+    pub fn new(amount: f64) -> Self {
+        Principal(amount)
+    }
+
+    pub fn amount(&self) -> f64 {
+        self.0
+    }
+}
+
+impl From<f64> for Principal {
+    fn from(amount: f64) -> Self {
+        Principal(amount)
+    }
+}
+
+// Below this a percent rate is more likely a fraction passed by mistake (0.05 for 5%)
+const LIKELY_FRACTION_PERCENT: f64 = 1.0;
+
+// An annual rate, held in percent like the rest of the crate. There is no `From<f64>`, since
+// a bare number doesn't say whether it is 5.0 or 0.05
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Rate(f64);
+
+impl Rate {
+    // 5.0 for 5%
+    pub fn percent(percent: f64) -> Self {
+        Rate(percent)
+    }
+
+    // 0.05 for 5%
+    pub fn fraction(fraction: f64) -> Self {
+        Rate(fraction * 100.0)
+    }
+
+    pub fn as_percent(&self) -> f64 {
+        self.0
+    }
+
+    pub fn as_fraction(&self) -> f64 {
+        self.0 / 100.0
+    }
+
+    // A nonzero rate under 1% given as a percent; usually a fraction meant as a percent
+    pub fn is_likely_fraction(&self) -> bool {
+        self.0 > 0.0 && self.0 < LIKELY_FRACTION_PERCENT
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Term(u32);
+
+impl Term {
+    pub fn months(months: u32) -> Self {
+        Term(months)
+    }
+
+    pub fn years(years: u32) -> Self {
+        Term(years.saturating_mul(12))
+    }
+
+    pub fn years_months(years: u32, months: u32) -> Self {
+        Term(years.saturating_mul(12).saturating_add(months))
+    }
+
+    // `periods` installments at `frequency`, to the nearest month
+    pub fn of_periods(periods: u32, frequency: PaymentFrequency) -> Self {
+        let per_year = frequency.periods_per_year() as u64;
+        Term(((periods as u64 * 12 + per_year / 2) / per_year) as u32)
+    }
+
+    pub fn in_months(&self) -> u32 {
+        self.0
+    }
+
+    pub fn whole_years(&self) -> u32 {
+        self.0 / 12
+    }
+
+    // Months left over after the whole years
+    pub fn remaining_months(&self) -> u32 {
+        self.0 % 12
+    }
+
+    // How this term compares with `baseline`: "7 years, 3 months sooner", "2 months later"
+    pub fn change_from(&self, baseline: Term) -> String {
+        match self.0.cmp(&baseline.0) {
+            Ordering::Less => format!("{} sooner", Term(baseline.0 - self.0)),
+            Ordering::Greater => format!("{} later", Term(self.0 - baseline.0)),
+            Ordering::Equal => "same term".to_string(),
+        }
+    }
+}
+
+fn count(n: u32, unit: &str) -> String {
+    format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" })
+}
+
+// "30 years", "5 years, 6 months", "1 month"; a zero term is "0 months"
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.whole_years(), self.remaining_months()) {
+            (0, months) => write!(f, "{}", count(months, "month")),
+            (years, 0) => write!(f, "{}", count(years, "year")),
+            (years, months) => write!(f, "{}, {}", count(years, "year"), count(months, "month")),
+        }
+    }
+}
+
+impl From<u32> for Term {
+    fn from(months: u32) -> Self {
+        Term(months)
+    }
+}
+
+impl CalculatorConfig {
+    pub fn from_terms(principal: Principal, rate: Rate, term: Term, start_date: Option<NaiveDate>) -> Self {
+        CalculatorConfig::new(principal.amount(), rate.as_percent(), term.in_months(), start_date)
+    }
+}
+
+impl Amortization {
+    // Time from the first installment to payoff, which extra payments shorten
+    pub fn payoff_term(&self) -> Term {
+        Term::of_periods(self.schedule.len() as u32, self.config.frequency)
+    }
+
+    pub fn from_terms(principal: Principal, rate: Rate, term: Term, start_date: Option<NaiveDate>) -> Result<Self, AmortizationError> {
+        Self::from_config(CalculatorConfig::from_terms(principal, rate, term, start_date))
+    }
+}
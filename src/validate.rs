@@ -0,0 +1,118 @@
+use std::fmt;
+use crate::calculator::Amortization;
+use crate::error::AmortizationError;
+
+// Amounts are compared to the cent
+const TOLERANCE: f64 = 0.01;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Invariant {
+    // Principal repaid equals the original balance plus capitalized interest
+    PrincipalRepaid,
+    // Interest plus principal, less any capitalized or deferred amount, equals the installment
+    InstallmentSplit,
+    // Each beginning balance equals the previous remaining balance
+    BalanceChain,
+    // Remaining balance equals beginning balance less principal plus capitalized interest
+    BalanceArithmetic,
+    NonNegative,
+    FullyRepaid,
+    TotalPayment,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub invariant: Invariant,
+    pub installment_number: Option<u32>,
+    pub expected: f64,
+    pub actual: f64,
+}
+
+// A problem with one field of a `CalculatorConfig`, found before any schedule is built
+#[derive(Debug)]
+pub struct ValidationIssue {
+    pub field: &'static str,
+    pub error: AmortizationError,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ValidationReport {
+    pub checked_payments: usize,
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    fn check(&mut self, invariant: Invariant, installment_number: Option<u32>, expected: f64, actual: f64) {
+        if (expected - actual).abs() >= TOLERANCE || !actual.is_finite() {
+            self.violations.push(Violation { invariant, installment_number, expected, actual });
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.installment_number {
+            Some(n) => write!(f, "{:?} violated at installment {}: expected {:.2}, got {:.2}", self.invariant, n, self.expected, self.actual),
+            None => write!(f, "{:?} violated: expected {:.2}, got {:.2}", self.invariant, self.expected, self.actual),
+        }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Checked {} payments, {} violations", self.checked_payments, self.violations.len())?;
+        for violation in &self.violations {
+            writeln!(f, "{}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+impl Amortization {
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport {
+            checked_payments: self.schedule.len(),
+            violations: Vec::new(),
+        };
+
+        let mut previous_balance = self.balance;
+        for payment in &self.schedule {
+            let n = Some(payment.installment_number);
+            report.check(Invariant::BalanceChain, n, previous_balance, payment.beginning_balance);
+            report.check(
+                Invariant::BalanceArithmetic, n,
+                payment.beginning_balance - payment.principal + payment.capitalized_interest,
+                payment.remaining_balance,
+            );
+            // Deferred interest accrues over several rows but is only capitalized in the last one
+            if !self.is_deferred(payment.installment_number) {
+                report.check(
+                    Invariant::InstallmentSplit, n,
+                    payment.interest + payment.principal - payment.capitalized_interest - payment.deferred_amount + payment.fees,
+                    payment.installment_amount,
+                );
+            }
+            let amounts = [payment.beginning_balance, payment.remaining_balance, payment.interest, payment.principal, payment.installment_amount];
+            if let Some(&negative) = amounts.iter().find(|&&amount| amount <= -TOLERANCE) {
+                report.check(Invariant::NonNegative, n, 0.0, negative);
+            }
+            previous_balance = payment.remaining_balance;
+        }
+
+        let principal: f64 = self.schedule.iter().map(|p| p.principal).sum();
+        report.check(Invariant::PrincipalRepaid, None, self.balance + self.total_capitalized_interest(), principal);
+        report.check(Invariant::FullyRepaid, None, 0.0, previous_balance);
+        report.check(Invariant::TotalPayment, None, self.calculate_total_payment(), self.total_payment);
+        report
+    }
+}
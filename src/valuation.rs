@@ -0,0 +1,57 @@
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::day_count::DayCount;
+use crate::error::AmortizationError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValuationPoint {
+    // Right after installment n has been paid; 0 values the whole schedule
+    Period(u32),
+    // End of the day, so an installment falling on the date is excluded
+    Date(NaiveDate),
+}
+
+impl From<u32> for ValuationPoint {
+    fn from(period: u32) -> Self {
+        ValuationPoint::Period(period)
+    }
+}
+
+impl From<NaiveDate> for ValuationPoint {
+    fn from(date: NaiveDate) -> Self {
+        ValuationPoint::Date(date)
+    }
+}
+
+impl Amortization {
+    // Value of the installments still to be paid at an annual discount rate in percent,
+    // compounded once per payment period. When valuing at a date, the time to each installment
+    // is measured with the loan's day count (actual/365 by default), so fractional periods
+    // are discounted too
+    pub fn present_value(&self, discount_rate: f64, as_of: impl Into<ValuationPoint>) -> Result<f64, AmortizationError> {
+        let periods_per_year = self.config.frequency.periods_per_year() as f64;
+        let rate = discount_rate / 100.0 / periods_per_year;
+        if rate <= -1.0 || !rate.is_finite() {
+            return Err(AmortizationError::InvalidInterestRate(discount_rate));
+        }
+
+        match as_of.into() {
+            ValuationPoint::Period(period) => {
+                if period as usize > self.schedule.len() {
+                    return Err(AmortizationError::PeriodOutOfRange(period));
+                }
+                Ok(self.schedule[period as usize..].iter()
+                    .map(|p| p.installment_amount / (1.0 + rate).powi((p.installment_number - period) as i32))
+                    .sum())
+            }
+            ValuationPoint::Date(date) => {
+                self.start_date.ok_or(AmortizationError::MissingParameter("start_date"))?;
+                let day_count = self.config.day_count.unwrap_or(DayCount::Actual365);
+                Ok(self.schedule.iter()
+                    .filter_map(|p| p.date.filter(|&due| due > date).map(|due| (due, p.installment_amount)))
+                    .map(|(due, amount)| amount / (1.0 + rate).powf(day_count.year_fraction(date, due) * periods_per_year))
+                    .sum())
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+// wasm-bindgen exports for browser builds. Inputs and outputs are plain numbers and strings,
+// the schedule is an array of payment objects, and errors are thrown as messages
+use chrono::NaiveDate;
+use wasm_bindgen::prelude::wasm_bindgen;
+use crate::builder::AmortizationBuilder;
+use crate::calculator::Amortization;
+use crate::frequency::PaymentFrequency;
+use crate::json::JsonValue;
+use crate::units::Rate;
+
+#[wasm_bindgen(js_name = Amortization)]
+#[derive(Debug, Clone)]
+pub struct JsAmortization {
+    inner: Amortization,
+}
+
+#[wasm_bindgen(js_name = AmortizationBuilder)]
+#[derive(Debug, Clone, Default)]
+pub struct JsAmortizationBuilder {
+    inner: AmortizationBuilder,
+}
+
+// One schedule row; `date` is an ISO date, undefined for undated schedules
+#[wasm_bindgen(js_name = Payment, getter_with_clone)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsPayment {
+    pub installment_number: u32,
+    pub date: Option<String>,
+    pub installment_amount: f64,
+    pub interest: f64,
+    pub principal: f64,
+    pub remaining_balance: f64,
+}
+
+fn parse_frequency(frequency: &str) -> Result<PaymentFrequency, String> {
+    let name = frequency.to_ascii_lowercase();
+    PaymentFrequency::from_name(&name).ok_or_else(|| format!("Unknown payment frequency: {}", name))
+}
+
+#[wasm_bindgen(js_class = AmortizationBuilder)]
+impl JsAmortizationBuilder {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Setters take `&mut self` since JS keeps ownership of the builder object
+    pub fn principal(&mut self, principal: f64) {
+        self.inner = self.inner.clone().principal(principal);
+    }
+
+    pub fn apr(&mut self, apr: f64) {
+        self.inner = self.inner.clone().rate(Rate::percent(apr));
+    }
+
+    pub fn term_months(&mut self, term_months: u32) {
+        self.inner = self.inner.clone().term_months(term_months);
+    }
+
+    pub fn extra_payment(&mut self, extra_payment: f64) {
+        self.inner = self.inner.clone().extra_payment(extra_payment);
+    }
+
+    // Expects an ISO date such as "2024-01-31"
+    pub fn start_date(&mut self, start_date: &str) -> Result<(), String> {
+        let date = NaiveDate::parse_from_str(start_date, "%Y-%m-%d").map_err(|e| e.to_string())?;
+        self.inner = self.inner.clone().start_date(date);
+        Ok(())
+    }
+
+    pub fn frequency(&mut self, frequency: &str) -> Result<(), String> {
+        self.inner = self.inner.clone().frequency(parse_frequency(frequency)?);
+        Ok(())
+    }
+
+    pub fn build(&self) -> Result<JsAmortization, String> {
+        self.inner.build().map(|inner| JsAmortization { inner }).map_err(|e| e.to_string())
+    }
+}
+
+#[wasm_bindgen(js_class = Amortization)]
+impl JsAmortization {
+    #[wasm_bindgen(constructor)]
+    pub fn new(principal: f64, apr: f64, term_months: u32) -> Result<JsAmortization, String> {
+        Amortization::new(principal, apr, term_months, None)
+            .map(|inner| JsAmortization { inner })
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn periodic_payment(&self) -> f64 {
+        self.inner.periodic_payment
+    }
+
+    pub fn total_payment(&self) -> f64 {
+        self.inner.total_payment
+    }
+
+    pub fn total_interest(&self) -> f64 {
+        self.inner.total_interest
+    }
+
+    pub fn periods(&self) -> usize {
+        self.inner.schedule.len()
+    }
+
+    pub fn schedule(&self) -> Vec<JsPayment> {
+        self.inner.schedule.iter().map(|payment| JsPayment {
+            installment_number: payment.installment_number,
+            date: payment.date.map(|date| date.to_string()),
+            installment_amount: payment.installment_amount,
+            interest: payment.interest,
+            principal: payment.principal,
+            remaining_balance: payment.remaining_balance,
+        }).collect()
+    }
+
+    // Every field of every row as a JSON array, ready for `JSON.parse`
+    pub fn schedule_json(&self) -> String {
+        self.inner.to_json_value().get("schedule").map_or_else(|| JsonValue::Array(Vec::new()).to_string(), |s| s.to_string())
+    }
+
+    pub fn to_json(&self) -> String {
+        self.inner.to_json()
+    }
+}
+
+impl JsAmortization {
+    pub fn inner(&self) -> &Amortization {
+        &self.inner
+    }
+}
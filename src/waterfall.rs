@@ -0,0 +1,201 @@
+// Sequential-pay tranching of projected pool cash flows. Each period the pool's interest pays
+// the tranches' coupons in order of seniority, any excess going to the residual holder, and
+// all principal collected retires the most senior tranche still outstanding. Losses write
+// down the most junior tranche first
+use chrono::NaiveDate;
+use crate::error::AmortizationError;
+use crate::projection::CashFlowProjection;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tranche {
+    pub name: String,
+    pub balance: f64,
+    // Annual coupon in percent, paid on the tranche's balance at the start of each period
+    pub coupon: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranchePeriod {
+    pub period: u32,
+    pub date: Option<NaiveDate>,
+    pub beginning_balance: f64,
+    // Coupon for the period plus any shortfall carried from earlier periods
+    pub interest_due: f64,
+    pub interest_paid: f64,
+    pub principal: f64,
+    pub writedown: f64,
+    // Interest due but unpaid, owed again next period
+    pub interest_shortfall: f64,
+    pub remaining_balance: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrancheCashFlows {
+    pub name: String,
+    pub original_balance: f64,
+    pub coupon: f64,
+    pub rows: Vec<TranchePeriod>,
+    pub total_interest: f64,
+    pub total_principal: f64,
+    pub total_writedowns: f64,
+    // Periods in a year, for converting row periods into years
+    pub periods_per_year: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaterfallResult {
+    // Senior first, as the tranches were given
+    pub tranches: Vec<TrancheCashFlows>,
+    // Interest left once every coupon is paid, per period
+    pub residual: Vec<f64>,
+    pub total_residual: f64,
+    // Pool principal collected after every tranche was retired
+    pub excess_principal: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Waterfall {
+    pub tranches: Vec<Tranche>,
+}
+
+impl Tranche {
+    pub fn new(name: &str, balance: f64, coupon: f64) -> Self {
+        Tranche { name: name.to_string(), balance, coupon }
+    }
+}
+
+impl TrancheCashFlows {
+    // Average time in years until the tranche's principal is repaid
+    pub fn weighted_average_life(&self) -> f64 {
+        if self.total_principal == 0.0 {
+            return 0.0;
+        }
+        self.rows.iter()
+            .map(|row| row.period as f64 / self.periods_per_year as f64 * row.principal)
+            .sum::<f64>() / self.total_principal
+    }
+
+    // Period in which the tranche's balance reaches zero, if it does
+    pub fn final_period(&self) -> Option<u32> {
+        self.rows.iter().find(|row| row.remaining_balance <= 0.005).map(|row| row.period)
+    }
+}
+
+impl WaterfallResult {
+    pub fn tranche(&self, name: &str) -> Option<&TrancheCashFlows> {
+        self.tranches.iter().find(|tranche| tranche.name == name)
+    }
+}
+
+impl Waterfall {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Tranches are paid in the order they are added, so add the senior tranche first
+    pub fn with_tranche(mut self, tranche: Tranche) -> Self {
+        self.tranches.push(tranche);
+        self
+    }
+
+    fn validate(&self) -> Result<(), AmortizationError> {
+        if self.tranches.is_empty() {
+            return Err(AmortizationError::MissingParameter("tranches"));
+        }
+        for tranche in &self.tranches {
+            if tranche.balance <= 0.0 || !tranche.balance.is_finite() {
+                return Err(AmortizationError::InvalidTranche(format!("'{}' must have a positive balance", tranche.name)));
+            }
+            if tranche.coupon < 0.0 || !tranche.coupon.is_finite() {
+                return Err(AmortizationError::InvalidTranche(format!("'{}' must have a coupon of zero or more", tranche.name)));
+            }
+        }
+        Ok(())
+    }
+
+    // Runs the projection's interest, principal (scheduled, prepaid and recovered) and losses
+    // through the tranches. Tranches may add up to more or less than the pool's balance; any
+    // shortfall of principal is left outstanding at the end
+    pub fn run(&self, projection: &CashFlowProjection) -> Result<WaterfallResult, AmortizationError> {
+        self.validate()?;
+        let periods_per_year = projection.periods_per_year;
+        let mut balances: Vec<f64> = self.tranches.iter().map(|t| t.balance).collect();
+        let mut shortfalls = vec![0.0; self.tranches.len()];
+        let mut rows: Vec<Vec<TranchePeriod>> = vec![Vec::with_capacity(projection.rows.len()); self.tranches.len()];
+        let mut residual = Vec::with_capacity(projection.rows.len());
+        let mut excess_principal = 0.0;
+
+        for pool in &projection.rows {
+            let beginning: Vec<f64> = balances.clone();
+            let mut interest = pool.interest;
+            let mut principal = pool.scheduled_principal + pool.prepaid_principal + pool.recovery;
+            let mut paid = vec![(0.0, 0.0, 0.0); self.tranches.len()];
+
+            let due: Vec<f64> = self.tranches.iter().zip(&beginning).zip(&shortfalls)
+                .map(|((tranche, balance), shortfall)| balance * tranche.coupon / 100.0 / periods_per_year as f64 + shortfall)
+                .collect();
+            for (index, due) in due.iter().enumerate() {
+                let payment = due.min(interest);
+                interest -= payment;
+                paid[index].0 = payment;
+            }
+            residual.push(interest);
+
+            for (index, balance) in balances.iter_mut().enumerate() {
+                let payment = balance.min(principal);
+                principal -= payment;
+                *balance -= payment;
+                paid[index].1 = payment;
+            }
+            excess_principal += principal;
+
+            let mut loss = pool.loss;
+            for (index, balance) in balances.iter_mut().enumerate().rev() {
+                let writedown = balance.min(loss);
+                loss -= writedown;
+                *balance -= writedown;
+                paid[index].2 = writedown;
+            }
+
+            for (index, (interest_paid, principal, writedown)) in paid.into_iter().enumerate() {
+                shortfalls[index] = due[index] - interest_paid;
+                rows[index].push(TranchePeriod {
+                    period: pool.period,
+                    date: pool.date,
+                    beginning_balance: beginning[index],
+                    interest_due: due[index],
+                    interest_paid,
+                    principal,
+                    writedown,
+                    interest_shortfall: shortfalls[index],
+                    remaining_balance: balances[index],
+                });
+            }
+        }
+
+        let tranches = self.tranches.iter().zip(rows)
+            .map(|(tranche, rows)| TrancheCashFlows {
+                name: tranche.name.clone(),
+                original_balance: tranche.balance,
+                coupon: tranche.coupon,
+                total_interest: rows.iter().map(|r| r.interest_paid).sum(),
+                total_principal: rows.iter().map(|r| r.principal).sum(),
+                total_writedowns: rows.iter().map(|r| r.writedown).sum(),
+                periods_per_year,
+                rows,
+            })
+            .collect();
+        Ok(WaterfallResult {
+            tranches,
+            total_residual: residual.iter().sum(),
+            residual,
+            excess_principal,
+        })
+    }
+}
+
+impl CashFlowProjection {
+    pub fn waterfall(&self, waterfall: &Waterfall) -> Result<WaterfallResult, AmortizationError> {
+        waterfall.run(self)
+    }
+}
@@ -0,0 +1,226 @@
+// Minimal XLSX writer: an uncompressed ZIP holding a summary sheet and a schedule sheet with
+// typed numeric, currency and date cells
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use chrono::NaiveDate;
+use crate::calculator::Amortization;
+use crate::money::Currency;
+
+// Cell formats, indexes into `cellXfs` in the stylesheet
+const STYLE_CURRENCY: u32 = 1;
+const STYLE_DATE: u32 = 2;
+const STYLE_HEADER: u32 = 3;
+const STYLE_PERCENT: u32 = 4;
+
+const SCHEDULE_HEADER: [&str; 7] = ["Installment", "Date", "Beginning Balance", "Payment", "Interest", "Principal", "Remaining Balance"];
+
+enum Cell {
+    Text(String),
+    Number(f64, Option<u32>),
+    Date(NaiveDate),
+    Empty,
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// Spreadsheet serial day number, counted from 1899-12-30
+fn date_serial(date: NaiveDate) -> i64 {
+    let epoch = NaiveDate::from_ymd_opt(1899, 12, 30).expect("valid epoch");
+    (date - epoch).num_days()
+}
+
+fn column_name(mut index: usize) -> String {
+    let mut name = String::new();
+    loop {
+        name.insert(0, (b'A' + (index % 26) as u8) as char);
+        if index < 26 {
+            return name;
+        }
+        index = index / 26 - 1;
+    }
+}
+
+fn sheet_xml(rows: &[Vec<Cell>]) -> String {
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#);
+    for (r, row) in rows.iter().enumerate() {
+        xml.push_str(&format!(r#"<row r="{}">"#, r + 1));
+        for (c, cell) in row.iter().enumerate() {
+            let reference = format!("{}{}", column_name(c), r + 1);
+            match cell {
+                Cell::Text(text) => xml.push_str(&format!(r#"<c r="{}" t="inlineStr"{}><is><t>{}</t></is></c>"#,
+                    reference, if r == 0 { format!(r#" s="{}""#, STYLE_HEADER) } else { String::new() }, escape(text))),
+                Cell::Number(value, Some(style)) => xml.push_str(&format!(r#"<c r="{}" s="{}"><v>{}</v></c>"#, reference, style, value)),
+                Cell::Number(value, None) => xml.push_str(&format!(r#"<c r="{}"><v>{}</v></c>"#, reference, value)),
+                Cell::Date(date) => xml.push_str(&format!(r#"<c r="{}" s="{}"><v>{}</v></c>"#, reference, STYLE_DATE, date_serial(*date))),
+                Cell::Empty => {}
+            }
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+// Amounts show as many decimals as the currency has minor units, none for yen
+fn styles_xml(currency: Currency) -> String {
+    let decimals = match currency.minor_units() {
+        0 => String::new(),
+        units => format!(".{}", "0".repeat(units as usize)),
+    };
+    format!(r##"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+<numFmts count="3"><numFmt numFmtId="164" formatCode="#,##0{} &quot;{}&quot;"/><numFmt numFmtId="165" formatCode="yyyy-mm-dd"/><numFmt numFmtId="166" formatCode="0.000%"/></numFmts>
+<fonts count="2"><font><sz val="11"/><name val="Calibri"/></font><font><b/><sz val="11"/><name val="Calibri"/></font></fonts>
+<fills count="2"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill></fills>
+<borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders>
+<cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs>
+<cellXfs count="5"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/><xf numFmtId="164" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/><xf numFmtId="165" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/><xf numFmtId="0" fontId="1" fillId="0" borderId="0" xfId="0" applyFont="1"/><xf numFmtId="166" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/></cellXfs>
+</styleSheet>"##, decimals, escape(currency.code()))
+}
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/><Override PartName="/xl/worksheets/sheet2.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/><Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/></Types>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#;
+
+const WORKBOOK: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Summary" sheetId="1" r:id="rId1"/><sheet name="Schedule" sheetId="2" r:id="rId2"/></sheets></workbook>"#;
+
+const WORKBOOK_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet2.xml"/><Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/></Relationships>"#;
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+// Writes the entries as a ZIP archive using the "stored" (uncompressed) method
+fn write_zip<W: Write>(writer: &mut W, entries: &[(&str, Vec<u8>)]) -> io::Result<()> {
+    // 1980-01-01 00:00, the earliest DOS timestamp
+    const DOS_DATE: u16 = 0x21;
+    let mut offset = 0u32;
+    let mut central_directory = Vec::new();
+
+    for (name, data) in entries {
+        let crc = crc32(data);
+        let size = data.len() as u32;
+        let mut header = Vec::new();
+        header.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        header.extend_from_slice(&20u16.to_le_bytes());
+        header.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        header.extend_from_slice(&DOS_DATE.to_le_bytes());
+        header.extend_from_slice(&crc.to_le_bytes());
+        header.extend_from_slice(&size.to_le_bytes());
+        header.extend_from_slice(&size.to_le_bytes());
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(name.as_bytes());
+        writer.write_all(&header)?;
+        writer.write_all(data)?;
+
+        central_directory.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        central_directory.extend_from_slice(&20u16.to_le_bytes());
+        central_directory.extend_from_slice(&20u16.to_le_bytes());
+        central_directory.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        central_directory.extend_from_slice(&DOS_DATE.to_le_bytes());
+        central_directory.extend_from_slice(&crc.to_le_bytes());
+        central_directory.extend_from_slice(&size.to_le_bytes());
+        central_directory.extend_from_slice(&size.to_le_bytes());
+        central_directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        central_directory.extend_from_slice(&[0; 12]);
+        central_directory.extend_from_slice(&offset.to_le_bytes());
+        central_directory.extend_from_slice(name.as_bytes());
+
+        offset += header.len() as u32 + size;
+    }
+
+    writer.write_all(&central_directory)?;
+    let count = entries.len() as u16;
+    let mut end = Vec::new();
+    end.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    end.extend_from_slice(&[0, 0, 0, 0]);
+    end.extend_from_slice(&count.to_le_bytes());
+    end.extend_from_slice(&count.to_le_bytes());
+    end.extend_from_slice(&(central_directory.len() as u32).to_le_bytes());
+    end.extend_from_slice(&offset.to_le_bytes());
+    end.extend_from_slice(&0u16.to_le_bytes());
+    writer.write_all(&end)
+}
+
+impl Amortization {
+    fn summary_rows(&self) -> Vec<Vec<Cell>> {
+        let date = |date: Option<NaiveDate>| date.map_or(Cell::Empty, Cell::Date);
+        let currency = |amount: f64| Cell::Number(self.config.currency.round(amount), Some(STYLE_CURRENCY));
+        let mut rows = vec![
+            vec![Cell::Text("Field".to_string()), Cell::Text("Value".to_string())],
+            vec![Cell::Text("Loan Amount".to_string()), currency(self.balance)],
+            vec![Cell::Text("APR".to_string()), Cell::Number(self.config.apr / 100.0, Some(STYLE_PERCENT))],
+            vec![Cell::Text("Periods".to_string()), Cell::Number(self.periods as f64, None)],
+            vec![Cell::Text("Periodic Payment".to_string()), currency(self.periodic_payment)],
+            vec![Cell::Text("Final Payment".to_string()), currency(self.final_payment_amount)],
+            vec![Cell::Text("Total Payment".to_string()), currency(self.total_payment)],
+            vec![Cell::Text("Total Interest".to_string()), currency(self.total_interest)],
+            vec![Cell::Text("Start Date".to_string()), date(self.start_date)],
+            vec![Cell::Text("End Date".to_string()), date(self.end_date)],
+        ];
+        if !self.config.fees.is_empty() {
+            rows.insert(8, vec![Cell::Text("Total Fees".to_string()), currency(self.total_fees())]);
+        }
+        rows
+    }
+
+    fn schedule_rows(&self) -> Vec<Vec<Cell>> {
+        let currency = |amount: f64| Cell::Number(self.config.currency.round(amount), Some(STYLE_CURRENCY));
+        let fees = !self.config.fees.is_empty();
+        let mut header: Vec<Cell> = SCHEDULE_HEADER.iter().map(|h| Cell::Text(h.to_string())).collect();
+        if fees {
+            header.push(Cell::Text("Fees".to_string()));
+        }
+        let mut rows = vec![header];
+        rows.extend(self.schedule.iter().map(|p| {
+            let mut row = vec![
+                Cell::Number(p.installment_number as f64, None),
+                p.date.map_or(Cell::Empty, Cell::Date),
+                currency(p.beginning_balance),
+                currency(p.installment_amount),
+                currency(p.interest),
+                currency(p.principal),
+                currency(p.remaining_balance),
+            ];
+            if fees {
+                row.push(currency(p.fees));
+            }
+            row
+        }));
+        rows
+    }
+
+    pub fn write_xlsx<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let entries = [
+            ("[Content_Types].xml", CONTENT_TYPES.as_bytes().to_vec()),
+            ("_rels/.rels", ROOT_RELS.as_bytes().to_vec()),
+            ("xl/workbook.xml", WORKBOOK.as_bytes().to_vec()),
+            ("xl/_rels/workbook.xml.rels", WORKBOOK_RELS.as_bytes().to_vec()),
+            ("xl/styles.xml", styles_xml(self.config.currency).into_bytes()),
+            ("xl/worksheets/sheet1.xml", sheet_xml(&self.summary_rows()).into_bytes()),
+            ("xl/worksheets/sheet2.xml", sheet_xml(&self.schedule_rows()).into_bytes()),
+        ];
+        write_zip(&mut writer, &entries)?;
+        writer.flush()
+    }
+
+    pub fn to_xlsx<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_xlsx(BufWriter::new(File::create(path)?))
+    }
+}